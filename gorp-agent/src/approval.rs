@@ -0,0 +1,129 @@
+// ABOUTME: Hook for approving tool calls before a backend runs them.
+// ABOUTME: The embedding app installs a ToolApprover on a handle; ACP and mux backends ask it first.
+
+use futures::future::BoxFuture;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+/// A tool call waiting to be approved
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolApprovalRequest {
+    /// Session the call belongs to
+    pub session_id: String,
+    /// Backend's id for this call
+    pub tool_call_id: String,
+    /// Tool name, or the backend's title for the call (e.g. "bash", "Run `rm -rf build/`")
+    pub name: String,
+    /// Broad kind of tool: "execute", "delete", "fetch", "edit", "move", "read",
+    /// "search", "think" or "other"
+    pub kind: String,
+    /// Input the tool would be called with
+    pub input: Value,
+}
+
+/// Decides whether a tool call may run
+pub trait ToolApprover: Send + Sync {
+    /// Resolve to true to let the call run, false to deny it
+    fn review(&self, request: ToolApprovalRequest) -> BoxFuture<'static, bool>;
+}
+
+/// Approver slot shared between an AgentHandle and its backend worker.
+///
+/// With nothing installed every call is allowed, which is how backends behave
+/// without approvals.
+#[derive(Clone, Default)]
+pub struct ApprovalHook {
+    approver: Arc<RwLock<Option<Arc<dyn ToolApprover>>>>,
+}
+
+impl ApprovalHook {
+    /// Install `approver` until the returned guard is dropped
+    pub fn install(&self, approver: Arc<dyn ToolApprover>) -> ApproverGuard {
+        self.set(Some(approver));
+        ApproverGuard { hook: self.clone() }
+    }
+
+    fn set(&self, approver: Option<Arc<dyn ToolApprover>>) {
+        *self.approver.write().unwrap_or_else(|e| e.into_inner()) = approver;
+    }
+
+    /// Whether an approver is currently installed
+    pub fn is_installed(&self) -> bool {
+        self.approver
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+
+    /// Ask the installed approver about a call; allowed when there is none
+    pub async fn review(&self, request: ToolApprovalRequest) -> bool {
+        let approver = self
+            .approver
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        match approver {
+            Some(approver) => {
+                let (name, id) = (request.name.clone(), request.tool_call_id.clone());
+                let approved = approver.review(request).await;
+                tracing::info!(tool = %name, tool_call_id = %id, approved, "Tool call reviewed");
+                approved
+            }
+            None => true,
+        }
+    }
+}
+
+/// Removes the installed approver when dropped
+pub struct ApproverGuard {
+    hook: ApprovalHook,
+}
+
+impl Drop for ApproverGuard {
+    fn drop(&mut self) {
+        self.hook.set(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(bool);
+
+    impl ToolApprover for Fixed {
+        fn review(&self, _request: ToolApprovalRequest) -> BoxFuture<'static, bool> {
+            let answer = self.0;
+            Box::pin(async move { answer })
+        }
+    }
+
+    fn request() -> ToolApprovalRequest {
+        ToolApprovalRequest {
+            session_id: "s1".to_string(),
+            tool_call_id: "t1".to_string(),
+            name: "bash".to_string(),
+            kind: "execute".to_string(),
+            input: serde_json::json!({ "command": "rm -rf build/" }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calls_allowed_without_approver() {
+        let hook = ApprovalHook::default();
+        assert!(!hook.is_installed());
+        assert!(hook.review(request()).await);
+    }
+
+    #[tokio::test]
+    async fn test_installed_approver_decides_until_guard_drops() {
+        let hook = ApprovalHook::default();
+        let guard = hook.clone().install(Arc::new(Fixed(false)));
+        assert!(hook.is_installed());
+        assert!(!hook.review(request()).await);
+
+        drop(guard);
+        assert!(!hook.is_installed());
+        assert!(hook.review(request()).await);
+    }
+}
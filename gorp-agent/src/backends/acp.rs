@@ -1,6 +1,7 @@
 // ABOUTME: ACP protocol backend - communicates with claude-code-acp or codex-acp.
 // ABOUTME: Keeps ACP process alive across prompts for session persistence.
 
+use crate::approval::{ApprovalHook, ToolApprovalRequest};
 use crate::event::{AgentEvent, ErrorCode};
 use crate::handle::{AgentHandle, Command};
 use acp::Agent as _;
@@ -28,6 +29,9 @@ pub struct AcpConfig {
     /// Extra CLI arguments to pass to the ACP binary
     #[serde(default)]
     pub extra_args: Vec<String>,
+    /// Extra environment variables for the agent process, on top of gorp's own
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 fn default_timeout() -> u64 {
@@ -61,17 +65,21 @@ struct AcpClientHandler {
     working_dir: PathBuf,
     /// Buffer for accumulating text to parse **status** patterns across chunks
     text_buffer: std::sync::Mutex<String>,
+    /// Consulted before answering the agent's permission requests
+    approval: ApprovalHook,
 }
 
 impl AcpClientHandler {
     fn new(
         event_tx: Arc<std::sync::RwLock<mpsc::Sender<AgentEvent>>>,
         working_dir: PathBuf,
+        approval: ApprovalHook,
     ) -> Self {
         Self {
             event_tx,
             working_dir,
             text_buffer: std::sync::Mutex::new(String::new()),
+            approval,
         }
     }
 
@@ -93,11 +101,8 @@ impl AcpClientHandler {
         let tx = self.event_tx.read().unwrap_or_else(|e| e.into_inner());
         if let Err(e) = tx.try_send(event) {
             match e {
-                mpsc::error::TrySendError::Full(dropped_event) => {
-                    tracing::warn!(
-                        event = ?dropped_event,
-                        "Event channel buffer full (2048), dropping event"
-                    );
+                mpsc::error::TrySendError::Full(_) => {
+                    tracing::warn!("Event channel buffer full (2048), dropping event");
                 }
                 mpsc::error::TrySendError::Closed(_) => {
                     tracing::debug!("Event channel closed, receiver dropped");
@@ -201,10 +206,32 @@ impl acp::Client for AcpClientHandler {
         &self,
         args: acp::RequestPermissionRequest,
     ) -> acp::Result<acp::RequestPermissionResponse> {
+        let fields = &args.tool_call.fields;
+        let request = ToolApprovalRequest {
+            session_id: args.session_id.to_string(),
+            tool_call_id: args.tool_call.tool_call_id.to_string(),
+            name: fields.title.clone().unwrap_or_else(|| "tool".to_string()),
+            kind: tool_kind(fields.kind.as_ref()).to_string(),
+            input: fields.raw_input.clone().unwrap_or(serde_json::json!({})),
+        };
+        if !self.approval.review(request).await {
+            // Prefer an explicit "reject once" so the agent can carry on without the tool
+            let reject_option = args
+                .options
+                .iter()
+                .find(|opt| matches!(opt.kind, acp::PermissionOptionKind::RejectOnce));
+            return Ok(acp::RequestPermissionResponse::new(match reject_option {
+                Some(option) => acp::RequestPermissionOutcome::Selected(
+                    acp::SelectedPermissionOutcome::new(option.option_id.clone()),
+                ),
+                None => acp::RequestPermissionOutcome::Cancelled,
+            }));
+        }
+
         tracing::debug!(
             session_id = %args.session_id,
             tool_call_id = %args.tool_call.tool_call_id,
-            "Auto-approving permission request"
+            "Approving permission request"
         );
 
         // Find an "allow once" option to approve
@@ -424,6 +451,21 @@ impl acp::Client for AcpClientHandler {
     }
 }
 
+/// The approval kind name for an ACP tool kind
+fn tool_kind(kind: Option<&acp::ToolKind>) -> &'static str {
+    match kind {
+        Some(acp::ToolKind::Execute) => "execute",
+        Some(acp::ToolKind::Delete) => "delete",
+        Some(acp::ToolKind::Fetch) => "fetch",
+        Some(acp::ToolKind::Edit) => "edit",
+        Some(acp::ToolKind::Move) => "move",
+        Some(acp::ToolKind::Read) => "read",
+        Some(acp::ToolKind::Search) => "search",
+        Some(acp::ToolKind::Think) => "think",
+        _ => "other",
+    }
+}
+
 /// Persistent ACP client that stays alive across prompts
 struct PersistentAcpClient {
     child: Child,
@@ -449,6 +491,7 @@ impl PersistentAcpClient {
         extra_args: &[String],
         initial_event_tx: mpsc::Sender<AgentEvent>,
         env_vars: &HashMap<String, String>,
+        approval: ApprovalHook,
     ) -> Result<Self> {
         if agent_binary.contains("..") || agent_binary.contains('\0') {
             anyhow::bail!("Invalid agent binary path");
@@ -480,6 +523,7 @@ impl PersistentAcpClient {
         let handler = Arc::new(AcpClientHandler::new(
             Arc::clone(&shared_event_tx),
             working_dir.to_path_buf(),
+            approval,
         ));
 
         // Clone handler for the connection (it implements Client)
@@ -686,7 +730,11 @@ impl acp::Client for HandlerWrapper {
 }
 
 /// Run the persistent ACP worker on a dedicated thread
-fn run_persistent_worker(config: AcpConfig, mut cmd_rx: mpsc::Receiver<WorkerCommand>) {
+fn run_persistent_worker(
+    config: AcpConfig,
+    mut cmd_rx: mpsc::Receiver<WorkerCommand>,
+    approval: ApprovalHook,
+) {
     // Create a new runtime for this thread
     let rt = match tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -703,7 +751,8 @@ fn run_persistent_worker(config: AcpConfig, mut cmd_rx: mpsc::Receiver<WorkerCom
         let local = tokio::task::LocalSet::new();
         local
             .run_until(async {
-                let env_vars: HashMap<String, String> = std::env::vars().collect();
+                let mut env_vars: HashMap<String, String> = std::env::vars().collect();
+                env_vars.extend(config.env.clone());
 
                 // Create a dummy channel for initial spawn - will be replaced on first prompt
                 let (dummy_tx, _dummy_rx) = mpsc::channel(1);
@@ -715,6 +764,7 @@ fn run_persistent_worker(config: AcpConfig, mut cmd_rx: mpsc::Receiver<WorkerCom
                     &config.extra_args,
                     dummy_tx,
                     &env_vars,
+                    approval,
                 )
                 .await
                 {
@@ -832,11 +882,13 @@ impl AcpBackend {
         let (worker_tx, worker_rx) = mpsc::channel::<WorkerCommand>(32);
         let name = "acp";
         let config = self.config;
+        let handle = AgentHandle::new(handle_tx, name);
 
         // Spawn the persistent worker on a dedicated thread
         let worker_config = config.clone();
+        let approval = handle.approval_hook();
         thread::spawn(move || {
-            run_persistent_worker(worker_config, worker_rx);
+            run_persistent_worker(worker_config, worker_rx, approval);
         });
 
         // Spawn the command router that translates Handle commands to Worker commands
@@ -926,6 +978,10 @@ impl AcpBackend {
                         }
                         let _ = reply.send(Ok(()));
                     }
+                    Command::ListModels { reply } => {
+                        // The ACP agent is the Claude CLI; no need to bother the worker
+                        let _ = reply.send(Ok(crate::model::default_models()));
+                    }
                 }
             }
 
@@ -933,7 +989,7 @@ impl AcpBackend {
             let _ = worker_tx_clone.send(WorkerCommand::Shutdown).await;
         });
 
-        AgentHandle::new(handle_tx, name)
+        handle
     }
 
     /// Factory function for the registry
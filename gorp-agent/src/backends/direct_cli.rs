@@ -6,11 +6,15 @@ use crate::handle::{AgentHandle, Command};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as ProcessCommand;
 use tokio::sync::mpsc;
 
+/// Lines of CLI stderr kept to explain a failed run
+const STDERR_TAIL_LINES: usize = 5;
+
 /// Configuration for the Direct CLI backend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DirectCliConfig {
@@ -20,8 +24,27 @@ pub struct DirectCliConfig {
     pub sdk_url: Option<String>,
     /// Working directory for the agent
     pub working_dir: PathBuf,
+    /// Extra environment variables for the spawned CLI
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Run in plan mode so the CLI can read the workspace but not change it
+    #[serde(default)]
+    pub read_only: bool,
+    /// Model passed to the CLI with --model; None uses the CLI's own default
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Let the CLI use its web tools; when false they are passed to --disallowedTools
+    #[serde(default = "default_network_access")]
+    pub network_access: bool,
+}
+
+fn default_network_access() -> bool {
+    true
 }
 
+/// The CLI's tools that reach the network
+const CLI_NETWORK_TOOLS: &str = "WebFetch,WebSearch";
+
 pub struct DirectCliBackend {
     config: DirectCliConfig,
 }
@@ -69,6 +92,9 @@ impl DirectCliBackend {
                         // TODO: Kill the running process
                         let _ = reply.send(Ok(()));
                     }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(crate::model::default_models()));
+                    }
                 }
             }
         });
@@ -86,20 +112,24 @@ impl DirectCliBackend {
     }
 }
 
-async fn run_prompt(
-    config: &DirectCliConfig,
-    session_id: &str,
-    text: &str,
-    event_tx: mpsc::Sender<AgentEvent>,
-    is_new_session: bool,
-) -> Result<()> {
+/// CLI arguments for a prompt, everything except the prompt text itself
+pub fn cli_args(config: &DirectCliConfig, session_id: &str, is_new_session: bool) -> Vec<String> {
     let mut args = vec![
         "--print".to_string(),
         "--output-format".to_string(),
         "stream-json".to_string(),
         "--verbose".to_string(),
-        "--dangerously-skip-permissions".to_string(),
     ];
+    if config.read_only {
+        args.push("--permission-mode".to_string());
+        args.push("plan".to_string());
+    } else {
+        args.push("--dangerously-skip-permissions".to_string());
+    }
+    // The `=` form keeps the variadic flag from swallowing the prompt
+    if !config.network_access {
+        args.push(format!("--disallowedTools={}", CLI_NETWORK_TOOLS));
+    }
 
     // Only use --resume for existing sessions, not new ones
     if !is_new_session {
@@ -107,18 +137,35 @@ async fn run_prompt(
         args.push(session_id.to_string());
     }
 
+    if let Some(ref model) = config.model {
+        args.push("--model".to_string());
+        args.push(model.clone());
+    }
+
     if let Some(ref url) = config.sdk_url {
         args.push("--sdk-url".to_string());
         args.push(url.clone());
     }
+    args
+}
 
-    args.push(text.to_string());
+async fn run_prompt(
+    config: &DirectCliConfig,
+    session_id: &str,
+    text: &str,
+    event_tx: mpsc::Sender<AgentEvent>,
+    is_new_session: bool,
+) -> Result<()> {
+    let mut args = cli_args(config, session_id, is_new_session);
 
-    tracing::debug!(?args, "Spawning Claude CLI");
+    // Logged before the prompt goes in: prompts stay out of the agent's logs
+    tracing::debug!(?args, prompt_len = text.len(), "Spawning Claude CLI");
+    args.push(text.to_string());
 
     let mut child = ProcessCommand::new(&config.binary)
         .args(&args)
         .current_dir(&config.working_dir)
+        .envs(&config.env)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .spawn()
@@ -129,14 +176,20 @@ async fn run_prompt(
 
     let stderr_tx = event_tx.clone();
 
-    // Spawn task to read stderr and detect errors - we'll join this later
+    // Spawn task to read stderr and detect errors - we'll join this later.
+    // The last few lines explain a failed run (e.g. an unknown --model).
     let stderr_handle = tokio::spawn(async move {
         let reader = BufReader::new(stderr);
         let mut lines = reader.lines();
+        let mut tail: Vec<String> = Vec::new();
 
         while let Ok(Some(line)) = lines.next_line().await {
             if !line.is_empty() {
                 tracing::warn!(stderr = %line, "Claude CLI stderr");
+                if tail.len() == STDERR_TAIL_LINES {
+                    tail.remove(0);
+                }
+                tail.push(line.clone());
 
                 // Check for orphaned session error
                 if line.contains("No conversation found with session ID") {
@@ -149,6 +202,7 @@ async fn run_prompt(
                 }
             }
         }
+        tail
     });
 
     let reader = BufReader::new(stdout);
@@ -173,21 +227,30 @@ async fn run_prompt(
     }
 
     let status = child.wait().await?;
+
+    // Wait for stderr reader to complete - ensures we don't leak the task
+    let stderr_tail = match stderr_handle.await {
+        Ok(tail) => tail,
+        Err(e) => {
+            tracing::warn!(error = %e, "stderr reader task failed to complete");
+            Vec::new()
+        }
+    };
+
     if !status.success() {
+        let mut message = format!("CLI exited with status: {:?}", status.code());
+        if !stderr_tail.is_empty() {
+            message.push_str(&format!(": {}", stderr_tail.join("\n")));
+        }
         let _ = event_tx
             .send(AgentEvent::Error {
                 code: ErrorCode::BackendError,
-                message: format!("CLI exited with status: {:?}", status.code()),
+                message,
                 recoverable: false,
             })
             .await;
     }
 
-    // Wait for stderr reader to complete - ensures we don't leak the task
-    if let Err(e) = stderr_handle.await {
-        tracing::warn!(error = %e, "stderr reader task failed to complete");
-    }
-
     Ok(())
 }
 
@@ -3,9 +3,11 @@
 
 use crate::event::{AgentEvent, ErrorCode};
 use crate::handle::{AgentHandle, Command};
+use crate::model::ModelInfo;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as ProcessCommand;
@@ -22,6 +24,17 @@ pub struct DirectCodexConfig {
     /// Sandbox mode: read-only, workspace-write, or danger-full-access
     #[serde(default = "default_sandbox")]
     pub sandbox_mode: String,
+    /// Extra environment variables for the spawned CLI
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Models the Codex CLI runs
+pub fn codex_models() -> Vec<ModelInfo> {
+    vec![
+        ModelInfo::new("gpt-5-codex", 400_000),
+        ModelInfo::new("gpt-5", 400_000),
+    ]
 }
 
 fn default_sandbox() -> String {
@@ -78,6 +91,9 @@ impl DirectCodexBackend {
                         // TODO: Kill the running process
                         let _ = reply.send(Ok(()));
                     }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(codex_models()));
+                    }
                 }
             }
         });
@@ -129,10 +145,12 @@ async fn run_prompt(
         ]);
     }
 
-    tracing::debug!(cmd = ?cmd, "Spawning Codex CLI");
+    // The command line ends with the prompt, so only its length is logged
+    tracing::debug!(session_id, prompt_len = text.len(), "Spawning Codex CLI");
 
     let mut child = cmd
         .current_dir(&config.working_dir)
+        .envs(&config.env)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .spawn()
@@ -43,6 +43,7 @@
 
 use crate::event::AgentEvent;
 use crate::handle::{AgentHandle, Command};
+use crate::model::ModelInfo;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
@@ -139,6 +140,9 @@ impl MockBackend {
                     Command::Cancel { reply, .. } => {
                         let _ = reply.send(Ok(()));
                     }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(mock_models()));
+                    }
                 }
             }
         });
@@ -155,6 +159,14 @@ impl MockBackend {
     }
 }
 
+/// The fixed model list every mock handle reports
+pub fn mock_models() -> Vec<ModelInfo> {
+    vec![
+        ModelInfo::new("mock-small", 32_000),
+        ModelInfo::new("mock-large", 1_000_000),
+    ]
+}
+
 impl Default for MockBackend {
     fn default() -> Self {
         Self::new()
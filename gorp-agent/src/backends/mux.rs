@@ -1,8 +1,10 @@
 // ABOUTME: Mux backend - uses mux-rs for native Rust agent execution.
 // ABOUTME: Provides streaming LLM responses with SQLite session persistence.
 
+use crate::approval::{ApprovalHook, ToolApprovalRequest};
 use crate::event::{AgentEvent, ErrorCode, Usage};
 use crate::handle::{AgentHandle, Command};
+use crate::model::ModelInfo;
 use anyhow::{Context, Result};
 use futures::StreamExt;
 use mux::mcp::{McpClient, McpServerConfig, McpTransport};
@@ -39,6 +41,13 @@ pub struct MuxConfig {
     /// MCP servers to connect to
     #[serde(default)]
     pub mcp_servers: Vec<MuxMcpServerConfig>,
+    /// Extra environment variables for MCP servers and the bash tool;
+    /// a server's own `env` entries win over these
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Register the web_fetch and web_search tools
+    #[serde(default = "default_network_access")]
+    pub network_access: bool,
 }
 
 /// Configuration for an MCP server
@@ -60,6 +69,22 @@ fn default_max_tokens() -> u32 {
     8192
 }
 
+fn default_network_access() -> bool {
+    true
+}
+
+/// Anthropic API models mux is known to run
+pub fn known_models() -> Vec<ModelInfo> {
+    vec![
+        ModelInfo::new("claude-opus-4-1-20250805", 200_000),
+        ModelInfo::new("claude-opus-4-20250514", 200_000),
+        ModelInfo::new("claude-sonnet-4-5-20250929", 200_000),
+        ModelInfo::new("claude-sonnet-4-20250514", 200_000),
+        ModelInfo::new("claude-haiku-4-5-20251001", 200_000),
+        ModelInfo::new("claude-3-5-haiku-20241022", 200_000),
+    ]
+}
+
 fn default_local_prompt_files() -> Vec<String> {
     vec![
         "claude.md".to_string(),
@@ -355,13 +380,15 @@ impl MuxBackend {
         let (tx, mut rx) = mpsc::channel::<Command>(32);
         let name = "mux";
         let config = self.config;
+        let handle = AgentHandle::new(tx, name);
+        let approval = handle.approval_hook();
 
         // Create the Anthropic client
         let client = match AnthropicClient::from_env() {
             Ok(c) => Arc::new(c),
             Err(e) => {
                 tracing::error!(error = %e, "Failed to create Anthropic client");
-                return AgentHandle::new(tx, name);
+                return handle;
             }
         };
 
@@ -371,7 +398,7 @@ impl MuxBackend {
             Ok(db) => Arc::new(db),
             Err(e) => {
                 tracing::error!(error = %e, "Failed to create session database");
-                return AgentHandle::new(tx, name);
+                return handle;
             }
         };
 
@@ -392,6 +419,11 @@ impl MuxBackend {
         let mut mcp_configs = config.mcp_servers.clone();
         let json_configs = read_mcp_json(&config.working_dir);
         mcp_configs.extend(json_configs);
+        for server in &mut mcp_configs {
+            let mut env = config.env.clone();
+            env.extend(std::mem::take(&mut server.env));
+            server.env = env;
+        }
 
         tracing::info!(
             config_servers = config.mcp_servers.len(),
@@ -422,6 +454,8 @@ impl MuxBackend {
         // Clone registry for command loop (tools will be registered here FIRST)
         let registry_for_loop = Arc::clone(&registry);
         let working_dir_for_tools = config.working_dir.clone();
+        let env_for_tools = config.env.clone();
+        let network_access = config.network_access;
         let additional_tools = self.additional_tools;
 
         tokio::spawn(async move {
@@ -443,7 +477,7 @@ impl MuxBackend {
                 .await;
             // 4. bash - Execute shell commands
             registry_for_loop
-                .register(WdBashTool::new(wd.clone()))
+                .register(WdBashTool::new(wd.clone()).with_env(env_for_tools))
                 .await;
             // 5. list_files - List directory contents
             registry_for_loop
@@ -453,15 +487,22 @@ impl MuxBackend {
             registry_for_loop
                 .register(WdSearchTool::new(wd.clone()))
                 .await;
-            // 7. web_fetch - Fetch URL content
-            registry_for_loop.register(WebFetchTool::new()).await;
-            // 8. web_search - Web search queries
-            registry_for_loop.register(WebSearchTool::new()).await;
+            if network_access {
+                // 7. web_fetch - Fetch URL content
+                registry_for_loop.register(WebFetchTool::new()).await;
+                // 8. web_search - Web search queries
+                registry_for_loop.register(WebSearchTool::new()).await;
 
-            tracing::info!(
-                working_dir = %wd.display(),
-                "Registered 8 built-in tools: read_file, write_file, edit, bash, list_files, search, web_fetch, web_search"
-            );
+                tracing::info!(
+                    working_dir = %wd.display(),
+                    "Registered 8 built-in tools: read_file, write_file, edit, bash, list_files, search, web_fetch, web_search"
+                );
+            } else {
+                tracing::info!(
+                    working_dir = %wd.display(),
+                    "Registered 6 built-in tools without network access: read_file, write_file, edit, bash, list_files, search"
+                );
+            }
 
             // Register additional custom tools (e.g., DISPATCH tools)
             let additional_count = additional_tools.len();
@@ -541,6 +582,7 @@ impl MuxBackend {
                         let session_db = Arc::clone(&session_db);
                         let registry = Arc::clone(&registry);
                         let config = config.clone();
+                        let approval = approval.clone();
 
                         tokio::spawn(async move {
                             if let Err(e) = run_prompt(
@@ -548,6 +590,7 @@ impl MuxBackend {
                                 &sessions,
                                 &session_db,
                                 &registry,
+                                &approval,
                                 &config,
                                 &session_id,
                                 &text,
@@ -562,11 +605,14 @@ impl MuxBackend {
                     Command::Cancel { reply, .. } => {
                         let _ = reply.send(Ok(()));
                     }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(known_models()));
+                    }
                 }
             }
         });
 
-        AgentHandle::new(tx, name)
+        handle
     }
 
     /// Factory function for the registry
@@ -658,12 +704,25 @@ async fn connect_mcp_server(
     Ok(client)
 }
 
+/// The approval kind name for one of the built-in tools; MCP tools are "other"
+fn tool_kind(tool_name: &str) -> &'static str {
+    match tool_name {
+        "bash" => "execute",
+        "web_fetch" | "web_search" => "fetch",
+        "write_file" | "edit" => "edit",
+        "read_file" | "list_files" => "read",
+        "search" => "search",
+        _ => "other",
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_prompt(
     client: &AnthropicClient,
     sessions: &Arc<RwLock<HashMap<String, MuxSession>>>,
     session_db: &Arc<SessionDb>,
     registry: &Registry,
+    approval: &ApprovalHook,
     config: &MuxConfig,
     session_id: &str,
     text: &str,
@@ -858,8 +917,21 @@ async fn run_prompt(
         for (tool_id, tool_name, tool_input) in tool_uses {
             let start_time = Instant::now();
 
-            // Look up and execute the tool
-            let (output, is_error) = if let Some(tool) = registry.get(&tool_name).await {
+            let request = ToolApprovalRequest {
+                session_id: session_id.to_string(),
+                tool_call_id: tool_id.clone(),
+                name: tool_name.clone(),
+                kind: tool_kind(&tool_name).to_string(),
+                input: tool_input.clone(),
+            };
+
+            // Look up and execute the tool, unless the approver turned it down
+            let (output, is_error) = if !approval.review(request).await {
+                (
+                    "Tool call denied: it was not approved in chat".to_string(),
+                    true,
+                )
+            } else if let Some(tool) = registry.get(&tool_name).await {
                 match tool.execute(tool_input.clone()).await {
                     Ok(result) => (result.content, result.is_error),
                     Err(e) => (format!("Tool execution error: {}", e), true),
@@ -4,6 +4,7 @@
 use async_trait::async_trait;
 use mux::tool::{Tool, ToolResult};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
@@ -420,11 +421,21 @@ impl Tool for WdEditTool {
 /// BashTool with working directory default.
 pub struct WdBashTool {
     working_dir: PathBuf,
+    env: HashMap<String, String>,
 }
 
 impl WdBashTool {
     pub fn new(working_dir: PathBuf) -> Self {
-        Self { working_dir }
+        Self {
+            working_dir,
+            env: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for every command this tool runs
+    pub fn with_env(mut self, env: HashMap<String, String>) -> Self {
+        self.env = env;
+        self
     }
 }
 
@@ -465,6 +476,7 @@ impl Tool for WdBashTool {
 
         let mut cmd = tokio::process::Command::new("bash");
         cmd.arg("-c").arg(&params.command);
+        cmd.envs(&self.env);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
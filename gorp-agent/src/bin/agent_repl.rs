@@ -48,6 +48,10 @@ fn create_backend(backend_type: &str) -> Result<AgentHandle, String> {
                 binary: std::env::var("CLAUDE_BINARY").unwrap_or_else(|_| "claude".to_string()),
                 sdk_url: std::env::var("CLAUDE_SDK_URL").ok(),
                 working_dir,
+                env: Default::default(),
+                read_only: false,
+                model: None,
+                network_access: true,
             };
             let backend = DirectCliBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
@@ -58,6 +62,7 @@ fn create_backend(backend_type: &str) -> Result<AgentHandle, String> {
                 timeout_secs: 300,
                 working_dir,
                 extra_args: vec![],
+                env: Default::default(),
             };
             let backend = AcpBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
@@ -73,6 +78,7 @@ fn create_backend(backend_type: &str) -> Result<AgentHandle, String> {
                     "-c".to_string(),
                     "sandbox_mode=\"danger-full-access\"".to_string(),
                 ],
+                env: Default::default(),
             };
             let backend = AcpBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
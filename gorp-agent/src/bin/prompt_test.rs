@@ -18,6 +18,10 @@ fn create_backend(backend_type: &str) -> Result<gorp_agent::AgentHandle, String>
                 binary: std::env::var("CLAUDE_BINARY").unwrap_or_else(|_| "claude".to_string()),
                 sdk_url: std::env::var("CLAUDE_SDK_URL").ok(),
                 working_dir,
+                env: Default::default(),
+                read_only: false,
+                model: None,
+                network_access: true,
             };
             let backend = DirectCliBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
@@ -27,6 +31,7 @@ fn create_backend(backend_type: &str) -> Result<gorp_agent::AgentHandle, String>
                 binary: std::env::var("CODEX_BINARY").unwrap_or_else(|_| "codex".to_string()),
                 working_dir,
                 sandbox_mode: "danger-full-access".to_string(),
+                env: Default::default(),
             };
             let backend = DirectCodexBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
@@ -37,6 +42,7 @@ fn create_backend(backend_type: &str) -> Result<gorp_agent::AgentHandle, String>
                 timeout_secs: 300,
                 working_dir,
                 extra_args: vec![],
+                env: Default::default(),
             };
             let backend = AcpBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
@@ -50,6 +56,7 @@ fn create_backend(backend_type: &str) -> Result<gorp_agent::AgentHandle, String>
                     "-c".to_string(),
                     "sandbox_mode=\"danger-full-access\"".to_string(),
                 ],
+                env: Default::default(),
             };
             let backend = AcpBackend::new(config).map_err(|e| e.to_string())?;
             Ok(backend.into_handle())
@@ -1,6 +1,8 @@
 // ABOUTME: AgentHandle provides Send+Sync wrapper around potentially !Send backends.
 // ABOUTME: Uses channels to communicate with backend worker thread.
 
+use crate::approval::{ApprovalHook, ApproverGuard, ToolApprover};
+use crate::model::ModelInfo;
 use crate::AgentEvent;
 use anyhow::Result;
 use tokio::sync::{mpsc, oneshot};
@@ -41,6 +43,9 @@ pub enum Command {
         session_id: String,
         reply: oneshot::Sender<Result<()>>,
     },
+    ListModels {
+        reply: oneshot::Sender<Result<Vec<ModelInfo>>>,
+    },
 }
 
 /// Send + Sync handle that gorp interacts with.
@@ -58,6 +63,8 @@ pub struct AgentHandle {
     /// are not tracked here (treated as Active implicitly).
     session_states:
         std::sync::Arc<std::sync::RwLock<std::collections::HashMap<String, SessionState>>>,
+    /// Shared with the backend worker, which consults it before running tools
+    approval: ApprovalHook,
 }
 
 impl AgentHandle {
@@ -69,9 +76,21 @@ impl AgentHandle {
             session_states: std::sync::Arc::new(std::sync::RwLock::new(
                 std::collections::HashMap::new(),
             )),
+            approval: ApprovalHook::default(),
         }
     }
 
+    /// The approval hook backends hand to their worker before it starts.
+    /// Backends that never consult it run every tool call unasked.
+    pub fn approval_hook(&self) -> ApprovalHook {
+        self.approval.clone()
+    }
+
+    /// Have `approver` review tool calls until the returned guard is dropped
+    pub fn set_tool_approver(&self, approver: std::sync::Arc<dyn ToolApprover>) -> ApproverGuard {
+        self.approval.install(approver)
+    }
+
     /// Get the backend name
     pub fn name(&self) -> &'static str {
         self.name
@@ -190,6 +209,18 @@ impl AgentHandle {
             .map_err(|_| anyhow::anyhow!("Backend worker dropped reply channel"))?
     }
 
+    /// List the models the backend can run
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(Command::ListModels { reply: reply_tx })
+            .await
+            .map_err(|_| anyhow::anyhow!("Backend worker closed"))?;
+        reply_rx
+            .await
+            .map_err(|_| anyhow::anyhow!("Backend worker dropped reply channel"))?
+    }
+
     /// Abandon a session that was created but will never be used.
     ///
     /// Call this if you create a session via `new_session()` but decide not to
@@ -1,9 +1,11 @@
 // ABOUTME: Pluggable agent backend abstraction for gorp.
 // ABOUTME: Provides trait-based backends (ACP, direct CLI, mock) with Send+Sync handles.
 
+pub mod approval;
 pub mod config;
 pub mod event;
 pub mod handle;
+pub mod model;
 pub mod registry;
 pub mod traits;
 
@@ -11,8 +13,10 @@ pub mod backends;
 pub mod testing;
 
 // Re-exports
+pub use approval::{ApprovalHook, ApproverGuard, ToolApprovalRequest, ToolApprover};
 pub use config::{BackendConfig, Config};
 pub use event::{AgentEvent, ErrorCode, Usage};
 pub use handle::{AgentHandle, EventReceiver, SessionState};
+pub use model::ModelInfo;
 pub use registry::{AgentRegistry, BackendFactory};
 pub use traits::AgentBackend;
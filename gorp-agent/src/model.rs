@@ -0,0 +1,56 @@
+// ABOUTME: Model descriptions that backends report through list_models.
+// ABOUTME: Includes the static list used by backends that can't ask their provider.
+
+use serde::{Deserialize, Serialize};
+
+/// A model a backend can run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Model ID as passed to the backend (e.g. "claude-sonnet-4-5-20250929")
+    pub id: String,
+    /// Context window size in tokens
+    pub context_window: u32,
+}
+
+impl ModelInfo {
+    pub fn new(id: &str, context_window: u32) -> Self {
+        Self {
+            id: id.to_string(),
+            context_window,
+        }
+    }
+
+    /// Context window for display, e.g. "200K" or "1M"
+    pub fn context_label(&self) -> String {
+        let tokens = self.context_window;
+        if tokens >= 1_000_000 && tokens % 1_000_000 == 0 {
+            format!("{}M", tokens / 1_000_000)
+        } else if tokens >= 1_000 {
+            format!("{}K", tokens / 1_000)
+        } else {
+            tokens.to_string()
+        }
+    }
+}
+
+/// Models the Claude CLI accepts with --model, for backends that don't report their own
+pub fn default_models() -> Vec<ModelInfo> {
+    vec![
+        ModelInfo::new("claude-opus-4-1", 200_000),
+        ModelInfo::new("claude-sonnet-4-5", 200_000),
+        ModelInfo::new("claude-haiku-4-5", 200_000),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_label() {
+        assert_eq!(ModelInfo::new("a", 200_000).context_label(), "200K");
+        assert_eq!(ModelInfo::new("a", 1_000_000).context_label(), "1M");
+        assert_eq!(ModelInfo::new("a", 1_500_000).context_label(), "1500K");
+        assert_eq!(ModelInfo::new("a", 512).context_label(), "512");
+    }
+}
@@ -5,13 +5,17 @@ use crate::handle::AgentHandle;
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Factory function that creates an AgentHandle from config
 pub type BackendFactory = Box<dyn Fn(&Value) -> Result<AgentHandle> + Send + Sync>;
 
 /// Registry for runtime backend selection
+///
+/// Cloning is cheap and shares the registered factories.
+#[derive(Clone)]
 pub struct AgentRegistry {
-    factories: HashMap<String, BackendFactory>,
+    factories: HashMap<String, Arc<dyn Fn(&Value) -> Result<AgentHandle> + Send + Sync>>,
 }
 
 impl AgentRegistry {
@@ -27,7 +31,7 @@ impl AgentRegistry {
     where
         F: Fn(&Value) -> Result<AgentHandle> + Send + Sync + 'static,
     {
-        self.factories.insert(name.to_string(), Box::new(factory));
+        self.factories.insert(name.to_string(), Arc::new(factory));
         self
     }
 
@@ -45,6 +49,11 @@ impl AgentRegistry {
         self.factories.keys().map(|s| s.as_str()).collect()
     }
 
+    /// Whether a backend is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
     /// Create a backend from a BackendConfig
     pub fn create_from_config(&self, config: &crate::config::BackendConfig) -> Result<AgentHandle> {
         let json_config = config.to_json_value();
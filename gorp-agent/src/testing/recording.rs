@@ -185,6 +185,9 @@ impl ReplayAgent {
                     Command::Cancel { reply, .. } => {
                         let _ = reply.send(Ok(()));
                     }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(crate::model::default_models()));
+                    }
                 }
             }
         });
@@ -1,6 +1,7 @@
 // ABOUTME: Core AgentBackend trait that all backends implement.
 // ABOUTME: Defines session management and prompt execution interface.
 
+use crate::model::{default_models, ModelInfo};
 use crate::AgentEvent;
 use anyhow::Result;
 use futures::future::BoxFuture;
@@ -32,4 +33,11 @@ pub trait AgentBackend {
 
     /// Cancel an in-progress prompt
     fn cancel<'a>(&'a self, session_id: &'a str) -> BoxFuture<'a, Result<()>>;
+
+    /// Models this backend can run
+    ///
+    /// Defaults to the static list of models the Claude CLI accepts.
+    fn list_models<'a>(&'a self) -> BoxFuture<'a, Result<Vec<ModelInfo>>> {
+        Box::pin(async { Ok(default_models()) })
+    }
 }
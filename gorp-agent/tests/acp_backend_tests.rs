@@ -37,6 +37,7 @@ mod acp_tests {
             timeout_secs: 300,
             working_dir: PathBuf::from("/tmp"),
             extra_args: vec![],
+            env: Default::default(),
         };
 
         let backend = AcpBackend::new(config).unwrap();
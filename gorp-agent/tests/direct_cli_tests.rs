@@ -1,4 +1,4 @@
-use gorp_agent::backends::direct_cli::DirectCliConfig;
+use gorp_agent::backends::direct_cli::{cli_args, DirectCliConfig};
 
 #[test]
 fn test_direct_cli_config_deserializes() {
@@ -32,3 +32,37 @@ fn test_direct_cli_config_minimal() {
     assert_eq!(config.binary, "/usr/local/bin/claude");
     assert_eq!(config.working_dir.to_str().unwrap(), "/home/user/project");
 }
+
+#[test]
+fn test_direct_cli_config_read_only() {
+    let json = serde_json::json!({
+        "binary": "claude",
+        "working_dir": "/tmp"
+    });
+    let config: DirectCliConfig = serde_json::from_value(json).unwrap();
+    assert!(!config.read_only);
+
+    let json = serde_json::json!({
+        "binary": "claude",
+        "working_dir": "/tmp",
+        "read_only": true
+    });
+    let config: DirectCliConfig = serde_json::from_value(json).unwrap();
+    assert!(config.read_only);
+}
+
+#[test]
+fn test_direct_cli_disallows_web_tools_without_network_access() {
+    let json = serde_json::json!({
+        "binary": "claude",
+        "working_dir": "/tmp"
+    });
+    let mut config: DirectCliConfig = serde_json::from_value(json).unwrap();
+    assert!(config.network_access);
+    let args = cli_args(&config, "s1", true);
+    assert!(!args.iter().any(|a| a.starts_with("--disallowedTools")));
+
+    config.network_access = false;
+    let args = cli_args(&config, "s1", false);
+    assert!(args.contains(&"--disallowedTools=WebFetch,WebSearch".to_string()));
+}
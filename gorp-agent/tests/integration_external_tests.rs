@@ -22,6 +22,10 @@ fn real_claude_config() -> DirectCliConfig {
         binary: std::env::var("CLAUDE_BINARY").unwrap_or_else(|_| "claude".to_string()),
         sdk_url: std::env::var("CLAUDE_SDK_URL").ok(),
         working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: Default::default(),
+        read_only: false,
+        model: None,
+        network_access: true,
     }
 }
 
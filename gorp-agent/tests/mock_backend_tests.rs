@@ -212,3 +212,12 @@ async fn test_mock_backend_name() {
     let handle = mock.into_handle();
     assert_eq!(handle.name(), "mock");
 }
+
+#[tokio::test]
+async fn test_mock_backend_lists_fixed_models() {
+    let handle = MockBackend::new().into_handle();
+    let models = handle.list_models().await.unwrap();
+    assert_eq!(models, gorp_agent::backends::mock::mock_models());
+    assert_eq!(models[0].id, "mock-small");
+    assert_eq!(models[0].context_window, 32_000);
+}
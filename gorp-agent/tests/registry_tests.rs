@@ -57,6 +57,23 @@ fn test_registry_custom_factory() {
     assert_eq!(handle.name(), "custom");
 }
 
+#[test]
+fn test_registry_clone_keeps_custom_factories() {
+    use gorp_agent::handle::AgentHandle;
+    use tokio::sync::mpsc;
+
+    let registry = AgentRegistry::new().register("custom", |_config| {
+        let (tx, _rx) = mpsc::channel(1);
+        Ok(AgentHandle::new(tx, "custom"))
+    });
+    let cloned = registry.clone();
+    assert_eq!(cloned.available(), vec!["custom"]);
+    assert_eq!(
+        cloned.create("custom", &json!({})).unwrap().name(),
+        "custom"
+    );
+}
+
 #[tokio::test]
 async fn test_mock_backend_via_registry_works() {
     use gorp_agent::AgentEvent;
@@ -50,3 +50,11 @@ async fn test_backend_returns_name() {
     let backend = TestBackend;
     assert_eq!(backend.name(), "test");
 }
+
+#[tokio::test]
+async fn test_backend_lists_default_models() {
+    let backend = TestBackend;
+    let models = backend.list_models().await.unwrap();
+    assert_eq!(models, gorp_agent::model::default_models());
+    assert!(models.iter().all(|m| m.context_window > 0));
+}
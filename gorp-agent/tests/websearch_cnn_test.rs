@@ -12,6 +12,10 @@ fn real_claude_config() -> DirectCliConfig {
         binary: std::env::var("CLAUDE_BINARY").unwrap_or_else(|_| "claude".to_string()),
         sdk_url: std::env::var("CLAUDE_SDK_URL").ok(),
         working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: Default::default(),
+        read_only: false,
+        model: None,
+        network_access: true,
     }
 }
 
@@ -160,6 +164,7 @@ async fn run_acp_websearch_test(binary_name: &str, display_name: &str) {
         timeout_secs: 300,
         working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         extra_args,
+        env: Default::default(),
     };
 
     let backend = AcpBackend::new(config).expect("Failed to create ACP backend");
@@ -0,0 +1,658 @@
+// ABOUTME: Catalog of chat commands with the metadata behind docs, exports and completions.
+// ABOUTME: Built-in commands are listed here; extra commands can be registered on top.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Where a command can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandContext {
+    /// Direct message with the bot
+    Dm,
+    /// A channel room
+    Room,
+}
+
+impl CommandContext {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandContext::Dm => "dm",
+            CommandContext::Room => "room",
+        }
+    }
+}
+
+/// Who may run a command. Admins can run everything users can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionTier {
+    /// Any allowed user
+    User,
+    /// Bot administrators only
+    Admin,
+}
+
+impl PermissionTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionTier::User => "user",
+            PermissionTier::Admin => "admin",
+        }
+    }
+}
+
+/// A positional argument
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArgSpec {
+    pub name: String,
+    pub required: bool,
+    pub description: String,
+}
+
+/// A trailing keyword that changes how a command behaves (e.g. `strict`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FlagSpec {
+    pub name: String,
+    pub description: String,
+}
+
+/// Everything known about one chat command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandSpec {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+    pub args: Vec<ArgSpec>,
+    pub flags: Vec<FlagSpec>,
+    pub contexts: Vec<CommandContext>,
+    pub permission: PermissionTier,
+    pub examples: Vec<String>,
+}
+
+impl CommandSpec {
+    /// A user command usable in both DMs and rooms; narrow it with the builder methods
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            description: description.to_string(),
+            args: Vec::new(),
+            flags: Vec::new(),
+            contexts: vec![CommandContext::Dm, CommandContext::Room],
+            permission: PermissionTier::User,
+            examples: Vec::new(),
+        }
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.aliases.push(alias.to_string());
+        self
+    }
+
+    pub fn arg(mut self, name: &str, required: bool, description: &str) -> Self {
+        self.args.push(ArgSpec {
+            name: name.to_string(),
+            required,
+            description: description.to_string(),
+        });
+        self
+    }
+
+    pub fn flag(mut self, name: &str, description: &str) -> Self {
+        self.flags.push(FlagSpec {
+            name: name.to_string(),
+            description: description.to_string(),
+        });
+        self
+    }
+
+    pub fn dm_only(mut self) -> Self {
+        self.contexts = vec![CommandContext::Dm];
+        self
+    }
+
+    pub fn room_only(mut self) -> Self {
+        self.contexts = vec![CommandContext::Room];
+        self
+    }
+
+    pub fn admin(mut self) -> Self {
+        self.permission = PermissionTier::Admin;
+        self
+    }
+
+    pub fn example(mut self, example: &str) -> Self {
+        self.examples.push(example.to_string());
+        self
+    }
+
+    /// One-line synopsis, e.g. `!length <mode> [strict]`
+    pub fn usage(&self) -> String {
+        let mut usage = format!("!{}", self.name);
+        for arg in &self.args {
+            if arg.required {
+                usage.push_str(&format!(" <{}>", arg.name));
+            } else {
+                usage.push_str(&format!(" [{}]", arg.name));
+            }
+        }
+        for flag in &self.flags {
+            usage.push_str(&format!(" [{}]", flag.name));
+        }
+        usage
+    }
+
+    fn answers_to(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias == name)
+    }
+}
+
+/// The set of commands the bot understands, in the order they are documented
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandCatalog {
+    commands: Vec<CommandSpec>,
+}
+
+impl CommandCatalog {
+    /// Every command the bot ships with
+    pub fn builtin() -> Self {
+        let mut catalog = Self::default();
+        for spec in builtin_commands() {
+            catalog
+                .register(spec)
+                .expect("built-in command names are unique");
+        }
+        catalog
+    }
+
+    /// Add a command; fails if its name or an alias is already taken
+    pub fn register(&mut self, spec: CommandSpec) -> Result<()> {
+        for name in std::iter::once(&spec.name).chain(&spec.aliases) {
+            if self.get(name).is_some() {
+                bail!("Command name '{}' is already registered", name);
+            }
+        }
+        self.commands.push(spec);
+        Ok(())
+    }
+
+    /// Look up a command by name or alias
+    pub fn get(&self, name: &str) -> Option<&CommandSpec> {
+        self.commands.iter().find(|spec| spec.answers_to(name))
+    }
+
+    /// Let `alias` stand for an existing command (e.g. from `[commands.aliases]`);
+    /// fails if the alias is already a command name or alias, or the command is unknown
+    pub fn add_alias(&mut self, alias: &str, command: &str) -> Result<()> {
+        let alias = alias.trim().trim_start_matches('!').to_lowercase();
+        let command = command.trim().trim_start_matches('!').to_lowercase();
+        if alias.is_empty() || alias.contains(char::is_whitespace) {
+            bail!("Invalid command alias '{}'", alias);
+        }
+        if let Some(taken) = self.get(&alias) {
+            bail!(
+                "Alias '{}' would shadow the built-in !{} command",
+                alias,
+                taken.name
+            );
+        }
+        let Some(spec) = self
+            .commands
+            .iter_mut()
+            .find(|spec| spec.answers_to(&command))
+        else {
+            bail!("Alias '{}' points at unknown command '{}'", alias, command);
+        };
+        spec.aliases.push(alias);
+        Ok(())
+    }
+
+    /// The command `name` stands for, or `name` itself if it isn't an alias
+    pub fn canonical_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.get(name).map_or(name, |spec| spec.name.as_str())
+    }
+
+    /// Every alias as (alias, command), in catalog order
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.commands.iter().flat_map(|spec| {
+            spec.aliases
+                .iter()
+                .map(move |alias| (alias.as_str(), spec.name.as_str()))
+        })
+    }
+
+    pub fn commands(&self) -> &[CommandSpec] {
+        &self.commands
+    }
+
+    /// Full metadata as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// A reference document covering every command
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from(
+            "# gorp Command Reference\n\nChat commands are sent as messages starting with `!`.\n",
+        );
+        for spec in &self.commands {
+            out.push_str(&format!(
+                "\n## `{}`\n\n{}\n\n",
+                spec.usage(),
+                spec.description
+            ));
+            let contexts: Vec<&str> = spec.contexts.iter().map(|c| c.as_str()).collect();
+            out.push_str(&format!("- Contexts: {}\n", contexts.join(", ")));
+            out.push_str(&format!("- Permission: {}\n", spec.permission.as_str()));
+            if !spec.aliases.is_empty() {
+                let aliases: Vec<String> =
+                    spec.aliases.iter().map(|a| format!("`!{}`", a)).collect();
+                out.push_str(&format!("- Aliases: {}\n", aliases.join(", ")));
+            }
+            if !spec.args.is_empty() {
+                out.push_str("\n**Arguments**\n\n");
+                for arg in &spec.args {
+                    let need = if arg.required { "required" } else { "optional" };
+                    out.push_str(&format!(
+                        "- `{}` ({}) - {}\n",
+                        arg.name, need, arg.description
+                    ));
+                }
+            }
+            if !spec.flags.is_empty() {
+                out.push_str("\n**Flags**\n\n");
+                for flag in &spec.flags {
+                    out.push_str(&format!("- `{}` - {}\n", flag.name, flag.description));
+                }
+            }
+            if !spec.examples.is_empty() {
+                out.push_str("\n**Examples**\n\n```\n");
+                for example in &spec.examples {
+                    out.push_str(example);
+                    out.push('\n');
+                }
+                out.push_str("```\n");
+            }
+        }
+        out
+    }
+}
+
+fn builtin_commands() -> Vec<CommandSpec> {
+    vec![
+        CommandSpec::new("help", "Show detailed help").example("!help"),
+        CommandSpec::new("setup", "Run the onboarding wizard again")
+            .dm_only()
+            .example("!setup"),
+        CommandSpec::new("create", "Create a new channel with its own workspace")
+            .alias("new")
+            .admin()
+            .arg("name", true, "Channel name")
+            .example("!create research"),
+        CommandSpec::new("clone", "Copy a channel to another platform")
+            .admin()
+            .arg("channel", true, "Channel to copy")
+            .flag(
+                "--to <platform>",
+                "Platform to create the copy on (required)",
+            )
+            .flag(
+                "--share",
+                "Share the original session instead of forking it",
+            )
+            .example("!clone research --to slack")
+            .example("!clone research --to slack --share"),
+        CommandSpec::new("join", "Get invited to an existing channel")
+            .dm_only()
+            .arg("name", true, "Channel name")
+            .example("!join research"),
+        CommandSpec::new("delete", "Remove a channel (keeps workspace files)")
+            .alias("rm")
+            .dm_only()
+            .admin()
+            .arg("name", true, "Channel name")
+            .example("!delete research"),
+        CommandSpec::new(
+            "reset",
+            "Reset a channel's agent session (reloads MCP tools)",
+        )
+        .arg(
+            "name",
+            false,
+            "Channel to reset from a DM; defaults to this room",
+        )
+        .example("!reset")
+        .example("!reset research"),
+        CommandSpec::new(
+            "clear",
+            "Start a fresh agent session, keeping the workspace files",
+        )
+        .example("!clear"),
+        CommandSpec::new("list", "Show all your channels")
+            .alias("ls")
+            .dm_only()
+            .example("!list"),
+        CommandSpec::new("cleanup", "Leave orphaned rooms")
+            .dm_only()
+            .admin()
+            .example("!cleanup"),
+        CommandSpec::new(
+            "restore-rooms",
+            "Restore channels from workspace directories",
+        )
+        .dm_only()
+        .admin()
+        .example("!restore-rooms"),
+        CommandSpec::new(
+            "status",
+            "Show channel info (session, directory, debug state)",
+        )
+        .room_only()
+        .example("!status"),
+        CommandSpec::new(
+            "health",
+            "Show whether the bot is running normally or in safe mode",
+        )
+        .example("!health"),
+        CommandSpec::new("safemode", "Show or leave safe mode")
+            .admin()
+            .arg("action", false, "off, then off confirm")
+            .example("!safemode")
+            .example("!safemode off confirm"),
+        CommandSpec::new(
+            "backend",
+            "View or change the backend for this channel, or in a DM the default backend",
+        )
+        .arg(
+            "action",
+            false,
+            "list, set <name> or reset in a room; a backend name in a DM",
+        )
+        .example("!backend list")
+        .example("!backend set mux")
+        .example("!backend mux"),
+        CommandSpec::new("model", "Show or change the agent model for this channel")
+            .room_only()
+            .arg("action", false, "show, set <name> or reset")
+            .example("!model show")
+            .example("!model set claude-opus-4-1")
+            .example("!model reset"),
+        CommandSpec::new("models", "List the models the channel's backend offers")
+            .room_only()
+            .example("!models"),
+        CommandSpec::new("repo", "Check out a git repo into this channel's workspace")
+            .room_only()
+            .admin()
+            .arg("action", false, "show, set <url> [options], sync or clear")
+            .example("!repo set git@github.com:acme/api.git --branch main --pull-on-start")
+            .example("!repo sync")
+            .example("!repo clear"),
+        CommandSpec::new("debug", "Toggle tool usage display")
+            .room_only()
+            .arg("state", false, "on or off")
+            .example("!debug on"),
+        CommandSpec::new(
+            "stream",
+            "Stream responses by editing a message as it is written",
+        )
+        .room_only()
+        .arg("state", false, "on or off")
+        .example("!stream on"),
+        CommandSpec::new(
+            "reactions",
+            "Mark your message with status reactions while working",
+        )
+        .room_only()
+        .arg("state", false, "on or off")
+        .example("!reactions on"),
+        CommandSpec::new("usage", "Show token usage and cost")
+            .room_only()
+            .example("!usage"),
+        CommandSpec::new("budget", "Show or set this channel's monthly budget")
+            .room_only()
+            .arg("action", false, "set <amount> or clear")
+            .example("!budget")
+            .example("!budget set 10")
+            .example("!budget clear"),
+        CommandSpec::new("template", "Save and reuse prompts")
+            .room_only()
+            .arg(
+                "action",
+                true,
+                "save <name> [prompt], list, run <name> [extra context] or delete <name>",
+            )
+            .example("!template save standup Summarize yesterday's commits for {{args}}")
+            .example("!template run standup the api repo")
+            .example("!template list"),
+        CommandSpec::new(
+            "approve",
+            "List or accept room invites waiting for approval",
+        )
+        .arg("room_id", false, "the invited room to join")
+        .admin()
+        .example("!approve")
+        .example("!approve !abc123:matrix.org"),
+        CommandSpec::new("approvals", "Ask in chat before the agent runs risky tools")
+            .room_only()
+            .arg("level", false, "on, off, high, medium or low")
+            .example("!approvals on")
+            .example("!approvals medium")
+            .example("!approvals off"),
+        CommandSpec::new("network", "Allow or block the agent's web tools")
+            .room_only()
+            .admin()
+            .arg("state", false, "on or off")
+            .example("!network off")
+            .example("!network on"),
+        CommandSpec::new(
+            "cache",
+            "Reuse answers to repeat questions until files change",
+        )
+        .room_only()
+        .arg("action", false, "on, off or clear")
+        .example("!cache on")
+        .example("!cache clear"),
+        CommandSpec::new("length", "Set how long answers should be")
+            .room_only()
+            .arg("mode", true, "brief, normal or detailed")
+            .flag("strict", "Also trim answers that run long")
+            .example("!length brief")
+            .example("!length brief strict"),
+        CommandSpec::new(
+            "attachments",
+            "Show or change this channel's attachment limit",
+        )
+        .room_only()
+        .arg("action", false, "limit <size> or reset")
+        .example("!attachments")
+        .example("!attachments limit 200MB"),
+        CommandSpec::new(
+            "transcription",
+            "Show or set the language voice notes are transcribed in",
+        )
+        .room_only()
+        .arg("action", false, "lang <code> or lang auto")
+        .example("!transcription lang es")
+        .example("!transcription lang auto"),
+        CommandSpec::new("locale", "Show or set the language the bot uses")
+            .arg("code", false, "Language code, or reset")
+            .example("!locale fr")
+            .example("!locale reset"),
+        CommandSpec::new(
+            "sendguard",
+            "Hold long messages as a draft until you !send them",
+        )
+        .arg("state", false, "on or off")
+        .example("!sendguard on"),
+        CommandSpec::new("send", "Submit your held draft").example("!send"),
+        CommandSpec::new("append", "Add text to your held draft")
+            .arg("text", true, "Text to add")
+            .example("!append and include the test results"),
+        CommandSpec::new("discard", "Drop your held draft").example("!discard"),
+        CommandSpec::new("pin", "Save the last response (or the replied-to message)")
+            .room_only()
+            .arg("note", false, "Note stored with the pin")
+            .example("!pin")
+            .example("!pin release checklist"),
+        CommandSpec::new("pins", "List pinned responses")
+            .room_only()
+            .example("!pins"),
+        CommandSpec::new(
+            "history",
+            "Show recent prompts and responses in this channel",
+        )
+        .arg(
+            "n",
+            false,
+            "Number of exchanges to show (default 5), or export [n]",
+        )
+        .example("!history")
+        .example("!history 10")
+        .example("!history export"),
+        CommandSpec::new("context", "Show or extend the MCP context file")
+            .room_only()
+            .arg("action", false, "show, set <key> <value> or clear [key]")
+            .example("!context show")
+            .example("!context set project apollo")
+            .example("!context clear"),
+        CommandSpec::new(
+            "compare",
+            "Run one prompt on two backends and show both answers",
+        )
+        .room_only()
+        .arg("backend-a", true, "First backend")
+        .arg("backend-b", true, "Second backend")
+        .arg("prompt", true, "Prompt to run on both")
+        .example("!compare acp mux summarize README.md"),
+        CommandSpec::new("ask", "Answer a side question without touching the session")
+            .room_only()
+            .arg("question", true, "Question to answer")
+            .flag("--write", "Allow tools that change the workspace")
+            .example("!ask what does deploy.sh do?")
+            .example("!ask --write fix the typo in README.md"),
+        CommandSpec::new("summarize", "Recap this channel's history as bullets")
+            .room_only()
+            .example("!summarize"),
+        CommandSpec::new("invite", "Invite someone into this channel's room")
+            .room_only()
+            .arg("user", true, "User ID, e.g. @bob:matrix.org")
+            .example("!invite @bob:matrix.org"),
+        CommandSpec::new("deliver", "Hold agent output outside a delivery window")
+            .room_only()
+            .arg(
+                "action",
+                true,
+                "window <days> <HH:MM-HH:MM> [timezone] [notice], now or off",
+            )
+            .example("!deliver window weekdays 08:00-18:00")
+            .example("!deliver now")
+            .example("!deliver off"),
+        CommandSpec::new("schedule", "Create and manage scheduled prompts")
+            .room_only()
+            .arg(
+                "time",
+                true,
+                "When to run, or list/edit/log/delete/pause/resume/export/import",
+            )
+            .arg("prompt", false, "Prompt to run")
+            .example("!schedule in 2 hours check my inbox")
+            .example("!schedule every monday 8am weekly standup")
+            .example("!schedule list"),
+        CommandSpec::new("promote", "Raise a member's power level in this room")
+            .room_only()
+            .admin()
+            .arg("user", true, "User ID, e.g. @bob:matrix.org")
+            .arg("level", false, "Power level below 100 (default 50)")
+            .example("!promote @bob:matrix.org"),
+        CommandSpec::new("topic", "Show or change this room's topic")
+            .room_only()
+            .arg("text", false, "New topic")
+            .example("!topic Release planning for v2"),
+        CommandSpec::new("leave", "Bot leaves this room (preserves workspace)")
+            .room_only()
+            .example("!leave"),
+        CommandSpec::new("dispatch", "Switch this DM to the DISPATCH control plane")
+            .dm_only()
+            .example("!dispatch"),
+        CommandSpec::new("changelog", "Show recent changes").example("!changelog"),
+        CommandSpec::new("motd", "Show the message of the day").example("!motd"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_builtin_has_description_and_example() {
+        for spec in CommandCatalog::builtin().commands() {
+            assert!(
+                !spec.description.is_empty(),
+                "!{} has no description",
+                spec.name
+            );
+            assert!(!spec.examples.is_empty(), "!{} has no example", spec.name);
+            assert!(!spec.contexts.is_empty(), "!{} has no context", spec.name);
+            for example in &spec.examples {
+                let invoked = example
+                    .trim_start_matches('!')
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default();
+                assert!(
+                    example.starts_with('!') && spec.answers_to(invoked),
+                    "example '{}' doesn't invoke !{}",
+                    example,
+                    spec.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_taken_names_and_aliases() {
+        let mut catalog = CommandCatalog::builtin();
+        assert!(catalog
+            .register(CommandSpec::new("help", "Another help"))
+            .is_err());
+        assert!(catalog
+            .register(CommandSpec::new("hlp", "Another help").alias("status"))
+            .is_err());
+
+        catalog
+            .register(CommandSpec::new("standup", "Post a standup").alias("su"))
+            .unwrap();
+        assert_eq!(catalog.get("su").map(|s| s.name.as_str()), Some("standup"));
+    }
+
+    #[test]
+    fn test_configured_aliases_cannot_shadow_commands() {
+        let mut catalog = CommandCatalog::builtin();
+        assert!(catalog.add_alias("help", "delete").is_err());
+        assert!(catalog.add_alias("ls", "status").is_err());
+        assert!(catalog.add_alias("nuke", "explode").is_err());
+
+        catalog.add_alias("!Sched", "schedule").unwrap();
+        catalog.add_alias("mk", "new").unwrap();
+        assert_eq!(catalog.canonical_name("sched"), "schedule");
+        assert_eq!(catalog.canonical_name("mk"), "create");
+        assert_eq!(catalog.canonical_name("help"), "help");
+        assert_eq!(catalog.canonical_name("unknown"), "unknown");
+        assert!(catalog.aliases().any(|pair| pair == ("sched", "schedule")));
+    }
+
+    #[test]
+    fn test_usage_marks_required_and_optional() {
+        let catalog = CommandCatalog::builtin();
+        assert_eq!(
+            catalog.get("length").unwrap().usage(),
+            "!length <mode> [strict]"
+        );
+        assert_eq!(catalog.get("locale").unwrap().usage(), "!locale [code]");
+        assert_eq!(
+            catalog.get("compare").unwrap().usage(),
+            "!compare <backend-a> <backend-b> <prompt>"
+        );
+    }
+}
@@ -1,6 +1,11 @@
 // ABOUTME: Generic command parsing for chat bot commands
 // ABOUTME: Platform-agnostic !command handling
 
+use crate::command_catalog::CommandCatalog;
+
+/// Command prefix used when a platform doesn't configure one
+pub const DEFAULT_COMMAND_PREFIX: &str = "!";
+
 /// Represents a parsed command from a chat message
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Command {
@@ -10,6 +15,9 @@ pub struct Command {
     pub args: Vec<String>,
     /// The raw argument string after the command name
     pub raw_args: String,
+    /// What the command was typed after, for replies that quote commands back
+    /// (e.g. "!" for `!help`, "!claude " for `!claude help`)
+    pub prefix: String,
 }
 
 impl Command {
@@ -19,9 +27,16 @@ impl Command {
             name: name.into(),
             args,
             raw_args: raw_args.into(),
+            prefix: DEFAULT_COMMAND_PREFIX.to_string(),
         }
     }
 
+    /// Set the prefix the command was typed after
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
     /// Get the first argument if present
     pub fn first_arg(&self) -> Option<&str> {
         self.args.first().map(|s| s.as_str())
@@ -126,7 +141,7 @@ fn parse_args(input: &str) -> Vec<String> {
 ///
 /// # Arguments
 /// * `body` - The message body to parse
-/// * `bot_prefix` - The bot's command prefix (e.g., "!claude")
+/// * `prefixes` - Accepted command prefixes (e.g., `["!claude", "!"]`)
 ///
 /// # Returns
 /// * `ParseResult::Command` - If the message is a valid command
@@ -134,14 +149,17 @@ fn parse_args(input: &str) -> Vec<String> {
 /// * `ParseResult::Ignore` - If the message should be ignored
 ///
 /// # Command Recognition
-/// Recognizes commands in these formats:
-/// - `!command` - Simple command prefix
-/// - `{bot_prefix} command` - Bot mention prefix (e.g., "!claude help")
+/// Prefixes are matched case-insensitively, longest first:
+/// - A prefix ending in a letter or digit is a bot mention and needs a space
+///   before the command (e.g., "!claude help")
+/// - Any other prefix is followed directly by the command name, which must
+///   start with a letter (e.g., "!help", "?help")
 ///
 /// # Escape Sequences
-/// - Messages starting with `!!` are treated as regular messages (escape)
+/// - A doubled prefix (`!!` for `!`) marks a regular message that would
+///   otherwise look like a command
 /// - Empty messages are ignored
-pub fn parse_message(body: &str, bot_prefix: &str) -> ParseResult {
+pub fn parse_message(body: &str, prefixes: &[impl AsRef<str>]) -> ParseResult {
     let trimmed = body.trim();
 
     // Empty messages are ignored
@@ -149,40 +167,44 @@ pub fn parse_message(body: &str, bot_prefix: &str) -> ParseResult {
         return ParseResult::Ignore;
     }
 
-    // Escape sequence: !! at start means treat as regular message
-    if let Some(stripped) = trimmed.strip_prefix("!!") {
-        let escaped = stripped.trim();
-        if escaped.is_empty() {
-            return ParseResult::Ignore;
-        }
-        return ParseResult::Message(escaped.to_string());
-    }
-
-    // Check for bot prefix style: "!claude command args"
-    let bot_prefix_lower = bot_prefix.to_lowercase();
-    let trimmed_lower = trimmed.to_lowercase();
+    let mut prefixes: Vec<&str> = prefixes
+        .iter()
+        .map(|p| p.as_ref())
+        .filter(|p| !p.is_empty())
+        .collect();
+    prefixes.sort_by_key(|p| std::cmp::Reverse(p.len()));
 
-    if trimmed_lower.starts_with(&bot_prefix_lower)
-        && trimmed.len() > bot_prefix.len()
-        && trimmed
-            .chars()
-            .nth(bot_prefix.len())
-            .is_some_and(|c| c.is_whitespace())
-    {
-        let remainder = trimmed[bot_prefix.len()..].trim();
-        if remainder.is_empty() {
-            // Just the prefix with nothing after it
-            return ParseResult::Command(Command::new("", Vec::new(), ""));
+    // Escape sequence: a doubled prefix means treat as regular message
+    for prefix in prefixes.iter().filter(|p| !is_mention_prefix(p)) {
+        if let Some(stripped) = strip_prefix_ignore_case(trimmed, &prefix.repeat(2)) {
+            let escaped = stripped.trim();
+            if escaped.is_empty() {
+                return ParseResult::Ignore;
+            }
+            return ParseResult::Message(escaped.to_string());
         }
-        return parse_command_from_text(remainder);
     }
 
-    // Check for simple !command style
-    if trimmed.starts_with('!') && trimmed.len() > 1 {
-        let after_bang = &trimmed[1..];
-        // Must start with an alphabetic character
-        if after_bang.chars().next().is_some_and(|c| c.is_alphabetic()) {
-            return parse_command_from_text(after_bang);
+    for prefix in prefixes {
+        let Some(rest) = strip_prefix_ignore_case(trimmed, prefix) else {
+            continue;
+        };
+
+        if is_mention_prefix(prefix) {
+            // Bot mention style: "!claude command args"
+            if rest.starts_with(char::is_whitespace) {
+                let remainder = rest.trim();
+                if remainder.is_empty() {
+                    // Just the prefix with nothing after it
+                    return ParseResult::Command(
+                        Command::new("", Vec::new(), "").with_prefix(format!("{} ", prefix)),
+                    );
+                }
+                return parse_command_from_text(remainder, &format!("{} ", prefix));
+            }
+        } else if rest.chars().next().is_some_and(|c| c.is_alphabetic()) {
+            // Simple "!command" style
+            return parse_command_from_text(rest, prefix);
         }
     }
 
@@ -190,11 +212,26 @@ pub fn parse_message(body: &str, bot_prefix: &str) -> ParseResult {
     ParseResult::Message(trimmed.to_string())
 }
 
+/// Whether `prefix` names the bot (needs a space after it) rather than being a symbol
+fn is_mention_prefix(prefix: &str) -> bool {
+    prefix.chars().last().is_some_and(|c| c.is_alphanumeric())
+}
+
+/// `text` without a leading `prefix`, compared ignoring ASCII case
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        text.get(prefix.len()..)
+    } else {
+        None
+    }
+}
+
 /// Parse command name and arguments from text (without the prefix)
-fn parse_command_from_text(text: &str) -> ParseResult {
+fn parse_command_from_text(text: &str, prefix: &str) -> ParseResult {
     let text = text.trim();
     if text.is_empty() {
-        return ParseResult::Command(Command::new("", Vec::new(), ""));
+        return ParseResult::Command(Command::new("", Vec::new(), "").with_prefix(prefix));
     }
 
     // Split into command name and rest
@@ -203,7 +240,22 @@ fn parse_command_from_text(text: &str) -> ParseResult {
     let raw_args = parts.get(1).map(|s| s.trim()).unwrap_or("").to_string();
     let args = parse_args(&raw_args);
 
-    ParseResult::Command(Command::new(name, args, raw_args))
+    ParseResult::Command(Command::new(name, args, raw_args).with_prefix(prefix))
+}
+
+/// `parse_message`, with command aliases in `catalog` resolved to their command
+pub fn parse_message_with_aliases(
+    body: &str,
+    prefixes: &[impl AsRef<str>],
+    catalog: &CommandCatalog,
+) -> ParseResult {
+    match parse_message(body, prefixes) {
+        ParseResult::Command(mut cmd) => {
+            cmd.name = catalog.canonical_name(&cmd.name).to_string();
+            ParseResult::Command(cmd)
+        }
+        other => other,
+    }
 }
 
 /// Trait for handling parsed commands
@@ -281,9 +333,11 @@ impl<C, R, E> CommandRegistry<C, R, E> {
 mod tests {
     use super::*;
 
+    const PREFIXES: &[&str] = &["!claude", "!"];
+
     #[test]
     fn test_parse_simple_command() {
-        let result = parse_message("!help", "!claude");
+        let result = parse_message("!help", PREFIXES);
         assert!(matches!(
             result,
             ParseResult::Command(ref cmd) if cmd.name == "help"
@@ -292,7 +346,7 @@ mod tests {
 
     #[test]
     fn test_parse_command_with_args() {
-        let result = parse_message("!create my-channel", "!claude");
+        let result = parse_message("!create my-channel", PREFIXES);
         match result {
             ParseResult::Command(cmd) => {
                 assert_eq!(cmd.name, "create");
@@ -305,7 +359,7 @@ mod tests {
 
     #[test]
     fn test_parse_bot_prefix_command() {
-        let result = parse_message("!claude help", "!claude");
+        let result = parse_message("!claude help", PREFIXES);
         match result {
             ParseResult::Command(cmd) => {
                 assert_eq!(cmd.name, "help");
@@ -317,7 +371,7 @@ mod tests {
 
     #[test]
     fn test_parse_bot_prefix_with_args() {
-        let result = parse_message("!claude create my-channel", "!claude");
+        let result = parse_message("!claude create my-channel", PREFIXES);
         match result {
             ParseResult::Command(cmd) => {
                 assert_eq!(cmd.name, "create");
@@ -329,7 +383,7 @@ mod tests {
 
     #[test]
     fn test_parse_escape_sequence() {
-        let result = parse_message("!!not a command", "!claude");
+        let result = parse_message("!!not a command", PREFIXES);
         match result {
             ParseResult::Message(msg) => {
                 assert_eq!(msg, "not a command");
@@ -340,7 +394,7 @@ mod tests {
 
     #[test]
     fn test_parse_regular_message() {
-        let result = parse_message("hello world", "!claude");
+        let result = parse_message("hello world", PREFIXES);
         match result {
             ParseResult::Message(msg) => {
                 assert_eq!(msg, "hello world");
@@ -351,25 +405,25 @@ mod tests {
 
     #[test]
     fn test_parse_empty_message() {
-        let result = parse_message("", "!claude");
+        let result = parse_message("", PREFIXES);
         assert!(matches!(result, ParseResult::Ignore));
     }
 
     #[test]
     fn test_parse_whitespace_only() {
-        let result = parse_message("   ", "!claude");
+        let result = parse_message("   ", PREFIXES);
         assert!(matches!(result, ParseResult::Ignore));
     }
 
     #[test]
     fn test_parse_just_exclamation() {
-        let result = parse_message("!", "!claude");
+        let result = parse_message("!", PREFIXES);
         assert!(matches!(result, ParseResult::Message(_)));
     }
 
     #[test]
     fn test_parse_quoted_args() {
-        let result = parse_message("!search \"hello world\" today", "!claude");
+        let result = parse_message("!search \"hello world\" today", PREFIXES);
         match result {
             ParseResult::Command(cmd) => {
                 assert_eq!(cmd.name, "search");
@@ -381,7 +435,7 @@ mod tests {
 
     #[test]
     fn test_parse_single_quoted_args() {
-        let result = parse_message("!search 'hello world' today", "!claude");
+        let result = parse_message("!search 'hello world' today", PREFIXES);
         match result {
             ParseResult::Command(cmd) => {
                 assert_eq!(cmd.name, "search");
@@ -393,7 +447,7 @@ mod tests {
 
     #[test]
     fn test_parse_case_insensitive_prefix() {
-        let result = parse_message("!CLAUDE help", "!claude");
+        let result = parse_message("!CLAUDE help", PREFIXES);
         match result {
             ParseResult::Command(cmd) => {
                 assert_eq!(cmd.name, "help");
@@ -438,18 +492,82 @@ mod tests {
     #[test]
     fn test_non_alphabetic_after_bang() {
         // !123 should not be a command
-        let result = parse_message("!123", "!claude");
+        let result = parse_message("!123", PREFIXES);
         assert!(matches!(result, ParseResult::Message(_)));
 
         // !-test should not be a command
-        let result = parse_message("!-test", "!claude");
+        let result = parse_message("!-test", PREFIXES);
         assert!(matches!(result, ParseResult::Message(_)));
     }
 
     #[test]
     fn test_escape_empty() {
         // !! followed by nothing should be ignored
-        let result = parse_message("!!", "!claude");
+        let result = parse_message("!!", PREFIXES);
         assert!(matches!(result, ParseResult::Ignore));
     }
+
+    #[test]
+    fn test_parse_records_prefix() {
+        let result = parse_message("!help", PREFIXES);
+        assert_eq!(result.as_command().unwrap().prefix, "!");
+        let result = parse_message("!claude help", PREFIXES);
+        assert_eq!(result.as_command().unwrap().prefix, "!claude ");
+    }
+
+    #[test]
+    fn test_parse_configured_prefixes() {
+        let prefixes = &["?claude", "?"];
+        let result = parse_message("?status", prefixes);
+        assert_eq!(result.as_command().unwrap().name, "status");
+        assert_eq!(result.as_command().unwrap().prefix, "?");
+        let result = parse_message("?claude create my-channel", prefixes);
+        assert_eq!(result.as_command().unwrap().name, "create");
+
+        // Other bots' commands are left alone
+        let result = parse_message("!deploy prod", prefixes);
+        assert_eq!(result.as_message(), Some("!deploy prod"));
+    }
+
+    #[test]
+    fn test_escape_follows_configured_prefix() {
+        let result = parse_message("??status is a word", &["?"]);
+        assert_eq!(result.as_message(), Some("status is a word"));
+        assert!(parse_message("??", &["?"]).is_ignore());
+
+        // Escapes for prefixes that aren't configured are plain text
+        let result = parse_message("!!help", &["?"]);
+        assert_eq!(result.as_message(), Some("!!help"));
+
+        let result = parse_message("gorp!!help", &["gorp!"]);
+        assert_eq!(result.as_message(), Some("help"));
+    }
+
+    #[test]
+    fn test_multi_character_prefix() {
+        let result = parse_message("gorp!help", &["gorp!"]);
+        assert_eq!(result.as_command().unwrap().name, "help");
+        let result = parse_message("GORP!help", &["gorp!"]);
+        assert_eq!(result.as_command().unwrap().name, "help");
+        let result = parse_message("gorp! help", &["gorp!"]);
+        assert!(result.is_message());
+    }
+
+    #[test]
+    fn test_aliases_resolve_to_their_command() {
+        let catalog = CommandCatalog::builtin();
+        let result = parse_message_with_aliases("!new research", PREFIXES, &catalog);
+        let cmd = result.as_command().unwrap();
+        assert_eq!(cmd.name, "create");
+        assert_eq!(cmd.first_arg(), Some("research"));
+
+        let result = parse_message_with_aliases("!claude ls", PREFIXES, &catalog);
+        assert_eq!(result.as_command().unwrap().name, "list");
+
+        // Anything else passes through untouched
+        let result = parse_message_with_aliases("!frobnicate", PREFIXES, &catalog);
+        assert_eq!(result.as_command().unwrap().name, "frobnicate");
+        let result = parse_message_with_aliases("new ideas", PREFIXES, &catalog);
+        assert_eq!(result.as_message(), Some("new ideas"));
+    }
 }
@@ -0,0 +1,219 @@
+// ABOUTME: Destructive commands waiting for the person who ran them to press Confirm or Cancel.
+// ABOUTME: A token ties each button press to its stored command; unanswered ones expire.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long Confirm / Cancel buttons stay live
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(120);
+
+/// Command name a Confirm press comes back as
+pub const CONFIRM_COMMAND: &str = "confirm";
+
+/// Command name a Cancel press comes back as
+pub const CANCEL_COMMAND: &str = "cancel";
+
+/// The command (without prefix) a button press is turned into, e.g. `confirm 1a2b…`
+pub fn answer_command(confirmed: bool, token: &str) -> String {
+    let name = if confirmed {
+        CONFIRM_COMMAND
+    } else {
+        CANCEL_COMMAND
+    };
+    format!("{} {}", name, token)
+}
+
+/// A command held until its requester answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingConfirmation {
+    pub platform_id: String,
+    pub channel_id: String,
+    pub requester_id: String,
+    /// Command name, e.g. "delete"
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+/// What a button press amounts to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// The requester confirmed; run the command
+    Confirmed(PendingConfirmation),
+    /// The requester cancelled; drop the command
+    Cancelled(PendingConfirmation),
+    /// Someone else pressed; the command stays pending
+    NotRequester,
+    /// No such token here, or it expired
+    Unknown,
+}
+
+#[derive(Debug)]
+struct Entry {
+    pending: PendingConfirmation,
+    at: Instant,
+}
+
+/// Pending confirmations by token. Expired entries are dropped whenever the
+/// tracker is touched.
+#[derive(Debug)]
+pub struct PendingConfirmations {
+    entries: Mutex<HashMap<String, Entry>>,
+    ttl: Duration,
+}
+
+impl Default for PendingConfirmations {
+    fn default() -> Self {
+        Self::new(CONFIRMATION_TTL)
+    }
+}
+
+impl PendingConfirmations {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Hold `pending` and return the token its buttons carry
+    pub fn insert(&self, pending: PendingConfirmation) -> String {
+        // Short enough for Telegram's 64-byte callback data, long enough not to guess
+        let token = uuid::Uuid::new_v4().simple().to_string()[..16].to_string();
+        if let Ok(mut entries) = self.entries.lock() {
+            self.prune(&mut entries);
+            entries.insert(
+                token.clone(),
+                Entry {
+                    pending,
+                    at: Instant::now(),
+                },
+            );
+        }
+        token
+    }
+
+    /// Settle `token` for a press by `presser_id` in `platform_id`'s `channel_id`.
+    /// Only the requester, in the chat the command was run in, can settle it.
+    pub fn resolve(
+        &self,
+        token: &str,
+        platform_id: &str,
+        channel_id: &str,
+        presser_id: &str,
+        confirmed: bool,
+    ) -> Resolution {
+        let Ok(mut entries) = self.entries.lock() else {
+            return Resolution::Unknown;
+        };
+        self.prune(&mut entries);
+        let Some(entry) = entries.get(token) else {
+            return Resolution::Unknown;
+        };
+        let pending = &entry.pending;
+        if pending.platform_id != platform_id || pending.channel_id != channel_id {
+            return Resolution::Unknown;
+        }
+        if pending.requester_id != presser_id {
+            return Resolution::NotRequester;
+        }
+        let pending = entries
+            .remove(token)
+            .map(|entry| entry.pending)
+            .expect("entry checked above");
+        if confirmed {
+            Resolution::Confirmed(pending)
+        } else {
+            Resolution::Cancelled(pending)
+        }
+    }
+
+    /// Number of confirmations still waiting
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|mut entries| {
+                self.prune(&mut entries);
+                entries.len()
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn prune(&self, entries: &mut HashMap<String, Entry>) {
+        let ttl = self.ttl;
+        entries.retain(|_, entry| entry.at.elapsed() < ttl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(requester: &str) -> PendingConfirmation {
+        PendingConfirmation {
+            platform_id: "telegram".to_string(),
+            channel_id: "42".to_string(),
+            requester_id: requester.to_string(),
+            command: "delete".to_string(),
+            args: vec!["research".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_only_requester_can_resolve() {
+        let tracker = PendingConfirmations::default();
+        let token = tracker.insert(pending("7"));
+
+        assert_eq!(
+            tracker.resolve(&token, "telegram", "42", "8", true),
+            Resolution::NotRequester
+        );
+        assert_eq!(
+            tracker.resolve(&token, "telegram", "42", "7", true),
+            Resolution::Confirmed(pending("7"))
+        );
+        // Settled once only
+        assert_eq!(
+            tracker.resolve(&token, "telegram", "42", "7", true),
+            Resolution::Unknown
+        );
+    }
+
+    #[test]
+    fn test_cancel_and_wrong_chat() {
+        let tracker = PendingConfirmations::default();
+        let token = tracker.insert(pending("7"));
+        assert_eq!(
+            tracker.resolve(&token, "telegram", "99", "7", true),
+            Resolution::Unknown
+        );
+        assert_eq!(
+            tracker.resolve(&token, "telegram", "42", "7", false),
+            Resolution::Cancelled(pending("7"))
+        );
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let tracker = PendingConfirmations::new(Duration::from_millis(20));
+        let token = tracker.insert(pending("7"));
+        assert_eq!(tracker.len(), 1);
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(
+            tracker.resolve(&token, "telegram", "42", "7", true),
+            Resolution::Unknown
+        );
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_answer_command() {
+        assert_eq!(answer_command(true, "abc"), "confirm abc");
+        assert_eq!(answer_command(false, "abc"), "cancel abc");
+    }
+}
@@ -0,0 +1,102 @@
+// ABOUTME: Connection state a platform shares with its background tasks. Every change is
+// ABOUTME: broadcast as a ConnectionEvent, so status views update without polling.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+
+use crate::traits::PlatformConnectionState;
+
+/// Events kept for a subscriber that falls behind
+const EVENT_CAPACITY: usize = 64;
+
+/// A platform's connection state changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionEvent {
+    pub platform_id: String,
+    pub state: PlatformConnectionState,
+}
+
+impl ConnectionEvent {
+    /// Why the platform disconnected, for disconnect events
+    pub fn reason(&self) -> Option<&str> {
+        match &self.state {
+            PlatformConnectionState::Disconnected { reason } => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+/// A platform's current connection state. Clones share the state, and `set`
+/// from any of them notifies every subscriber.
+#[derive(Debug, Clone)]
+pub struct ConnectionTracker {
+    platform_id: &'static str,
+    state: Arc<Mutex<PlatformConnectionState>>,
+    events: broadcast::Sender<ConnectionEvent>,
+}
+
+impl ConnectionTracker {
+    pub fn new(platform_id: &'static str, initial: PlatformConnectionState) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            platform_id,
+            state: Arc::new(Mutex::new(initial)),
+            events,
+        }
+    }
+
+    pub fn get(&self) -> PlatformConnectionState {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Record the state, announcing it if it differs from the current one.
+    /// Returns whether it changed.
+    pub fn set(&self, state: PlatformConnectionState) -> bool {
+        {
+            let mut current = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            if *current == state {
+                return false;
+            }
+            *current = state.clone();
+        }
+        // No subscribers is fine: the state is still recorded for polling
+        let _ = self.events.send(ConnectionEvent {
+            platform_id: self.platform_id.to_string(),
+            state,
+        });
+        true
+    }
+
+    /// Changes made after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_changes_are_announced() {
+        let tracker = ConnectionTracker::new("slack", PlatformConnectionState::Connecting);
+        let mut events = tracker.subscribe();
+
+        assert!(tracker.set(PlatformConnectionState::Connected));
+        assert!(!tracker.clone().set(PlatformConnectionState::Connected));
+        assert!(tracker.set(PlatformConnectionState::Disconnected {
+            reason: "socket closed".to_string(),
+        }));
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.platform_id, "slack");
+        assert_eq!(event.state, PlatformConnectionState::Connected);
+        assert_eq!(event.reason(), None);
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.reason(), Some("socket closed"));
+        assert!(events.try_recv().is_err());
+        assert_eq!(tracker.get().label(), "disconnected");
+    }
+}
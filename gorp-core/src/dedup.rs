@@ -0,0 +1,156 @@
+// ABOUTME: LRU cache of recently handled (platform_id, event_id) pairs with a TTL.
+// ABOUTME: Lets message handlers drop events replayed after a sync restart or offset mistake.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+type DedupKey = (String, String);
+
+struct Entry {
+    seen_at: Instant,
+    /// Matches the queue slot that currently represents this entry
+    generation: u64,
+}
+
+struct Inner {
+    entries: HashMap<DedupKey, Entry>,
+    /// Recency order, oldest first; slots whose generation is stale are skipped
+    order: VecDeque<(DedupKey, u64)>,
+    next_generation: u64,
+}
+
+/// Bounded, time-limited record of events that have already been handled.
+///
+/// Lookups refresh recency, so an event that keeps being replayed stays cached
+/// while quiet entries are evicted first. Entries older than the TTL count as unseen.
+pub struct DedupCache {
+    inner: Mutex<Inner>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl DedupCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                next_generation: 0,
+            }),
+            capacity: capacity.max(1),
+            ttl,
+        }
+    }
+
+    /// Record the event and return true if it has not been seen within the TTL.
+    ///
+    /// Returns false for a duplicate, which the caller should drop silently.
+    pub fn check_and_mark(&self, platform_id: &str, event_id: &str) -> bool {
+        let now = Instant::now();
+        let key = (platform_id.to_string(), event_id.to_string());
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        let generation = inner.next_generation;
+        inner.next_generation += 1;
+
+        let is_new = match inner.entries.get_mut(&key) {
+            Some(entry) if now.duration_since(entry.seen_at) < self.ttl => {
+                entry.generation = generation;
+                false
+            }
+            Some(entry) => {
+                entry.seen_at = now;
+                entry.generation = generation;
+                true
+            }
+            None => {
+                inner.entries.insert(
+                    key.clone(),
+                    Entry {
+                        seen_at: now,
+                        generation,
+                    },
+                );
+                true
+            }
+        };
+        inner.order.push_back((key, generation));
+
+        self.evict(&mut inner, now);
+        is_new
+    }
+
+    /// Number of events currently remembered
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn evict(&self, inner: &mut Inner, now: Instant) {
+        while let Some((key, generation)) = inner.order.front().cloned() {
+            let Some(entry) = inner.entries.get(&key) else {
+                inner.order.pop_front();
+                continue;
+            };
+            if entry.generation != generation {
+                // Superseded by a later lookup of the same key
+                inner.order.pop_front();
+                continue;
+            }
+            let expired = now.duration_since(entry.seen_at) >= self.ttl;
+            if !expired && inner.entries.len() <= self.capacity {
+                break;
+            }
+            inner.order.pop_front();
+            inner.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_is_rejected() {
+        let cache = DedupCache::new(10, Duration::from_secs(60));
+        assert!(cache.check_and_mark("matrix", "$a"));
+        assert!(!cache.check_and_mark("matrix", "$a"));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_same_event_id_on_other_platform_is_distinct() {
+        let cache = DedupCache::new(10, Duration::from_secs(60));
+        assert!(cache.check_and_mark("matrix", "42"));
+        assert!(cache.check_and_mark("telegram", "42"));
+    }
+
+    #[test]
+    fn test_least_recently_seen_is_evicted() {
+        let cache = DedupCache::new(2, Duration::from_secs(60));
+        assert!(cache.check_and_mark("matrix", "$a"));
+        assert!(cache.check_and_mark("matrix", "$b"));
+        // Touch $a so $b becomes the least recently seen
+        assert!(!cache.check_and_mark("matrix", "$a"));
+        assert!(cache.check_and_mark("matrix", "$c"));
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.check_and_mark("matrix", "$a"));
+        assert!(cache.check_and_mark("matrix", "$b"));
+    }
+
+    #[test]
+    fn test_expired_entries_are_forgotten() {
+        let cache = DedupCache::new(10, Duration::ZERO);
+        assert!(cache.check_and_mark("matrix", "$a"));
+        assert!(cache.check_and_mark("matrix", "$a"));
+    }
+}
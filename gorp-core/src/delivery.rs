@@ -0,0 +1,324 @@
+// ABOUTME: Per-channel delivery windows that hold non-urgent output until the channel is open.
+// ABOUTME: Parses specs like "weekdays 08:00-18:00 Europe/Berlin" and evaluates them in local time.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Bitmask for Monday through Friday
+const WEEKDAYS: u8 = 0b0001_1111;
+/// Bitmask for Saturday and Sunday
+const WEEKENDS: u8 = 0b0110_0000;
+/// Bitmask for every day of the week
+const DAILY: u8 = 0b0111_1111;
+
+/// How urgently an outgoing message needs to reach its channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryPriority {
+    /// Regular agent output - held while the channel's window is closed
+    Normal,
+    /// Errors and alerts - always delivered immediately
+    Urgent,
+}
+
+/// A recurring window during which a channel accepts agent output.
+///
+/// Times are wall-clock times in `timezone`, so the window follows DST
+/// transitions. A window whose end is before its start spans midnight and
+/// belongs to the day it starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryWindow {
+    days: u8,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub timezone: String,
+    /// Post an "N messages held" notice before flushing held messages
+    pub announce: bool,
+}
+
+/// A message held back because its channel's delivery window was closed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeldMessage {
+    pub id: i64,
+    pub channel_name: String,
+    /// Platform to deliver on; None means publish to the message bus
+    pub platform_id: Option<String>,
+    /// Platform channel/room to deliver to; None means publish to the message bus
+    pub target_id: Option<String>,
+    pub body: String,
+    pub created_at: String,
+}
+
+fn day_bit(day: Weekday) -> u8 {
+    1 << day.num_days_from_monday()
+}
+
+fn parse_days(input: &str) -> Result<u8> {
+    match input.to_lowercase().as_str() {
+        "weekdays" => return Ok(WEEKDAYS),
+        "weekends" => return Ok(WEEKENDS),
+        "daily" | "everyday" => return Ok(DAILY),
+        _ => {}
+    }
+
+    let mut mask = 0u8;
+    for part in input.split(',') {
+        if let Some((from, to)) = part.split_once('-') {
+            let from: Weekday = from
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Unknown day: {}", from))?;
+            let to: Weekday = to
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Unknown day: {}", to))?;
+            let mut day = from;
+            loop {
+                mask |= day_bit(day);
+                if day == to {
+                    break;
+                }
+                day = day.succ();
+            }
+        } else {
+            let day: Weekday = part
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Unknown day: {}", part))?;
+            mask |= day_bit(day);
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_time_range(input: &str) -> Result<(NaiveTime, NaiveTime)> {
+    let (start, end) = input
+        .split_once('-')
+        .with_context(|| format!("Expected a time range like 08:00-18:00, got '{}'", input))?;
+    let start = NaiveTime::parse_from_str(start, "%H:%M")
+        .with_context(|| format!("Invalid start time: {}", start))?;
+    let end = NaiveTime::parse_from_str(end, "%H:%M")
+        .with_context(|| format!("Invalid end time: {}", end))?;
+    if start == end {
+        anyhow::bail!("Delivery window start and end must differ");
+    }
+    Ok((start, end))
+}
+
+impl DeliveryWindow {
+    /// Parse a window spec: `<days> <HH:MM-HH:MM> [timezone] [notice]`.
+    ///
+    /// Days may be `weekdays`, `weekends`, `daily`, a comma list (`mon,wed,fri`)
+    /// or a range (`mon-thu`). The timezone defaults to `default_timezone`.
+    pub fn parse(spec: &str, default_timezone: &str) -> Result<Self> {
+        let mut tokens = spec.split_whitespace();
+        let days = tokens
+            .next()
+            .context("Missing days (e.g. weekdays)")
+            .and_then(parse_days)?;
+        let (start, end) = tokens
+            .next()
+            .context("Missing time range (e.g. 08:00-18:00)")
+            .and_then(parse_time_range)?;
+
+        let mut timezone = default_timezone.to_string();
+        let mut announce = false;
+        for token in tokens {
+            if token.eq_ignore_ascii_case("notice") {
+                announce = true;
+            } else {
+                timezone = token.to_string();
+            }
+        }
+        timezone
+            .parse::<Tz>()
+            .map_err(|_| anyhow::anyhow!("Unknown timezone: {}", timezone))?;
+
+        Ok(Self {
+            days,
+            start,
+            end,
+            timezone,
+            announce,
+        })
+    }
+
+    /// Whether the window includes the given day of the week
+    pub fn includes(&self, day: Weekday) -> bool {
+        self.days & day_bit(day) != 0
+    }
+
+    /// Whether output may be delivered at `now`
+    pub fn is_open_at(&self, now: DateTime<Utc>) -> bool {
+        let Ok(tz) = self.timezone.parse::<Tz>() else {
+            // Validated on parse; fail open rather than hold output forever
+            return true;
+        };
+        let local = now.with_timezone(&tz);
+        let today = local.weekday();
+        let time = local.time();
+
+        if self.start < self.end {
+            self.includes(today) && time >= self.start && time < self.end
+        } else {
+            (self.includes(today) && time >= self.start)
+                || (self.includes(today.pred()) && time < self.end)
+        }
+    }
+
+    fn days_label(&self) -> String {
+        match self.days {
+            WEEKDAYS => "weekdays".to_string(),
+            WEEKENDS => "weekends".to_string(),
+            DAILY => "daily".to_string(),
+            _ => {
+                let mut day = Weekday::Mon;
+                let mut names = Vec::new();
+                for _ in 0..7 {
+                    if self.includes(day) {
+                        names.push(day.to_string().to_lowercase());
+                    }
+                    day = day.succ();
+                }
+                names.join(",")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DeliveryWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}-{} {}",
+            self.days_label(),
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M"),
+            self.timezone
+        )?;
+        if self.announce {
+            write!(f, " notice")?;
+        }
+        Ok(())
+    }
+}
+
+/// Decide whether a message for a channel with `window` should be held at `now`.
+///
+/// Urgent messages and channels without a window are never held.
+pub fn should_hold(
+    window: Option<&DeliveryWindow>,
+    priority: DeliveryPriority,
+    now: DateTime<Utc>,
+) -> bool {
+    if priority == DeliveryPriority::Urgent {
+        return false;
+    }
+    match window {
+        Some(window) => !window.is_open_at(now),
+        None => false,
+    }
+}
+
+/// Notice posted ahead of a flush when the window has `announce` set
+pub fn held_notice(count: usize) -> String {
+    format!(
+        "📬 {} message{} held outside the delivery window:",
+        count,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_weekdays_window() {
+        let w = DeliveryWindow::parse("weekdays 08:00-18:00", "UTC").unwrap();
+        assert!(w.includes(Weekday::Mon));
+        assert!(w.includes(Weekday::Fri));
+        assert!(!w.includes(Weekday::Sat));
+        assert_eq!(w.timezone, "UTC");
+        assert!(!w.announce);
+    }
+
+    #[test]
+    fn test_parse_day_list_range_and_flags() {
+        let w =
+            DeliveryWindow::parse("mon-wed,sat 09:30-17:00 Europe/Berlin notice", "UTC").unwrap();
+        assert!(w.includes(Weekday::Tue));
+        assert!(w.includes(Weekday::Sat));
+        assert!(!w.includes(Weekday::Thu));
+        assert_eq!(w.timezone, "Europe/Berlin");
+        assert!(w.announce);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(DeliveryWindow::parse("weekdays", "UTC").is_err());
+        assert!(DeliveryWindow::parse("someday 08:00-18:00", "UTC").is_err());
+        assert!(DeliveryWindow::parse("weekdays 08:00", "UTC").is_err());
+        assert!(DeliveryWindow::parse("weekdays 08:00-08:00", "UTC").is_err());
+        assert!(DeliveryWindow::parse("weekdays 08:00-18:00 Mars/Olympus", "UTC").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for spec in [
+            "weekdays 08:00-18:00 UTC",
+            "daily 22:00-06:00 America/New_York notice",
+            "mon,wed,fri 10:00-12:00 Asia/Tokyo",
+        ] {
+            let w = DeliveryWindow::parse(spec, "UTC").unwrap();
+            assert_eq!(w.to_string(), spec);
+            assert_eq!(DeliveryWindow::parse(&w.to_string(), "UTC").unwrap(), w);
+        }
+    }
+
+    #[test]
+    fn test_window_boundaries() {
+        let w = DeliveryWindow::parse("weekdays 08:00-18:00", "UTC").unwrap();
+        // 2025-03-03 is a Monday
+        assert!(!w.is_open_at(utc(2025, 3, 3, 7, 59)));
+        assert!(w.is_open_at(utc(2025, 3, 3, 8, 0)));
+        assert!(w.is_open_at(utc(2025, 3, 3, 17, 59)));
+        assert!(!w.is_open_at(utc(2025, 3, 3, 18, 0)));
+        // Saturday is outside the window entirely
+        assert!(!w.is_open_at(utc(2025, 3, 8, 12, 0)));
+    }
+
+    #[test]
+    fn test_overnight_window_belongs_to_start_day() {
+        let w = DeliveryWindow::parse("fri 22:00-06:00", "UTC").unwrap();
+        // Friday night and early Saturday are open
+        assert!(w.is_open_at(utc(2025, 3, 7, 23, 0)));
+        assert!(w.is_open_at(utc(2025, 3, 8, 5, 59)));
+        assert!(!w.is_open_at(utc(2025, 3, 8, 6, 0)));
+        // Early Friday belongs to Thursday's (excluded) window
+        assert!(!w.is_open_at(utc(2025, 3, 7, 3, 0)));
+    }
+
+    #[test]
+    fn test_window_uses_channel_timezone_across_dst() {
+        let w = DeliveryWindow::parse("weekdays 08:00-18:00 America/New_York", "UTC").unwrap();
+        // Before DST (EST, UTC-5): 12:30 UTC is 07:30 local -> closed
+        assert!(!w.is_open_at(utc(2025, 3, 7, 12, 30)));
+        // After DST starts on 2025-03-09 (EDT, UTC-4): 12:30 UTC is 08:30 local -> open
+        assert!(w.is_open_at(utc(2025, 3, 10, 12, 30)));
+        // 22:30 UTC is 18:30 EDT -> closed, although it would be 17:30 EST
+        assert!(!w.is_open_at(utc(2025, 3, 10, 22, 30)));
+    }
+
+    #[test]
+    fn test_should_hold_bypasses() {
+        let w = DeliveryWindow::parse("weekdays 08:00-18:00", "UTC").unwrap();
+        let saturday = utc(2025, 3, 8, 12, 0);
+        assert!(should_hold(Some(&w), DeliveryPriority::Normal, saturday));
+        assert!(!should_hold(Some(&w), DeliveryPriority::Urgent, saturday));
+        assert!(!should_hold(None, DeliveryPriority::Normal, saturday));
+    }
+}
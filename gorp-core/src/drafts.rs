@@ -0,0 +1,127 @@
+// ABOUTME: Send-guard drafts: long prompts from opted-in users wait for !send instead of going straight out.
+// ABOUTME: Holds the draft type, append spacing and the hold decision; storage lives in SessionStore.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Reply posted when a message is held as a draft
+pub const QUEUED_NOTICE: &str =
+    "📝 Queued — reply `!send` to submit, `!append <text>` to add more, or `!discard`";
+
+/// A prompt held for one user in one channel until they send or discard it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Draft {
+    pub platform_id: String,
+    pub channel_id: String,
+    pub user_id: String,
+    pub body: String,
+    pub created_at: String,
+    /// Last time the draft was created or appended to; expiry counts from here
+    pub updated_at: String,
+}
+
+impl Draft {
+    /// Whether the draft has gone untouched for longer than `ttl`
+    pub fn is_expired(&self, ttl: Duration, now: DateTime<Utc>) -> bool {
+        match DateTime::parse_from_rfc3339(&self.updated_at) {
+            Ok(updated) => now - updated.with_timezone(&Utc) >= ttl,
+            // An unreadable timestamp would otherwise never expire
+            Err(_) => true,
+        }
+    }
+}
+
+/// Whether a message should be held rather than sent to the agent.
+/// Only messages longer than the threshold from opted-in users are held.
+pub fn should_hold(guard_enabled: bool, body: &str, threshold_chars: usize) -> bool {
+    guard_enabled && body.chars().count() > threshold_chars
+}
+
+/// Join more text onto a draft.
+///
+/// Multi-line pieces are separated by a blank line so paragraphs stay apart;
+/// single-line pieces are joined with one space.
+pub fn append_text(draft: &str, more: &str) -> String {
+    let draft = draft.trim_end();
+    let more = more.trim();
+    if more.is_empty() {
+        return draft.to_string();
+    }
+    if draft.is_empty() {
+        return more.to_string();
+    }
+    let separator = if draft.contains('\n') || more.contains('\n') {
+        "\n\n"
+    } else {
+        " "
+    };
+    format!("{}{}{}", draft, separator, more)
+}
+
+/// Reminder posted when a draft expires; it carries the text so nothing is lost
+pub fn expiry_notice(draft: &Draft) -> String {
+    format!(
+        "⌛ Your queued draft expired without being sent, so I've discarded it. Here it is in case you still need it:\n\n{}",
+        draft.body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft_updated_at(updated_at: &str) -> Draft {
+        Draft {
+            platform_id: "matrix".to_string(),
+            channel_id: "!room:example.com".to_string(),
+            user_id: "@ana:example.com".to_string(),
+            body: "half a thought".to_string(),
+            created_at: updated_at.to_string(),
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_should_hold_only_long_messages_from_opted_in_users() {
+        assert!(should_hold(true, &"x".repeat(11), 10));
+        assert!(!should_hold(true, &"x".repeat(10), 10));
+        assert!(!should_hold(false, &"x".repeat(500), 10));
+        // Counted in characters, not bytes
+        assert!(!should_hold(true, &"é".repeat(10), 10));
+    }
+
+    #[test]
+    fn test_append_spacing() {
+        assert_eq!(
+            append_text("first part", "second part"),
+            "first part second part"
+        );
+        assert_eq!(
+            append_text("para one\nstill one", "para two"),
+            "para one\nstill one\n\npara two"
+        );
+        assert_eq!(
+            append_text("one  \n", "  \ntwo\nthree"),
+            "one\n\ntwo\nthree"
+        );
+        assert_eq!(append_text("draft", "   "), "draft");
+        assert_eq!(append_text("", "fresh"), "fresh");
+    }
+
+    #[test]
+    fn test_expiry_counts_from_last_update() {
+        let now = DateTime::parse_from_rfc3339("2025-03-03T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ttl = Duration::minutes(60);
+        assert!(!draft_updated_at("2025-03-03T11:30:00Z").is_expired(ttl, now));
+        assert!(draft_updated_at("2025-03-03T11:00:00Z").is_expired(ttl, now));
+        assert!(draft_updated_at("not a timestamp").is_expired(ttl, now));
+    }
+
+    #[test]
+    fn test_expiry_notice_keeps_text() {
+        let draft = draft_updated_at("2025-03-03T11:00:00Z");
+        assert!(expiry_notice(&draft).ends_with("half a thought"));
+    }
+}
@@ -0,0 +1,175 @@
+// ABOUTME: Tracks recent prompts so edited chat messages can be applied as corrections.
+// ABOUTME: Edits rewrite prompts not yet sent to the agent, or become follow-ups if already answered.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Prefix for follow-up prompts created from an edit to an answered message
+pub const CORRECTION_PREFIX: &str = "Correction to previous message:";
+
+/// What to do with an edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// The original was still waiting to be sent; its prompt now uses the edited text
+    Swapped,
+    /// The original was already sent within the correction window; send this prompt next
+    FollowUp(String),
+    /// The original is unknown or older than the correction window
+    Ignored,
+}
+
+#[derive(Debug)]
+enum Status {
+    /// Received but not yet sent to the agent; holds the current prompt text
+    Queued(String),
+    /// Sent to the agent
+    Sent,
+}
+
+#[derive(Debug)]
+struct Entry {
+    status: Status,
+    /// When the entry was queued, or when it was sent
+    at: Instant,
+}
+
+/// Remembers prompts by (platform_id, event_id) for the length of the correction window
+#[derive(Debug)]
+pub struct EditTracker {
+    entries: Mutex<HashMap<(String, String), Entry>>,
+    window: Duration,
+}
+
+impl EditTracker {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            window,
+        }
+    }
+
+    /// Record a prompt that has been received but not yet sent to the agent
+    pub fn queue(&self, platform_id: &str, event_id: &str, body: &str) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        self.prune(&mut entries);
+        entries.insert(
+            (platform_id.to_string(), event_id.to_string()),
+            Entry {
+                status: Status::Queued(body.to_string()),
+                at: Instant::now(),
+            },
+        );
+    }
+
+    /// Mark a prompt as sent and return its current text.
+    /// Returns None if the prompt was never queued.
+    pub fn take_for_send(&self, platform_id: &str, event_id: &str) -> Option<String> {
+        let mut entries = self.entries.lock().ok()?;
+        let entry = entries.get_mut(&(platform_id.to_string(), event_id.to_string()))?;
+        let queued = match std::mem::replace(&mut entry.status, Status::Sent) {
+            Status::Queued(body) => Some(body),
+            Status::Sent => None,
+        };
+        entry.at = Instant::now();
+        queued
+    }
+
+    /// Apply an edit of `original_event_id` whose new text is `new_body`
+    pub fn apply_edit(
+        &self,
+        platform_id: &str,
+        original_event_id: &str,
+        new_body: &str,
+    ) -> EditOutcome {
+        let Ok(mut entries) = self.entries.lock() else {
+            return EditOutcome::Ignored;
+        };
+        self.prune(&mut entries);
+
+        let key = (platform_id.to_string(), original_event_id.to_string());
+        match entries.get_mut(&key) {
+            Some(Entry {
+                status: Status::Queued(body),
+                ..
+            }) => {
+                *body = new_body.to_string();
+                EditOutcome::Swapped
+            }
+            Some(Entry {
+                status: Status::Sent,
+                ..
+            }) => EditOutcome::FollowUp(format!("{}\n\n{}", CORRECTION_PREFIX, new_body)),
+            None => EditOutcome::Ignored,
+        }
+    }
+
+    /// Drop entries older than the correction window
+    fn prune(&self, entries: &mut HashMap<(String, String), Entry>) {
+        let window = self.window;
+        entries.retain(|_, entry| entry.at.elapsed() <= window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_before_send_swaps_prompt() {
+        let tracker = EditTracker::new(Duration::from_secs(600));
+        tracker.queue("matrix", "$a", "deploy to stagign");
+
+        assert_eq!(
+            tracker.apply_edit("matrix", "$a", "deploy to staging"),
+            EditOutcome::Swapped
+        );
+        assert_eq!(
+            tracker.take_for_send("matrix", "$a").as_deref(),
+            Some("deploy to staging")
+        );
+    }
+
+    #[test]
+    fn test_edit_after_send_becomes_follow_up() {
+        let tracker = EditTracker::new(Duration::from_secs(600));
+        tracker.queue("matrix", "$a", "what is 2+2?");
+        tracker.take_for_send("matrix", "$a");
+
+        assert_eq!(
+            tracker.apply_edit("matrix", "$a", "what is 2+3?"),
+            EditOutcome::FollowUp("Correction to previous message:\n\nwhat is 2+3?".to_string())
+        );
+        // Already sent, so there is nothing left to take
+        assert_eq!(tracker.take_for_send("matrix", "$a"), None);
+    }
+
+    #[test]
+    fn test_edit_of_unknown_or_old_message_is_ignored() {
+        let tracker = EditTracker::new(Duration::from_millis(20));
+        assert_eq!(
+            tracker.apply_edit("matrix", "$missing", "text"),
+            EditOutcome::Ignored
+        );
+
+        tracker.queue("matrix", "$old", "text");
+        tracker.take_for_send("matrix", "$old");
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(
+            tracker.apply_edit("matrix", "$old", "edited"),
+            EditOutcome::Ignored
+        );
+    }
+
+    #[test]
+    fn test_platforms_are_tracked_separately() {
+        let tracker = EditTracker::new(Duration::from_secs(600));
+        tracker.queue("slack", "1", "hello");
+        assert_eq!(
+            tracker.apply_edit("telegram", "1", "hi"),
+            EditOutcome::Ignored
+        );
+    }
+}
@@ -0,0 +1,437 @@
+// ABOUTME: Per-channel git repo checked out into the workspace, read from `.gorp/git_repo`.
+// ABOUTME: Builds the clone/pull commands and runs them; failures are logged, never fatal.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a clone or pull may run before it is given up on
+pub const SYNC_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The repo a channel's workspace is seeded from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitRepoSetting {
+    /// Anything `git clone` accepts (https or ssh URL, local path)
+    pub url: String,
+    /// Branch to check out; the remote's default branch when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Checkout directory relative to the workspace; derived from the URL when unset.
+    /// Settings whose checkout would land outside the workspace are ignored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// Pull every time the channel's agent session starts, not just on first checkout
+    #[serde(default)]
+    pub pull_on_session_start: bool,
+    /// Private key for ssh URLs (e.g. "~/.ssh/id_ed25519")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key_path: Option<String>,
+}
+
+/// A git invocation: arguments after `git`, plus extra environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitCommand {
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl GitRepoSetting {
+    /// Checkout directory name: `dir` if set, else the URL's last path segment without `.git`
+    pub fn checkout_dir(&self) -> String {
+        if let Some(dir) = self.dir.as_deref().filter(|d| !d.trim().is_empty()) {
+            return dir.trim().to_string();
+        }
+        let name = self
+            .url
+            .trim_end_matches('/')
+            .rsplit(['/', ':'])
+            .next()
+            .unwrap_or_default();
+        let name = name.strip_suffix(".git").unwrap_or(name);
+        if name.is_empty() {
+            "repo".to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Absolute checkout path inside the channel workspace
+    pub fn checkout_path(&self, channel_dir: &str) -> PathBuf {
+        Path::new(channel_dir).join(self.checkout_dir())
+    }
+
+    /// Whether a session starting now should clone or pull
+    pub fn syncs_on_session_start(&self, channel_dir: &str) -> bool {
+        self.pull_on_session_start || !self.checkout_path(channel_dir).join(".git").exists()
+    }
+
+    /// Whether the checkout directory stays inside the workspace
+    pub fn checks_out_inside_workspace(&self) -> bool {
+        stays_inside(&self.checkout_dir())
+    }
+
+    fn env(&self) -> Vec<(String, String)> {
+        let mut env = vec![("GIT_TERMINAL_PROMPT".to_string(), "0".to_string())];
+        if let Some(key) = &self.ssh_key_path {
+            // Git runs this through the shell, so the key path is quoted for it
+            env.push((
+                "GIT_SSH_COMMAND".to_string(),
+                format!(
+                    "ssh -i {} -o IdentitiesOnly=yes -o BatchMode=yes",
+                    shell_quote(&crate::config::expand_tilde(key))
+                ),
+            ));
+        }
+        env
+    }
+
+    /// `git clone [--branch <b> --single-branch] -- <url> <dest>`
+    pub fn clone_command(&self, dest: &Path) -> GitCommand {
+        let mut args = vec!["clone".to_string()];
+        if let Some(branch) = &self.branch {
+            args.extend([
+                "--branch".to_string(),
+                branch.clone(),
+                "--single-branch".to_string(),
+            ]);
+        }
+        args.extend([
+            "--".to_string(),
+            self.url.clone(),
+            dest.to_string_lossy().to_string(),
+        ]);
+        GitCommand {
+            args,
+            env: self.env(),
+        }
+    }
+
+    /// `git -C <dest> pull --ff-only [origin <b>]`
+    pub fn pull_command(&self, dest: &Path) -> GitCommand {
+        let mut args = vec![
+            "-C".to_string(),
+            dest.to_string_lossy().to_string(),
+            "pull".to_string(),
+            "--ff-only".to_string(),
+        ];
+        if let Some(branch) = &self.branch {
+            args.extend(["origin".to_string(), branch.clone()]);
+        }
+        GitCommand {
+            args,
+            env: self.env(),
+        }
+    }
+
+    /// Clone when there is no checkout yet, pull otherwise
+    pub fn sync_command(&self, channel_dir: &str) -> GitCommand {
+        let dest = self.checkout_path(channel_dir);
+        if dest.join(".git").exists() {
+            self.pull_command(&dest)
+        } else {
+            self.clone_command(&dest)
+        }
+    }
+}
+
+/// Whether `dir` is a relative path that never climbs out of where it starts
+fn stays_inside(dir: &str) -> bool {
+    let path = Path::new(dir);
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)))
+}
+
+/// `value` in single quotes for a POSIX shell, embedded quotes included
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn setting_path(channel_dir: &str) -> PathBuf {
+    Path::new(channel_dir).join(".gorp").join("git_repo")
+}
+
+/// Read the channel's repo from `.gorp/git_repo`, if one is set. The agent can
+/// write this file, so it gets the same checks as `!repo set`: a file that
+/// doesn't parse or would check out outside the workspace is logged and treated as unset.
+pub fn read_git_repo(channel_dir: &str) -> Option<GitRepoSetting> {
+    let path = setting_path(channel_dir);
+    let content = std::fs::read_to_string(&path).ok()?;
+    match toml::from_str::<GitRepoSetting>(&content) {
+        Ok(setting) if setting.url.trim().is_empty() => None,
+        Ok(setting) if !setting.checks_out_inside_workspace() => {
+            tracing::warn!(
+                file = %path.display(),
+                dir = %setting.checkout_dir(),
+                "Ignoring git repo setting that checks out outside the workspace"
+            );
+            None
+        }
+        Ok(setting) => Some(setting),
+        Err(e) => {
+            tracing::warn!(file = %path.display(), error = %e, "Ignoring unreadable git repo setting");
+            None
+        }
+    }
+}
+
+/// Set the channel's repo, or remove it with None. An existing checkout is left in place.
+pub fn write_git_repo(channel_dir: &str, setting: Option<&GitRepoSetting>) -> Result<()> {
+    let path = setting_path(channel_dir);
+    match setting {
+        Some(setting) => {
+            let gorp_dir = Path::new(channel_dir).join(".gorp");
+            std::fs::create_dir_all(&gorp_dir)
+                .with_context(|| format!("Failed to create {}", gorp_dir.display()))?;
+            let content = toml::to_string(setting).context("Failed to serialize git repo")?;
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))
+        }
+        None => match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Run git to completion. Dropping the future (as the timeout in [`sync`] does) kills it.
+async fn run(command: &GitCommand) -> Result<()> {
+    let output = tokio::process::Command::new("git")
+        .args(&command.args)
+        .envs(command.env.iter().map(|(k, v)| (k, v)))
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .context("Failed to run git")?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let op = command
+        .args
+        .iter()
+        .find(|a| *a == "clone" || *a == "pull")
+        .map_or("", |a| a.as_str());
+    anyhow::bail!(
+        "git {} exited with {}: {}",
+        op,
+        output.status,
+        stderr.trim()
+    )
+}
+
+/// Clone or pull the channel's repo into its workspace, stopping git if it
+/// runs past [`SYNC_TIMEOUT`]
+pub async fn sync(channel_dir: &str, setting: &GitRepoSetting) -> Result<()> {
+    sync_within(channel_dir, setting, SYNC_TIMEOUT).await
+}
+
+async fn sync_within(channel_dir: &str, setting: &GitRepoSetting, limit: Duration) -> Result<()> {
+    anyhow::ensure!(
+        setting.checks_out_inside_workspace(),
+        "Checkout directory {} is outside the workspace",
+        setting.checkout_dir()
+    );
+    let command = setting.sync_command(channel_dir);
+    match tokio::time::timeout(limit, run(&command)).await {
+        Ok(result) => result,
+        Err(_) => anyhow::bail!("git timed out after {}s", limit.as_secs()),
+    }
+}
+
+/// Clone or pull, logging instead of failing: a stale checkout shouldn't stop the agent
+pub async fn sync_logged(channel_name: &str, channel_dir: &str, setting: &GitRepoSetting) {
+    match sync(channel_dir, setting).await {
+        Ok(()) => tracing::info!(
+            channel = %channel_name,
+            url = %setting.url,
+            checkout = %setting.checkout_dir(),
+            "Synced channel git repo"
+        ),
+        Err(e) => tracing::warn!(
+            channel = %channel_name,
+            url = %setting.url,
+            error = %format!("{:#}", e),
+            "Failed to sync channel git repo"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setting(url: &str) -> GitRepoSetting {
+        GitRepoSetting {
+            url: url.to_string(),
+            branch: None,
+            dir: None,
+            pull_on_session_start: false,
+            ssh_key_path: None,
+        }
+    }
+
+    #[test]
+    fn test_checkout_dir_derived_from_url() {
+        assert_eq!(setting("git@github.com:acme/api.git").checkout_dir(), "api");
+        assert_eq!(
+            setting("https://example.com/acme/web/").checkout_dir(),
+            "web"
+        );
+        assert_eq!(setting("git@host:repo.git").checkout_dir(), "repo");
+        let mut named = setting("git@github.com:acme/api.git");
+        named.dir = Some("backend".to_string());
+        assert_eq!(named.checkout_dir(), "backend");
+    }
+
+    #[test]
+    fn test_clone_then_pull_command_construction() {
+        let dir = TempDir::new().unwrap();
+        let channel_dir = dir.path().to_str().unwrap();
+        let mut repo = setting("git@github.com:acme/api.git");
+        repo.branch = Some("main".to_string());
+        repo.ssh_key_path = Some("/keys/deploy".to_string());
+        let dest = dir.path().join("api");
+
+        let clone = repo.sync_command(channel_dir);
+        assert_eq!(
+            clone.args,
+            vec![
+                "clone",
+                "--branch",
+                "main",
+                "--single-branch",
+                "--",
+                "git@github.com:acme/api.git",
+                dest.to_str().unwrap(),
+            ]
+        );
+        assert!(clone.env.contains(&(
+            "GIT_SSH_COMMAND".to_string(),
+            "ssh -i '/keys/deploy' -o IdentitiesOnly=yes -o BatchMode=yes".to_string()
+        )));
+        assert!(clone
+            .env
+            .contains(&("GIT_TERMINAL_PROMPT".to_string(), "0".to_string())));
+        assert!(repo.syncs_on_session_start(channel_dir));
+
+        std::fs::create_dir_all(dest.join(".git")).unwrap();
+        let pull = repo.sync_command(channel_dir);
+        assert_eq!(
+            pull.args,
+            vec![
+                "-C",
+                dest.to_str().unwrap(),
+                "pull",
+                "--ff-only",
+                "origin",
+                "main"
+            ]
+        );
+        assert!(!repo.syncs_on_session_start(channel_dir));
+        repo.pull_on_session_start = true;
+        assert!(repo.syncs_on_session_start(channel_dir));
+    }
+
+    #[test]
+    fn test_settings_checking_out_outside_the_workspace_are_ignored() {
+        let dir = TempDir::new().unwrap();
+        let channel_dir = dir.path().to_str().unwrap();
+        let gorp_dir = dir.path().join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        let written = |content: &str| {
+            std::fs::write(gorp_dir.join("git_repo"), content).unwrap();
+            read_git_repo(channel_dir)
+        };
+
+        assert_eq!(
+            written("url = \"https://example.com/acme/web.git\"\ndir = \"../other-channel\"\n"),
+            None
+        );
+        assert_eq!(
+            written("url = \"https://example.com/acme/web.git\"\ndir = \"/etc\"\n"),
+            None
+        );
+        assert_eq!(written("url = \"https://example.com/acme/..\"\n"), None);
+        assert!(
+            written("url = \"https://example.com/acme/web.git\"\ndir = \"src/web\"\n").is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_refuses_checkouts_outside_the_workspace() {
+        let dir = TempDir::new().unwrap();
+        let mut repo = setting("https://example.com/acme/web.git");
+        repo.dir = Some("../escape".to_string());
+
+        let err = sync(dir.path().to_str().unwrap(), &repo).await.unwrap_err();
+        assert!(err.to_string().contains("outside the workspace"));
+    }
+
+    #[test]
+    fn test_ssh_key_path_is_quoted_for_the_shell() {
+        let mut repo = setting("git@github.com:acme/api.git");
+        repo.ssh_key_path = Some("/keys/it's'; touch /tmp/pwned; '".to_string());
+        let command = repo.clone_command(Path::new("/tmp/api"));
+        let (_, ssh) = command
+            .env
+            .iter()
+            .find(|(k, _)| k == "GIT_SSH_COMMAND")
+            .unwrap();
+
+        // The shell sees the whole path as the one argument after -i
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!(
+                "set -- {}; printf '%s' \"$3\"",
+                ssh.trim_end_matches(" -o IdentitiesOnly=yes -o BatchMode=yes")
+            ))
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "/keys/it's'; touch /tmp/pwned; '"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_gives_up_on_a_stuck_git() {
+        // Accepts connections but never answers, so the clone hangs
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/acme/web.git", listener.local_addr().unwrap());
+        let dir = TempDir::new().unwrap();
+
+        let started = std::time::Instant::now();
+        let err = sync_within(
+            dir.path().to_str().unwrap(),
+            &setting(&url),
+            Duration::from_millis(500),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_setting_round_trips_through_gorp_dir() {
+        let dir = TempDir::new().unwrap();
+        let channel_dir = dir.path().to_str().unwrap();
+        assert_eq!(read_git_repo(channel_dir), None);
+
+        let mut repo = setting("https://example.com/acme/web.git");
+        repo.pull_on_session_start = true;
+        write_git_repo(channel_dir, Some(&repo)).unwrap();
+        assert_eq!(read_git_repo(channel_dir), Some(repo));
+
+        write_git_repo(channel_dir, None).unwrap();
+        assert_eq!(read_git_repo(channel_dir), None);
+        write_git_repo(channel_dir, None).unwrap();
+    }
+}
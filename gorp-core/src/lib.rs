@@ -1,28 +1,52 @@
 // ABOUTME: Platform-agnostic chat orchestration for AI agents
 // ABOUTME: Provides traits and core logic for any chat interface
 
+pub mod command_catalog;
 pub mod commands;
 pub mod config;
+pub mod confirmations;
+pub mod connection;
+pub mod dedup;
+pub mod delivery;
 pub mod dispatch_events;
+pub mod drafts;
+pub mod edits;
+pub mod git_seed;
+pub mod logging;
 pub mod metrics;
 pub mod orchestrator;
 pub mod paths;
+pub mod preprocess;
+pub mod rate_limit;
+pub mod reconnect;
+pub mod redactions;
+pub mod relocate;
+pub mod rich_response;
+pub mod runtime_mode;
 pub mod scheduler;
 pub mod session;
+pub mod templates;
 pub mod traits;
+pub mod transcription;
+pub mod typing;
+pub mod usage;
 pub mod utils;
 pub mod warm_session;
+pub mod webhook_batch;
 
+pub use connection::{ConnectionEvent, ConnectionTracker};
 pub use dispatch_events::WorkerEvent;
 
 // Re-export core traits for convenient access
 pub use traits::{
+    ActionButtons,
     // Optional Capabilities
     AttachmentHandler,
     // Data Types
     AttachmentInfo,
     ChannelCreator,
     ChannelManager,
+    ChannelTyping,
     // Tier 2: Chat Platform
     ChatChannel,
     // Deprecated (backwards compatibility)
@@ -30,7 +54,9 @@ pub use traits::{
     ChatPlatform,
     ChatRoom,
     ChatUser,
+    ConfirmationProvider,
     EncryptedPlatform,
+    EphemeralSender,
     // Tier 1: Messaging Platform
     EventStream,
     IncomingMessage,
@@ -40,6 +66,9 @@ pub use traits::{
     MessagingPlatform,
     // Health & Lifecycle
     PlatformConnectionState,
+    Presence,
+    PresenceProvider,
+    ReceiptSender,
     // Extension Traits (optional platform capabilities)
     RichFormatter,
     SlashCommandDef,
@@ -47,6 +76,7 @@ pub use traits::{
     SlashCommandProvider,
     ThreadedPlatform,
     TypingIndicator,
+    WebhookIngress,
     WorkspaceInfo,
 };
 
@@ -0,0 +1,128 @@
+// ABOUTME: Content policy for logs: how much of a user's prompt or a response may be logged.
+// ABOUTME: Log statements pass user content through loggable_content instead of logging it raw.
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::config::ContentPolicy;
+
+/// Characters kept by the `preview` policy
+pub const PREVIEW_CHARS: usize = 50;
+
+static POLICY: AtomicU8 = AtomicU8::new(ContentPolicy::Preview as u8);
+
+/// Per-process key for `hashed`; the same text hashes alike within one run only
+static HASH_KEY: OnceLock<RandomState> = OnceLock::new();
+
+/// Apply `[logging] content_policy` for the rest of the process
+pub fn set_content_policy(policy: ContentPolicy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+pub fn content_policy() -> ContentPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        p if p == ContentPolicy::Full as u8 => ContentPolicy::Full,
+        p if p == ContentPolicy::Hashed as u8 => ContentPolicy::Hashed,
+        p if p == ContentPolicy::None as u8 => ContentPolicy::None,
+        _ => ContentPolicy::Preview,
+    }
+}
+
+/// User content as it may appear in a log line under some policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoggableContent {
+    Full(String),
+    Preview { text: String, truncated: bool },
+    Hashed { hash: String, chars: usize },
+    Omitted { chars: usize },
+}
+
+impl fmt::Display for LoggableContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(text) => write!(f, "{}", text),
+            Self::Preview { text, truncated } => {
+                write!(f, "{}{}", text, if *truncated { "…" } else { "" })
+            }
+            Self::Hashed { hash, chars } => write!(f, "hash:{} ({} chars)", hash, chars),
+            Self::Omitted { chars } => write!(f, "<{} chars omitted>", chars),
+        }
+    }
+}
+
+/// `content` in the form the configured policy allows in logs
+pub fn loggable_content(content: &str) -> LoggableContent {
+    loggable_content_with(content_policy(), content)
+}
+
+/// `content` in the form `policy` allows in logs
+pub fn loggable_content_with(policy: ContentPolicy, content: &str) -> LoggableContent {
+    match policy {
+        ContentPolicy::Full => LoggableContent::Full(content.to_string()),
+        ContentPolicy::Preview => {
+            let mut chars = content.chars();
+            let text: String = chars.by_ref().take(PREVIEW_CHARS).collect();
+            LoggableContent::Preview {
+                text,
+                truncated: chars.next().is_some(),
+            }
+        }
+        ContentPolicy::Hashed => {
+            let key = HASH_KEY.get_or_init(RandomState::new);
+            LoggableContent::Hashed {
+                hash: format!("{:016x}", key.hash_one(content)),
+                chars: content.chars().count(),
+            }
+        }
+        ContentPolicy::None => LoggableContent::Omitted {
+            chars: content.chars().count(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_truncates_on_char_boundaries() {
+        let long = "é".repeat(PREVIEW_CHARS + 10);
+        let LoggableContent::Preview { text, truncated } =
+            loggable_content_with(ContentPolicy::Preview, &long)
+        else {
+            panic!("expected a preview");
+        };
+        assert_eq!(text.chars().count(), PREVIEW_CHARS);
+        assert!(truncated);
+
+        assert_eq!(
+            loggable_content_with(ContentPolicy::Preview, "short").to_string(),
+            "short"
+        );
+    }
+
+    #[test]
+    fn test_hashed_is_stable_within_a_run_and_hides_the_text() {
+        let a = loggable_content_with(ContentPolicy::Hashed, "deploy key is hunter2");
+        let b = loggable_content_with(ContentPolicy::Hashed, "deploy key is hunter2");
+        let c = loggable_content_with(ContentPolicy::Hashed, "something else");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(!a.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_none_keeps_only_the_length() {
+        assert_eq!(
+            loggable_content_with(ContentPolicy::None, "hunter2").to_string(),
+            "<7 chars omitted>"
+        );
+        assert_eq!(
+            loggable_content_with(ContentPolicy::Full, "hunter2").to_string(),
+            "hunter2"
+        );
+    }
+}
@@ -1,10 +1,15 @@
 // ABOUTME: Prometheus metrics definitions and initialization
 // ABOUTME: Exposes counters, gauges, and histograms for monitoring gorp
 
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 
+use crate::usage::InvocationOrigin;
+
 /// Initialize the Prometheus metrics recorder and return the handle for the /metrics endpoint
 pub fn init_metrics() -> Result<PrometheusHandle> {
     let builder = PrometheusBuilder::new();
@@ -74,6 +79,22 @@ fn describe_counters() {
         "gorp_claude_cost_cents_total",
         "Total cost in cents (USD) for Claude API usage"
     );
+    describe_counter!(
+        "gorp_responses_truncated_total",
+        "Total number of responses cut down to backend.max_response_chars"
+    );
+    describe_counter!(
+        "gorp_rate_limited_total",
+        "Total number of messages rejected by the per-user rate limiter"
+    );
+    describe_counter!(
+        "gorp_message_queue_full_total",
+        "Total number of incoming messages that found the handler queue full"
+    );
+    describe_counter!(
+        "gorp_platform_reconnects_total",
+        "Total number of platform connections re-established after dropping"
+    );
 }
 
 fn describe_gauges() {
@@ -82,6 +103,10 @@ fn describe_gauges() {
         "gorp_schedules_active",
         "Current number of active schedules"
     );
+    describe_gauge!(
+        "gorp_message_queue_depth",
+        "Current number of incoming messages waiting for the handler"
+    );
 }
 
 fn describe_histograms() {
@@ -103,6 +128,45 @@ fn describe_histograms() {
     );
 }
 
+// ============================================================================
+// Snapshots to disk
+// ============================================================================
+
+/// Write the registry to `path` in Prometheus text format. The file is replaced
+/// in one step, so a reader never sees half a snapshot.
+pub fn write_snapshot(handle: &PrometheusHandle, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, handle.render())
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// Write a snapshot to `path` straight away and then every `interval`, for as
+/// long as the process runs
+pub fn spawn_snapshot_task(
+    handle: PrometheusHandle,
+    path: PathBuf,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = write_snapshot(&handle, &path) {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to write metrics snapshot");
+            }
+        }
+    })
+}
+
 // ============================================================================
 // Helper functions for recording metrics
 // ============================================================================
@@ -118,8 +182,13 @@ pub fn record_message_sent() {
 }
 
 /// Record a Claude CLI invocation
-pub fn record_claude_invocation(source: &str) {
-    counter!("gorp_claude_invocations_total", "source" => source.to_string()).increment(1);
+pub fn record_claude_invocation(source: &str, origin: InvocationOrigin) {
+    counter!(
+        "gorp_claude_invocations_total",
+        "source" => source.to_string(),
+        "origin" => origin.as_str()
+    )
+    .increment(1);
 }
 
 /// Record a webhook request
@@ -152,6 +221,31 @@ pub fn record_schedule_executed() {
     counter!("gorp_schedules_executed_total").increment(1);
 }
 
+/// Record a response cut down to the configured maximum length
+pub fn record_response_truncated() {
+    counter!("gorp_responses_truncated_total").increment(1);
+}
+
+/// Record a message rejected by the per-user rate limiter
+pub fn record_rate_limited(platform_id: &str) {
+    counter!("gorp_rate_limited_total", "platform" => platform_id.to_string()).increment(1);
+}
+
+/// Record a platform connection coming back after it dropped
+pub fn record_reconnect(platform_id: &str) {
+    counter!("gorp_platform_reconnects_total", "platform" => platform_id.to_string()).increment(1);
+}
+
+/// Record an incoming message that found the handler queue full
+pub fn record_queue_full() {
+    counter!("gorp_message_queue_full_total").increment(1);
+}
+
+/// Update the handler queue depth gauge
+pub fn set_message_queue_depth(depth: usize) {
+    gauge!("gorp_message_queue_depth").set(depth as f64);
+}
+
 /// Update the active channels gauge
 pub fn set_active_channels(count: u64) {
     gauge!("gorp_channels_active").set(count as f64);
@@ -203,14 +297,64 @@ pub fn record_webhook_duration(duration_secs: f64) {
 }
 
 /// Record Claude token usage
-pub fn record_claude_tokens(input: u64, output: u64, cache_read: u64, cache_creation: u64) {
-    counter!("gorp_claude_input_tokens_total").increment(input);
-    counter!("gorp_claude_output_tokens_total").increment(output);
-    counter!("gorp_claude_cache_read_tokens_total").increment(cache_read);
-    counter!("gorp_claude_cache_creation_tokens_total").increment(cache_creation);
+pub fn record_claude_tokens(
+    input: u64,
+    output: u64,
+    cache_read: u64,
+    cache_creation: u64,
+    origin: InvocationOrigin,
+) {
+    let origin = origin.as_str();
+    counter!("gorp_claude_input_tokens_total", "origin" => origin).increment(input);
+    counter!("gorp_claude_output_tokens_total", "origin" => origin).increment(output);
+    counter!("gorp_claude_cache_read_tokens_total", "origin" => origin).increment(cache_read);
+    counter!("gorp_claude_cache_creation_tokens_total", "origin" => origin)
+        .increment(cache_creation);
 }
 
 /// Record Claude cost in cents (we use cents to avoid floating point counter issues)
-pub fn record_claude_cost_cents(cost_cents: u64) {
-    counter!("gorp_claude_cost_cents_total").increment(cost_cents);
+pub fn record_claude_cost_cents(cost_cents: u64, origin: InvocationOrigin) {
+    counter!("gorp_claude_cost_cents_total", "origin" => origin.as_str()).increment(cost_cents);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_snapshot_task_writes_registry_to_file() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            describe_counters();
+            describe_gauges();
+            record_message_sent();
+            record_command("status");
+            set_active_channels(3);
+        });
+
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("snapshots").join("metrics.prom");
+        let task = spawn_snapshot_task(handle, path.clone(), Duration::from_millis(10));
+
+        let mut waited = Duration::ZERO;
+        while !path.exists() && waited < Duration::from_secs(5) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            waited += Duration::from_millis(10);
+        }
+        task.abort();
+
+        let snapshot = std::fs::read_to_string(&path).expect("snapshot file written");
+        assert!(snapshot.contains("gorp_messages_sent_total 1"));
+        assert!(snapshot.contains("gorp_commands_total{command=\"status\"} 1"));
+        assert!(snapshot.contains("gorp_channels_active 3"));
+        assert!(snapshot.contains("# HELP gorp_messages_sent_total"));
+        assert!(!tmp
+            .path()
+            .join("snapshots")
+            .join("metrics.prom.tmp")
+            .exists());
+    }
 }
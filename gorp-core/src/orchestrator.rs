@@ -2,10 +2,11 @@
 // ABOUTME: Platform-agnostic message handling using ChatInterface trait
 
 use crate::{
-    commands::{parse_message, Command, ParseResult},
+    commands::{parse_message, Command, ParseResult, DEFAULT_COMMAND_PREFIX},
     metrics,
     session::{Channel, SessionStore},
     traits::{ChatInterface, ChatRoom, IncomingMessage, MessageContent},
+    usage::InvocationOrigin,
     utils::{chunk_message, markdown_to_html, strip_function_calls, MAX_CHUNK_SIZE},
     warm_session::{
         prepare_session_async, send_prompt_with_handle, SharedWarmSessionManager, WarmSessionHandle,
@@ -154,7 +155,10 @@ impl<I: ChatInterface> Orchestrator<I> {
         };
 
         // Parse the message
-        let parsed = parse_message(&msg.body, &self.config.bot_prefix);
+        let parsed = parse_message(
+            &msg.body,
+            &[self.config.bot_prefix.as_str(), DEFAULT_COMMAND_PREFIX],
+        );
 
         match parsed {
             ParseResult::Ignore => Ok(HandleResult::Ignored),
@@ -221,7 +225,7 @@ impl<I: ChatInterface> Orchestrator<I> {
         room.set_typing(true).await?;
 
         // Record the claude invocation
-        metrics::record_claude_invocation("orchestrator");
+        metrics::record_claude_invocation("orchestrator", InvocationOrigin::User);
 
         // Prepare session (creates or resumes)
         let (session_handle, session_id, is_new_session) =
@@ -253,7 +257,14 @@ impl<I: ChatInterface> Orchestrator<I> {
             "Sending prompt to agent"
         );
 
-        let mut event_rx = match send_prompt_with_handle(&session_handle, &session_id, body).await {
+        let mut event_rx = match send_prompt_with_handle(
+            &session_handle,
+            &session_id,
+            body,
+            InvocationOrigin::User,
+        )
+        .await
+        {
             Ok(rx) => rx,
             Err(e) => {
                 room.set_typing(false).await?;
@@ -346,6 +357,14 @@ impl<I: ChatInterface> Orchestrator<I> {
                         full_response = text;
                     }
 
+                    if let Err(e) = self.session_store.record_usage(
+                        &channel.channel_name,
+                        InvocationOrigin::User,
+                        usage.as_ref(),
+                    ) {
+                        tracing::warn!(error = %e, "Failed to record channel usage");
+                    }
+
                     // Record usage metrics if available
                     if let Some(usage) = usage {
                         metrics::record_claude_tokens(
@@ -353,9 +372,13 @@ impl<I: ChatInterface> Orchestrator<I> {
                             usage.output_tokens,
                             usage.cache_read_tokens.unwrap_or(0),
                             usage.cache_write_tokens.unwrap_or(0),
+                            InvocationOrigin::User,
                         );
                         if let Some(cost) = usage.cost_usd {
-                            metrics::record_claude_cost_cents((cost * 100.0) as u64);
+                            metrics::record_claude_cost_cents(
+                                (cost * 100.0) as u64,
+                                InvocationOrigin::User,
+                            );
                         }
                     }
 
@@ -0,0 +1,572 @@
+// ABOUTME: Prompt preprocessing: chainable steps that rewrite a prompt before it reaches the agent.
+// ABOUTME: Includes the URL-fetch step that appends the readable text of pages linked in the prompt.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use regex::Regex;
+
+use crate::config::{PreprocessConfig, UrlFetchConfig};
+use crate::session::Channel;
+use crate::warm_session::is_network_allowed;
+
+/// Redirects followed per linked page, each checked like the link itself
+const MAX_REDIRECTS: usize = 5;
+
+/// One step of prompt preprocessing
+#[async_trait]
+pub trait PromptPreprocessor: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &str;
+
+    /// Rewrite `body` for `channel`. An error leaves the prompt as it was before this step.
+    async fn process(&self, body: String, channel: &Channel) -> Result<String>;
+}
+
+/// Preprocessors applied in order, each seeing the previous one's output
+#[derive(Clone, Default)]
+pub struct PreprocessPipeline {
+    steps: Vec<Arc<dyn PromptPreprocessor>>,
+}
+
+impl PreprocessPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The pipeline for the `[preprocess]` config section; steps that are off are left out
+    pub fn from_config(config: &PreprocessConfig) -> Result<Self> {
+        let mut pipeline = Self::new();
+        if config.url_fetch.enabled {
+            let fetcher = HttpPageFetcher::new(&config.url_fetch)?;
+            pipeline = pipeline.with(UrlFetchPreprocessor::new(
+                Arc::new(fetcher),
+                &config.url_fetch,
+            ));
+        }
+        Ok(pipeline)
+    }
+
+    /// Add a step to the end of the pipeline
+    pub fn with(mut self, step: impl PromptPreprocessor + 'static) -> Self {
+        self.steps.push(Arc::new(step));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Names of the steps, in the order they run
+    pub fn names(&self) -> Vec<String> {
+        self.steps.iter().map(|s| s.name().to_string()).collect()
+    }
+
+    /// Run every step. A failing step is logged and skipped, so the prompt
+    /// always goes out, at worst exactly as it came in.
+    pub async fn run(&self, body: String, channel: &Channel) -> String {
+        let mut body = body;
+        for step in &self.steps {
+            match step.process(body.clone(), channel).await {
+                Ok(processed) => body = processed,
+                Err(e) => {
+                    tracing::warn!(
+                        preprocessor = step.name(),
+                        channel = %channel.channel_name,
+                        error = %e,
+                        "Prompt preprocessor failed, passing the prompt through"
+                    );
+                }
+            }
+        }
+        body
+    }
+}
+
+/// Fetches the readable text of a web page
+#[async_trait]
+pub trait PageFetcher: Send + Sync {
+    async fn fetch(&self, url: &str) -> Result<String>;
+}
+
+/// PageFetcher over HTTP, reading at most `max_bytes` of each page. Only public
+/// addresses are fetched, on the first request and on every redirect.
+pub struct HttpPageFetcher {
+    client: reqwest::Client,
+    guard: AddressGuard,
+    max_bytes: usize,
+}
+
+impl HttpPageFetcher {
+    pub fn new(config: &UrlFetchConfig) -> Result<Self> {
+        Self::with_guard(config, AddressGuard::default())
+    }
+
+    fn with_guard(config: &UrlFetchConfig, guard: AddressGuard) -> Result<Self> {
+        let redirect_guard = guard.clone();
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(concat!("gorp/", env!("CARGO_PKG_VERSION")))
+            // Hostnames are checked as they resolve, so a name can't pass the
+            // check and then connect somewhere else. A proxy would resolve them
+            // out of sight, so none is used.
+            .no_proxy()
+            .dns_resolver(Arc::new(guard.clone()))
+            .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    return attempt.error(format!("more than {} redirects", MAX_REDIRECTS));
+                }
+                match redirect_guard.check_url(attempt.url()) {
+                    Ok(()) => attempt.follow(),
+                    Err(e) => attempt.error(e),
+                }
+            }))
+            .build()
+            .context("Failed to build URL fetch HTTP client")?;
+        Ok(Self {
+            client,
+            guard,
+            max_bytes: config.max_bytes,
+        })
+    }
+}
+
+#[async_trait]
+impl PageFetcher for HttpPageFetcher {
+    async fn fetch(&self, url: &str) -> Result<String> {
+        let parsed = reqwest::Url::parse(url).with_context(|| format!("Invalid URL {}", url))?;
+        self.guard
+            .check_url(&parsed)
+            .map_err(|e| anyhow::anyhow!("Refusing to fetch {}: {}", url, e))?;
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Failed to fetch {}", url))?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let is_html = content_type.contains("html");
+        if !content_type.is_empty() && !is_html && !content_type.starts_with("text/") {
+            anyhow::bail!("{} is {}, not text", url, content_type);
+        }
+
+        // Pages can be huge; stop reading at the cap and use what arrived
+        let mut data = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .with_context(|| format!("Failed to read {}", url))?
+        {
+            let room = self.max_bytes.saturating_sub(data.len());
+            data.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            if data.len() >= self.max_bytes {
+                break;
+            }
+        }
+
+        let text = String::from_utf8_lossy(&data);
+        Ok(if is_html {
+            html_to_text(&text)
+        } else {
+            text.trim().to_string()
+        })
+    }
+}
+
+/// Keeps linked-page fetches on the public internet: loopback (the bot's own
+/// webhook and admin ports), private and link-local networks (cloud metadata at
+/// 169.254.169.254) and other special-use addresses are refused
+#[derive(Clone, Default)]
+struct AddressGuard {
+    /// Addresses let through anyway, so tests can serve pages locally
+    allowed: Vec<IpAddr>,
+}
+
+impl AddressGuard {
+    fn check_ip(&self, ip: IpAddr) -> std::result::Result<(), String> {
+        if is_public_ip(ip) || self.allowed.contains(&ip) {
+            Ok(())
+        } else {
+            Err(format!("{} is not a public address", ip))
+        }
+    }
+
+    /// The scheme and, for a literal IP, the address. Hostnames are checked
+    /// when they resolve.
+    fn check_url(&self, url: &reqwest::Url) -> std::result::Result<(), String> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("{} links aren't fetched", url.scheme()));
+        }
+        let host = url.host_str().ok_or("the link has no host")?;
+        match host.trim_start_matches('[').trim_end_matches(']').parse() {
+            Ok(ip) => self.check_ip(ip),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl reqwest::dns::Resolve for AddressGuard {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let guard = self.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<_> = tokio::net::lookup_host((host, 0)).await?.collect();
+            // One private answer is enough to refuse; the connection could use any of them
+            for addr in &addrs {
+                guard
+                    .check_ip(addr.ip())
+                    .map_err(|e| format!("{} ({})", e, host))?;
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(addrs)
+        })
+    }
+}
+
+/// Whether `ip` is an ordinary internet address rather than loopback, private,
+/// link-local, shared (CGNAT), multicast, documentation or otherwise reserved
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public_ipv4(v4),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local fc00::/7 and link-local fe80::/10
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        // Documentation 2001:db8::/32
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8)
+        // NAT64 64:ff9b::/96 reaches IPv4 addresses, private ones included
+        || (first == 0x0064 && ip.segments()[1] == 0xff9b))
+}
+
+/// Appends the text of pages linked in the prompt, so the agent can read them
+/// without fetching anything itself. Channels with network access off
+/// (`!network off`) are skipped, so the bot doesn't reach out on their behalf.
+pub struct UrlFetchPreprocessor {
+    fetcher: Arc<dyn PageFetcher>,
+    max_urls: usize,
+    max_chars: usize,
+}
+
+impl UrlFetchPreprocessor {
+    pub fn new(fetcher: Arc<dyn PageFetcher>, config: &UrlFetchConfig) -> Self {
+        Self {
+            fetcher,
+            max_urls: config.max_urls,
+            max_chars: config.max_chars,
+        }
+    }
+}
+
+#[async_trait]
+impl PromptPreprocessor for UrlFetchPreprocessor {
+    fn name(&self) -> &str {
+        "url_fetch"
+    }
+
+    async fn process(&self, body: String, channel: &Channel) -> Result<String> {
+        if !is_network_allowed(&channel.directory) {
+            return Ok(body);
+        }
+        let mut pages = Vec::new();
+        for url in extract_urls(&body).into_iter().take(self.max_urls) {
+            // One dead link shouldn't cost the others their context
+            match self.fetcher.fetch(&url).await {
+                Ok(text) if !text.trim().is_empty() => {
+                    pages.push((url, truncate_chars(text.trim(), self.max_chars)));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        channel = %channel.channel_name,
+                        url = %url,
+                        error = %e,
+                        "Failed to fetch linked page"
+                    );
+                }
+            }
+        }
+
+        if pages.is_empty() {
+            return Ok(body);
+        }
+
+        let mut out = body;
+        out.push_str("\n\n[Linked pages, fetched automatically]");
+        for (url, text) in pages {
+            out.push_str(&format!("\n\n<page url=\"{}\">\n{}\n</page>", url, text));
+        }
+        Ok(out)
+    }
+}
+
+/// http(s) URLs in `text`, in order of appearance, without repeats or trailing punctuation
+pub fn extract_urls(text: &str) -> Vec<String> {
+    let re = Regex::new(r#"https?://[^\s<>"'`\[\]{}|\\^]+"#).unwrap();
+    let mut urls: Vec<String> = Vec::new();
+    for m in re.find_iter(text) {
+        let mut url = m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        // A closing paren belongs to the URL only if the URL opened one
+        while url.ends_with(')') && url.matches('(').count() < url.matches(')').count() {
+            url = &url[..url.len() - 1];
+        }
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+/// Readable text of an HTML page: scripts, styles and tags removed, common
+/// entities decoded and blank runs collapsed
+pub fn html_to_text(html: &str) -> String {
+    let hidden = Regex::new(
+        r"(?is)<(script|style|noscript|head|svg)\b.*?</(script|style|noscript|head|svg)\s*>",
+    )
+    .unwrap();
+    let comments = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    let breaks = Regex::new(r"(?i)<(br|/p|/div|/li|/h[1-6]|/tr|/pre|/blockquote)\b[^>]*>").unwrap();
+    let tags = Regex::new(r"(?s)<[^>]*>").unwrap();
+
+    let text = hidden.replace_all(html, " ");
+    let text = comments.replace_all(&text, " ");
+    let text = breaks.replace_all(&text, "\n");
+    let text = tags.replace_all(&text, " ");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    let mut out = String::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&line);
+    }
+    out
+}
+
+/// At most `max_chars` characters of `text`, marked when cut short
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}\n[…truncated]", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_urls() {
+        let urls = extract_urls(
+            "See https://example.com/a, and (https://en.wikipedia.org/wiki/Rust_(language)). \
+             Again: https://example.com/a",
+        );
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/a".to_string(),
+                "https://en.wikipedia.org/wiki/Rust_(language)".to_string(),
+            ]
+        );
+        assert!(extract_urls("no links, just ftp://example.com").is_empty());
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<html><head><title>x</title></head><body>\
+                    <script>var a = 1;</script><h1>Release&nbsp;notes</h1>\
+                    <p>Fixed <b>3</b> bugs &amp; added   tests.</p><!-- hidden --></body></html>";
+        assert_eq!(
+            html_to_text(html),
+            "Release notes\nFixed 3 bugs & added tests."
+        );
+    }
+
+    /// Records the URLs it's asked for and returns the same text for each
+    #[derive(Default)]
+    struct RecordingFetcher {
+        urls: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl PageFetcher for RecordingFetcher {
+        async fn fetch(&self, url: &str) -> Result<String> {
+            self.urls.lock().unwrap().push(url.to_string());
+            Ok("release notes".to_string())
+        }
+    }
+
+    fn channel_in(dir: &std::path::Path) -> Channel {
+        Channel {
+            channel_name: "research".to_string(),
+            room_id: "!research:matrix.org".to_string(),
+            session_id: "session".to_string(),
+            directory: dir.to_string_lossy().to_string(),
+            started: false,
+            created_at: String::new(),
+            backend_type: None,
+            is_dispatch_room: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_url_fetch_skipped_when_network_is_off() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let channel = channel_in(tmp.path());
+        let fetcher = Arc::new(RecordingFetcher::default());
+        let step = UrlFetchPreprocessor::new(fetcher.clone(), &UrlFetchConfig::default());
+        let body = "what changed in https://example.com/notes?".to_string();
+
+        let out = step.process(body.clone(), &channel).await.unwrap();
+        assert!(out.contains("<page url=\"https://example.com/notes\">\nrelease notes"));
+
+        crate::warm_session::set_network_allowed(&channel.directory, false).unwrap();
+        let out = step.process(body.clone(), &channel).await.unwrap();
+        assert_eq!(out, body);
+        assert_eq!(fetcher.urls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "2606:4700::1111", "8.8.8.8"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "64:ff9b::a00:1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_refuses_non_public_hosts() {
+        let fetcher = HttpPageFetcher::new(&UrlFetchConfig::default()).unwrap();
+        for url in [
+            "http://127.0.0.1:13000/admin",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/",
+            "http://10.0.0.5/",
+            "file:///etc/passwd",
+        ] {
+            let err = fetcher.fetch(url).await.unwrap_err();
+            assert!(
+                format!("{:#}", err).contains("Refusing to fetch"),
+                "{}",
+                url
+            );
+        }
+
+        // Names are checked once resolved
+        let err = fetcher.fetch("http://localhost:13000/").await.unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("is not a public address"),
+            "{:#}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_refuses_redirects_to_non_public_hosts() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 302 Found\r\n\
+                      Location: http://169.254.169.254/latest/meta-data/\r\n\
+                      Content-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+        });
+
+        // Let the local test server through, as if it were a public site
+        let guard = AddressGuard {
+            allowed: vec![addr.ip()],
+        };
+        let fetcher = HttpPageFetcher::with_guard(&UrlFetchConfig::default(), guard).unwrap();
+        let err = fetcher
+            .fetch(&format!("http://{}/notes", addr))
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("169.254.169.254 is not a public address"),
+            "{:#}",
+            err
+        );
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("héllo", 10), "héllo");
+        assert_eq!(truncate_chars("héllo", 2), "hé\n[…truncated]");
+    }
+}
@@ -0,0 +1,207 @@
+// ABOUTME: Per-sender token-bucket rate limiting keyed by (platform_id, sender_id).
+// ABOUTME: Shared by the Matrix and generic message paths so a user has one budget everywhere.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Commands that never spend a token, so a limited user can still see what's going on
+pub const EXEMPT_COMMANDS: &[&str] = &["help", "status"];
+
+/// Buckets kept before full (idle) ones are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+type LimitKey = (String, String);
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+    /// Set once the sender has been told they're limited; cleared when a message gets through
+    notified: bool,
+}
+
+/// Outcome of a rate-limit check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateDecision {
+    /// Go ahead; a token was spent
+    Allowed,
+    /// Over the limit; `notify` is true only for the first rejection in a run,
+    /// so a flood gets one reply rather than one per message
+    Limited { retry_after: Duration, notify: bool },
+}
+
+/// Token bucket per (platform, sender): `burst` messages at once, refilled at
+/// `per_minute` tokens a minute. A `per_minute` of 0 disables limiting.
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<LimitKey, Bucket>>,
+    per_minute: u32,
+    burst: u32,
+}
+
+/// Whether a command bypasses the limiter
+pub fn is_exempt_command(name: &str) -> bool {
+    EXEMPT_COMMANDS.contains(&name)
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32, burst: u32) -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            per_minute,
+            burst: burst.max(1),
+        }
+    }
+
+    /// Spend a token for this sender if one is available
+    pub fn check(&self, platform_id: &str, sender_id: &str) -> RateDecision {
+        self.check_at(platform_id, sender_id, Instant::now())
+    }
+
+    /// [`RateLimiter::check`] at an explicit instant, for deterministic tests
+    pub fn check_at(&self, platform_id: &str, sender_id: &str, now: Instant) -> RateDecision {
+        if self.per_minute == 0 {
+            return RateDecision::Allowed;
+        }
+        let capacity = self.burst as f64;
+        let refill_per_sec = self.per_minute as f64 / 60.0;
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| {
+                let elapsed = now.saturating_duration_since(bucket.updated_at);
+                bucket.tokens + elapsed.as_secs_f64() * refill_per_sec < capacity
+            });
+        }
+
+        let bucket = buckets
+            .entry((platform_id.to_string(), sender_id.to_string()))
+            .or_insert(Bucket {
+                tokens: capacity,
+                updated_at: now,
+                notified: false,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * refill_per_sec).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.notified = false;
+            return RateDecision::Allowed;
+        }
+
+        let retry_after = Duration::from_secs_f64((1.0 - bucket.tokens) / refill_per_sec);
+        let notify = !bucket.notified;
+        bucket.notified = true;
+        RateDecision::Limited {
+            retry_after,
+            notify,
+        }
+    }
+}
+
+/// Reply sent the first time a sender is limited
+pub fn limited_notice(retry_after: Duration) -> String {
+    // Round up so "try again in 0s" never happens
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    format!(
+        "⏳ You're sending messages faster than I can keep up with. Please try again in {}s.",
+        secs.max(1)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_limited_then_refilled() {
+        let limiter = RateLimiter::new(60, 2); // one token a second
+        let start = Instant::now();
+
+        assert_eq!(
+            limiter.check_at("slack", "U1", start),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("slack", "U1", start),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("slack", "U1", start),
+            RateDecision::Limited {
+                retry_after: Duration::from_secs(1),
+                notify: true
+            }
+        );
+
+        let later = start + Duration::from_secs(1);
+        assert_eq!(
+            limiter.check_at("slack", "U1", later),
+            RateDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn test_only_first_rejection_notifies() {
+        let limiter = RateLimiter::new(60, 1);
+        let start = Instant::now();
+        limiter.check_at("matrix", "@a:x", start);
+
+        let notifies: Vec<bool> = (0..3)
+            .map(|_| match limiter.check_at("matrix", "@a:x", start) {
+                RateDecision::Limited { notify, .. } => notify,
+                RateDecision::Allowed => panic!("should be limited"),
+            })
+            .collect();
+        assert_eq!(notifies, vec![true, false, false]);
+
+        // Getting through again re-arms the notice
+        let later = start + Duration::from_secs(1);
+        assert_eq!(
+            limiter.check_at("matrix", "@a:x", later),
+            RateDecision::Allowed
+        );
+        assert!(matches!(
+            limiter.check_at("matrix", "@a:x", later),
+            RateDecision::Limited { notify: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_senders_and_platforms_have_separate_buckets() {
+        let limiter = RateLimiter::new(60, 1);
+        let now = Instant::now();
+        assert_eq!(
+            limiter.check_at("telegram", "42", now),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("telegram", "43", now),
+            RateDecision::Allowed
+        );
+        assert_eq!(limiter.check_at("slack", "42", now), RateDecision::Allowed);
+        assert!(matches!(
+            limiter.check_at("telegram", "42", now),
+            RateDecision::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn test_zero_rate_disables_limiting() {
+        let limiter = RateLimiter::new(0, 1);
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(limiter.check_at("slack", "U1", now), RateDecision::Allowed);
+        }
+    }
+
+    #[test]
+    fn test_limited_notice_rounds_up() {
+        assert!(limited_notice(Duration::from_millis(2500)).ends_with("try again in 3s."));
+        assert!(limited_notice(Duration::ZERO).ends_with("try again in 1s."));
+        assert!(is_exempt_command("help"));
+        assert!(!is_exempt_command("create"));
+    }
+}
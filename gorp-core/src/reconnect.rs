@@ -1,9 +1,13 @@
-// ABOUTME: Exponential backoff reconnection for coven gRPC streams.
-// ABOUTME: Retries with 2s, 4s, 8s... up to 60s max delay on stream drop.
+// ABOUTME: Exponential backoff with jitter for platform and gateway reconnection loops.
+// ABOUTME: Retries with 2s, 4s, 8s... up to a max delay, spread out so clients don't retry in step.
 
 use std::time::Duration;
 
-/// Backoff configuration for gRPC stream reconnection
+use rand::Rng;
+
+use crate::config::ReconnectConfig;
+
+/// Backoff configuration for reconnection
 #[derive(Debug, Clone)]
 pub struct BackoffConfig {
     /// Starting delay between retries
@@ -14,6 +18,9 @@ pub struct BackoffConfig {
     pub multiplier: u32,
     /// Maximum number of consecutive failures before giving up (0 = unlimited)
     pub max_retries: u32,
+    /// Fraction of each delay (0.0-1.0) that is randomized away, so that
+    /// connections dropped by the same outage don't all retry at once
+    pub jitter: f64,
 }
 
 impl Default for BackoffConfig {
@@ -23,10 +30,28 @@ impl Default for BackoffConfig {
             max_delay: Duration::from_secs(60),
             multiplier: 2,
             max_retries: 0, // unlimited
+            jitter: 0.0,
+        }
+    }
+}
+
+impl From<&ReconnectConfig> for BackoffConfig {
+    fn from(config: &ReconnectConfig) -> Self {
+        Self {
+            initial_delay: Duration::from_secs(config.initial_delay_secs),
+            max_delay: Duration::from_secs(config.max_delay_secs),
+            jitter: config.jitter,
+            ..Self::default()
         }
     }
 }
 
+/// `delay` shortened by up to `jitter` of itself; `unit` in [0, 1) picks how much
+pub fn jittered_delay(delay: Duration, jitter: f64, unit: f64) -> Duration {
+    let jitter = jitter.clamp(0.0, 1.0);
+    delay.mul_f64(1.0 - jitter * unit.clamp(0.0, 1.0))
+}
+
 /// Tracks reconnection state with exponential backoff
 #[derive(Debug)]
 pub struct BackoffState {
@@ -52,6 +77,14 @@ impl BackoffState {
         self.current_delay = self.config.initial_delay;
     }
 
+    /// Record a failure and return the jittered delay before next retry, or None
+    /// if max retries exceeded
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        let delay = self.record_failure()?;
+        let unit = rand::thread_rng().gen::<f64>();
+        Some(jittered_delay(delay, self.config.jitter, unit))
+    }
+
     /// Record a failure and return the delay before next retry, or None if max retries exceeded
     pub fn record_failure(&mut self) -> Option<Duration> {
         self.consecutive_failures += 1;
@@ -189,6 +222,7 @@ mod tests {
             max_delay: Duration::from_secs(10),
             multiplier: 3,
             max_retries: 0,
+            jitter: 0.0,
         };
         let mut state = BackoffState::new(config);
 
@@ -203,4 +237,44 @@ mod tests {
         // Still 10s
         assert_eq!(state.record_failure(), Some(Duration::from_secs(10)));
     }
+
+    #[test]
+    fn test_jittered_delay_stays_within_bounds() {
+        let delay = Duration::from_secs(10);
+        assert_eq!(jittered_delay(delay, 0.5, 0.0), delay);
+        assert_eq!(jittered_delay(delay, 0.5, 0.5), Duration::from_millis(7500));
+        assert_eq!(jittered_delay(delay, 0.5, 1.0), Duration::from_secs(5));
+        assert_eq!(jittered_delay(delay, 0.0, 0.9), delay);
+        // Out-of-range settings are clamped rather than producing negative delays
+        assert_eq!(jittered_delay(delay, 3.0, 1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_next_delay_spreads_retries_below_the_backoff() {
+        let config = BackoffConfig {
+            jitter: 0.5,
+            ..BackoffConfig::default()
+        };
+        let mut state = BackoffState::new(config);
+
+        for expected in [2, 4, 8, 16] {
+            let delay = state.next_delay().unwrap();
+            let full = Duration::from_secs(expected);
+            assert!(delay <= full && delay >= full / 2, "{:?}", delay);
+        }
+        assert_eq!(state.consecutive_failures(), 4);
+    }
+
+    #[test]
+    fn test_backoff_config_from_reconnect_settings() {
+        let config = BackoffConfig::from(&ReconnectConfig {
+            initial_delay_secs: 1,
+            max_delay_secs: 30,
+            jitter: 0.25,
+        });
+        assert_eq!(config.initial_delay, Duration::from_secs(1));
+        assert_eq!(config.max_delay, Duration::from_secs(30));
+        assert_eq!(config.jitter, 0.25);
+        assert_eq!(config.max_retries, 0);
+    }
 }
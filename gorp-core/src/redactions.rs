@@ -0,0 +1,112 @@
+// ABOUTME: Remembers which bot messages answered which prompt, for a limited window.
+// ABOUTME: When a prompt is redacted, its answers are looked up here so they can be redacted too.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Entry {
+    /// Every event sent in answer, in the order they were sent
+    responses: Vec<String>,
+    /// When the latest response was recorded
+    at: Instant,
+}
+
+/// Maps (platform_id, prompt event_id) to the bot's response event IDs.
+/// Entries expire `window` after their latest response; a zero window tracks nothing.
+#[derive(Debug)]
+pub struct ResponseTracker {
+    entries: Mutex<HashMap<(String, String), Entry>>,
+    window: Duration,
+}
+
+impl ResponseTracker {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            window,
+        }
+    }
+
+    /// Record that `response_event_id` was sent in answer to `prompt_event_id`
+    pub fn record(&self, platform_id: &str, prompt_event_id: &str, response_event_id: &str) {
+        if self.window.is_zero() {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        self.prune(&mut entries);
+        let entry = entries
+            .entry((platform_id.to_string(), prompt_event_id.to_string()))
+            .or_insert_with(|| Entry {
+                responses: Vec::new(),
+                at: Instant::now(),
+            });
+        entry.responses.push(response_event_id.to_string());
+        entry.at = Instant::now();
+    }
+
+    /// Forget a prompt and return the responses recorded for it.
+    /// Empty if the prompt is unknown or its window has passed.
+    pub fn take(&self, platform_id: &str, prompt_event_id: &str) -> Vec<String> {
+        let Ok(mut entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        self.prune(&mut entries);
+        entries
+            .remove(&(platform_id.to_string(), prompt_event_id.to_string()))
+            .map(|entry| entry.responses)
+            .unwrap_or_default()
+    }
+
+    /// Drop entries whose latest response is older than the window
+    fn prune(&self, entries: &mut HashMap<(String, String), Entry>) {
+        let window = self.window;
+        entries.retain(|_, entry| entry.at.elapsed() <= window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_chunk_is_returned_once() {
+        let tracker = ResponseTracker::new(Duration::from_secs(600));
+        tracker.record("matrix", "$prompt", "$chunk1");
+        tracker.record("matrix", "$prompt", "$chunk2");
+        tracker.record("matrix", "$other", "$answer");
+
+        assert_eq!(
+            tracker.take("matrix", "$prompt"),
+            vec!["$chunk1", "$chunk2"]
+        );
+        assert!(tracker.take("matrix", "$prompt").is_empty());
+        assert_eq!(tracker.take("matrix", "$other"), vec!["$answer"]);
+    }
+
+    #[test]
+    fn test_entries_expire_after_the_window() {
+        let tracker = ResponseTracker::new(Duration::from_millis(20));
+        tracker.record("matrix", "$old", "$answer");
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(tracker.take("matrix", "$old").is_empty());
+    }
+
+    #[test]
+    fn test_zero_window_tracks_nothing() {
+        let tracker = ResponseTracker::new(Duration::ZERO);
+        tracker.record("matrix", "$prompt", "$answer");
+        assert!(tracker.take("matrix", "$prompt").is_empty());
+    }
+
+    #[test]
+    fn test_platforms_are_tracked_separately() {
+        let tracker = ResponseTracker::new(Duration::from_secs(600));
+        tracker.record("slack", "1", "2");
+        assert!(tracker.take("telegram", "1").is_empty());
+        assert_eq!(tracker.take("slack", "1"), vec!["2"]);
+    }
+}
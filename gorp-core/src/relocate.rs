@@ -0,0 +1,518 @@
+// ABOUTME: Moves channel workspaces to a new root: copy, verify, swap the stored paths, tidy up.
+// ABOUTME: Anything that fails before the swap leaves the channel on its old, untouched directory.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::hash::Hasher;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::scheduler::SchedulerStore;
+use crate::session::{Channel, SessionStore};
+use crate::warm_session::{acquire_turn, SharedWarmSessionManager};
+
+/// How many files get their contents hashed on both sides after a copy
+const HASH_SAMPLE: usize = 32;
+
+/// What to do with a channel being moved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelocateRequest {
+    pub new_root: PathBuf,
+    /// Leave a symlink at the old path pointing at the new one
+    #[serde(default)]
+    pub tombstone: bool,
+}
+
+/// A finished move
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Relocation {
+    pub channel_name: String,
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub files: u64,
+    pub bytes: u64,
+    /// Settings and schedule prompts whose absolute paths were rewritten
+    pub rewritten: usize,
+    pub tombstone: bool,
+}
+
+/// Entry count and total size of a directory tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirSummary {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Drain the channel's in-flight turn, drop its warm session, move it, then
+/// let queued prompts through again against the new directory
+pub async fn relocate_live(
+    store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    channel_name: &str,
+    request: &RelocateRequest,
+) -> Result<Relocation> {
+    let channel = store
+        .get_by_name(channel_name)?
+        .with_context(|| format!("Channel not found: {}", channel_name))?;
+
+    let _turn = acquire_turn(warm_manager, &channel.room_id).await;
+    warm_manager
+        .write()
+        .await
+        .invalidate_session(&channel.channel_name);
+
+    let store = store.clone();
+    let request = request.clone();
+    tokio::task::spawn_blocking(move || relocate_channel(&store, &channel, &request))
+        .await
+        .context("Relocation task panicked")?
+}
+
+/// Move one channel's workspace under `request.new_root`. The caller is
+/// responsible for making sure nothing is running in the channel meanwhile.
+pub fn relocate_channel(
+    store: &SessionStore,
+    channel: &Channel,
+    request: &RelocateRequest,
+) -> Result<Relocation> {
+    relocate_with(store, channel, request, copy_dir)
+}
+
+fn relocate_with<C>(
+    store: &SessionStore,
+    channel: &Channel,
+    request: &RelocateRequest,
+    copy: C,
+) -> Result<Relocation>
+where
+    C: Fn(&Path, &Path) -> Result<()>,
+{
+    if channel.is_dispatch_room || channel.directory.is_empty() {
+        bail!("Channel {} has no workspace to move", channel.channel_name);
+    }
+    if !request.new_root.is_absolute() {
+        bail!(
+            "New root must be an absolute path: {}",
+            request.new_root.display()
+        );
+    }
+
+    let from = PathBuf::from(&channel.directory)
+        .canonicalize()
+        .with_context(|| format!("Workspace not found: {}", channel.directory))?;
+    std::fs::create_dir_all(&request.new_root)
+        .with_context(|| format!("Failed to create {}", request.new_root.display()))?;
+    let new_root = request.new_root.canonicalize()?;
+    if new_root.starts_with(&from) {
+        bail!("New root is inside the channel's own workspace");
+    }
+    let to = new_root.join(&channel.channel_name);
+    if to.exists() {
+        bail!("{} already exists", to.display());
+    }
+
+    // Copy beside the target and rename into place, so a half-finished copy
+    // never sits at the final path
+    let staging = new_root.join(format!(".{}.relocating", channel.channel_name));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)
+            .with_context(|| format!("Failed to clear {}", staging.display()))?;
+    }
+    let summary = match copy(&from, &staging).and_then(|_| verify_copy(&from, &staging)) {
+        Ok(summary) => summary,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e.context(format!("Copy of {} failed", channel.channel_name)));
+        }
+    };
+    if let Err(e) = std::fs::rename(&staging, &to) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e).with_context(|| format!("Failed to move copy into {}", to.display()));
+    }
+
+    // Stored paths use the directory as recorded, which may not be canonical
+    let recorded = Path::new(&channel.directory);
+    let rewritten = match swap_paths(store, &channel.channel_name, recorded, &to) {
+        Ok(rewritten) => rewritten,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&to);
+            return Err(e);
+        }
+    };
+
+    // The channel lives at `to` from here on; trouble with the old copy is only worth a warning
+    let mut tombstone = false;
+    match std::fs::remove_dir_all(&from) {
+        Ok(()) if request.tombstone => match make_tombstone(&to, &from) {
+            Ok(()) => tombstone = true,
+            Err(e) => tracing::warn!(
+                path = %from.display(),
+                error = %e,
+                "Failed to leave tombstone symlink"
+            ),
+        },
+        Ok(()) => {}
+        Err(e) => tracing::warn!(
+            path = %from.display(),
+            error = %e,
+            "Failed to remove old workspace"
+        ),
+    }
+
+    tracing::info!(
+        channel = %channel.channel_name,
+        from = %from.display(),
+        to = %to.display(),
+        files = summary.files,
+        bytes = summary.bytes,
+        rewritten,
+        "Channel workspace relocated"
+    );
+    Ok(Relocation {
+        channel_name: channel.channel_name.clone(),
+        from,
+        to,
+        files: summary.files,
+        bytes: summary.bytes,
+        rewritten,
+        tombstone,
+    })
+}
+
+/// Point the channel at `to` and rewrite `from` in settings and schedule
+/// prompts, all in one transaction
+fn swap_paths(store: &SessionStore, channel_name: &str, from: &Path, to: &Path) -> Result<usize> {
+    SchedulerStore::new(store.db_connection()).initialize_schema()?;
+    let from = from.to_string_lossy().into_owned();
+    let to = to.to_string_lossy().into_owned();
+
+    let db = store.db_connection();
+    let mut conn = db
+        .lock()
+        .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+    let tx = conn.transaction()?;
+    let updated = tx.execute(
+        "UPDATE channels SET directory = ?1 WHERE channel_name = ?2",
+        rusqlite::params![to, channel_name],
+    )?;
+    if updated != 1 {
+        bail!("Channel {} disappeared during the move", channel_name);
+    }
+
+    let mut rewritten = 0;
+    for (select, update) in [
+        (
+            "SELECT key, value FROM settings",
+            "UPDATE settings SET value = ?1 WHERE key = ?2",
+        ),
+        (
+            "SELECT id, prompt FROM scheduled_prompts",
+            "UPDATE scheduled_prompts SET prompt = ?1 WHERE id = ?2",
+        ),
+    ] {
+        let rows: Vec<(String, String)> = tx
+            .prepare(select)?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (id, text) in rows {
+            if let Some(text) = rewrite_path(&text, &from, &to) {
+                tx.execute(update, rusqlite::params![text, id])?;
+                rewritten += 1;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(rewritten)
+}
+
+/// Replace `old` with `new` wherever it appears as a whole path (or the
+/// start of one). Returns None when nothing changed.
+pub fn rewrite_path(text: &str, old: &str, new: &str) -> Option<String> {
+    fn is_path_char(c: char) -> bool {
+        c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (i, _) in text.match_indices(old) {
+        let before = text[..i].chars().next_back();
+        let after = text[i + old.len()..].chars().next();
+        let whole = !before.is_some_and(is_path_char)
+            && !after.is_some_and(|c| c != '/' && is_path_char(c));
+        if whole && i >= last {
+            out.push_str(&text[last..i]);
+            out.push_str(new);
+            last = i + old.len();
+        }
+    }
+    if last == 0 {
+        return None;
+    }
+    out.push_str(&text[last..]);
+    Some(out)
+}
+
+/// Recursively copy `src` into a new directory `dst`, keeping symlinks as symlinks
+pub fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    std::fs::create_dir(dst).with_context(|| format!("Failed to create {}", dst.display()))?;
+    for entry in
+        std::fs::read_dir(src).with_context(|| format!("Failed to read {}", src.display()))?
+    {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let (src_path, dst_path) = (entry.path(), dst.join(entry.file_name()));
+        if file_type.is_dir() {
+            copy_dir(&src_path, &dst_path)?;
+        } else if file_type.is_symlink() {
+            copy_symlink(&src_path, &dst_path)?;
+        } else {
+            std::fs::copy(&src_path, &dst_path)
+                .with_context(|| format!("Failed to copy {}", src_path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> Result<()> {
+    let target = std::fs::read_link(src)?;
+    std::os::unix::fs::symlink(target, dst)
+        .with_context(|| format!("Failed to copy symlink {}", src.display()))
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, _dst: &Path) -> Result<()> {
+    bail!("Can't copy symlink {} on this platform", src.display())
+}
+
+#[cfg(unix)]
+fn make_tombstone(to: &Path, from: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(to, from)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_tombstone(_to: &Path, _from: &Path) -> Result<()> {
+    bail!("Tombstone symlinks are only supported on Unix")
+}
+
+/// Check the copy has the same entries and size as the original, and that a
+/// sample of its files hash the same
+pub fn verify_copy(src: &Path, dst: &Path) -> Result<DirSummary> {
+    let (src_summary, files) = summarize(src)?;
+    let (dst_summary, _) = summarize(dst)?;
+    if src_summary != dst_summary {
+        bail!(
+            "Copy doesn't match: {} files/{} bytes, expected {} files/{} bytes",
+            dst_summary.files,
+            dst_summary.bytes,
+            src_summary.files,
+            src_summary.bytes
+        );
+    }
+
+    let step = files.len().div_ceil(HASH_SAMPLE).max(1);
+    for relative in files.iter().step_by(step) {
+        if hash_file(&src.join(relative))? != hash_file(&dst.join(relative))? {
+            bail!("Copy of {} differs from the original", relative.display());
+        }
+    }
+    Ok(src_summary)
+}
+
+/// Summary of `dir` plus its regular files (relative, sorted) for hash sampling
+fn summarize(dir: &Path) -> Result<(DirSummary, Vec<PathBuf>)> {
+    let mut summary = DirSummary::default();
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in std::fs::read_dir(dir.join(&relative))? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = relative.join(entry.file_name());
+            summary.files += 1;
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                summary.bytes += entry.metadata()?.len();
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok((summary, files))
+}
+
+fn hash_file(path: &Path) -> Result<u64> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A store with one channel ("news") holding a couple of files
+    fn setup(tmp: &TempDir) -> (SessionStore, Channel) {
+        let store = SessionStore::new(tmp.path().join("old")).unwrap();
+        let channel = store.create_channel("news", "!news:example.org").unwrap();
+        let dir = Path::new(&channel.directory);
+        std::fs::create_dir_all(dir.join("notes")).unwrap();
+        std::fs::write(dir.join("notes/today.md"), "# Today").unwrap();
+        std::fs::write(dir.join("feeds.txt"), "a\nb\nc\n").unwrap();
+        (store, channel)
+    }
+
+    fn request(tmp: &TempDir) -> RelocateRequest {
+        RelocateRequest {
+            new_root: tmp.path().join("new"),
+            tombstone: false,
+        }
+    }
+
+    #[test]
+    fn test_relocate_copies_verifies_and_swaps() {
+        let tmp = TempDir::new().unwrap();
+        let (store, channel) = setup(&tmp);
+        let old_dir = PathBuf::from(&channel.directory).canonicalize().unwrap();
+
+        let moved = relocate_channel(&store, &channel, &request(&tmp)).unwrap();
+
+        let new_dir = tmp.path().join("new/news").canonicalize().unwrap();
+        assert_eq!(moved.to, new_dir);
+        assert_eq!(moved.files, 3);
+        assert_eq!(
+            std::fs::read_to_string(new_dir.join("notes/today.md")).unwrap(),
+            "# Today"
+        );
+        assert!(!old_dir.exists());
+        assert!(!tmp.path().join("new/.news.relocating").exists());
+
+        let stored = store.get_by_name("news").unwrap().unwrap();
+        assert_eq!(PathBuf::from(stored.directory), new_dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_relocate_leaves_tombstone_when_asked() {
+        let tmp = TempDir::new().unwrap();
+        let (store, channel) = setup(&tmp);
+        let old_dir = PathBuf::from(&channel.directory).canonicalize().unwrap();
+        let request = RelocateRequest {
+            tombstone: true,
+            ..request(&tmp)
+        };
+
+        let moved = relocate_channel(&store, &channel, &request).unwrap();
+
+        assert!(moved.tombstone);
+        assert_eq!(std::fs::read_link(&old_dir).unwrap(), moved.to);
+        assert!(old_dir.join("feeds.txt").exists());
+    }
+
+    #[test]
+    fn test_failed_copy_leaves_channel_on_old_directory() {
+        let tmp = TempDir::new().unwrap();
+        let (store, channel) = setup(&tmp);
+
+        // Copies everything, then loses the last byte of one file
+        let result = relocate_with(&store, &channel, &request(&tmp), |src, dst| {
+            copy_dir(src, dst)?;
+            std::fs::write(dst.join("feeds.txt"), "a\nb\nc")?;
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        // Or fails outright partway through
+        let result = relocate_with(&store, &channel, &request(&tmp), |_, dst| {
+            std::fs::create_dir(dst)?;
+            bail!("disk full")
+        });
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Copy of news failed"));
+
+        let stored = store.get_by_name("news").unwrap().unwrap();
+        assert_eq!(stored.directory, channel.directory);
+        assert_eq!(
+            std::fs::read_to_string(Path::new(&channel.directory).join("feeds.txt")).unwrap(),
+            "a\nb\nc\n"
+        );
+        assert!(!tmp.path().join("new/news").exists());
+        assert!(!tmp.path().join("new/.news.relocating").exists());
+    }
+
+    #[test]
+    fn test_relocate_rewrites_stored_paths() {
+        let tmp = TempDir::new().unwrap();
+        let (store, channel) = setup(&tmp);
+        let old = channel.directory.clone();
+        store
+            .set_setting("context:news", &format!(r#"{{"notes":"{}/notes"}}"#, old))
+            .unwrap();
+        store
+            .set_setting("unrelated", &format!("{}-archive/x", old))
+            .unwrap();
+        let scheduler = SchedulerStore::new(store.db_connection());
+        scheduler.initialize_schema().unwrap();
+        store
+            .db_connection()
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO scheduled_prompts (id, channel_name, room_id, prompt, created_by, created_at, next_execution_at)
+                 VALUES ('s1', 'news', '!news:example.org', ?1, 'me', 'now', 'later')",
+                [format!("Summarize {}/feeds.txt", old)],
+            )
+            .unwrap();
+
+        let moved = relocate_channel(&store, &channel, &request(&tmp)).unwrap();
+        let new = moved.to.to_string_lossy().to_string();
+
+        assert_eq!(moved.rewritten, 2);
+        assert_eq!(
+            store.get_setting("context:news").unwrap().unwrap(),
+            format!(r#"{{"notes":"{}/notes"}}"#, new)
+        );
+        assert_eq!(
+            store.get_setting("unrelated").unwrap().unwrap(),
+            format!("{}-archive/x", old)
+        );
+        assert_eq!(
+            scheduler.get_by_id("s1").unwrap().unwrap().prompt,
+            format!("Summarize {}/feeds.txt", new)
+        );
+    }
+
+    #[test]
+    fn test_rewrite_path_matches_whole_paths_only() {
+        assert_eq!(
+            rewrite_path("cat /ws/news/a.md", "/ws/news", "/big/news").as_deref(),
+            Some("cat /big/news/a.md")
+        );
+        assert_eq!(
+            rewrite_path("\"/ws/news\"", "/ws/news", "/big/news").as_deref(),
+            Some("\"/big/news\"")
+        );
+        assert_eq!(
+            rewrite_path("/ws/newsletter", "/ws/news", "/big/news"),
+            None
+        );
+        assert_eq!(
+            rewrite_path("/backup/ws/news", "/ws/news", "/big/news"),
+            None
+        );
+    }
+}
@@ -0,0 +1,195 @@
+// ABOUTME: Multi-part agent responses: ordered text blocks, files and suggested next actions.
+// ABOUTME: The agent writes .gorp/response.json; this parses it and resolves its file paths safely.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Manifest the agent writes (relative to its workspace) to send a multi-part reply
+pub const RESPONSE_MANIFEST: &str = ".gorp/response.json";
+
+/// Heading for action suggestions when the manifest doesn't give one
+pub const DEFAULT_ACTIONS_TEXT: &str = "Suggested next steps:";
+
+/// One piece of a multi-part response, sent in manifest order
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ResponsePart {
+    /// Markdown text
+    Text { text: String },
+    /// A file from the workspace, sent as an attachment
+    File {
+        path: String,
+        #[serde(default)]
+        caption: Option<String>,
+    },
+    /// Suggested replies; tapping one sends it back as the user's next message
+    Actions {
+        #[serde(default)]
+        text: Option<String>,
+        actions: Vec<String>,
+    },
+}
+
+/// A structured reply that replaces the agent's plain text response
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RichResponse {
+    pub parts: Vec<ResponsePart>,
+}
+
+impl RichResponse {
+    pub fn parse(json: &str) -> Result<Self> {
+        let response: Self = serde_json::from_str(json).context("Invalid response manifest")?;
+        if response.parts.is_empty() {
+            bail!("Response manifest has no parts");
+        }
+        for part in &response.parts {
+            if let ResponsePart::Actions { actions, .. } = part {
+                if actions.is_empty() || actions.iter().any(|a| a.trim().is_empty()) {
+                    bail!("Response manifest has an empty action");
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    /// Single-message rendering, for queues and platforms that can only take text
+    pub fn to_plain_text(&self) -> String {
+        let blocks: Vec<String> = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                ResponsePart::Text { text } => text.clone(),
+                ResponsePart::File { path, caption } => match caption {
+                    Some(caption) => format!("📎 {} ({})", caption, path),
+                    None => format!("📎 {}", path),
+                },
+                ResponsePart::Actions { text, actions } => {
+                    actions_as_list(text.as_deref(), actions)
+                }
+            })
+            .collect();
+        blocks.join("\n\n")
+    }
+}
+
+/// Read and remove the manifest left in `workspace`, if there is one.
+///
+/// The file is removed before parsing so a broken manifest doesn't hijack later replies.
+pub fn take_manifest(workspace: &Path) -> Result<Option<RichResponse>> {
+    let path = workspace.join(RESPONSE_MANIFEST);
+    let json = match std::fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    RichResponse::parse(&json).map(Some)
+}
+
+/// Resolve a file part's path inside `workspace`, refusing anything that escapes it
+pub fn resolve_file(workspace: &Path, path: &str) -> Result<PathBuf> {
+    let workspace = workspace
+        .canonicalize()
+        .context("Failed to resolve workspace")?;
+    let resolved = workspace
+        .join(path)
+        .canonicalize()
+        .with_context(|| format!("File not found: {}", path))?;
+    if !resolved.starts_with(&workspace) {
+        bail!("File is outside the workspace: {}", path);
+    }
+    if !resolved.is_file() {
+        bail!("Not a file: {}", path);
+    }
+    Ok(resolved)
+}
+
+/// Numbered list of suggestions, for platforms without buttons
+pub fn actions_as_list(text: Option<&str>, actions: &[String]) -> String {
+    let mut list = text.unwrap_or(DEFAULT_ACTIONS_TEXT).to_string();
+    for (i, action) in actions.iter().enumerate() {
+        list.push_str(&format!("\n{}. {}", i + 1, action));
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MANIFEST: &str = r#"{
+        "parts": [
+            {"type": "text", "text": "Here's the report."},
+            {"type": "file", "path": "out/report.csv", "caption": "Q3 numbers"},
+            {"type": "actions", "actions": ["Chart it", "Email it"]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_keeps_part_order() {
+        let response = RichResponse::parse(MANIFEST).unwrap();
+        assert_eq!(
+            response.parts,
+            vec![
+                ResponsePart::Text {
+                    text: "Here's the report.".to_string()
+                },
+                ResponsePart::File {
+                    path: "out/report.csv".to_string(),
+                    caption: Some("Q3 numbers".to_string()),
+                },
+                ResponsePart::Actions {
+                    text: None,
+                    actions: vec!["Chart it".to_string(), "Email it".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_or_unknown_parts() {
+        assert!(RichResponse::parse(r#"{"parts": []}"#).is_err());
+        assert!(RichResponse::parse(r#"{"parts": [{"type": "video", "url": "x"}]}"#).is_err());
+        assert!(RichResponse::parse(r#"{"parts": [{"type": "actions", "actions": []}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_take_manifest_consumes_the_file() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(take_manifest(tmp.path()).unwrap(), None);
+
+        std::fs::create_dir_all(tmp.path().join(".gorp")).unwrap();
+        std::fs::write(tmp.path().join(RESPONSE_MANIFEST), MANIFEST).unwrap();
+        assert!(take_manifest(tmp.path()).unwrap().is_some());
+        assert!(!tmp.path().join(RESPONSE_MANIFEST).exists());
+        assert_eq!(take_manifest(tmp.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolve_file_stays_inside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().join("ws");
+        std::fs::create_dir_all(workspace.join("out")).unwrap();
+        std::fs::write(workspace.join("out/report.csv"), "a,b").unwrap();
+        std::fs::write(tmp.path().join("secret.txt"), "no").unwrap();
+
+        assert!(resolve_file(&workspace, "out/report.csv").is_ok());
+        assert!(resolve_file(&workspace, "../secret.txt").is_err());
+        assert!(resolve_file(&workspace, "out").is_err());
+        assert!(resolve_file(&workspace, "missing.txt").is_err());
+    }
+
+    #[test]
+    fn test_plain_text_rendering() {
+        let response = RichResponse::parse(MANIFEST).unwrap();
+        assert_eq!(
+            response.to_plain_text(),
+            "Here's the report.\n\n📎 Q3 numbers (out/report.csv)\n\n\
+             Suggested next steps:\n1. Chart it\n2. Email it"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// ABOUTME: Safe mode: the bot stays connected and answers read-only commands but takes no action.
+// ABOUTME: Every surface that can act (agent, scheduler, webhook, notifications, commands) asks RuntimeMode first.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shown in the management room and admin panel while safe mode is active
+pub const BANNER: &str = "🛟 Safe mode is active: agents, schedules, webhooks and notifications are paused. \
+Read-only commands still work. Restart without --safe-mode or have an admin run !safemode off to resume.";
+
+/// Commands that only read state, and so still run in safe mode
+const READ_ONLY_COMMANDS: &[&str] = &["help", "status", "list", "usage", "health", "safemode"];
+
+/// Something that wants to act, checked against the runtime mode before it does
+#[derive(Debug, Clone, Copy)]
+pub enum Gate<'a> {
+    /// A prompt sent to an agent, from chat, a schedule, a webhook or DISPATCH
+    AgentTurn,
+    /// A scheduler tick
+    Scheduler,
+    /// An incoming webhook delivery
+    Webhook,
+    /// A message the bot sends on its own (budget warnings, expired drafts, held deliveries, ...)
+    Notification,
+    /// A chat command, by canonical name
+    Command { name: &'a str, args: &'a [String] },
+}
+
+/// Why safe mode refused something, worded for the person who asked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeModeRefusal(String);
+
+impl SafeModeRefusal {
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SafeModeRefusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SafeModeRefusal {}
+
+/// Whether the bot is running normally or in safe mode.
+///
+/// Clones share one flag, so leaving safe mode through `!safemode off` is seen
+/// by the pipeline, scheduler and webhook server at once.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeMode {
+    safe: Arc<AtomicBool>,
+}
+
+impl RuntimeMode {
+    pub fn new(safe: bool) -> Self {
+        Self {
+            safe: Arc::new(AtomicBool::new(safe)),
+        }
+    }
+
+    pub fn is_safe(&self) -> bool {
+        self.safe.load(Ordering::SeqCst)
+    }
+
+    /// Resume normal operation; false if safe mode was not active
+    pub fn leave_safe_mode(&self) -> bool {
+        self.safe.swap(false, Ordering::SeqCst)
+    }
+
+    /// Allow `gate`, or explain why safe mode refuses it
+    pub fn check(&self, gate: Gate<'_>) -> Result<(), SafeModeRefusal> {
+        if !self.is_safe() || is_read_only(gate) {
+            return Ok(());
+        }
+        let what = match gate {
+            Gate::AgentTurn => "agents are not being run".to_string(),
+            Gate::Scheduler => "scheduled prompts are suspended".to_string(),
+            Gate::Webhook => "webhook deliveries are rejected".to_string(),
+            Gate::Notification => "outbound notifications are disabled".to_string(),
+            Gate::Command { name, .. } => format!("!{} is unavailable", name),
+        };
+        Err(SafeModeRefusal(format!(
+            "🛟 Safe mode is active, so {}. Only read-only commands work until an admin runs !safemode off or the bot restarts.",
+            what
+        )))
+    }
+}
+
+fn is_read_only(gate: Gate<'_>) -> bool {
+    match gate {
+        Gate::Command { name, args } => {
+            READ_ONLY_COMMANDS.contains(&name)
+                || (name == "schedule" && args.len() == 1 && args[0].eq_ignore_ascii_case("list"))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command<'a>(name: &'a str, args: &'a [String]) -> Gate<'a> {
+        Gate::Command { name, args }
+    }
+
+    #[test]
+    fn test_normal_mode_allows_everything() {
+        let mode = RuntimeMode::default();
+        assert!(!mode.is_safe());
+        assert!(mode.check(Gate::AgentTurn).is_ok());
+        assert!(mode.check(Gate::Scheduler).is_ok());
+        assert!(mode.check(Gate::Webhook).is_ok());
+        assert!(mode.check(Gate::Notification).is_ok());
+        assert!(mode.check(command("reset", &[])).is_ok());
+    }
+
+    #[test]
+    fn test_safe_mode_refuses_every_acting_surface() {
+        let mode = RuntimeMode::new(true);
+        for (gate, reason) in [
+            (Gate::AgentTurn, "agents are not being run"),
+            (Gate::Scheduler, "scheduled prompts are suspended"),
+            (Gate::Webhook, "webhook deliveries are rejected"),
+            (Gate::Notification, "outbound notifications are disabled"),
+        ] {
+            let refusal = mode.check(gate).unwrap_err();
+            assert!(refusal.message().starts_with("🛟 Safe mode is active"));
+            assert!(refusal.message().contains(reason), "{}", refusal);
+        }
+    }
+
+    #[test]
+    fn test_safe_mode_allows_only_read_only_commands() {
+        let mode = RuntimeMode::new(true);
+        for name in ["help", "status", "list", "usage", "health", "safemode"] {
+            assert!(mode.check(command(name, &[])).is_ok(), "{}", name);
+        }
+        let list = vec!["list".to_string()];
+        assert!(mode.check(command("schedule", &list)).is_ok());
+
+        let delete = vec!["delete".to_string(), "abc".to_string()];
+        let refusal = mode.check(command("schedule", &delete)).unwrap_err();
+        assert!(refusal.message().contains("!schedule is unavailable"));
+        assert!(mode.check(command("reset", &[])).is_err());
+        assert!(mode.check(command("backend", &[])).is_err());
+    }
+
+    #[test]
+    fn test_leaving_safe_mode_is_shared_by_clones() {
+        let mode = RuntimeMode::new(true);
+        let scheduler_view = mode.clone();
+        assert!(scheduler_view.check(Gate::Scheduler).is_err());
+
+        assert!(mode.leave_safe_mode());
+        assert!(!mode.leave_safe_mode());
+        assert!(scheduler_view.check(Gate::Scheduler).is_ok());
+    }
+}
@@ -3,12 +3,13 @@
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
 use cron::Schedule;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
 
 /// Callback for when scheduler needs to send results
 #[async_trait]
@@ -35,6 +36,20 @@ pub struct ScheduledPrompt {
     pub execution_count: i32,
 }
 
+/// Longest result preview kept for a run, in characters
+pub const RUN_PREVIEW_CHARS: usize = 200;
+
+/// One execution of a schedule, as listed by `!schedule log`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduleRun {
+    pub schedule_id: String,
+    pub started_at: String,
+    pub duration_ms: i64,
+    pub success: bool,
+    /// Start of the response, or the error for a failed run
+    pub preview: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ScheduleStatus {
@@ -75,6 +90,106 @@ impl FromStr for ScheduleStatus {
     }
 }
 
+/// What a schedule does when it falls due during quiet hours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuietPolicy {
+    /// Wait until quiet hours end, then run
+    #[default]
+    Defer,
+    /// Run on time, keeping the response in the workspace instead of posting it
+    Silent,
+}
+
+impl std::fmt::Display for QuietPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuietPolicy::Defer => write!(f, "defer"),
+            QuietPolicy::Silent => write!(f, "silent"),
+        }
+    }
+}
+
+impl FromStr for QuietPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "defer" => Ok(QuietPolicy::Defer),
+            "silent" => Ok(QuietPolicy::Silent),
+            _ => anyhow::bail!("Unknown quiet policy: {} (use defer or silent)", s),
+        }
+    }
+}
+
+/// A daily span of wall-clock time in the scheduler timezone during which
+/// schedules don't notify anyone. An end before the start spans midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Parse a span like "22:00-07:00"
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (start, end) = spec
+            .trim()
+            .split_once('-')
+            .with_context(|| format!("Expected quiet hours like 22:00-07:00, got '{}'", spec))?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M")
+            .with_context(|| format!("Invalid quiet hours start: {}", start))?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M")
+            .with_context(|| format!("Invalid quiet hours end: {}", end))?;
+        if start == end {
+            anyhow::bail!("Quiet hours start and end must differ");
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Whether `at` falls inside quiet hours in `timezone`
+    pub fn contains(&self, at: DateTime<Utc>, timezone: &str) -> bool {
+        self.next_allowed(at, timezone).is_some()
+    }
+
+    /// When the quiet hours around `at` end, or None if `at` isn't quiet.
+    /// An unknown timezone is never quiet, so schedules still run.
+    pub fn next_allowed(&self, at: DateTime<Utc>, timezone: &str) -> Option<DateTime<Utc>> {
+        let tz: chrono_tz::Tz = timezone.parse().ok()?;
+        let local = at.with_timezone(&tz);
+        let time = local.time();
+        let today = local.date_naive();
+
+        let end_date = if self.start < self.end {
+            (time >= self.start && time < self.end).then_some(today)?
+        } else if time >= self.start {
+            today.succ_opt()?
+        } else if time < self.end {
+            today
+        } else {
+            return None;
+        };
+
+        // An end that falls in a DST gap is taken an hour later
+        let end = end_date.and_time(self.end);
+        let end = tz.from_local_datetime(&end).earliest().or_else(|| {
+            tz.from_local_datetime(&(end + chrono::Duration::hours(1)))
+                .earliest()
+        })?;
+        Some(end.with_timezone(&Utc))
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
 /// Result of parsing a time expression
 #[derive(Debug)]
 pub enum ParsedSchedule {
@@ -320,6 +435,32 @@ fn parse_weekday_time(input: &str) -> Result<String> {
     Ok(format!("{} {} * * {}", minute, hour, cron_day))
 }
 
+/// Parse the time part of a schedule on its own, without a trailing prompt.
+///
+/// Accepts a raw 5-field cron expression (as produced by schedule exports) or any
+/// natural language expression understood by `parse_time_expression`.
+pub fn parse_schedule_time(time: &str, timezone: &str) -> Result<ParsedSchedule> {
+    let time = time.trim();
+    if is_cron_expression(time) {
+        let next = compute_next_cron_execution_in_tz(time, timezone)?;
+        return Ok(ParsedSchedule::Recurring {
+            cron: time.to_string(),
+            next,
+        });
+    }
+    parse_time_expression(time, timezone)
+}
+
+/// Five whitespace-separated fields made only of cron characters
+fn is_cron_expression(s: &str) -> bool {
+    let fields: Vec<&str> = s.split_whitespace().collect();
+    fields.len() == 5
+        && fields.iter().all(|f| {
+            f.chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '*' | '-' | '/' | ','))
+        })
+}
+
 /// Compute the next execution time for a cron expression in the given timezone
 pub fn compute_next_cron_execution(cron_expr: &str) -> Result<DateTime<Utc>> {
     compute_next_cron_execution_in_tz(cron_expr, "UTC")
@@ -327,6 +468,15 @@ pub fn compute_next_cron_execution(cron_expr: &str) -> Result<DateTime<Utc>> {
 
 /// Compute the next execution time for a cron expression in the given timezone
 pub fn compute_next_cron_execution_in_tz(cron_expr: &str, timezone: &str) -> Result<DateTime<Utc>> {
+    compute_next_cron_execution_after(cron_expr, timezone, Utc::now())
+}
+
+/// First time strictly after `after` that a cron expression fires in the given timezone
+pub fn compute_next_cron_execution_after(
+    cron_expr: &str,
+    timezone: &str,
+    after: DateTime<Utc>,
+) -> Result<DateTime<Utc>> {
     // Cron crate expects 6-field expressions (with seconds), but we use 5-field
     // Prepend "0 " for seconds
     let cron_with_seconds = format!("0 {}", cron_expr);
@@ -340,7 +490,7 @@ pub fn compute_next_cron_execution_in_tz(cron_expr: &str, timezone: &str) -> Res
         .with_context(|| format!("Invalid timezone: {}", timezone))?;
 
     let next_local = schedule
-        .upcoming(tz)
+        .after(&after.with_timezone(&tz))
         .next()
         .context("Could not compute next execution time")?;
 
@@ -352,11 +502,46 @@ pub fn compute_next_cron_execution_in_tz(cron_expr: &str, timezone: &str) -> Res
 #[derive(Clone)]
 pub struct SchedulerStore {
     db: Arc<Mutex<Connection>>,
+    /// Signalled whenever a change may move the next deadline; clones share it
+    changed: Arc<Notify>,
 }
 
 impl SchedulerStore {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
-        Self { db }
+        Self {
+            db,
+            changed: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Wait until a schedule is created, rescheduled, paused, resumed or removed
+    /// through this store or one of its clones. A change made while nobody is
+    /// waiting is remembered, so the next call returns at once.
+    pub async fn wait_for_change(&self) {
+        self.changed.notified().await;
+    }
+
+    fn notify_changed(&self) {
+        self.changed.notify_one();
+    }
+
+    /// Earliest `next_execution_at` among active schedules
+    pub fn next_due_at(&self) -> Result<Option<DateTime<Utc>>> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let next: Option<String> = conn.query_row(
+            "SELECT MIN(next_execution_at) FROM scheduled_prompts WHERE status = 'active'",
+            [],
+            |row| row.get(0),
+        )?;
+        next.map(|at| {
+            DateTime::parse_from_rfc3339(&at)
+                .map(|at| at.with_timezone(&Utc))
+                .with_context(|| format!("Invalid next_execution_at: {}", at))
+        })
+        .transpose()
     }
 
     /// Initialize the database schema
@@ -385,6 +570,12 @@ impl SchedulerStore {
             [],
         )?;
 
+        // Added after the table; NULL means the default policy
+        let _ = conn.execute(
+            "ALTER TABLE scheduled_prompts ADD COLUMN quiet_policy TEXT",
+            [],
+        );
+
         // Create index for efficient due schedule queries
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_scheduled_prompts_next_execution
@@ -400,6 +591,23 @@ impl SchedulerStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS schedule_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                schedule_id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                success INTEGER NOT NULL,
+                preview TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_schedule_runs_schedule
+             ON schedule_runs(schedule_id, id)",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -431,6 +639,7 @@ impl SchedulerStore {
                 schedule.execution_count,
             ],
         )?;
+        self.notify_changed();
         Ok(())
     }
 
@@ -518,10 +727,112 @@ impl SchedulerStore {
                 params![now, id],
             )?;
         }
+        self.notify_changed();
+
+        Ok(())
+    }
+
+    /// Change a schedule's time and/or prompt in place, keeping its ID, run count and
+    /// history. A new time replaces the old one and its next execution; a completed
+    /// or failed schedule given a new time becomes active again. Schedules that are
+    /// executing are left alone. Returns false if nothing was updated.
+    pub fn update_schedule(
+        &self,
+        id: &str,
+        time: Option<&ParsedSchedule>,
+        prompt: Option<&str>,
+    ) -> Result<bool> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+
+        let rows = match time {
+            Some(time) => {
+                let (execute_at, cron_expr, next_exec) = match time {
+                    ParsedSchedule::OneTime(dt) => (Some(dt.to_rfc3339()), None, dt.to_rfc3339()),
+                    ParsedSchedule::Recurring { cron, next } => {
+                        (None, Some(cron.clone()), next.to_rfc3339())
+                    }
+                };
+                conn.execute(
+                    "UPDATE scheduled_prompts
+                     SET prompt = COALESCE(?1, prompt),
+                         execute_at = ?2,
+                         cron_expression = ?3,
+                         next_execution_at = ?4,
+                         error_message = CASE WHEN status IN ('completed', 'failed')
+                                              THEN NULL ELSE error_message END,
+                         status = CASE WHEN status IN ('completed', 'failed')
+                                       THEN 'active' ELSE status END
+                     WHERE id = ?5 AND status != 'executing'",
+                    params![prompt, execute_at, cron_expr, next_exec, id],
+                )?
+            }
+            None => conn.execute(
+                "UPDATE scheduled_prompts
+                 SET prompt = COALESCE(?1, prompt)
+                 WHERE id = ?2 AND status != 'executing'",
+                params![prompt, id],
+            )?,
+        };
+        if rows > 0 && time.is_some() {
+            self.notify_changed();
+        }
+        Ok(rows > 0)
+    }
 
+    /// Put a claimed schedule back to wait until `until` without counting a run
+    pub fn defer_schedule(&self, id: &str, until: DateTime<Utc>) -> Result<()> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE scheduled_prompts
+             SET next_execution_at = ?1,
+                 status = 'active',
+                 error_message = NULL
+             WHERE id = ?2",
+            params![until.to_rfc3339(), id],
+        )?;
+        self.notify_changed();
         Ok(())
     }
 
+    /// What the schedule does when it falls due during quiet hours
+    pub fn quiet_policy(&self, id: &str) -> Result<QuietPolicy> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let policy: Option<String> = conn
+            .query_row(
+                "SELECT quiet_policy FROM scheduled_prompts WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        match policy {
+            Some(policy) => policy.parse(),
+            None => Ok(QuietPolicy::default()),
+        }
+    }
+
+    /// Set what the schedule does during quiet hours; false if there is no such schedule
+    pub fn set_quiet_policy(&self, id: &str, policy: QuietPolicy) -> Result<bool> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let rows = conn.execute(
+            "UPDATE scheduled_prompts SET quiet_policy = ?1 WHERE id = ?2",
+            params![policy.to_string(), id],
+        )?;
+        Ok(rows > 0)
+    }
+
     /// Mark a schedule as failed
     pub fn mark_failed(&self, id: &str, error: &str) -> Result<()> {
         let conn = self
@@ -627,6 +938,13 @@ impl SchedulerStore {
             .lock()
             .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
         let rows = conn.execute("DELETE FROM scheduled_prompts WHERE id = ?1", params![id])?;
+        conn.execute(
+            "DELETE FROM schedule_runs WHERE schedule_id = ?1",
+            params![id],
+        )?;
+        if rows > 0 {
+            self.notify_changed();
+        }
         Ok(rows > 0)
     }
 
@@ -640,6 +958,9 @@ impl SchedulerStore {
             "UPDATE scheduled_prompts SET status = 'paused' WHERE id = ?1 AND status = 'active'",
             params![id],
         )?;
+        if rows > 0 {
+            self.notify_changed();
+        }
         Ok(rows > 0)
     }
 
@@ -653,6 +974,9 @@ impl SchedulerStore {
             "UPDATE scheduled_prompts SET status = 'active' WHERE id = ?1 AND status = 'paused'",
             params![id],
         )?;
+        if rows > 0 {
+            self.notify_changed();
+        }
         Ok(rows > 0)
     }
 
@@ -695,6 +1019,66 @@ impl SchedulerStore {
         }
     }
 
+    /// Record a run of a schedule, keeping only its `keep` most recent runs.
+    /// The preview is cut to [`RUN_PREVIEW_CHARS`].
+    pub fn record_run(&self, run: &ScheduleRun, keep: usize) -> Result<()> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let preview = match run.preview.char_indices().nth(RUN_PREVIEW_CHARS) {
+            Some((end, _)) => format!("{}…", &run.preview[..end]),
+            None => run.preview.clone(),
+        };
+        conn.execute(
+            "INSERT INTO schedule_runs (schedule_id, started_at, duration_ms, success, preview)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run.schedule_id,
+                run.started_at,
+                run.duration_ms,
+                run.success,
+                preview
+            ],
+        )?;
+        conn.execute(
+            "DELETE FROM schedule_runs
+             WHERE schedule_id = ?1 AND id NOT IN (
+                 SELECT id FROM schedule_runs WHERE schedule_id = ?1 ORDER BY id DESC LIMIT ?2
+             )",
+            params![run.schedule_id, keep as i64],
+        )?;
+        Ok(())
+    }
+
+    /// A schedule's most recent runs, newest first
+    pub fn list_runs(&self, schedule_id: &str, limit: usize) -> Result<Vec<ScheduleRun>> {
+        let conn = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT schedule_id, started_at, duration_ms, success, preview
+             FROM schedule_runs
+             WHERE schedule_id = ?1
+             ORDER BY id DESC
+             LIMIT ?2",
+        )?;
+        let runs = stmt
+            .query_map(params![schedule_id, limit as i64], |row| {
+                Ok(ScheduleRun {
+                    schedule_id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    duration_ms: row.get(2)?,
+                    success: row.get(3)?,
+                    preview: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(runs)
+    }
+
     /// Alias for get_by_id
     pub fn get_schedule(&self, id: &str) -> Result<Option<ScheduledPrompt>> {
         self.get_by_id(id)
@@ -751,6 +1135,60 @@ impl SchedulerStore {
             "UPDATE scheduled_prompts SET status = 'cancelled' WHERE id = ?1",
             params![id],
         )?;
+        if rows > 0 {
+            self.notify_changed();
+        }
         Ok(rows > 0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_store() -> SchedulerStore {
+        let store =
+            SchedulerStore::new(Arc::new(Mutex::new(Connection::open_in_memory().unwrap())));
+        store.initialize_schema().unwrap();
+        store
+    }
+
+    fn run(schedule_id: &str, preview: &str) -> ScheduleRun {
+        ScheduleRun {
+            schedule_id: schedule_id.to_string(),
+            started_at: "2026-03-02T09:00:00+00:00".to_string(),
+            duration_ms: 1500,
+            success: true,
+            preview: preview.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_run_keeps_newest_runs_per_schedule() {
+        let store = test_store();
+        for i in 0..5 {
+            store
+                .record_run(&run("daily", &format!("run {}", i)), 3)
+                .unwrap();
+        }
+        store.record_run(&run("weekly", "only run"), 3).unwrap();
+
+        let runs = store.list_runs("daily", 10).unwrap();
+        let previews: Vec<_> = runs.iter().map(|r| r.preview.as_str()).collect();
+        assert_eq!(previews, ["run 4", "run 3", "run 2"]);
+        assert_eq!(store.list_runs("weekly", 10).unwrap().len(), 1);
+        assert_eq!(store.list_runs("daily", 1).unwrap()[0].preview, "run 4");
+    }
+
+    #[test]
+    fn test_record_run_truncates_preview() {
+        let store = test_store();
+        store
+            .record_run(&run("daily", &"é".repeat(500)), 3)
+            .unwrap();
+
+        let preview = &store.list_runs("daily", 1).unwrap()[0].preview;
+        assert_eq!(preview.chars().count(), RUN_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+    }
+}
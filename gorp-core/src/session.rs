@@ -3,8 +3,33 @@
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::config::{ChannelScope, RiskLevel};
+use crate::delivery::{DeliveryWindow, HeldMessage};
+use crate::drafts::Draft;
+use crate::templates::PromptTemplate;
+use crate::usage::{
+    forecast_spend, ChannelUsage, Experiment, InvocationOrigin, SpendForecast, UsageBucket,
+    UsageTotals, FORECAST_WINDOW_DAYS,
+};
+use crate::webhook_batch::{WebhookBatch, MAX_SAMPLES};
+
+/// Settings keyed "<prefix>:<channel_name>" that a cloned channel inherits.
+/// Budget warnings and usage stay with the original channel.
+const CLONED_SETTING_PREFIXES: &[&str] = &[
+    "delivery_window",
+    "locale:channel",
+    "transcription_language",
+    "attachment_max_bytes",
+    "context",
+    "budget_cents",
+    "tool_approval",
+    "response_cache",
+];
 
 /// Recursively copy all contents from source directory to destination
 fn copy_dir_contents(src: &Path, dst: &Path) -> Result<()> {
@@ -47,6 +72,23 @@ pub struct Channel {
     pub is_dispatch_room: bool,
 }
 
+/// A Matrix room invite waiting for `!approve` (invite_policy = "require_approval")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingInvite {
+    pub room_id: String,
+    pub inviter: String,
+    pub room_name: Option<String>,
+    pub invited_at: String,
+}
+
+/// Someone added to a channel's room with `!invite`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelMember {
+    pub user_id: String,
+    pub invited_by: String,
+    pub invited_at: String,
+}
+
 /// An event from a worker room routed to DISPATCH
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DispatchEvent {
@@ -130,10 +172,14 @@ impl Channel {
     }
 }
 
+/// How long a statement waits on another connection's write before failing with "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct SessionStore {
     db: Arc<Mutex<Connection>>,
     workspace_path: PathBuf,
+    channel_scope: ChannelScope,
 }
 
 impl SessionStore {
@@ -146,6 +192,24 @@ impl SessionStore {
         let db_path = workspace_path.join("sessions.db");
         let conn = Connection::open(&db_path).context("Failed to open SQLite database")?;
 
+        // Other stores open the same file with their own connections. In WAL mode
+        // their reads don't wait on a write, and writers queue for the busy
+        // timeout instead of failing at once.
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .context("Failed to set SQLite busy timeout")?;
+        let journal_mode: String = conn
+            .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))
+            .context("Failed to switch SQLite to WAL mode")?;
+        if !journal_mode.eq_ignore_ascii_case("wal") {
+            tracing::warn!(
+                db = %db_path.display(),
+                journal_mode = %journal_mode,
+                "SQLite refused WAL mode; concurrent access may hit lock errors"
+            );
+        }
+        conn.pragma_update(None, "synchronous", "NORMAL")
+            .context("Failed to set SQLite synchronous mode")?;
+
         // Create channels table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS channels (
@@ -230,6 +294,162 @@ impl SessionStore {
             [],
         )?;
 
+        // Create outgoing_queue table for output held outside a channel's delivery window.
+        // Rows are delivered in id order once the window opens, then deleted.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS outgoing_queue (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel_name TEXT NOT NULL,
+                platform_id TEXT,
+                target_id TEXT,
+                body TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create channel_usage table: running totals per channel, split by usage bucket
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS channel_usage (
+                channel_name TEXT NOT NULL,
+                bucket TEXT NOT NULL,
+                invocations INTEGER NOT NULL DEFAULT 0,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                cost_cents INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (channel_name, bucket)
+            )",
+            [],
+        )?;
+
+        // Create channel_usage_daily table: cost per channel per UTC day, for spend forecasts
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS channel_usage_daily (
+                channel_name TEXT NOT NULL,
+                day TEXT NOT NULL,
+                cost_cents INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (channel_name, day)
+            )",
+            [],
+        )?;
+
+        // Create experiments table: one row per side-by-side run (e.g. !compare), details as JSON
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS experiments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel_name TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                prompt TEXT NOT NULL,
+                details TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create drafts table: send-guarded prompts waiting for !send, one per user per channel
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS drafts (
+                channel_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                platform_id TEXT NOT NULL,
+                body TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (channel_id, user_id)
+            )",
+            [],
+        )?;
+
+        // Create templates table: reusable prompts saved per channel with !template save
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS templates (
+                channel_name TEXT NOT NULL,
+                name TEXT NOT NULL,
+                body TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (channel_name, name)
+            )",
+            [],
+        )?;
+
+        // Create pending_invites table: room invites waiting for an admin's !approve
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS pending_invites (
+                room_id TEXT PRIMARY KEY,
+                inviter TEXT NOT NULL,
+                room_name TEXT,
+                invited_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create webhook_batches table: coalesced webhook deliveries waiting for their window to close
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhook_batches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel_name TEXT NOT NULL,
+                group_key TEXT NOT NULL,
+                prompt TEXT NOT NULL,
+                samples_json TEXT NOT NULL DEFAULT '[]',
+                count INTEGER NOT NULL,
+                first_at INTEGER NOT NULL,
+                last_at INTEGER NOT NULL,
+                flush_at INTEGER NOT NULL,
+                UNIQUE (channel_name, group_key)
+            )",
+            [],
+        )?;
+
+        // Create response_cache table: the last answer to each prompt in channels with !cache on,
+        // valid while the workspace still has the fingerprint it was answered against
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS response_cache (
+                channel_name TEXT NOT NULL,
+                prompt TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                response TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (channel_name, prompt)
+            )",
+            [],
+        )?;
+
+        // Create channel_members table: people invited into a channel's room with !invite
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS channel_members (
+                channel_name TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                invited_by TEXT NOT NULL,
+                invited_at TEXT NOT NULL,
+                PRIMARY KEY (channel_name, user_id)
+            )",
+            [],
+        )?;
+
+        // Migration: the platform a channel was created on, and the name it was given there.
+        // channel_name stays the unique key for workspaces and settings; with platform-scoped
+        // names it can differ from the label, which only has to be unique per platform.
+        let _ = conn.execute("ALTER TABLE channels ADD COLUMN platform TEXT", []);
+        let _ = conn.execute("ALTER TABLE channels ADD COLUMN label TEXT", []);
+        conn.execute(
+            "UPDATE channels SET label = channel_name WHERE label IS NULL",
+            [],
+        )?;
+        conn.execute(
+            "UPDATE channels SET platform = COALESCE(
+                 (SELECT platform_id FROM channel_bindings WHERE channel_id = channels.room_id LIMIT 1),
+                 CASE WHEN room_id LIKE '!%' THEN 'matrix' END
+             )
+             WHERE platform IS NULL",
+            [],
+        )?;
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_channels_platform_label
+             ON channels (platform, label)",
+            [],
+        )?;
+
         tracing::info!(
             workspace = %workspace_path.display(),
             db = %db_path.display(),
@@ -239,9 +459,20 @@ impl SessionStore {
         Ok(SessionStore {
             db: Arc::new(Mutex::new(conn)),
             workspace_path,
+            channel_scope: ChannelScope::default(),
         })
     }
 
+    /// Use `scope` to decide whether channel names must be unique across platforms
+    pub fn with_channel_scope(mut self, scope: ChannelScope) -> Self {
+        self.channel_scope = scope;
+        self
+    }
+
+    pub fn channel_scope(&self) -> ChannelScope {
+        self.channel_scope
+    }
+
     /// Get the shared database connection for use by other stores (like SchedulerStore)
     pub fn db_connection(&self) -> Arc<Mutex<Connection>> {
         Arc::clone(&self.db)
@@ -318,10 +549,91 @@ impl SessionStore {
         }
     }
 
+    /// Get the channel a user on `platform_id` means by `channel_name` (case-insensitive).
+    /// With globally unique names that is simply the channel of that name; scoped by
+    /// platform, it is the one created under that name on the platform, or a channel
+    /// from before platforms were recorded.
+    pub fn get_by_name_on(&self, platform_id: &str, channel_name: &str) -> Result<Option<Channel>> {
+        if self.channel_scope == ChannelScope::Global {
+            return self.get_by_name(channel_name);
+        }
+        let label = channel_name.to_lowercase();
+        let channel_name = {
+            let db = self
+                .db
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+            db.query_row(
+                "SELECT channel_name FROM channels
+                 WHERE (platform = ?1 AND label = ?2) OR (platform IS NULL AND channel_name = ?2)
+                 ORDER BY platform IS NULL
+                 LIMIT 1",
+                params![platform_id, label],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?
+        };
+        match channel_name {
+            Some(name) => self.get_by_name(&name),
+            None => Ok(None),
+        }
+    }
+
     /// Create a new channel with auto-generated session ID and directory
     pub fn create_channel(&self, channel_name: &str, room_id: &str) -> Result<Channel> {
-        // Normalize to lowercase for case-insensitive matching
         let channel_name = channel_name.to_lowercase();
+        self.insert_channel(&channel_name, &channel_name, None, room_id)
+    }
+
+    /// Create a channel on `platform_id`. Scoped by platform, the name only has to be
+    /// free on that platform; when another platform already uses it, the channel's
+    /// key and workspace directory get the platform as a suffix, as a forked clone's do.
+    pub fn create_channel_on(
+        &self,
+        platform_id: &str,
+        channel_name: &str,
+        room_id: &str,
+    ) -> Result<Channel> {
+        let label = channel_name.to_lowercase();
+        if self.channel_scope == ChannelScope::Global {
+            return self.insert_channel(&label, &label, Some(platform_id), room_id);
+        }
+        if self.get_by_name_on(platform_id, &label)?.is_some() {
+            anyhow::bail!("Channel '{}' already exists on {}", label, platform_id);
+        }
+        let key = if self.name_taken(&label)? {
+            format!("{}-{}", label, platform_id)
+        } else {
+            label.clone()
+        };
+        self.insert_channel(&key, &label, Some(platform_id), room_id)
+    }
+
+    /// Whether any channel already has `channel_name` as its key
+    fn name_taken(&self, channel_name: &str) -> Result<bool> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        Ok(db
+            .query_row(
+                "SELECT 1 FROM channels WHERE channel_name = ?1",
+                params![channel_name],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    /// Insert a channel keyed by `channel_name`, which also names its directory
+    fn insert_channel(
+        &self,
+        channel_name: &str,
+        label: &str,
+        platform_id: Option<&str>,
+        room_id: &str,
+    ) -> Result<Channel> {
+        let channel_name = channel_name.to_string();
 
         // Validate channel_name
         if !channel_name
@@ -357,8 +669,8 @@ impl SessionStore {
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
 
         match db.execute(
-            "INSERT INTO channels (channel_name, room_id, session_id, directory, started, created_at, backend_type, is_dispatch_room)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO channels (channel_name, room_id, session_id, directory, started, created_at, backend_type, is_dispatch_room, platform, label)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 &channel.channel_name,
                 &channel.room_id,
@@ -368,6 +680,8 @@ impl SessionStore {
                 &channel.created_at,
                 &channel.backend_type,
                 0, // is_dispatch_room defaults to false
+                platform_id,
+                label,
             ],
         ) {
             Ok(_) => {
@@ -452,6 +766,35 @@ impl SessionStore {
         Ok(channels)
     }
 
+    /// List the channels created on `platform_id`
+    pub fn list_on(&self, platform_id: &str) -> Result<Vec<Channel>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT channel_name, room_id, session_id, directory, started, created_at, backend_type, is_dispatch_room
+             FROM channels WHERE platform = ?1 ORDER BY created_at DESC",
+        )?;
+
+        let channels = stmt
+            .query_map(params![platform_id], |row| {
+                Ok(Channel {
+                    channel_name: row.get(0)?,
+                    room_id: row.get(1)?,
+                    session_id: row.get(2)?,
+                    directory: row.get(3)?,
+                    started: row.get::<_, i32>(4)? != 0,
+                    created_at: row.get(5)?,
+                    backend_type: row.get(6)?,
+                    is_dispatch_room: row.get::<_, i32>(7)? != 0,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(channels)
+    }
+
     /// Delete a channel by name
     pub fn delete_channel(&self, channel_name: &str) -> Result<()> {
         let db = self
@@ -815,102 +1158,1234 @@ impl SessionStore {
     }
 
     // =========================================================================
-    // Mux Session Persistence
+    // Delivery Windows & Outgoing Queue
     // =========================================================================
 
-    /// Save a mux session's message history to the database
-    pub fn save_mux_session(
-        &self,
-        session_id: &str,
-        messages_json: &str,
-        system_prompt: Option<&str>,
-    ) -> Result<()> {
+    /// Get the delivery window for a channel (stored as a spec in settings table)
+    pub fn get_delivery_window(&self, channel_name: &str) -> Result<Option<DeliveryWindow>> {
+        let key = format!("delivery_window:{}", channel_name);
+        match self.get_setting(&key)? {
+            // Stored specs always carry their timezone, so the default is unused
+            Some(spec) => Ok(Some(DeliveryWindow::parse(&spec, "UTC")?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the delivery window for a channel
+    pub fn set_delivery_window(&self, channel_name: &str, window: &DeliveryWindow) -> Result<()> {
+        let key = format!("delivery_window:{}", channel_name);
+        self.set_setting(&key, &window.to_string())
+    }
+
+    /// Remove a channel's delivery window so output is delivered immediately
+    pub fn clear_delivery_window(&self, channel_name: &str) -> Result<()> {
+        let key = format!("delivery_window:{}", channel_name);
         let db = self
             .db
             .lock()
             .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+        Ok(())
+    }
 
-        let now = chrono::Utc::now().to_rfc3339();
+    // =========================================================================
+    // Locale Preferences
+    // =========================================================================
 
-        db.execute(
-            "INSERT INTO mux_sessions (session_id, messages_json, system_prompt, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?4)
-             ON CONFLICT(session_id) DO UPDATE SET
-                messages_json = ?2,
-                system_prompt = ?3,
-                updated_at = ?4",
-            params![session_id, messages_json, system_prompt, now],
-        )?;
+    /// Get the locale chosen for a channel's bot messages, if any
+    pub fn get_channel_locale(&self, channel_name: &str) -> Result<Option<String>> {
+        self.get_setting(&format!("locale:channel:{}", channel_name))
+    }
 
-        tracing::debug!(session_id = %session_id, "Mux session saved");
-        Ok(())
+    /// Set a channel's locale; None returns it to the default
+    pub fn set_channel_locale(&self, channel_name: &str, locale: Option<&str>) -> Result<()> {
+        self.put_or_clear_setting(&format!("locale:channel:{}", channel_name), locale)
     }
 
-    /// Load a mux session's message history from the database
-    /// Returns (messages_json, system_prompt) if found
-    pub fn load_mux_session(&self, session_id: &str) -> Result<Option<(String, Option<String>)>> {
-        let db = self
-            .db
-            .lock()
-            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+    /// Get the locale a user chose for DMs, if any
+    pub fn get_user_locale(&self, user_id: &str) -> Result<Option<String>> {
+        self.get_setting(&format!("locale:user:{}", user_id))
+    }
 
-        let mut stmt = db.prepare(
-            "SELECT messages_json, system_prompt FROM mux_sessions WHERE session_id = ?1",
-        )?;
+    /// Set a user's locale; None returns it to the default
+    pub fn set_user_locale(&self, user_id: &str, locale: Option<&str>) -> Result<()> {
+        self.put_or_clear_setting(&format!("locale:user:{}", user_id), locale)
+    }
 
-        let result = stmt.query_row(params![session_id], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
-        });
+    // =========================================================================
+    // Transcription Language
+    // =========================================================================
 
-        match result {
-            Ok((messages, prompt)) => {
-                tracing::debug!(session_id = %session_id, "Mux session loaded");
-                Ok(Some((messages, prompt)))
-            }
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e.into()),
-        }
+    /// Get the language hint for transcribing a channel's voice notes; None means auto-detect
+    pub fn get_transcription_language(&self, channel_name: &str) -> Result<Option<String>> {
+        self.get_setting(&format!("transcription_language:{}", channel_name))
     }
 
-    /// Delete a mux session from the database
-    pub fn delete_mux_session(&self, session_id: &str) -> Result<()> {
-        let db = self
-            .db
-            .lock()
-            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+    /// Set a channel's transcription language hint; None returns it to auto-detect
+    pub fn set_transcription_language(
+        &self,
+        channel_name: &str,
+        language: Option<&str>,
+    ) -> Result<()> {
+        self.put_or_clear_setting(
+            &format!("transcription_language:{}", channel_name),
+            language,
+        )
+    }
 
-        db.execute(
-            "DELETE FROM mux_sessions WHERE session_id = ?1",
-            params![session_id],
-        )?;
+    // =========================================================================
+    // Awaiting Reply
+    // =========================================================================
 
-        tracing::debug!(session_id = %session_id, "Mux session deleted");
-        Ok(())
+    /// Get the question a channel's agent stopped to ask, while it waits for an answer
+    pub fn get_awaiting_reply(&self, channel_name: &str) -> Result<Option<String>> {
+        self.get_setting(&format!("awaiting_reply:{}", channel_name))
     }
 
-    /// Check if a mux session exists in the database
-    pub fn mux_session_exists(&self, session_id: &str) -> Result<bool> {
-        let db = self
-            .db
-            .lock()
-            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+    /// Record the agent's open question; None means it is no longer waiting
+    pub fn set_awaiting_reply(&self, channel_name: &str, question: Option<&str>) -> Result<()> {
+        self.put_or_clear_setting(&format!("awaiting_reply:{}", channel_name), question)
+    }
 
-        let mut stmt = db.prepare("SELECT 1 FROM mux_sessions WHERE session_id = ?1")?;
-        let exists = stmt.exists(params![session_id])?;
-        Ok(exists)
+    // =========================================================================
+    // Room Topic
+    // =========================================================================
+
+    /// Get the topic set with `!topic` for a channel's room
+    pub fn get_room_topic(&self, channel_name: &str) -> Result<Option<String>> {
+        self.get_setting(&format!("room_topic:{}", channel_name))
+    }
+
+    /// Store a channel's room topic; None falls back to the default topic
+    pub fn set_room_topic(&self, channel_name: &str, topic: Option<&str>) -> Result<()> {
+        self.put_or_clear_setting(&format!("room_topic:{}", channel_name), topic)
     }
 
     // =========================================================================
-    // Dispatch Event Persistence
+    // Attachment Limits
     // =========================================================================
 
-    /// Insert a dispatch event
-    pub fn insert_dispatch_event(&self, event: &DispatchEvent) -> Result<()> {
+    /// Get a channel's own attachment size limit in bytes; None means the configured default
+    pub fn get_attachment_max_bytes(&self, channel_name: &str) -> Result<Option<u64>> {
+        match self.get_setting(&format!("attachment_max_bytes:{}", channel_name))? {
+            Some(value) => Ok(Some(
+                value.parse().context("Invalid attachment size limit")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Set a channel's attachment size limit; None returns it to the configured default
+    pub fn set_attachment_max_bytes(&self, channel_name: &str, bytes: Option<u64>) -> Result<()> {
+        self.put_or_clear_setting(
+            &format!("attachment_max_bytes:{}", channel_name),
+            bytes.map(|b| b.to_string()).as_deref(),
+        )
+    }
+
+    // =========================================================================
+    // Custom Context
+    // =========================================================================
+
+    /// Get the user-defined keys merged into a channel's MCP context file
+    pub fn get_custom_context(&self, channel_name: &str) -> Result<BTreeMap<String, String>> {
+        match self.get_setting(&format!("context:{}", channel_name))? {
+            Some(json) => serde_json::from_str(&json).context("Invalid custom context"),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    /// Replace a channel's custom context keys; an empty map removes them
+    pub fn set_custom_context(
+        &self,
+        channel_name: &str,
+        context: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        let json = if context.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(context)?)
+        };
+        self.put_or_clear_setting(&format!("context:{}", channel_name), json.as_deref())
+    }
+
+    fn put_or_clear_setting(&self, key: &str, value: Option<&str>) -> Result<()> {
+        match value {
+            Some(value) => self.set_setting(key, value),
+            None => {
+                let db = self
+                    .db
+                    .lock()
+                    .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+                db.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Queue a message for later delivery.
+    /// A target of None means the message is published to the bus on flush.
+    pub fn hold_message(
+        &self,
+        channel_name: &str,
+        target: Option<(&str, &str)>,
+        body: &str,
+    ) -> Result<i64> {
         let db = self
             .db
             .lock()
             .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
-        let payload_str = serde_json::to_string(&event.payload)?;
+        let (platform_id, target_id) = match target {
+            Some((platform_id, target_id)) => (Some(platform_id), Some(target_id)),
+            None => (None, None),
+        };
+        db.execute(
+            "INSERT INTO outgoing_queue (channel_name, platform_id, target_id, body, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                channel_name,
+                platform_id,
+                target_id,
+                body,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(db.last_insert_rowid())
+    }
+
+    /// List held messages for a channel in the order they were generated
+    pub fn list_held_messages(&self, channel_name: &str) -> Result<Vec<HeldMessage>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT id, channel_name, platform_id, target_id, body, created_at
+             FROM outgoing_queue WHERE channel_name = ?1 ORDER BY id ASC",
+        )?;
+        let messages = stmt
+            .query_map(params![channel_name], |row| {
+                Ok(HeldMessage {
+                    id: row.get(0)?,
+                    channel_name: row.get(1)?,
+                    platform_id: row.get(2)?,
+                    target_id: row.get(3)?,
+                    body: row.get(4)?,
+                    created_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(messages)
+    }
+
+    /// Remove a held message once it has been delivered
+    pub fn remove_held_message(&self, id: i64) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute("DELETE FROM outgoing_queue WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// List channel names that currently have held messages
+    pub fn channels_with_held_messages(&self) -> Result<Vec<String>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT channel_name FROM outgoing_queue GROUP BY channel_name ORDER BY MIN(id)",
+        )?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(names)
+    }
+
+    // =========================================================================
+    // Send Guard Drafts
+    // =========================================================================
+
+    /// Whether a user has turned on the send guard
+    pub fn is_send_guard_enabled(&self, user_id: &str) -> Result<bool> {
+        Ok(self
+            .get_setting(&format!("sendguard:user:{}", user_id))?
+            .as_deref()
+            == Some("on"))
+    }
+
+    /// Turn a user's send guard on or off
+    pub fn set_send_guard(&self, user_id: &str, enabled: bool) -> Result<()> {
+        self.put_or_clear_setting(
+            &format!("sendguard:user:{}", user_id),
+            enabled.then_some("on"),
+        )
+    }
+
+    /// Get a user's pending draft in a channel, if any
+    pub fn get_draft(&self, channel_id: &str, user_id: &str) -> Result<Option<Draft>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        Self::query_draft(&db, channel_id, user_id)
+    }
+
+    /// Create or replace a user's draft in a channel; the original created_at is kept
+    pub fn save_draft(
+        &self,
+        platform_id: &str,
+        channel_id: &str,
+        user_id: &str,
+        body: &str,
+    ) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let now = chrono::Utc::now().to_rfc3339();
+        db.execute(
+            "INSERT INTO drafts (channel_id, user_id, platform_id, body, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+             ON CONFLICT(channel_id, user_id) DO UPDATE SET
+                platform_id = ?3, body = ?4, updated_at = ?5",
+            params![channel_id, user_id, platform_id, body, now],
+        )?;
+        Ok(())
+    }
+
+    /// Remove and return a user's draft in a channel
+    pub fn take_draft(&self, channel_id: &str, user_id: &str) -> Result<Option<Draft>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let draft = Self::query_draft(&db, channel_id, user_id)?;
+        if draft.is_some() {
+            db.execute(
+                "DELETE FROM drafts WHERE channel_id = ?1 AND user_id = ?2",
+                params![channel_id, user_id],
+            )?;
+        }
+        Ok(draft)
+    }
+
+    /// List every pending draft, least recently updated first
+    pub fn list_drafts(&self) -> Result<Vec<Draft>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT platform_id, channel_id, user_id, body, created_at, updated_at
+             FROM drafts ORDER BY updated_at ASC",
+        )?;
+        let drafts = stmt
+            .query_map([], Self::row_to_draft)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(drafts)
+    }
+
+    /// Delete a draft only if nobody has appended to it since it was read.
+    /// Returns true if it was deleted.
+    pub fn discard_draft_if_unchanged(&self, draft: &Draft) -> Result<bool> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let deleted = db.execute(
+            "DELETE FROM drafts WHERE channel_id = ?1 AND user_id = ?2 AND updated_at = ?3",
+            params![draft.channel_id, draft.user_id, draft.updated_at],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    fn query_draft(db: &Connection, channel_id: &str, user_id: &str) -> Result<Option<Draft>> {
+        let draft = db
+            .query_row(
+                "SELECT platform_id, channel_id, user_id, body, created_at, updated_at
+                 FROM drafts WHERE channel_id = ?1 AND user_id = ?2",
+                params![channel_id, user_id],
+                Self::row_to_draft,
+            )
+            .optional()?;
+        Ok(draft)
+    }
+
+    fn row_to_draft(row: &rusqlite::Row) -> rusqlite::Result<Draft> {
+        Ok(Draft {
+            platform_id: row.get(0)?,
+            channel_id: row.get(1)?,
+            user_id: row.get(2)?,
+            body: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+
+    // =========================================================================
+    // Prompt Templates
+    // =========================================================================
+
+    /// Create or replace a channel's template
+    pub fn save_template(
+        &self,
+        channel_name: &str,
+        name: &str,
+        body: &str,
+        created_by: &str,
+    ) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute(
+            "INSERT INTO templates (channel_name, name, body, created_by, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(channel_name, name) DO UPDATE SET
+                body = ?3, created_by = ?4, updated_at = ?5",
+            params![
+                channel_name,
+                name,
+                body,
+                created_by,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get one of a channel's templates by name
+    pub fn get_template(&self, channel_name: &str, name: &str) -> Result<Option<PromptTemplate>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let template = db
+            .query_row(
+                "SELECT channel_name, name, body, created_by, updated_at
+                 FROM templates WHERE channel_name = ?1 AND name = ?2",
+                params![channel_name, name],
+                Self::row_to_template,
+            )
+            .optional()?;
+        Ok(template)
+    }
+
+    /// List a channel's templates in name order
+    pub fn list_templates(&self, channel_name: &str) -> Result<Vec<PromptTemplate>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT channel_name, name, body, created_by, updated_at
+             FROM templates WHERE channel_name = ?1 ORDER BY name",
+        )?;
+        let templates = stmt
+            .query_map(params![channel_name], Self::row_to_template)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(templates)
+    }
+
+    /// Delete a channel's template. Returns true if it existed.
+    pub fn delete_template(&self, channel_name: &str, name: &str) -> Result<bool> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let deleted = db.execute(
+            "DELETE FROM templates WHERE channel_name = ?1 AND name = ?2",
+            params![channel_name, name],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    fn row_to_template(row: &rusqlite::Row) -> rusqlite::Result<PromptTemplate> {
+        Ok(PromptTemplate {
+            channel_name: row.get(0)?,
+            name: row.get(1)?,
+            body: row.get(2)?,
+            created_by: row.get(3)?,
+            updated_at: row.get(4)?,
+        })
+    }
+
+    /// Mark `user_id`'s next message in a room as the body of template `name`;
+    /// None cancels a pending capture
+    pub fn set_template_capture(
+        &self,
+        room_id: &str,
+        user_id: &str,
+        name: Option<&str>,
+    ) -> Result<()> {
+        self.put_or_clear_setting(&format!("template_capture:{}:{}", room_id, user_id), name)
+    }
+
+    /// Take the template name waiting for `user_id`'s next message in a room, if any
+    pub fn take_template_capture(&self, room_id: &str, user_id: &str) -> Result<Option<String>> {
+        let key = format!("template_capture:{}:{}", room_id, user_id);
+        let name = self.get_setting(&key)?;
+        if name.is_some() {
+            self.put_or_clear_setting(&key, None)?;
+        }
+        Ok(name)
+    }
+
+    // =========================================================================
+    // Pending Invites
+    // =========================================================================
+
+    /// Queue an invite for approval. Returns false if the room was already queued,
+    /// so a repeated invite doesn't notify the admin room twice.
+    pub fn queue_invite(
+        &self,
+        room_id: &str,
+        inviter: &str,
+        room_name: Option<&str>,
+    ) -> Result<bool> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let inserted = db.execute(
+            "INSERT OR IGNORE INTO pending_invites (room_id, inviter, room_name, invited_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![room_id, inviter, room_name, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Invites waiting for approval, oldest first
+    pub fn list_pending_invites(&self) -> Result<Vec<PendingInvite>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT room_id, inviter, room_name, invited_at
+             FROM pending_invites ORDER BY invited_at, room_id",
+        )?;
+        let invites = stmt
+            .query_map([], Self::row_to_pending_invite)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(invites)
+    }
+
+    /// Remove and return the pending invite for a room, if there is one
+    pub fn take_pending_invite(&self, room_id: &str) -> Result<Option<PendingInvite>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let invite = db
+            .query_row(
+                "SELECT room_id, inviter, room_name, invited_at
+                 FROM pending_invites WHERE room_id = ?1",
+                params![room_id],
+                Self::row_to_pending_invite,
+            )
+            .optional()?;
+        if invite.is_some() {
+            db.execute(
+                "DELETE FROM pending_invites WHERE room_id = ?1",
+                params![room_id],
+            )?;
+        }
+        Ok(invite)
+    }
+
+    fn row_to_pending_invite(row: &rusqlite::Row) -> rusqlite::Result<PendingInvite> {
+        Ok(PendingInvite {
+            room_id: row.get(0)?,
+            inviter: row.get(1)?,
+            room_name: row.get(2)?,
+            invited_at: row.get(3)?,
+        })
+    }
+
+    // =========================================================================
+    // Channel Members
+    // =========================================================================
+
+    /// Record that `user_id` was invited into the channel's room. Returns false
+    /// if they were already recorded, keeping the original invite.
+    pub fn add_channel_member(
+        &self,
+        channel_name: &str,
+        user_id: &str,
+        invited_by: &str,
+    ) -> Result<bool> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let inserted = db.execute(
+            "INSERT OR IGNORE INTO channel_members (channel_name, user_id, invited_by, invited_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                channel_name,
+                user_id,
+                invited_by,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// People invited into the channel's room, in the order they were invited
+    pub fn list_channel_members(&self, channel_name: &str) -> Result<Vec<ChannelMember>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT user_id, invited_by, invited_at FROM channel_members
+             WHERE channel_name = ?1 ORDER BY invited_at, user_id",
+        )?;
+        let members = stmt
+            .query_map(params![channel_name], |row| {
+                Ok(ChannelMember {
+                    user_id: row.get(0)?,
+                    invited_by: row.get(1)?,
+                    invited_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(members)
+    }
+
+    // =========================================================================
+    // Webhook Batches
+    // =========================================================================
+
+    /// Add a webhook delivery to the open batch for its channel and key, opening
+    /// one that closes `window` from now if there isn't one yet
+    pub fn add_webhook_delivery(
+        &self,
+        channel_name: &str,
+        group_key: &str,
+        prompt: &str,
+        sample: Option<&str>,
+        window: chrono::Duration,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<WebhookBatch> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let now_ms = now.timestamp_millis();
+        let existing = db
+            .query_row(
+                "SELECT id, channel_name, group_key, prompt, samples_json, count, first_at, last_at
+                 FROM webhook_batches WHERE channel_name = ?1 AND group_key = ?2",
+                params![channel_name, group_key],
+                Self::row_to_webhook_batch,
+            )
+            .optional()?;
+
+        let id = match existing {
+            Some(batch) => {
+                let mut samples = batch.samples;
+                if samples.len() < MAX_SAMPLES {
+                    samples.extend(sample.map(str::to_string));
+                }
+                db.execute(
+                    "UPDATE webhook_batches SET count = count + 1, last_at = ?1, samples_json = ?2
+                     WHERE id = ?3",
+                    params![now_ms, serde_json::to_string(&samples)?, batch.id],
+                )?;
+                batch.id
+            }
+            None => {
+                let samples: Vec<&str> = sample.into_iter().collect();
+                db.execute(
+                    "INSERT INTO webhook_batches
+                        (channel_name, group_key, prompt, samples_json, count, first_at, last_at, flush_at)
+                     VALUES (?1, ?2, ?3, ?4, 1, ?5, ?5, ?6)",
+                    params![
+                        channel_name,
+                        group_key,
+                        prompt,
+                        serde_json::to_string(&samples)?,
+                        now_ms,
+                        (now + window).timestamp_millis()
+                    ],
+                )?;
+                db.last_insert_rowid()
+            }
+        };
+
+        let batch = db.query_row(
+            "SELECT id, channel_name, group_key, prompt, samples_json, count, first_at, last_at
+             FROM webhook_batches WHERE id = ?1",
+            params![id],
+            Self::row_to_webhook_batch,
+        )?;
+        Ok(batch)
+    }
+
+    /// Remove and return every batch whose window closed at or before `now`, oldest first
+    pub fn take_due_webhook_batches(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<WebhookBatch>> {
+        self.take_webhook_batches(now.timestamp_millis())
+    }
+
+    /// Remove and return every pending batch, window or not; used on startup
+    /// to flush what was held when the process stopped
+    pub fn take_all_webhook_batches(&self) -> Result<Vec<WebhookBatch>> {
+        self.take_webhook_batches(i64::MAX)
+    }
+
+    fn take_webhook_batches(&self, flush_by_ms: i64) -> Result<Vec<WebhookBatch>> {
+        let mut db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let tx = db.transaction()?;
+        let batches = tx
+            .prepare(
+                "SELECT id, channel_name, group_key, prompt, samples_json, count, first_at, last_at
+                 FROM webhook_batches WHERE flush_at <= ?1 ORDER BY first_at ASC",
+            )?
+            .query_map(params![flush_by_ms], Self::row_to_webhook_batch)?
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.execute(
+            "DELETE FROM webhook_batches WHERE flush_at <= ?1",
+            params![flush_by_ms],
+        )?;
+        tx.commit()?;
+        Ok(batches)
+    }
+
+    fn row_to_webhook_batch(row: &rusqlite::Row) -> rusqlite::Result<WebhookBatch> {
+        let timestamp = |i: usize| -> rusqlite::Result<chrono::DateTime<chrono::Utc>> {
+            let ms: i64 = row.get(i)?;
+            Ok(chrono::DateTime::from_timestamp_millis(ms).unwrap_or_default())
+        };
+        let samples_json: String = row.get(4)?;
+        Ok(WebhookBatch {
+            id: row.get(0)?,
+            channel_name: row.get(1)?,
+            group_key: row.get(2)?,
+            prompt: row.get(3)?,
+            samples: serde_json::from_str(&samples_json).unwrap_or_default(),
+            count: row.get(5)?,
+            first_at: timestamp(6)?,
+            last_at: timestamp(7)?,
+        })
+    }
+
+    // =========================================================================
+    // Usage Accounting
+    // =========================================================================
+
+    /// Add one invocation to the channel's totals in the bucket for `origin`.
+    /// `usage` is None when the backend doesn't report token counts.
+    pub fn record_usage(
+        &self,
+        channel_name: &str,
+        origin: InvocationOrigin,
+        usage: Option<&gorp_agent::Usage>,
+    ) -> Result<()> {
+        let (input, output, cost_cents) = usage
+            .map(|u| {
+                (
+                    u.input_tokens as i64,
+                    u.output_tokens as i64,
+                    u.cost_usd.map(|c| (c * 100.0).round() as i64).unwrap_or(0),
+                )
+            })
+            .unwrap_or((0, 0, 0));
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute(
+            "INSERT INTO channel_usage
+                (channel_name, bucket, invocations, input_tokens, output_tokens, cost_cents)
+             VALUES (?1, ?2, 1, ?3, ?4, ?5)
+             ON CONFLICT(channel_name, bucket) DO UPDATE SET
+                invocations = invocations + 1,
+                input_tokens = input_tokens + excluded.input_tokens,
+                output_tokens = output_tokens + excluded.output_tokens,
+                cost_cents = cost_cents + excluded.cost_cents",
+            params![
+                channel_name,
+                origin.bucket().as_str(),
+                input,
+                output,
+                cost_cents
+            ],
+        )?;
+        drop(db);
+        self.add_daily_spend(channel_name, chrono::Utc::now().date_naive(), cost_cents)
+    }
+
+    /// Add to a channel's spend for one day; every invocation gets a row, even a free one
+    fn add_daily_spend(
+        &self,
+        channel_name: &str,
+        day: chrono::NaiveDate,
+        cost_cents: i64,
+    ) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute(
+            "INSERT INTO channel_usage_daily (channel_name, day, cost_cents)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(channel_name, day) DO UPDATE SET
+                cost_cents = cost_cents + excluded.cost_cents",
+            params![channel_name, day.to_string(), cost_cents],
+        )?;
+        Ok(())
+    }
+
+    /// A channel's spend per day from `since` on, oldest first.
+    /// Also returns the first day any usage was recorded, None if there never was any.
+    pub fn get_daily_spend(
+        &self,
+        channel_name: &str,
+        since: chrono::NaiveDate,
+    ) -> Result<(Vec<(chrono::NaiveDate, u64)>, Option<chrono::NaiveDate>)> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let first: Option<String> = db.query_row(
+            "SELECT MIN(day) FROM channel_usage_daily WHERE channel_name = ?1",
+            params![channel_name],
+            |row| row.get(0),
+        )?;
+        let mut stmt = db.prepare(
+            "SELECT day, cost_cents FROM channel_usage_daily
+             WHERE channel_name = ?1 AND day >= ?2 ORDER BY day",
+        )?;
+        let rows = stmt
+            .query_map(params![channel_name, since.to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let daily = rows
+            .into_iter()
+            .filter_map(|(day, cents)| Some((day.parse().ok()?, cents as u64)))
+            .collect();
+        Ok((daily, first.and_then(|day| day.parse().ok())))
+    }
+
+    /// Project a channel's month-end spend as of `today` against its budget.
+    /// None when the channel has no recorded usage.
+    pub fn get_spend_forecast(
+        &self,
+        channel_name: &str,
+        today: chrono::NaiveDate,
+    ) -> Result<Option<SpendForecast>> {
+        use chrono::Datelike;
+
+        // Enough history for both the averaging window and the month so far
+        let window_start = today - chrono::Duration::days(FORECAST_WINDOW_DAYS - 1);
+        let since = window_start.min(today.with_day(1).unwrap_or(today));
+        let (daily, tracked_since) = self.get_daily_spend(channel_name, since)?;
+        let budget = self.get_budget_cents(channel_name)?;
+        Ok(forecast_spend(&daily, tracked_since, today, budget))
+    }
+
+    // =========================================================================
+    // Budgets
+    // =========================================================================
+
+    /// Get a channel's monthly budget in cents, if one is set
+    pub fn get_budget_cents(&self, channel_name: &str) -> Result<Option<u64>> {
+        match self.get_setting(&format!("budget_cents:{}", channel_name))? {
+            Some(value) => Ok(Some(value.parse().context("Invalid channel budget")?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Set a channel's monthly budget; None removes it
+    pub fn set_budget_cents(&self, channel_name: &str, cents: Option<u64>) -> Result<()> {
+        self.put_or_clear_setting(
+            &format!("budget_cents:{}", channel_name),
+            cents.map(|c| c.to_string()).as_deref(),
+        )
+    }
+
+    /// When the channel was last warned that it is on course to overspend
+    pub fn get_budget_warned_at(
+        &self,
+        channel_name: &str,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        Ok(self
+            .get_setting(&format!("budget_warned_at:{}", channel_name))?
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(&value).ok())
+            .map(|at| at.with_timezone(&chrono::Utc)))
+    }
+
+    /// Remember that the channel was warned about its budget at `at`
+    pub fn set_budget_warned_at(
+        &self,
+        channel_name: &str,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        self.set_setting(
+            &format!("budget_warned_at:{}", channel_name),
+            &at.to_rfc3339(),
+        )
+    }
+
+    /// Lowest risk level whose tool calls need approval in this channel; None means no approvals
+    pub fn get_approval_level(&self, channel_name: &str) -> Result<Option<RiskLevel>> {
+        Ok(self
+            .get_setting(&format!("tool_approval:{}", channel_name))?
+            .and_then(|value| RiskLevel::parse(&value)))
+    }
+
+    /// Require approval for tool calls at or above `level`; None turns approvals off
+    pub fn set_approval_level(&self, channel_name: &str, level: Option<RiskLevel>) -> Result<()> {
+        self.put_or_clear_setting(
+            &format!("tool_approval:{}", channel_name),
+            level.map(|l| l.as_str()),
+        )
+    }
+
+    // =========================================================================
+    // Response Cache
+    // =========================================================================
+
+    /// Whether repeat prompts in this channel are answered from the response cache
+    pub fn is_response_cache_enabled(&self, channel_name: &str) -> Result<bool> {
+        Ok(self
+            .get_setting(&format!("response_cache:{}", channel_name))?
+            .is_some_and(|value| value == "on"))
+    }
+
+    /// Turn the response cache on or off; turning it off also drops cached answers
+    pub fn set_response_cache(&self, channel_name: &str, enabled: bool) -> Result<()> {
+        self.put_or_clear_setting(
+            &format!("response_cache:{}", channel_name),
+            enabled.then_some("on"),
+        )?;
+        if !enabled {
+            self.clear_response_cache(channel_name)?;
+        }
+        Ok(())
+    }
+
+    /// The cached answer to `prompt`, if it was given against this workspace `fingerprint`
+    pub fn get_cached_response(
+        &self,
+        channel_name: &str,
+        prompt: &str,
+        fingerprint: &str,
+    ) -> Result<Option<String>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let response = db
+            .query_row(
+                "SELECT response FROM response_cache
+                 WHERE channel_name = ?1 AND prompt = ?2 AND fingerprint = ?3",
+                params![channel_name, prompt, fingerprint],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(response)
+    }
+
+    /// Cache the answer to `prompt`, replacing any answer given against an older workspace
+    pub fn put_cached_response(
+        &self,
+        channel_name: &str,
+        prompt: &str,
+        fingerprint: &str,
+        response: &str,
+    ) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute(
+            "INSERT INTO response_cache (channel_name, prompt, fingerprint, response, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(channel_name, prompt) DO UPDATE SET
+                fingerprint = ?3, response = ?4, created_at = ?5",
+            params![
+                channel_name,
+                prompt,
+                fingerprint,
+                response,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Drop every cached answer in the channel. Returns how many there were.
+    pub fn clear_response_cache(&self, channel_name: &str) -> Result<usize> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let deleted = db.execute(
+            "DELETE FROM response_cache WHERE channel_name = ?1",
+            params![channel_name],
+        )?;
+        Ok(deleted)
+    }
+
+    /// How many answers the channel has cached
+    pub fn count_cached_responses(&self, channel_name: &str) -> Result<usize> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let count: i64 = db.query_row(
+            "SELECT COUNT(*) FROM response_cache WHERE channel_name = ?1",
+            params![channel_name],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    // =========================================================================
+    // Cloning
+    // =========================================================================
+
+    /// Copy a channel's configuration onto another channel: per-channel settings,
+    /// backend, prompt templates and the files under `.gorp/`. The target keeps
+    /// its own session, room and usage; its context file is rewritten on its
+    /// first turn, so the source's copy is not carried over.
+    pub fn copy_channel_config(&self, source: &Channel, target: &Channel) -> Result<()> {
+        for prefix in CLONED_SETTING_PREFIXES {
+            let value = self.get_setting(&format!("{}:{}", prefix, source.channel_name))?;
+            self.put_or_clear_setting(
+                &format!("{}:{}", prefix, target.channel_name),
+                value.as_deref(),
+            )?;
+        }
+        self.update_backend_type(&target.channel_name, source.backend_type.as_deref())?;
+
+        for template in self.list_templates(&source.channel_name)? {
+            self.save_template(
+                &target.channel_name,
+                &template.name,
+                &template.body,
+                &template.created_by,
+            )?;
+        }
+
+        let source_dir = Path::new(&source.directory).join(".gorp");
+        if source_dir.is_dir() {
+            let target_dir = Path::new(&target.directory).join(".gorp");
+            std::fs::create_dir_all(&target_dir)
+                .with_context(|| format!("Failed to create {}", target_dir.display()))?;
+            copy_dir_contents(&source_dir, &target_dir)?;
+            let context_path = target_dir.join("context.json");
+            if context_path.exists() {
+                std::fs::remove_file(&context_path)
+                    .context("Failed to remove copied context file")?;
+            }
+        }
+
+        tracing::info!(
+            source = %source.channel_name,
+            target = %target.channel_name,
+            "Copied channel configuration"
+        );
+        Ok(())
+    }
+
+    /// Get a channel's usage totals, split into conversation and overhead
+    pub fn get_channel_usage(&self, channel_name: &str) -> Result<ChannelUsage> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT bucket, invocations, input_tokens, output_tokens, cost_cents
+             FROM channel_usage WHERE channel_name = ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![channel_name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    UsageTotals {
+                        invocations: row.get::<_, i64>(1)? as u64,
+                        input_tokens: row.get::<_, i64>(2)? as u64,
+                        output_tokens: row.get::<_, i64>(3)? as u64,
+                        cost_cents: row.get::<_, i64>(4)? as u64,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut usage = ChannelUsage::default();
+        for (bucket, totals) in rows {
+            if bucket == UsageBucket::Overhead.as_str() {
+                usage.overhead = totals;
+            } else {
+                usage.conversation = totals;
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Record an experiment run such as a backend comparison
+    pub fn record_experiment(
+        &self,
+        channel_name: &str,
+        kind: &str,
+        prompt: &str,
+        details: &serde_json::Value,
+    ) -> Result<i64> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        db.execute(
+            "INSERT INTO experiments (channel_name, kind, prompt, details, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                channel_name,
+                kind,
+                prompt,
+                details.to_string(),
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(db.last_insert_rowid())
+    }
+
+    /// List a channel's experiments, oldest first
+    pub fn list_experiments(&self, channel_name: &str) -> Result<Vec<Experiment>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let mut stmt = db.prepare(
+            "SELECT id, channel_name, kind, prompt, details, created_at
+             FROM experiments WHERE channel_name = ?1 ORDER BY id ASC",
+        )?;
+        let experiments = stmt
+            .query_map(params![channel_name], |row| {
+                let details: String = row.get(4)?;
+                Ok(Experiment {
+                    id: row.get(0)?,
+                    channel_name: row.get(1)?,
+                    kind: row.get(2)?,
+                    prompt: row.get(3)?,
+                    details: serde_json::from_str(&details).unwrap_or_default(),
+                    created_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(experiments)
+    }
+
+    // =========================================================================
+    // Mux Session Persistence
+    // =========================================================================
+
+    /// Save a mux session's message history to the database
+    pub fn save_mux_session(
+        &self,
+        session_id: &str,
+        messages_json: &str,
+        system_prompt: Option<&str>,
+    ) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+
+        db.execute(
+            "INSERT INTO mux_sessions (session_id, messages_json, system_prompt, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(session_id) DO UPDATE SET
+                messages_json = ?2,
+                system_prompt = ?3,
+                updated_at = ?4",
+            params![session_id, messages_json, system_prompt, now],
+        )?;
+
+        tracing::debug!(session_id = %session_id, "Mux session saved");
+        Ok(())
+    }
+
+    /// Load a mux session's message history from the database
+    /// Returns (messages_json, system_prompt) if found
+    pub fn load_mux_session(&self, session_id: &str) -> Result<Option<(String, Option<String>)>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+
+        let mut stmt = db.prepare(
+            "SELECT messages_json, system_prompt FROM mux_sessions WHERE session_id = ?1",
+        )?;
+
+        let result = stmt.query_row(params![session_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        });
+
+        match result {
+            Ok((messages, prompt)) => {
+                tracing::debug!(session_id = %session_id, "Mux session loaded");
+                Ok(Some((messages, prompt)))
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Delete a mux session from the database
+    pub fn delete_mux_session(&self, session_id: &str) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+
+        db.execute(
+            "DELETE FROM mux_sessions WHERE session_id = ?1",
+            params![session_id],
+        )?;
+
+        tracing::debug!(session_id = %session_id, "Mux session deleted");
+        Ok(())
+    }
+
+    /// Check if a mux session exists in the database
+    pub fn mux_session_exists(&self, session_id: &str) -> Result<bool> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+
+        let mut stmt = db.prepare("SELECT 1 FROM mux_sessions WHERE session_id = ?1")?;
+        let exists = stmt.exists(params![session_id])?;
+        Ok(exists)
+    }
+
+    // =========================================================================
+    // Dispatch Event Persistence
+    // =========================================================================
+
+    /// Insert a dispatch event
+    pub fn insert_dispatch_event(&self, event: &DispatchEvent) -> Result<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
+        let payload_str = serde_json::to_string(&event.payload)?;
         db.execute(
             "INSERT INTO dispatch_events (id, source_room_id, event_type, payload, created_at, acknowledged_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
@@ -1210,11 +2685,7 @@ impl SessionStore {
 
     /// Look up which session name a platform channel is bound to.
     /// Returns None if no binding exists.
-    pub fn resolve_binding(
-        &self,
-        platform_id: &str,
-        channel_id: &str,
-    ) -> Result<Option<String>> {
+    pub fn resolve_binding(&self, platform_id: &str, channel_id: &str) -> Result<Option<String>> {
         let db = self
             .db
             .lock()
@@ -1231,10 +2702,7 @@ impl SessionStore {
     }
 
     /// List all (platform_id, channel_id) pairs bound to a given session name.
-    pub fn list_bindings_for_session(
-        &self,
-        session_name: &str,
-    ) -> Result<Vec<(String, String)>> {
+    pub fn list_bindings_for_session(&self, session_name: &str) -> Result<Vec<(String, String)>> {
         let db = self
             .db
             .lock()
@@ -1259,9 +2727,8 @@ impl SessionStore {
             .db
             .lock()
             .map_err(|e| anyhow::anyhow!("Database mutex poisoned: {}", e))?;
-        let mut stmt = db.prepare(
-            "SELECT platform_id, channel_id, session_name FROM channel_bindings",
-        )?;
+        let mut stmt =
+            db.prepare("SELECT platform_id, channel_id, session_name FROM channel_bindings")?;
         let rows = stmt.query_map([], |row| {
             Ok((
                 row.get::<_, String>(0)?,
@@ -1292,14 +2759,643 @@ mod tests {
     }
 
     #[test]
-    fn test_create_and_list_channel_bindings() {
+    fn test_delivery_window_round_trip() {
+        let (store, _dir) = create_test_store();
+        assert!(store.get_delivery_window("ops").unwrap().is_none());
+
+        let window = DeliveryWindow::parse("weekdays 08:00-18:00 Europe/Berlin", "UTC").unwrap();
+        store.set_delivery_window("ops", &window).unwrap();
+        assert_eq!(store.get_delivery_window("ops").unwrap(), Some(window));
+
+        store.clear_delivery_window("ops").unwrap();
+        assert!(store.get_delivery_window("ops").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_held_messages_flush_in_order() {
         let (store, _dir) = create_test_store();
+        store.hold_message("ops", None, "first").unwrap();
         store
-            .bind_channel("matrix", "!room1:m.org", "research")
+            .hold_message("ops", Some(("matrix", "!ops:m.org")), "second")
+            .unwrap();
+        store.hold_message("other", None, "unrelated").unwrap();
+        store.hold_message("ops", None, "third").unwrap();
+
+        let held = store.list_held_messages("ops").unwrap();
+        let bodies: Vec<&str> = held.iter().map(|m| m.body.as_str()).collect();
+        assert_eq!(bodies, vec!["first", "second", "third"]);
+        assert_eq!(held[1].platform_id.as_deref(), Some("matrix"));
+        assert_eq!(held[1].target_id.as_deref(), Some("!ops:m.org"));
+        assert!(held[0].platform_id.is_none());
+
+        assert_eq!(
+            store.channels_with_held_messages().unwrap(),
+            vec!["ops".to_string(), "other".to_string()]
+        );
+
+        store.remove_held_message(held[0].id).unwrap();
+        let remaining = store.list_held_messages("ops").unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].body, "second");
+    }
+
+    fn usage(input: u64, output: u64, cost_usd: f64) -> gorp_agent::Usage {
+        gorp_agent::Usage {
+            input_tokens: input,
+            output_tokens: output,
+            cost_usd: Some(cost_usd),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_usage_bucketing_per_origin() {
+        let (store, _dir) = create_test_store();
+        store
+            .record_usage("ops", InvocationOrigin::User, Some(&usage(100, 50, 0.10)))
+            .unwrap();
+        store
+            .record_usage("ops", InvocationOrigin::Webhook, Some(&usage(10, 5, 0.01)))
+            .unwrap();
+        store
+            .record_usage("ops", InvocationOrigin::Schedule, None)
+            .unwrap();
+        store
+            .record_usage("ops", InvocationOrigin::Internal, Some(&usage(7, 3, 0.02)))
+            .unwrap();
+
+        let totals = store.get_channel_usage("ops").unwrap();
+        assert_eq!(
+            totals.conversation,
+            UsageTotals {
+                invocations: 3,
+                input_tokens: 110,
+                output_tokens: 55,
+                cost_cents: 11,
+            }
+        );
+        assert_eq!(
+            totals.overhead,
+            UsageTotals {
+                invocations: 1,
+                input_tokens: 7,
+                output_tokens: 3,
+                cost_cents: 2,
+            }
+        );
+        assert_eq!(
+            store.get_channel_usage("other").unwrap(),
+            ChannelUsage::default()
+        );
+    }
+
+    #[test]
+    fn test_daily_spend_and_forecast() {
+        use chrono::NaiveDate;
+
+        let (store, _dir) = create_test_store();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        for d in 1..=10 {
+            store.add_daily_spend("news", day(d), 25).unwrap();
+            store.add_daily_spend("news", day(d), 25).unwrap();
+        }
+        store.add_daily_spend("other", day(10), 999).unwrap();
+
+        let (daily, first) = store.get_daily_spend("news", day(9)).unwrap();
+        assert_eq!(daily, vec![(day(9), 50), (day(10), 50)]);
+        assert_eq!(first, Some(day(1)));
+
+        assert_eq!(store.get_spend_forecast("quiet", day(10)).unwrap(), None);
+
+        store.set_budget_cents("news", Some(1000)).unwrap();
+        assert_eq!(store.get_budget_cents("news").unwrap(), Some(1000));
+        let forecast = store.get_spend_forecast("news", day(10)).unwrap().unwrap();
+        assert_eq!(forecast.month_to_date_cents, 500);
+        assert_eq!(forecast.projected_month_cents, 1550);
+        assert_eq!(forecast.exceeds_budget_on, Some(day(21)));
+
+        store.set_budget_cents("news", None).unwrap();
+        assert_eq!(store.get_budget_cents("news").unwrap(), None);
+    }
+
+    #[test]
+    fn test_record_usage_tracks_todays_spend() {
+        let (store, _dir) = create_test_store();
+        store
+            .record_usage("ops", InvocationOrigin::User, Some(&usage(100, 50, 0.10)))
+            .unwrap();
+        store
+            .record_usage("ops", InvocationOrigin::Internal, Some(&usage(7, 3, 0.02)))
+            .unwrap();
+
+        let today = chrono::Utc::now().date_naive();
+        let (daily, first) = store.get_daily_spend("ops", today).unwrap();
+        assert_eq!(daily, vec![(today, 12)]);
+        assert_eq!(first, Some(today));
+    }
+
+    #[test]
+    fn test_templates_are_per_channel() {
+        let (store, _dir) = create_test_store();
+        store
+            .save_template("news", "daily", "Summarize the news", "@alice:example.com")
+            .unwrap();
+        store
+            .save_template(
+                "news",
+                "brief",
+                "One line on {{args}}",
+                "@alice:example.com",
+            )
+            .unwrap();
+        store
+            .save_template("ops", "daily", "Check the alerts", "@bob:example.com")
+            .unwrap();
+
+        let names: Vec<String> = store
+            .list_templates("news")
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["brief", "daily"]);
+
+        store
+            .save_template("news", "daily", "Summarize world news", "@bob:example.com")
+            .unwrap();
+        let daily = store.get_template("news", "daily").unwrap().unwrap();
+        assert_eq!(daily.body, "Summarize world news");
+        assert_eq!(daily.created_by, "@bob:example.com");
+        assert_eq!(
+            store.get_template("ops", "daily").unwrap().unwrap().body,
+            "Check the alerts"
+        );
+
+        assert!(store.delete_template("news", "daily").unwrap());
+        assert!(!store.delete_template("news", "daily").unwrap());
+        assert!(store.get_template("news", "daily").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_copy_channel_config() {
+        let (store, _dir) = create_test_store();
+        let source = store.create_channel("research", "!research:m.org").unwrap();
+        store.update_backend_type("research", Some("mux")).unwrap();
+        store.set_channel_locale("research", Some("de")).unwrap();
+        store.set_budget_cents("research", Some(2500)).unwrap();
+        store
+            .save_template("research", "daily", "Summarize arXiv", "@alice:m.org")
+            .unwrap();
+        let gorp_dir = Path::new(&source.directory).join(".gorp");
+        std::fs::create_dir_all(gorp_dir.join("prompts")).unwrap();
+        std::fs::write(gorp_dir.join("warmup_prompt"), "You are a researcher").unwrap();
+        std::fs::write(gorp_dir.join("prompts/style.md"), "Cite sources").unwrap();
+        std::fs::write(gorp_dir.join("context.json"), "{}").unwrap();
+        let source = store.get_by_name("research").unwrap().unwrap();
+
+        let target = store.create_channel("research-slack", "C0123").unwrap();
+        store.copy_channel_config(&source, &target).unwrap();
+
+        let copied = store.get_by_name("research-slack").unwrap().unwrap();
+        assert_eq!(copied.backend_type.as_deref(), Some("mux"));
+        assert_ne!(copied.session_id, source.session_id);
+        assert_eq!(
+            store
+                .get_channel_locale("research-slack")
+                .unwrap()
+                .as_deref(),
+            Some("de")
+        );
+        assert_eq!(
+            store.get_budget_cents("research-slack").unwrap(),
+            Some(2500)
+        );
+        assert_eq!(store.get_approval_level("research-slack").unwrap(), None);
+        let daily = store
+            .get_template("research-slack", "daily")
+            .unwrap()
+            .unwrap();
+        assert_eq!(daily.body, "Summarize arXiv");
+
+        let target_dir = Path::new(&target.directory).join(".gorp");
+        assert_eq!(
+            std::fs::read_to_string(target_dir.join("warmup_prompt")).unwrap(),
+            "You are a researcher"
+        );
+        assert_eq!(
+            std::fs::read_to_string(target_dir.join("prompts/style.md")).unwrap(),
+            "Cite sources"
+        );
+        assert!(!target_dir.join("context.json").exists());
+    }
+
+    #[test]
+    fn test_template_capture_is_taken_once() {
+        let (store, _dir) = create_test_store();
+        store
+            .set_template_capture("!room:example.com", "@alice:example.com", Some("daily"))
+            .unwrap();
+
+        assert_eq!(
+            store
+                .take_template_capture("!room:example.com", "@bob:example.com")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            store
+                .take_template_capture("!room:example.com", "@alice:example.com")
+                .unwrap()
+                .as_deref(),
+            Some("daily")
+        );
+        assert_eq!(
+            store
+                .take_template_capture("!room:example.com", "@alice:example.com")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_approval_level_per_channel() {
+        let (store, _dir) = create_test_store();
+        assert_eq!(store.get_approval_level("ops").unwrap(), None);
+
+        store
+            .set_approval_level("ops", Some(RiskLevel::Medium))
+            .unwrap();
+        assert_eq!(
+            store.get_approval_level("ops").unwrap(),
+            Some(RiskLevel::Medium)
+        );
+        assert_eq!(store.get_approval_level("news").unwrap(), None);
+
+        store.set_approval_level("ops", None).unwrap();
+        assert_eq!(store.get_approval_level("ops").unwrap(), None);
+    }
+
+    #[test]
+    fn test_channel_members_recorded_once() {
+        let (store, _tmp) = create_test_store();
+        store
+            .create_channel("research", "!research:example.org")
+            .unwrap();
+
+        assert!(store
+            .add_channel_member("research", "@bob:example.org", "@alice:example.org")
+            .unwrap());
+        assert!(store
+            .add_channel_member("research", "@carol:example.org", "@alice:example.org")
+            .unwrap());
+        assert!(!store
+            .add_channel_member("research", "@bob:example.org", "@dave:example.org")
+            .unwrap());
+
+        let members = store.list_channel_members("research").unwrap();
+        let ids: Vec<&str> = members.iter().map(|m| m.user_id.as_str()).collect();
+        assert_eq!(ids, vec!["@bob:example.org", "@carol:example.org"]);
+        assert_eq!(members[0].invited_by, "@alice:example.org");
+        assert!(store.list_channel_members("other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_response_cache_matches_fingerprint() {
+        let (store, _dir) = create_test_store();
+        assert!(!store.is_response_cache_enabled("code").unwrap());
+        store.set_response_cache("code", true).unwrap();
+        assert!(store.is_response_cache_enabled("code").unwrap());
+
+        store
+            .put_cached_response("code", "what does main do?", "fp1", "It starts the bot")
+            .unwrap();
+        assert_eq!(
+            store
+                .get_cached_response("code", "what does main do?", "fp1")
+                .unwrap()
+                .as_deref(),
+            Some("It starts the bot")
+        );
+        assert_eq!(
+            store
+                .get_cached_response("code", "what does main do?", "fp2")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            store
+                .get_cached_response("news", "what does main do?", "fp1")
+                .unwrap(),
+            None
+        );
+
+        // A newer answer replaces the old one rather than piling up
+        store
+            .put_cached_response("code", "what does main do?", "fp2", "It exits")
+            .unwrap();
+        assert_eq!(store.count_cached_responses("code").unwrap(), 1);
+
+        store.set_response_cache("code", false).unwrap();
+        assert!(!store.is_response_cache_enabled("code").unwrap());
+        assert_eq!(store.count_cached_responses("code").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_pending_invites_queue_and_take() {
+        let (store, _dir) = create_test_store();
+        assert!(store.list_pending_invites().unwrap().is_empty());
+
+        assert!(store
+            .queue_invite("!a:example.com", "@eve:example.com", Some("Ops"))
+            .unwrap());
+        assert!(!store
+            .queue_invite("!a:example.com", "@eve:example.com", Some("Ops"))
+            .unwrap());
+        assert!(store
+            .queue_invite("!b:example.com", "@bob:example.com", None)
+            .unwrap());
+
+        let pending = store.list_pending_invites().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].room_name.as_deref(), Some("Ops"));
+
+        let taken = store
+            .take_pending_invite("!a:example.com")
+            .unwrap()
+            .unwrap();
+        assert_eq!(taken.inviter, "@eve:example.com");
+        assert!(store
+            .take_pending_invite("!a:example.com")
+            .unwrap()
+            .is_none());
+        assert_eq!(store.list_pending_invites().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_channel_and_user_locales() {
+        let (store, _dir) = create_test_store();
+        assert_eq!(store.get_channel_locale("ops").unwrap(), None);
+
+        store.set_channel_locale("ops", Some("es")).unwrap();
+        store
+            .set_user_locale("@ana:example.com", Some("pt-BR"))
+            .unwrap();
+        assert_eq!(
+            store.get_channel_locale("ops").unwrap().as_deref(),
+            Some("es")
+        );
+        assert_eq!(
+            store
+                .get_user_locale("@ana:example.com")
+                .unwrap()
+                .as_deref(),
+            Some("pt-BR")
+        );
+
+        store.set_channel_locale("ops", None).unwrap();
+        assert_eq!(store.get_channel_locale("ops").unwrap(), None);
+    }
+
+    #[test]
+    fn test_send_guard_toggle_is_per_user() {
+        let (store, _dir) = create_test_store();
+        assert!(!store.is_send_guard_enabled("@ana:example.com").unwrap());
+
+        store.set_send_guard("@ana:example.com", true).unwrap();
+        assert!(store.is_send_guard_enabled("@ana:example.com").unwrap());
+        assert!(!store.is_send_guard_enabled("@bo:example.com").unwrap());
+
+        store.set_send_guard("@ana:example.com", false).unwrap();
+        assert!(!store.is_send_guard_enabled("@ana:example.com").unwrap());
+    }
+
+    #[test]
+    fn test_drafts_are_per_user_and_channel() {
+        let (store, _dir) = create_test_store();
+        store
+            .save_draft("matrix", "!ops:example.com", "@ana:example.com", "first")
+            .unwrap();
+        store
+            .save_draft(
+                "matrix",
+                "!dev:example.com",
+                "@ana:example.com",
+                "other room",
+            )
+            .unwrap();
+        store
+            .save_draft(
+                "matrix",
+                "!ops:example.com",
+                "@bo:example.com",
+                "other user",
+            )
+            .unwrap();
+
+        let created = store
+            .get_draft("!ops:example.com", "@ana:example.com")
+            .unwrap()
+            .unwrap();
+        store
+            .save_draft(
+                "matrix",
+                "!ops:example.com",
+                "@ana:example.com",
+                "first second",
+            )
+            .unwrap();
+        let updated = store
+            .get_draft("!ops:example.com", "@ana:example.com")
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.body, "first second");
+        assert_eq!(updated.created_at, created.created_at);
+        assert_eq!(store.list_drafts().unwrap().len(), 3);
+
+        let taken = store
+            .take_draft("!ops:example.com", "@ana:example.com")
+            .unwrap()
+            .unwrap();
+        assert_eq!(taken.body, "first second");
+        assert!(store
+            .take_draft("!ops:example.com", "@ana:example.com")
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            store
+                .get_draft("!ops:example.com", "@bo:example.com")
+                .unwrap()
+                .unwrap()
+                .body,
+            "other user"
+        );
+    }
+
+    #[test]
+    fn test_drafts_survive_reopening_the_store() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = SessionStore::new(dir.path()).unwrap();
+            store
+                .save_draft("telegram", "-100", "42", "long prompt")
+                .unwrap();
+        }
+        let store = SessionStore::new(dir.path()).unwrap();
+        assert_eq!(
+            store.get_draft("-100", "42").unwrap().unwrap().body,
+            "long prompt"
+        );
+    }
+
+    #[test]
+    fn test_discard_draft_if_unchanged_skips_appended_drafts() {
+        let (store, _dir) = create_test_store();
+        store.save_draft("telegram", "-100", "42", "v1").unwrap();
+        let stale = store.get_draft("-100", "42").unwrap().unwrap();
+
+        let appended = Draft {
+            updated_at: "2999-01-01T00:00:00+00:00".to_string(),
+            ..stale.clone()
+        };
+        assert!(!store.discard_draft_if_unchanged(&appended).unwrap());
+        assert!(store.discard_draft_if_unchanged(&stale).unwrap());
+        assert!(store.get_draft("-100", "42").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_transcription_language_defaults_to_auto_detect() {
+        let (store, _dir) = create_test_store();
+        assert_eq!(store.get_transcription_language("ops").unwrap(), None);
+
+        store.set_transcription_language("ops", Some("es")).unwrap();
+        assert_eq!(
+            store.get_transcription_language("ops").unwrap().as_deref(),
+            Some("es")
+        );
+        assert_eq!(store.get_transcription_language("other").unwrap(), None);
+
+        store.set_transcription_language("ops", None).unwrap();
+        assert_eq!(store.get_transcription_language("ops").unwrap(), None);
+    }
+
+    #[test]
+    fn test_awaiting_reply_is_per_channel() {
+        let (store, _dir) = create_test_store();
+        assert_eq!(store.get_awaiting_reply("ops").unwrap(), None);
+
+        store
+            .set_awaiting_reply("ops", Some("Which branch?"))
+            .unwrap();
+        assert_eq!(
+            store.get_awaiting_reply("ops").unwrap().as_deref(),
+            Some("Which branch?")
+        );
+        assert_eq!(store.get_awaiting_reply("other").unwrap(), None);
+
+        store.set_awaiting_reply("ops", None).unwrap();
+        assert_eq!(store.get_awaiting_reply("ops").unwrap(), None);
+    }
+
+    #[test]
+    fn test_room_topic_is_per_channel() {
+        let (store, _dir) = create_test_store();
+        assert_eq!(store.get_room_topic("ops").unwrap(), None);
+
+        store.set_room_topic("ops", Some("Deploys")).unwrap();
+        assert_eq!(
+            store.get_room_topic("ops").unwrap().as_deref(),
+            Some("Deploys")
+        );
+        assert_eq!(store.get_room_topic("other").unwrap(), None);
+
+        store.set_room_topic("ops", None).unwrap();
+        assert_eq!(store.get_room_topic("ops").unwrap(), None);
+    }
+
+    #[test]
+    fn test_attachment_max_bytes_override() {
+        let (store, _dir) = create_test_store();
+        assert_eq!(store.get_attachment_max_bytes("media").unwrap(), None);
+
+        store
+            .set_attachment_max_bytes("media", Some(200 * 1024 * 1024))
+            .unwrap();
+        assert_eq!(
+            store.get_attachment_max_bytes("media").unwrap(),
+            Some(200 * 1024 * 1024)
+        );
+
+        store.set_attachment_max_bytes("media", None).unwrap();
+        assert_eq!(store.get_attachment_max_bytes("media").unwrap(), None);
+    }
+
+    #[test]
+    fn test_webhook_batches_group_and_flush_when_due() {
+        let (store, _dir) = create_test_store();
+        let start = chrono::Utc::now();
+        let window = chrono::Duration::seconds(60);
+
+        store
+            .add_webhook_delivery("alerts", "DiskFull", "Check disk", Some("a"), window, start)
+            .unwrap();
+        store
+            .add_webhook_delivery("alerts", "CpuHigh", "Check cpu", None, window, start)
+            .unwrap();
+        let later = start + chrono::Duration::seconds(30);
+        let batch = store
+            .add_webhook_delivery("alerts", "DiskFull", "Check disk", Some("b"), window, later)
+            .unwrap();
+        assert_eq!(batch.count, 2);
+        assert_eq!(batch.samples, vec!["a".to_string(), "b".to_string()]);
+
+        // Later deliveries don't push the window out
+        assert!(store.take_due_webhook_batches(later).unwrap().is_empty());
+        let due = store.take_due_webhook_batches(start + window).unwrap();
+        assert_eq!(due.len(), 2);
+        assert!(store.take_all_webhook_batches().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_custom_context_round_trip() {
+        let (store, _dir) = create_test_store();
+        assert!(store.get_custom_context("ops").unwrap().is_empty());
+
+        let context = BTreeMap::from([
+            ("project".to_string(), "apollo".to_string()),
+            ("owner".to_string(), "ana".to_string()),
+        ]);
+        store.set_custom_context("ops", &context).unwrap();
+        assert_eq!(store.get_custom_context("ops").unwrap(), context);
+        assert!(store.get_custom_context("other").unwrap().is_empty());
+
+        store.set_custom_context("ops", &BTreeMap::new()).unwrap();
+        assert!(store.get_custom_context("ops").unwrap().is_empty());
+        assert_eq!(store.get_setting("context:ops").unwrap(), None);
+    }
+
+    #[test]
+    fn test_record_and_list_experiments() {
+        let (store, _dir) = create_test_store();
+        let details = serde_json::json!({"profiles": ["acp", "mux"]});
+        store
+            .record_experiment("ops", "compare", "hello", &details)
             .unwrap();
+
+        let experiments = store.list_experiments("ops").unwrap();
+        assert_eq!(experiments.len(), 1);
+        assert_eq!(experiments[0].kind, "compare");
+        assert_eq!(experiments[0].prompt, "hello");
+        assert_eq!(experiments[0].details, details);
+        assert!(store.list_experiments("other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_create_and_list_channel_bindings() {
+        let (store, _dir) = create_test_store();
         store
-            .bind_channel("slack", "C12345", "research")
+            .bind_channel("matrix", "!room1:m.org", "research")
             .unwrap();
+        store.bind_channel("slack", "C12345", "research").unwrap();
         let bindings = store.list_bindings_for_session("research").unwrap();
         assert_eq!(bindings.len(), 2);
     }
@@ -1367,4 +3463,67 @@ mod tests {
         // Should not error when unbinding a channel that doesn't exist
         store.unbind_channel("matrix", "!noroom:m.org").unwrap();
     }
+
+    #[test]
+    fn test_platform_scope_allows_the_same_name_on_each_platform() {
+        let (store, _dir) = create_test_store();
+        let store = store.with_channel_scope(ChannelScope::Platform);
+
+        let matrix = store
+            .create_channel_on("matrix", "PA", "!pa:m.org")
+            .unwrap();
+        let slack = store.create_channel_on("slack", "pa", "C123").unwrap();
+        assert_eq!(matrix.channel_name, "pa");
+        assert_eq!(slack.channel_name, "pa-slack");
+        assert_ne!(matrix.directory, slack.directory);
+
+        let found = store.get_by_name_on("slack", "PA").unwrap().unwrap();
+        assert_eq!(found.room_id, "C123");
+        let found = store.get_by_name_on("matrix", "pa").unwrap().unwrap();
+        assert_eq!(found.room_id, "!pa:m.org");
+        assert!(store.get_by_name_on("telegram", "pa").unwrap().is_none());
+
+        let err = store.create_channel_on("slack", "pa", "C456").unwrap_err();
+        assert!(err.to_string().contains("already exists on slack"));
+    }
+
+    #[test]
+    fn test_list_on_only_returns_that_platforms_channels() {
+        let (store, _dir) = create_test_store();
+        store
+            .create_channel_on("matrix", "notes", "!notes:m.org")
+            .unwrap();
+        store.create_channel_on("telegram", "ops", "-1001").unwrap();
+
+        let telegram = store.list_on("telegram").unwrap();
+        assert_eq!(telegram.len(), 1);
+        assert_eq!(telegram[0].room_id, "-1001");
+        assert!(store.list_on("slack").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_global_scope_keeps_names_unique_across_platforms() {
+        let (store, _dir) = create_test_store();
+        store
+            .create_channel_on("matrix", "pa", "!pa:m.org")
+            .unwrap();
+        assert!(store.create_channel_on("slack", "pa", "C123").is_err());
+        // Any platform finds the one channel
+        let found = store.get_by_name_on("slack", "pa").unwrap().unwrap();
+        assert_eq!(found.room_id, "!pa:m.org");
+    }
+
+    #[test]
+    fn test_platform_scope_finds_channels_created_before_platforms_were_recorded() {
+        let (store, dir) = create_test_store();
+        store.create_channel("legacy", "C999").unwrap();
+        drop(store);
+
+        // Reopening backfills what it can; a non-Matrix room without a binding stays unscoped
+        let store = SessionStore::new(dir.path())
+            .unwrap()
+            .with_channel_scope(ChannelScope::Platform);
+        let found = store.get_by_name_on("slack", "legacy").unwrap().unwrap();
+        assert_eq!(found.room_id, "C999");
+    }
 }
@@ -0,0 +1,130 @@
+// ABOUTME: Reusable per-channel prompt templates for !template and `template:<name>` schedules.
+// ABOUTME: Holds the template type, name rules and {{args}} rendering; storage lives in SessionStore.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Placeholder replaced with the extra context given to `!template run`
+pub const ARGS_PLACEHOLDER: &str = "{{args}}";
+
+/// Prefix that makes a schedule run a template instead of a literal prompt
+pub const SCHEDULE_PREFIX: &str = "template:";
+
+/// Longest allowed template name
+const MAX_NAME_LEN: usize = 50;
+
+/// A saved prompt, owned by one channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub channel_name: String,
+    pub name: String,
+    pub body: String,
+    pub created_by: String,
+    pub updated_at: String,
+}
+
+/// Check a template name: letters, digits, dashes and underscores only
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        bail!("Template names must be 1-{} characters", MAX_NAME_LEN);
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("Template names can only contain letters, numbers, dashes and underscores");
+    }
+    Ok(())
+}
+
+/// Fill in a template body.
+///
+/// Every `{{args}}` is replaced with `args`. A template without the placeholder
+/// gets non-empty `args` appended as a separate paragraph, so extra context is
+/// never silently dropped.
+pub fn render(body: &str, args: &str) -> String {
+    let args = args.trim();
+    if body.contains(ARGS_PLACEHOLDER) {
+        return body.replace(ARGS_PLACEHOLDER, args).trim().to_string();
+    }
+    if args.is_empty() {
+        body.to_string()
+    } else {
+        format!("{}\n\n{}", body.trim_end(), args)
+    }
+}
+
+/// The template name a schedule prompt refers to, if it is `template:<name>`
+pub fn schedule_reference(prompt: &str) -> Option<&str> {
+    prompt
+        .trim()
+        .strip_prefix(SCHEDULE_PREFIX)
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.contains(char::is_whitespace))
+}
+
+/// What follows the first `words` whitespace-separated words of `raw`, with
+/// its own line breaks intact (for template bodies given inline).
+pub fn rest_after_words(raw: &str, words: usize) -> &str {
+    let mut rest = raw.trim_start();
+    for _ in 0..words {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        rest = rest[end..].trim_start();
+    }
+    rest.trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("daily-email_2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name("emoji📧").is_err());
+        assert!(validate_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_render_substitutes_args() {
+        let body = "Summarize today's unread email about {{args}}, grouped by sender.";
+        assert_eq!(
+            render(body, " the launch "),
+            "Summarize today's unread email about the launch, grouped by sender."
+        );
+        assert_eq!(render("{{args}}", ""), "");
+    }
+
+    #[test]
+    fn test_render_appends_args_without_placeholder() {
+        assert_eq!(render("Summarize my inbox.", ""), "Summarize my inbox.");
+        assert_eq!(
+            render("Summarize my inbox.\n", "only from Alice"),
+            "Summarize my inbox.\n\nonly from Alice"
+        );
+    }
+
+    #[test]
+    fn test_schedule_reference() {
+        assert_eq!(
+            schedule_reference("template:daily-email"),
+            Some("daily-email")
+        );
+        assert_eq!(schedule_reference(" template: standup "), Some("standup"));
+        assert_eq!(schedule_reference("template:"), None);
+        assert_eq!(schedule_reference("template:two words"), None);
+        assert_eq!(schedule_reference("check my inbox"), None);
+    }
+
+    #[test]
+    fn test_rest_after_words_keeps_line_breaks() {
+        assert_eq!(
+            rest_after_words("save daily Summarize:\n- inbox\n- calendar", 2),
+            "Summarize:\n- inbox\n- calendar"
+        );
+        assert_eq!(rest_after_words("save daily", 2), "");
+        assert_eq!(rest_after_words("  run daily  extra  ", 2), "extra");
+    }
+}
@@ -1,11 +1,13 @@
 // ABOUTME: Core traits for tiered platform abstraction
 // ABOUTME: Tier 1 (MessagingPlatform), Tier 2 (ChatPlatform), Tier 3 (LocalInterface)
 
+use crate::connection::ConnectionEvent;
 use anyhow::Result;
 use async_trait::async_trait;
 use std::fmt::Debug;
 use std::pin::Pin;
 use std::time::Duration;
+use tokio::sync::broadcast;
 use tokio_stream::Stream;
 
 // =============================================================================
@@ -94,7 +96,7 @@ pub struct IncomingMessage {
     pub platform_id: String,
     /// The channel/room this message was sent in
     pub channel_id: String,
-    /// Thread identifier for platforms that support threading (Slack thread_ts, WhatsApp quoted message ID)
+    /// Thread identifier for platforms that support threading (Slack thread_ts, Matrix thread root event ID, WhatsApp quoted message ID)
     pub thread_id: Option<String>,
     /// The user who sent the message
     pub sender: ChatUser,
@@ -104,10 +106,12 @@ pub struct IncomingMessage {
     pub is_direct: bool,
     /// Whether this message is formatted (HTML, markdown, etc.)
     pub formatted: bool,
-    /// Attachment info if present
-    pub attachment: Option<AttachmentInfo>,
+    /// Files and images sent with the message, in the order the platform listed them
+    pub attachments: Vec<AttachmentInfo>,
     /// Platform-specific event ID
     pub event_id: String,
+    /// Event ID of the earlier message this one edits, if it is an edit
+    pub edits_event_id: Option<String>,
     /// Timestamp in seconds since Unix epoch
     pub timestamp: i64,
 }
@@ -117,6 +121,11 @@ impl IncomingMessage {
     pub fn room_id(&self) -> &str {
         &self.channel_id
     }
+
+    /// Backwards-compatible accessor for the first attachment
+    pub fn attachment(&self) -> Option<&AttachmentInfo> {
+        self.attachments.first()
+    }
 }
 
 // =============================================================================
@@ -127,7 +136,7 @@ impl IncomingMessage {
 pub type EventStream = Pin<Box<dyn Stream<Item = IncomingMessage> + Send>>;
 
 /// Connection state reported by each platform for health checks and monitoring
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlatformConnectionState {
     /// Platform is connected and processing events
     Connected,
@@ -141,6 +150,19 @@ pub enum PlatformConnectionState {
     RateLimited { retry_after: Duration },
 }
 
+impl PlatformConnectionState {
+    /// Short lowercase name of the state, as shown in status views
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlatformConnectionState::Connected => "connected",
+            PlatformConnectionState::Connecting => "connecting",
+            PlatformConnectionState::Disconnected { .. } => "disconnected",
+            PlatformConnectionState::AuthRequired => "auth_required",
+            PlatformConnectionState::RateLimited { .. } => "rate_limited",
+        }
+    }
+}
+
 /// Tier 1: Minimum platform interface for control plane access.
 ///
 /// Platforms implementing only this trait can receive messages and send responses,
@@ -177,6 +199,81 @@ pub trait MessagingPlatform: Send + Sync {
     fn connection_state(&self) -> PlatformConnectionState {
         PlatformConnectionState::Connected
     }
+
+    /// Optional: every change of `connection_state()`, as it happens.
+    /// Platforms that keep their state in a `ConnectionTracker` return its subscription.
+    fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+        None
+    }
+
+    /// Optional: status annotations on messages (e.g., Matrix reactions).
+    /// Platforms without one (currently Slack and Telegram) simply show no status.
+    fn annotator(&self) -> Option<&dyn MessageAnnotator> {
+        None
+    }
+
+    /// Optional: threaded conversation support (Slack threads, Matrix m.thread).
+    /// Lives on the base trait so the generic message path can reply in-thread.
+    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
+        None
+    }
+
+    /// Optional: buttons for suggested replies. Without them, suggestions
+    /// are sent as a numbered list.
+    fn action_buttons(&self) -> Option<&dyn ActionButtons> {
+        None
+    }
+
+    /// Optional: typing indicator by channel ID, for the generic message path
+    fn typing(&self) -> Option<&dyn ChannelTyping> {
+        None
+    }
+
+    /// Optional: download the attachments of incoming messages by source ID.
+    /// Without one, attachments are dropped and only the message text is used.
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        None
+    }
+
+    /// Optional: channel creation (create rooms/channels). On the base trait
+    /// so platforms looked up in the registry can be asked for new channels.
+    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
+        None
+    }
+
+    /// Optional: channel management (join/leave/invite). On the base trait so
+    /// the generic command path can invite people into a channel.
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        None
+    }
+
+    /// Optional: show senders the bot picked up their message before the agent runs
+    fn receipts(&self) -> Option<&dyn ReceiptSender> {
+        None
+    }
+
+    /// Optional: show whether the bot is around (online while running)
+    fn presence(&self) -> Option<&dyn PresenceProvider> {
+        None
+    }
+
+    /// Optional: replies only the sender sees (e.g., Slack ephemeral messages), for
+    /// errors and refusals that would be noise for everyone else in a shared channel
+    fn ephemeral(&self) -> Option<&dyn EphemeralSender> {
+        None
+    }
+
+    /// Optional: take updates pushed to the webhook server instead of fetching
+    /// them (e.g., Telegram in webhook mode)
+    fn webhook_ingress(&self) -> Option<&dyn WebhookIngress> {
+        None
+    }
+
+    /// Optional: Confirm / Cancel buttons for destructive commands (e.g.,
+    /// Telegram inline keyboards). Without them, those commands are refused.
+    fn confirmations(&self) -> Option<&dyn ConfirmationProvider> {
+        None
+    }
 }
 
 // =============================================================================
@@ -200,26 +297,11 @@ pub trait ChatPlatform: MessagingPlatform {
     /// List all joined channels
     async fn joined_channels(&self) -> Vec<Self::Channel>;
 
-    /// Optional: channel creation capability (create rooms/channels)
-    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
-        None
-    }
-
-    /// Optional: channel management (join/leave/invite)
-    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
-        None
-    }
-
     /// Optional: encryption support
     fn encryption(&self) -> Option<&dyn EncryptedPlatform> {
         None
     }
 
-    /// Optional: threaded conversation support
-    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
-        None
-    }
-
     /// Optional: slash command support
     fn slash_commands(&self) -> Option<&dyn SlashCommandProvider> {
         None
@@ -237,6 +319,9 @@ pub trait ChatChannel: Send + Sync + Debug + Clone {
     /// Unique identifier for this channel
     fn id(&self) -> &str;
 
+    /// Platform the channel is on, as reported by [`MessagingPlatform::platform_id`]
+    fn platform_id(&self) -> &str;
+
     /// Human-readable name of the channel, if available
     fn name(&self) -> Option<String>;
 
@@ -256,6 +341,16 @@ pub trait ChatChannel: Send + Sync + Debug + Clone {
         None
     }
 
+    /// Optional: join/leave/invite support for the platform the channel is on
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        None
+    }
+
+    /// Optional: replies only one member of the channel can see
+    fn ephemeral(&self) -> Option<&dyn EphemeralSender> {
+        None
+    }
+
     /// Get member count (defaults to unknown)
     async fn member_count(&self) -> Result<usize> {
         Ok(0)
@@ -326,11 +421,20 @@ pub trait ChannelManager: Send + Sync {
     async fn members(&self, channel_id: &str) -> Result<Vec<ChatUser>>;
 }
 
+/// How often a typing indicator is re-sent unless the platform says otherwise;
+/// a Matrix typing notice lasts 30 seconds
+pub const DEFAULT_TYPING_REFRESH: Duration = Duration::from_secs(25);
+
 /// Typing indicator capability
 #[async_trait]
 pub trait TypingIndicator: Send + Sync {
     /// Set typing indicator on/off
     async fn set_typing(&self, typing: bool) -> Result<()>;
+
+    /// How often to re-send the indicator so it doesn't lapse during a long turn
+    fn refresh_interval(&self) -> Duration {
+        DEFAULT_TYPING_REFRESH
+    }
 }
 
 /// Attachment handling capability
@@ -358,7 +462,7 @@ pub trait EncryptedPlatform: Send + Sync {
 // Extension Traits (optional platform capabilities)
 // =============================================================================
 
-/// Platforms that support threaded conversations (e.g., Slack)
+/// Platforms that support threaded conversations (e.g., Slack, Matrix)
 #[async_trait]
 pub trait ThreadedPlatform: Send + Sync {
     /// Send a message as a reply within a specific thread
@@ -410,6 +514,101 @@ pub trait RichFormatter: Send + Sync {
     fn format_as_blocks(&self, content: &str) -> serde_json::Value;
 }
 
+/// Platforms that can mark a message in place (e.g., Matrix m.reaction events)
+#[async_trait]
+pub trait MessageAnnotator: Send + Sync {
+    /// Attach an emoji to a message; returns an ID for removing it later
+    async fn add_reaction(&self, channel_id: &str, message_id: &str, emoji: &str)
+        -> Result<String>;
+
+    /// Remove a reaction previously returned by add_reaction
+    async fn remove_reaction(&self, channel_id: &str, reaction_id: &str) -> Result<()>;
+}
+
+/// Platforms that can show tappable suggested replies (e.g., Telegram keyboards, Slack buttons)
+#[async_trait]
+pub trait ActionButtons: Send + Sync {
+    /// Send `text` with one button per action; tapping one comes back as
+    /// an ordinary message from the user whose body is the action text
+    async fn send_actions(&self, channel_id: &str, text: &str, actions: &[String]) -> Result<()>;
+}
+
+/// Platforms that can show a typing indicator in any channel they know by ID
+#[async_trait]
+pub trait ChannelTyping: Send + Sync {
+    /// Set typing indicator on/off; platforms whose indicator expires on its own may ignore off
+    async fn set_typing(&self, channel_id: &str, typing: bool) -> Result<()>;
+
+    /// How often to re-send the indicator so it doesn't lapse during a long turn
+    fn refresh_interval(&self) -> Duration {
+        DEFAULT_TYPING_REFRESH
+    }
+}
+
+/// Platforms that can tell a sender their message was seen before the reply is
+/// ready (e.g., Matrix read receipts, a 👀 reaction on Slack)
+#[async_trait]
+pub trait ReceiptSender: Send + Sync {
+    /// Mark `message_id` in the channel as seen. Matrix receipts also cover
+    /// everything before it.
+    async fn send_read_receipt(&self, channel_id: &str, message_id: &str) -> Result<()>;
+}
+
+/// Platforms that can show a message to one user in a shared channel (e.g., Slack chat.postEphemeral)
+#[async_trait]
+pub trait EphemeralSender: Send + Sync {
+    /// Send `content` to `channel_id` so that only `user_id` sees it
+    async fn send_ephemeral(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        content: MessageContent,
+    ) -> Result<()>;
+}
+
+/// Platforms whose updates arrive as HTTP pushes on the webhook server
+#[async_trait]
+pub trait WebhookIngress: Send + Sync {
+    /// Whether a delivery carrying `path_secret` in its URL and `token` in the
+    /// platform's secret header really comes from the platform
+    fn verify(&self, path_secret: &str, token: Option<&str>) -> bool;
+
+    /// Feed one pushed update into the platform's event stream
+    async fn push(&self, body: &[u8]) -> Result<()>;
+}
+
+/// Platforms that can ask for a yes/no answer with buttons under a message
+#[async_trait]
+pub trait ConfirmationProvider: Send + Sync {
+    /// Send `text` with Confirm and Cancel buttons for the pending action `token`.
+    ///
+    /// Only `requester_id` may answer. A press comes back through the event
+    /// stream as a command from the presser, `confirm <token>` or `cancel <token>`
+    /// (see [`crate::confirmations::answer_command`]).
+    async fn ask_confirmation(
+        &self,
+        channel_id: &str,
+        requester_id: &str,
+        text: &str,
+        token: &str,
+    ) -> Result<()>;
+}
+
+/// Whether the bot is around, as shown to users
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Presence {
+    Online,
+    /// Connected but not answering, e.g. while shutting down
+    Unavailable,
+    Offline,
+}
+
+/// Platforms that show the bot's presence (e.g., Matrix m.presence)
+#[async_trait]
+pub trait PresenceProvider: Send + Sync {
+    async fn set_presence(&self, presence: Presence) -> Result<()>;
+}
+
 // =============================================================================
 // Backwards Compatibility - Deprecated Traits
 // =============================================================================
@@ -482,8 +681,9 @@ mod tests {
             body: "test".to_string(),
             is_direct: false,
             formatted: false,
-            attachment: None,
+            attachments: Vec::new(),
             event_id: "evt1".to_string(),
+            edits_event_id: None,
             timestamp: 0,
         };
         assert_eq!(msg.room_id(), "!room:example.com");
@@ -499,8 +699,9 @@ mod tests {
             body: "hello".to_string(),
             is_direct: true,
             formatted: false,
-            attachment: None,
+            attachments: Vec::new(),
             event_id: "msg_1".to_string(),
+            edits_event_id: None,
             timestamp: 1700000000,
         };
         assert_eq!(msg.platform_id, "telegram");
@@ -517,8 +718,9 @@ mod tests {
             body: "threaded reply".to_string(),
             is_direct: false,
             formatted: false,
-            attachment: None,
+            attachments: Vec::new(),
             event_id: "msg_2".to_string(),
+            edits_event_id: None,
             timestamp: 1700000001,
         };
         assert_eq!(msg.thread_id.as_deref(), Some("1700000000.000100"));
@@ -655,6 +857,9 @@ mod tests {
         fn id(&self) -> &str {
             &self.id
         }
+        fn platform_id(&self) -> &str {
+            "stub"
+        }
         fn name(&self) -> Option<String> {
             None
         }
@@ -713,6 +918,20 @@ mod tests {
         assert!(platform.rich_formatter().is_none());
     }
 
+    #[test]
+    fn test_messaging_platform_annotator_default_none() {
+        let platform = StubPlatform;
+        assert!(platform.annotator().is_none());
+    }
+
+    #[test]
+    fn test_messaging_platform_receipts_and_presence_default_none() {
+        let platform = StubPlatform;
+        assert!(platform.receipts().is_none());
+        assert!(platform.presence().is_none());
+        assert!(platform.ephemeral().is_none());
+    }
+
     #[test]
     fn test_messaging_platform_connection_state_default() {
         let platform = StubPlatform;
@@ -0,0 +1,137 @@
+// ABOUTME: Speech-to-text for voice messages before they reach the agent.
+// ABOUTME: Defines the Transcriber trait and an HTTP client for OpenAI-compatible and whisper.cpp servers.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+
+use crate::config::{TranscriptionConfig, TranscriptionProvider};
+
+/// Put in front of a transcript in the prompt so the agent knows it was spoken
+pub const VOICE_TRANSCRIPT_PREFIX: &str = "[Voice message transcript]";
+
+/// Reply to a voice message when no `[transcription]` service is configured
+pub const VOICE_UNSUPPORTED_NOTICE: &str =
+    "🎙️ Voice messages aren't supported here: no transcription service is configured.";
+
+/// Turns recorded audio into text
+#[async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Transcribe `audio`, a file named `filename` of type `mime_type`.
+    /// `language` is an ISO-639-1 hint such as "es"; None lets the service detect it.
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        language: Option<&str>,
+    ) -> Result<String>;
+}
+
+/// Whether an attachment of this type is audio to transcribe
+pub fn is_audio(mime_type: &str) -> bool {
+    mime_type
+        .trim()
+        .get(..6)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("audio/"))
+}
+
+/// Transcriber backed by an HTTP speech-to-text server
+pub struct HttpTranscriber {
+    client: reqwest::Client,
+    provider: TranscriptionProvider,
+    url: String,
+    api_key: Option<String>,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+impl HttpTranscriber {
+    pub fn new(config: &TranscriptionConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("Failed to build transcription HTTP client")?;
+        Ok(Self {
+            client,
+            provider: config.provider,
+            url: config.url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            model: config.model.clone(),
+        })
+    }
+
+    /// Where audio is posted: `/audio/transcriptions` for OpenAI-compatible
+    /// services, `/inference` for a whisper.cpp server
+    pub fn endpoint(&self) -> String {
+        match self.provider {
+            TranscriptionProvider::Openai => format!("{}/audio/transcriptions", self.url),
+            TranscriptionProvider::WhisperCpp => format!("{}/inference", self.url),
+        }
+    }
+}
+
+#[async_trait]
+impl Transcriber for HttpTranscriber {
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        language: Option<&str>,
+    ) -> Result<String> {
+        let file = Part::bytes(audio)
+            .file_name(filename.to_string())
+            .mime_str(mime_type)
+            .with_context(|| format!("Invalid audio MIME type: {}", mime_type))?;
+        let mut form = Form::new()
+            .part("file", file)
+            .text("response_format", "json");
+        form = match self.provider {
+            TranscriptionProvider::Openai => {
+                let form = form.text("model", self.model.clone());
+                match language {
+                    Some(language) => form.text("language", language.to_string()),
+                    None => form,
+                }
+            }
+            // whisper.cpp assumes English unless told to detect
+            TranscriptionProvider::WhisperCpp => {
+                form.text("language", language.unwrap_or("auto").to_string())
+            }
+        };
+
+        let mut request = self.client.post(self.endpoint()).multipart(form);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request
+            .send()
+            .await
+            .context("Transcription request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let preview: String = body.chars().take(200).collect();
+            anyhow::bail!("Transcription service returned {}: {}", status, preview);
+        }
+        let parsed: TranscriptionResponse = response
+            .json()
+            .await
+            .context("Unexpected transcription response")?;
+
+        tracing::debug!(
+            provider = ?self.provider,
+            chars = parsed.text.len(),
+            "Transcribed voice message"
+        );
+        Ok(parsed.text.trim().to_string())
+    }
+}
@@ -0,0 +1,102 @@
+// ABOUTME: Keeps a platform's typing indicator up while work runs, re-sending it before it lapses.
+// ABOUTME: Works on any TypingIndicator; ChannelTypingIndicator adapts platforms that type by channel ID.
+
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::traits::{ChannelTyping, TypingIndicator};
+
+/// A platform's by-ID typing indicator, bound to one channel
+pub struct ChannelTypingIndicator<'a> {
+    typing: &'a dyn ChannelTyping,
+    channel_id: &'a str,
+}
+
+impl<'a> ChannelTypingIndicator<'a> {
+    pub fn new(typing: &'a dyn ChannelTyping, channel_id: &'a str) -> Self {
+        Self { typing, channel_id }
+    }
+}
+
+#[async_trait]
+impl TypingIndicator for ChannelTypingIndicator<'_> {
+    async fn set_typing(&self, typing: bool) -> Result<()> {
+        self.typing.set_typing(self.channel_id, typing).await
+    }
+
+    fn refresh_interval(&self) -> Duration {
+        self.typing.refresh_interval()
+    }
+}
+
+/// Run `work` with the typing indicator on, re-sending it every `refresh_interval()`
+/// until `work` finishes, then turn it off. Failures to type are logged, never returned,
+/// and the indicator is cleared whether `work` succeeded or not.
+pub async fn with_typing<F: Future>(typing: &dyn TypingIndicator, work: F) -> F::Output {
+    if let Err(e) = typing.set_typing(true).await {
+        tracing::debug!(error = %e, "Failed to set typing indicator");
+    }
+    let refresh = typing.refresh_interval();
+    let keep_typing = async {
+        loop {
+            tokio::time::sleep(refresh).await;
+            if let Err(e) = typing.set_typing(true).await {
+                tracing::debug!(error = %e, "Failed to refresh typing indicator");
+            }
+        }
+    };
+    let output = tokio::select! {
+        output = work => output,
+        _ = keep_typing => unreachable!("typing refresh loop never ends"),
+    };
+    if let Err(e) = typing.set_typing(false).await {
+        tracing::debug!(error = %e, "Failed to clear typing indicator");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Recorder {
+        calls: Mutex<Vec<bool>>,
+        refresh: Duration,
+    }
+
+    #[async_trait]
+    impl TypingIndicator for Recorder {
+        async fn set_typing(&self, typing: bool) -> Result<()> {
+            self.calls.lock().unwrap().push(typing);
+            Ok(())
+        }
+
+        fn refresh_interval(&self) -> Duration {
+            self.refresh
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_typing_is_refreshed_at_the_platform_interval_and_cleared() {
+        let recorder = Recorder {
+            calls: Mutex::new(Vec::new()),
+            refresh: Duration::from_secs(4),
+        };
+        let result: Result<()> = with_typing(&recorder, async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Err(anyhow::anyhow!("agent failed"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        // On, refreshed at 4s and 8s, then off even though the work failed
+        assert_eq!(
+            *recorder.calls.lock().unwrap(),
+            vec![true, true, true, false]
+        );
+    }
+}
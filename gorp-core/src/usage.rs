@@ -0,0 +1,325 @@
+// ABOUTME: Tags each agent invocation with where it came from and buckets its usage accordingly.
+// ABOUTME: Internal setup traffic (preambles, self-tests) is tracked as overhead, apart from conversation.
+// ABOUTME: Also projects a channel's month-end spend from its recent daily spend, for budget warnings.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Days of recent spend the forecast averages over
+pub const FORECAST_WINDOW_DAYS: i64 = 14;
+
+/// What triggered an agent invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InvocationOrigin {
+    /// A person sent a message in a channel or DM
+    User,
+    /// An HTTP webhook request
+    Webhook,
+    /// A scheduled prompt firing
+    Schedule,
+    /// Bot-generated setup: context files, preambles, self-tests
+    Internal,
+}
+
+impl InvocationOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Webhook => "webhook",
+            Self::Schedule => "schedule",
+            Self::Internal => "internal",
+        }
+    }
+
+    /// Usage bucket this origin's cost is attributed to
+    pub fn bucket(&self) -> UsageBucket {
+        match self {
+            Self::Internal => UsageBucket::Overhead,
+            Self::User | Self::Webhook | Self::Schedule => UsageBucket::Conversation,
+        }
+    }
+
+    /// Whether exchanges with this origin are written to channel transcripts
+    pub fn in_transcript(&self) -> bool {
+        *self != Self::Internal
+    }
+}
+
+impl std::fmt::Display for InvocationOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-channel usage bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageBucket {
+    /// Work done for users, webhooks and schedules
+    Conversation,
+    /// Setup traffic the bot generates for itself
+    Overhead,
+}
+
+impl UsageBucket {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Conversation => "conversation",
+            Self::Overhead => "overhead",
+        }
+    }
+}
+
+/// Accumulated usage for one bucket
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub invocations: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_cents: u64,
+}
+
+/// Usage for a channel, split by bucket
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelUsage {
+    pub conversation: UsageTotals,
+    pub overhead: UsageTotals,
+}
+
+impl ChannelUsage {
+    pub fn bucket(&self, bucket: UsageBucket) -> &UsageTotals {
+        match bucket {
+            UsageBucket::Conversation => &self.conversation,
+            UsageBucket::Overhead => &self.overhead,
+        }
+    }
+}
+
+/// A recorded experiment, e.g. one prompt run against two backends by !compare
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Experiment {
+    pub id: i64,
+    pub channel_name: String,
+    /// What kind of experiment this was ("compare")
+    pub kind: String,
+    pub prompt: String,
+    /// Per-kind results such as latency, tokens and cost for each side
+    pub details: serde_json::Value,
+    pub created_at: String,
+}
+
+/// Where a channel's spend is heading this month at its recent rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendForecast {
+    /// Average daily spend over the forecast window, in cents
+    pub daily_average_cents: f64,
+    /// Spend so far this calendar month, in cents
+    pub month_to_date_cents: u64,
+    /// Month-to-date spend plus the daily average for each remaining day
+    pub projected_month_cents: u64,
+    /// Day the budget is exceeded at the current rate (today if it already is).
+    /// None without a budget, or when the month ends first.
+    pub exceeds_budget_on: Option<NaiveDate>,
+}
+
+/// Project the month-end spend from `daily` (day, cents) spend as of `today`.
+///
+/// The average covers the last [`FORECAST_WINDOW_DAYS`] days, or only the days
+/// since `tracked_since` (the first day usage was recorded) for newer channels,
+/// so a channel a few days old isn't diluted by days it didn't exist.
+/// Returns None when the channel has no recorded usage yet.
+pub fn forecast_spend(
+    daily: &[(NaiveDate, u64)],
+    tracked_since: Option<NaiveDate>,
+    today: NaiveDate,
+    budget_cents: Option<u64>,
+) -> Option<SpendForecast> {
+    let tracked_since = tracked_since?;
+    let window_start = (today - Duration::days(FORECAST_WINDOW_DAYS - 1)).max(tracked_since);
+    let observed_days = ((today - window_start).num_days() + 1).max(1);
+
+    let window_cents: u64 = daily
+        .iter()
+        .filter(|(day, _)| *day >= window_start && *day <= today)
+        .map(|(_, cents)| cents)
+        .sum();
+    let daily_average_cents = window_cents as f64 / observed_days as f64;
+
+    let month_start = today.with_day(1)?;
+    let month_to_date_cents: u64 = daily
+        .iter()
+        .filter(|(day, _)| *day >= month_start && *day <= today)
+        .map(|(_, cents)| cents)
+        .sum();
+    let days_left = days_in_month(today) - today.day();
+    let projected_month_cents =
+        month_to_date_cents + (daily_average_cents * days_left as f64).round() as u64;
+
+    let exceeds_budget_on = budget_cents.and_then(|budget| {
+        if month_to_date_cents > budget {
+            return Some(today);
+        }
+        if daily_average_cents <= 0.0 {
+            return None;
+        }
+        // First whole day on which the running total goes past the budget
+        let headroom = (budget - month_to_date_cents) as f64;
+        let days = (headroom / daily_average_cents).floor() as i64 + 1;
+        let day = today + Duration::days(days);
+        (day.month() == today.month() && day.year() == today.year()).then_some(day)
+    });
+
+    Some(SpendForecast {
+        daily_average_cents,
+        month_to_date_cents,
+        projected_month_cents,
+        exceeds_budget_on,
+    })
+}
+
+fn days_in_month(day: NaiveDate) -> u32 {
+    let (year, month) = match day.month() {
+        12 => (day.year() + 1, 1),
+        month => (day.year(), month + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(month: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, d).unwrap()
+    }
+
+    /// `cents` spent on every day from `from` through `to`
+    fn steady(from: NaiveDate, to: NaiveDate, cents: u64) -> Vec<(NaiveDate, u64)> {
+        from.iter_days()
+            .take_while(|d| *d <= to)
+            .map(|d| (d, cents))
+            .collect()
+    }
+
+    #[test]
+    fn test_forecast_steady_spend_projects_month_end() {
+        let daily = steady(day(1, 1), day(1, 10), 50);
+        let f = forecast_spend(&daily, Some(day(1, 1)), day(1, 10), Some(1000)).unwrap();
+
+        assert_eq!(f.daily_average_cents, 50.0);
+        assert_eq!(f.month_to_date_cents, 500);
+        // 21 days left in January at 50 cents a day
+        assert_eq!(f.projected_month_cents, 500 + 21 * 50);
+        // 500 of headroom lasts exactly 10 more days; the 11th goes over
+        assert_eq!(f.exceeds_budget_on, Some(day(1, 21)));
+    }
+
+    #[test]
+    fn test_forecast_within_budget_has_no_overrun() {
+        let daily = steady(day(1, 1), day(1, 10), 10);
+        let f = forecast_spend(&daily, Some(day(1, 1)), day(1, 10), Some(1000)).unwrap();
+
+        assert_eq!(f.projected_month_cents, 310);
+        assert_eq!(f.exceeds_budget_on, None);
+    }
+
+    #[test]
+    fn test_forecast_averages_only_the_last_window() {
+        // Heavy spend early in the month, then quiet for the last two weeks
+        let mut daily = steady(day(3, 1), day(3, 5), 1000);
+        daily.push((day(3, 20), 140));
+        let f = forecast_spend(&daily, Some(day(3, 1)), day(3, 20), None).unwrap();
+
+        assert_eq!(f.daily_average_cents, 10.0);
+        assert_eq!(f.month_to_date_cents, 5140);
+        assert_eq!(f.projected_month_cents, 5140 + 11 * 10);
+    }
+
+    #[test]
+    fn test_forecast_new_channel_averages_over_days_it_existed() {
+        // Three days old: 90 cents over 3 days, not diluted to 14
+        let daily = vec![(day(4, 8), 60), (day(4, 10), 30)];
+        let f = forecast_spend(&daily, Some(day(4, 8)), day(4, 10), Some(500)).unwrap();
+
+        assert_eq!(f.daily_average_cents, 30.0);
+        assert_eq!(f.projected_month_cents, 90 + 20 * 30);
+        assert_eq!(f.exceeds_budget_on, Some(day(4, 24)));
+    }
+
+    #[test]
+    fn test_forecast_window_spans_month_boundary() {
+        // Spend in late January counts toward the rate but not February's total
+        let daily = steady(day(1, 25), day(2, 3), 100);
+        let f = forecast_spend(&daily, Some(day(1, 2)), day(2, 3), Some(2500)).unwrap();
+
+        assert_eq!(f.daily_average_cents, 1000.0 / 14.0);
+        assert_eq!(f.month_to_date_cents, 300);
+        // 25 days left in February 2026
+        assert_eq!(
+            f.projected_month_cents,
+            300 + (1000.0 / 14.0 * 25.0_f64).round() as u64
+        );
+        assert_eq!(f.exceeds_budget_on, None);
+    }
+
+    #[test]
+    fn test_forecast_last_day_of_month() {
+        let daily = steady(day(12, 18), day(12, 31), 100);
+        let f = forecast_spend(&daily, Some(day(12, 18)), day(12, 31), Some(1500)).unwrap();
+
+        assert_eq!(f.projected_month_cents, f.month_to_date_cents);
+        assert_eq!(f.exceeds_budget_on, None);
+    }
+
+    #[test]
+    fn test_forecast_already_over_budget() {
+        let daily = steady(day(5, 1), day(5, 6), 300);
+        let f = forecast_spend(&daily, Some(day(5, 1)), day(5, 6), Some(1000)).unwrap();
+
+        assert_eq!(f.exceeds_budget_on, Some(day(5, 6)));
+    }
+
+    #[test]
+    fn test_forecast_needs_history() {
+        assert_eq!(forecast_spend(&[], None, day(6, 1), Some(1000)), None);
+
+        // Usage recorded, but nothing has cost anything
+        let f = forecast_spend(&[(day(6, 1), 0)], Some(day(6, 1)), day(6, 1), Some(1000)).unwrap();
+        assert_eq!(f.projected_month_cents, 0);
+        assert_eq!(f.exceeds_budget_on, None);
+    }
+
+    #[test]
+    fn test_origin_buckets() {
+        assert_eq!(InvocationOrigin::User.bucket(), UsageBucket::Conversation);
+        assert_eq!(
+            InvocationOrigin::Webhook.bucket(),
+            UsageBucket::Conversation
+        );
+        assert_eq!(
+            InvocationOrigin::Schedule.bucket(),
+            UsageBucket::Conversation
+        );
+        assert_eq!(InvocationOrigin::Internal.bucket(), UsageBucket::Overhead);
+    }
+
+    #[test]
+    fn test_only_internal_is_excluded_from_transcripts() {
+        assert!(InvocationOrigin::User.in_transcript());
+        assert!(InvocationOrigin::Webhook.in_transcript());
+        assert!(InvocationOrigin::Schedule.in_transcript());
+        assert!(!InvocationOrigin::Internal.in_transcript());
+    }
+
+    #[test]
+    fn test_origin_labels() {
+        assert_eq!(InvocationOrigin::Schedule.to_string(), "schedule");
+        assert_eq!(
+            serde_json::to_string(&InvocationOrigin::Internal).unwrap(),
+            "\"internal\""
+        );
+    }
+}
@@ -7,6 +7,8 @@ use serde::Serialize;
 use tokio::fs::{create_dir_all, OpenOptions};
 use tokio::io::AsyncWriteExt;
 
+use crate::usage::InvocationOrigin;
+
 /// Convert markdown to HTML for Matrix message formatting
 pub fn markdown_to_html(markdown: &str) -> String {
     let parser = Parser::new(markdown);
@@ -39,7 +41,12 @@ pub fn strip_function_calls(text: &str) -> String {
     result.trim().to_string()
 }
 
-/// Split long text into chunks, trying to break at paragraph boundaries
+/// Split long text into chunks that each render as valid markdown.
+///
+/// Breaks prefer paragraph boundaries. Fenced code blocks are never cut mid-fence:
+/// a block that is itself too long is split by lines, each part closed and reopened
+/// with the original fence (language tag included). Headers stay with the block that
+/// follows them, and oversized tables repeat their header row in every part.
 pub fn chunk_message(text: &str, max_chars: usize) -> Vec<String> {
     if text.len() <= max_chars {
         return vec![text.to_string()];
@@ -48,55 +55,378 @@ pub fn chunk_message(text: &str, max_chars: usize) -> Vec<String> {
     let mut chunks = Vec::new();
     let mut current = String::new();
 
-    for line in text.lines() {
-        // If adding this line would exceed limit, save current chunk
-        if !current.is_empty() && current.len() + line.len() + 1 > max_chars {
-            chunks.push(current.trim().to_string());
-            current = String::new();
+    for unit in group_units(parse_blocks(text)) {
+        let rendered = render_unit(&unit);
+
+        // Pack whole units together while they fit
+        if !current.is_empty() && current.len() + 2 + rendered.len() <= max_chars {
+            current.push_str("\n\n");
+            current.push_str(&rendered);
+            continue;
         }
 
-        // If a single line is too long, split it
-        if line.len() > max_chars {
-            if !current.is_empty() {
-                chunks.push(current.trim().to_string());
-                current = String::new();
+        if !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+
+        if rendered.len() <= max_chars {
+            current = rendered;
+        } else {
+            // The last part stays open so following units can share its chunk
+            let mut parts = split_unit(&unit, max_chars);
+            current = parts.pop().unwrap_or_default();
+            chunks.extend(parts);
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// A markdown block that chunking treats as a single piece where possible
+#[derive(Debug)]
+enum Block {
+    /// Fenced code block with its opening fence line (including any language tag)
+    Code {
+        open: String,
+        body: Vec<String>,
+        close: String,
+    },
+    /// ATX header line
+    Header(String),
+    /// Paragraph, list or table: a run of consecutive non-blank lines
+    Text(Vec<String>),
+}
+
+impl Block {
+    fn render(&self) -> String {
+        match self {
+            Block::Code { open, body, close } => {
+                let mut out = open.clone();
+                for line in body {
+                    out.push('\n');
+                    out.push_str(line);
+                }
+                out.push('\n');
+                out.push_str(close);
+                out
             }
-            // Split long line at word boundaries
-            let mut line_part = String::new();
-            for word in line.split_whitespace() {
-                if line_part.len() + word.len() + 1 > max_chars {
-                    if !line_part.is_empty() {
-                        chunks.push(line_part.trim().to_string());
-                    }
-                    line_part = word.to_string();
-                } else {
-                    if !line_part.is_empty() {
-                        line_part.push(' ');
-                    }
-                    line_part.push_str(word);
+            Block::Header(line) => line.clone(),
+            Block::Text(lines) => lines.join("\n"),
+        }
+    }
+}
+
+/// Returns the fence string ("```", "~~~~", ...) if the line opens a fenced code block
+fn fence_marker(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next()?;
+    if fence_char != '`' && fence_char != '~' {
+        return None;
+    }
+    let run = trimmed.chars().take_while(|&c| c == fence_char).count();
+    (run >= 3).then(|| fence_char.to_string().repeat(run))
+}
+
+/// Whether the line closes a code block opened with `fence`
+fn closes_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let fence_char = fence.chars().next().unwrap_or('`');
+    let run = trimmed.chars().take_while(|&c| c == fence_char).count();
+    run >= fence.len() && run == trimmed.chars().count()
+}
+
+fn is_header(line: &str) -> bool {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&hashes) && line[hashes..].starts_with(' ')
+}
+
+fn parse_blocks(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                blocks.push(Block::Text(std::mem::take(&mut paragraph)));
+            }
+            continue;
+        }
+
+        if let Some(fence) = fence_marker(line) {
+            if !paragraph.is_empty() {
+                blocks.push(Block::Text(std::mem::take(&mut paragraph)));
+            }
+            let mut body = Vec::new();
+            // An unclosed fence runs to the end of the text; close it so it renders
+            let mut close = fence.clone();
+            for inner in lines.by_ref() {
+                if closes_fence(inner, &fence) {
+                    close = inner.to_string();
+                    break;
                 }
+                body.push(inner.to_string());
             }
-            if !line_part.is_empty() {
-                current = line_part;
+            blocks.push(Block::Code {
+                open: line.to_string(),
+                body,
+                close,
+            });
+        } else if is_header(line) {
+            if !paragraph.is_empty() {
+                blocks.push(Block::Text(std::mem::take(&mut paragraph)));
             }
+            blocks.push(Block::Header(line.to_string()));
         } else {
+            paragraph.push(line.to_string());
+        }
+    }
+
+    if !paragraph.is_empty() {
+        blocks.push(Block::Text(paragraph));
+    }
+
+    blocks
+}
+
+/// Group blocks so that headers travel with the block after them
+fn group_units(blocks: Vec<Block>) -> Vec<Vec<Block>> {
+    let mut units = Vec::new();
+    let mut pending: Vec<Block> = Vec::new();
+
+    for block in blocks {
+        let is_header = matches!(block, Block::Header(_));
+        pending.push(block);
+        if !is_header {
+            units.push(std::mem::take(&mut pending));
+        }
+    }
+
+    if !pending.is_empty() {
+        units.push(pending);
+    }
+
+    units
+}
+
+fn render_unit(unit: &[Block]) -> String {
+    unit.iter()
+        .map(Block::render)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Split a unit that is too large for one chunk into parts of at most `max_chars`
+fn split_unit(unit: &[Block], max_chars: usize) -> Vec<String> {
+    let (headers, body) = match unit.last() {
+        Some(Block::Header(_)) | None => (unit, None),
+        Some(last) => (&unit[..unit.len() - 1], Some(last)),
+    };
+    let header_text = render_unit(headers);
+
+    let Some(body) = body else {
+        return pack_lines(header_text.lines(), max_chars, true);
+    };
+    if header_text.is_empty() {
+        return split_block(body, max_chars);
+    }
+
+    // Leave room for the header in every part so it can lead the first one
+    let reserved = header_text.len() + 2;
+    if reserved < max_chars / 2 {
+        let mut parts = split_block(body, max_chars - reserved);
+        if let Some(first) = parts.first_mut() {
+            *first = format!("{}\n\n{}", header_text, first);
+        }
+        return parts;
+    }
+
+    let mut parts = pack_lines(header_text.lines(), max_chars, true);
+    parts.extend(split_block(body, max_chars));
+    parts
+}
+
+fn split_block(block: &Block, max_chars: usize) -> Vec<String> {
+    match block {
+        Block::Code { open, body, close } => {
+            let overhead = open.len() + close.len() + 2;
+            let budget = max_chars.saturating_sub(overhead).max(1);
+            let pieces = pack_lines(body.iter().map(String::as_str), budget, false);
+            if pieces.is_empty() {
+                return vec![block.render()];
+            }
+            pieces
+                .into_iter()
+                .map(|piece| format!("{}\n{}\n{}", open, piece, close))
+                .collect()
+        }
+        Block::Header(line) => pack_lines(std::iter::once(line.as_str()), max_chars, true),
+        Block::Text(lines) => {
+            if is_table(lines) {
+                let head = format!("{}\n{}", lines[0], lines[1]);
+                if head.len() + 1 < max_chars / 2 {
+                    let budget = max_chars - head.len() - 1;
+                    return pack_lines(lines[2..].iter().map(String::as_str), budget, true)
+                        .into_iter()
+                        .map(|rows| format!("{}\n{}", head, rows))
+                        .collect();
+                }
+            }
+            pack_lines(lines.iter().map(String::as_str), max_chars, true)
+        }
+    }
+}
+
+/// A table whose header row is followed by a `|---|---|` delimiter row
+fn is_table(lines: &[String]) -> bool {
+    lines.len() > 2
+        && lines[0].trim_start().starts_with('|')
+        && lines[1].contains('-')
+        && lines[1]
+            .trim()
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Greedily pack whole lines into pieces of at most `budget` bytes.
+/// Lines longer than the budget are split at word boundaries (prose) or hard-cut (code).
+fn pack_lines<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+    budget: usize,
+    split_words: bool,
+) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+
+    for line in lines {
+        if line.len() > budget {
             if !current.is_empty() {
-                current.push('\n');
+                pieces.push(std::mem::take(&mut current));
             }
-            current.push_str(line);
+            let mut parts = if split_words {
+                split_at_words(line, budget)
+            } else {
+                hard_split(line, budget)
+            };
+            current = parts.pop().unwrap_or_default();
+            pieces.extend(parts);
+            continue;
+        }
+
+        if !current.is_empty() && current.len() + 1 + line.len() > budget {
+            pieces.push(std::mem::take(&mut current));
         }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
     }
 
     if !current.is_empty() {
-        chunks.push(current.trim().to_string());
+        pieces.push(current);
     }
 
-    chunks
+    pieces
+}
+
+fn split_at_words(line: &str, budget: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for word in line.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > budget {
+            parts.push(std::mem::take(&mut current));
+        }
+        if word.len() > budget {
+            let mut pieces = hard_split(word, budget);
+            current = pieces.pop().unwrap_or_default();
+            parts.extend(pieces);
+            continue;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
+/// Cut text into pieces of at most `budget` bytes without splitting a UTF-8 character
+fn hard_split(text: &str, budget: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for c in text.chars() {
+        if !current.is_empty() && current.len() + c.len_utf8() > budget {
+            parts.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+
+    parts
 }
 
 /// Maximum chunk size for Matrix messages (chars)
 pub const MAX_CHUNK_SIZE: usize = 8000;
 
+/// Cut a response down to at most `max_chars` characters.
+///
+/// Returns the kept text and how many characters were dropped, or None if the text
+/// already fits (or `max_chars` is 0, meaning unlimited). The cut lands on the last
+/// paragraph, line or word break in the second half of the allowance, falling back to
+/// a hard cut, and an open code fence is closed so the kept text still renders.
+pub fn truncate_response(text: &str, max_chars: usize) -> Option<(String, usize)> {
+    let total = text.chars().count();
+    if max_chars == 0 || total <= max_chars {
+        return None;
+    }
+
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let head = &text[..cut];
+    let min_keep = head
+        .char_indices()
+        .nth(max_chars / 2)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let end = head
+        .rfind("\n\n")
+        .or_else(|| head.rfind('\n'))
+        .or_else(|| head.rfind(char::is_whitespace))
+        .filter(|&i| i >= min_keep)
+        .unwrap_or(cut);
+
+    let mut kept = head[..end].trim_end().to_string();
+    let omitted = total - kept.chars().count();
+    let open_fences = kept
+        .lines()
+        .filter(|line| line.trim_start().starts_with("```"))
+        .count();
+    if open_fences % 2 == 1 {
+        kept.push_str("\n```");
+    }
+    Some((kept, omitted))
+}
+
 /// Expand a slash command to its full prompt content
 /// Reads from {workspace}/.claude/commands/{command}.md
 /// Returns Ok(expanded_content) if found, or Ok(original) if not a slash command
@@ -160,6 +490,7 @@ pub struct MatrixMessageLog {
     pub room_id: String,
     pub message_type: String,
     pub content: String,
+    pub origin: InvocationOrigin,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub html: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -169,6 +500,8 @@ pub struct MatrixMessageLog {
 }
 
 /// Log a Matrix message to .gorp/matrix-messages.jsonl
+/// Messages from origins excluded from transcripts (internal setup) are skipped.
+#[allow(clippy::too_many_arguments)]
 pub async fn log_matrix_message(
     working_dir: &str,
     room_id: &str,
@@ -177,7 +510,12 @@ pub async fn log_matrix_message(
     html: Option<&str>,
     chunk_index: Option<usize>,
     total_chunks: Option<usize>,
+    origin: InvocationOrigin,
 ) {
+    if !origin.in_transcript() {
+        return;
+    }
+
     let gorp_dir = format!("{}/.gorp", working_dir);
     if let Err(e) = create_dir_all(&gorp_dir).await {
         tracing::warn!(error = %e, "Failed to create .gorp directory for logging");
@@ -190,6 +528,7 @@ pub async fn log_matrix_message(
         room_id: room_id.to_string(),
         message_type: message_type.to_string(),
         content: content.to_string(),
+        origin,
         html: html.map(String::from),
         chunk_index,
         total_chunks,
@@ -251,4 +590,194 @@ mod tests {
         let result = strip_function_calls(input);
         assert_eq!(result, "Hello\n\nWorld");
     }
+
+    /// Every fence opened in a chunk must also be closed in that chunk
+    fn fences_balanced(chunk: &str) -> bool {
+        chunk
+            .lines()
+            .filter(|l| l.trim_start().starts_with("```"))
+            .count()
+            % 2
+            == 0
+    }
+
+    #[test]
+    fn test_chunk_message_short_text_unchanged() {
+        let input = "# Title\n\nShort body";
+        assert_eq!(chunk_message(input, 100), vec![input.to_string()]);
+    }
+
+    #[test]
+    fn test_chunk_message_prefers_paragraph_boundaries() {
+        let first = "a".repeat(60);
+        let second = "b ".repeat(30).trim_end().to_string();
+        let input = format!("{}\n\n{}", first, second);
+
+        let chunks = chunk_message(&input, 100);
+        assert_eq!(chunks, vec![first, second]);
+    }
+
+    #[test]
+    fn test_chunk_message_keeps_code_block_whole_when_it_fits() {
+        let intro = "Some intro text. ".repeat(4);
+        let code: Vec<String> = (0..6).map(|i| format!("let x{} = {};", i, i)).collect();
+        let input = format!(
+            "{}\n\n```rust\n{}\n```\n\nAfterwards.",
+            intro.trim(),
+            code.join("\n")
+        );
+
+        let chunks = chunk_message(&input, 120);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 120, "chunk too long: {}", chunk.len());
+            assert!(fences_balanced(chunk), "fence split in: {}", chunk);
+        }
+        let with_code: Vec<_> = chunks.iter().filter(|c| c.contains("let x0")).collect();
+        assert_eq!(with_code.len(), 1);
+        assert!(with_code[0].contains("let x5"));
+    }
+
+    #[test]
+    fn test_chunk_message_reopens_long_code_block_with_language() {
+        let body: Vec<String> = (0..50)
+            .map(|i| format!("    println!(\"line {}\");", i))
+            .collect();
+        let input = format!("```rust\n{}\n```", body.join("\n"));
+
+        let chunks = chunk_message(&input, 200);
+        assert!(chunks.len() > 1);
+        let mut rejoined = Vec::new();
+        for chunk in &chunks {
+            assert!(chunk.len() <= 200, "chunk too long: {}", chunk.len());
+            assert!(chunk.starts_with("```rust\n"), "missing reopen: {}", chunk);
+            assert!(chunk.ends_with("\n```"), "missing close: {}", chunk);
+            let lines: Vec<&str> = chunk.lines().collect();
+            rejoined.extend(lines[1..lines.len() - 1].iter().map(|l| l.to_string()));
+        }
+        // Indentation and line order survive the split
+        assert_eq!(rejoined, body);
+    }
+
+    #[test]
+    fn test_chunk_message_handles_tilde_and_unclosed_fences() {
+        let body = "x = 1\n".repeat(30);
+        let input = format!("~~~python\n{}", body);
+
+        let chunks = chunk_message(&input, 80);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.starts_with("~~~python\n"));
+            assert!(chunk.ends_with("\n~~~"));
+        }
+    }
+
+    #[test]
+    fn test_chunk_message_keeps_header_with_following_content() {
+        let filler = "word ".repeat(15).trim_end().to_string();
+        let input = format!(
+            "{}\n\n## Next section\n\nSection body that follows the header.",
+            filler
+        );
+
+        let chunks = chunk_message(&input, 100);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], filler);
+        assert!(chunks[1].starts_with("## Next section\n\nSection body"));
+    }
+
+    #[test]
+    fn test_chunk_message_header_leads_split_code_block() {
+        let body: Vec<String> = (0..40).map(|i| format!("echo {}", i)).collect();
+        let input = format!("### Script\n\n```sh\n{}\n```", body.join("\n"));
+
+        let chunks = chunk_message(&input, 120);
+        assert!(chunks[0].starts_with("### Script\n\n```sh\n"));
+        for chunk in &chunks {
+            assert!(chunk.len() <= 120);
+            assert!(fences_balanced(chunk));
+            assert!(!chunk.trim_end().ends_with("### Script"));
+        }
+    }
+
+    #[test]
+    fn test_chunk_message_splits_nested_lists_on_item_lines() {
+        let mut lines = Vec::new();
+        for i in 0..20 {
+            lines.push(format!("- item {}", i));
+            lines.push(format!("  - nested {}.a", i));
+            lines.push(format!("    - deeper {}.a.i", i));
+        }
+        let input = lines.join("\n");
+
+        let chunks = chunk_message(&input, 150);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 150);
+            for line in chunk.lines() {
+                assert!(lines.contains(&line.to_string()), "mangled line: {}", line);
+            }
+        }
+        assert_eq!(chunks.join("\n"), input);
+    }
+
+    #[test]
+    fn test_chunk_message_repeats_table_header() {
+        let mut rows = vec![
+            "| name | value |".to_string(),
+            "|------|-------|".to_string(),
+        ];
+        rows.extend((0..30).map(|i| format!("| key{} | {} |", i, i * 10)));
+        let input = rows.join("\n");
+
+        let chunks = chunk_message(&input, 150);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 150);
+            assert!(chunk.starts_with("| name | value |\n|------|-------|\n| key"));
+        }
+        let data_rows: usize = chunks.iter().map(|c| c.lines().count() - 2).sum();
+        assert_eq!(data_rows, 30);
+    }
+
+    #[test]
+    fn test_chunk_message_hard_splits_unbroken_text() {
+        let input = "é".repeat(100);
+        let chunks = chunk_message(&input, 51);
+        assert!(chunks.iter().all(|c| c.len() <= 51));
+        assert_eq!(chunks.concat(), input);
+    }
+
+    #[test]
+    fn test_truncate_response_zero_means_unlimited() {
+        let long = "word ".repeat(1000);
+        assert_eq!(truncate_response(&long, 0), None);
+        assert_eq!(truncate_response("short", 100), None);
+    }
+
+    #[test]
+    fn test_truncate_response_cuts_at_paragraph() {
+        let input = format!("{}\n\n{}", "a".repeat(60), "b".repeat(60));
+        let (kept, omitted) = truncate_response(&input, 100).unwrap();
+        assert_eq!(kept, "a".repeat(60));
+        assert_eq!(omitted, input.chars().count() - 60);
+    }
+
+    #[test]
+    fn test_truncate_response_closes_open_fence() {
+        let code: Vec<String> = (0..50).map(|i| format!("let x{} = {};", i, i)).collect();
+        let input = format!("Here:\n\n```rust\n{}\n```", code.join("\n"));
+        let (kept, _) = truncate_response(&input, 200).unwrap();
+        assert!(kept.starts_with("Here:\n\n```rust\n"));
+        assert!(kept.ends_with("\n```"));
+        assert_eq!(kept.matches("```").count(), 2);
+    }
+
+    #[test]
+    fn test_truncate_response_is_char_safe() {
+        let input = "é".repeat(300);
+        let (kept, omitted) = truncate_response(&input, 100).unwrap();
+        assert_eq!(kept.chars().count(), 100);
+        assert_eq!(omitted, 200);
+    }
 }
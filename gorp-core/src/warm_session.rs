@@ -1,13 +1,16 @@
 // ABOUTME: Manages warm Claude Code sessions to avoid 2-minute startup latency.
 // ABOUTME: Keeps AgentHandle instances alive per channel, with lazy creation and TTL cleanup.
 
+use crate::preprocess::PreprocessPipeline;
+use crate::runtime_mode::{Gate, RuntimeMode};
 use crate::session::Channel;
-use anyhow::Result;
-use gorp_agent::{AgentHandle, AgentRegistry};
+use crate::usage::InvocationOrigin;
+use anyhow::{Context, Result};
+use gorp_agent::{AgentHandle, AgentRegistry, ApproverGuard, ModelInfo, ToolApprover};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 
 /// Configuration for warm session behavior
 #[derive(Debug, Clone)]
@@ -25,6 +28,10 @@ pub struct WarmConfig {
     pub global_system_prompt_path: Option<String>,
     /// MCP server configs (for mux backend)
     pub mcp_servers: Vec<crate::config::McpServerConfig>,
+    /// Truncate responses longer than this many characters; 0 means unlimited
+    pub max_response_chars: usize,
+    /// Keep the full text of truncated responses in the channel workspace
+    pub save_truncated_responses: bool,
 }
 
 /// A warm session holding an active AgentHandle
@@ -33,10 +40,13 @@ pub struct WarmConfig {
 pub struct WarmSession {
     handle: AgentHandle,
     session_id: String,
+    created: Instant,
     last_used: Instant,
     /// Set to true when session is invalidated (orphaned/lost)
     /// Concurrent users should check this before using
     invalidated: bool,
+    /// Held for writing while a warm-up prompt runs; prompts take a read lock first
+    warmup: Arc<RwLock<()>>,
 }
 
 impl WarmSession {
@@ -63,6 +73,26 @@ impl WarmSession {
     }
 }
 
+/// What can be seen of one warm session from outside, e.g. in the admin panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmSessionInfo {
+    pub channel_name: String,
+    /// None while a prompt holds the session: its details can't be read then
+    pub session_id: Option<String>,
+    /// How long ago the session was started
+    pub age: Option<Duration>,
+    /// How long ago a prompt last used it
+    pub idle: Option<Duration>,
+    pub invalidated: bool,
+}
+
+impl WarmSessionInfo {
+    /// Whether a prompt is using the session right now
+    pub fn is_busy(&self) -> bool {
+        self.session_id.is_none()
+    }
+}
+
 /// Handle to a warm session, allowing concurrent access across channels
 pub type WarmSessionHandle = Arc<Mutex<WarmSession>>;
 
@@ -72,17 +102,26 @@ pub struct WarmSessionManager {
     /// Map of channel names to their session handles
     /// Each session has its own Mutex for per-channel locking
     sessions: HashMap<String, WarmSessionHandle>,
+    /// Per-room locks that make overlapping prompts in one channel take turns
+    turn_locks: HashMap<String, Arc<Mutex<()>>>,
     config: WarmConfig,
     /// Registry for creating agent backends
     registry: AgentRegistry,
+    /// Normal or safe mode; safe mode refuses every agent turn
+    runtime: RuntimeMode,
+    /// Steps run on prompts before they are sent
+    preprocessors: PreprocessPipeline,
 }
 
 impl WarmSessionManager {
     pub fn new(config: WarmConfig) -> Self {
         Self {
             sessions: HashMap::new(),
+            turn_locks: HashMap::new(),
             config,
             registry: AgentRegistry::default(),
+            runtime: RuntimeMode::default(),
+            preprocessors: PreprocessPipeline::default(),
         }
     }
 
@@ -90,8 +129,11 @@ impl WarmSessionManager {
     pub fn with_registry(config: WarmConfig, registry: AgentRegistry) -> Self {
         Self {
             sessions: HashMap::new(),
+            turn_locks: HashMap::new(),
             config,
             registry,
+            runtime: RuntimeMode::default(),
+            preprocessors: PreprocessPipeline::default(),
         }
     }
 
@@ -105,11 +147,42 @@ impl WarmSessionManager {
         &self.config.backend_type
     }
 
+    /// Switch the backend new handles are created with. Warm sessions keep the
+    /// backend they started on until they are evicted or expire.
+    pub fn set_default_backend(&mut self, backend_type: &str) -> Result<()> {
+        if !self.registry.contains(backend_type) {
+            let mut available = self.registry.available();
+            available.sort_unstable();
+            anyhow::bail!(
+                "Unknown backend: {} (available: {})",
+                backend_type,
+                available.join(", ")
+            );
+        }
+        tracing::info!(
+            from = %self.config.backend_type,
+            to = %backend_type,
+            "Default backend switched"
+        );
+        self.config.backend_type = backend_type.to_string();
+        Ok(())
+    }
+
     /// Get the keep-alive duration
     pub fn keep_alive_duration(&self) -> Duration {
         self.config.keep_alive_duration
     }
 
+    /// Get the response length limit in characters (0 means unlimited)
+    pub fn max_response_chars(&self) -> usize {
+        self.config.max_response_chars
+    }
+
+    /// Whether truncated responses are saved in full to the channel workspace
+    pub fn save_truncated_responses(&self) -> bool {
+        self.config.save_truncated_responses
+    }
+
     /// Get config clone for use outside lock
     pub fn config(&self) -> WarmConfig {
         self.config.clone()
@@ -143,6 +216,14 @@ impl WarmSessionManager {
                 }
             }
         });
+        // A turn lock nobody else holds or waits on can be recreated on demand
+        self.turn_locks
+            .retain(|_, lock| Arc::strong_count(lock) > 1);
+    }
+
+    /// The lock serializing agent turns in a room; see [`acquire_turn`]
+    pub fn turn_lock(&mut self, room_id: &str) -> Arc<Mutex<()>> {
+        Arc::clone(self.turn_locks.entry(room_id.to_string()).or_default())
     }
 
     /// Quick lookup for existing session - returns cloned Arc if exists
@@ -151,6 +232,39 @@ impl WarmSessionManager {
         self.sessions.get(channel_name).map(Arc::clone)
     }
 
+    /// Every warm session, sorted by channel name. Never waits on a session:
+    /// one a prompt is holding (perhaps a wedged one) is listed as busy.
+    pub fn snapshot(&self) -> Vec<WarmSessionInfo> {
+        let now = Instant::now();
+        let mut sessions: Vec<WarmSessionInfo> = self
+            .sessions
+            .iter()
+            .map(|(channel_name, handle)| match handle.try_lock() {
+                Ok(session) => WarmSessionInfo {
+                    channel_name: channel_name.clone(),
+                    session_id: Some(session.session_id.clone()),
+                    age: Some(now.duration_since(session.created)),
+                    idle: Some(now.duration_since(session.last_used)),
+                    invalidated: session.invalidated,
+                },
+                Err(_) => WarmSessionInfo {
+                    channel_name: channel_name.clone(),
+                    session_id: None,
+                    age: None,
+                    idle: None,
+                    invalidated: false,
+                },
+            })
+            .collect();
+        sessions.sort_by(|a, b| a.channel_name.cmp(&b.channel_name));
+        sessions
+    }
+
+    /// Number of warm sessions currently held
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
     /// Check if a channel has a warm session
     pub fn has_session(&self, channel_name: &str) -> bool {
         self.sessions.contains_key(channel_name)
@@ -175,28 +289,7 @@ impl WarmSessionManager {
     /// Create an AgentHandle using the registry
     /// This is synchronous and fast
     fn create_agent_handle(&self, working_dir: &str) -> Result<AgentHandle> {
-        let mut config = serde_json::json!({
-            "working_dir": working_dir,
-            "binary": self.config.agent_binary,
-        });
-
-        // Add mux-specific config if using mux backend
-        if self.config.backend_type == "mux" {
-            if let Some(ref model) = self.config.model {
-                config["model"] = serde_json::json!(model);
-            }
-            if let Some(max_tokens) = self.config.max_tokens {
-                config["max_tokens"] = serde_json::json!(max_tokens);
-            }
-            if let Some(ref path) = self.config.global_system_prompt_path {
-                config["global_system_prompt_path"] = serde_json::json!(path);
-            }
-            if !self.config.mcp_servers.is_empty() {
-                config["mcp_servers"] = serde_json::to_value(&self.config.mcp_servers)?;
-            }
-        }
-
-        self.registry.create(&self.config.backend_type, &config)
+        Self::create_agent_handle_with_config(&self.registry, working_dir, &self.config, None)
     }
 
     /// Create agent handle with explicit config (for use outside lock)
@@ -209,15 +302,52 @@ impl WarmSessionManager {
     ) -> Result<AgentHandle> {
         // Use override if provided, otherwise fall back to config default
         let backend_type = backend_override.unwrap_or(&warm_config.backend_type);
+        let config = Self::backend_config(working_dir, warm_config, backend_type)?;
 
+        tracing::info!(backend = %backend_type, working_dir = %working_dir, "Creating agent handle");
+        registry.create(backend_type, &config)
+    }
+
+    /// Create a handle for a one-off prompt that runs beside the channel's main session.
+    /// It is never cached: the backend shuts down once the handle is dropped.
+    /// With `read_only`, backends that support it refuse edits and state-changing tools.
+    pub fn create_ephemeral_handle(
+        registry: &AgentRegistry,
+        working_dir: &str,
+        warm_config: &WarmConfig,
+        backend_override: Option<&str>,
+        read_only: bool,
+    ) -> Result<AgentHandle> {
+        let backend_type = backend_override.unwrap_or(&warm_config.backend_type);
+        let mut config = Self::backend_config(working_dir, warm_config, backend_type)?;
+        config["read_only"] = serde_json::json!(read_only);
+
+        tracing::info!(
+            backend = %backend_type,
+            working_dir = %working_dir,
+            read_only,
+            "Creating ephemeral agent handle"
+        );
+        registry.create(backend_type, &config)
+    }
+
+    /// Backend config JSON for a handle working in `working_dir`
+    fn backend_config(
+        working_dir: &str,
+        warm_config: &WarmConfig,
+        backend_type: &str,
+    ) -> Result<serde_json::Value> {
         let mut config = serde_json::json!({
             "working_dir": working_dir,
             "binary": warm_config.agent_binary,
         });
 
+        // A model picked with !model wins over the configured one
+        let channel_model = read_channel_model(working_dir);
+
         // Add mux-specific config if using mux backend
         if backend_type == "mux" {
-            if let Some(ref model) = warm_config.model {
+            if let Some(model) = channel_model.as_ref().or(warm_config.model.as_ref()) {
                 config["model"] = serde_json::json!(model);
             }
             if let Some(max_tokens) = warm_config.max_tokens {
@@ -231,15 +361,51 @@ impl WarmSessionManager {
             }
         }
 
-        tracing::info!(backend = %backend_type, working_dir = %working_dir, "Creating agent handle");
-        registry.create(backend_type, &config)
+        // The CLI picks its own default unless the channel names a model
+        if backend_type == "direct" {
+            if let Some(model) = &channel_model {
+                config["model"] = serde_json::json!(model);
+            }
+        }
+
+        // Backends that know their web tools leave them out; the rest are stopped at approval
+        if !is_network_allowed(working_dir) {
+            config["network_access"] = serde_json::json!(false);
+        }
+
+        // Only key names are logged; channel env files often hold tokens
+        let env = read_channel_env(working_dir);
+        if !env.is_empty() {
+            let mut keys: Vec<&str> = env.keys().map(String::as_str).collect();
+            keys.sort_unstable();
+            tracing::info!(working_dir = %working_dir, keys = ?keys, "Applying channel environment");
+            config["env"] = serde_json::to_value(&env)?;
+        }
+        Ok(config)
+    }
+
+    /// The runtime mode shared with the scheduler, webhook server and notifiers
+    pub fn runtime_mode(&self) -> RuntimeMode {
+        self.runtime.clone()
+    }
+
+    /// Share `mode` with everything that prepares sessions through this manager
+    pub fn set_runtime_mode(&mut self, mode: RuntimeMode) {
+        self.runtime = mode;
+    }
+
+    /// The prompt preprocessing pipeline, cloned for use outside the lock
+    pub fn preprocessors(&self) -> PreprocessPipeline {
+        self.preprocessors.clone()
+    }
+
+    pub fn set_preprocessors(&mut self, pipeline: PreprocessPipeline) {
+        self.preprocessors = pipeline;
     }
 
     /// Get a clone of the registry for use outside the lock
     pub fn registry(&self) -> AgentRegistry {
-        // AgentRegistry doesn't implement Clone, so we create a new default one
-        // This is fine because all backends are registered in Default::default()
-        AgentRegistry::default()
+        self.registry.clone()
     }
 
     /// Evict a session from the warm cache
@@ -390,10 +556,14 @@ impl WarmSessionManager {
         let warm_session = WarmSession {
             handle: agent_handle,
             session_id: session_id.clone(),
+            created: Instant::now(),
             last_used: Instant::now(),
             invalidated: false,
+            warmup: Arc::new(RwLock::new(())),
         };
 
+        start_warmup(&warm_session, channel, is_new);
+
         let handle = Arc::new(Mutex::new(warm_session));
         self.sessions
             .insert(channel_name.clone(), Arc::clone(&handle));
@@ -463,8 +633,10 @@ impl WarmSessionManager {
         let session = WarmSession {
             handle,
             session_id,
+            created: last_used,
             last_used,
             invalidated: false,
+            warmup: Arc::new(RwLock::new(())),
         };
 
         self.sessions
@@ -472,18 +644,208 @@ impl WarmSessionManager {
     }
 }
 
+/// How long a warm-up prompt may run before prompts stop waiting for it
+const WARMUP_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Read the channel's warm-up prompt from `.gorp/warmup_prompt`, if one is set
+pub fn read_warmup_prompt(channel_dir: &str) -> Option<String> {
+    let path = std::path::Path::new(channel_dir)
+        .join(".gorp")
+        .join("warmup_prompt");
+    let prompt = std::fs::read_to_string(path).ok()?;
+    let prompt = prompt.trim();
+    (!prompt.is_empty()).then(|| prompt.to_string())
+}
+
+/// Backends that take a model when a session is created
+pub const MODEL_BACKENDS: &[&str] = &["mux", "direct"];
+
+/// Whether `name` could be a model ID; whether the backend knows it is up to the backend
+pub fn is_valid_model_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._:/@".contains(c))
+}
+
+/// Read the channel's model from `.gorp/model`, if `!model set` chose one
+pub fn read_channel_model(channel_dir: &str) -> Option<String> {
+    let path = std::path::Path::new(channel_dir)
+        .join(".gorp")
+        .join("model");
+    let model = std::fs::read_to_string(path).ok()?;
+    let model = model.trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+/// Set the channel's model, or go back to the configured default with None.
+/// Takes effect the next time the channel's agent handle is created.
+pub fn write_channel_model(channel_dir: &str, model: Option<&str>) -> Result<()> {
+    let gorp_dir = std::path::Path::new(channel_dir).join(".gorp");
+    let path = gorp_dir.join("model");
+    match model {
+        Some(model) => {
+            std::fs::create_dir_all(&gorp_dir)
+                .with_context(|| format!("Failed to create {}", gorp_dir.display()))?;
+            std::fs::write(&path, format!("{}\n", model))
+                .with_context(|| format!("Failed to write {}", path.display()))
+        }
+        None => match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Whether a tool call reaches the network: any call of kind "fetch" (which covers the
+/// mux web tools and ACP fetches) or one of the Claude CLI's web tools
+pub fn is_network_tool(name: &str, kind: &str) -> bool {
+    kind.eq_ignore_ascii_case("fetch")
+        || ["webfetch", "websearch", "web_fetch", "web_search"]
+            .contains(&name.to_lowercase().as_str())
+}
+
+/// Whether the channel's agent may use network tools; `!network off` creates
+/// `.gorp/no-network`, so channels have network access by default
+pub fn is_network_allowed(channel_dir: &str) -> bool {
+    !std::path::Path::new(channel_dir)
+        .join(".gorp")
+        .join("no-network")
+        .exists()
+}
+
+/// Allow or deny the channel's agent network tools.
+/// Takes effect the next time the channel's agent handle is created.
+pub fn set_network_allowed(channel_dir: &str, allowed: bool) -> Result<()> {
+    let gorp_dir = std::path::Path::new(channel_dir).join(".gorp");
+    let path = gorp_dir.join("no-network");
+    if allowed {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    } else {
+        std::fs::create_dir_all(&gorp_dir)
+            .with_context(|| format!("Failed to create {}", gorp_dir.display()))?;
+        std::fs::write(&path, "").with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Read the channel's extra agent environment from `.gorp/env`, if it has one
+pub fn read_channel_env(channel_dir: &str) -> HashMap<String, String> {
+    let path = std::path::Path::new(channel_dir).join(".gorp").join("env");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => parse_channel_env(&content, &path.display().to_string()),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Parse `KEY=VALUE` lines. Blank lines and `#` comments are ignored, an
+/// `export ` prefix and matching quotes around the value are dropped, and
+/// anything else is skipped with a warning that names the line but never its value.
+pub fn parse_channel_env(content: &str, source: &str) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            tracing::warn!(file = %source, line = number + 1, "Skipping env line without '='");
+            continue;
+        };
+        let key = key.trim();
+        let valid_key = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            tracing::warn!(file = %source, line = number + 1, "Skipping env line with invalid key");
+            continue;
+        }
+        let value = value.trim();
+        let value = ['"', '\'']
+            .into_iter()
+            .find_map(|q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+            .unwrap_or(value);
+        env.insert(key.to_string(), value.to_string());
+    }
+    env
+}
+
+/// Bring a session up in the background: check out or pull the channel's git repo
+/// when it has one, then send the warm-up prompt if the session is new. The warm-up
+/// output is discarded; the first real prompt waits on the session's warm-up lock.
+fn start_warmup(session: &WarmSession, channel: &Channel, is_new: bool) {
+    let repo = crate::git_seed::read_git_repo(&channel.directory)
+        .filter(|repo| repo.syncs_on_session_start(&channel.directory));
+    let prompt = read_warmup_prompt(&channel.directory).filter(|_| is_new);
+    if repo.is_none() && prompt.is_none() {
+        return;
+    }
+    let Ok(guard) = Arc::clone(&session.warmup).try_write_owned() else {
+        return;
+    };
+    let agent_handle = session.handle.clone();
+    let session_id = session.session_id.clone();
+    let channel_name = channel.channel_name.clone();
+    let channel_dir = channel.directory.clone();
+
+    tokio::spawn(async move {
+        let _guard = guard;
+        if let Some(repo) = repo {
+            crate::git_seed::sync_logged(&channel_name, &channel_dir, &repo).await;
+        }
+        let Some(prompt) = prompt else {
+            return;
+        };
+        tracing::info!(channel = %channel_name, session_id = %session_id, "Sending warm-up prompt");
+        let warmup = async {
+            let mut receiver = agent_handle.prompt(&session_id, &prompt).await?;
+            while let Some(event) = receiver.recv().await {
+                match event {
+                    gorp_agent::AgentEvent::Result { .. } => return Ok(()),
+                    gorp_agent::AgentEvent::Error { message, .. } => {
+                        return Err(anyhow::anyhow!(message))
+                    }
+                    gorp_agent::AgentEvent::SessionInvalid { reason } => {
+                        return Err(anyhow::anyhow!(reason))
+                    }
+                    _ => {}
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        match tokio::time::timeout(WARMUP_TIMEOUT, warmup).await {
+            Ok(Ok(())) => tracing::info!(channel = %channel_name, "Warm-up prompt finished"),
+            Ok(Err(e)) => {
+                tracing::warn!(channel = %channel_name, error = %e, "Warm-up prompt failed")
+            }
+            Err(_) => tracing::warn!(channel = %channel_name, "Warm-up prompt timed out"),
+        }
+    });
+}
+
 /// Send a prompt using a session handle - does NOT require manager lock
 /// This allows concurrent prompts across different channels
 /// Returns the EventReceiver directly - caller is responsible for consuming events
+/// `origin` records what triggered the prompt; there is deliberately no default.
 pub async fn send_prompt_with_handle(
     handle: &WarmSessionHandle,
     session_id: &str,
     text: &str,
+    origin: InvocationOrigin,
 ) -> Result<gorp_agent::EventReceiver> {
-    tracing::debug!(session_id = %session_id, prompt_len = text.len(), "Sending prompt");
+    tracing::debug!(session_id = %session_id, prompt_len = text.len(), origin = %origin, "Sending prompt");
 
     // Hold lock briefly just to clone the AgentHandle, check validity, and update last_used
-    let agent_handle = {
+    let (agent_handle, warmup) = {
         let mut session = handle.lock().await;
         // Check if session was invalidated by another task (orphan recovery)
         if session.invalidated {
@@ -492,16 +854,50 @@ pub async fn send_prompt_with_handle(
             ));
         }
         session.last_used = Instant::now();
-        session.handle.clone()
+        (session.handle.clone(), Arc::clone(&session.warmup))
     };
     // Lock released here - allows concurrent prompts to same channel to proceed
 
+    // Let a still-running warm-up finish so the prompt lands in a loaded context
+    drop(warmup.read().await);
+
     // Send prompt and get event receiver - this happens outside the lock
     let receiver = agent_handle.prompt(session_id, text).await?;
 
     Ok(receiver)
 }
 
+/// Have `approver` review the session's tool calls until the returned guard is dropped
+pub async fn set_tool_approver(
+    handle: &WarmSessionHandle,
+    approver: Arc<dyn ToolApprover>,
+) -> ApproverGuard {
+    handle.lock().await.handle.set_tool_approver(approver)
+}
+
+/// Ask the backend behind a warm session which models it can run
+pub async fn list_models_with_handle(handle: &WarmSessionHandle) -> Result<Vec<ModelInfo>> {
+    let agent_handle = handle.lock().await.handle.clone();
+    agent_handle.list_models().await
+}
+
+/// Wait for the turn already running in `room_id` (if any), then hold the channel
+/// until the guard is dropped. Two prompts sent close together would otherwise run
+/// against the same session at once and interleave its context; waiters go in arrival order.
+pub async fn acquire_turn(
+    manager: &SharedWarmSessionManager,
+    room_id: &str,
+) -> OwnedMutexGuard<()> {
+    let lock = manager.write().await.turn_lock(room_id);
+    match Arc::clone(&lock).try_lock_owned() {
+        Ok(guard) => guard,
+        Err(_) => {
+            tracing::info!(room_id = %room_id, "Channel busy, prompt queued behind the current turn");
+            lock.lock_owned().await
+        }
+    }
+}
+
 /// Thread-safe wrapper for WarmSessionManager
 pub type SharedWarmSessionManager = Arc<RwLock<WarmSessionManager>>;
 
@@ -518,6 +914,14 @@ pub async fn prepare_session_async(
 ) -> Result<(WarmSessionHandle, String, bool)> {
     let channel_name = &channel.channel_name;
 
+    // Every agent turn comes through here, so this is where safe mode stops them
+    manager
+        .read()
+        .await
+        .runtime
+        .check(Gate::AgentTurn)
+        .map_err(anyhow::Error::new)?;
+
     // Step 1: Quick read lock to check for existing session
     {
         let mgr = manager.read().await;
@@ -652,10 +1056,14 @@ pub async fn prepare_session_async(
     let warm_session = WarmSession {
         handle: agent_handle,
         session_id: session_id.clone(),
+        created: Instant::now(),
         last_used: Instant::now(),
         invalidated: false,
+        warmup: Arc::new(RwLock::new(())),
     };
 
+    start_warmup(&warm_session, channel, is_new);
+
     let handle = Arc::new(Mutex::new(warm_session));
 
     // Step 5: Brief write lock to insert (handles race condition)
@@ -683,6 +1091,8 @@ mod tests {
             max_tokens: None,
             global_system_prompt_path: None,
             mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
         };
         let manager = WarmSessionManager::new(config);
         assert_eq!(manager.agent_binary(), "claude");
@@ -700,6 +1110,8 @@ mod tests {
             max_tokens: None,
             global_system_prompt_path: None,
             mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
         };
         let mut manager = WarmSessionManager::new(config);
 
@@ -742,6 +1154,8 @@ mod tests {
             max_tokens: None,
             global_system_prompt_path: None,
             mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
         };
         let mut manager = WarmSessionManager::new(config);
 
@@ -761,6 +1175,8 @@ mod tests {
             max_tokens: None,
             global_system_prompt_path: None,
             mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
         };
         let mut manager = WarmSessionManager::new(config);
 
@@ -791,6 +1207,8 @@ mod tests {
             max_tokens: None,
             global_system_prompt_path: None,
             mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
         };
         let mut manager = WarmSessionManager::new(config);
 
@@ -809,6 +1227,43 @@ mod tests {
         assert!(!manager.has_session("test_channel"));
     }
 
+    #[tokio::test]
+    async fn test_snapshot_reports_idle_and_busy_sessions() {
+        let mut manager = WarmSessionManager::new(WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "acp".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        });
+        manager.inject_test_session(
+            "idle".to_string(),
+            "session_idle".to_string(),
+            Instant::now() - Duration::from_secs(90),
+        );
+        manager.inject_test_session(
+            "busy".to_string(),
+            "session_busy".to_string(),
+            Instant::now(),
+        );
+        let busy = manager.get_existing_session("busy").unwrap();
+        let _held = busy.lock().await;
+
+        let snapshot = manager.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].channel_name, "busy");
+        assert!(snapshot[0].is_busy());
+        assert_eq!(snapshot[1].channel_name, "idle");
+        assert_eq!(snapshot[1].session_id.as_deref(), Some("session_idle"));
+        assert!(snapshot[1].idle.unwrap() >= Duration::from_secs(90));
+        assert!(!snapshot[1].is_busy());
+    }
+
     #[test]
     fn test_evict_returns_false_for_nonexistent_session() {
         let config = WarmConfig {
@@ -820,6 +1275,8 @@ mod tests {
             max_tokens: None,
             global_system_prompt_path: None,
             mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
         };
         let mut manager = WarmSessionManager::new(config);
 
@@ -830,4 +1287,347 @@ mod tests {
             "evict() should return false when session doesn't exist"
         );
     }
+
+    #[tokio::test]
+    async fn test_set_default_backend_validates_and_keeps_warm_sessions() {
+        let config = WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "direct".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        };
+        let mut manager = WarmSessionManager::new(config);
+        manager.inject_test_session(
+            "test_channel".to_string(),
+            "session_123".to_string(),
+            Instant::now(),
+        );
+
+        let err = manager.set_default_backend("nonesuch").unwrap_err();
+        assert!(err.to_string().contains("Unknown backend: nonesuch"));
+        assert_eq!(manager.backend_type(), "direct");
+
+        manager.set_default_backend("mock").unwrap();
+        assert_eq!(manager.backend_type(), "mock");
+        assert_eq!(manager.config().backend_type, "mock");
+        // Sessions already running are left alone
+        assert!(manager.has_session("test_channel"));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_turn_serializes_prompts_per_room() {
+        let manager = create_shared_manager(WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "acp".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        });
+
+        let first = acquire_turn(&manager, "!a:example.com").await;
+        // Other rooms are never held up
+        drop(acquire_turn(&manager, "!b:example.com").await);
+
+        let waiter = tokio::spawn({
+            let manager = Arc::clone(&manager);
+            async move { drop(acquire_turn(&manager, "!a:example.com").await) }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        drop(first);
+        waiter.await.unwrap();
+
+        // Idle locks are dropped with stale sessions
+        manager.write().await.cleanup_stale();
+        assert!(manager.read().await.turn_locks.is_empty());
+    }
+
+    /// Registry with a "recording" backend that logs every prompt it receives
+    fn recording_registry(prompts: Arc<std::sync::Mutex<Vec<String>>>) -> AgentRegistry {
+        use gorp_agent::handle::Command;
+        use gorp_agent::AgentEvent;
+
+        AgentRegistry::new().register("recording", move |_config| {
+            let prompts = Arc::clone(&prompts);
+            let (tx, mut rx) = tokio::sync::mpsc::channel::<Command>(8);
+            tokio::spawn(async move {
+                while let Some(cmd) = rx.recv().await {
+                    match cmd {
+                        Command::NewSession { reply } => {
+                            let _ = reply.send(Ok("recording-session".to_string()));
+                        }
+                        Command::LoadSession { reply, .. } => {
+                            let _ = reply.send(Ok(()));
+                        }
+                        Command::Prompt {
+                            text,
+                            event_tx,
+                            reply,
+                            ..
+                        } => {
+                            prompts.lock().unwrap().push(text.clone());
+                            let _ = reply.send(Ok(()));
+                            let _ = event_tx
+                                .send(AgentEvent::Result {
+                                    text: format!("done: {}", text),
+                                    usage: None,
+                                    metadata: serde_json::json!({}),
+                                })
+                                .await;
+                        }
+                        Command::Cancel { reply, .. } => {
+                            let _ = reply.send(Ok(()));
+                        }
+                        Command::ListModels { reply } => {
+                            let _ = reply.send(Ok(gorp_agent::model::default_models()));
+                        }
+                    }
+                }
+            });
+            Ok(AgentHandle::new(tx, "recording"))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_warmup_prompt_runs_once_before_first_user_prompt() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".gorp")).unwrap();
+        std::fs::write(
+            dir.path().join(".gorp/warmup_prompt"),
+            "load the project context\n",
+        )
+        .unwrap();
+
+        let prompts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let config = WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "recording".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        };
+        let manager = Arc::new(RwLock::new(WarmSessionManager::with_registry(
+            config,
+            recording_registry(Arc::clone(&prompts)),
+        )));
+        let channel = Channel {
+            channel_name: "warm".to_string(),
+            room_id: "!warm:example.com".to_string(),
+            session_id: String::new(),
+            directory: dir.path().to_string_lossy().to_string(),
+            started: false,
+            created_at: String::new(),
+            backend_type: None,
+            is_dispatch_room: false,
+        };
+
+        for text in ["first question", "second question"] {
+            let (handle, session_id, _) = prepare_session_async(&manager, &channel).await.unwrap();
+            let mut receiver =
+                send_prompt_with_handle(&handle, &session_id, text, InvocationOrigin::User)
+                    .await
+                    .unwrap();
+            while receiver.recv().await.is_some() {}
+        }
+
+        assert_eq!(
+            *prompts.lock().unwrap(),
+            vec![
+                "load the project context".to_string(),
+                "first question".to_string(),
+                "second question".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_safe_mode_refuses_to_prepare_sessions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let prompts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let config = WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "recording".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        };
+        let mut manager =
+            WarmSessionManager::with_registry(config, recording_registry(Arc::clone(&prompts)));
+        let mode = RuntimeMode::new(true);
+        manager.set_runtime_mode(mode.clone());
+        let manager = Arc::new(RwLock::new(manager));
+        let channel = Channel {
+            channel_name: "safe".to_string(),
+            room_id: "!safe:example.com".to_string(),
+            session_id: String::new(),
+            directory: dir.path().to_string_lossy().to_string(),
+            started: false,
+            created_at: String::new(),
+            backend_type: None,
+            is_dispatch_room: false,
+        };
+
+        let err = prepare_session_async(&manager, &channel)
+            .await
+            .err()
+            .unwrap();
+        let refusal = err
+            .downcast_ref::<crate::runtime_mode::SafeModeRefusal>()
+            .unwrap();
+        assert!(refusal.message().contains("agents are not being run"));
+        assert!(!manager.read().await.has_session("safe"));
+
+        mode.leave_safe_mode();
+        assert!(prepare_session_async(&manager, &channel).await.is_ok());
+    }
+
+    #[test]
+    fn test_parse_channel_env() {
+        let content = "\
+# API credentials
+
+GITHUB_TOKEN=ghp_abc=123
+export REGION = \"eu-west-1\"
+QUOTED='single quoted'
+not a variable
+1BAD=x
+    # indented comment
+EMPTY=
+";
+        let env = parse_channel_env(content, ".gorp/env");
+
+        assert_eq!(env.len(), 4);
+        assert_eq!(env["GITHUB_TOKEN"], "ghp_abc=123");
+        assert_eq!(env["REGION"], "eu-west-1");
+        assert_eq!(env["QUOTED"], "single quoted");
+        assert_eq!(env["EMPTY"], "");
+    }
+
+    #[test]
+    fn test_read_channel_env_missing_file_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(read_channel_env(dir.path().to_str().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_channel_model_overrides_backend_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+        let config = WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "acp".to_string(),
+            model: Some("claude-sonnet-4-5".to_string()),
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        };
+
+        let mux = WarmSessionManager::backend_config(dir_str, &config, "mux").unwrap();
+        assert_eq!(mux["model"], "claude-sonnet-4-5");
+        let direct = WarmSessionManager::backend_config(dir_str, &config, "direct").unwrap();
+        assert!(direct.get("model").is_none());
+
+        write_channel_model(dir_str, Some("claude-opus-4-1")).unwrap();
+        assert_eq!(
+            read_channel_model(dir_str).as_deref(),
+            Some("claude-opus-4-1")
+        );
+        for backend in MODEL_BACKENDS {
+            let cfg = WarmSessionManager::backend_config(dir_str, &config, backend).unwrap();
+            assert_eq!(cfg["model"], "claude-opus-4-1", "{}", backend);
+        }
+        let acp = WarmSessionManager::backend_config(dir_str, &config, "acp").unwrap();
+        assert!(acp.get("model").is_none());
+
+        write_channel_model(dir_str, None).unwrap();
+        write_channel_model(dir_str, None).unwrap();
+        assert_eq!(read_channel_model(dir_str), None);
+    }
+
+    #[test]
+    fn test_network_access_is_left_out_of_backend_config_when_off() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+        let config = WarmConfig {
+            keep_alive_duration: Duration::from_secs(3600),
+            pre_warm_lead_time: Duration::from_secs(300),
+            agent_binary: "claude".to_string(),
+            backend_type: "mux".to_string(),
+            model: Some("claude-sonnet-4-5".to_string()),
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: Vec::new(),
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        };
+
+        assert!(is_network_allowed(dir_str));
+        let cfg = WarmSessionManager::backend_config(dir_str, &config, "mux").unwrap();
+        assert!(cfg.get("network_access").is_none());
+
+        set_network_allowed(dir_str, false).unwrap();
+        assert!(!is_network_allowed(dir_str));
+        for backend in ["mux", "direct", "acp"] {
+            let cfg = WarmSessionManager::backend_config(dir_str, &config, backend).unwrap();
+            assert_eq!(cfg["network_access"], false, "{}", backend);
+        }
+
+        set_network_allowed(dir_str, true).unwrap();
+        set_network_allowed(dir_str, true).unwrap();
+        assert!(is_network_allowed(dir_str));
+    }
+
+    #[test]
+    fn test_is_network_tool() {
+        assert!(is_network_tool("web_fetch", "fetch"));
+        assert!(is_network_tool("Fetch https://example.com", "fetch"));
+        assert!(is_network_tool("WebSearch", "other"));
+        assert!(!is_network_tool("bash", "execute"));
+        assert!(!is_network_tool("read_file", "read"));
+    }
+
+    #[test]
+    fn test_is_valid_model_name() {
+        assert!(is_valid_model_name("claude-opus-4-1"));
+        assert!(is_valid_model_name("anthropic/claude-3.5-sonnet@latest"));
+        assert!(!is_valid_model_name(""));
+        assert!(!is_valid_model_name("opus; rm -rf /"));
+    }
+
+    #[test]
+    fn test_read_warmup_prompt_ignores_missing_and_blank_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+        assert_eq!(read_warmup_prompt(dir_str), None);
+
+        std::fs::create_dir_all(dir.path().join(".gorp")).unwrap();
+        std::fs::write(dir.path().join(".gorp/warmup_prompt"), "  \n").unwrap();
+        assert_eq!(read_warmup_prompt(dir_str), None);
+    }
 }
@@ -0,0 +1,166 @@
+// ABOUTME: Webhook burst coalescing: grouping keys, the pending batch type and the prompt a batch becomes.
+// ABOUTME: Batches are stored in SessionStore so deliveries held at a crash are still flushed on restart.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Payload samples kept per batch; later deliveries only add to the count
+pub const MAX_SAMPLES: usize = 5;
+
+/// Longest payload sample kept, in characters
+pub const MAX_SAMPLE_CHARS: usize = 500;
+
+/// Deliveries to one channel with the same grouping key, waiting for their window to close
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookBatch {
+    pub id: i64,
+    pub channel_name: String,
+    pub group_key: String,
+    /// Prompt of the first delivery; the rest are assumed to be near-identical
+    pub prompt: String,
+    pub samples: Vec<String>,
+    pub count: u32,
+    pub first_at: DateTime<Utc>,
+    pub last_at: DateTime<Utc>,
+}
+
+impl WebhookBatch {
+    /// The single prompt the agent receives for the whole batch
+    pub fn render(&self) -> String {
+        if self.count <= 1 {
+            return self.prompt.clone();
+        }
+        let mut text = format!(
+            "{} occurrences of {} between {} and {} UTC.\n\n{}",
+            self.count,
+            self.group_key,
+            self.first_at.format("%H:%M:%S"),
+            self.last_at.format("%H:%M:%S"),
+            self.prompt
+        );
+        if !self.samples.is_empty() {
+            text.push_str("\n\nPayload samples:");
+            for sample in &self.samples {
+                text.push_str(&format!("\n- {}", sample));
+            }
+        }
+        text
+    }
+}
+
+/// What a delivery is grouped by: the `group_by` field of its payload when
+/// there is one, otherwise the prompt itself
+pub fn group_key(prompt: &str, payload: Option<&Value>, group_by: Option<&str>) -> String {
+    match (payload, group_by) {
+        (Some(payload), Some(path)) => match extract_path(payload, path) {
+            Some(value) => value,
+            None => prompt.trim().to_string(),
+        },
+        _ => prompt.trim().to_string(),
+    }
+}
+
+/// Look up a simple JSONPath (`$.labels.alertname`, `$.alerts[0].name`) in `value`.
+/// Strings come back bare; other values as compact JSON.
+pub fn extract_path(value: &Value, path: &str) -> Option<String> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (field, indexes) = match segment.find('[') {
+            Some(i) => segment.split_at(i),
+            None => (segment, ""),
+        };
+        if !field.is_empty() {
+            current = current.get(field)?;
+        }
+        for index in indexes.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.strip_suffix(']')?.parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    match current {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// A payload shortened for the batch prompt
+pub fn sample_of(payload: &Value) -> String {
+    let text = payload.to_string();
+    if text.chars().count() <= MAX_SAMPLE_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(MAX_SAMPLE_CHARS).collect();
+    format!("{}…", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    #[test]
+    fn test_extract_path() {
+        let payload = json!({
+            "labels": {"alertname": "DiskFull", "severity": 2},
+            "alerts": [{"name": "first"}, {"name": "second"}]
+        });
+        assert_eq!(
+            extract_path(&payload, "$.labels.alertname").as_deref(),
+            Some("DiskFull")
+        );
+        assert_eq!(
+            extract_path(&payload, "labels.severity").as_deref(),
+            Some("2")
+        );
+        assert_eq!(
+            extract_path(&payload, "$.alerts[1].name").as_deref(),
+            Some("second")
+        );
+        assert_eq!(extract_path(&payload, "$.alerts[5].name"), None);
+        assert_eq!(extract_path(&payload, "$.missing"), None);
+    }
+
+    #[test]
+    fn test_group_key_falls_back_to_prompt() {
+        let payload = json!({"alertname": "DiskFull"});
+        assert_eq!(
+            group_key("Investigate", Some(&payload), Some("$.alertname")),
+            "DiskFull"
+        );
+        assert_eq!(
+            group_key(" Investigate ", Some(&payload), Some("$.other")),
+            "Investigate"
+        );
+        assert_eq!(
+            group_key("Investigate", None, Some("$.alertname")),
+            "Investigate"
+        );
+    }
+
+    #[test]
+    fn test_render_batch() {
+        let batch = WebhookBatch {
+            id: 1,
+            channel_name: "alerts".to_string(),
+            group_key: "DiskFull".to_string(),
+            prompt: "Investigate this alert".to_string(),
+            samples: vec![r#"{"host":"a"}"#.to_string(), r#"{"host":"b"}"#.to_string()],
+            count: 12,
+            first_at: Utc.with_ymd_and_hms(2026, 3, 4, 10, 2, 5).unwrap(),
+            last_at: Utc.with_ymd_and_hms(2026, 3, 4, 10, 3, 0).unwrap(),
+        };
+        assert_eq!(
+            batch.render(),
+            "12 occurrences of DiskFull between 10:02:05 and 10:03:00 UTC.\n\n\
+             Investigate this alert\n\n\
+             Payload samples:\n- {\"host\":\"a\"}\n- {\"host\":\"b\"}"
+        );
+
+        let single = WebhookBatch { count: 1, ..batch };
+        assert_eq!(single.render(), "Investigate this alert");
+    }
+}
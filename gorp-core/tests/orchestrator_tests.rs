@@ -207,25 +207,25 @@ mod tests {
 
     #[test]
     fn test_command_parsing_with_prefix() {
-        let result = parse_message("!claude help", "!claude");
+        let result = parse_message("!claude help", &["!claude", "!"]);
         assert!(matches!(result, ParseResult::Command(cmd) if cmd.name == "help"));
     }
 
     #[test]
     fn test_command_parsing_bang_prefix() {
-        let result = parse_message("!help", "!claude");
+        let result = parse_message("!help", &["!claude", "!"]);
         assert!(matches!(result, ParseResult::Command(cmd) if cmd.name == "help"));
     }
 
     #[test]
     fn test_command_parsing_not_command() {
-        let result = parse_message("hello world", "!claude");
+        let result = parse_message("hello world", &["!claude", "!"]);
         assert!(matches!(result, ParseResult::Message(_)));
     }
 
     #[test]
     fn test_command_with_args() {
-        let result = parse_message("!backend set mux", "!claude");
+        let result = parse_message("!backend set mux", &["!claude", "!"]);
         if let ParseResult::Command(cmd) = result {
             assert_eq!(cmd.name, "backend");
             assert_eq!(cmd.args, vec!["set", "mux"]);
@@ -245,8 +245,9 @@ mod tests {
             body: "Hello, bot!".to_string(),
             is_direct: false,
             formatted: false,
-            attachment: None,
+            attachments: Vec::new(),
             event_id: "$event123".to_string(),
+            edits_event_id: None,
             timestamp: 1234567890,
         };
 
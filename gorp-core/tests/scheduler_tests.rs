@@ -1,10 +1,11 @@
 // ABOUTME: Tests for the scheduler module - time parsing and schedule store CRUD
 // ABOUTME: Covers natural language parsing, cron expressions, and database operations
 
-use chrono::{Duration, Utc};
+use chrono::{Duration, TimeZone, Utc};
 use gorp_core::scheduler::{
-    compute_next_cron_execution, compute_next_cron_execution_in_tz, parse_time_expression,
-    ParsedSchedule, ScheduleStatus, ScheduledPrompt, SchedulerStore,
+    compute_next_cron_execution, compute_next_cron_execution_after,
+    compute_next_cron_execution_in_tz, parse_time_expression, ParsedSchedule, QuietHours,
+    QuietPolicy, ScheduleStatus, ScheduledPrompt, SchedulerStore,
 };
 use rusqlite::Connection;
 use std::sync::{Arc, Mutex};
@@ -309,6 +310,17 @@ fn test_compute_next_cron_execution_in_tz() {
     assert!(next > Utc::now());
 }
 
+#[test]
+fn test_compute_next_cron_execution_after_is_strictly_later() {
+    let at_nine = Utc.with_ymd_and_hms(2026, 3, 4, 9, 0, 0).unwrap();
+    let next = compute_next_cron_execution_after("0 9 * * *", "UTC", at_nine).unwrap();
+    assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 5, 9, 0, 0).unwrap());
+
+    let just_before = at_nine - Duration::milliseconds(1);
+    let next = compute_next_cron_execution_after("0 9 * * *", "UTC", just_before).unwrap();
+    assert_eq!(next, at_nine);
+}
+
 #[test]
 fn test_compute_next_cron_execution_invalid_cron() {
     assert!(compute_next_cron_execution("invalid cron").is_err());
@@ -582,3 +594,132 @@ fn test_store_get_schedule_alias() {
     assert!(retrieved.is_some());
     assert_eq!(retrieved.unwrap().id, "alias-test");
 }
+
+#[test]
+fn test_store_next_due_at_ignores_inactive_schedules() {
+    let store = create_test_store();
+    assert_eq!(store.next_due_at().unwrap(), None);
+
+    let soon = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+    let later = soon + Duration::hours(2);
+    let mut first = create_test_schedule("soon", "general", "Soon");
+    first.next_execution_at = soon.to_rfc3339();
+    let mut second = create_test_schedule("later", "general", "Later");
+    second.next_execution_at = later.to_rfc3339();
+    store.create_schedule(&first).unwrap();
+    store.create_schedule(&second).unwrap();
+    assert_eq!(store.next_due_at().unwrap(), Some(soon));
+
+    store.pause_schedule("soon").unwrap();
+    assert_eq!(store.next_due_at().unwrap(), Some(later));
+}
+
+#[test]
+fn test_store_quiet_policy_defaults_to_defer() {
+    let store = create_test_store();
+    store
+        .create_schedule(&create_test_schedule("quiet", "general", "Quiet"))
+        .unwrap();
+    assert_eq!(store.quiet_policy("quiet").unwrap(), QuietPolicy::Defer);
+
+    assert!(store
+        .set_quiet_policy("quiet", QuietPolicy::Silent)
+        .unwrap());
+    assert_eq!(store.quiet_policy("quiet").unwrap(), QuietPolicy::Silent);
+    assert!(!store
+        .set_quiet_policy("missing", QuietPolicy::Silent)
+        .unwrap());
+}
+
+#[test]
+fn test_store_defer_schedule_waits_without_counting_a_run() {
+    let store = create_test_store();
+    let mut schedule = create_test_schedule("deferred", "general", "Deferred");
+    schedule.next_execution_at = (Utc::now() - Duration::minutes(1)).to_rfc3339();
+    store.create_schedule(&schedule).unwrap();
+    assert_eq!(store.claim_due_schedules(Utc::now()).unwrap().len(), 1);
+
+    let until = Utc.with_ymd_and_hms(2030, 1, 2, 7, 0, 0).unwrap();
+    store.defer_schedule("deferred", until).unwrap();
+
+    let retrieved = store.get_by_id("deferred").unwrap().unwrap();
+    assert_eq!(retrieved.status, ScheduleStatus::Active);
+    assert_eq!(retrieved.execution_count, 0);
+    assert_eq!(store.next_due_at().unwrap(), Some(until));
+}
+
+// =============================================================================
+// Quiet Hours Tests
+// =============================================================================
+
+#[test]
+fn test_quiet_hours_parse() {
+    let quiet = QuietHours::parse("22:00-07:00").unwrap();
+    assert_eq!(quiet.to_string(), "22:00-07:00");
+    assert!(QuietHours::parse("22:00").is_err());
+    assert!(QuietHours::parse("25:00-07:00").is_err());
+    assert!(QuietHours::parse("07:00-07:00").is_err());
+}
+
+#[test]
+fn test_quiet_hours_straddling_midnight() {
+    let quiet = QuietHours::parse("22:00-07:00").unwrap();
+
+    // Late evening waits for the next morning
+    let evening = Utc.with_ymd_and_hms(2026, 3, 2, 23, 30, 0).unwrap();
+    assert_eq!(
+        quiet.next_allowed(evening, "UTC"),
+        Some(Utc.with_ymd_and_hms(2026, 3, 3, 7, 0, 0).unwrap())
+    );
+
+    // Small hours wait for the same morning
+    let night = Utc.with_ymd_and_hms(2026, 3, 3, 3, 0, 0).unwrap();
+    assert_eq!(
+        quiet.next_allowed(night, "UTC"),
+        Some(Utc.with_ymd_and_hms(2026, 3, 3, 7, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn test_quiet_hours_just_outside_window() {
+    let quiet = QuietHours::parse("22:00-07:00").unwrap();
+    let before_start = Utc.with_ymd_and_hms(2026, 3, 2, 21, 59, 0).unwrap();
+    let at_end = Utc.with_ymd_and_hms(2026, 3, 3, 7, 0, 0).unwrap();
+    assert!(!quiet.contains(before_start, "UTC"));
+    assert!(!quiet.contains(at_end, "UTC"));
+    assert!(quiet.contains(before_start + Duration::minutes(1), "UTC"));
+}
+
+#[test]
+fn test_quiet_hours_use_scheduler_timezone() {
+    let quiet = QuietHours::parse("22:00-07:00").unwrap();
+    // 04:00 UTC is 22:00 the previous evening in Chicago (CST, UTC-6)
+    let at = Utc.with_ymd_and_hms(2026, 1, 15, 4, 0, 0).unwrap();
+    assert_eq!(
+        quiet.next_allowed(at, "America/Chicago"),
+        Some(Utc.with_ymd_and_hms(2026, 1, 15, 13, 0, 0).unwrap())
+    );
+    assert!(!quiet.contains(at, "Not/AZone"));
+}
+
+#[tokio::test]
+async fn test_store_changes_wake_waiters() {
+    let store = create_test_store();
+    let waiter = store.clone();
+    let woken = tokio::spawn(async move { waiter.wait_for_change().await });
+    tokio::task::yield_now().await;
+
+    store
+        .create_schedule(&create_test_schedule("new", "general", "New"))
+        .unwrap();
+    tokio::time::timeout(std::time::Duration::from_secs(1), woken)
+        .await
+        .expect("creating a schedule should wake the waiter")
+        .unwrap();
+
+    // A change with nobody waiting is kept for the next wait
+    store.pause_schedule("new").unwrap();
+    tokio::time::timeout(std::time::Duration::from_secs(1), store.wait_for_change())
+        .await
+        .expect("the pending change should be delivered");
+}
@@ -23,6 +23,15 @@ pub enum FfiError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("Invalid time expression: {0}")]
+    InvalidTimeExpression(String),
+
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
 }
 
 impl From<anyhow::Error> for FfiError {
@@ -5,10 +5,14 @@ use crate::error::FfiError;
 use crate::session::FfiSessionStore;
 use chrono::Utc;
 use gorp_core::scheduler::{
-    parse_time_expression, ParsedSchedule, ScheduleStatus, ScheduledPrompt, SchedulerStore,
+    parse_schedule_time, ParsedSchedule, ScheduleStatus, ScheduledPrompt, SchedulerStore,
 };
+use gorp_core::session::SessionStore;
 use std::sync::Arc;
 
+/// Recorded as the creator of schedules made through `create`
+const APP_CREATOR: &str = "app";
+
 /// FFI-safe schedule status
 #[derive(Debug, Clone, Copy, uniffi::Enum)]
 pub enum FfiScheduleStatus {
@@ -75,6 +79,41 @@ impl From<ScheduledPrompt> for FfiScheduledPrompt {
 #[derive(uniffi::Object)]
 pub struct FfiSchedulerStore {
     inner: SchedulerStore,
+    /// Used to resolve a room to its channel when creating schedules
+    sessions: SessionStore,
+}
+
+/// Build a new active schedule from a parsed time
+fn new_schedule(
+    channel_name: String,
+    room_id: String,
+    prompt: String,
+    created_by: String,
+    parsed: ParsedSchedule,
+) -> ScheduledPrompt {
+    let (execute_at, cron_expression, next_execution_at) = match parsed {
+        ParsedSchedule::OneTime(dt) => {
+            let dt_str = dt.to_rfc3339();
+            (Some(dt_str.clone()), None, dt_str)
+        }
+        ParsedSchedule::Recurring { cron, next } => (None, Some(cron), next.to_rfc3339()),
+    };
+
+    ScheduledPrompt {
+        id: uuid::Uuid::new_v4().to_string(),
+        channel_name,
+        room_id,
+        prompt,
+        created_by,
+        created_at: Utc::now().to_rfc3339(),
+        execute_at,
+        cron_expression,
+        last_executed_at: None,
+        next_execution_at,
+        status: ScheduleStatus::Active,
+        error_message: None,
+        execution_count: 0,
+    }
 }
 
 #[uniffi::export]
@@ -87,7 +126,10 @@ impl FfiSchedulerStore {
         store
             .initialize_schema()
             .map_err(|e| FfiError::DatabaseError(e.to_string()))?;
-        Ok(Arc::new(Self { inner: store }))
+        Ok(Arc::new(Self {
+            inner: store,
+            sessions: session_store.inner().clone(),
+        }))
     }
 
     /// List all scheduled prompts
@@ -172,45 +214,109 @@ impl FfiSchedulerStore {
         time_expression: String,
         timezone: String,
     ) -> Result<FfiScheduledPrompt, FfiError> {
-        // Parse the time expression
-        let parsed = parse_time_expression(&time_expression, &timezone)
-            .map_err(|e| FfiError::InvalidInput(format!("Invalid time expression: {}", e)))?;
-
-        let now = Utc::now();
-        let created_at = now.to_rfc3339();
-
-        // Build the scheduled prompt based on parsed result
-        let (execute_at, cron_expression, next_execution_at) = match parsed {
-            ParsedSchedule::OneTime(dt) => {
-                let dt_str = dt.to_rfc3339();
-                (Some(dt_str.clone()), None, dt_str)
-            }
-            ParsedSchedule::Recurring { cron, next } => {
-                let next_str = next.to_rfc3339();
-                (None, Some(cron), next_str)
-            }
-        };
-
-        let schedule = ScheduledPrompt {
-            id: uuid::Uuid::new_v4().to_string(),
-            channel_name,
-            room_id,
-            prompt,
-            created_by,
-            created_at,
-            execute_at,
-            cron_expression,
-            last_executed_at: None,
-            next_execution_at,
-            status: ScheduleStatus::Active,
-            error_message: None,
-            execution_count: 0,
-        };
+        let parsed = parse_schedule_time(&time_expression, &timezone)
+            .map_err(|e| FfiError::InvalidTimeExpression(e.to_string()))?;
 
+        let schedule = new_schedule(channel_name, room_id, prompt, created_by, parsed);
+
+        self.inner
+            .create_schedule(&schedule)
+            .map_err(|e| FfiError::DatabaseError(e.to_string()))?;
+
+        Ok(schedule.into())
+    }
+
+    /// Create a schedule for the channel attached to a room
+    ///
+    /// The time expression is read the same way as schedule imports: a raw
+    /// 5-field cron expression, or natural language ("in 2 hours",
+    /// "tomorrow 9am", "every monday 8am"). The returned record carries the
+    /// computed `next_execution_at`.
+    pub fn create(
+        &self,
+        room_id: String,
+        prompt: String,
+        time_expr: String,
+        timezone: String,
+    ) -> Result<FfiScheduledPrompt, FfiError> {
+        if prompt.trim().is_empty() {
+            return Err(FfiError::InvalidInput("Prompt cannot be empty".to_string()));
+        }
+        // Checked up front so a bad timezone isn't reported as a bad time
+        if timezone.parse::<chrono_tz::Tz>().is_err() {
+            return Err(FfiError::InvalidTimezone(timezone));
+        }
+
+        let channel = self
+            .sessions
+            .get_by_room(&room_id)
+            .map_err(|e| FfiError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| FfiError::NotFound(format!("No channel for room {}", room_id)))?;
+
+        let parsed = parse_schedule_time(&time_expr, &timezone)
+            .map_err(|e| FfiError::InvalidTimeExpression(e.to_string()))?;
+
+        let schedule = new_schedule(
+            channel.channel_name,
+            channel.room_id,
+            prompt,
+            APP_CREATOR.to_string(),
+            parsed,
+        );
         self.inner
             .create_schedule(&schedule)
             .map_err(|e| FfiError::DatabaseError(e.to_string()))?;
 
         Ok(schedule.into())
     }
+
+    /// Pause an active schedule, returning the updated record
+    pub fn pause(&self, id: String) -> Result<FfiScheduledPrompt, FfiError> {
+        self.inner
+            .pause_schedule(&id)
+            .map_err(|e| FfiError::DatabaseError(e.to_string()))?;
+        self.expect_status(&id, ScheduleStatus::Paused)
+    }
+
+    /// Resume a paused schedule, returning the updated record
+    pub fn resume(&self, id: String) -> Result<FfiScheduledPrompt, FfiError> {
+        self.inner
+            .resume_schedule(&id)
+            .map_err(|e| FfiError::DatabaseError(e.to_string()))?;
+        self.expect_status(&id, ScheduleStatus::Active)
+    }
+
+    /// Delete a schedule, failing with `NotFound` if it doesn't exist
+    pub fn delete(&self, id: String) -> Result<(), FfiError> {
+        let deleted = self
+            .inner
+            .delete_schedule(&id)
+            .map_err(|e| FfiError::DatabaseError(e.to_string()))?;
+        if !deleted {
+            return Err(FfiError::NotFound(format!("Schedule {}", id)));
+        }
+        Ok(())
+    }
+}
+
+impl FfiSchedulerStore {
+    /// Reload a schedule after a status change and confirm it landed in `expected`
+    fn expect_status(
+        &self,
+        id: &str,
+        expected: ScheduleStatus,
+    ) -> Result<FfiScheduledPrompt, FfiError> {
+        let schedule = self
+            .inner
+            .get_by_id(id)
+            .map_err(|e| FfiError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| FfiError::NotFound(format!("Schedule {}", id)))?;
+        if schedule.status != expected {
+            return Err(FfiError::InvalidInput(format!(
+                "Schedule {} is {} and cannot become {}",
+                id, schedule.status, expected
+            )));
+        }
+        Ok(schedule.into())
+    }
 }
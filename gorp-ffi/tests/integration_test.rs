@@ -2,8 +2,8 @@
 // ABOUTME: Tests the FFI layer without generating actual bindings.
 
 use gorp_ffi::{
-    AgentEventCallback, FfiAgentRegistry, FfiErrorCode, FfiSchedulerStore, FfiSessionStore,
-    FfiUsage,
+    AgentEventCallback, FfiAgentRegistry, FfiError, FfiErrorCode, FfiScheduleStatus,
+    FfiSchedulerStore, FfiSessionStore, FfiUsage,
 };
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -219,6 +219,127 @@ fn test_scheduler_invalid_time_expression() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_scheduler_create_by_room() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace_path = temp_dir.path().to_string_lossy().to_string();
+
+    let session_store = FfiSessionStore::new(workspace_path).unwrap();
+    let scheduler_store = FfiSchedulerStore::new(&session_store).unwrap();
+    session_store
+        .create_channel("ops".to_string(), "!ops:example.com".to_string())
+        .unwrap();
+
+    let schedule = scheduler_store
+        .create(
+            "!ops:example.com".to_string(),
+            "summarize overnight alerts".to_string(),
+            "in 2 hours".to_string(),
+            "UTC".to_string(),
+        )
+        .unwrap();
+    assert_eq!(schedule.channel_name, "ops");
+    assert_eq!(schedule.created_by, "app");
+    assert!(schedule.execute_at.is_some());
+    assert!(!schedule.next_execution_at.is_empty());
+
+    // Raw cron expressions are accepted, as in schedule imports
+    let cron = scheduler_store
+        .create(
+            "!ops:example.com".to_string(),
+            "weekly report".to_string(),
+            "30 8 * * 1".to_string(),
+            "Europe/Berlin".to_string(),
+        )
+        .unwrap();
+    assert_eq!(cron.cron_expression.as_deref(), Some("30 8 * * 1"));
+}
+
+#[test]
+fn test_scheduler_create_errors_map_to_variants() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace_path = temp_dir.path().to_string_lossy().to_string();
+
+    let session_store = FfiSessionStore::new(workspace_path).unwrap();
+    let scheduler_store = FfiSchedulerStore::new(&session_store).unwrap();
+    session_store
+        .create_channel("ops".to_string(), "!ops:example.com".to_string())
+        .unwrap();
+
+    let bad_time = scheduler_store.create(
+        "!ops:example.com".to_string(),
+        "prompt".to_string(),
+        "whenever you feel like it".to_string(),
+        "UTC".to_string(),
+    );
+    assert!(matches!(bad_time, Err(FfiError::InvalidTimeExpression(_))));
+
+    let bad_tz = scheduler_store.create(
+        "!ops:example.com".to_string(),
+        "prompt".to_string(),
+        "in 1 hour".to_string(),
+        "Mars/Olympus_Mons".to_string(),
+    );
+    assert!(matches!(bad_tz, Err(FfiError::InvalidTimezone(_))));
+
+    let no_room = scheduler_store.create(
+        "!missing:example.com".to_string(),
+        "prompt".to_string(),
+        "in 1 hour".to_string(),
+        "UTC".to_string(),
+    );
+    assert!(matches!(no_room, Err(FfiError::NotFound(_))));
+
+    let empty = scheduler_store.create(
+        "!ops:example.com".to_string(),
+        "  ".to_string(),
+        "in 1 hour".to_string(),
+        "UTC".to_string(),
+    );
+    assert!(matches!(empty, Err(FfiError::InvalidInput(_))));
+}
+
+#[test]
+fn test_scheduler_pause_resume_delete_by_id() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace_path = temp_dir.path().to_string_lossy().to_string();
+
+    let session_store = FfiSessionStore::new(workspace_path).unwrap();
+    let scheduler_store = FfiSchedulerStore::new(&session_store).unwrap();
+    session_store
+        .create_channel("ops".to_string(), "!ops:example.com".to_string())
+        .unwrap();
+    let schedule = scheduler_store
+        .create(
+            "!ops:example.com".to_string(),
+            "daily digest".to_string(),
+            "every day 9am".to_string(),
+            "UTC".to_string(),
+        )
+        .unwrap();
+
+    let paused = scheduler_store.pause(schedule.id.clone()).unwrap();
+    assert!(matches!(paused.status, FfiScheduleStatus::Paused));
+
+    // Resuming twice is rejected rather than silently succeeding
+    let resumed = scheduler_store.resume(schedule.id.clone()).unwrap();
+    assert!(matches!(resumed.status, FfiScheduleStatus::Active));
+    assert!(matches!(
+        scheduler_store.resume(schedule.id.clone()),
+        Err(FfiError::InvalidInput(_))
+    ));
+
+    scheduler_store.delete(schedule.id.clone()).unwrap();
+    assert!(matches!(
+        scheduler_store.delete(schedule.id.clone()),
+        Err(FfiError::NotFound(_))
+    ));
+    assert!(matches!(
+        scheduler_store.pause(schedule.id),
+        Err(FfiError::NotFound(_))
+    ));
+}
+
 /// Test callback for tracking events received from the agent
 struct TestCallback {
     text_received: AtomicBool,
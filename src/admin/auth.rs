@@ -79,11 +79,9 @@ impl AuthConfig {
             return Ok(None);
         }
 
-        let content =
-            std::fs::read_to_string(&path).context("Failed to read auth config file")?;
+        let content = std::fs::read_to_string(&path).context("Failed to read auth config file")?;
 
-        let config: AuthConfig =
-            toml::from_str(&content).context("Failed to parse auth config")?;
+        let config: AuthConfig = toml::from_str(&content).context("Failed to parse auth config")?;
 
         Ok(Some(config))
     }
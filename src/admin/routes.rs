@@ -3,9 +3,10 @@
 
 use axum::{
     extract::{Path as AxumPath, State},
+    http::StatusCode,
     response::{IntoResponse, Response},
     routing::{get, post},
-    Form, Router,
+    Form, Json, Router,
 };
 use chrono_tz::Tz;
 use serde::Deserialize;
@@ -15,17 +16,23 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::admin::templates::{
-    BrowseEntry, ChannelDetailTemplate, ChannelListTemplate, ChannelRow, ChatHistoryPartialTemplate,
-    ChatHistoryRow, ChatTemplate, ConfigField, ConfigTemplate, DashboardTemplate, WorkspaceRow,
-    DirectoryTemplate, ErrorEntry, FeedRow, FeedTemplate, FileTemplate, GatewayConfigTemplate,
-    GatewayRow, GatewaysTemplate, HealthTemplate, LogViewerTemplate, MarkdownTemplate, WorkspacesTemplate,
-    MatrixDirTemplate, MatrixFileEntry, MessageEntry, MessageHistoryTemplate, ScheduleFormTemplate,
-    ScheduleRow, SchedulesTemplate, SearchResult, SearchTemplate, ToastTemplate,
+    BrowseEntry, ChannelDetailTemplate, ChannelListTemplate, ChannelRow,
+    ChatHistoryPartialTemplate, ChatHistoryRow, ChatTemplate, ConfigField, ConfigTemplate,
+    DashboardTemplate, DirectoryTemplate, ErrorEntry, FeedRow, FeedTemplate, FileTemplate,
+    GatewayConfigTemplate, GatewayRow, GatewaysTemplate, HealthTemplate, LogViewerTemplate,
+    MarkdownTemplate, MatrixDirTemplate, MatrixFileEntry, MessageEntry, MessageHistoryTemplate,
+    SafeModeBannerTemplate, ScheduleFormTemplate, ScheduleRow, SchedulesTemplate, SearchResult,
+    SearchTemplate, ToastTemplate, WarmSessionRow, WarmSessionsTemplate, WorkspaceRow,
+    WorkspacesTemplate,
 };
+use crate::budget::runway_label;
 use crate::config::Config;
 use crate::paths;
+use crate::relocate::{relocate_live, RelocateRequest};
+use crate::runtime_mode;
 use crate::scheduler::{ScheduleStatus, SchedulerStore};
 use crate::session::SessionStore;
+use crate::warm_session::{prepare_session_async, SharedWarmSessionManager};
 
 #[derive(Clone)]
 pub struct AdminState {
@@ -36,6 +43,7 @@ pub struct AdminState {
     pub ws_hub: super::websocket::WsHub,
     pub registry: Option<crate::platform::SharedPlatformRegistry>,
     pub bus: Option<Arc<crate::bus::MessageBus>>,
+    pub warm_manager: Option<SharedWarmSessionManager>,
 }
 
 #[derive(Deserialize)]
@@ -59,14 +67,19 @@ pub fn admin_router() -> Router<AdminState> {
         .route("/channels/{name}/matrix", get(channel_matrix_dir))
         .route("/channels/{name}/delete", post(channel_delete))
         .route("/channels/{name}/debug", post(channel_toggle_debug))
+        .route("/api/channels/{name}/relocate", post(channel_relocate))
         .route("/messages", get(messages_view))
         .route("/health", get(health_view))
+        .route("/safe-mode-banner", get(safe_mode_banner))
         .route("/schedules", get(schedules_list))
         .route("/schedules/new", get(schedule_form))
         .route("/schedules/create", post(schedule_create))
         .route("/schedules/{id}/cancel", post(schedule_cancel))
         .route("/schedules/{id}/pause", post(schedule_pause))
         .route("/schedules/{id}/resume", post(schedule_resume))
+        .route("/sessions", get(warm_sessions_list))
+        .route("/sessions/{name}/evict", post(warm_session_evict))
+        .route("/sessions/{name}/prewarm", post(warm_session_prewarm))
         .route("/browse", get(browse_root))
         .route("/browse/{*path}", get(browse_path))
         .route("/render/{*path}", get(render_markdown))
@@ -145,10 +158,7 @@ async fn dashboard(State(state): State<AdminState>) -> DashboardTemplate {
             let (configured, config_summary) = platform_config_summary(&state.config, id);
             let connected = live_health.iter().any(|h| {
                 h.platform_id == *id
-                    && matches!(
-                        h.state,
-                        gorp_core::PlatformConnectionState::Connected
-                    )
+                    && matches!(h.state, gorp_core::PlatformConnectionState::Connected)
             });
             GatewayRow {
                 platform_id: id.to_string(),
@@ -292,10 +302,28 @@ async fn channels_list(State(state): State<AdminState>) -> ChannelListTemplate {
         }
     };
 
+    let today = chrono::Utc::now().date_naive();
     let channel_rows: Vec<ChannelRow> = channels
         .iter()
         .map(|ch| {
             let debug_enabled = is_debug_enabled(ch);
+            let budget = state
+                .session_store
+                .get_budget_cents(&ch.channel_name)
+                .unwrap_or_else(|e| {
+                    tracing::warn!(channel = %ch.channel_name, error = %e, "Failed to load budget");
+                    None
+                });
+            let forecast = match budget {
+                Some(_) => state
+                    .session_store
+                    .get_spend_forecast(&ch.channel_name, today)
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(channel = %ch.channel_name, error = %e, "Failed to forecast spend");
+                        None
+                    }),
+                None => None,
+            };
             ChannelRow {
                 name: ch.channel_name.clone(),
                 platform_id: ch.room_id.clone(),
@@ -303,6 +331,7 @@ async fn channels_list(State(state): State<AdminState>) -> ChannelListTemplate {
                 debug_enabled,
                 directory: ch.directory.clone(),
                 created_at: ch.created_at.clone(),
+                runway: runway_label(forecast.as_ref(), budget, today),
             }
         })
         .collect();
@@ -598,6 +627,28 @@ async fn channel_delete(
     }
 }
 
+/// Move a channel's workspace on the running instance (called by `gorp workspace relocate`)
+async fn channel_relocate(
+    State(state): State<AdminState>,
+    AxumPath(name): AxumPath<String>,
+    Json(request): Json<RelocateRequest>,
+) -> Response {
+    let Some(warm_manager) = &state.warm_manager else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Session manager not available",
+        )
+            .into_response();
+    };
+    match relocate_live(&state.session_store, warm_manager, &name, &request).await {
+        Ok(moved) => Json(moved).into_response(),
+        Err(e) => {
+            tracing::error!(channel = %name, error = %e, "Workspace relocation failed");
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e)).into_response()
+        }
+    }
+}
+
 async fn channel_toggle_debug(
     State(state): State<AdminState>,
     AxumPath(name): AxumPath<String>,
@@ -805,6 +856,17 @@ fn is_debug_enabled(channel: &crate::session::Channel) -> bool {
 // Health & Monitoring Handlers
 // ============================================================================
 
+/// Banner partial every admin page polls; empty unless safe mode is active
+async fn safe_mode_banner(State(state): State<AdminState>) -> SafeModeBannerTemplate {
+    let safe = match &state.warm_manager {
+        Some(manager) => manager.read().await.runtime_mode().is_safe(),
+        None => false,
+    };
+    SafeModeBannerTemplate {
+        banner: safe.then(|| runtime_mode::BANNER.to_string()),
+    }
+}
+
 async fn health_view(State(state): State<AdminState>) -> HealthTemplate {
     let channels = match state.session_store.list_all() {
         Ok(c) => c,
@@ -999,6 +1061,150 @@ async fn schedule_resume(
     }
 }
 
+/// Every channel with its warm session, if it has one
+async fn warm_sessions_list(State(state): State<AdminState>) -> WarmSessionsTemplate {
+    let warm = match &state.warm_manager {
+        Some(manager) => manager.read().await.snapshot(),
+        None => Vec::new(),
+    };
+    let mut names: Vec<String> = match state.session_store.list_all() {
+        Ok(channels) => channels.into_iter().map(|c| c.channel_name).collect(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to list channels for warm sessions view");
+            Vec::new()
+        }
+    };
+    // A session can outlive its channel until it goes stale
+    for info in &warm {
+        if !names.contains(&info.channel_name) {
+            names.push(info.channel_name.clone());
+        }
+    }
+    names.sort();
+
+    let sessions: Vec<WarmSessionRow> = names
+        .into_iter()
+        .map(
+            |name| match warm.iter().find(|info| info.channel_name == name) {
+                Some(info) => WarmSessionRow {
+                    channel_name: name,
+                    warm: true,
+                    busy: info.is_busy(),
+                    invalidated: info.invalidated,
+                    session_id: info.session_id.clone().unwrap_or_default(),
+                    age: info.age.map(format_duration).unwrap_or_default(),
+                    idle: info.idle.map(format_duration).unwrap_or_default(),
+                },
+                None => WarmSessionRow {
+                    channel_name: name,
+                    warm: false,
+                    busy: false,
+                    invalidated: false,
+                    session_id: String::new(),
+                    age: String::new(),
+                    idle: String::new(),
+                },
+            },
+        )
+        .collect();
+
+    WarmSessionsTemplate {
+        title: "Warm Sessions - gorp Admin".to_string(),
+        warm_count: warm.len(),
+        sessions,
+    }
+}
+
+/// Drop a channel's warm session so its next message starts the agent afresh
+async fn warm_session_evict(
+    State(state): State<AdminState>,
+    AxumPath(name): AxumPath<String>,
+) -> ToastTemplate {
+    let Some(warm_manager) = &state.warm_manager else {
+        return ToastTemplate {
+            message: "Session manager not available".to_string(),
+            is_error: true,
+        };
+    };
+
+    // Invalidate rather than just evict, so a prompt still holding it sees it's gone
+    let evicted = warm_manager.write().await.invalidate_session(&name);
+    match evicted {
+        Some(_) => {
+            tracing::info!(channel = %name, "Warm session evicted from admin panel");
+            ToastTemplate {
+                message: format!("Evicted warm session for '{}'", name),
+                is_error: false,
+            }
+        }
+        None => ToastTemplate {
+            message: format!("No warm session for '{}'", name),
+            is_error: true,
+        },
+    }
+}
+
+/// Start a channel's session now rather than on its next message
+async fn warm_session_prewarm(
+    State(state): State<AdminState>,
+    AxumPath(name): AxumPath<String>,
+) -> ToastTemplate {
+    let Some(warm_manager) = &state.warm_manager else {
+        return ToastTemplate {
+            message: "Session manager not available".to_string(),
+            is_error: true,
+        };
+    };
+    let channel = match state.session_store.get_by_name(&name) {
+        Ok(Some(ch)) => ch,
+        Ok(None) => {
+            return ToastTemplate {
+                message: format!("Channel not found: {}", name),
+                is_error: true,
+            }
+        }
+        Err(e) => {
+            return ToastTemplate {
+                message: format!("Database error: {}", e),
+                is_error: true,
+            }
+        }
+    };
+
+    match prepare_session_async(warm_manager, &channel).await {
+        Ok((_, session_id, is_new)) => {
+            if is_new {
+                if let Err(e) = state
+                    .session_store
+                    .update_session_id(&channel.room_id, &session_id)
+                {
+                    tracing::error!(error = %e, "Failed to update session ID in store");
+                }
+            }
+            tracing::info!(channel = %name, session_id = %session_id, "Session pre-warmed from admin panel");
+            ToastTemplate {
+                message: format!("Pre-warmed '{}'", name),
+                is_error: false,
+            }
+        }
+        Err(e) => ToastTemplate {
+            message: format!("Failed to pre-warm '{}': {}", name, e),
+            is_error: true,
+        },
+    }
+}
+
+/// Short human-readable duration, e.g. "45s", "12m", "3h 5m" or "2d 4h"
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
 // ============================================================================
 // Message History Handler
 // ============================================================================
@@ -2030,6 +2236,12 @@ async fn feed_view(State(state): State<AdminState>) -> FeedTemplate {
     if state.config.slack.is_some() {
         platforms.push("slack".to_string());
     }
+    if state.config.irc.is_some() {
+        platforms.push("irc".to_string());
+    }
+    if state.config.zulip.is_some() {
+        platforms.push("zulip".to_string());
+    }
 
     // Recent messages from channel message logs
     let messages = match state.session_store.list_all() {
@@ -2216,9 +2428,10 @@ async fn gateways_overview(State(state): State<AdminState>) -> GatewaysTemplate
         .iter()
         .map(|id| {
             let (configured, config_summary) = platform_config_summary(&state.config, id);
-            let connected = live_health
-                .iter()
-                .any(|h| h.platform_id == *id && matches!(h.state, gorp_core::PlatformConnectionState::Connected));
+            let connected = live_health.iter().any(|h| {
+                h.platform_id == *id
+                    && matches!(h.state, gorp_core::PlatformConnectionState::Connected)
+            });
             GatewayRow {
                 platform_id: id.to_string(),
                 configured,
@@ -2248,9 +2461,10 @@ async fn gateway_config(
 
     let connected = if let Some(ref reg) = state.registry {
         let reg = reg.read().await;
-        reg.health()
-            .iter()
-            .any(|h| h.platform_id == platform && matches!(h.state, gorp_core::PlatformConnectionState::Connected))
+        reg.health().iter().any(|h| {
+            h.platform_id == platform
+                && matches!(h.state, gorp_core::PlatformConnectionState::Connected)
+        })
     } else {
         false
     };
@@ -2330,21 +2544,28 @@ async fn gateway_connect(
     }
 
     // Create platform from config
-    match crate::platform::factory::create_platform(&state.config, &platform).await {
+    match crate::platform::factory::create_platform(
+        &state.config,
+        &state.scheduler_store,
+        &platform,
+    )
+    .await
+    {
         Ok(new_platform) => {
             let mut reg = registry.write().await;
             reg.register(new_platform);
             tracing::info!(platform = %platform, "Hot-connected platform");
 
             // Broadcast status update
-            state.ws_hub.broadcast(
-                super::websocket::ServerMessage::StatusPlatform {
+            state
+                .ws_hub
+                .broadcast(super::websocket::ServerMessage::StatusPlatform {
                     data: super::websocket::PlatformStatusData {
                         platform: platform.clone(),
                         state: "connected".to_string(),
+                        reason: None,
                     },
-                },
-            );
+                });
 
             ToastTemplate {
                 message: format!("{} connected successfully.", platform),
@@ -2380,14 +2601,15 @@ async fn gateway_disconnect(
             tracing::info!(platform = %platform, "Disconnected platform");
 
             // Broadcast status update
-            state.ws_hub.broadcast(
-                super::websocket::ServerMessage::StatusPlatform {
+            state
+                .ws_hub
+                .broadcast(super::websocket::ServerMessage::StatusPlatform {
                     data: super::websocket::PlatformStatusData {
                         platform: platform.clone(),
                         state: "disconnected".to_string(),
+                        reason: None,
                     },
-                },
-            );
+                });
 
             ToastTemplate {
                 message: format!("{} disconnected.", platform),
@@ -2567,8 +2789,7 @@ fn platform_config_fields(config: &Config, platform_id: &str) -> Vec<ConfigField
                 ConfigField {
                     name: "thread_in_channels".to_string(),
                     label: "Thread in Channels".to_string(),
-                    value: sc
-                        .map_or("true".to_string(), |c| c.thread_in_channels.to_string()),
+                    value: sc.map_or("true".to_string(), |c| c.thread_in_channels.to_string()),
                     placeholder: "Reply in threads instead of channel".to_string(),
                     field_type: "checkbox".to_string(),
                     is_set: sc.is_some(),
@@ -2623,7 +2844,11 @@ fn save_platform_config(
 
         // Handle comma-separated list fields
         if key == "allowed_users" || key == "allowed_channels" || key == "allowed_chats" {
-            let items: Vec<&str> = value.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).collect();
+            let items: Vec<&str> = value
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
             let mut arr = toml_edit::Array::new();
             for item in items {
                 arr.push(item);
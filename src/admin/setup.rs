@@ -25,8 +25,7 @@ pub fn setup_router() -> Router<AdminState> {
 
 /// Build the login router for /login
 pub fn login_router() -> Router<AdminState> {
-    Router::new()
-        .route("/", get(login_view).post(login_submit))
+    Router::new().route("/", get(login_view).post(login_submit))
 }
 
 // =============================================================================
@@ -211,7 +210,10 @@ async fn setup_finish(State(state): State<AdminState>) -> Response {
 
             // Update in-memory state so middleware sees setup_complete immediately
             let mut guard = state.auth_config.write().await;
-            tracing::info!(setup_complete = config.setup_complete, "Setup wizard completed — updating in-memory auth state");
+            tracing::info!(
+                setup_complete = config.setup_complete,
+                "Setup wizard completed — updating in-memory auth state"
+            );
             *guard = Some(config);
             drop(guard);
             Redirect::to("/login").into_response()
@@ -273,13 +275,19 @@ async fn login_submit(
         .into_response();
     }
 
-    tracing::info!(username = username, "User logged in — session created, redirecting to /admin");
+    tracing::info!(
+        username = username,
+        "User logged in — session created, redirecting to /admin"
+    );
 
     // Verify in-memory auth state is current (load from disk if stale)
     {
         let guard = state.auth_config.read().await;
         let mem_complete = guard.as_ref().map_or(false, |c| c.setup_complete);
-        tracing::debug!(in_memory_setup_complete = mem_complete, "Login: auth state check before redirect");
+        tracing::debug!(
+            in_memory_setup_complete = mem_complete,
+            "Login: auth state check before redirect"
+        );
     }
 
     Redirect::to("/admin").into_response()
@@ -311,7 +319,9 @@ mod tests {
             error_message: Some("Passwords do not match".to_string()),
             prefill_username: "admin".to_string(),
         };
-        let rendered = template.render().expect("Step 1 error template should render");
+        let rendered = template
+            .render()
+            .expect("Step 1 error template should render");
         assert!(rendered.contains("Passwords do not match"));
         assert!(rendered.contains("admin")); // Prefilled username
     }
@@ -364,7 +374,9 @@ mod tests {
         let template = LoginTemplate {
             error_message: Some("Invalid username or password".to_string()),
         };
-        let rendered = template.render().expect("Login error template should render");
+        let rendered = template
+            .render()
+            .expect("Login error template should render");
         assert!(rendered.contains("Invalid username or password"));
     }
 }
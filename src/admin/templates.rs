@@ -60,6 +60,13 @@ pub struct ToastTemplate {
     pub is_error: bool,
 }
 
+/// Shown at the top of every admin page while safe mode is active
+#[derive(Template)]
+#[template(path = "partials/safe_mode_banner.html")]
+pub struct SafeModeBannerTemplate {
+    pub banner: Option<String>,
+}
+
 /// Session row data for list view
 #[derive(Clone)]
 pub struct ChannelRow {
@@ -69,6 +76,8 @@ pub struct ChannelRow {
     pub debug_enabled: bool,
     pub directory: String,
     pub created_at: String,
+    /// When the monthly budget runs out at the current rate, or "-" without one
+    pub runway: String,
 }
 
 #[derive(Template)]
@@ -106,6 +115,27 @@ pub struct HealthTemplate {
     pub recent_errors: Vec<ErrorEntry>,
 }
 
+/// One channel on the warm sessions page, whether or not it has a warm session
+#[derive(Clone)]
+pub struct WarmSessionRow {
+    pub channel_name: String,
+    pub warm: bool,
+    /// A prompt holds the session, so its details can't be read
+    pub busy: bool,
+    pub invalidated: bool,
+    pub session_id: String,
+    pub age: String,
+    pub idle: String,
+}
+
+#[derive(Template)]
+#[template(path = "admin/sessions.html")]
+pub struct WarmSessionsTemplate {
+    pub title: String,
+    pub warm_count: usize,
+    pub sessions: Vec<WarmSessionRow>,
+}
+
 /// Error entry data for health view
 #[derive(Clone)]
 pub struct ErrorEntry {
@@ -391,10 +421,12 @@ impl_into_response!(
     DashboardTemplate,
     ConfigTemplate,
     ToastTemplate,
+    SafeModeBannerTemplate,
     ChannelListTemplate,
     ChannelDetailTemplate,
     HealthTemplate,
     SchedulesTemplate,
+    WarmSessionsTemplate,
     LogViewerTemplate,
     MessageHistoryTemplate,
     ScheduleFormTemplate,
@@ -506,6 +538,66 @@ mod tests {
         assert!(rendered.contains("bg-red-500"));
     }
 
+    #[test]
+    fn test_safe_mode_banner_renders_only_when_active() {
+        let active = SafeModeBannerTemplate {
+            banner: Some(crate::runtime_mode::BANNER.to_string()),
+        }
+        .render()
+        .expect("Banner template should render successfully");
+        assert!(active.contains("Safe mode is active"));
+
+        let inactive = SafeModeBannerTemplate { banner: None }
+            .render()
+            .expect("Empty banner template should render successfully");
+        assert!(inactive.trim().is_empty());
+    }
+
+    #[test]
+    fn test_warm_sessions_template_renders() {
+        let template = WarmSessionsTemplate {
+            title: "Warm Sessions".to_string(),
+            warm_count: 2,
+            sessions: vec![
+                WarmSessionRow {
+                    channel_name: "research".to_string(),
+                    warm: true,
+                    busy: false,
+                    invalidated: false,
+                    session_id: "sess-123".to_string(),
+                    age: "2h 5m".to_string(),
+                    idle: "45s".to_string(),
+                },
+                WarmSessionRow {
+                    channel_name: "wedged".to_string(),
+                    warm: true,
+                    busy: true,
+                    invalidated: false,
+                    session_id: String::new(),
+                    age: String::new(),
+                    idle: String::new(),
+                },
+                WarmSessionRow {
+                    channel_name: "cold".to_string(),
+                    warm: false,
+                    busy: false,
+                    invalidated: false,
+                    session_id: String::new(),
+                    age: String::new(),
+                    idle: String::new(),
+                },
+            ],
+        };
+        let rendered = template
+            .render()
+            .expect("Warm sessions template should render successfully");
+        assert!(rendered.contains("sess-123"));
+        assert!(rendered.contains("/admin/sessions/research/evict"));
+        assert!(rendered.contains("/admin/sessions/wedged/evict"));
+        assert!(rendered.contains("/admin/sessions/cold/prewarm"));
+        assert!(!rendered.contains("/admin/sessions/research/prewarm"));
+    }
+
     #[test]
     fn test_health_template_renders_no_errors() {
         let template = HealthTemplate {
@@ -658,9 +750,7 @@ mod tests {
 
     #[test]
     fn test_chat_history_partial_empty() {
-        let template = ChatHistoryPartialTemplate {
-            messages: vec![],
-        };
+        let template = ChatHistoryPartialTemplate { messages: vec![] };
         let rendered = template
             .render()
             .expect("Empty chat history partial should render");
@@ -731,9 +821,7 @@ mod tests {
                 },
             ],
         };
-        let rendered = template
-            .render()
-            .expect("Gateways template should render");
+        let rendered = template.render().expect("Gateways template should render");
         assert!(rendered.contains("Gateways Test"));
         assert!(rendered.contains("matrix"));
         assert!(rendered.contains("Connected"));
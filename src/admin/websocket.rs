@@ -9,6 +9,7 @@ use axum::{
     response::IntoResponse,
 };
 use futures_util::{SinkExt, StreamExt};
+use gorp_core::PlatformConnectionState;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -41,10 +42,7 @@ enum ClientMessage {
 #[serde(tag = "type")]
 pub enum ServerMessage {
     #[serde(rename = "feed.message")]
-    FeedMessage {
-        html: String,
-        data: FeedMessageData,
-    },
+    FeedMessage { html: String, data: FeedMessageData },
     #[serde(rename = "status.platform")]
     StatusPlatform { data: PlatformStatusData },
     #[serde(rename = "chat.chunk")]
@@ -67,6 +65,22 @@ pub struct FeedMessageData {
 pub struct PlatformStatusData {
     pub platform: String,
     pub state: String,
+    /// Why the platform disconnected, on disconnect updates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PlatformStatusData {
+    pub fn from_state(platform: &str, state: &PlatformConnectionState) -> Self {
+        Self {
+            platform: platform.to_string(),
+            state: state.label().to_string(),
+            reason: match state {
+                PlatformConnectionState::Disconnected { reason } => Some(reason.clone()),
+                _ => None,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -154,8 +168,7 @@ pub async fn ws_handler(
 
 /// Handle a WebSocket connection
 async fn handle_ws(socket: WebSocket, state: AdminState) {
-    let (mut ws_sink, mut ws_stream) =
-        socket.split::<Message>();
+    let (mut ws_sink, mut ws_stream) = socket.split::<Message>();
     let (_tx, mut rx) = mpsc::channel::<ServerMessage>(64);
 
     // Track which channels this client is subscribed to
@@ -251,7 +264,8 @@ async fn handle_ws(socket: WebSocket, state: AdminState) {
                                 "Chat message received via WebSocket"
                             );
                             if let Some(ref bus) = reader_bus {
-                                let session_target = bus.resolve_target_async("web", &workspace).await;
+                                let session_target =
+                                    bus.resolve_target_async("web", &workspace).await;
                                 let msg = crate::bus::BusMessage {
                                     id: uuid::Uuid::new_v4().to_string(),
                                     source: crate::bus::MessageSource::Web {
@@ -261,10 +275,14 @@ async fn handle_ws(socket: WebSocket, state: AdminState) {
                                     sender: "admin".to_string(),
                                     body,
                                     timestamp: chrono::Utc::now(),
+                                    silent: false,
+                                    urgent: false,
                                 };
                                 bus.publish_inbound(msg);
                             } else {
-                                tracing::warn!("Chat message received but no message bus configured");
+                                tracing::warn!(
+                                    "Chat message received but no message bus configured"
+                                );
                             }
                         }
                         ClientMessage::ChatCancel { workspace } => {
@@ -385,6 +403,7 @@ mod tests {
             data: PlatformStatusData {
                 platform: "telegram".to_string(),
                 state: "connected".to_string(),
+                reason: None,
             },
         };
         let json = serde_json::to_string(&msg).unwrap();
@@ -427,6 +446,7 @@ mod tests {
             data: PlatformStatusData {
                 platform: "test".to_string(),
                 state: "connected".to_string(),
+                reason: None,
             },
         });
 
@@ -448,6 +468,7 @@ mod tests {
             data: PlatformStatusData {
                 platform: "test".to_string(),
                 state: "connected".to_string(),
+                reason: None,
             },
         });
     }
@@ -483,6 +504,7 @@ mod tests {
             data: PlatformStatusData {
                 platform: "telegram".to_string(),
                 state: "connected".to_string(),
+                reason: None,
             },
         };
         assert_eq!(message_channel(&msg), "status");
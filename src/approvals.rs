@@ -0,0 +1,333 @@
+// ABOUTME: In-chat approval of risky agent tool calls, for channels that turn it on with !approvals.
+// ABOUTME: Posts "Agent wants to run ... - approve?", waits for an approver's yes or no, denies on timeout.
+
+use futures_util::future::BoxFuture;
+use gorp_agent::{ToolApprovalRequest, ToolApprover};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use crate::config::{RiskLevel, ToolApprovalConfig};
+use crate::message_handler::truncate_str;
+use crate::warm_session::is_network_tool;
+
+/// Longest command, URL or path quoted in an approval request
+const MAX_ACTION_CHARS: usize = 200;
+
+/// Approval requests waiting for an answer, at most one per room
+#[derive(Clone, Default)]
+pub struct PendingApprovals {
+    waiting: Arc<Mutex<HashMap<String, oneshot::Sender<bool>>>>,
+}
+
+impl PendingApprovals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start waiting for an answer in `room_id`, replacing any unanswered request there
+    fn register(&self, room_id: &str) -> oneshot::Receiver<bool> {
+        let (tx, rx) = oneshot::channel();
+        self.lock().insert(room_id.to_string(), tx);
+        rx
+    }
+
+    /// Whether a request in `room_id` is waiting for an answer
+    pub fn is_waiting(&self, room_id: &str) -> bool {
+        self.lock().contains_key(room_id)
+    }
+
+    /// Answer the request waiting in `room_id`; false if there was none
+    pub fn answer(&self, room_id: &str, approved: bool) -> bool {
+        match self.lock().remove(room_id) {
+            Some(tx) => tx.send(approved).is_ok(),
+            None => false,
+        }
+    }
+
+    fn forget(&self, room_id: &str) {
+        self.lock().remove(room_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, oneshot::Sender<bool>>> {
+        self.waiting.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Read a reply to an approval request: yes/approve/👍 or no/deny/👎
+pub fn parse_answer(body: &str) -> Option<bool> {
+    let answer = body.trim().trim_end_matches(['.', '!']).to_lowercase();
+    match answer.as_str() {
+        "yes" | "y" | "approve" | "approved" | "allow" | "ok" | "👍" | "✅" => Some(true),
+        "no" | "n" | "deny" | "denied" | "reject" | "👎" | "❌" => Some(false),
+        _ => None,
+    }
+}
+
+/// What the tool call would do, e.g. "run `rm -rf build/`"
+pub fn describe(request: &ToolApprovalRequest) -> String {
+    let field = |key: &str| request.input.get(key).and_then(|v| v.as_str());
+    let quoted = |text: &str| format!("`{}`", truncate_str(text, MAX_ACTION_CHARS));
+
+    if let Some(command) = field("command").or_else(|| field("cmd")) {
+        return format!("run {}", quoted(command));
+    }
+    if let Some(url) = field("url") {
+        return format!("fetch {}", quoted(url));
+    }
+    if let Some(path) = field("path").or_else(|| field("file_path")) {
+        let verb = match request.kind.as_str() {
+            "delete" => "delete",
+            "edit" => "edit",
+            "move" => "move",
+            _ => "use",
+        };
+        return format!("{} {}", verb, quoted(path));
+    }
+    format!("use {}", quoted(&request.name))
+}
+
+/// Posts a message to the room the approval is asked in
+pub type Notify = Arc<dyn Fn(String) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Asks in chat before tool calls at or above the channel's approval level
+pub struct ChatApprover {
+    pending: PendingApprovals,
+    room_id: String,
+    threshold: RiskLevel,
+    config: ToolApprovalConfig,
+    notify: Notify,
+}
+
+impl ChatApprover {
+    pub fn new(
+        pending: PendingApprovals,
+        room_id: &str,
+        threshold: RiskLevel,
+        config: ToolApprovalConfig,
+        notify: Notify,
+    ) -> Self {
+        Self {
+            pending,
+            room_id: room_id.to_string(),
+            threshold,
+            config,
+            notify,
+        }
+    }
+}
+
+impl ToolApprover for ChatApprover {
+    fn review(&self, request: ToolApprovalRequest) -> BoxFuture<'static, bool> {
+        let risk = self.config.risk_of(&request.name, &request.kind);
+        if risk < self.threshold {
+            return Box::pin(async { true });
+        }
+
+        let pending = self.pending.clone();
+        let room_id = self.room_id.clone();
+        let notify = Arc::clone(&self.notify);
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let action = describe(&request);
+
+        Box::pin(async move {
+            let answer = pending.register(&room_id);
+            tracing::info!(room_id = %room_id, tool = %request.name, risk = risk.as_str(), "Asking for tool approval");
+            notify(format!(
+                "🔐 Agent wants to {} ({} risk) - approve?\nReply yes or no. No answer in {}s denies it.",
+                action,
+                risk.as_str(),
+                timeout.as_secs()
+            ))
+            .await;
+
+            match tokio::time::timeout(timeout, answer).await {
+                Ok(Ok(approved)) => approved,
+                // A newer request in the room took over this one
+                Ok(Err(_)) => false,
+                Err(_) => {
+                    pending.forget(&room_id);
+                    notify(format!(
+                        "⌛ No answer in {}s, so the agent was not allowed to {}.",
+                        timeout.as_secs(),
+                        action
+                    ))
+                    .await;
+                    false
+                }
+            }
+        })
+    }
+}
+
+/// Denies network tool calls in channels with `!network off` and hands every other
+/// call to the channel's own approver, if it has one
+pub struct NetworkBlocker {
+    inner: Option<Arc<dyn ToolApprover>>,
+    notify: Option<Notify>,
+}
+
+impl NetworkBlocker {
+    pub fn new(inner: Option<Arc<dyn ToolApprover>>, notify: Option<Notify>) -> Self {
+        Self { inner, notify }
+    }
+}
+
+impl ToolApprover for NetworkBlocker {
+    fn review(&self, request: ToolApprovalRequest) -> BoxFuture<'static, bool> {
+        if !is_network_tool(&request.name, &request.kind) {
+            return match &self.inner {
+                Some(inner) => inner.review(request),
+                None => Box::pin(async { true }),
+            };
+        }
+
+        tracing::info!(tool = %request.name, "Blocked network tool with network access off");
+        let notify = self.notify.clone();
+        let action = describe(&request);
+        Box::pin(async move {
+            if let Some(notify) = notify {
+                notify(format!(
+                    "🌐 The agent tried to {}, but network access is off in this channel. !network on allows it.",
+                    action
+                ))
+                .await;
+            }
+            false
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOM: &str = "!ops:matrix.org";
+
+    fn recording_notify() -> (Notify, Arc<Mutex<Vec<String>>>) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&sent);
+        let notify: Notify = Arc::new(move |text| {
+            log.lock().unwrap().push(text);
+            Box::pin(async {})
+        });
+        (notify, sent)
+    }
+
+    fn request(kind: &str, input: serde_json::Value) -> ToolApprovalRequest {
+        ToolApprovalRequest {
+            session_id: "s1".to_string(),
+            tool_call_id: "t1".to_string(),
+            name: "bash".to_string(),
+            kind: kind.to_string(),
+            input,
+        }
+    }
+
+    fn approver(pending: &PendingApprovals, timeout_secs: u64, notify: Notify) -> ChatApprover {
+        let config = ToolApprovalConfig {
+            timeout_secs,
+            ..Default::default()
+        };
+        ChatApprover::new(pending.clone(), ROOM, RiskLevel::High, config, notify)
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("Yes!"), Some(true));
+        assert_eq!(parse_answer(" approve "), Some(true));
+        assert_eq!(parse_answer("👍"), Some(true));
+        assert_eq!(parse_answer("no."), Some(false));
+        assert_eq!(parse_answer("Deny"), Some(false));
+        assert_eq!(parse_answer("yes, but only in build/"), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        let rm = request("execute", serde_json::json!({ "command": "rm -rf build/" }));
+        assert_eq!(describe(&rm), "run `rm -rf build/`");
+        let fetch = request("fetch", serde_json::json!({ "url": "https://example.com" }));
+        assert_eq!(describe(&fetch), "fetch `https://example.com`");
+        let delete = request("delete", serde_json::json!({ "path": "notes.md" }));
+        assert_eq!(describe(&delete), "delete `notes.md`");
+        assert_eq!(
+            describe(&request("other", serde_json::json!({}))),
+            "use `bash`"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_low_risk_calls_run_without_asking() {
+        let pending = PendingApprovals::new();
+        let (notify, sent) = recording_notify();
+        let approver = approver(&pending, 60, notify);
+
+        let read = request("read", serde_json::json!({ "path": "README.md" }));
+        assert!(approver.review(read).await);
+        assert!(sent.lock().unwrap().is_empty());
+        assert!(!pending.is_waiting(ROOM));
+    }
+
+    #[tokio::test]
+    async fn test_approved_call_runs() {
+        let pending = PendingApprovals::new();
+        let (notify, sent) = recording_notify();
+        let approver = approver(&pending, 60, notify);
+
+        let rm = request("execute", serde_json::json!({ "command": "rm -rf build/" }));
+        let review = tokio::spawn(approver.review(rm));
+        while !pending.is_waiting(ROOM) {
+            tokio::task::yield_now().await;
+        }
+        assert!(sent.lock().unwrap()[0]
+            .starts_with("🔐 Agent wants to run `rm -rf build/` (high risk) - approve?"));
+
+        assert!(pending.answer(ROOM, true));
+        assert!(review.await.unwrap());
+        assert!(!pending.is_waiting(ROOM));
+        assert!(!pending.answer(ROOM, true));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unanswered_call_is_denied_on_timeout() {
+        let pending = PendingApprovals::new();
+        let (notify, sent) = recording_notify();
+        let approver = approver(&pending, 30, notify);
+
+        let rm = request("execute", serde_json::json!({ "command": "rm -rf build/" }));
+        assert!(!approver.review(rm).await);
+        assert!(!pending.is_waiting(ROOM));
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(
+            sent[1],
+            "⌛ No answer in 30s, so the agent was not allowed to run `rm -rf build/`."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_network_blocker_denies_fetches_and_defers_the_rest() {
+        let pending = PendingApprovals::new();
+        let (notify, sent) = recording_notify();
+        let inner: Arc<dyn ToolApprover> = Arc::new(approver(&pending, 60, notify.clone()));
+        let blocker = NetworkBlocker::new(Some(inner), Some(notify));
+
+        let fetch = request("fetch", serde_json::json!({ "url": "https://example.com" }));
+        assert!(!blocker.review(fetch).await);
+        assert_eq!(
+            sent.lock().unwrap()[0],
+            "🌐 The agent tried to fetch `https://example.com`, but network access is off in this channel. !network on allows it."
+        );
+        assert!(!pending.is_waiting(ROOM));
+
+        let read = request("read", serde_json::json!({ "path": "README.md" }));
+        assert!(blocker.review(read).await);
+        assert_eq!(sent.lock().unwrap().len(), 1);
+
+        let alone = NetworkBlocker::new(None, None);
+        let other = request("other", serde_json::json!({}));
+        assert!(alone.review(other).await);
+    }
+}
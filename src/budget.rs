@@ -0,0 +1,287 @@
+// ABOUTME: Monthly channel budgets: runway text for !usage/!budget and the admin page, plus overrun warnings.
+// ABOUTME: A background watcher posts a warning when a channel is on course to overspend, at most weekly.
+
+pub use gorp_core::usage::{SpendForecast, FORECAST_WINDOW_DAYS};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{
+    bus::{BusResponse, MessageBus, ResponseContent},
+    delivery::{hold_if_outside_window, DeliveryPriority},
+    platform::SharedPlatformRegistry,
+    runtime_mode::{Gate, RuntimeMode},
+    session::SessionStore,
+};
+use gorp_core::traits::MessageContent;
+
+/// Minimum time between two overrun warnings for the same channel
+pub const WARNING_INTERVAL_DAYS: i64 = 7;
+
+/// Render cents as dollars, e.g. 1050 -> "$10.50"
+pub fn format_cents(cents: u64) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+/// Parse a budget amount in dollars ("10", "$10", "12.50") into cents
+pub fn parse_budget(input: &str) -> Option<u64> {
+    let amount: f64 = input.trim().trim_start_matches('$').parse().ok()?;
+    if !amount.is_finite() || amount <= 0.0 {
+        return None;
+    }
+    Some((amount * 100.0).round() as u64)
+}
+
+fn format_day(day: NaiveDate) -> String {
+    day.format("%b %-d").to_string()
+}
+
+/// Lines describing the month's spend and, with a budget, how long it lasts
+pub fn runway_summary(
+    forecast: &SpendForecast,
+    budget_cents: Option<u64>,
+    today: NaiveDate,
+) -> String {
+    let mut text = format!(
+        "This month: {} so far, projected {} ({}-day average {}/day)",
+        format_cents(forecast.month_to_date_cents),
+        format_cents(forecast.projected_month_cents),
+        FORECAST_WINDOW_DAYS,
+        format_cents(forecast.daily_average_cents.round() as u64)
+    );
+    if let Some(budget) = budget_cents {
+        let runway = match forecast.exceeds_budget_on {
+            Some(day) if day <= today => "already exceeded".to_string(),
+            Some(day) => format!("runs out around {} at this rate", format_day(day)),
+            None => "on track".to_string(),
+        };
+        text.push_str(&format!(
+            "\nBudget: {} per month, {}",
+            format_cents(budget),
+            runway
+        ));
+    }
+    text
+}
+
+/// Short runway label for the admin channels table
+pub fn runway_label(
+    forecast: Option<&SpendForecast>,
+    budget_cents: Option<u64>,
+    today: NaiveDate,
+) -> String {
+    match (budget_cents, forecast.and_then(|f| f.exceeds_budget_on)) {
+        (None, _) => "-".to_string(),
+        (Some(_), None) => "On track".to_string(),
+        (Some(_), Some(day)) if day <= today => "Over budget".to_string(),
+        (Some(_), Some(day)) => format!("Out {}", format_day(day)),
+    }
+}
+
+/// The warning posted to a channel that is on course to exceed its budget
+pub fn warning_text(
+    channel_name: &str,
+    budget_cents: u64,
+    forecast: &SpendForecast,
+    today: NaiveDate,
+) -> String {
+    match forecast.exceeds_budget_on {
+        Some(day) if day > today => format!(
+            "⚠️ At the current rate, '{}' will exceed its {} budget around {} (projected {} this month).",
+            channel_name,
+            format_cents(budget_cents),
+            format_day(day),
+            format_cents(forecast.projected_month_cents)
+        ),
+        _ => format!(
+            "⚠️ '{}' is already over its {} budget this month ({} spent, projected {}).",
+            channel_name,
+            format_cents(budget_cents),
+            format_cents(forecast.month_to_date_cents),
+            format_cents(forecast.projected_month_cents)
+        ),
+    }
+}
+
+/// Whether enough time has passed since the last warning to send another
+pub fn warning_due(last_warned: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_warned.is_none_or(|at| now - at >= ChronoDuration::days(WARNING_INTERVAL_DAYS))
+}
+
+/// Warn every budgeted channel whose spend is projected past its budget.
+///
+/// Matrix channel rooms get the warning directly (held if the delivery window
+/// is closed); other channels get it as a system notice on the bus.
+/// Returns the number of warnings sent or held.
+pub async fn check_budgets(
+    session_store: &SessionStore,
+    bus: &MessageBus,
+    registry: &SharedPlatformRegistry,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let today = now.date_naive();
+    let mut warned = 0;
+
+    for channel in session_store.list_all()? {
+        let name = &channel.channel_name;
+        let Some(budget) = session_store.get_budget_cents(name)? else {
+            continue;
+        };
+        let Some(forecast) = session_store.get_spend_forecast(name, today)? else {
+            continue;
+        };
+        if forecast.exceeds_budget_on.is_none()
+            || !warning_due(session_store.get_budget_warned_at(name)?, now)
+        {
+            continue;
+        }
+
+        let text = warning_text(name, budget, &forecast, today);
+        let is_matrix_room = channel.room_id.starts_with('!');
+        let delivered = if is_matrix_room {
+            let target = ("matrix", channel.room_id.as_str());
+            if hold_if_outside_window(
+                session_store,
+                name,
+                Some(target),
+                &text,
+                DeliveryPriority::Normal,
+            )? {
+                true
+            } else {
+                let registry = registry.read().await;
+                match registry.get("matrix") {
+                    Some(platform) => match platform
+                        .send(&channel.room_id, MessageContent::plain(&text))
+                        .await
+                    {
+                        Ok(()) => true,
+                        Err(e) => {
+                            tracing::warn!(channel = %name, error = %e, "Failed to send budget warning");
+                            false
+                        }
+                    },
+                    None => false,
+                }
+            }
+        } else {
+            bus.publish_response(BusResponse {
+                session_name: name.clone(),
+                content: ResponseContent::SystemNotice(text),
+                timestamp: now,
+            });
+            true
+        };
+
+        if delivered {
+            session_store.set_budget_warned_at(name, now)?;
+            tracing::info!(
+                channel = %name,
+                budget_cents = budget,
+                projected_cents = forecast.projected_month_cents,
+                "Warned channel about projected budget overrun"
+            );
+            warned += 1;
+        }
+    }
+
+    Ok(warned)
+}
+
+/// Start the background task that warns channels heading over budget.
+pub async fn start_budget_watcher(
+    session_store: SessionStore,
+    bus: Arc<MessageBus>,
+    registry: SharedPlatformRegistry,
+    check_interval: Duration,
+    runtime: RuntimeMode,
+) {
+    tracing::info!(
+        interval_secs = check_interval.as_secs(),
+        "Starting budget runway watcher"
+    );
+
+    let mut ticker = tokio::time::interval(check_interval);
+
+    loop {
+        ticker.tick().await;
+        if runtime.check(Gate::Notification).is_err() {
+            continue;
+        }
+        if let Err(e) = check_budgets(&session_store, &bus, &registry, Utc::now()).await {
+            tracing::error!(error = %e, "Failed to check channel budgets");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, d).unwrap()
+    }
+
+    fn forecast(exceeds_budget_on: Option<NaiveDate>) -> SpendForecast {
+        SpendForecast {
+            daily_average_cents: 50.0,
+            month_to_date_cents: 500,
+            projected_month_cents: 1550,
+            exceeds_budget_on,
+        }
+    }
+
+    #[test]
+    fn test_parse_budget() {
+        assert_eq!(parse_budget("10"), Some(1000));
+        assert_eq!(parse_budget("$12.50"), Some(1250));
+        assert_eq!(parse_budget("0"), None);
+        assert_eq!(parse_budget("-5"), None);
+        assert_eq!(parse_budget("ten"), None);
+        assert_eq!(format_cents(1205), "$12.05");
+    }
+
+    #[test]
+    fn test_warning_text() {
+        assert_eq!(
+            warning_text("news", 1000, &forecast(Some(day(24))), day(10)),
+            "⚠️ At the current rate, 'news' will exceed its $10.00 budget around Jan 24 (projected $15.50 this month)."
+        );
+        assert!(warning_text("news", 400, &forecast(Some(day(10))), day(10))
+            .contains("already over its $4.00 budget"));
+    }
+
+    #[test]
+    fn test_runway_summary_and_label() {
+        let summary = runway_summary(&forecast(Some(day(21))), Some(1000), day(10));
+        assert!(summary
+            .contains("This month: $5.00 so far, projected $15.50 (14-day average $0.50/day)"));
+        assert!(summary.contains("Budget: $10.00 per month, runs out around Jan 21 at this rate"));
+        assert!(!runway_summary(&forecast(None), None, day(10)).contains("Budget"));
+
+        assert_eq!(runway_label(None, None, day(10)), "-");
+        assert_eq!(
+            runway_label(Some(&forecast(None)), Some(5000), day(10)),
+            "On track"
+        );
+        assert_eq!(
+            runway_label(Some(&forecast(Some(day(21)))), Some(1000), day(10)),
+            "Out Jan 21"
+        );
+        assert_eq!(
+            runway_label(Some(&forecast(Some(day(10)))), Some(400), day(10)),
+            "Over budget"
+        );
+    }
+
+    #[test]
+    fn test_warning_at_most_weekly() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap();
+        assert!(warning_due(None, now));
+        assert!(!warning_due(Some(now - ChronoDuration::days(6)), now));
+        assert!(warning_due(Some(now - ChronoDuration::days(7)), now));
+    }
+}
@@ -7,6 +7,8 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use tokio::sync::{broadcast, RwLock};
 
+use crate::delivery::DeliveryPriority;
+
 /// A message entering the bus from any source (gateway, web, API).
 #[derive(Debug, Clone)]
 pub struct BusMessage {
@@ -22,6 +24,22 @@ pub struct BusMessage {
     pub body: String,
     /// When the message was created
     pub timestamp: DateTime<Utc>,
+    /// Keep the agent's response in the channel workspace instead of posting it
+    /// (schedules running silently during quiet hours)
+    pub silent: bool,
+    /// Deliver the response even while the channel's delivery window is closed
+    pub urgent: bool,
+}
+
+impl BusMessage {
+    /// How the response to this message is treated by the channel's delivery window
+    pub fn priority(&self) -> DeliveryPriority {
+        if self.urgent {
+            DeliveryPriority::Urgent
+        } else {
+            DeliveryPriority::Normal
+        }
+    }
 }
 
 /// Identifies where a message originated.
@@ -33,13 +51,9 @@ pub enum MessageSource {
         channel_id: String,
     },
     /// From the web admin chat UI
-    Web {
-        connection_id: String,
-    },
+    Web { connection_id: String },
     /// From the webhook API
-    Api {
-        token_hint: String,
-    },
+    Api { token_hint: String },
 }
 
 /// Where a message should be routed.
@@ -110,6 +124,11 @@ impl MessageBus {
         self.inbound_tx.subscribe()
     }
 
+    /// Whether anything is listening for inbound messages yet.
+    pub fn has_inbound_subscribers(&self) -> bool {
+        self.inbound_tx.receiver_count() > 0
+    }
+
     /// Publish an outbound response to all subscribers.
     pub fn publish_response(&self, resp: BusResponse) {
         let _ = self.outbound_tx.send(resp);
@@ -137,11 +156,7 @@ impl MessageBus {
     /// Resolve (platform_id, channel_id) to a SessionTarget (async).
     ///
     /// Returns `SessionTarget::Session` if a binding exists, `SessionTarget::Dispatch` otherwise.
-    pub async fn resolve_target_async(
-        &self,
-        platform_id: &str,
-        channel_id: &str,
-    ) -> SessionTarget {
+    pub async fn resolve_target_async(&self, platform_id: &str, channel_id: &str) -> SessionTarget {
         let map = self.channel_map.read().await;
         match map.get(&(platform_id.to_string(), channel_id.to_string())) {
             Some(session_name) => SessionTarget::Session {
@@ -202,10 +217,7 @@ impl MessageBus {
     }
 
     /// List all (platform_id, channel_id) pairs bound to a given session (async).
-    pub async fn bindings_for_session_async(
-        &self,
-        session_name: &str,
-    ) -> Vec<(String, String)> {
+    pub async fn bindings_for_session_async(&self, session_name: &str) -> Vec<(String, String)> {
         let map = self.channel_map.read().await;
         map.iter()
             .filter(|(_, v)| v.as_str() == session_name)
@@ -10,6 +10,7 @@ use tokio::sync::{mpsc, Mutex};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::claude::{ClaudeEvent, ClaudeUsage};
+use crate::logging::loggable_content;
 
 /// Request message sent to Claude Jail
 #[derive(Debug, Serialize)]
@@ -146,7 +147,12 @@ impl ClaudeJailClient {
                 input,
             } => {
                 let input_preview = get_input_preview(&input, &tool);
-                tracing::info!(%channel_id, %tool, %input_preview, "Tool use");
+                tracing::info!(
+                    %channel_id,
+                    %tool,
+                    input = %loggable_content(&input_preview),
+                    "Tool use"
+                );
                 (
                     channel_id,
                     ClaudeEvent::ToolUse {
@@ -0,0 +1,359 @@
+// ABOUTME: Renders `gorp commands export` output from the command catalog and the gorp CLI.
+// ABOUTME: JSON and Markdown come from the catalog; bash/zsh/fish completions walk the clap tree.
+
+use anyhow::Result;
+use clap::ValueEnum;
+
+use crate::command_catalog::CommandCatalog;
+
+/// Output formats for `gorp commands export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+    Fish,
+    Zsh,
+    Bash,
+}
+
+/// The exact text `gorp commands export` prints
+pub fn export(
+    catalog: &CommandCatalog,
+    cli: &clap::Command,
+    format: ExportFormat,
+) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Json => format!("{}\n", catalog.to_json()?),
+        ExportFormat::Markdown => catalog.to_markdown(),
+        ExportFormat::Fish => fish_completion(cli),
+        ExportFormat::Zsh => zsh_completion(cli),
+        ExportFormat::Bash => bash_completion(cli),
+    })
+}
+
+/// One CLI (sub)command, reduced to what completion scripts need
+struct CliNode {
+    /// Command names from the binary down, e.g. ["gorp", "config", "init"]
+    path: Vec<String>,
+    /// Visible subcommands as (name, about)
+    subcommands: Vec<(String, String)>,
+    options: Vec<CliOption>,
+}
+
+struct CliOption {
+    long: Option<String>,
+    short: Option<char>,
+    help: String,
+    takes_value: bool,
+    values: Vec<String>,
+}
+
+impl CliOption {
+    /// `--long` then `-s`, whichever exist
+    fn words(&self) -> Vec<String> {
+        let mut words = Vec::new();
+        if let Some(long) = &self.long {
+            words.push(format!("--{}", long));
+        }
+        if let Some(short) = self.short {
+            words.push(format!("-{}", short));
+        }
+        words
+    }
+}
+
+impl CliNode {
+    /// Key the bash/zsh scripts build while walking the command line
+    fn key(&self) -> String {
+        self.path.join("__")
+    }
+
+    /// Everything worth offering at this point: subcommands first, then options
+    fn candidates(&self) -> Vec<String> {
+        self.subcommands
+            .iter()
+            .map(|(name, _)| name.clone())
+            .chain(self.options.iter().flat_map(CliOption::words))
+            .collect()
+    }
+}
+
+/// Every command in the tree, parents before children
+fn flatten(cli: &clap::Command) -> Vec<CliNode> {
+    let mut nodes = Vec::new();
+    collect(cli, Vec::new(), &mut nodes);
+    nodes
+}
+
+fn collect(cmd: &clap::Command, mut path: Vec<String>, nodes: &mut Vec<CliNode>) {
+    path.push(cmd.get_name().to_string());
+    let subcommands: Vec<&clap::Command> = cmd
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .collect();
+    let options = cmd
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+        .map(|arg| CliOption {
+            long: arg.get_long().map(str::to_string),
+            short: arg.get_short(),
+            help: arg
+                .get_help()
+                .map(|help| help.to_string())
+                .unwrap_or_default(),
+            takes_value: arg.get_action().takes_values(),
+            values: arg
+                .get_value_parser()
+                .possible_values()
+                .map(|values| {
+                    values
+                        .filter(|value| !value.is_hide_set())
+                        .map(|value| value.get_name().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    nodes.push(CliNode {
+        path: path.clone(),
+        subcommands: subcommands
+            .iter()
+            .map(|sub| {
+                let about = sub.get_about().map(|about| about.to_string());
+                (sub.get_name().to_string(), about.unwrap_or_default())
+            })
+            .collect(),
+        options,
+    });
+    for sub in subcommands {
+        collect(sub, path.clone(), nodes);
+    }
+}
+
+/// Options that consume the next word, deduplicated across the tree
+fn value_options(nodes: &[CliNode]) -> Vec<&CliOption> {
+    let mut seen: Vec<&CliOption> = Vec::new();
+    for option in nodes.iter().flat_map(|node| &node.options) {
+        if option.takes_value && !seen.iter().any(|o| o.words() == option.words()) {
+            seen.push(option);
+        }
+    }
+    seen
+}
+
+fn bash_completion(cli: &clap::Command) -> String {
+    let bin = cli.get_name();
+    let nodes = flatten(cli);
+    let value_options = value_options(&nodes);
+
+    let mut out = format!(
+        "# bash completion for {bin}\n\
+         # Generated by `{bin} commands export --format bash`\n\
+         \n\
+         _{bin}() {{\n    \
+             local cur prev cmd_path i opts\n    \
+             cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n    \
+             prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"\n"
+    );
+
+    let with_values: Vec<&&CliOption> = value_options
+        .iter()
+        .filter(|option| !option.values.is_empty())
+        .collect();
+    if !with_values.is_empty() {
+        out.push_str("\n    case \"$prev\" in\n");
+        for option in with_values {
+            out.push_str(&format!(
+                "        {})\n            \
+                     COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            \
+                     return\n            \
+                     ;;\n",
+                option.words().join("|"),
+                option.values.join(" ")
+            ));
+        }
+        out.push_str("    esac\n");
+    }
+
+    out.push_str(&format!(
+        "\n    cmd_path=\"{bin}\"\n    \
+         for ((i = 1; i < COMP_CWORD; i++)); do\n        \
+             case \"${{COMP_WORDS[i]}}\" in\n"
+    ));
+    for option in &value_options {
+        out.push_str(&format!(
+            "            {}) ((i++)) ;;\n",
+            option.words().join("|")
+        ));
+    }
+    out.push_str(
+        "            -*) ;;\n            \
+         *) cmd_path=\"${cmd_path}__${COMP_WORDS[i]}\" ;;\n        \
+         esac\n    \
+         done\n\
+         \n    \
+         opts=\"\"\n    \
+         case \"$cmd_path\" in\n",
+    );
+    for node in &nodes {
+        let candidates = node.candidates();
+        if !candidates.is_empty() {
+            out.push_str(&format!(
+                "        {}) opts=\"{}\" ;;\n",
+                node.key(),
+                candidates.join(" ")
+            ));
+        }
+    }
+    out.push_str(&format!(
+        "    esac\n    \
+         COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))\n\
+         }}\n\
+         \n\
+         complete -F _{bin} {bin}\n"
+    ));
+    out
+}
+
+fn zsh_completion(cli: &clap::Command) -> String {
+    let bin = cli.get_name();
+    let nodes = flatten(cli);
+    let value_options = value_options(&nodes);
+
+    // `path` is special in zsh (it mirrors $PATH), hence `cmd_path`
+    let mut out = format!(
+        "#compdef {bin}\n\
+         # Generated by `{bin} commands export --format zsh`\n\
+         \n\
+         _{bin}() {{\n    \
+             local cmd_path={bin} word skip=0\n    \
+             local -a opts\n"
+    );
+
+    let with_values: Vec<&&CliOption> = value_options
+        .iter()
+        .filter(|option| !option.values.is_empty())
+        .collect();
+    if !with_values.is_empty() {
+        out.push_str("\n    case ${words[CURRENT-1]} in\n");
+        for option in with_values {
+            out.push_str(&format!(
+                "        {})\n            compadd -- {}\n            return\n            ;;\n",
+                option.words().join("|"),
+                option.values.join(" ")
+            ));
+        }
+        out.push_str("    esac\n");
+    }
+
+    out.push_str(
+        "\n    for word in ${words[2,CURRENT-1]}; do\n        \
+         if (( skip )); then\n            \
+             skip=0\n            \
+             continue\n        \
+         fi\n        \
+         case $word in\n",
+    );
+    for option in &value_options {
+        out.push_str(&format!(
+            "            {}) skip=1 ;;\n",
+            option.words().join("|")
+        ));
+    }
+    out.push_str(
+        "            -*) ;;\n            \
+         *) cmd_path=\"${cmd_path}__${word}\" ;;\n        \
+         esac\n    \
+         done\n\
+         \n    \
+         case $cmd_path in\n",
+    );
+    for node in &nodes {
+        let candidates = node.candidates();
+        if !candidates.is_empty() {
+            out.push_str(&format!(
+                "        {}) opts=({}) ;;\n",
+                node.key(),
+                candidates.join(" ")
+            ));
+        }
+    }
+    out.push_str(&format!(
+        "    esac\n    \
+         compadd -- $opts\n\
+         }}\n\
+         \n\
+         _{bin} \"$@\"\n"
+    ));
+    out
+}
+
+fn fish_completion(cli: &clap::Command) -> String {
+    let bin = cli.get_name();
+    let mut out = format!(
+        "# fish completion for {bin}\n\
+         # Generated by `{bin} commands export --format fish`\n\
+         \n\
+         complete -c {bin} -f\n"
+    );
+
+    for node in flatten(cli) {
+        let seen: Vec<String> = node.path[1..]
+            .iter()
+            .map(|name| format!("__fish_seen_subcommand_from {}", name))
+            .collect();
+
+        if !node.subcommands.is_empty() {
+            let condition = if seen.is_empty() {
+                "__fish_use_subcommand".to_string()
+            } else {
+                let names: Vec<&str> = node.subcommands.iter().map(|(n, _)| n.as_str()).collect();
+                format!(
+                    "{}; and not __fish_seen_subcommand_from {}",
+                    seen.join("; and "),
+                    names.join(" ")
+                )
+            };
+            for (name, about) in &node.subcommands {
+                out.push_str(&format!(
+                    "complete -c {} -n \"{}\" -a {}",
+                    bin, condition, name
+                ));
+                if !about.is_empty() {
+                    out.push_str(&format!(" -d {}", fish_quote(about)));
+                }
+                out.push('\n');
+            }
+        }
+
+        for option in &node.options {
+            out.push_str(&format!("complete -c {}", bin));
+            if !seen.is_empty() {
+                out.push_str(&format!(" -n \"{}\"", seen.join("; and ")));
+            }
+            if let Some(short) = option.short {
+                out.push_str(&format!(" -s {}", short));
+            }
+            if let Some(long) = &option.long {
+                out.push_str(&format!(" -l {}", long));
+            }
+            if option.takes_value {
+                out.push_str(" -r");
+            }
+            if !option.values.is_empty() {
+                out.push_str(&format!(" -a {}", fish_quote(&option.values.join(" "))));
+            }
+            if !option.help.is_empty() {
+                out.push_str(&format!(" -d {}", fish_quote(&option.help)));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Single-quote a string for fish
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
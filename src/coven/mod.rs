@@ -1,7 +1,6 @@
 // ABOUTME: Coven gateway provider for registering workspaces as agents
 // ABOUTME: Manages gRPC streams to coven-gateway with heartbeat and message handling
 
-pub mod reconnect;
 pub mod stream;
 
 use std::collections::HashMap;
@@ -12,7 +11,8 @@ use tokio::sync::Mutex;
 use tonic::transport::Channel;
 use uuid::Uuid;
 
-use crate::config::CovenConfig;
+use crate::config::{CovenConfig, ReconnectConfig};
+use crate::reconnect::{BackoffConfig, BackoffState};
 use crate::session::SessionStore;
 use gorp_agent::AgentHandle;
 use gorp_core::warm_session::SharedWarmSessionManager;
@@ -91,19 +91,12 @@ impl CovenProvider {
     /// Register a workspace as an agent with the gateway
     async fn register_workspace(&mut self, workspace_name: &str) -> anyhow::Result<()> {
         let agent_id = self.deterministic_agent_id(workspace_name);
-        let display_name = format!(
-            "{}-{}",
-            self.config.agent_name_prefix, workspace_name
-        );
+        let display_name = format!("{}-{}", self.config.agent_name_prefix, workspace_name);
 
         let register = RegisterAgent {
             agent_id: agent_id.clone(),
             name: display_name,
-            capabilities: vec![
-                "chat".to_string(),
-                "code".to_string(),
-                "search".to_string(),
-            ],
+            capabilities: vec!["chat".to_string(), "code".to_string(), "search".to_string()],
             metadata: Some(AgentMetadata {
                 working_directory: Path::new(&self.workspace_dir)
                     .join(workspace_name)
@@ -115,10 +108,7 @@ impl CovenProvider {
                 workspaces: vec![workspace_name.to_string()],
                 backend: "mux".to_string(),
             }),
-            protocol_features: vec![
-                "token_usage".to_string(),
-                "cancellation".to_string(),
-            ],
+            protocol_features: vec!["token_usage".to_string(), "cancellation".to_string()],
         };
 
         self.spawn_agent_stream(agent_id, workspace_name.to_string(), register)
@@ -133,10 +123,7 @@ impl CovenProvider {
         let register = RegisterAgent {
             agent_id: agent_id.clone(),
             name: display_name,
-            capabilities: vec![
-                "dispatch".to_string(),
-                "admin".to_string(),
-            ],
+            capabilities: vec!["dispatch".to_string(), "admin".to_string()],
             metadata: Some(AgentMetadata {
                 working_directory: self.workspace_dir.clone(),
                 git: None,
@@ -186,7 +173,9 @@ impl CovenProvider {
         drop(mgr);
 
         let model = warm_config.model.clone().ok_or_else(|| {
-            anyhow::anyhow!("No model configured for DISPATCH. Set 'model' in config.toml under [mux] section.")
+            anyhow::anyhow!(
+                "No model configured for DISPATCH. Set 'model' in config.toml under [mux] section."
+            )
         })?;
 
         let dispatch_working_dir = std::env::temp_dir().join("gorp-dispatch");
@@ -204,6 +193,8 @@ impl CovenProvider {
                 .map(PathBuf::from),
             local_prompt_files: vec![],
             mcp_servers: vec![],
+            env: Default::default(),
+            network_access: true,
         };
 
         let dispatch_tools =
@@ -253,7 +244,7 @@ impl CovenProvider {
         tokio::spawn(async move {
             let mut cancel_rx = cancel_rx;
             let mut sessions: HashMap<String, String> = HashMap::new();
-            let mut backoff = reconnect::BackoffState::new(reconnect::BackoffConfig::default());
+            let mut backoff = BackoffState::new(BackoffConfig::from(&ReconnectConfig::default()));
             let mut client = client;
             let mut tx = tx;
             let mut inbound = inbound;
@@ -287,11 +278,11 @@ impl CovenProvider {
                         break 'reconnect;
                     }
 
-                    match backoff.record_failure() {
+                    match backoff.next_delay() {
                         Some(delay) => {
                             tracing::info!(
                                 agent_id = %agent_id_clone,
-                                delay_secs = delay.as_secs(),
+                                delay_secs = delay.as_secs_f64(),
                                 attempt = backoff.consecutive_failures(),
                                 "Reconnecting after backoff"
                             );
@@ -590,8 +581,7 @@ async fn handle_server_message(
                 request_id = %cancel.request_id,
                 "Cancel request from gateway"
             );
-            if let Err(e) =
-                stream::handle_cancel_request(&cancel, agent_handle, sessions, tx).await
+            if let Err(e) = stream::handle_cancel_request(&cancel, agent_handle, sessions, tx).await
             {
                 tracing::error!(
                     agent_id = %agent_id,
@@ -615,9 +605,7 @@ async fn handle_server_message(
 
 /// Get the system hostname
 fn hostname() -> String {
-    gethostname::gethostname()
-        .to_string_lossy()
-        .to_string()
+    gethostname::gethostname().to_string_lossy().to_string()
 }
 
 // =============================================================================
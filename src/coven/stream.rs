@@ -62,8 +62,10 @@ pub fn map_event_to_responses(request_id: &str, event: AgentEvent) -> Vec<AgentM
                     Event::Usage(proto::TokenUsage {
                         input_tokens: u.input_tokens.min(i32::MAX as u64) as i32,
                         output_tokens: u.output_tokens.min(i32::MAX as u64) as i32,
-                        cache_read_tokens: u.cache_read_tokens.unwrap_or(0).min(i32::MAX as u64) as i32,
-                        cache_write_tokens: u.cache_write_tokens.unwrap_or(0).min(i32::MAX as u64) as i32,
+                        cache_read_tokens: u.cache_read_tokens.unwrap_or(0).min(i32::MAX as u64)
+                            as i32,
+                        cache_write_tokens: u.cache_write_tokens.unwrap_or(0).min(i32::MAX as u64)
+                            as i32,
                         thinking_tokens: 0,
                     }),
                 ));
@@ -0,0 +1,264 @@
+// ABOUTME: Flushes output held outside a channel's delivery window once the window opens.
+// ABOUTME: Held messages go to their original platform target, or back onto the bus for bound channels.
+
+// Re-export core delivery window types from gorp-core
+pub use gorp_core::delivery::{
+    held_notice, should_hold, DeliveryPriority, DeliveryWindow, HeldMessage,
+};
+
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{
+    bus::{BusResponse, MessageBus, ResponseContent},
+    platform::SharedPlatformRegistry,
+    runtime_mode::{Gate, RuntimeMode},
+    session::SessionStore,
+    utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
+};
+use gorp_core::traits::MessageContent;
+
+/// Hold `body` in the outgoing queue if the channel's delivery window is closed.
+///
+/// Returns true when the message was held and must not be sent now. `target` is
+/// the (platform_id, channel_id) to deliver to later; None re-publishes to the bus.
+pub fn hold_if_outside_window(
+    session_store: &SessionStore,
+    channel_name: &str,
+    target: Option<(&str, &str)>,
+    body: &str,
+    priority: DeliveryPriority,
+) -> Result<bool> {
+    let window = session_store.get_delivery_window(channel_name)?;
+    if !should_hold(window.as_ref(), priority, Utc::now()) {
+        return Ok(false);
+    }
+    let id = session_store.hold_message(channel_name, target, body)?;
+    tracing::info!(
+        channel = %channel_name,
+        held_id = id,
+        "Output held until delivery window opens"
+    );
+    Ok(true)
+}
+
+/// Deliver a single held message to wherever it was originally headed.
+async fn deliver_held(
+    message: &HeldMessage,
+    bus: &MessageBus,
+    registry: &SharedPlatformRegistry,
+) -> Result<()> {
+    match (&message.platform_id, &message.target_id) {
+        (Some(platform_id), Some(target_id)) => {
+            let registry = registry.read().await;
+            let platform = registry
+                .get(platform_id)
+                .ok_or_else(|| anyhow::anyhow!("Platform '{}' is not registered", platform_id))?;
+            for chunk in chunk_message(&message.body, MAX_CHUNK_SIZE) {
+                let html = markdown_to_html(&chunk);
+                platform
+                    .send(target_id, MessageContent::html(&chunk, &html))
+                    .await?;
+            }
+        }
+        _ => {
+            bus.publish_response(BusResponse {
+                session_name: message.channel_name.clone(),
+                content: ResponseContent::Complete(message.body.clone()),
+                timestamp: Utc::now(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Deliver all held messages for a channel in the order they were generated.
+///
+/// When `announce` is set, a "N messages held" notice precedes the first
+/// message sent to each target. Returns the number of messages delivered;
+/// delivery stops at the first failure so ordering is preserved for the retry.
+pub async fn flush_channel(
+    channel_name: &str,
+    session_store: &SessionStore,
+    bus: &MessageBus,
+    registry: &SharedPlatformRegistry,
+    announce: bool,
+) -> Result<usize> {
+    let held = session_store.list_held_messages(channel_name)?;
+    if held.is_empty() {
+        return Ok(0);
+    }
+
+    if announce {
+        let notice = held_notice(held.len());
+        let mut announced: Vec<(Option<&str>, Option<&str>)> = Vec::new();
+        for message in &held {
+            let target = (message.platform_id.as_deref(), message.target_id.as_deref());
+            if announced.contains(&target) {
+                continue;
+            }
+            announced.push(target);
+            let result = match target {
+                (Some(platform_id), Some(target_id)) => {
+                    let registry = registry.read().await;
+                    match registry.get(platform_id) {
+                        Some(platform) => {
+                            platform
+                                .send(target_id, MessageContent::plain(&notice))
+                                .await
+                        }
+                        None => Ok(()),
+                    }
+                }
+                _ => {
+                    bus.publish_response(BusResponse {
+                        session_name: channel_name.to_string(),
+                        content: ResponseContent::SystemNotice(notice.clone()),
+                        timestamp: Utc::now(),
+                    });
+                    Ok(())
+                }
+            };
+            if let Err(e) = result {
+                tracing::warn!(channel = %channel_name, error = %e, "Failed to send held-messages notice");
+            }
+        }
+    }
+
+    let mut delivered = 0;
+    for message in &held {
+        if let Err(e) = deliver_held(message, bus, registry).await {
+            tracing::warn!(
+                channel = %channel_name,
+                held_id = message.id,
+                error = %e,
+                "Failed to deliver held message, will retry"
+            );
+            break;
+        }
+        session_store.remove_held_message(message.id)?;
+        delivered += 1;
+    }
+
+    tracing::info!(channel = %channel_name, delivered, "Flushed held messages");
+    Ok(delivered)
+}
+
+/// Start the background task that flushes held messages when windows open.
+///
+/// A window's "N messages held" notice goes out once each time it opens, even
+/// when a failed delivery keeps messages queued across several ticks.
+pub async fn start_delivery_flusher(
+    session_store: SessionStore,
+    bus: Arc<MessageBus>,
+    registry: SharedPlatformRegistry,
+    check_interval: Duration,
+    runtime: RuntimeMode,
+) {
+    tracing::info!(
+        interval_secs = check_interval.as_secs(),
+        "Starting delivery window flusher"
+    );
+
+    let mut ticker = tokio::time::interval(check_interval);
+    // Channels whose notice went out since their window last opened
+    let mut announced: HashSet<String> = HashSet::new();
+
+    loop {
+        ticker.tick().await;
+        // Held messages stay queued until safe mode ends
+        if runtime.check(Gate::Notification).is_err() {
+            continue;
+        }
+
+        let channels = match session_store.channels_with_held_messages() {
+            Ok(channels) => channels,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to list channels with held messages");
+                continue;
+            }
+        };
+        announced.retain(|channel| channels.contains(channel));
+
+        let now = Utc::now();
+        for channel_name in channels {
+            let window = match session_store.get_delivery_window(&channel_name) {
+                Ok(window) => window,
+                Err(e) => {
+                    tracing::warn!(channel = %channel_name, error = %e, "Failed to load delivery window");
+                    continue;
+                }
+            };
+
+            // A removed window means the channel is always open
+            if window.as_ref().is_some_and(|w| !w.is_open_at(now)) {
+                announced.remove(&channel_name);
+                continue;
+            }
+
+            let announce = window.as_ref().is_some_and(|w| w.announce)
+                && announced.insert(channel_name.clone());
+            if let Err(e) =
+                flush_channel(&channel_name, &session_store, &bus, &registry, announce).await
+            {
+                tracing::error!(channel = %channel_name, error = %e, "Failed to flush held messages");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_handler::traits::MockPlatform;
+    use crate::platform::PlatformRegistry;
+    use tempfile::TempDir;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_held_notice_is_sent_once_while_a_delivery_keeps_failing() {
+        let tmp = TempDir::new().unwrap();
+        let session_store = SessionStore::new(tmp.path()).unwrap();
+        let now = Utc::now();
+        let spec = format!(
+            "daily {}-{} notice",
+            (now - chrono::Duration::hours(1)).format("%H:%M"),
+            (now + chrono::Duration::hours(1)).format("%H:%M")
+        );
+        let window = DeliveryWindow::parse(&spec, "UTC").unwrap();
+        session_store
+            .set_delivery_window("research", &window)
+            .unwrap();
+        // Nothing is registered as "slack", so the first delivery fails every tick
+        session_store
+            .hold_message("research", Some(("slack", "C1")), "overnight report")
+            .unwrap();
+        session_store
+            .hold_message("research", Some(("telegram", "42")), "morning summary")
+            .unwrap();
+
+        let platform = MockPlatform::new("telegram");
+        let sent = platform.sent.clone();
+        let mut registry = PlatformRegistry::new();
+        registry.register(Box::new(platform));
+        tokio::spawn(start_delivery_flusher(
+            session_store.clone(),
+            Arc::new(MessageBus::new(16)),
+            Arc::new(tokio::sync::RwLock::new(registry)),
+            Duration::from_secs(60),
+            RuntimeMode::new(false),
+        ));
+
+        tokio::time::sleep(Duration::from_secs(200)).await;
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(matches!(&sent[0], (target, MessageContent::Plain(text))
+            if target == "42" && *text == held_notice(2)));
+        assert_eq!(
+            session_store.list_held_messages("research").unwrap().len(),
+            2
+        );
+    }
+}
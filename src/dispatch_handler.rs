@@ -7,13 +7,20 @@ use gorp_agent::AgentEvent;
 use matrix_sdk::{
     room::Room, ruma::events::room::message::RoomMessageEventContent, Client, RoomState,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::{
     config::Config,
+    dispatch_history::{
+        dispatch_workspace, format_history, trim_for_context, DispatchHistory, CONTEXT_MAX_CHARS,
+        CONTEXT_MAX_ENTRIES,
+    },
     dispatch_system_prompt::generate_dispatch_prompt,
     dispatch_tools::create_dispatch_tools,
+    logging::loggable_content,
+    platform::matrix::signing::Signer,
+    runtime_mode::Gate,
     session::SessionStore,
     utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
     warm_session::SharedWarmSessionManager,
@@ -23,7 +30,8 @@ use crate::{
 ///
 /// DISPATCH is a special agent that:
 /// - Runs in the 1:1 DM with the bot
-/// - Has no filesystem workspace (pure coordination)
+/// - Has no project workspace (pure coordination), only `.dispatch/` for its own state
+/// - Remembers each user's past conversations across restarts and resets
 /// - Can query status of all workspace rooms
 /// - Can dispatch tasks to worker rooms
 /// - Receives events from worker rooms
@@ -31,7 +39,7 @@ pub async fn handle_dispatch_message(
     room: Room,
     event: matrix_sdk::ruma::events::room::message::OriginalSyncRoomMessageEvent,
     _client: Client,
-    _config: Config,
+    config: Config,
     session_store: SessionStore,
     warm_manager: SharedWarmSessionManager,
 ) -> Result<()> {
@@ -44,10 +52,18 @@ pub async fn handle_dispatch_message(
 
     tracing::info!(
         room_id = %room.room_id(),
-        body_preview = %body.chars().take(50).collect::<String>(),
+        message = %loggable_content(body),
         "DISPATCH message received"
     );
 
+    // DISPATCH runs its own agent instead of a warm session, so it asks about safe mode itself
+    let runtime = warm_manager.read().await.runtime_mode();
+    if let Err(refusal) = runtime.check(Gate::AgentTurn) {
+        room.send(RoomMessageEventContent::text_plain(refusal.message()))
+            .await?;
+        return Ok(());
+    }
+
     // Get or create DISPATCH channel
     let dispatch_channel = session_store.get_or_create_dispatch_channel(room.room_id().as_str())?;
 
@@ -101,8 +117,8 @@ pub async fn handle_dispatch_message(
         mgr.config()
     };
 
-    // DISPATCH uses a temporary directory since it doesn't have a workspace
-    let dispatch_working_dir = std::env::temp_dir().join("gorp-dispatch");
+    // DISPATCH has no project workspace; it works in .dispatch/, which also holds its history
+    let dispatch_working_dir = dispatch_workspace(Path::new(&config.workspace.path));
 
     // Ensure the dispatch directory exists
     if let Err(e) = std::fs::create_dir_all(&dispatch_working_dir) {
@@ -126,6 +142,8 @@ pub async fn handle_dispatch_message(
             .map(PathBuf::from),
         local_prompt_files: vec![], // DISPATCH doesn't use local prompts
         mcp_servers: vec![],        // DISPATCH uses its own tools, not MCP servers
+        env: Default::default(),
+        network_access: true,
     };
 
     // Create DISPATCH-specific tools with access to session store
@@ -162,12 +180,27 @@ pub async fn handle_dispatch_message(
         // It will create a new one automatically
     }
 
+    // Replay this user's recent exchanges so DISPATCH remembers them even in a fresh session
+    let history = DispatchHistory::new(&dispatch_working_dir);
+    let sender = event.sender.to_string();
+    let past = history.load(&sender).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, sender = %sender, "Failed to load DISPATCH history");
+        Vec::new()
+    });
+    let history_block = format_history(trim_for_context(
+        &past,
+        CONTEXT_MAX_ENTRIES,
+        CONTEXT_MAX_CHARS,
+    ))
+    .map(|block| format!("{}\n\n", block))
+    .unwrap_or_default();
+
     // Build prompt with system context
     // For DISPATCH, we prepend the system prompt to the user message as context
     // since mux backend doesn't support per-message system prompts in the same way
     let full_prompt = format!(
-        "<system>\n{}\n</system>\n\n<user_message>\n{}\n</user_message>",
-        system_prompt, body
+        "<system>\n{}\n</system>\n\n{}<user_message>\n{}\n</user_message>",
+        system_prompt, history_block, body
     );
 
     // Send prompt and stream response
@@ -221,7 +254,10 @@ pub async fn handle_dispatch_message(
                 tracing::debug!(tool = %name, success = success, "DISPATCH tool completed");
             }
             _ => {
-                tracing::trace!(event = ?agent_event, "DISPATCH received event");
+                tracing::trace!(
+                    event = %loggable_content(&format!("{:?}", agent_event)),
+                    "DISPATCH received event"
+                );
             }
         }
     }
@@ -233,11 +269,18 @@ pub async fn handle_dispatch_message(
 
     // Send response (chunk if needed)
     if !response_text.is_empty() {
+        if let Err(e) = history.record_exchange(&sender, body, &response_text) {
+            tracing::warn!(error = %e, sender = %sender, "Failed to save DISPATCH history");
+        }
+        let signer = Signer::from_config(&config.signing);
         let chunks = chunk_message(&response_text, MAX_CHUNK_SIZE);
         for chunk in chunks {
             let html = markdown_to_html(&chunk);
-            room.send(RoomMessageEventContent::text_html(&chunk, &html))
-                .await?;
+            let content = match &signer {
+                Some(signer) => signer.html_event(room.room_id().as_str(), chunk, html),
+                None => RoomMessageEventContent::text_html(chunk, html),
+            };
+            room.send(content).await?;
         }
     } else {
         room.send(RoomMessageEventContent::text_plain(
@@ -0,0 +1,188 @@
+// ABOUTME: Per-user DISPATCH conversation history, persisted in the dispatch workspace.
+// ABOUTME: Exchanges go to a per-user JSONL file; recent ones are replayed into each prompt.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Entries kept on disk per user; older ones are dropped when the file is rewritten
+pub const MAX_STORED_ENTRIES: usize = 200;
+
+/// Most entries replayed into a single prompt
+pub const CONTEXT_MAX_ENTRIES: usize = 20;
+
+/// Character budget for the replayed history in a single prompt
+pub const CONTEXT_MAX_CHARS: usize = 8_000;
+
+/// DISPATCH's persistent workspace under the gorp workspace root
+pub fn dispatch_workspace(workspace_path: &Path) -> PathBuf {
+    workspace_path.join(".dispatch")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryRole {
+    User,
+    Dispatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub role: HistoryRole,
+    pub text: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Conversation history for every user talking to DISPATCH.
+///
+/// Lives beside the agent session rather than in it, so it survives restarts
+/// and `reset_room`/session resets.
+#[derive(Debug, Clone)]
+pub struct DispatchHistory {
+    dir: PathBuf,
+}
+
+impl DispatchHistory {
+    /// History stored under `<dispatch_workspace>/history/`
+    pub fn new(dispatch_workspace: &Path) -> Self {
+        Self {
+            dir: dispatch_workspace.join("history"),
+        }
+    }
+
+    /// File holding `user_id`'s history, e.g. `@alice:example.com` -> `alice_example.com.jsonl`
+    pub fn path_for(&self, user_id: &str) -> PathBuf {
+        let name: String = user_id
+            .trim_start_matches('@')
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.jsonl", name))
+    }
+
+    /// All stored entries for `user_id`, oldest first. Unreadable lines are skipped.
+    pub fn load(&self, user_id: &str) -> Result<Vec<HistoryEntry>> {
+        let path = self.path_for(user_id);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %e,
+                        "Skipping bad DISPATCH history line"
+                    );
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Append one user message and DISPATCH's reply, pruning the file past `MAX_STORED_ENTRIES`
+    pub fn record_exchange(&self, user_id: &str, message: &str, reply: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path_for(user_id);
+        let now = Utc::now();
+        let new_entries = [
+            HistoryEntry {
+                role: HistoryRole::User,
+                text: message.to_string(),
+                timestamp: now,
+            },
+            HistoryEntry {
+                role: HistoryRole::Dispatch,
+                text: reply.to_string(),
+                timestamp: now,
+            },
+        ];
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        for entry in &new_entries {
+            writeln!(file, "{}", serde_json::to_string(entry)?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        drop(file);
+
+        let entries = self.load(user_id)?;
+        if entries.len() > MAX_STORED_ENTRIES {
+            self.rewrite(&path, &entries[entries.len() - MAX_STORED_ENTRIES..])?;
+        }
+        Ok(())
+    }
+
+    fn rewrite(&self, path: &Path, entries: &[HistoryEntry]) -> Result<()> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, content)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// The newest entries that fit within `max_entries` and `max_chars`, oldest first
+pub fn trim_for_context(
+    entries: &[HistoryEntry],
+    max_entries: usize,
+    max_chars: usize,
+) -> &[HistoryEntry] {
+    let mut chars = 0;
+    let mut start = entries.len();
+    for (i, entry) in entries.iter().enumerate().rev() {
+        let len = entry.text.chars().count();
+        if entries.len() - i > max_entries || chars + len > max_chars {
+            break;
+        }
+        chars += len;
+        start = i;
+    }
+    &entries[start..]
+}
+
+/// Render history as a prompt block, or None when there is nothing to replay
+pub fn format_history(entries: &[HistoryEntry]) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+    let mut block = String::from("<conversation_history>\n");
+    for entry in entries {
+        let speaker = match entry.role {
+            HistoryRole::User => "User",
+            HistoryRole::Dispatch => "DISPATCH",
+        };
+        block.push_str(&format!(
+            "[{}] {}: {}\n",
+            entry.timestamp.format("%Y-%m-%d %H:%M UTC"),
+            speaker,
+            entry.text.trim()
+        ));
+    }
+    block.push_str("</conversation_history>");
+    Some(block)
+}
@@ -1,6 +1,7 @@
 // ABOUTME: MCP tools for DISPATCH control plane - room queries and task dispatch.
 // ABOUTME: These tools give DISPATCH cross-room visibility without filesystem access.
 
+use crate::logging::loggable_content;
 use crate::session::{Channel, DispatchTask, DispatchTaskStatus, SessionStore};
 use async_trait::async_trait;
 use mux::tool::{Tool, ToolResult};
@@ -120,7 +121,7 @@ pub fn dispatch_task(
     tracing::info!(
         task_id = %task.id,
         target_room = %room_id,
-        prompt_preview = %prompt.chars().take(50).collect::<String>(),
+        prompt = %loggable_content(prompt),
         "Task dispatched"
     );
 
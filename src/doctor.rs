@@ -0,0 +1,369 @@
+// ABOUTME: Environment checks behind `gorp doctor`: config, agent binary, workspace, database,
+// ABOUTME: webhook port and optionally Matrix login, each reported on its own line.
+
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::{
+    config::{BackendConfig, Config},
+    matrix_client,
+    scheduler::SchedulerStore,
+    session::SessionStore,
+};
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    /// Works, but probably not the way it was meant to
+    Warn,
+    /// gorp won't start or won't work like this
+    Fail,
+    /// Not run, because it was not asked for or an earlier check failed
+    Skip,
+}
+
+impl Status {
+    fn marker(self) -> &'static str {
+        match self {
+            Status::Pass => "✓",
+            Status::Warn => "!",
+            Status::Fail => "✗",
+            Status::Skip => "-",
+        }
+    }
+}
+
+/// One line of the doctor's checklist
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Backends that run a local agent binary
+const BINARY_BACKENDS: &[&str] = &["acp", "direct"];
+
+/// Whether the config loaded, and whether it connects to anything
+pub fn check_config(config: &Result<Config>) -> Check {
+    const NAME: &str = "Config";
+    match config {
+        Err(e) => Check::new(NAME, Status::Fail, format!("{:#}", e)),
+        Ok(config) => {
+            let platforms: Vec<&str> = [
+                ("matrix", config.matrix.is_some()),
+                ("telegram", config.telegram.is_some()),
+                ("slack", config.slack.is_some()),
+                ("whatsapp", config.whatsapp.is_some()),
+                ("irc", config.irc.is_some()),
+                ("zulip", config.zulip.is_some()),
+            ]
+            .into_iter()
+            .filter_map(|(name, configured)| configured.then_some(name))
+            .collect();
+            if platforms.is_empty() {
+                Check::new(
+                    NAME,
+                    Status::Warn,
+                    "valid, but no chat platform is configured",
+                )
+            } else {
+                Check::new(
+                    NAME,
+                    Status::Pass,
+                    format!("valid ({})", platforms.join(", ")),
+                )
+            }
+        }
+    }
+}
+
+/// Find `binary` the way a spawned process would: as given when it names a path,
+/// else in each directory of `path_var`
+pub fn resolve_binary(binary: &str, path_var: Option<&OsStr>) -> Option<PathBuf> {
+    let given = Path::new(binary);
+    if given.components().count() > 1 || given.is_absolute() {
+        return is_executable(given).then(|| given.to_path_buf());
+    }
+    std::env::split_paths(path_var?)
+        .map(|dir| dir.join(binary))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+/// Whether the configured backend's agent binary can be started
+pub fn check_agent_binary(backend: &BackendConfig, path_var: Option<&OsStr>) -> Check {
+    const NAME: &str = "Agent binary";
+    if !BINARY_BACKENDS.contains(&backend.backend_type.as_str()) {
+        return Check::new(
+            NAME,
+            Status::Pass,
+            format!("not needed by the {} backend", backend.backend_type),
+        );
+    }
+    let binary = backend.binary.as_deref().unwrap_or("claude");
+    match resolve_binary(binary, path_var) {
+        Some(path) => Check::new(NAME, Status::Pass, path.display().to_string()),
+        None => Check::new(
+            NAME,
+            Status::Fail,
+            format!(
+                "{} not found or not executable (set [backend] binary or fix PATH)",
+                binary
+            ),
+        ),
+    }
+}
+
+/// Whether files can be created in the workspace directory
+pub fn check_workspace(path: &Path) -> Check {
+    const NAME: &str = "Workspace";
+    let probe = path.join(format!(".gorp-doctor-{}", std::process::id()));
+    let result = std::fs::create_dir_all(path)
+        .and_then(|()| std::fs::write(&probe, b"ok"))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::new(
+            NAME,
+            Status::Pass,
+            format!("{} is writable", path.display()),
+        ),
+        Err(e) => Check::new(
+            NAME,
+            Status::Fail,
+            format!("{} is not writable: {}", path.display(), e),
+        ),
+    }
+}
+
+/// Whether the session and scheduler databases open and their schemas are current
+pub fn check_database(workspace: &Path) -> Check {
+    const NAME: &str = "Database";
+    let opened = SessionStore::new(workspace).and_then(|store| {
+        SchedulerStore::new(store.db_connection()).initialize_schema()?;
+        Ok(store.list_all()?.len())
+    });
+    match opened {
+        Ok(channels) => Check::new(
+            NAME,
+            Status::Pass,
+            format!(
+                "{} opened, {} channel(s)",
+                workspace.join("sessions.db").display(),
+                channels
+            ),
+        ),
+        Err(e) => Check::new(NAME, Status::Fail, format!("{:#}", e)),
+    }
+}
+
+/// Whether the webhook server could listen where it's configured to
+pub fn check_webhook_port(host: &str, port: u16) -> Check {
+    const NAME: &str = "Webhook port";
+    match std::net::TcpListener::bind((host, port)) {
+        Ok(_) => Check::new(NAME, Status::Pass, format!("{}:{} is free", host, port)),
+        Err(e) => Check::new(
+            NAME,
+            Status::Fail,
+            format!(
+                "can't bind {}:{}: {} (is gorp already running?)",
+                host, port, e
+            ),
+        ),
+    }
+}
+
+/// Whether the bot can log in to its Matrix homeserver
+pub async fn check_matrix_login(config: &Config) -> Check {
+    const NAME: &str = "Matrix login";
+    let Some(matrix) = &config.matrix else {
+        return Check::new(NAME, Status::Skip, "Matrix is not configured");
+    };
+    let login = async {
+        let client =
+            matrix_client::create_client(&matrix.home_server, &matrix.user_id, &matrix.device_name)
+                .await?;
+        matrix_client::login(
+            &client,
+            &matrix.user_id,
+            matrix.password.as_deref(),
+            matrix.access_token.as_deref(),
+            &matrix.device_name,
+        )
+        .await
+    };
+    match login.await {
+        Ok(()) => Check::new(
+            NAME,
+            Status::Pass,
+            format!("{} on {}", matrix.user_id, matrix.home_server),
+        ),
+        Err(e) => Check::new(NAME, Status::Fail, format!("{:#}", e)),
+    }
+}
+
+/// Run every check. Checks that need the config are skipped when it doesn't load;
+/// the Matrix login, which talks to the homeserver, only runs when asked for.
+pub async fn run_checks(config: Result<Config>, matrix_login: bool) -> Vec<Check> {
+    let mut checks = vec![check_config(&config)];
+    let Ok(config) = config else {
+        for name in [
+            "Agent binary",
+            "Workspace",
+            "Database",
+            "Webhook port",
+            "Matrix login",
+        ] {
+            checks.push(Check::new(name, Status::Skip, "needs a valid config"));
+        }
+        return checks;
+    };
+
+    checks.push(check_agent_binary(
+        &config.backend,
+        std::env::var_os("PATH").as_deref(),
+    ));
+    let workspace = PathBuf::from(&config.workspace.path);
+    let writable = check_workspace(&workspace);
+    let database = if writable.status == Status::Fail {
+        Check::new("Database", Status::Skip, "needs a writable workspace")
+    } else {
+        check_database(&workspace)
+    };
+    checks.extend([
+        writable,
+        database,
+        check_webhook_port(&config.webhook.host, config.webhook.port),
+    ]);
+    checks.push(if matrix_login {
+        check_matrix_login(&config).await
+    } else {
+        Check::new(
+            "Matrix login",
+            Status::Skip,
+            "pass --matrix-login to try it",
+        )
+    });
+    checks
+}
+
+/// The checklist as printed by `gorp doctor`
+pub fn render(checks: &[Check]) -> String {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for check in checks {
+        let _ = writeln!(
+            out,
+            "{} {:<width$}  {}",
+            check.status.marker(),
+            check.name,
+            check.detail,
+            width = width
+        );
+    }
+    out
+}
+
+/// Whether any check failed outright
+pub fn has_failures(checks: &[Check]) -> bool {
+    checks.iter().any(|c| c.status == Status::Fail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_binary_searches_path_and_honours_explicit_paths() {
+        let dir = TempDir::new().unwrap();
+        let agent = dir.path().join("agent");
+        std::fs::write(&agent, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&agent, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let path_var =
+            std::env::join_paths(["/nonexistent", dir.path().to_str().unwrap()]).unwrap();
+
+        assert_eq!(
+            resolve_binary("agent", Some(&path_var)),
+            Some(agent.clone())
+        );
+        assert_eq!(resolve_binary("missing", Some(&path_var)), None);
+        assert_eq!(resolve_binary("agent", None), None);
+        assert_eq!(
+            resolve_binary(agent.to_str().unwrap(), None),
+            Some(agent.clone())
+        );
+        assert_eq!(resolve_binary("/nonexistent/agent", Some(&path_var)), None);
+    }
+
+    #[test]
+    fn test_workspace_and_database_checks() {
+        let dir = TempDir::new().unwrap();
+        let workspace = dir.path().join("workspace");
+
+        assert_eq!(check_workspace(&workspace).status, Status::Pass);
+        let database = check_database(&workspace);
+        assert_eq!(database.status, Status::Pass);
+        assert!(database.detail.contains("0 channel(s)"));
+
+        // A file where the workspace directory should be
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        assert_eq!(check_workspace(&blocked).status, Status::Fail);
+    }
+
+    #[test]
+    fn test_port_in_use_fails() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let check = check_webhook_port("127.0.0.1", port);
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.detail.contains("is gorp already running?"));
+        drop(listener);
+        assert_eq!(check_webhook_port("127.0.0.1", port).status, Status::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_bad_config_skips_dependent_checks_and_fails() {
+        let checks = run_checks(Err(anyhow::anyhow!("missing [workspace]")), true).await;
+        assert_eq!(checks[0].status, Status::Fail);
+        assert!(checks[1..].iter().all(|c| c.status == Status::Skip));
+        assert!(has_failures(&checks));
+
+        let rendered = render(&checks);
+        assert!(rendered.starts_with("✗ Config        missing [workspace]\n"));
+        assert!(rendered.contains("- Webhook port  needs a valid config\n"));
+    }
+}
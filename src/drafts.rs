@@ -0,0 +1,122 @@
+// ABOUTME: Send-guard interception for the chat paths and the sweep that expires forgotten drafts.
+// ABOUTME: Drafts are persisted per (channel, user) so a restart doesn't lose a half-written prompt.
+
+// Re-export core draft rules from gorp-core
+pub use gorp_core::drafts::{append_text, expiry_notice, should_hold, Draft, QUEUED_NOTICE};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::{
+    config::SendGuardConfig,
+    platform::SharedPlatformRegistry,
+    runtime_mode::{Gate, RuntimeMode},
+    session::SessionStore,
+};
+use gorp_core::traits::MessageContent;
+
+/// Hold a chat message as a draft if the sender's send guard applies.
+///
+/// Returns the reply to post when the message was held; None means the message
+/// should go to the agent as usual. A long message arriving while a draft is
+/// pending is appended to it rather than replacing it.
+pub fn hold_if_guarded(
+    session_store: &SessionStore,
+    config: &SendGuardConfig,
+    platform_id: &str,
+    channel_id: &str,
+    user_id: &str,
+    body: &str,
+) -> Result<Option<String>> {
+    let enabled = session_store.is_send_guard_enabled(user_id)?;
+    if !should_hold(enabled, body, config.threshold_chars) {
+        return Ok(None);
+    }
+
+    let draft = match session_store.get_draft(channel_id, user_id)? {
+        Some(existing) => append_text(&existing.body, body),
+        None => body.to_string(),
+    };
+    session_store.save_draft(platform_id, channel_id, user_id, &draft)?;
+    tracing::info!(
+        channel = %channel_id,
+        user = %user_id,
+        chars = draft.chars().count(),
+        "Held message as send-guard draft"
+    );
+    Ok(Some(QUEUED_NOTICE.to_string()))
+}
+
+/// Discard every draft untouched for longer than `ttl`, posting the expiry
+/// reminder (with the draft text) where the draft was written.
+/// Returns the number of drafts expired.
+pub async fn sweep_expired_drafts(
+    session_store: &SessionStore,
+    registry: &SharedPlatformRegistry,
+    ttl: chrono::Duration,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut expired = 0;
+    for draft in session_store.list_drafts()? {
+        if !draft.is_expired(ttl, now) {
+            continue;
+        }
+        // An !append since we listed refreshes the draft; leave it for a later sweep
+        if !session_store.discard_draft_if_unchanged(&draft)? {
+            continue;
+        }
+        expired += 1;
+
+        let registry = registry.read().await;
+        let Some(platform) = registry.get(&draft.platform_id) else {
+            tracing::warn!(
+                platform = %draft.platform_id,
+                channel = %draft.channel_id,
+                "Draft expired on an unregistered platform; reminder not sent"
+            );
+            continue;
+        };
+        if let Err(e) = platform
+            .send(
+                &draft.channel_id,
+                MessageContent::plain(expiry_notice(&draft)),
+            )
+            .await
+        {
+            tracing::warn!(channel = %draft.channel_id, error = %e, "Failed to send draft expiry reminder");
+        }
+    }
+    Ok(expired)
+}
+
+/// Start the background task that expires forgotten drafts.
+pub async fn start_draft_sweeper(
+    session_store: SessionStore,
+    registry: SharedPlatformRegistry,
+    config: SendGuardConfig,
+    check_interval: Duration,
+    runtime: RuntimeMode,
+) {
+    tracing::info!(
+        expiry_mins = config.draft_expiry_mins,
+        interval_secs = check_interval.as_secs(),
+        "Starting send-guard draft sweeper"
+    );
+
+    let ttl = chrono::Duration::minutes(config.draft_expiry_mins as i64);
+    let mut ticker = tokio::time::interval(check_interval);
+
+    loop {
+        ticker.tick().await;
+        // Expiring a draft posts a reminder, so drafts outlive their expiry in safe mode
+        if runtime.check(Gate::Notification).is_err() {
+            continue;
+        }
+        match sweep_expired_drafts(&session_store, &registry, ttl, Utc::now()).await {
+            Ok(0) => {}
+            Ok(expired) => tracing::info!(expired, "Expired send-guard drafts"),
+            Err(e) => tracing::error!(error = %e, "Failed to sweep send-guard drafts"),
+        }
+    }
+}
@@ -11,6 +11,7 @@ use tokio::sync::Mutex;
 
 use crate::bus::{BusMessage, MessageBus, MessageSource, ResponseContent, SessionTarget};
 use crate::gateway::GatewayAdapter;
+use crate::platform::matrix::signing::Signer;
 use gorp_core::config::MatrixConfig;
 
 /// Matrix gateway adapter that bridges Matrix rooms and the message bus.
@@ -21,14 +22,17 @@ use gorp_core::config::MatrixConfig;
 pub struct MatrixAdapter {
     client: Client,
     config: MatrixConfig,
+    signer: Option<Signer>,
     bus: Mutex<Option<Arc<MessageBus>>>,
 }
 
 impl MatrixAdapter {
-    pub fn new(client: Client, config: MatrixConfig) -> Self {
+    /// Adapter sending through `client`; agent output is signed when there's a `signer`
+    pub fn new(client: Client, config: MatrixConfig, signer: Option<Signer>) -> Self {
         Self {
             client,
             config,
+            signer,
             bus: Mutex::new(None),
         }
     }
@@ -57,6 +61,7 @@ impl GatewayAdapter for MatrixAdapter {
         // Spawn outbound loop: subscribe to bus responses, filter for sessions
         // bound to matrix channels, and send responses to the appropriate rooms
         let client = self.client.clone();
+        let signer = self.signer.clone();
         let outbound_bus = bus.clone();
         tokio::spawn(async move {
             let mut rx = outbound_bus.subscribe_responses();
@@ -69,8 +74,13 @@ impl GatewayAdapter for MatrixAdapter {
                             .await;
                         for (platform_id, channel_id) in bindings {
                             if platform_id == "matrix" {
-                                if let Err(e) =
-                                    send_to_room(&client, &channel_id, &resp.content).await
+                                if let Err(e) = send_to_room(
+                                    &client,
+                                    &channel_id,
+                                    &resp.content,
+                                    signer.as_ref(),
+                                )
+                                .await
                                 {
                                     tracing::error!(
                                         room = %channel_id,
@@ -97,7 +107,7 @@ impl GatewayAdapter for MatrixAdapter {
     }
 
     async fn send(&self, channel_id: &str, content: ResponseContent) -> anyhow::Result<()> {
-        send_to_room(&self.client, channel_id, &content).await
+        send_to_room(&self.client, channel_id, &content, self.signer.as_ref()).await
     }
 
     async fn stop(&self) -> anyhow::Result<()> {
@@ -113,6 +123,7 @@ async fn send_to_room(
     client: &Client,
     room_id: &str,
     content: &ResponseContent,
+    signer: Option<&Signer>,
 ) -> anyhow::Result<()> {
     let room_id: OwnedRoomId = room_id
         .parse()
@@ -122,21 +133,29 @@ async fn send_to_room(
         .get_room(&room_id)
         .ok_or_else(|| anyhow::anyhow!("Room not found: {}", room_id))?;
 
-    let message_content = response_to_matrix_message(content);
+    let message_content = response_to_matrix_message(content, room.room_id().as_str(), signer);
     room.send(message_content).await?;
     Ok(())
 }
 
-/// Convert a ResponseContent to a Matrix RoomMessageEventContent.
+/// Convert a ResponseContent to a Matrix RoomMessageEventContent for `room_id`.
 ///
 /// Plain text content is also rendered as HTML via markdown conversion
-/// for rich display in Matrix clients that support it.
-pub fn response_to_matrix_message(content: &ResponseContent) -> RoomMessageEventContent {
+/// for rich display in Matrix clients that support it. Agent output is signed
+/// when there's a `signer`; errors and system notices never are.
+pub fn response_to_matrix_message(
+    content: &ResponseContent,
+    room_id: &str,
+    signer: Option<&Signer>,
+) -> RoomMessageEventContent {
     match content {
         ResponseContent::Chunk(text) | ResponseContent::Complete(text) => {
             // Use both plain text and HTML for rich rendering
             let html = crate::utils::markdown_to_html(text);
-            RoomMessageEventContent::text_html(text, html)
+            match signer {
+                Some(signer) => signer.html_event(room_id, text, html),
+                None => RoomMessageEventContent::text_html(text, html),
+            }
         }
         ResponseContent::Error(error) => {
             let plain = format!("Error: {}", error);
@@ -170,5 +189,7 @@ pub fn matrix_event_to_bus_message(
         sender: sender.to_string(),
         body: body.to_string(),
         timestamp: chrono::Utc::now(),
+        silent: false,
+        urgent: false,
     }
 }
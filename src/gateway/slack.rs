@@ -69,13 +69,9 @@ impl GatewayAdapter for SlackAdapter {
                             .await;
                         for (platform_id, channel_id) in bindings {
                             if platform_id == "slack" {
-                                if let Err(e) = send_to_channel(
-                                    &client,
-                                    &bot_token,
-                                    &channel_id,
-                                    &resp.content,
-                                )
-                                .await
+                                if let Err(e) =
+                                    send_to_channel(&client, &bot_token, &channel_id, &resp.content)
+                                        .await
                                 {
                                     tracing::error!(
                                         channel = %channel_id,
@@ -172,5 +168,7 @@ pub fn slack_event_to_bus_message(
         sender: sender.to_string(),
         body: body.to_string(),
         timestamp: chrono::Utc::now(),
+        silent: false,
+        urgent: false,
     }
 }
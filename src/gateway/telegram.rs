@@ -62,8 +62,7 @@ impl GatewayAdapter for TelegramAdapter {
                             .await;
                         for (platform_id, channel_id) in bindings {
                             if platform_id == "telegram" {
-                                if let Err(e) =
-                                    send_to_chat(&bot, &channel_id, &resp.content).await
+                                if let Err(e) = send_to_chat(&bot, &channel_id, &resp.content).await
                                 {
                                     tracing::error!(
                                         chat_id = %channel_id,
@@ -102,16 +101,9 @@ impl GatewayAdapter for TelegramAdapter {
 }
 
 /// Send a ResponseContent to a Telegram chat via the Bot API.
-async fn send_to_chat(
-    bot: &Bot,
-    chat_id: &str,
-    content: &ResponseContent,
-) -> anyhow::Result<()> {
-    let chat_id: ChatId = ChatId(
-        chat_id
-            .parse::<i64>()
-            .map_err(|e| anyhow::anyhow!("Invalid Telegram chat ID '{}': {}", chat_id, e))?,
-    );
+async fn send_to_chat(bot: &Bot, chat_id: &str, content: &ResponseContent) -> anyhow::Result<()> {
+    // A forum topic's channel ID also carries its thread
+    let (chat_id, thread_id) = crate::platform::telegram::parse_channel_id(chat_id)?;
 
     let text = response_to_telegram_message(content);
 
@@ -119,8 +111,11 @@ async fn send_to_chat(
         return Ok(());
     }
 
-    bot.send_message(chat_id, text)
-        .await
+    let mut req = bot.send_message(chat_id, text);
+    if let Some(thread_id) = thread_id {
+        req = req.message_thread_id(thread_id);
+    }
+    req.await
         .map_err(|e| anyhow::anyhow!("Failed to send Telegram message: {}", e))?;
 
     Ok(())
@@ -159,5 +154,7 @@ pub fn telegram_event_to_bus_message(
         sender: sender.to_string(),
         body: body.to_string(),
         timestamp: chrono::Utc::now(),
+        silent: false,
+        urgent: false,
     }
 }
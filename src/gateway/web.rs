@@ -5,7 +5,9 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use crate::admin::websocket::{ChatChunkData, ChatCompleteData, ChatErrorData, ServerMessage, WsHub};
+use crate::admin::websocket::{
+    ChatChunkData, ChatCompleteData, ChatErrorData, ServerMessage, WsHub,
+};
 use crate::bus::{MessageBus, ResponseContent};
 use crate::gateway::GatewayAdapter;
 
@@ -39,10 +41,8 @@ impl GatewayAdapter for WebAdapter {
             loop {
                 match rx.recv().await {
                     Ok(resp) => {
-                        let messages = response_to_server_messages(
-                            &resp.session_name,
-                            resp.content,
-                        );
+                        let messages =
+                            response_to_server_messages(&resp.session_name, resp.content);
                         for msg in messages {
                             hub.broadcast(msg);
                         }
@@ -350,11 +350,10 @@ impl GorpApp {
                         tracing::info!(room_count = self.rooms.len(), "Fetched room list");
 
                         // Start Matrix sync loop (only when Matrix is configured)
-                        if let (Some(ref client), Some(ref token)) = (&server.matrix_client, &server.sync_token) {
-                            let sync_rx = sync::start_sync(
-                                client.clone(),
-                                token.clone(),
-                            );
+                        if let (Some(ref client), Some(ref token)) =
+                            (&server.matrix_client, &server.sync_token)
+                        {
+                            let sync_rx = sync::start_sync(client.clone(), token.clone());
                             self.sync_rx = Some(sync_rx);
                             tracing::info!("Started Matrix sync for GUI");
                         } else {
@@ -1117,7 +1116,8 @@ impl GorpApp {
 
                 if let Some(ref server) = self.server {
                     let Some(ref client) = server.matrix_client else {
-                        self.room_creation_error = Some("Matrix not configured — cannot create rooms".to_string());
+                        self.room_creation_error =
+                            Some("Matrix not configured — cannot create rooms".to_string());
                         return Task::none();
                     };
                     let client = client.clone();
@@ -0,0 +1,458 @@
+// ABOUTME: Message catalog for user-facing bot text, keyed by identifier with per-language TOML files.
+// ABOUTME: Resolves channel/user locales with English fallback; optional catalog dir is hot-reloaded.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{Context, Result};
+
+use crate::session::SessionStore;
+
+/// Language every key must exist in; lookups fall back to it
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Catalogs compiled into the binary
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.toml")),
+    ("es", include_str!("../locales/es.toml")),
+];
+
+/// How often the catalog dir is checked for changes
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Translations for every loaded language, keyed by "<section>.<name>"
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    languages: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Catalog {
+    /// The catalogs shipped with gorp
+    pub fn builtin() -> Self {
+        let mut catalog = Self::default();
+        for (lang, source) in BUILTIN {
+            // Built-in catalogs are covered by tests, so a parse failure is a build bug
+            catalog
+                .merge(lang, source)
+                .unwrap_or_else(|e| panic!("built-in catalog {} is invalid: {:#}", lang, e));
+        }
+        catalog
+    }
+
+    /// Built-in catalogs plus every `<lang>.toml` in `dir`, which win on conflicts
+    pub fn load(dir: Option<&Path>) -> Result<Self> {
+        let mut catalog = Self::builtin();
+        let Some(dir) = dir else {
+            return Ok(catalog);
+        };
+        for path in catalog_files(dir)? {
+            let Some(lang) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let source = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            catalog
+                .merge(lang, &source)
+                .with_context(|| format!("Invalid catalog {}", path.display()))?;
+        }
+        Ok(catalog)
+    }
+
+    /// Add one language's TOML, flattening nested tables into dotted keys
+    pub fn merge(&mut self, lang: &str, source: &str) -> Result<()> {
+        let table: toml::Table = toml::from_str(source)?;
+        let entries = self.languages.entry(normalize(lang)).or_default();
+        flatten("", &table, entries)
+    }
+
+    /// Text for `key` in `locale`, trying the base language ("pt" for "pt-BR") and then English
+    pub fn lookup(&self, locale: &str, key: &str) -> Option<&str> {
+        let locale = normalize(locale);
+        let base = locale.split('-').next().unwrap_or(&locale).to_string();
+        [locale, base, FALLBACK_LOCALE.to_string()]
+            .iter()
+            .find_map(|lang| self.languages.get(lang)?.get(key))
+            .map(String::as_str)
+    }
+
+    /// Whether a catalog exists for `locale` or its base language
+    pub fn has_language(&self, locale: &str) -> bool {
+        let locale = normalize(locale);
+        let base = locale.split('-').next().unwrap_or(&locale);
+        self.languages.contains_key(&locale) || self.languages.contains_key(base)
+    }
+
+    pub fn languages(&self) -> Vec<&str> {
+        self.languages.keys().map(String::as_str).collect()
+    }
+
+    /// English keys each other language has no translation for
+    pub fn missing_keys(&self) -> BTreeMap<String, Vec<String>> {
+        let Some(reference) = self.languages.get(FALLBACK_LOCALE) else {
+            return BTreeMap::new();
+        };
+        self.languages
+            .iter()
+            .filter(|(lang, _)| lang.as_str() != FALLBACK_LOCALE)
+            .map(|(lang, entries)| {
+                let missing = reference
+                    .keys()
+                    .filter(|key| !entries.contains_key(*key))
+                    .cloned()
+                    .collect();
+                (lang.clone(), missing)
+            })
+            .collect()
+    }
+}
+
+fn normalize(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_lowercase()
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut BTreeMap<String, String>) -> Result<()> {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match value {
+            toml::Value::String(text) => {
+                out.insert(key, text.clone());
+            }
+            toml::Value::Table(nested) => flatten(&key, nested, out)?,
+            _ => anyhow::bail!("{} must be a string or a table", key),
+        }
+    }
+    Ok(())
+}
+
+fn catalog_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Newest modification time among the catalog files, used to spot edits
+fn dir_stamp(dir: &Path) -> Option<SystemTime> {
+    catalog_files(dir)
+        .ok()?
+        .iter()
+        .filter_map(|path| path.metadata().and_then(|m| m.modified()).ok())
+        .max()
+}
+
+// =============================================================================
+// Process-wide catalog
+// =============================================================================
+
+struct Cache {
+    catalog: Arc<Catalog>,
+    default_locale: String,
+    dir: Option<PathBuf>,
+    stamp: Option<SystemTime>,
+    checked_at: Instant,
+}
+
+fn cache() -> &'static RwLock<Cache> {
+    static CACHE: OnceLock<RwLock<Cache>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        RwLock::new(Cache {
+            catalog: Arc::new(Catalog::builtin()),
+            default_locale: FALLBACK_LOCALE.to_string(),
+            dir: None,
+            stamp: None,
+            checked_at: Instant::now(),
+        })
+    })
+}
+
+/// Set the default locale and the optional catalog dir, loading it right away
+pub fn init(default_locale: &str, dir: Option<PathBuf>) -> Result<()> {
+    let catalog = Catalog::load(dir.as_deref())?;
+    let mut cache = cache().write().unwrap_or_else(|e| e.into_inner());
+    cache.stamp = dir.as_deref().and_then(dir_stamp);
+    cache.catalog = Arc::new(catalog);
+    cache.default_locale = normalize(default_locale);
+    cache.dir = dir;
+    cache.checked_at = Instant::now();
+    Ok(())
+}
+
+/// The current catalog, reloaded first if a file in the catalog dir changed
+pub fn catalog() -> Arc<Catalog> {
+    {
+        let cache = cache().read().unwrap_or_else(|e| e.into_inner());
+        if cache.dir.is_none() || cache.checked_at.elapsed() < RELOAD_CHECK_INTERVAL {
+            return Arc::clone(&cache.catalog);
+        }
+    }
+
+    let mut cache = cache().write().unwrap_or_else(|e| e.into_inner());
+    cache.checked_at = Instant::now();
+    if let Some(dir) = cache.dir.clone() {
+        let stamp = dir_stamp(&dir);
+        if stamp != cache.stamp {
+            match Catalog::load(Some(&dir)) {
+                Ok(catalog) => {
+                    tracing::info!(dir = %dir.display(), "Reloaded message catalog");
+                    cache.catalog = Arc::new(catalog);
+                    cache.stamp = stamp;
+                }
+                // Keep serving the last good catalog until the file is fixed
+                Err(e) => tracing::warn!(error = %e, "Failed to reload message catalog"),
+            }
+        }
+    }
+    Arc::clone(&cache.catalog)
+}
+
+/// Locale used when neither the channel nor the user picked one
+pub fn default_locale() -> String {
+    cache()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .default_locale
+        .clone()
+}
+
+/// Look up `key` in `locale`; a key missing from every catalog comes back as itself
+pub fn t(locale: &str, key: &str) -> String {
+    catalog()
+        .lookup(locale, key)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            tracing::warn!(key, "Missing message catalog key");
+            key.to_string()
+        })
+}
+
+/// Look up `key` and fill its `{name}` placeholders from `args`
+pub fn tf(locale: &str, key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(t(locale, key), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Look up the `<key>_one` or `<key>_other` form for `count`, filling `{count}` and `args`
+pub fn tn(locale: &str, key: &str, count: usize, args: &[(&str, &str)]) -> String {
+    let key = if count == 1 {
+        format!("{}_one", key)
+    } else {
+        format!("{}_other", key)
+    };
+    let count = count.to_string();
+    tf(locale, &key, args).replace("{count}", &count)
+}
+
+/// Locale for a user's DMs: their own choice, else the default
+pub fn user_locale(session_store: &SessionStore, user_id: &str) -> String {
+    session_store
+        .get_user_locale(user_id)
+        .ok()
+        .flatten()
+        .unwrap_or_else(default_locale)
+}
+
+/// Locale for a channel: the channel's choice, then the user's, then the default
+pub fn channel_locale(session_store: &SessionStore, channel_name: &str, user_id: &str) -> String {
+    match session_store.get_channel_locale(channel_name) {
+        Ok(Some(locale)) => locale,
+        _ => user_locale(session_store, user_id),
+    }
+}
+
+/// Catalog key for the title of an agent error
+pub fn error_title_key(code: gorp_agent::ErrorCode) -> &'static str {
+    use gorp_agent::ErrorCode;
+    match code {
+        ErrorCode::Timeout => "error.timeout",
+        ErrorCode::RateLimited => "error.rate_limited",
+        ErrorCode::AuthFailed => "error.auth_failed",
+        ErrorCode::SessionOrphaned => "error.session_orphaned",
+        ErrorCode::ToolFailed => "error.tool_failed",
+        ErrorCode::PermissionDenied => "error.permission_denied",
+        ErrorCode::BackendError => "error.backend_error",
+        ErrorCode::Unknown => "error.unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_catalogs_parse_and_are_complete() {
+        let catalog = Catalog::builtin();
+        assert!(catalog.languages().contains(&"en"));
+        for (lang, missing) in catalog.missing_keys() {
+            assert!(missing.is_empty(), "{} is missing {:?}", lang, missing);
+        }
+    }
+
+    #[test]
+    fn test_lookup_falls_back_to_base_language_then_english() {
+        let mut catalog = Catalog::default();
+        catalog
+            .merge("en", "[greet]\nhello = \"Hello\"\nbye = \"Bye\"")
+            .unwrap();
+        catalog.merge("pt", "[greet]\nhello = \"Olá\"").unwrap();
+
+        assert_eq!(catalog.lookup("pt-BR", "greet.hello"), Some("Olá"));
+        assert_eq!(catalog.lookup("pt_BR", "greet.hello"), Some("Olá"));
+        assert_eq!(catalog.lookup("pt", "greet.bye"), Some("Bye"));
+        assert_eq!(catalog.lookup("fr", "greet.hello"), Some("Hello"));
+        assert_eq!(catalog.lookup("en", "greet.missing"), None);
+        assert!(catalog.has_language("pt-BR"));
+        assert!(!catalog.has_language("fr"));
+        assert_eq!(
+            catalog.missing_keys().get("pt"),
+            Some(&vec!["greet.bye".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_tf_fills_placeholders() {
+        assert_eq!(
+            tf("en", "schedule.deleted", &[("prompt", "water plants")]),
+            "🗑️ Deleted schedule: water plants"
+        );
+        assert_eq!(
+            tf("es", "schedule.deleted", &[("prompt", "regar")]),
+            "🗑️ Programación eliminada: regar"
+        );
+        // Unknown keys are shown as-is rather than failing
+        assert_eq!(t("en", "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_tn_picks_the_plural_form() {
+        let args = [("channel", "ops")];
+        assert_eq!(
+            tn("en", "cache.cleared", 1, &args),
+            "♻️ Dropped 1 cached answer in ops."
+        );
+        assert_eq!(
+            tn("en", "cache.cleared", 3, &args),
+            "♻️ Dropped 3 cached answers in ops."
+        );
+        assert_eq!(
+            tn("es", "cache.cleared", 3, &args),
+            "♻️ Se descartaron 3 respuestas guardadas en ops."
+        );
+    }
+
+    #[test]
+    fn test_catalog_dir_overrides_and_adds_languages() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("en.toml"),
+            "[schedule]\ndeleted = \"Gone: {prompt}\"",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("de.toml"),
+            "[locale]\nset = \"Sprache: {locale}\"",
+        )
+        .unwrap();
+
+        let catalog = Catalog::load(Some(tmp.path())).unwrap();
+        assert_eq!(
+            catalog.lookup("en", "schedule.deleted"),
+            Some("Gone: {prompt}")
+        );
+        assert_eq!(
+            catalog.lookup("de", "locale.set"),
+            Some("Sprache: {locale}")
+        );
+        // Keys the override doesn't mention keep their built-in text
+        assert_eq!(
+            catalog.lookup("de", "schedule.paused"),
+            Some("⏸️ Paused schedule: {prompt}")
+        );
+        assert!(!catalog.missing_keys()["de"].is_empty());
+    }
+
+    /// Every key passed to t()/tf() in the source tree must exist in the English catalog,
+    /// and both plural forms of every key passed to tn()
+    #[test]
+    fn test_referenced_keys_exist_in_english_catalog() {
+        let catalog = Catalog::builtin();
+        let call = Regex::new(r#"(?s)\bt[f]?\(\s*[^,()"]+,\s*"([a-z0-9_.]+)""#).unwrap();
+        let title = Regex::new(r#"=> "(error\.[a-z_]+)""#).unwrap();
+        let plural = Regex::new(r#"(?s)\btn\(\s*[^,()"]+,\s*"([a-z0-9_.]+)""#).unwrap();
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+        let mut pending = vec![src];
+        let mut checked = 0;
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                if path.extension().is_none_or(|ext| ext != "rs") {
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                for caps in call
+                    .captures_iter(&source)
+                    .chain(title.captures_iter(&source))
+                {
+                    let key = &caps[1];
+                    if key == "no.such.key" || key.starts_with("greet.") {
+                        continue;
+                    }
+                    assert!(
+                        catalog.lookup(FALLBACK_LOCALE, key).is_some(),
+                        "{} references unknown catalog key {}",
+                        path.display(),
+                        key
+                    );
+                    checked += 1;
+                }
+                for caps in plural.captures_iter(&source) {
+                    for form in ["one", "other"] {
+                        let key = format!("{}_{}", &caps[1], form);
+                        assert!(
+                            catalog.lookup(FALLBACK_LOCALE, &key).is_some(),
+                            "{} references unknown catalog key {}",
+                            path.display(),
+                            key
+                        );
+                        checked += 1;
+                    }
+                }
+            }
+        }
+        assert!(checked > 20, "scan found only {} keys", checked);
+    }
+
+    #[test]
+    fn test_locale_resolution_prefers_channel_then_user() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+        let user = "@ana:example.com";
+        assert_eq!(channel_locale(&store, "ops", user), default_locale());
+
+        store.set_user_locale(user, Some("es")).unwrap();
+        assert_eq!(user_locale(&store, user), "es");
+        assert_eq!(channel_locale(&store, "ops", user), "es");
+
+        store.set_channel_locale("ops", Some("de")).unwrap();
+        assert_eq!(channel_locale(&store, "ops", user), "de");
+    }
+}
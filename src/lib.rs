@@ -32,8 +32,10 @@ pub use platform::matrix as matrix_client;
 #[cfg(feature = "admin")]
 pub mod admin;
 pub mod dispatch_handler;
+pub mod dispatch_history;
 pub mod dispatch_system_prompt;
 pub mod dispatch_tools;
+pub mod i18n;
 pub mod matrix_interface;
 pub mod mcp;
 pub mod message_handler;
@@ -45,20 +47,63 @@ pub mod webhook;
 pub mod scheduler;
 pub mod task_executor;
 
+// Delivery window flushing; core window logic lives in gorp_core::delivery
+pub mod delivery;
+
+// `gorp commands export` output; the command metadata lives in gorp_core::command_catalog
+pub mod command_export;
+
+// Send-guard draft interception and expiry; core draft rules live in gorp_core::drafts
+pub mod drafts;
+
+// Monthly budget runway text and overrun warnings; forecast math lives in gorp_core::usage
+pub mod budget;
+
+// Signal handling and draining of in-flight agent turns for `gorp start`
+pub mod shutdown;
+
+// The bounded queue between platform event handlers and the message handler task
+pub mod message_queue;
+
+// In-chat approval of risky tool calls; risk levels are configured in gorp_core::config
+pub mod approvals;
+
+// `gorp doctor` environment checks
+pub mod doctor;
+
 // Re-export platform-agnostic modules from gorp-core
 pub use gorp_core::config;
+pub use gorp_core::confirmations;
+pub use gorp_core::connection;
+pub use gorp_core::dedup;
+pub use gorp_core::edits;
+pub use gorp_core::git_seed;
+pub use gorp_core::logging;
 pub use gorp_core::metrics;
 pub use gorp_core::paths;
+pub use gorp_core::preprocess;
+pub use gorp_core::rate_limit;
+pub use gorp_core::reconnect;
+pub use gorp_core::redactions;
+pub use gorp_core::relocate;
+pub use gorp_core::rich_response;
+pub use gorp_core::runtime_mode;
 pub use gorp_core::session;
+pub use gorp_core::templates;
+pub use gorp_core::usage;
 pub use gorp_core::utils;
 pub use gorp_core::warm_session;
+pub use gorp_core::webhook_batch;
 
 // Message bus orchestrator (local, DISPATCH command parser + routing)
 pub mod orchestrator;
 
 // Re-export gorp-core traits and types
+pub use gorp_core::command_catalog;
 pub use gorp_core::commands;
 pub use gorp_core::traits;
+pub use gorp_core::transcription;
+pub use gorp_core::typing;
 
 // Re-export gorp-agent types for convenience
 pub use gorp_agent::{AgentEvent, AgentHandle, AgentRegistry};
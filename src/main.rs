@@ -2,18 +2,29 @@
 // ABOUTME: CLI interface with subcommands for start, config, and schedule management
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use futures_util::StreamExt;
 use gorp::{
-    config::Config,
+    approvals::PendingApprovals,
+    command_catalog::CommandCatalog,
+    command_export::{export, ExportFormat},
+    config::{Config, ConsoleFormat, InviteDecision, MatrixConfig, ReconnectConfig},
+    connection::ConnectionTracker,
+    doctor,
     gateway::{registry::GatewayRegistry, GatewayAdapter},
-    matrix_client, message_handler,
+    matrix_client, message_handler, message_queue,
     orchestrator::Orchestrator,
     paths,
     platform::{MatrixPlatform, PlatformRegistry, SharedPlatformRegistry},
+    reconnect::{BackoffConfig, BackoffState},
+    redactions::ResponseTracker,
+    relocate::{relocate_channel, RelocateRequest, Relocation},
+    runtime_mode::{self, Gate, RuntimeMode},
     scheduler::{start_scheduler, SchedulerStore},
     session::SessionStore,
+    shutdown,
     task_executor::start_task_executor,
+    traits::{PlatformConnectionState, Presence},
     warm_session::SharedWarmSessionManager,
     webhook,
 };
@@ -25,11 +36,12 @@ use matrix_sdk::{
         events::room::name::RoomNameEventContent,
         OwnedRoomId, OwnedUserId,
     },
-    Client,
+    Client, LoopCtrl,
 };
 use std::collections::HashSet;
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
+use tokio_util::task::TaskTracker;
 
 /// Startup timestamp - used to filter out historical messages on initial sync
 /// Messages older than this are skipped to prevent processing old backlog
@@ -236,7 +248,12 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Start the Matrix-Claude bridge
-    Start,
+    Start {
+        /// Connect and answer read-only commands, but run no agents, schedules,
+        /// webhooks or notifications until an admin runs !safemode off
+        #[arg(long)]
+        safe_mode: bool,
+    },
     /// Launch the terminal user interface
     Tui,
     /// Configuration management
@@ -259,12 +276,68 @@ enum Commands {
         #[command(subcommand)]
         action: GatewaysAction,
     },
+    /// Message catalog (translation) tools
+    I18n {
+        #[command(subcommand)]
+        action: I18nAction,
+    },
+    /// Chat command reference and shell completions
+    Commands {
+        #[command(subcommand)]
+        action: CommandsAction,
+    },
+    /// Workspace management
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
+    /// Check config, agent binary, workspace, database and webhook port
+    Doctor {
+        /// Also log in to the Matrix homeserver
+        #[arg(long)]
+        matrix_login: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkspaceAction {
+    /// Move channel workspaces to a new root directory
+    Relocate {
+        /// Directory the channel workspaces move into
+        new_root: std::path::PathBuf,
+        /// Only move this channel (default: every channel)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Leave a symlink at each old path pointing at the new one
+        #[arg(long)]
+        tombstone: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommandsAction {
+    /// Print the command reference or a shell completion script
+    Export {
+        /// Output format
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum I18nAction {
+    /// List catalog keys each language is missing compared to English
+    Check,
 }
 
 #[derive(Subcommand)]
 enum RoomsAction {
-    /// Sync all room names to match current prefix
+    /// Sync all room names to match current prefix, add rooms to the channel space,
+    /// and give rooms without a topic their channel topic
     Sync,
+    /// Apply the channel room power levels to existing rooms: only the bot can
+    /// rename them, change their state or kick, and members get member_power_level
+    Harden,
 }
 
 #[derive(Subcommand)]
@@ -273,7 +346,7 @@ enum GatewaysAction {
     List,
     /// Show detailed status for a specific gateway
     Status {
-        /// Platform name (matrix, telegram, slack, whatsapp)
+        /// Platform name (matrix, telegram, slack, whatsapp, irc, zulip)
         platform: String,
     },
 }
@@ -323,11 +396,13 @@ fn is_valid_recovery_key_format(key: &str) -> bool {
 
 /// Announce startup to the management room
 /// This lets humans know when bots come online
-async fn announce_startup_to_management(client: &Client) {
+async fn announce_startup_to_management(
+    client: &Client,
+    management_room: &str,
+    runtime: &RuntimeMode,
+) {
     use matrix_sdk::ruma::events::room::message::RoomMessageEventContent;
 
-    const MANAGEMENT_ROOM_ID: &str = "!llllhqZbfveDbueMJZ:matrix.org";
-
     let timestamp = chrono::Utc::now()
         .format("%Y-%m-%d %H:%M:%S UTC")
         .to_string();
@@ -338,13 +413,17 @@ async fn announce_startup_to_management(client: &Client) {
         .map(|id| id.to_string())
         .unwrap_or_else(|| "unknown".to_string());
 
-    let message = format!(
+    let mut message = format!(
         "🤖 **Reporting for service**\n\nBot: `{}`\nTime: {}",
         bot_id, timestamp
     );
+    if runtime.is_safe() {
+        message.push_str("\n\n");
+        message.push_str(runtime_mode::BANNER);
+    }
 
     // Parse the management room ID
-    let room_id: matrix_sdk::ruma::OwnedRoomId = match MANAGEMENT_ROOM_ID.parse() {
+    let room_id: matrix_sdk::ruma::OwnedRoomId = match management_room.parse() {
         Ok(id) => id,
         Err(e) => {
             tracing::warn!(error = %e, "Invalid management room ID");
@@ -357,10 +436,7 @@ async fn announce_startup_to_management(client: &Client) {
         Some(r) if r.state() == matrix_sdk::RoomState::Joined => r,
         Some(r) if r.state() == matrix_sdk::RoomState::Invited => {
             // We have an invite, accept it
-            tracing::info!(
-                "Accepting invite to management room: {}",
-                MANAGEMENT_ROOM_ID
-            );
+            tracing::info!("Accepting invite to management room: {}", management_room);
             match r.join().await {
                 Ok(_) => {
                     // Need to get the room again after joining
@@ -380,7 +456,7 @@ async fn announce_startup_to_management(client: &Client) {
         }
         _ => {
             // Try to join the room by ID
-            tracing::info!("Attempting to join management room: {}", MANAGEMENT_ROOM_ID);
+            tracing::info!("Attempting to join management room: {}", management_room);
             match client.join_room_by_id(&room_id).await {
                 Ok(r) => r,
                 Err(e) => {
@@ -753,10 +829,10 @@ async fn main() -> Result<()> {
             #[cfg(not(feature = "gui"))]
             {
                 // Fall through to headless start when GUI is not compiled
-                run_start().await
+                run_start(false).await
             }
         }
-        Some(Commands::Start) => run_start().await,
+        Some(Commands::Start { safe_mode }) => run_start(safe_mode).await,
         Some(Commands::Tui) => {
             #[cfg(feature = "tui")]
             {
@@ -772,6 +848,164 @@ async fn main() -> Result<()> {
         Some(Commands::Schedule { action }) => run_schedule(action),
         Some(Commands::Rooms { action }) => run_rooms(action).await,
         Some(Commands::Gateways { action }) => run_gateways(action),
+        Some(Commands::I18n { action }) => run_i18n(action),
+        Some(Commands::Commands { action }) => run_commands(action),
+        Some(Commands::Workspace { action }) => run_workspace(action).await,
+        Some(Commands::Doctor { matrix_login }) => run_doctor(matrix_login).await,
+    }
+}
+
+/// Run every environment check and exit non-zero if any failed
+async fn run_doctor(matrix_login: bool) -> Result<()> {
+    dotenvy::dotenv().ok();
+    println!("Config file: {}\n", paths::config_file().display());
+    let checks = doctor::run_checks(Config::load(), matrix_login).await;
+    print!("{}", doctor::render(&checks));
+    if doctor::has_failures(&checks) {
+        eprintln!("\nSome checks failed.");
+        std::process::exit(1);
+    }
+    println!("\nAll critical checks passed.");
+    Ok(())
+}
+
+/// Handle workspace subcommands
+async fn run_workspace(action: WorkspaceAction) -> Result<()> {
+    dotenvy::dotenv().ok();
+    let config = Config::load()?;
+    let session_store = SessionStore::new(&config.workspace.path)?
+        .with_channel_scope(config.workspace.channel_scope);
+
+    match action {
+        WorkspaceAction::Relocate {
+            new_root,
+            channel,
+            tombstone,
+        } => {
+            let channels = match channel {
+                Some(name) => vec![session_store
+                    .get_by_name(&name)?
+                    .with_context(|| format!("Channel not found: {}", name))?],
+                None => session_store
+                    .list_all()?
+                    .into_iter()
+                    .filter(|c| !c.is_dispatch_room && !c.directory.is_empty())
+                    .collect(),
+            };
+            if channels.is_empty() {
+                println!("No channels to move.");
+                return Ok(());
+            }
+            let request = RelocateRequest {
+                new_root: std::path::absolute(&new_root)?,
+                tombstone,
+            };
+
+            // A running bot has to do the move itself so it can drain and pause the channel
+            let admin_url = format!("http://127.0.0.1:{}/admin", config.webhook.port);
+            let live = tokio::net::TcpStream::connect(("127.0.0.1", config.webhook.port))
+                .await
+                .is_ok();
+            if live {
+                println!("Relocating through the running instance at {}", admin_url);
+            }
+
+            let mut failed = 0;
+            for channel in &channels {
+                let result = if live {
+                    relocate_via_admin_api(&admin_url, &channel.channel_name, &request).await
+                } else {
+                    relocate_channel(&session_store, channel, &request)
+                };
+                match result {
+                    Ok(moved) => println!(
+                        "✓ {}: {} → {} ({} files, {} bytes, {} stored paths updated{})",
+                        moved.channel_name,
+                        moved.from.display(),
+                        moved.to.display(),
+                        moved.files,
+                        moved.bytes,
+                        moved.rewritten,
+                        if moved.tombstone {
+                            ", tombstone left"
+                        } else {
+                            ""
+                        }
+                    ),
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!(
+                            "✗ {}: {:#}\n  Still at {}",
+                            channel.channel_name, e, channel.directory
+                        );
+                    }
+                }
+            }
+
+            if failed > 0 {
+                eprintln!("\n{} of {} channel(s) not moved.", failed, channels.len());
+                std::process::exit(1);
+            }
+            println!(
+                "\nDone. Set workspace.path to {} in your config to create new channels there too.",
+                request.new_root.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Ask the running instance to move a channel, authenticating with its API token
+#[cfg(feature = "admin")]
+async fn relocate_via_admin_api(
+    admin_url: &str,
+    channel_name: &str,
+    request: &RelocateRequest,
+) -> Result<Relocation> {
+    let data_dir = paths::data_dir();
+    let auth = gorp::admin::AuthConfig::load(&data_dir.to_string_lossy())?
+        .context("The running instance has no admin API token yet; finish setup at /setup first")?;
+
+    // Redirects mean setup or login pages, never a relocation result
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+    let response = client
+        .post(format!(
+            "{}/api/channels/{}/relocate",
+            admin_url, channel_name
+        ))
+        .header("X-API-Key", &auth.api_token)
+        .json(request)
+        .send()
+        .await
+        .context("Failed to reach the running instance")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Admin API returned {}: {}", status, body.trim());
+    }
+    Ok(response.json().await?)
+}
+
+#[cfg(not(feature = "admin"))]
+async fn relocate_via_admin_api(
+    _admin_url: &str,
+    _channel_name: &str,
+    _request: &RelocateRequest,
+) -> Result<Relocation> {
+    anyhow::bail!("gorp is running but this build has no admin API; stop it and retry")
+}
+
+/// Handle commands subcommands
+fn run_commands(action: CommandsAction) -> Result<()> {
+    match action {
+        CommandsAction::Export { format } => {
+            let catalog = CommandCatalog::builtin();
+            print!("{}", export(&catalog, &Cli::command(), format)?);
+            Ok(())
+        }
     }
 }
 
@@ -887,11 +1121,54 @@ fn run_config(action: ConfigAction) -> Result<()> {
     }
 }
 
+/// Handle i18n subcommands
+fn run_i18n(action: I18nAction) -> Result<()> {
+    dotenvy::dotenv().ok();
+    // Without a config there are no catalog overrides, but the built-ins can still be checked
+    let catalog_dir = Config::load()
+        .ok()
+        .and_then(|config| config.i18n.catalog_dir)
+        .map(std::path::PathBuf::from);
+
+    match action {
+        I18nAction::Check => {
+            if let Some(ref dir) = catalog_dir {
+                println!("Catalog dir: {}", dir.display());
+            }
+            let catalog = gorp::i18n::Catalog::load(catalog_dir.as_deref())?;
+            let missing = catalog.missing_keys();
+            let total: usize = missing.values().map(Vec::len).sum();
+
+            for (lang, keys) in &missing {
+                if keys.is_empty() {
+                    println!("✓ {} complete", lang);
+                    continue;
+                }
+                println!("✗ {} missing {} key(s):", lang, keys.len());
+                for key in keys {
+                    println!("    {}", key);
+                }
+            }
+
+            if total > 0 {
+                eprintln!(
+                    "\n{} missing translation(s); English text is shown for them.",
+                    total
+                );
+                std::process::exit(1);
+            }
+            println!("\nAll catalogs cover every English key.");
+            Ok(())
+        }
+    }
+}
+
 /// Handle schedule subcommands
 fn run_schedule(action: ScheduleAction) -> Result<()> {
     dotenvy::dotenv().ok();
     let config = Config::load()?;
-    let session_store = SessionStore::new(&config.workspace.path)?;
+    let session_store = SessionStore::new(&config.workspace.path)?
+        .with_channel_scope(config.workspace.channel_scope);
     let scheduler_store = SchedulerStore::new(session_store.db_connection());
     scheduler_store.initialize_schema()?;
 
@@ -962,7 +1239,8 @@ fn run_schedule(action: ScheduleAction) -> Result<()> {
 async fn run_rooms(action: RoomsAction) -> Result<()> {
     dotenvy::dotenv().ok();
     let config = Config::load()?;
-    let session_store = SessionStore::new(&config.workspace.path)?;
+    let session_store = SessionStore::new(&config.workspace.path)?
+        .with_channel_scope(config.workspace.channel_scope);
 
     match action {
         RoomsAction::Sync => {
@@ -973,33 +1251,15 @@ async fn run_rooms(action: RoomsAction) -> Result<()> {
             );
 
             // Need to login to Matrix to rename rooms
-            let client = matrix_client::create_client(
-                &matrix.home_server,
-                &matrix.user_id,
-                &matrix.device_name,
-            )
-            .await?;
-
-            matrix_client::login(
-                &client,
-                &matrix.user_id,
-                matrix.password.as_deref(),
-                matrix.access_token.as_deref(),
-                &matrix.device_name,
-            )
-            .await?;
-
-            // Do initial sync to get room list
-            print!("Syncing with server... ");
-            client
-                .sync_once(SyncSettings::default())
-                .await
-                .context("Initial sync failed")?;
-            println!("done.");
+            let client = connect_for_rooms(matrix).await?;
 
             // Get all channels and rename their rooms
             let channels = session_store.list_all()?;
             let prefix = &matrix.room_prefix;
+            let space = matrix_client::space::resolve(&client, matrix, &session_store).await?;
+            if let Some(space) = &space {
+                println!("Adding channel rooms to space {}", space);
+            }
 
             for channel in &channels {
                 let room_id: OwnedRoomId = match channel.room_id.parse() {
@@ -1015,6 +1275,25 @@ async fn run_rooms(action: RoomsAction) -> Result<()> {
                     continue;
                 };
 
+                // Adopt rooms created before the space existed
+                if let Some(space) = &space {
+                    if let Err(e) = matrix_client::space::add_child(&client, space, &room_id).await
+                    {
+                        println!("  ✗ {}: couldn't add to space: {}", channel.channel_name, e);
+                    }
+                }
+
+                // Rooms created before channels got topics get theirs now
+                if room.topic().unwrap_or_default().is_empty() {
+                    let result = match matrix_client::channel_topic(&session_store, channel) {
+                        Ok(topic) => matrix_client::set_topic(&client, &room_id, &topic).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        println!("  ✗ {}: couldn't set topic: {}", channel.channel_name, e);
+                    }
+                }
+
                 let new_name = format!("{}: {}", prefix, channel.channel_name);
                 let current_name = room.name().unwrap_or_default();
 
@@ -1042,11 +1321,74 @@ async fn run_rooms(action: RoomsAction) -> Result<()> {
             println!("\nDone. Renamed {} room(s).", channels.len());
             Ok(())
         }
+        RoomsAction::Harden => {
+            let matrix = config.matrix_config()?;
+            println!(
+                "Hardening room power levels (members at {})",
+                matrix.member_power_level
+            );
+            let client = connect_for_rooms(matrix).await?;
+
+            let mut hardened = 0;
+            for channel in session_store.list_on("matrix")? {
+                let room_id: OwnedRoomId = match channel.room_id.parse() {
+                    Ok(id) => id,
+                    Err(_) => {
+                        println!("  ✗ {}: invalid room ID", channel.channel_name);
+                        continue;
+                    }
+                };
+                match matrix_client::harden_room(&client, &room_id, matrix.member_power_level).await
+                {
+                    Ok(untouchable) if untouchable.is_empty() => {
+                        hardened += 1;
+                        println!("  ✓ {}", channel.channel_name);
+                    }
+                    Ok(untouchable) => {
+                        hardened += 1;
+                        let names: Vec<_> = untouchable.iter().map(|u| u.as_str()).collect();
+                        println!(
+                            "  ⚠ {}: {} still at the bot's level; only they can lower it",
+                            channel.channel_name,
+                            names.join(", ")
+                        );
+                    }
+                    Err(e) => println!("  ✗ {}: {:#}", channel.channel_name, e),
+                }
+            }
+            println!("\nDone. Hardened {} room(s).", hardened);
+            Ok(())
+        }
     }
 }
 
+/// Log in as the bot and sync once so the rooms it is in are known
+async fn connect_for_rooms(matrix: &MatrixConfig) -> Result<Client> {
+    let client =
+        matrix_client::create_client(&matrix.home_server, &matrix.user_id, &matrix.device_name)
+            .await?;
+
+    matrix_client::login(
+        &client,
+        &matrix.user_id,
+        matrix.password.as_deref(),
+        matrix.access_token.as_deref(),
+        &matrix.device_name,
+    )
+    .await?;
+
+    // Do initial sync to get room list
+    print!("Syncing with server... ");
+    client
+        .sync_once(SyncSettings::default())
+        .await
+        .context("Initial sync failed")?;
+    println!("done.");
+    Ok(client)
+}
+
 /// Known platform identifiers
-const PLATFORM_IDS: &[&str] = &["matrix", "telegram", "slack", "whatsapp"];
+const PLATFORM_IDS: &[&str] = &["matrix", "telegram", "slack", "whatsapp", "irc", "zulip"];
 
 /// Handle gateways subcommands
 fn run_gateways(action: GatewaysAction) -> Result<()> {
@@ -1121,6 +1463,25 @@ fn run_gateways(action: GatewaysAction) -> Result<()> {
                         println!("\n  Hot-connect:   no (uses sidecar process)");
                     }
                 }
+                "irc" => {
+                    if let Some(ref i) = config.irc {
+                        println!("\n  Server:        {}:{}{}", i.server, i.port, if i.use_tls { " (TLS)" } else { "" });
+                        println!("  Nickname:      {}", i.nickname);
+                        println!("  Channels:      {}", i.channels.join(", "));
+                        println!("  Allowed users: {}", i.allowed_users.len());
+                        println!("  SASL:          {}", if i.sasl_password.is_some() { "set (redacted)" } else { "not set" });
+                        println!("  Hot-connect:   yes");
+                    }
+                }
+                "zulip" => {
+                    if let Some(ref z) = config.zulip {
+                        println!("\n  Site:          {}", z.site);
+                        println!("  Bot email:     {}", z.bot_email);
+                        println!("  Allowed users: {}", z.allowed_users.len());
+                        println!("  Streams:       {}", if z.allowed_streams.is_empty() { "all subscribed".to_string() } else { z.allowed_streams.join(", ") });
+                        println!("  Hot-connect:   yes");
+                    }
+                }
                 _ => {}
             }
 
@@ -1153,12 +1514,98 @@ fn platform_config_status(config: &Config, platform_id: &str) -> (bool, String)
             Some(_) => (true, "sidecar mode".to_string()),
             None => (false, "not configured".to_string()),
         },
+        "irc" => match &config.irc {
+            Some(i) => (true, format!("{}@{}", i.nickname, i.server)),
+            None => (false, "not configured".to_string()),
+        },
+        "zulip" => match &config.zulip {
+            Some(z) => (true, if z.api_key.is_empty() { "API key missing".to_string() } else { z.bot_email.clone() }),
+            None => (false, "not configured".to_string()),
+        },
         _ => (false, "unknown platform".to_string()),
     }
 }
 
+/// Console layer for `gorp start`, filtered to warn+ (info for gorp itself).
+/// Noisy SDK warnings are suppressed here; the file layer still has them.
+fn console_layer<S, W>(format: ConsoleFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    W: for<'writer> fmt::MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let filter = tracing_subscriber::EnvFilter::new(
+        "warn,gorp=info,matrix_sdk_crypto=error,matrix_sdk::encryption=error",
+    );
+    match format {
+        ConsoleFormat::Pretty => fmt::layer()
+            .pretty()
+            .with_target(true)
+            .with_writer(writer)
+            .with_filter(filter)
+            .boxed(),
+        ConsoleFormat::Json => fmt::layer()
+            .json()
+            .with_target(true)
+            .with_writer(writer)
+            .with_filter(filter)
+            .boxed(),
+    }
+}
+
+#[cfg(test)]
+mod console_layer_tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Collects everything a layer writes
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn console_output(format: ConsoleFormat) -> String {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber =
+            tracing_subscriber::registry().with(console_layer(format, move || writer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(room_id = "!ops:example.com", "Sync failed");
+            tracing::debug!("Below the console level");
+        });
+        let bytes = captured.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_json_console_writes_one_object_per_line() {
+        let output = console_output(ConsoleFormat::Json);
+        assert_eq!(output.lines().count(), 1);
+        let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["fields"]["message"], "Sync failed");
+        assert_eq!(line["fields"]["room_id"], "!ops:example.com");
+    }
+
+    #[test]
+    fn test_pretty_console_is_not_json() {
+        let output = console_output(ConsoleFormat::Pretty);
+        assert!(output.contains("Sync failed"));
+        assert!(!output.contains("Below the console level"));
+        assert!(serde_json::from_str::<serde_json::Value>(output.trim()).is_err());
+    }
+}
+
 /// Start the Matrix-Claude bridge
-async fn run_start() -> Result<()> {
+async fn run_start(safe_mode: bool) -> Result<()> {
     // Set up panic hook to log panics before they crash the process
     std::panic::set_hook(Box::new(|panic_info| {
         eprintln!("\n╔══════════════════════════════════════════════════════════╗");
@@ -1169,13 +1616,18 @@ async fn run_start() -> Result<()> {
         eprintln!("{:?}", std::backtrace::Backtrace::force_capture());
     }));
 
-    // Initialize dual logging: JSON file (debug) + pretty console (warn+)
+    // Load configuration first: [logging] decides how the console layer formats
+    dotenvy::dotenv().ok();
+    let mut config = Config::load()?;
+    config.runtime.safe_mode |= safe_mode;
+
+    // Initialize dual logging: JSON file (debug) + console (warn+, pretty or JSON)
     let log_dir = paths::log_dir();
     std::fs::create_dir_all(&log_dir).expect("Failed to create log directory");
 
     // File appender for JSON logs (rotates daily)
     let file_appender = tracing_appender::rolling::daily(&log_dir, "debug.log");
-    let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
+    let (non_blocking, log_guard) = tracing_appender::non_blocking(file_appender);
 
     // JSON file layer - captures everything at debug level
     let file_layer = fmt::layer()
@@ -1188,22 +1640,17 @@ async fn run_start() -> Result<()> {
             }),
         );
 
-    // Console layer - pretty output filtered to warn+
-    // Suppress noisy SDK warnings (still logged to file)
-    let console_layer =
-        fmt::layer()
-            .pretty()
-            .with_target(true)
-            .with_filter(tracing_subscriber::EnvFilter::new(
-                "warn,gorp=info,matrix_sdk_crypto=error,matrix_sdk::encryption=error",
-            ));
+    let console_layer = console_layer(config.logging.console_format, std::io::stdout);
 
     tracing_subscriber::registry()
         .with(file_layer)
         .with(console_layer)
         .init();
 
-    tracing::info!("Starting gorp - Matrix-Claude Bridge");
+    tracing::info!(
+        console_format = ?config.logging.console_format,
+        "Starting gorp - Matrix-Claude Bridge"
+    );
 
     // Log PATH for debugging agent spawn issues
     if let Ok(path) = std::env::var("PATH") {
@@ -1217,9 +1664,14 @@ async fn run_start() -> Result<()> {
         tracing::error!("No PATH environment variable set!");
     }
 
-    // Load configuration
-    dotenvy::dotenv().ok();
-    let config = Config::load()?;
+    gorp::i18n::init(
+        &config.i18n.default_locale,
+        config
+            .i18n
+            .catalog_dir
+            .as_ref()
+            .map(std::path::PathBuf::from),
+    )?;
 
     if let Some(ref matrix) = config.matrix {
         tracing::info!(
@@ -1246,8 +1698,16 @@ async fn run_start() -> Result<()> {
     let session_store_arc = Arc::clone(&server.session_store);
     let scheduler_store = server.scheduler_store.clone();
     let warm_manager = server.warm_manager.clone();
+    let runtime = warm_manager.read().await.runtime_mode();
     let matrix_client = server.matrix_client.clone();
     let sync_token = server.sync_token.clone();
+    let dedup_cache = Arc::clone(&server.dedup);
+    let edit_tracker = Arc::clone(&server.edits);
+    let response_tracker = Arc::clone(&server.responses);
+    let rate_limiter = Arc::clone(&server.rate_limiter);
+    let transcriber = server.transcriber.clone();
+    // Tool calls waiting for a yes or no in a Matrix room
+    let pending_approvals = PendingApprovals::new();
 
     // ── Message Bus Orchestrator ──────────────────────────────────
     // The orchestrator consumes inbound bus messages and routes them to agent
@@ -1273,8 +1733,12 @@ async fn run_start() -> Result<()> {
     #[cfg(feature = "matrix")]
     if let Some(ref client) = matrix_client {
         if let Some(ref matrix_cfg) = config_arc.matrix {
-            use gorp::gateway::matrix::MatrixAdapter;
-            let matrix_adapter = MatrixAdapter::new(client.clone(), matrix_cfg.clone());
+            use gorp::{gateway::matrix::MatrixAdapter, platform::matrix::signing::Signer};
+            let matrix_adapter = MatrixAdapter::new(
+                client.clone(),
+                matrix_cfg.clone(),
+                Signer::from_config(&config_arc.signing),
+            );
             if let Err(e) = matrix_adapter.start(Arc::clone(&server.bus)).await {
                 tracing::error!(error = %e, "Failed to start Matrix gateway adapter");
             } else {
@@ -1328,37 +1792,31 @@ async fn run_start() -> Result<()> {
         "Gateway adapter registry initialized"
     );
 
-    // Wire graceful shutdown for gateway adapters
-    let shutdown_gw_registry = Arc::new(tokio::sync::RwLock::new(gateway_registry));
-    {
-        let gw_reg = Arc::clone(&shutdown_gw_registry);
-        tokio::spawn(async move {
-            match tokio::signal::ctrl_c().await {
-                Ok(()) => {
-                    tracing::info!("Shutting down gateway adapters...");
-                    gw_reg.write().await.shutdown_all().await;
-                    tracing::info!("All gateway adapters shut down");
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "Failed to listen for gateway shutdown signal");
-                }
-            }
-        });
-    }
-
     // ── Platform Registry ────────────────────────────────────────
     // Conditionally initialize platforms based on config and register them.
     let mut registry = PlatformRegistry::new();
 
+    // The Matrix sync loop runs out here, so it reports the platform's state itself
+    let mut matrix_connection: Option<ConnectionTracker> = None;
     if let Some(ref client) = matrix_client {
         let matrix_platform = MatrixPlatform::new(client.clone());
+        matrix_connection = Some(matrix_platform.connection_tracker());
         registry.register(Box::new(matrix_platform));
         tracing::info!("Matrix platform registered");
     }
 
     #[cfg(feature = "telegram")]
     if let Some(ref tg_config) = config_arc.telegram {
-        match gorp::platform::TelegramPlatform::new(tg_config.clone()).await {
+        // The platform drops senders outside a non-empty allowlist before the
+        // gate sees them, so it has to know about [access] users too
+        let mut tg_config = tg_config.clone();
+        if !tg_config.allowed_users.is_empty() {
+            let access_ids = config_arc.access_ids_on("telegram");
+            tg_config
+                .allowed_users
+                .extend(access_ids.iter().filter_map(|id| id.parse::<i64>().ok()));
+        }
+        match gorp::platform::TelegramPlatform::new(tg_config).await {
             Ok(telegram_platform) => {
                 registry.register(Box::new(telegram_platform));
                 tracing::info!("Telegram platform registered");
@@ -1377,8 +1835,16 @@ async fn run_start() -> Result<()> {
 
     #[cfg(feature = "slack")]
     if let Some(ref slack_config) = config_arc.slack {
-        match gorp::platform::SlackPlatform::new(slack_config.clone()).await {
+        let mut slack_config = slack_config.clone();
+        if !slack_config.allowed_users.is_empty() {
+            slack_config
+                .allowed_users
+                .extend(config_arc.access_ids_on("slack"));
+        }
+        match gorp::platform::SlackPlatform::new(slack_config).await {
             Ok(slack_platform) => {
+                let slack_platform =
+                    slack_platform.with_scheduler(scheduler_store.clone(), Arc::clone(&config_arc));
                 registry.register(Box::new(slack_platform));
                 tracing::info!("Slack platform registered");
             }
@@ -1394,10 +1860,50 @@ async fn run_start() -> Result<()> {
         tracing::warn!("Slack config present but binary compiled without 'slack' feature");
     }
 
+    #[cfg(feature = "irc")]
+    if let Some(ref irc_config) = config_arc.irc {
+        match gorp::platform::IrcPlatform::new(irc_config.clone()).await {
+            Ok(irc_platform) => {
+                registry.register(Box::new(irc_platform));
+                tracing::info!("IRC platform registered");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to initialize IRC platform");
+                anyhow::bail!("IRC platform initialization failed: {}", e);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "irc"))]
+    if config_arc.irc.is_some() {
+        tracing::warn!("IRC config present but binary compiled without 'irc' feature");
+    }
+
+    #[cfg(feature = "zulip")]
+    if let Some(ref zulip_config) = config_arc.zulip {
+        match gorp::platform::ZulipPlatform::new(zulip_config.clone()).await {
+            Ok(zulip_platform) => {
+                registry.register(Box::new(zulip_platform));
+                tracing::info!("Zulip platform registered");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to initialize Zulip platform");
+                anyhow::bail!("Zulip platform initialization failed: {}", e);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "zulip"))]
+    if config_arc.zulip.is_some() {
+        tracing::warn!("Zulip config present but binary compiled without 'zulip' feature");
+    }
+
     if config_arc.whatsapp.is_some() {
         tracing::warn!("WhatsApp config present but platform not yet implemented");
     }
 
+    #[cfg(feature = "coven")]
+    let mut coven_provider = None;
     #[cfg(feature = "coven")]
     if let Some(ref coven_config) = config_arc.coven {
         let workspace_dir = config_arc.workspace.path.clone();
@@ -1409,16 +1915,12 @@ async fn run_start() -> Result<()> {
         )
         .await
         {
-            Ok(mut coven_provider) => {
-                if let Err(e) = coven_provider.start().await {
+            Ok(mut provider) => {
+                if let Err(e) = provider.start().await {
                     tracing::error!(error = %e, "Failed to start coven provider");
                 } else {
                     tracing::info!("Coven provider started");
-                    // Spawn shutdown watcher for coven
-                    tokio::spawn(async move {
-                        tokio::signal::ctrl_c().await.ok();
-                        coven_provider.shutdown().await;
-                    });
+                    coven_provider = Some(provider);
                 }
             }
             Err(e) => {
@@ -1445,23 +1947,10 @@ async fn run_start() -> Result<()> {
         "Platform registry initialized"
     );
 
-    // Wire graceful shutdown to registry
     let registry: SharedPlatformRegistry =
         Arc::new(tokio::sync::RwLock::new(registry));
-    let shutdown_registry = Arc::clone(&registry);
-    tokio::spawn(async move {
-        match tokio::signal::ctrl_c().await {
-            Ok(()) => {
-                tracing::info!("Received shutdown signal, shutting down platforms...");
-                shutdown_registry.read().await.shutdown().await;
-                tracing::info!("All platforms shut down");
-                std::process::exit(0);
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to listen for ctrl+c signal");
-            }
-        }
-    });
+    // Shown as online from here on; shutdown turns it to unavailable while turns drain
+    registry.read().await.set_presence(Presence::Online).await;
 
     // Start webhook server in background (can run before initial sync)
     let webhook_port = config_arc.webhook.port;
@@ -1469,13 +1958,17 @@ async fn run_start() -> Result<()> {
     let webhook_config_arc = Arc::clone(&config_arc);
     let webhook_registry = Arc::clone(&registry);
     let webhook_bus = Arc::clone(&server.bus);
+    let webhook_warm_manager = warm_manager.clone();
+    let webhook_scheduler_store = scheduler_store.clone();
     tokio::spawn(async move {
         if let Err(e) = webhook::start_webhook_server(
             webhook_port,
             webhook_store,
+            webhook_scheduler_store,
             webhook_bus,
             webhook_config_arc,
             webhook_registry,
+            webhook_warm_manager,
         )
         .await
         {
@@ -1486,7 +1979,7 @@ async fn run_start() -> Result<()> {
     // Clone scheduler_store for message handler before moving into background task
     let scheduler_store_for_handler = scheduler_store.clone();
 
-    // Start scheduler background task (checks every 60 seconds)
+    // Start scheduler background task (sleeps until the next deadline, at most 60 seconds)
     // The scheduler publishes BusMessages to the bus; no longer needs LocalSet
     let scheduler_session_store = (*session_store_arc).clone();
     let scheduler_bus = Arc::clone(&server.bus);
@@ -1504,6 +1997,54 @@ async fn run_start() -> Result<()> {
         .await;
     });
 
+    // Start delivery window flusher (delivers held output once windows open)
+    let delivery_session_store = (*session_store_arc).clone();
+    let delivery_bus = Arc::clone(&server.bus);
+    let delivery_registry = Arc::clone(&registry);
+    let delivery_runtime = runtime.clone();
+    tokio::spawn(async move {
+        gorp::delivery::start_delivery_flusher(
+            delivery_session_store,
+            delivery_bus,
+            delivery_registry,
+            Duration::from_secs(60),
+            delivery_runtime,
+        )
+        .await;
+    });
+
+    // Start budget watcher (warns channels projected to exceed their monthly budget)
+    let budget_session_store = (*session_store_arc).clone();
+    let budget_bus = Arc::clone(&server.bus);
+    let budget_registry = Arc::clone(&registry);
+    let budget_runtime = runtime.clone();
+    tokio::spawn(async move {
+        gorp::budget::start_budget_watcher(
+            budget_session_store,
+            budget_bus,
+            budget_registry,
+            Duration::from_secs(3600),
+            budget_runtime,
+        )
+        .await;
+    });
+
+    // Start send-guard draft sweeper (expires forgotten drafts with a reminder)
+    let drafts_session_store = (*session_store_arc).clone();
+    let drafts_registry = Arc::clone(&registry);
+    let drafts_config = config_arc.send_guard.clone();
+    let drafts_runtime = runtime.clone();
+    tokio::spawn(async move {
+        gorp::drafts::start_draft_sweeper(
+            drafts_session_store,
+            drafts_registry,
+            drafts_config,
+            Duration::from_secs(60),
+            drafts_runtime,
+        )
+        .await;
+    });
+
     // Start task executor for dispatched work
     start_task_executor(
         matrix_client.clone(),
@@ -1596,17 +2137,35 @@ async fn run_start() -> Result<()> {
         // Check if room prefix changed and rename rooms if needed
         check_and_rename_rooms_for_prefix_change(client, &config_arc, &session_store_arc).await;
 
+        // Resolve the space new channel rooms are added to, and how much say people get in them
+        if let Some(matrix) = config_arc.matrix.as_ref() {
+            matrix_client::set_member_power_level(matrix.member_power_level);
+            match matrix_client::space::resolve(client, matrix, &session_store_arc).await {
+                Ok(Some(space)) => {
+                    tracing::info!(%space, "Channel rooms go in this space");
+                    matrix_client::space::set_channel_space(space);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(error = %e, "Couldn't set up the channel space"),
+            }
+        }
+
+        // Upload the avatar new channel rooms start with
+        if let Some(path) = config_arc
+            .matrix
+            .as_ref()
+            .and_then(|m| m.room_avatar.as_deref())
+        {
+            match matrix_client::upload_room_avatar(client, std::path::Path::new(path)).await {
+                Ok(()) => tracing::info!(path, "Uploaded avatar for new channel rooms"),
+                Err(e) => tracing::warn!(error = %e, path, "Couldn't upload the room avatar"),
+            }
+        }
+
         // Create a channel for message events - handlers will send events here
         // A LocalSet task will receive and process them, ensuring spawn_local works
-        let (msg_tx, mut msg_rx) = tokio::sync::mpsc::channel::<(
-            Room,
-            matrix_sdk::ruma::events::room::message::OriginalSyncRoomMessageEvent,
-            Client,
-            Arc<Config>,
-            Arc<SessionStore>,
-            SchedulerStore,
-            SharedWarmSessionManager,
-        )>(256);
+        let (msg_tx, mut msg_rx) =
+            tokio::sync::mpsc::channel::<MessageEvent>(config_arc.runtime.message_queue_capacity);
 
         // NOW register event handlers after encryption is established
         // This prevents handlers from firing before the client is ready
@@ -1616,32 +2175,60 @@ async fn run_start() -> Result<()> {
             &session_store_arc,
             scheduler_store_for_handler,
             warm_manager.clone(),
+            Arc::clone(&response_tracker),
             msg_tx, // Pass the sender to the handler
         );
         tracing::info!("Event handlers registered");
 
         tracing::info!("Bot ready - DM me to create Claude rooms!");
 
-        // Announce startup to management room
-        announce_startup_to_management(client).await;
+        // Announce startup to management room (with the safe mode banner when active)
+        let matrix = config_arc.matrix.as_ref();
+        match matrix.and_then(|m| m.management_room.as_deref()) {
+            Some(room) if matrix.is_some_and(|m| m.announce_startup) => {
+                announce_startup_to_management(client, room, &runtime).await;
+            }
+            Some(_) => tracing::debug!("matrix.announce_startup is off; not announcing startup"),
+            None => tracing::debug!("No matrix.management_room; not announcing startup"),
+        }
 
-        // Notify allowed users that the bot is ready
-        notify_ready(client, &config_arc).await;
+        // Ready and DISPATCH greetings are outbound notifications, so safe mode skips them
+        if runtime.check(Gate::Notification).is_ok() {
+            // Notify allowed users that the bot is ready
+            if matrix.is_some_and(|m| m.notify_ready) {
+                notify_ready(client, &config_arc).await;
+            }
 
-        // Notify DISPATCH channels with contextual status
-        dispatch_startup_notification(client, &session_store_arc).await;
+            // Notify DISPATCH channels with contextual status
+            dispatch_startup_notification(client, &session_store_arc).await;
+        }
 
         // Start continuous sync loop with the sync token from initial sync
         // Use LocalSet because message handlers with ACP client futures are !Send
         let sync_token = sync_token.expect("sync_token must be Some when Matrix client is present");
         let settings = SyncSettings::default().token(sync_token);
         let client = client.clone();
+        let reconnect = config_arc
+            .matrix
+            .as_ref()
+            .map(|matrix| matrix.reconnect.clone())
+            .unwrap_or_default();
         tracing::info!("Starting continuous sync loop with LocalSet");
 
+        // Every spawned handle_message task, so shutdown can wait for agent turns
+        let in_flight = TaskTracker::new();
+        let drain_timeout = Duration::from_secs(config_arc.shutdown.drain_timeout_secs);
+        let (stop_tx, mut stop_rx) = tokio::sync::watch::channel(false);
+        // !clone creates channels on the other registered platforms
+        let handler_registry = Arc::clone(&registry);
+        let handler_bus = Arc::clone(&server.bus);
+        let presence_registry = Arc::clone(&registry);
+
         let local = tokio::task::LocalSet::new();
         local.run_until(async move {
             // Spawn the message handler task inside the LocalSet
             // This ensures spawn_local works correctly
+            let handler_in_flight = in_flight.clone();
             let mut handler_task = tokio::task::spawn_local(async move {
                 tracing::info!("Message handler LocalSet task started");
 
@@ -1649,8 +2236,22 @@ async fn run_start() -> Result<()> {
                 // processing the same Matrix event multiple times (can happen during
                 // sync reconnections or SDK event delivery quirks)
                 let mut deduplicator = EventDeduplicator::new(10000);
-
-                while let Some((room, event, client, config, session_store, scheduler, warm_mgr)) = msg_rx.recv().await {
+                let mut stopping = false;
+
+                loop {
+                    let next = tokio::select! {
+                        next = msg_rx.recv() => next,
+                        _ = stop_rx.changed(), if !stopping => {
+                            // Refuse new events; ones already queued are still handled
+                            msg_rx.close();
+                            stopping = true;
+                            continue;
+                        }
+                    };
+                    let Some((room, event, client, config, session_store, scheduler, warm_mgr)) = next else {
+                        break;
+                    };
+                    gorp::metrics::set_message_queue_depth(msg_rx.len());
                     // Deduplicate by event_id - skip if we've already processed this event
                     let event_id = event.event_id.to_string();
                     if !deduplicator.check_and_mark(&event_id) {
@@ -1663,9 +2264,17 @@ async fn run_start() -> Result<()> {
                     }
 
                     let room_id = room.room_id().to_owned();
+                    let dedup = Arc::clone(&dedup_cache);
+                    let edits = Arc::clone(&edit_tracker);
+                    let responses = Arc::clone(&response_tracker);
+                    let approvals = pending_approvals.clone();
+                    let limiter = Arc::clone(&rate_limiter);
+                    let transcriber = transcriber.clone();
+                    let platforms = Arc::clone(&handler_registry);
+                    let bus = Arc::clone(&handler_bus);
                     tracing::info!(room_id = %room_id, event_id = %event_id, "Spawning concurrent message handler");
                     // Spawn each message handler concurrently instead of awaiting sequentially
-                    tokio::task::spawn_local(async move {
+                    handler_in_flight.spawn_local(async move {
                         tracing::info!(room_id = %room_id, "Processing message concurrently");
                         if let Err(e) = message_handler::handle_message(
                             room,
@@ -1675,6 +2284,14 @@ async fn run_start() -> Result<()> {
                             (*session_store).clone(),
                             scheduler,
                             warm_mgr,
+                            &dedup,
+                            &edits,
+                            &responses,
+                            &approvals,
+                            &limiter,
+                            transcriber.as_deref(),
+                            &platforms,
+                            &bus,
                         )
                         .await
                         {
@@ -1689,48 +2306,142 @@ async fn run_start() -> Result<()> {
             tokio::task::yield_now().await;
             tracing::info!("Handler task spawned, starting sync");
 
-            // Run sync - let the SDK handle reconnection internally
-            // The SDK's sync() is designed to run forever with built-in retry logic.
+            // Run sync - the SDK handles transient errors internally, and
+            // sync_with_backoff restarts it after errors the SDK gives up on.
             // Previously we wrapped this in a 90-second timeout, but that can cause
             // state corruption when cancelled mid-operation, leading to duplicate events.
             // If the handler task exits, we'll exit too.
-            tokio::select! {
-                sync_result = client.sync(settings.clone()) => {
-                    match sync_result {
-                        Ok(_) => {
-                            // Sync completed normally (shouldn't happen, sync is infinite)
-                            tracing::warn!("Matrix sync returned unexpectedly");
-                            Ok(())
-                        }
-                        Err(e) => {
-                            // Sync error - this is fatal, let it propagate
-                            tracing::error!(error = %e, "Matrix sync failed");
-                            Err(e)
-                        }
-                    }
+            let stopped = tokio::select! {
+                sync_result = sync_with_backoff(
+                    &client,
+                    settings.clone(),
+                    &reconnect,
+                    matrix_connection.as_ref(),
+                ) => {
+                    Some(sync_result)
                 }
                 _ = &mut handler_task => {
                     tracing::error!("Message handler task exited unexpectedly");
-                    Err(matrix_sdk::Error::UnknownError(Box::new(std::io::Error::other(
+                    Some(Err(matrix_sdk::Error::UnknownError(Box::new(std::io::Error::other(
                         "Message handler exited"
-                    ))))
+                    )))))
+                }
+                signal = shutdown::signal() => {
+                    if let Err(e) = signal {
+                        tracing::error!(error = %e, "Failed to listen for shutdown signal");
+                    }
+                    None
                 }
+            };
+            if let Some(result) = stopped {
+                return result;
             }
+
+            // Stop taking Matrix events, then let running agent turns finish.
+            // Handlers are spawn_local tasks, so this has to happen inside the LocalSet.
+            tracing::info!("Received shutdown signal, no longer accepting messages");
+            presence_registry.read().await.set_presence(Presence::Unavailable).await;
+            let _ = stop_tx.send(true);
+            let _ = handler_task.await;
+            shutdown::drain(&in_flight, drain_timeout).await;
+            Ok(())
         }).await?;
     } else {
         // ── No Matrix — run headless with webhook/admin only ──
         tracing::info!("No Matrix sync loop — waiting for shutdown signal");
         tracing::info!("Admin panel available at http://localhost:{}/admin", webhook_port);
-        tokio::signal::ctrl_c().await?;
+        shutdown::signal().await?;
         tracing::info!("Shutdown signal received");
+        registry.read().await.set_presence(Presence::Unavailable).await;
+    }
+
+    tracing::info!("Shutting down platforms...");
+    registry.read().await.shutdown().await;
+    gateway_registry.shutdown_all().await;
+    #[cfg(feature = "coven")]
+    if let Some(provider) = coven_provider {
+        provider.shutdown().await;
     }
+    tracing::info!("Shutdown complete");
 
+    // Flush buffered log lines to the file before the process exits
+    drop(log_guard);
     Ok(())
 }
 
-/// Registers all event handlers for the Matrix client.
-/// Type alias for the message event channel
-type MessageEventSender = tokio::sync::mpsc::Sender<(
+/// Run the Matrix sync loop, restarting it with jittered backoff when it fails.
+/// Each sync response marks the platform connected and each failure disconnected.
+/// Only returns if sync ends on its own, which shouldn't happen.
+async fn sync_with_backoff(
+    client: &Client,
+    settings: SyncSettings,
+    reconnect: &ReconnectConfig,
+    connection: Option<&ConnectionTracker>,
+) -> matrix_sdk::Result<()> {
+    let mut backoff = BackoffState::new(BackoffConfig::from(reconnect));
+    let mut settings = settings;
+    loop {
+        let started = std::time::Instant::now();
+        let synced = client.sync_with_callback(settings.clone(), |_| {
+            let connection = connection.cloned();
+            async move {
+                if let Some(connection) = connection {
+                    connection.set(PlatformConnectionState::Connected);
+                }
+                LoopCtrl::Continue
+            }
+        });
+        match synced.await {
+            Ok(_) => {
+                // Sync completed normally (shouldn't happen, sync is infinite)
+                tracing::warn!("Matrix sync returned unexpectedly");
+                return Ok(());
+            }
+            Err(e) => {
+                // A sync that ran for a while before failing is a new outage
+                if started.elapsed() > Duration::from_secs(reconnect.max_delay_secs) {
+                    backoff.record_success();
+                }
+                // Unlimited retries: next_delay always has a delay
+                let delay = backoff.next_delay().unwrap_or_default();
+                tracing::error!(
+                    error = %e,
+                    retry_in_secs = delay.as_secs_f64(),
+                    attempt = backoff.consecutive_failures(),
+                    "Matrix sync failed, restarting"
+                );
+                if let Some(connection) = connection {
+                    connection.set(PlatformConnectionState::Disconnected {
+                        reason: e.to_string(),
+                    });
+                }
+                tokio::time::sleep(delay).await;
+                // Resume from the sync token the client stored, not the initial one
+                settings = SyncSettings::default();
+            }
+        }
+    }
+}
+
+/// Post a notice to the configured admin room, if the bot is in it
+async fn notify_admin_room(client: &Client, admin_room: Option<&str>, text: &str) {
+    let Some(room_id) = admin_room else {
+        return;
+    };
+    let Some(room) = matrix_sdk::ruma::RoomId::parse(room_id)
+        .ok()
+        .and_then(|id| client.get_room(&id))
+    else {
+        tracing::warn!(room_id, "Admin room not found; is the bot a member?");
+        return;
+    };
+    if let Err(e) = room.send(RoomMessageEventContent::text_plain(text)).await {
+        tracing::warn!(error = %e, room_id, "Failed to notify admin room");
+    }
+}
+
+/// A Matrix message queued for the LocalSet handler, with everything needed to handle it
+type MessageEvent = (
     Room,
     matrix_sdk::ruma::events::room::message::OriginalSyncRoomMessageEvent,
     Client,
@@ -1738,8 +2449,37 @@ type MessageEventSender = tokio::sync::mpsc::Sender<(
     Arc<SessionStore>,
     SchedulerStore,
     SharedWarmSessionManager,
-)>;
+);
+
+/// Type alias for the message event channel
+type MessageEventSender = tokio::sync::mpsc::Sender<MessageEvent>;
+
+/// Queue a Matrix message for the handler; see [`message_queue::enqueue`] for
+/// what happens when the queue is full
+async fn enqueue_message_event(tx: &MessageEventSender, message: MessageEvent, wait: Duration) {
+    let room_id = message.0.room_id().to_string();
+    let sender = message.1.sender.to_string();
+    let config = Arc::clone(&message.3);
+    message_queue::enqueue(
+        tx,
+        message,
+        wait,
+        &config,
+        "matrix",
+        &room_id,
+        &sender,
+        |(room, ..)| async move {
+            room.send(RoomMessageEventContent::text_plain(
+                message_queue::QUEUE_FULL_REPLY,
+            ))
+            .await?;
+            anyhow::Ok(())
+        },
+    )
+    .await;
+}
 
+/// Registers all event handlers for the Matrix client.
 /// Called AFTER initial sync to ensure encryption is established before processing events.
 fn register_event_handlers(
     client: &Client,
@@ -1747,19 +2487,22 @@ fn register_event_handlers(
     session_store_arc: &Arc<SessionStore>,
     scheduler_store: SchedulerStore,
     warm_manager: SharedWarmSessionManager,
+    responses: Arc<ResponseTracker>,
     msg_tx: MessageEventSender,
 ) {
     let config_for_invite = Arc::clone(config_arc);
+    let session_store_for_invite = Arc::clone(session_store_arc);
     let config_for_messages = Arc::clone(config_arc);
     let session_store_for_messages = Arc::clone(session_store_arc);
     let warm_manager_for_messages = warm_manager.clone();
 
-    // Auto-join room invites from allowed users
+    // Answer room invites according to matrix.invite_policy
     client.add_event_handler(
         move |ev: matrix_sdk::ruma::events::room::member::StrippedRoomMemberEvent,
               client: Client,
               room: matrix_sdk::room::Room| {
             let config = Arc::clone(&config_for_invite);
+            let session_store = Arc::clone(&session_store_for_invite);
             async move {
                 if ev.state_key != client.user_id().unwrap() {
                     return; // Not an invite for us
@@ -1769,38 +2512,78 @@ fn register_event_handlers(
                     return; // Not an invite
                 }
 
-                // Check if inviter is in allowed_users
-                let allowed_users = config.allowed_users_set();
+                let Some(matrix) = config.matrix.as_ref() else {
+                    return;
+                };
                 let inviter = ev.sender.as_str();
+                let inviter_allowed = config.is_user_allowed("matrix", inviter);
 
-                if allowed_users.contains(inviter) {
-                    tracing::info!(
-                        room_id = %room.room_id(),
-                        inviter = %inviter,
-                        "Auto-joining room invite from allowed user"
-                    );
+                match matrix.invite_policy.decide(inviter_allowed) {
+                    InviteDecision::Join => {
+                        tracing::info!(
+                            room_id = %room.room_id(),
+                            inviter = %inviter,
+                            policy = ?matrix.invite_policy,
+                            "Auto-joining room invite"
+                        );
 
-                    match room.join().await {
-                        Ok(_) => {
-                            tracing::info!(
-                                room_id = %room.room_id(),
-                                "Successfully joined room"
-                            );
+                        match room.join().await {
+                            Ok(_) => {
+                                tracing::info!(
+                                    room_id = %room.room_id(),
+                                    "Successfully joined room"
+                                );
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    error = %e,
+                                    room_id = %room.room_id(),
+                                    "Failed to join room"
+                                );
+                            }
                         }
-                        Err(e) => {
-                            tracing::error!(
-                                error = %e,
-                                room_id = %room.room_id(),
-                                "Failed to join room"
-                            );
+                    }
+                    InviteDecision::Queue => {
+                        let room_name = room.name();
+                        let queued = match session_store.queue_invite(
+                            room.room_id().as_str(),
+                            inviter,
+                            room_name.as_deref(),
+                        ) {
+                            Ok(queued) => queued,
+                            Err(e) => {
+                                tracing::error!(error = %e, room_id = %room.room_id(), "Failed to queue room invite");
+                                return;
+                            }
+                        };
+                        if !queued {
+                            return; // Already waiting; the admin room has been told
                         }
+                        tracing::info!(
+                            room_id = %room.room_id(),
+                            inviter = %inviter,
+                            "Queued room invite for approval"
+                        );
+                        notify_admin_room(
+                            &client,
+                            matrix.admin_room.as_deref(),
+                            &format!(
+                                "📨 {} invited me to {} ({}).\nJoin with !approve {}",
+                                inviter,
+                                room_name.as_deref().unwrap_or("an unnamed room"),
+                                room.room_id(),
+                                room.room_id()
+                            ),
+                        )
+                        .await;
+                    }
+                    InviteDecision::Ignore => {
+                        tracing::warn!(
+                            room_id = %room.room_id(),
+                            inviter = %inviter,
+                            "Ignoring room invite from unauthorized user"
+                        );
                     }
-                } else {
-                    tracing::warn!(
-                        room_id = %room.room_id(),
-                        inviter = %inviter,
-                        "Ignoring room invite from unauthorized user"
-                    );
                 }
             }
         },
@@ -1837,12 +2620,54 @@ fn register_event_handlers(
 
             // Send to LocalSet task for processing (ensures spawn_local context)
             tracing::debug!(room_id = %room.room_id(), "Sending message event to LocalSet handler");
-            if let Err(e) = tx.send((room, original_event, client, config, session_store, scheduler, warm_mgr)).await {
-                tracing::error!(error = %e, "Failed to send message to handler channel");
-            }
+            let wait = Duration::from_secs(config.runtime.queue_full_wait_secs);
+            enqueue_message_event(
+                &tx,
+                (room, original_event, client, config, session_store, scheduler, warm_mgr),
+                wait,
+            )
+            .await;
         }
     });
 
+    // Redacting a prompt redacts the bot's answer to it, so quoted secrets don't linger
+    client.add_event_handler(
+        move |event: matrix_sdk::ruma::events::room::redaction::OriginalSyncRoomRedactionEvent,
+              room: Room,
+              client: Client| {
+            let responses = Arc::clone(&responses);
+            async move {
+                if client.user_id() == Some(&*event.sender) {
+                    return; // Our own redactions, including the ones below
+                }
+                let Some(prompt) = event.content.redacts.as_ref().or(event.redacts.as_ref()) else {
+                    return;
+                };
+                let answers = responses.take("matrix", prompt.as_str());
+                if answers.is_empty() {
+                    return;
+                }
+                tracing::info!(
+                    room_id = %room.room_id(),
+                    prompt = %prompt,
+                    count = answers.len(),
+                    "Prompt redacted, redacting its responses"
+                );
+                for answer in answers {
+                    let Ok(event_id) = answer.parse::<matrix_sdk::ruma::OwnedEventId>() else {
+                        continue;
+                    };
+                    if let Err(e) = room
+                        .redact(&event_id, Some("The prompt this answered was redacted"), None)
+                        .await
+                    {
+                        tracing::warn!(error = %e, event_id = %event_id, "Failed to redact response");
+                    }
+                }
+            }
+        },
+    );
+
     // Register verification event handler with proper error handling
     client.add_event_handler(
         |ev: matrix_sdk::ruma::events::key::verification::request::ToDeviceKeyVerificationRequestEvent,
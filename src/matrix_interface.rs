@@ -68,13 +68,10 @@ impl ChatRoom for MatrixRoom {
                 caption,
             } => {
                 // For attachments, we need to upload to Matrix media server first
-                use matrix_sdk::ruma::events::room::message::{
-                    FileMessageEventContent, MessageType,
-                };
-
                 let content_type: mime_guess::mime::Mime = mime_type
                     .parse()
                     .unwrap_or(mime_guess::mime::APPLICATION_OCTET_STREAM);
+                let size = data.len();
 
                 // Upload the file
                 let response = self
@@ -84,13 +81,12 @@ impl ChatRoom for MatrixRoom {
                     .await
                     .context("Failed to upload attachment")?;
 
-                // Create file message with the MXC URI
-                let body = caption.unwrap_or_else(|| filename.clone());
+                // Images as m.image, anything else as m.file, pointing at the MXC URI
                 let source =
                     matrix_sdk::ruma::events::room::MediaSource::Plain(response.content_uri);
-                let file_content = FileMessageEventContent::new(body, source);
-
-                RoomMessageEventContent::new(MessageType::File(file_content))
+                RoomMessageEventContent::new(crate::platform::matrix::channel::attachment_message(
+                    filename, &mime_type, size, caption, source,
+                ))
             }
         };
 
@@ -9,6 +9,7 @@ use std::sync::Arc;
 use matrix_sdk::Client;
 
 use crate::matrix_client;
+use crate::platform::matrix::signing::Signer;
 use crate::scheduler::{
     parse_time_expression, ParsedSchedule, ScheduleStatus, ScheduledPrompt, SchedulerStore,
 };
@@ -23,6 +24,10 @@ pub struct McpState {
     pub timezone: String,
     pub workspace_path: String,
     pub room_prefix: String,
+    /// Where report_to_management sends reports (`[matrix] management_room`)
+    pub management_room: Option<String>,
+    /// Signs agent reports when `signing.secret` is set
+    pub signer: Option<Signer>,
 }
 
 /// JSON-RPC request structure
@@ -599,7 +604,9 @@ async fn handle_send_attachment(state: &McpState, args: &Value) -> Result<String
         .map_err(|e| format!("Invalid room_id: {}", e))?;
 
     // Get the room (requires Matrix)
-    let matrix_client = state.matrix_client.as_ref()
+    let matrix_client = state
+        .matrix_client
+        .as_ref()
         .ok_or("Matrix not configured — cannot attach files to rooms")?;
     let room = matrix_client
         .get_room(&room_id)
@@ -1011,7 +1018,8 @@ async fn handle_create_channel(state: &McpState, args: &Value) -> Result<String,
             .map_err(|e| format!("Failed to create Matrix room: {}", e))?
     } else {
         // Generate a placeholder room ID for non-Matrix operation
-        format!("!local-{}", uuid::Uuid::new_v4()).parse()
+        format!("!local-{}", uuid::Uuid::new_v4())
+            .parse()
             .map_err(|e| format!("Failed to create placeholder room ID: {}", e))?
     };
 
@@ -1020,6 +1028,10 @@ async fn handle_create_channel(state: &McpState, args: &Value) -> Result<String,
         .session_store
         .create_channel(channel_name, room_id.as_ref())
         .map_err(|e| format!("Failed to create channel: {}", e))?;
+    if let Some(ref matrix_client) = state.matrix_client {
+        matrix_client::apply_channel_topic(matrix_client, &state.session_store, &room_id, &channel)
+            .await;
+    }
 
     // Invite user if specified (only when Matrix is available)
     if let Some(user_id) = invite_user {
@@ -1082,7 +1094,9 @@ async fn handle_invite_to_channel(state: &McpState, args: &Value) -> Result<Stri
         .map_err(|e| format!("Invalid room ID: {}", e))?;
 
     // Invite the user (requires Matrix)
-    let matrix_client = state.matrix_client.as_ref()
+    let matrix_client = state
+        .matrix_client
+        .as_ref()
         .ok_or("Matrix not configured — cannot invite users")?;
     matrix_client::invite_user(matrix_client, &room_id, user_id)
         .await
@@ -1130,7 +1144,9 @@ async fn handle_set_room_avatar(state: &McpState, args: &Value) -> Result<String
         .parse()
         .map_err(|e| format!("Invalid room ID: {}", e))?;
 
-    let matrix_client = state.matrix_client.as_ref()
+    let matrix_client = state
+        .matrix_client
+        .as_ref()
         .ok_or("Matrix not configured — cannot send images to rooms")?;
     let room = matrix_client
         .get_room(&room_id)
@@ -1248,7 +1264,9 @@ async fn handle_set_room_topic(state: &McpState, args: &Value) -> Result<String,
         .parse()
         .map_err(|e| format!("Invalid room ID: {}", e))?;
 
-    let matrix_client = state.matrix_client.as_ref()
+    let matrix_client = state
+        .matrix_client
+        .as_ref()
         .ok_or("Matrix not configured — cannot set room topic")?;
     let room = matrix_client
         .get_room(&room_id)
@@ -1259,6 +1277,10 @@ async fn handle_set_room_topic(state: &McpState, args: &Value) -> Result<String,
     room.send_state_event(content)
         .await
         .map_err(|e| format!("Failed to set room topic: {}", e))?;
+    state
+        .session_store
+        .set_room_topic(&channel.channel_name, Some(topic))
+        .map_err(|e| format!("Topic set but not saved: {}", e))?;
 
     Ok(format!(
         "Set topic for room '{}' to: {}",
@@ -1271,8 +1293,11 @@ async fn handle_set_room_topic(state: &McpState, args: &Value) -> Result<String,
 async fn handle_report_to_management(state: &McpState, args: &Value) -> Result<String, String> {
     use matrix_sdk::ruma::{events::room::message::RoomMessageEventContent, OwnedRoomId, RoomId};
 
-    // Hardcoded management room - this is where all agent reports go
-    const MANAGEMENT_ROOM_ID: &str = "!llllhqZbfveDbueMJZ:matrix.org";
+    // The configured management room is where all agent reports go
+    let management_room = state
+        .management_room
+        .as_deref()
+        .ok_or("No management room configured (matrix.management_room)")?;
 
     let message = args
         .get("message")
@@ -1301,18 +1326,20 @@ async fn handle_report_to_management(state: &McpState, args: &Value) -> Result<S
     };
 
     // Parse the management room ID
-    let room_id: OwnedRoomId = MANAGEMENT_ROOM_ID
+    let room_id: OwnedRoomId = management_room
         .parse()
         .map_err(|e| format!("Invalid management room ID: {}", e))?;
 
     // Try to get the room - if we're not in it, try to join (requires Matrix)
-    let matrix_client = state.matrix_client.as_ref()
+    let matrix_client = state
+        .matrix_client
+        .as_ref()
         .ok_or("Matrix not configured — cannot send management alerts")?;
     let room = match matrix_client.get_room(&room_id) {
         Some(r) => r,
         None => {
             // Try to join the room
-            tracing::info!("Attempting to join management room: {}", MANAGEMENT_ROOM_ID);
+            tracing::info!("Attempting to join management room: {}", management_room);
             let room_id_ref: &RoomId = room_id.as_ref();
             matrix_client
                 .join_room_by_id(room_id_ref)
@@ -1391,7 +1418,11 @@ async fn handle_report_to_management(state: &McpState, args: &Value) -> Result<S
     );
 
     // Send to management room
-    room.send(RoomMessageEventContent::text_html(&plain_text, &html))
+    let content = match &state.signer {
+        Some(signer) => signer.html_event(room.room_id().as_str(), plain_text, html),
+        None => RoomMessageEventContent::text_html(plain_text, html),
+    };
+    room.send(content)
         .await
         .map_err(|e| format!("Failed to send report to management: {}", e))?;
 
@@ -0,0 +1,78 @@
+// ABOUTME: !approvals: whether risky tools in a channel wait for a yes in chat before running.
+// ABOUTME: The level is stored per channel; approvers and the timeout come from [tool_approval].
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    config::{RiskLevel, ToolApprovalConfig},
+    i18n::{t, tf},
+    session::SessionStore,
+};
+
+use super::commands::channel_for_command;
+
+/// `!approvals [on|off|high|medium|low]`: show or change the channel's approval level
+pub(crate) async fn handle_approvals_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    settings: &ToolApprovalConfig,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "approvals", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let arg = args.first().map(|s| s.to_lowercase());
+    let level = match arg.as_deref() {
+        Some("on") => Some(Some(RiskLevel::High)),
+        Some("off") => Some(None),
+        Some(other) => RiskLevel::parse(other).map(Some),
+        None => None,
+    };
+    let reply = match (arg, level) {
+        (None, _) => match session_store.get_approval_level(&ch.channel_name)? {
+            Some(level) => {
+                let who = if settings.approvers.is_empty() {
+                    t(locale, "approvals.anyone")
+                } else {
+                    settings.approvers.join(", ")
+                };
+                tf(
+                    locale,
+                    "approvals.status_on",
+                    &[
+                        ("channel", &ch.channel_name),
+                        ("level", level.as_str()),
+                        ("approvers", &who),
+                        ("timeout", &settings.timeout_secs.to_string()),
+                    ],
+                )
+            }
+            None => tf(
+                locale,
+                "approvals.status_off",
+                &[("channel", &ch.channel_name)],
+            ),
+        },
+        (Some(_), Some(None)) => {
+            session_store.set_approval_level(&ch.channel_name, None)?;
+            tf(locale, "approvals.off", &[("channel", &ch.channel_name)])
+        }
+        (Some(_), Some(Some(level))) => {
+            session_store.set_approval_level(&ch.channel_name, Some(level))?;
+            tracing::info!(channel = %ch.channel_name, level = level.as_str(), sender, "Tool approval enabled");
+            tf(
+                locale,
+                "approvals.on",
+                &[("level", level.as_str()), ("channel", &ch.channel_name)],
+            )
+        }
+        (Some(_), None) => t(locale, "approvals.usage"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
@@ -0,0 +1,71 @@
+// ABOUTME: Housekeeping run on a channel just before it is archived with !delete.
+// ABOUTME: Optionally has the agent recap the conversation into .gorp/archive-summary.md.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::{
+    config::MaintenanceConfig,
+    session::{Channel, SessionStore},
+    usage::InvocationOrigin,
+    warm_session::SharedWarmSessionManager,
+};
+
+/// Prompt for the final recap; the reply is saved as-is
+pub const ARCHIVE_SUMMARY_PROMPT: &str = "This channel is being archived. Write a final summary \
+of our conversation for someone picking it up later: what we worked on, decisions made, \
+where things were left and any open questions. Reply with the summary only, in Markdown.";
+
+pub fn archive_summary_path(channel_dir: &str) -> PathBuf {
+    Path::new(channel_dir)
+        .join(".gorp")
+        .join("archive-summary.md")
+}
+
+/// Write the archive summary for `channel` if `summarize_on_archive` is on.
+///
+/// Returns where the summary was saved, or None when there was nothing to do
+/// (option off, or the channel never had a conversation).
+pub async fn summarize_before_archive(
+    config: &MaintenanceConfig,
+    channel: &Channel,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+) -> Result<Option<PathBuf>> {
+    if !config.summarize_on_archive || !channel.started {
+        return Ok(None);
+    }
+
+    let summary = super::handle_text(
+        ARCHIVE_SUMMARY_PROMPT,
+        channel,
+        session_store,
+        warm_manager,
+        InvocationOrigin::Internal,
+    )
+    .await
+    .context("Agent failed to summarize the conversation")?;
+
+    let path = archive_summary_path(&channel.directory);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let content = format!(
+        "# Archive summary: {}\n\nArchived {}\n\n{}\n",
+        channel.channel_name,
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        summary.trim()
+    );
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    tracing::info!(
+        channel = %channel.channel_name,
+        path = %path.display(),
+        "Saved archive summary"
+    );
+    Ok(Some(path))
+}
@@ -0,0 +1,139 @@
+// ABOUTME: One-off side questions for !ask, answered beside the channel's main session.
+// ABOUTME: Uses a throwaway agent handle so the main session's ID, context and transcript stay untouched.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::{Channel, SessionStore},
+    usage::InvocationOrigin,
+    utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
+    warm_session::{SharedWarmSessionManager, WarmSessionManager},
+};
+
+use super::commands::{channel_for_command, reply_privately};
+use super::compare::collect_response;
+
+/// Marker in front of every !ask answer so it isn't mistaken for the main conversation
+pub const ONE_OFF_MARKER: &str = "(one-off)";
+
+/// Added to read-only questions for backends that can't enforce it themselves
+const READ_ONLY_DIRECTIVE: &str = "Answer without modifying any files or running commands \
+that change state; only read and explain.";
+
+/// Answer `prompt` in a short-lived session working in the channel's workspace.
+///
+/// Nothing about the exchange is stored on the channel: the session ID is
+/// discarded with the handle and the exchange stays out of the transcript.
+/// Usage is recorded as internal overhead.
+pub async fn ask_one_off(
+    prompt: &str,
+    channel: &Channel,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    read_only: bool,
+    timeout: Duration,
+) -> Result<String> {
+    let (warm_config, registry) = {
+        let mgr = warm_manager.read().await;
+        (mgr.config(), mgr.registry())
+    };
+    let working_dir = std::path::Path::new(&channel.directory)
+        .canonicalize()
+        .unwrap_or_else(|_| std::path::Path::new(&channel.directory).to_path_buf());
+
+    let handle = WarmSessionManager::create_ephemeral_handle(
+        &registry,
+        &working_dir.to_string_lossy(),
+        &warm_config,
+        channel.backend_type.as_deref(),
+        read_only,
+    )
+    .context("Failed to start backend")?;
+
+    let prompt = if read_only {
+        format!("{}\n\n{}", READ_ONLY_DIRECTIVE, prompt)
+    } else {
+        prompt.to_string()
+    };
+    let (text, usage) =
+        match tokio::time::timeout(timeout, collect_response(&handle, &prompt)).await {
+            Ok(result) => result?,
+            Err(_) => anyhow::bail!("Timed out after {}s", timeout.as_secs()),
+        };
+
+    session_store.record_usage(
+        &channel.channel_name,
+        InvocationOrigin::Internal,
+        usage.as_ref(),
+    )?;
+    Ok(text)
+}
+
+/// `!ask [--write] <question>`: answer a side question without touching the main session
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn handle_ask_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    timeout: Duration,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "ask", is_dm, locale).await? else {
+        return Ok(());
+    };
+
+    // Read-only unless the question explicitly allows changes
+    let (read_only, words) = match args.first().map(String::as_str) {
+        Some("--write") => (false, &args[1..]),
+        _ => (true, args),
+    };
+    let prompt = words.join(" ");
+    if prompt.trim().is_empty() {
+        let usage = MessageContent::plain(t(locale, "ask.usage"));
+        return reply_privately(channel, sender, usage).await;
+    }
+
+    let typing = channel.typing_indicator();
+    if let Some(typing) = typing {
+        typing.set_typing(true).await?;
+    }
+    let answer = ask_one_off(
+        &prompt,
+        &ch,
+        session_store,
+        warm_manager,
+        read_only,
+        timeout,
+    )
+    .await;
+    if let Some(typing) = typing {
+        typing.set_typing(false).await?;
+    }
+
+    match answer {
+        Ok(text) => {
+            let text = format!("{} {}", ONE_OFF_MARKER, text.trim());
+            for chunk in chunk_message(&text, MAX_CHUNK_SIZE) {
+                let html = markdown_to_html(&chunk);
+                channel.send(MessageContent::html(&chunk, &html)).await?;
+            }
+            Ok(())
+        }
+        Err(e) => {
+            tracing::warn!(
+                channel = %ch.channel_name,
+                error = %e,
+                "One-off question failed"
+            );
+            let reply = tf(locale, "ask.failed", &[("error", &format!("{:#}", e))]);
+            channel.send(MessageContent::plain(reply)).await
+        }
+    }
+}
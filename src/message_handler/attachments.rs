@@ -1,32 +1,207 @@
-// ABOUTME: Attachment handling for Matrix messages
-// ABOUTME: Downloads images and files from Matrix media server to workspace
+// ABOUTME: Attachment handling for incoming messages
+// ABOUTME: Applies size/type limits (shown and changed with !attachments), saves files into the workspace and lists them in the prompt
 
 use anyhow::Result;
+use gorp_core::traits::{AttachmentHandler, AttachmentInfo, ChatChannel, MessageContent};
 use matrix_sdk::{
     media::{MediaFormat, MediaRequestParameters},
     Client,
 };
 use std::path::Path;
 
-/// Download an attachment from Matrix and save it to the workspace
+use crate::config::AttachmentsConfig;
+use crate::i18n::{t, tf};
+use crate::session::SessionStore;
+use crate::transcription::{is_audio, Transcriber, VOICE_TRANSCRIPT_PREFIX};
+
+use super::commands::channel_for_command;
+
+/// Largest combined size of the attachments in one message, unless a
+/// channel's own per-file limit is higher
+pub const MAX_ATTACHMENT_BATCH_BYTES: u64 = 50 * 1024 * 1024;
+
+const KB: u64 = 1024;
+const MB: u64 = 1024 * KB;
+const GB: u64 = 1024 * MB;
+
+/// An attachment saved into the channel workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedAttachment {
+    /// Absolute path of the saved file
+    pub path: String,
+    pub mime_type: String,
+}
+
+/// An attachment refused by the size or type limits; the text is meant for the sender
+#[derive(Debug)]
+pub struct AttachmentRejected(pub String);
+
+impl std::fmt::Display for AttachmentRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for AttachmentRejected {}
+
+/// Size and type limits for one channel's incoming attachments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentLimits {
+    pub max_size_bytes: u64,
+    pub allowed_mime_types: Vec<String>,
+    pub denied_mime_types: Vec<String>,
+}
+
+impl AttachmentLimits {
+    /// The configured limits, with the channel's own size limit if it has one
+    pub fn for_channel(
+        config: &AttachmentsConfig,
+        session_store: &SessionStore,
+        channel_name: &str,
+    ) -> Self {
+        let max_size_bytes = match session_store.get_attachment_max_bytes(channel_name) {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => config.max_size_bytes,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    channel = %channel_name,
+                    "Ignoring channel attachment limit"
+                );
+                config.max_size_bytes
+            }
+        };
+        Self {
+            max_size_bytes,
+            allowed_mime_types: config.allowed_mime_types.clone(),
+            denied_mime_types: config.denied_mime_types.clone(),
+        }
+    }
+
+    /// Refuse an attachment by its declared type and size, before any bytes are fetched
+    pub fn check(
+        &self,
+        filename: &str,
+        mime_type: &str,
+        size: Option<u64>,
+    ) -> Result<(), AttachmentRejected> {
+        let denied = self
+            .denied_mime_types
+            .iter()
+            .any(|pattern| mime_matches(pattern, mime_type));
+        let allowed = self.allowed_mime_types.is_empty()
+            || self
+                .allowed_mime_types
+                .iter()
+                .any(|pattern| mime_matches(pattern, mime_type));
+        if denied || !allowed {
+            let mut reason = format!(
+                "{} ({}) is not an accepted attachment type",
+                filename, mime_type
+            );
+            if !self.allowed_mime_types.is_empty() {
+                reason.push_str(&format!(
+                    "; accepted types: {}",
+                    self.allowed_mime_types.join(", ")
+                ));
+            }
+            return Err(AttachmentRejected(reason));
+        }
+        match size {
+            Some(size) => self.check_size(filename, size),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse an attachment over the size limit
+    pub fn check_size(&self, filename: &str, size: u64) -> Result<(), AttachmentRejected> {
+        if size > self.max_size_bytes {
+            return Err(AttachmentRejected(format!(
+                "{} is {}, over the {} attachment limit",
+                filename,
+                format_size(size),
+                format_size(self.max_size_bytes)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Combined size allowed for all attachments of one message
+    fn batch_limit(&self) -> u64 {
+        MAX_ATTACHMENT_BATCH_BYTES.max(self.max_size_bytes)
+    }
+}
+
+/// Whether `mime_type` matches a configured pattern: an exact type, `image/*`, or `*`.
+/// Parameters such as `; charset=utf-8` are ignored.
+fn mime_matches(pattern: &str, mime_type: &str) -> bool {
+    let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+    let pattern = pattern.trim();
+    if pattern == "*" || pattern == "*/*" {
+        return true;
+    }
+    match pattern.strip_suffix("/*") {
+        Some(top_level) => mime_type
+            .split('/')
+            .next()
+            .is_some_and(|t| t.eq_ignore_ascii_case(top_level)),
+        None => pattern.eq_ignore_ascii_case(mime_type),
+    }
+}
+
+/// A byte count for limit messages: "25 MB", "1.5 GB", "300 KB"
+pub fn format_size(bytes: u64) -> String {
+    let (unit, name) = match bytes {
+        b if b >= GB => (GB, "GB"),
+        b if b >= MB => (MB, "MB"),
+        b if b >= KB => (KB, "KB"),
+        _ => return format!("{} bytes", bytes),
+    };
+    if bytes % unit == 0 {
+        format!("{} {}", bytes / unit, name)
+    } else {
+        format!("{:.1} {}", bytes as f64 / unit as f64, name)
+    }
+}
+
+/// Parse a size such as "200MB", "1.5 gb", "512k" or a plain byte count
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim().to_ascii_lowercase();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "" | "b" => 1,
+        "k" | "kb" => KB,
+        "m" | "mb" => MB,
+        "g" | "gb" => GB,
+        _ => return None,
+    };
+    let bytes = number * multiplier as f64;
+    (bytes.is_finite() && bytes >= 0.0 && bytes < u64::MAX as f64).then_some(bytes as u64)
+}
+
+/// Download an attachment from Matrix and save it to the workspace.
+/// The event's declared type and size are checked against `limits` first.
 /// Returns the relative path to the saved file
 pub async fn download_attachment(
     client: &Client,
     source: &matrix_sdk::ruma::events::room::MediaSource,
     filename: &str,
+    mime_type: Option<&str>,
+    size: Option<u64>,
+    limits: &AttachmentLimits,
     workspace_dir: &str,
 ) -> Result<String> {
-    use tokio::io::AsyncWriteExt;
-
-    // Create attachments directory
-    let attachments_dir = Path::new(workspace_dir).join("attachments");
-    tokio::fs::create_dir_all(&attachments_dir).await?;
-
-    // Generate unique filename to avoid collisions
-    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-    let safe_filename = sanitize_filename(filename);
-    let unique_filename = format!("{}_{}", timestamp, safe_filename);
-    let file_path = attachments_dir.join(&unique_filename);
+    let mime_type = match mime_type {
+        Some(mime_type) => mime_type.to_string(),
+        None => mime_guess::from_path(filename)
+            .first_or_octet_stream()
+            .to_string(),
+    };
+    limits.check(filename, &mime_type, size)?;
 
     // Download the media
     let request = MediaRequestParameters {
@@ -39,10 +214,168 @@ pub async fn download_attachment(
         .get_media_content(&request, true) // use_cache=true
         .await
         .map_err(|e| anyhow::anyhow!("Failed to download media: {}", e))?;
+    // Events without size info are only caught once the bytes are here
+    limits.check_size(filename, data.len() as u64)?;
+
+    save_attachment(workspace_dir, filename, &data).await
+}
+
+/// Download every attachment of a message through the platform's handler and
+/// save each into the workspace. Each attachment must pass `limits`, the batch
+/// is refused if it adds up to more than the batch limit, and nothing is kept
+/// if any download fails.
+pub async fn download_attachments(
+    handler: &dyn AttachmentHandler,
+    attachments: &[AttachmentInfo],
+    limits: &AttachmentLimits,
+    workspace_dir: &str,
+) -> Result<Vec<SavedAttachment>> {
+    // Platforms usually report sizes up front, so most refusals happen before any download
+    for attachment in attachments {
+        limits.check(&attachment.filename, &attachment.mime_type, attachment.size)?;
+    }
+    let batch_limit = limits.batch_limit();
+    let declared: u64 = attachments.iter().filter_map(|a| a.size).sum();
+    if declared > batch_limit {
+        return Err(AttachmentRejected(format!(
+            "attachments total {}, over the {} limit for one message",
+            format_size(declared),
+            format_size(batch_limit)
+        ))
+        .into());
+    }
+
+    let mut saved = Vec::with_capacity(attachments.len());
+    let result = async {
+        let mut total = 0u64;
+        for attachment in attachments {
+            let (_, data, _) = handler.download(&attachment.source_id).await?;
+            limits.check_size(&attachment.filename, data.len() as u64)?;
+            total += data.len() as u64;
+            if total > batch_limit {
+                return Err(AttachmentRejected(format!(
+                    "attachments total over the {} limit for one message",
+                    format_size(batch_limit)
+                ))
+                .into());
+            }
+            let rel_path = save_attachment(workspace_dir, &attachment.filename, &data).await?;
+            saved.push(SavedAttachment {
+                path: format!("{}/{}", workspace_dir, rel_path),
+                mime_type: attachment.mime_type.clone(),
+            });
+        }
+        Ok::<(), anyhow::Error>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        for attachment in &saved {
+            let _ = tokio::fs::remove_file(&attachment.path).await;
+        }
+        return Err(e);
+    }
+    Ok(saved)
+}
+
+/// The reply for a failed attachment download: refusals are explained as-is,
+/// anything else is reported as a download failure
+pub fn failure_notice(err: &anyhow::Error) -> String {
+    match err.downcast_ref::<AttachmentRejected>() {
+        Some(rejected) => format!("🚫 Attachment refused: {}", rejected),
+        None => format!("⚠️ Failed to download attachments: {}", err),
+    }
+}
+
+/// Transcribe the saved audio attachments, in order, using the channel's language hint
+pub async fn transcribe_voice_notes(
+    saved: &[SavedAttachment],
+    transcriber: &dyn Transcriber,
+    language: Option<&str>,
+) -> Result<Vec<String>> {
+    let mut transcripts = Vec::new();
+    for attachment in saved.iter().filter(|a| is_audio(&a.mime_type)) {
+        let audio = tokio::fs::read(&attachment.path).await?;
+        let filename = Path::new(&attachment.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "voice.ogg".to_string());
+        transcripts.push(
+            transcriber
+                .transcribe(audio, &filename, &attachment.mime_type, language)
+                .await?,
+        );
+    }
+    Ok(transcripts)
+}
 
-    // Write to file
-    let mut file = tokio::fs::File::create(&file_path).await?;
-    file.write_all(&data).await?;
+/// Put a "[Voice message transcript] ..." line per transcript before `body`
+pub fn with_voice_transcripts(transcripts: &[String], body: &str) -> String {
+    if transcripts.is_empty() {
+        return body.to_string();
+    }
+    let mut prompt = String::new();
+    for transcript in transcripts {
+        prompt.push_str(&format!("{} {}\n", VOICE_TRANSCRIPT_PREFIX, transcript));
+    }
+    if !body.trim().is_empty() {
+        prompt.push('\n');
+        prompt.push_str(body);
+    }
+    prompt
+}
+
+/// Prefix `body` with one line per saved attachment so the agent knows where to find them
+pub fn with_attachment_preamble(saved: &[SavedAttachment], body: &str) -> String {
+    if saved.is_empty() {
+        return body.to_string();
+    }
+    let mut prompt = String::new();
+    for attachment in saved {
+        let kind = if attachment.mime_type.starts_with("image/") {
+            "image"
+        } else if is_audio(&attachment.mime_type) {
+            "audio"
+        } else {
+            "file"
+        };
+        prompt.push_str(&format!("[Attached {}: {}]\n", kind, attachment.path));
+    }
+    prompt.push('\n');
+    prompt.push_str(body);
+    prompt
+}
+
+/// Write `data` under the workspace's attachments directory with a timestamped
+/// name, numbering it when several files with the same name arrive together.
+/// Returns the path relative to the workspace.
+async fn save_attachment(workspace_dir: &str, filename: &str, data: &[u8]) -> Result<String> {
+    use tokio::io::AsyncWriteExt;
+
+    // Create attachments directory
+    let attachments_dir = Path::new(workspace_dir).join("attachments");
+    tokio::fs::create_dir_all(&attachments_dir).await?;
+
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let safe_filename = sanitize_filename(filename);
+    let mut n = 0;
+    let (unique_filename, mut file) = loop {
+        let unique_filename = match n {
+            0 => format!("{}_{}", timestamp, safe_filename),
+            n => format!("{}_{}_{}", timestamp, n, safe_filename),
+        };
+        match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(attachments_dir.join(&unique_filename))
+            .await
+        {
+            Ok(file) => break (unique_filename, file),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e.into()),
+        }
+    };
+    file.write_all(data).await?;
 
     tracing::info!(
         filename = %unique_filename,
@@ -61,9 +394,83 @@ pub fn sanitize_filename(filename: &str) -> String {
         .collect()
 }
 
+/// `!attachments [limit <size>|reset]`: show the channel's attachment limits or change its size limit
+pub(crate) async fn handle_attachments_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    config: &AttachmentsConfig,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) =
+        channel_for_command(channel, session_store, "attachments", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let default_limit = format_size(config.max_size_bytes);
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        Some("limit") => match parse_size(&args[1..].join("")) {
+            Some(bytes) if bytes > 0 => {
+                session_store.set_attachment_max_bytes(&ch.channel_name, Some(bytes))?;
+                tracing::info!(channel = %ch.channel_name, bytes, "Attachment limit set");
+                tf(
+                    locale,
+                    "attachments.set",
+                    &[("limit", &format_size(bytes)), ("default", &default_limit)],
+                )
+            }
+            _ => t(locale, "attachments.usage_limit"),
+        },
+        Some("reset") => {
+            session_store.set_attachment_max_bytes(&ch.channel_name, None)?;
+            tracing::info!(channel = %ch.channel_name, "Attachment limit reset");
+            tf(locale, "attachments.reset", &[("default", &default_limit)])
+        }
+        _ => {
+            let limit = match session_store.get_attachment_max_bytes(&ch.channel_name)? {
+                Some(bytes) => tf(
+                    locale,
+                    "attachments.limit_override",
+                    &[("limit", &format_size(bytes))],
+                ),
+                None => tf(
+                    locale,
+                    "attachments.limit_default",
+                    &[("limit", &default_limit)],
+                ),
+            };
+            let list = |types: &[String]| {
+                if types.is_empty() {
+                    t(locale, "attachments.none")
+                } else {
+                    types.join(", ")
+                }
+            };
+            let allowed = if config.allowed_mime_types.is_empty() {
+                t(locale, "attachments.any")
+            } else {
+                list(&config.allowed_mime_types)
+            };
+            tf(
+                locale,
+                "attachments.status",
+                &[
+                    ("limit", &limit),
+                    ("allowed", &allowed),
+                    ("denied", &list(&config.denied_mime_types)),
+                ],
+            )
+        }
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anyhow::bail;
 
     #[test]
     fn test_sanitize_filename() {
@@ -74,6 +481,273 @@ mod tests {
         assert_eq!(sanitize_filename("image (1).png"), "image1.png");
     }
 
+    struct FakeHandler;
+
+    #[async_trait::async_trait]
+    impl AttachmentHandler for FakeHandler {
+        async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
+            match source_id {
+                "missing" => bail!("not found"),
+                _ => Ok((
+                    source_id.to_string(),
+                    source_id.as_bytes().to_vec(),
+                    "application/octet-stream".to_string(),
+                )),
+            }
+        }
+    }
+
+    fn info(source_id: &str, filename: &str, mime_type: &str, size: Option<u64>) -> AttachmentInfo {
+        AttachmentInfo {
+            source_id: source_id.to_string(),
+            filename: filename.to_string(),
+            mime_type: mime_type.to_string(),
+            size,
+        }
+    }
+
+    fn limits(max_size_bytes: u64) -> AttachmentLimits {
+        AttachmentLimits {
+            max_size_bytes,
+            allowed_mime_types: Vec::new(),
+            denied_mime_types: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_attachments_saves_each_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().to_str().unwrap();
+        // Three screenshots with the same name, as phones tend to send them
+        let attachments = vec![
+            info("one", "image.png", "image/png", Some(3)),
+            info("two", "image.png", "image/png", Some(3)),
+            info("three", "image.png", "image/png", None),
+        ];
+
+        let saved = download_attachments(&FakeHandler, &attachments, &limits(MB), workspace)
+            .await
+            .unwrap();
+
+        assert_eq!(saved.len(), 3);
+        let contents: Vec<String> = saved
+            .iter()
+            .map(|a| std::fs::read_to_string(&a.path).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["one", "two", "three"]);
+    }
+
+    #[tokio::test]
+    async fn test_download_attachments_enforces_batch_limit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().to_str().unwrap();
+        let half = MAX_ATTACHMENT_BATCH_BYTES / 2 + 1;
+        let attachments = vec![
+            info("one", "a.pdf", "application/pdf", Some(half)),
+            info("two", "b.pdf", "application/pdf", Some(half)),
+        ];
+
+        let err = download_attachments(
+            &FakeHandler,
+            &attachments,
+            &limits(MAX_ATTACHMENT_BATCH_BYTES),
+            workspace,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("limit"));
+        assert!(!dir.path().join("attachments").exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_attachments_keeps_nothing_on_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().to_str().unwrap();
+        let attachments = vec![
+            info("one", "a.txt", "text/plain", None),
+            info("missing", "b.txt", "text/plain", None),
+        ];
+
+        assert!(
+            download_attachments(&FakeHandler, &attachments, &limits(MB), workspace)
+                .await
+                .is_err()
+        );
+        let left = std::fs::read_dir(dir.path().join("attachments"))
+            .unwrap()
+            .count();
+        assert_eq!(left, 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_attachment_is_refused_before_download() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().to_str().unwrap();
+        // "missing" would fail to download, so only a pre-download refusal gets this far
+        let attachments = vec![info("missing", "movie.mp4", "video/mp4", Some(2 * GB))];
+
+        let err = download_attachments(&FakeHandler, &attachments, &limits(25 * MB), workspace)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<AttachmentRejected>().is_some());
+        assert_eq!(
+            failure_notice(&err),
+            "🚫 Attachment refused: movie.mp4 is 2 GB, over the 25 MB attachment limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undeclared_size_is_checked_after_download() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().to_str().unwrap();
+        let attachments = vec![info("twelve bytes", "a.txt", "text/plain", None)];
+
+        let err = download_attachments(&FakeHandler, &attachments, &limits(10), workspace)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<AttachmentRejected>().is_some());
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("attachments"))
+                .map(|d| d.count())
+                .unwrap_or(0),
+            0
+        );
+    }
+
+    #[test]
+    fn test_mime_allow_and_deny_lists() {
+        let limits = AttachmentLimits {
+            max_size_bytes: MB,
+            allowed_mime_types: vec!["image/*".to_string(), "application/pdf".to_string()],
+            denied_mime_types: vec!["image/svg+xml".to_string()],
+        };
+        assert!(limits.check("a.png", "image/png", Some(10)).is_ok());
+        assert!(limits.check("a.pdf", "Application/PDF", None).is_ok());
+        // Deny wins over a matching allow pattern
+        let err = limits
+            .check("a.svg", "image/svg+xml", Some(10))
+            .unwrap_err();
+        assert!(err.to_string().contains("not an accepted attachment type"));
+        let err = limits
+            .check("run.exe", "application/x-msdownload", None)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("accepted types: image/*, application/pdf"));
+
+        let open = AttachmentLimits {
+            allowed_mime_types: Vec::new(),
+            ..limits
+        };
+        assert!(open
+            .check("notes.txt", "text/plain; charset=utf-8", None)
+            .is_ok());
+        assert!(open.check("a.svg", "image/svg+xml", None).is_err());
+    }
+
+    #[test]
+    fn test_format_and_parse_size() {
+        assert_eq!(format_size(25 * MB), "25 MB");
+        assert_eq!(format_size(3 * GB / 2), "1.5 GB");
+        assert_eq!(format_size(300 * KB), "300 KB");
+        assert_eq!(format_size(512), "512 bytes");
+
+        assert_eq!(parse_size("200MB"), Some(200 * MB));
+        assert_eq!(parse_size("1.5 gb"), Some(3 * GB / 2));
+        assert_eq!(parse_size("512k"), Some(512 * KB));
+        assert_eq!(parse_size("1048576"), Some(MB));
+        assert_eq!(parse_size("lots"), None);
+        assert_eq!(parse_size("10 parsecs"), None);
+    }
+
+    #[test]
+    fn test_attachment_preamble_lists_every_file() {
+        let saved = vec![
+            SavedAttachment {
+                path: "/ws/attachments/a.png".to_string(),
+                mime_type: "image/png".to_string(),
+            },
+            SavedAttachment {
+                path: "/ws/attachments/b.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+            },
+        ];
+        assert_eq!(
+            with_attachment_preamble(&saved, "what are these?"),
+            "[Attached image: /ws/attachments/a.png]\n\
+             [Attached file: /ws/attachments/b.pdf]\n\n\
+             what are these?"
+        );
+        assert_eq!(with_attachment_preamble(&[], "hi"), "hi");
+    }
+
+    /// Answers with the clip's contents and remembers the language hint it was given
+    #[derive(Default)]
+    struct EchoTranscriber {
+        languages: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transcriber for EchoTranscriber {
+        async fn transcribe(
+            &self,
+            audio: Vec<u8>,
+            _filename: &str,
+            _mime_type: &str,
+            language: Option<&str>,
+        ) -> Result<String> {
+            self.languages
+                .lock()
+                .unwrap()
+                .push(language.map(String::from));
+            Ok(String::from_utf8(audio)?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_audio_attachments_are_transcribed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().to_str().unwrap();
+        let attachments = vec![
+            info("deploy the api", "voice.ogg", "audio/ogg", None),
+            info("not speech", "notes.txt", "text/plain", None),
+        ];
+        let saved = download_attachments(&FakeHandler, &attachments, &limits(MB), workspace)
+            .await
+            .unwrap();
+
+        let transcriber = EchoTranscriber::default();
+        let transcripts = transcribe_voice_notes(&saved, &transcriber, Some("es"))
+            .await
+            .unwrap();
+
+        assert_eq!(transcripts, vec!["deploy the api".to_string()]);
+        assert_eq!(
+            *transcriber.languages.lock().unwrap(),
+            vec![Some("es".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_voice_transcripts_come_before_the_caption() {
+        let saved = vec![SavedAttachment {
+            path: "/ws/attachments/voice.ogg".to_string(),
+            mime_type: "audio/ogg".to_string(),
+        }];
+        let transcripts = vec!["what's failing in CI?".to_string()];
+
+        assert_eq!(
+            with_attachment_preamble(&saved, &with_voice_transcripts(&transcripts, "")),
+            "[Attached audio: /ws/attachments/voice.ogg]\n\n\
+             [Voice message transcript] what's failing in CI?\n"
+        );
+        assert_eq!(
+            with_voice_transcripts(&transcripts, "see above"),
+            "[Voice message transcript] what's failing in CI?\n\nsee above"
+        );
+        assert_eq!(with_voice_transcripts(&[], "hi"), "hi");
+    }
+
     #[test]
     fn test_sanitize_filename_preserves_extension() {
         assert_eq!(sanitize_filename("report.pdf"), "report.pdf");
@@ -0,0 +1,117 @@
+// ABOUTME: Clarifying questions from the agent: a reply marked with NEEDS_INPUT_MARKER leaves the
+// ABOUTME: channel awaiting an answer, and the user's next message is sent back as that answer.
+
+use crate::session::SessionStore;
+
+/// Line an agent adds to a reply that asks the user something before it can go on
+pub const NEEDS_INPUT_MARKER: &str = "[needs_input]";
+
+/// How much of the question is kept to remind the agent what the answer is for
+const QUESTION_CONTEXT_CHARS: usize = 2000;
+
+/// Remove marker lines from a response. Returns the cleaned text and whether it was marked.
+pub fn split_marker(response: &str) -> (String, bool) {
+    if !response.contains(NEEDS_INPUT_MARKER) {
+        return (response.to_string(), false);
+    }
+    let mut marked = false;
+    let lines: Vec<&str> = response
+        .lines()
+        .filter(|line| {
+            let is_marker = line.trim() == NEEDS_INPUT_MARKER;
+            marked |= is_marker;
+            !is_marker
+        })
+        .collect();
+    if !marked {
+        return (response.to_string(), false);
+    }
+    (lines.join("\n").trim_end().to_string(), true)
+}
+
+/// Frame a user's message as the answer to the agent's open question, if it has one,
+/// and stop waiting. Messages in a channel that isn't waiting pass through untouched.
+pub fn continue_reply(session_store: &SessionStore, channel_name: &str, prompt: &str) -> String {
+    let question = match session_store.get_awaiting_reply(channel_name) {
+        Ok(Some(question)) => question,
+        Ok(None) => return prompt.to_string(),
+        Err(e) => {
+            tracing::warn!(error = %e, channel = %channel_name, "Failed to read awaiting reply");
+            return prompt.to_string();
+        }
+    };
+    if let Err(e) = session_store.set_awaiting_reply(channel_name, None) {
+        tracing::warn!(error = %e, channel = %channel_name, "Failed to clear awaiting reply");
+    }
+    let quoted: Vec<String> = question.lines().map(|line| format!("> {}", line)).collect();
+    format!(
+        "[Answer to your question] You paused the task to ask:\n{}\n\nThe user answered:\n{}\n\nContinue the task with this answer.",
+        quoted.join("\n"),
+        prompt
+    )
+}
+
+/// Strip the marker from an agent's response; a marked response leaves the channel
+/// awaiting the user's answer. Returns the text to show and whether it asked a question.
+pub fn settle_response(
+    session_store: &SessionStore,
+    channel_name: &str,
+    response: &str,
+) -> (String, bool) {
+    let (response, marked) = split_marker(response);
+    if marked {
+        let question: String = response.chars().take(QUESTION_CONTEXT_CHARS).collect();
+        if let Err(e) = session_store.set_awaiting_reply(channel_name, Some(&question)) {
+            tracing::warn!(error = %e, channel = %channel_name, "Failed to record awaiting reply");
+        }
+    }
+    (response, marked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_split_marker_only_takes_whole_lines() {
+        assert_eq!(
+            split_marker("Which branch should I use?\n\n[needs_input]\n"),
+            ("Which branch should I use?".to_string(), true)
+        );
+        assert_eq!(
+            split_marker("I'll look for a [needs_input] tag"),
+            ("I'll look for a [needs_input] tag".to_string(), false)
+        );
+        assert_eq!(split_marker("Done."), ("Done.".to_string(), false));
+    }
+
+    #[test]
+    fn test_marked_question_awaits_and_the_reply_continues_it() {
+        let dir = TempDir::new().unwrap();
+        let store = SessionStore::new(dir.path()).unwrap();
+
+        let (shown, asked) =
+            settle_response(&store, "ops", "Deploy to staging or prod?\n[needs_input]");
+        assert!(asked);
+        assert_eq!(shown, "Deploy to staging or prod?");
+        assert_eq!(
+            store.get_awaiting_reply("ops").unwrap().as_deref(),
+            Some("Deploy to staging or prod?")
+        );
+
+        let prompt = continue_reply(&store, "ops", "staging");
+        assert!(prompt.starts_with("[Answer to your question]"));
+        assert!(prompt.contains("> Deploy to staging or prod?"));
+        assert!(prompt.contains("The user answered:\nstaging"));
+        assert_eq!(store.get_awaiting_reply("ops").unwrap(), None);
+
+        // Once answered, later messages go through as written
+        assert_eq!(continue_reply(&store, "ops", "thanks"), "thanks");
+        assert_eq!(
+            settle_response(&store, "ops", "Deployed."),
+            ("Deployed.".to_string(), false)
+        );
+        assert_eq!(store.get_awaiting_reply("ops").unwrap(), None);
+    }
+}
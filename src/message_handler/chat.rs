@@ -1,36 +1,77 @@
 // ABOUTME: Chat message processing module for Claude invocation and response streaming.
-// ABOUTME: Handles attachments, typing indicators, session management, and Matrix response chunking.
+// ABOUTME: Handles attachments, threads, typing indicators, session management, and response chunking.
 
 use anyhow::Result;
+use chrono::Utc;
 use matrix_sdk::{
     room::Room,
-    ruma::events::room::message::{
-        MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
+    ruma::{
+        events::room::message::{
+            MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
+        },
+        EventId,
     },
     Client,
 };
+use std::sync::Arc;
 
 use crate::{
+    approvals::{ChatApprover, NetworkBlocker, Notify, PendingApprovals},
+    config::{AttachmentsConfig, SigningConfig, ToolApprovalConfig},
+    delivery::{hold_if_outside_window, should_hold, DeliveryPriority},
+    edits::EditTracker,
+    i18n::{self, t, tf},
+    logging::loggable_content,
     metrics,
+    platform::{
+        matrix::{fallback::send_html, signing::Signer},
+        MatrixChannel, MatrixPlatform,
+    },
+    redactions::ResponseTracker,
+    rich_response::actions_as_list,
+    runtime_mode::SafeModeRefusal,
     session::{Channel, SessionStore},
+    transcription::{Transcriber, VOICE_UNSUPPORTED_NOTICE},
+    usage::InvocationOrigin,
     utils::{
         chunk_message, log_matrix_message, markdown_to_html, strip_function_calls, MAX_CHUNK_SIZE,
     },
-    warm_session::{prepare_session_async, SharedWarmSessionManager},
+    warm_session::{
+        acquire_turn, is_network_allowed, prepare_session_async, set_tool_approver,
+        SharedWarmSessionManager,
+    },
 };
-use gorp_agent::AgentEvent;
+use gorp_agent::{AgentEvent, ToolApprover};
+use gorp_core::traits::{ChatChannel, MessageAnnotator, MessageContent};
 
-use super::{download_attachment, is_debug_enabled, route_to_dispatch, write_context_file};
+use super::{
+    attachments::{
+        transcribe_voice_notes, with_attachment_preamble, with_voice_transcripts, AttachmentLimits,
+        AttachmentRejected, SavedAttachment,
+    },
+    awaiting, download_attachment,
+    helpers::{cap_response, format_tool_notice, is_status_reactions_enabled, with_footer},
+    is_debug_enabled, is_streaming_enabled,
+    response_cache::{self, cached_reply, Lookup},
+    response_length::{apply_directive, enforce_length, get_response_length},
+    rich_reply::{pending_response, render_parts, RenderedPart},
+    route_to_dispatch,
+    status_reactions::StatusReactions,
+    streaming::ResponseStreamer,
+    threads::{in_thread, root_message_body, thread_root, with_thread_context},
+    write_context_file,
+};
 
 /// Process a regular (non-command) chat message by invoking Claude and streaming the response.
 ///
 /// This function handles:
-/// - Building the prompt from image/file attachments
+/// - Building the prompt from image/file attachments and voice message transcripts
 /// - Writing context file for MCP tools
 /// - Managing typing indicators
 /// - Preparing and using warm sessions
 /// - Processing the agent event stream
 /// - Chunking and sending responses to Matrix
+#[allow(clippy::too_many_arguments)]
 pub async fn process_chat_message(
     room: Room,
     event: OriginalSyncRoomMessageEvent,
@@ -38,19 +79,45 @@ pub async fn process_chat_message(
     channel: Channel,
     session_store: SessionStore,
     warm_manager: SharedWarmSessionManager,
+    edits: &EditTracker,
+    responses: &ResponseTracker,
+    approvals: &PendingApprovals,
+    attachments_config: &AttachmentsConfig,
+    tool_approval: &ToolApprovalConfig,
+    signing: &SigningConfig,
+    transcriber: Option<&dyn Transcriber>,
 ) -> Result<()> {
     let start_time = std::time::Instant::now();
     let body = event.content.body();
+    let footer = signing.footer.as_str();
+    // Agent responses are signed; notices along the way are not
+    let signer = Signer::from_config(signing);
+
+    // Replies to a message in an m.thread go back into that thread
+    let thread = thread_root(&event);
+    let reply = |content: RoomMessageEventContent| match &thread {
+        Some(root) => in_thread(content, root, &event.event_id),
+        None => content,
+    };
+    // Everything sent in answer is remembered, so redacting the prompt can redact it too
+    let remember =
+        |sent: &EventId| responses.record("matrix", event.event_id.as_str(), sent.as_str());
 
     // Check for attachments (images, files) and build the prompt
+    let attachment_limits =
+        || AttachmentLimits::for_channel(attachments_config, &session_store, &channel.channel_name);
     let prompt = match &event.content.msgtype {
         MessageType::Image(image_content) => {
             // Download the image
             let filename = image_content.body.clone();
+            let info = image_content.info.as_deref();
             match download_attachment(
                 &client,
                 &image_content.source,
                 &filename,
+                info.and_then(|i| i.mimetype.as_deref()),
+                info.and_then(|i| i.size).map(u64::from),
+                &attachment_limits(),
                 &channel.directory,
             )
             .await
@@ -63,11 +130,12 @@ pub async fn process_chat_message(
                 }
                 Err(e) => {
                     tracing::error!(error = %e, "Failed to download image");
-                    room.send(RoomMessageEventContent::text_plain(format!(
-                        "⚠️ Failed to download image: {}",
-                        e
-                    )))
-                    .await?;
+                    let notice = match e.downcast_ref::<AttachmentRejected>() {
+                        Some(rejected) => format!("🚫 Attachment refused: {}", rejected),
+                        None => format!("⚠️ Failed to download image: {}", e),
+                    };
+                    room.send(reply(RoomMessageEventContent::text_plain(notice)))
+                        .await?;
                     return Ok(());
                 }
             }
@@ -75,8 +143,17 @@ pub async fn process_chat_message(
         MessageType::File(file_content) => {
             // Download the file
             let filename = file_content.body.clone();
-            match download_attachment(&client, &file_content.source, &filename, &channel.directory)
-                .await
+            let info = file_content.info.as_deref();
+            match download_attachment(
+                &client,
+                &file_content.source,
+                &filename,
+                info.and_then(|i| i.mimetype.as_deref()),
+                info.and_then(|i| i.size).map(u64::from),
+                &attachment_limits(),
+                &channel.directory,
+            )
+            .await
             {
                 Ok(rel_path) => {
                     let abs_path = format!("{}/{}", channel.directory, rel_path);
@@ -85,10 +162,77 @@ pub async fn process_chat_message(
                 }
                 Err(e) => {
                     tracing::error!(error = %e, "Failed to download file");
-                    room.send(RoomMessageEventContent::text_plain(format!(
-                        "⚠️ Failed to download file: {}",
+                    let notice = match e.downcast_ref::<AttachmentRejected>() {
+                        Some(rejected) => format!("🚫 Attachment refused: {}", rejected),
+                        None => format!("⚠️ Failed to download file: {}", e),
+                    };
+                    room.send(reply(RoomMessageEventContent::text_plain(notice)))
+                        .await?;
+                    return Ok(());
+                }
+            }
+        }
+        MessageType::Audio(audio_content) => {
+            let Some(transcriber) = transcriber else {
+                room.send(reply(RoomMessageEventContent::text_plain(
+                    VOICE_UNSUPPORTED_NOTICE,
+                )))
+                .await?;
+                return Ok(());
+            };
+            let filename = audio_content
+                .filename
+                .clone()
+                .unwrap_or_else(|| audio_content.body.clone());
+            let info = audio_content.info.as_deref();
+            let mime_type = info
+                .and_then(|i| i.mimetype.clone())
+                .unwrap_or_else(|| "audio/ogg".to_string());
+            let saved = match download_attachment(
+                &client,
+                &audio_content.source,
+                &filename,
+                Some(mime_type.as_str()),
+                info.and_then(|i| i.size).map(u64::from),
+                &attachment_limits(),
+                &channel.directory,
+            )
+            .await
+            {
+                Ok(rel_path) => SavedAttachment {
+                    path: format!("{}/{}", channel.directory, rel_path),
+                    mime_type,
+                },
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to download voice message");
+                    let notice = match e.downcast_ref::<AttachmentRejected>() {
+                        Some(rejected) => format!("🚫 Attachment refused: {}", rejected),
+                        None => format!("⚠️ Failed to download voice message: {}", e),
+                    };
+                    room.send(reply(RoomMessageEventContent::text_plain(notice)))
+                        .await?;
+                    return Ok(());
+                }
+            };
+            let language = session_store
+                .get_transcription_language(&channel.channel_name)
+                .unwrap_or_default();
+            let saved = std::slice::from_ref(&saved);
+            match transcribe_voice_notes(saved, transcriber, language.as_deref()).await {
+                Ok(transcripts) => {
+                    // The body is only a caption when the clip's filename is given separately
+                    let caption = match &audio_content.filename {
+                        Some(name) if *name != audio_content.body => audio_content.body.as_str(),
+                        _ => "",
+                    };
+                    with_attachment_preamble(saved, &with_voice_transcripts(&transcripts, caption))
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to transcribe voice message");
+                    room.send(reply(RoomMessageEventContent::text_plain(format!(
+                        "⚠️ Failed to transcribe voice message: {}",
                         e
-                    )))
+                    ))))
                     .await?;
                     return Ok(());
                 }
@@ -102,12 +246,21 @@ pub async fn process_chat_message(
 
     let _channel_args = channel.cli_args(); // Kept for potential future use
 
+    let length = get_response_length(&channel.directory);
+
     // Write context file for MCP tools (before Claude invocation)
+    let custom_context = session_store
+        .get_custom_context(&channel.channel_name)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load custom context");
+            Default::default()
+        });
     if let Err(e) = write_context_file(
         &channel.directory,
         room.room_id().as_str(),
         &channel.channel_name,
         &channel.session_id,
+        &custom_context,
     )
     .await
     {
@@ -147,9 +300,22 @@ pub async fn process_chat_message(
         }
     });
 
+    // One turn per channel at a time; the typing indicator stays up while queued
+    let _turn = acquire_turn(&warm_manager, &channel.room_id).await;
+
     // Invoke agent with streaming to show tool usage
     let claude_start = std::time::Instant::now();
-    metrics::record_claude_invocation("matrix");
+    metrics::record_claude_invocation("matrix", InvocationOrigin::User);
+
+    // Progress shown as reactions on the user's message (create .gorp/status-reactions to enable)
+    let annotator = MatrixPlatform::new(client.clone());
+    let mut status = StatusReactions::start(
+        is_status_reactions_enabled(&channel.directory)
+            .then_some(&annotator as &dyn MessageAnnotator),
+        room.room_id().as_str(),
+        event.event_id.as_str(),
+    )
+    .await;
 
     // Prepare session (creates session if needed)
     // Uses prepare_session_async which minimizes lock holding for concurrent access
@@ -162,9 +328,15 @@ pub async fn process_chat_message(
                 typing_handle.abort();
                 room.typing_notice(false).await?;
 
-                metrics::record_error("warm_session");
-                let error_msg = format!("⚠️ Failed to prepare session: {}", e);
-                room.send(RoomMessageEventContent::text_plain(&error_msg))
+                status.finish(false).await;
+                let error_msg = match e.downcast_ref::<SafeModeRefusal>() {
+                    Some(refusal) => refusal.to_string(),
+                    None => {
+                        metrics::record_error("warm_session");
+                        format!("⚠️ Failed to prepare session: {}", e)
+                    }
+                };
+                room.send(reply(RoomMessageEventContent::text_plain(&error_msg)))
                     .await?;
                 return Ok(());
             }
@@ -179,27 +351,130 @@ pub async fn process_chat_message(
         }
     }
 
+    // In channels with !approvals on, risky tool calls wait for a yes in the room
+    let approver: Option<Arc<dyn ToolApprover>> =
+        match session_store.get_approval_level(&channel.channel_name)? {
+            Some(level) => Some(Arc::new(ChatApprover::new(
+                approvals.clone(),
+                room.room_id().as_str(),
+                level,
+                tool_approval.clone(),
+                room_notifier(&room),
+            ))),
+            None => None,
+        };
+    // With !network off, web tools the backend still offers are refused before anyone is asked
+    let approver: Option<Arc<dyn ToolApprover>> = if is_network_allowed(&channel.directory) {
+        approver
+    } else {
+        Some(Arc::new(NetworkBlocker::new(
+            approver,
+            Some(room_notifier(&room)),
+        )))
+    };
+    let _approval_guard = match approver {
+        Some(approver) => Some(set_tool_approver(&session_handle, approver).await),
+        None => None,
+    };
+
     // Send prompt and get event receiver directly - no intermediate channel needed
     // The backend streams events through the returned EventReceiver
     tracing::info!(channel = %channel.channel_name, session_id = %session_id, "[CONCURRENCY] send_prompt START");
 
-    let mut event_rx =
-        match crate::warm_session::send_prompt_with_handle(&session_handle, &session_id, &prompt)
-            .await
-        {
-            Ok(receiver) => receiver,
-            Err(e) => {
-                let _ = typing_tx.send(());
-                typing_handle.abort();
-                room.typing_notice(false).await?;
-
-                metrics::record_error("prompt_send");
-                let error_msg = format!("⚠️ Failed to send prompt: {}", e);
-                room.send(RoomMessageEventContent::text_plain(&error_msg))
-                    .await?;
-                return Ok(());
+    // An edit may have replaced the text while the session was being prepared.
+    // Attachment prompts keep their file reference and ignore edited captions.
+    let queued = edits.take_for_send("matrix", event.event_id.as_str());
+    let prompt = match (queued, &event.content.msgtype) {
+        (Some(text), MessageType::Text(_) | MessageType::Notice(_) | MessageType::Emote(_)) => text,
+        _ => prompt,
+    };
+    // Logged as the user wrote it, before thread context and length directives
+    log_matrix_message(
+        &channel.directory,
+        room.room_id().as_str(),
+        "prompt",
+        &prompt,
+        None,
+        None,
+        None,
+        InvocationOrigin::User,
+    )
+    .await;
+    let prompt = awaiting::continue_reply(&session_store, &channel.channel_name, &prompt);
+    // Configured preprocessors (URL fetching and the like) add context, as in handle_text
+    let preprocessors = warm_manager.read().await.preprocessors();
+    let prompt = preprocessors.run(prompt, &channel).await;
+    let prompt = match &thread {
+        Some(root) => match root_message_body(&room, root).await {
+            Some(root_body) => with_thread_context(&root_body, &prompt),
+            None => prompt,
+        },
+        None => prompt,
+    };
+    let prompt = apply_directive(&prompt, length);
+
+    // People asking the same thing of an unchanged workspace get the earlier answer (!cache on)
+    let cache = match response_cache::lookup(&session_store, &channel, &prompt) {
+        Lookup::Hit(response) => {
+            let _ = typing_tx.send(());
+            typing_handle.abort();
+            room.typing_notice(false).await?;
+            status.finish(true).await;
+
+            let response = with_footer(&cached_reply(&response), footer);
+            let held = hold_if_outside_window(
+                &session_store,
+                &channel.channel_name,
+                Some(("matrix", room.room_id().as_str())),
+                &response,
+                DeliveryPriority::Normal,
+            )?;
+            if !held {
+                for chunk in chunk_message(&response, MAX_CHUNK_SIZE) {
+                    let html = markdown_to_html(&chunk);
+                    remember(&send_html(&room, &chunk, &html, signer.as_ref(), &reply).await?);
+                    metrics::record_message_sent();
+                }
             }
-        };
+            log_matrix_message(
+                &channel.directory,
+                room.room_id().as_str(),
+                "response",
+                &response,
+                None,
+                None,
+                None,
+                InvocationOrigin::User,
+            )
+            .await;
+            return Ok(());
+        }
+        Lookup::Miss(miss) => Some(miss),
+        Lookup::Off => None,
+    };
+
+    let mut event_rx = match crate::warm_session::send_prompt_with_handle(
+        &session_handle,
+        &session_id,
+        &prompt,
+        InvocationOrigin::User,
+    )
+    .await
+    {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            let _ = typing_tx.send(());
+            typing_handle.abort();
+            room.typing_notice(false).await?;
+
+            metrics::record_error("prompt_send");
+            status.finish(false).await;
+            let error_msg = format!("⚠️ Failed to send prompt: {}", e);
+            room.send(reply(RoomMessageEventContent::text_plain(&error_msg)))
+                .await?;
+            return Ok(());
+        }
+    };
 
     tracing::info!(channel = %channel.channel_name, "[CONCURRENCY] send_prompt DONE - got receiver");
 
@@ -210,80 +485,107 @@ pub async fn process_chat_message(
         tracing::debug!(channel = %channel.channel_name, "Debug mode enabled - will show tool usage");
     }
 
+    // Streaming edits a placeholder as text arrives (create .gorp/stream-responses to enable).
+    // Output that will be held for the delivery window is never streamed.
+    let window_closed = should_hold(
+        session_store
+            .get_delivery_window(&channel.channel_name)?
+            .as_ref(),
+        DeliveryPriority::Normal,
+        Utc::now(),
+    );
+    // The streamer posts top-level messages, so threaded replies arrive whole.
+    let mut streamer = if is_streaming_enabled(&channel.directory)
+        && !window_closed
+        && thread.is_none()
+    {
+        match ResponseStreamer::start(&room).await {
+            Ok(streamer) => Some(streamer),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to send streaming placeholder, falling back to chunked send");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Process streaming events from agent
     let mut final_response = String::new();
     let mut tools_used: Vec<String> = Vec::new();
     let mut session_id_from_event: Option<String> = None;
+    // Error notices are worded in the channel's language
+    let locale = i18n::channel_locale(&session_store, &channel.channel_name, event.sender.as_str());
 
     tracing::info!(channel = %channel.channel_name, "[CONCURRENCY] event_loop START - waiting for events");
     let mut event_count = 0;
 
     while let Some(event) = event_rx.recv().await {
         event_count += 1;
-        tracing::trace!(channel = %channel.channel_name, event_count, event = ?event, "Received agent event");
+        tracing::trace!(
+            channel = %channel.channel_name,
+            event_count,
+            event = %loggable_content(&format!("{:?}", event)),
+            "Received agent event"
+        );
         match event {
             AgentEvent::ToolStart { name, input, .. } => {
                 tools_used.push(name.clone());
                 metrics::record_tool_used(&name);
+                status.tool_started().await;
 
                 // When tool output is hidden, add paragraph break between text blocks
                 // This ensures text before and after tool usage is visually separated
-                if !debug_enabled
-                    && !final_response.is_empty()
-                    && !final_response.ends_with('\n')
-                {
+                if !debug_enabled && !final_response.is_empty() && !final_response.ends_with('\n') {
                     final_response.push_str("\n\n");
                 }
 
-                // Extract input preview from JSON input
-                let input_preview: String = input
-                    .as_object()
-                    .and_then(|o| o.get("command").or(o.get("file_path")).or(o.get("pattern")))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.chars().take(50).collect())
-                    .unwrap_or_default();
-
                 // Only send tool notifications if debug mode is enabled
                 if debug_enabled {
-                    // Build tool message with plain and HTML versions
-                    let (plain, html) = if input_preview.is_empty() {
-                        (format!("🔧 {}", name), format!("🔧 <code>{}</code>", name))
-                    } else {
-                        (
-                            format!("🔧 {} · {}", name, input_preview),
-                            format!("🔧 <code>{}</code> · <code>{}</code>", name, input_preview),
-                        )
-                    };
+                    let (plain, html) = format_tool_notice(&name, &input);
 
                     // Send tool notification to room
-                    if let Err(e) = room
-                        .send(RoomMessageEventContent::text_html(&plain, &html))
-                        .await
-                    {
-                        tracing::warn!(error = %e, "Failed to send tool notification");
-                    } else {
-                        log_matrix_message(
-                            &channel.directory,
-                            room.room_id().as_str(),
-                            "tool_notification",
-                            &plain,
-                            Some(&html),
-                            None,
-                            None,
-                        )
-                        .await;
+                    match send_html(&room, &plain, &html, None, &reply).await {
+                        Err(e) => tracing::warn!(error = %e, "Failed to send tool notification"),
+                        Ok(sent) => {
+                            remember(&sent);
+                            log_matrix_message(
+                                &channel.directory,
+                                room.room_id().as_str(),
+                                "tool_notification",
+                                &plain,
+                                Some(&html),
+                                None,
+                                None,
+                                InvocationOrigin::User,
+                            )
+                            .await;
+                        }
                     }
                 }
             }
             AgentEvent::ToolEnd { .. } => {
-                // Tool completion - just log for now
                 tracing::debug!("Tool completed");
+                status.tool_finished().await;
             }
             AgentEvent::Text(text) => {
                 // Accumulate text chunks
                 final_response.push_str(&text);
+                if let Some(streamer) = streamer.as_mut() {
+                    if let Err(e) = streamer.update(&final_response).await {
+                        tracing::warn!(error = %e, "Failed to edit streamed response");
+                    }
+                }
             }
-            AgentEvent::Result { text, .. } => {
+            AgentEvent::Result { text, usage, .. } => {
+                if let Err(e) = session_store.record_usage(
+                    &channel.channel_name,
+                    InvocationOrigin::User,
+                    usage.as_ref(),
+                ) {
+                    tracing::warn!(error = %e, "Failed to record channel usage");
+                }
+
                 // Final result - use the accumulated text if we have it, otherwise use result text
                 if !final_response.is_empty() {
                     // We already accumulated text, result is just completion marker
@@ -305,6 +607,13 @@ pub async fn process_chat_message(
                 let _ = typing_tx.send(());
                 typing_handle.abort();
                 room.typing_notice(false).await?;
+                if let Some(streamer) = streamer.take() {
+                    for sent in streamer.sent_event_ids() {
+                        remember(&sent);
+                    }
+                    streamer.cancel().await;
+                }
+                status.finish(false).await;
 
                 // Check for session orphaned error
                 if code == gorp_agent::ErrorCode::SessionOrphaned {
@@ -328,14 +637,22 @@ pub async fn process_chat_message(
                         "Evicted warm session after orphaned session"
                     );
                     metrics::record_error("invalid_session");
-                    room.send(RoomMessageEventContent::text_plain(
-                        "🔄 Session was reset (conversation data was lost). Please send your message again.",
-                    ))
+                    room.send(reply(RoomMessageEventContent::text_plain(t(
+                        &locale,
+                        "error.session_reset",
+                    ))))
                     .await?;
                 } else {
                     metrics::record_error("agent_streaming");
-                    let error_msg = format!("⚠️ Agent error: {}", message);
-                    room.send(RoomMessageEventContent::text_plain(&error_msg))
+                    let error_msg = tf(
+                        &locale,
+                        "error.agent",
+                        &[
+                            ("title", &t(&locale, i18n::error_title_key(code))),
+                            ("message", &message),
+                        ],
+                    );
+                    room.send(reply(RoomMessageEventContent::text_plain(&error_msg)))
                         .await?;
                 }
                 return Ok(());
@@ -344,8 +661,15 @@ pub async fn process_chat_message(
                 let _ = typing_tx.send(());
                 typing_handle.abort();
                 room.typing_notice(false).await?;
+                if let Some(streamer) = streamer.take() {
+                    for sent in streamer.sent_event_ids() {
+                        remember(&sent);
+                    }
+                    streamer.cancel().await;
+                }
 
                 tracing::warn!(reason = %reason, "Session invalid");
+                status.finish(false).await;
                 // Reset the session so next message starts fresh
                 if let Err(e) = session_store.reset_orphaned_session(room.room_id().as_str()) {
                     tracing::error!(error = %e, "Failed to reset invalid session");
@@ -366,9 +690,10 @@ pub async fn process_chat_message(
                     "Evicted warm session after invalid session"
                 );
                 metrics::record_error("invalid_session");
-                room.send(RoomMessageEventContent::text_plain(
-                    "🔄 Session was reset (conversation data was lost). Please send your message again.",
-                ))
+                room.send(reply(RoomMessageEventContent::text_plain(t(
+                    &locale,
+                    "error.session_reset",
+                ))))
                 .await?;
                 return Ok(());
             }
@@ -408,17 +733,25 @@ pub async fn process_chat_message(
         let _ = typing_tx.send(());
         typing_handle.abort();
         room.typing_notice(false).await?;
+        if let Some(streamer) = streamer.take() {
+            for sent in streamer.sent_event_ids() {
+                remember(&sent);
+            }
+            streamer.cancel().await;
+        }
 
         let backend_type = warm_manager.read().await.backend_type().to_string();
         metrics::record_error("agent_no_response");
-        room.send(RoomMessageEventContent::text_plain(format!(
+        status.finish(false).await;
+        room.send(reply(RoomMessageEventContent::text_plain(format!(
             "⚠️ {} backend finished without a response",
             backend_type
-        )))
+        ))))
         .await?;
         return Ok(());
     }
 
+    status.finish(true).await;
     let claude_duration = claude_start.elapsed().as_secs_f64();
     let backend_type = warm_manager.read().await.backend_type().to_string();
     metrics::record_claude_duration(claude_duration);
@@ -432,7 +765,14 @@ pub async fn process_chat_message(
 
     // Filter out XML function call blocks before sending to Matrix
     // Some backends may output raw XML that shouldn't be shown to users
-    let response = strip_function_calls(&final_response);
+    let response = enforce_length(&strip_function_calls(&final_response), length);
+    let (max_chars, save_full) = {
+        let mgr = warm_manager.read().await;
+        (mgr.max_response_chars(), mgr.save_truncated_responses())
+    };
+    let response = cap_response(response, &channel.directory, max_chars, save_full);
+    let (response, asked) =
+        awaiting::settle_response(&session_store, &channel.channel_name, &response);
 
     // Update session ID if Claude CLI reported a new one via SessionChanged event
     // This is critical for session continuity - the CLI generates its own session IDs
@@ -469,6 +809,90 @@ pub async fn process_chat_message(
     // Mark session as started BEFORE sending response (to ensure consistency)
     session_store.mark_started(room.room_id().as_str())?;
 
+    // A manifest from the agent replaces its plain reply with text, files and actions
+    let workspace = std::path::Path::new(&channel.directory);
+    let rich = pending_response(workspace);
+    let response_text = match &rich {
+        Some(rich) => rich.to_plain_text(),
+        None => response,
+    };
+    if let (Some(miss), None, false) = (cache, &rich, asked) {
+        response_cache::remember(&session_store, &channel, miss, &response_text);
+    }
+
+    // Streamed output is already visible, so finish it in place
+    if let Some(streamer) = streamer {
+        let _ = typing_tx.send(());
+        let _ = typing_handle.await;
+        room.typing_notice(false).await?;
+
+        let finished = streamer
+            .finish(
+                &with_footer(&response_text, footer),
+                &channel.directory,
+                signer.as_ref(),
+            )
+            .await;
+        for sent in streamer.sent_event_ids() {
+            remember(&sent);
+        }
+        let chunk_count = finished?;
+        if let Some(rich) = &rich {
+            // The finished text already lists the files; send the files themselves after it
+            let attachments = render_parts(workspace, rich)
+                .await
+                .into_iter()
+                .filter(|part| {
+                    matches!(
+                        part,
+                        RenderedPart::Content(MessageContent::Attachment { .. })
+                    )
+                })
+                .collect();
+            send_rendered_parts(
+                &room,
+                &client,
+                thread.as_deref(),
+                signer,
+                attachments,
+                remember,
+            )
+            .await?;
+        }
+        let total_duration = start_time.elapsed().as_secs_f64();
+        metrics::record_message_processing_duration(total_duration);
+        tracing::info!(chunk_count, "Streamed response finalized");
+        return Ok(());
+    }
+
+    // Outside the channel's delivery window the response is queued, not sent
+    if hold_if_outside_window(
+        &session_store,
+        &channel.channel_name,
+        Some(("matrix", room.room_id().as_str())),
+        &response_text,
+        DeliveryPriority::Normal,
+    )? {
+        let _ = typing_tx.send(());
+        let _ = typing_handle.await;
+        room.typing_notice(false).await?;
+        return Ok(());
+    }
+
+    if let Some(rich) = &rich {
+        let parts = render_parts(workspace, rich).await;
+        let _ = typing_tx.send(());
+        let _ = typing_handle.await;
+        room.typing_notice(false).await?;
+
+        send_rendered_parts(&room, &client, thread.as_deref(), signer, parts, remember).await?;
+        let total_duration = start_time.elapsed().as_secs_f64();
+        metrics::record_message_processing_duration(total_duration);
+        tracing::info!("Multi-part response sent");
+        return Ok(());
+    }
+    let response = with_footer(&response_text, footer);
+
     // Send response with markdown formatting, chunked if too long
     // Matrix limit is ~65KB but we chunk for better display
     let chunks = chunk_message(&response, MAX_CHUNK_SIZE);
@@ -479,8 +903,7 @@ pub async fn process_chat_message(
     // This ensures user sees message arriving before "stopped typing"
     if let Some((i, chunk)) = chunks_iter.next() {
         let html = markdown_to_html(&chunk);
-        room.send(RoomMessageEventContent::text_html(&chunk, &html))
-            .await?;
+        remember(&send_html(&room, &chunk, &html, signer.as_ref(), &reply).await?);
         metrics::record_message_sent();
 
         // Now stop typing indicator - user already sees first chunk arriving
@@ -501,6 +924,7 @@ pub async fn process_chat_message(
             } else {
                 None
             },
+            InvocationOrigin::User,
         )
         .await;
 
@@ -513,8 +937,7 @@ pub async fn process_chat_message(
     // Send remaining chunks
     for (i, chunk) in chunks_iter {
         let html = markdown_to_html(&chunk);
-        room.send(RoomMessageEventContent::text_html(&chunk, &html))
-            .await?;
+        remember(&send_html(&room, &chunk, &html, signer.as_ref(), &reply).await?);
         metrics::record_message_sent();
 
         // Log the Matrix message
@@ -530,6 +953,7 @@ pub async fn process_chat_message(
             } else {
                 None
             },
+            InvocationOrigin::User,
         )
         .await;
 
@@ -547,3 +971,49 @@ pub async fn process_chat_message(
 
     Ok(())
 }
+
+/// Send manifest parts to a Matrix room, in its thread if there is one, passing each
+/// sent event to `remember`. Matrix has no buttons, so suggested actions arrive as a numbered list.
+async fn send_rendered_parts(
+    room: &Room,
+    client: &Client,
+    thread: Option<&EventId>,
+    signer: Option<Signer>,
+    parts: Vec<RenderedPart>,
+    remember: impl Fn(&EventId),
+) -> Result<()> {
+    let channel = MatrixChannel::new(room.clone(), client.clone()).signed_by(signer);
+    for part in parts {
+        let content = match part {
+            RenderedPart::Content(content) => content,
+            RenderedPart::Actions { text, actions } => {
+                MessageContent::plain(actions_as_list(Some(&text), &actions))
+            }
+        };
+        let sent = channel
+            .post(content, thread.map(|root| root.as_str()))
+            .await?;
+        remember(&sent);
+        metrics::record_message_sent();
+    }
+    Ok(())
+}
+
+/// Post tool approval requests into the room. The approver is polled on the
+/// agent backend's thread, so sends are spawned on the runtime that owns the client.
+fn room_notifier(room: &Room) -> Notify {
+    let room = room.clone();
+    let runtime = tokio::runtime::Handle::current();
+    Arc::new(move |text: String| {
+        let room = room.clone();
+        let sent = runtime
+            .spawn(async move { room.send(RoomMessageEventContent::text_plain(text)).await });
+        Box::pin(async move {
+            match sent.await {
+                Ok(Ok(_)) => metrics::record_message_sent(),
+                Ok(Err(e)) => tracing::warn!(error = %e, "Failed to post tool approval message"),
+                Err(e) => tracing::warn!(error = %e, "Tool approval message task failed"),
+            }
+        })
+    })
+}
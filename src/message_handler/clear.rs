@@ -0,0 +1,48 @@
+// ABOUTME: !clear: start the channel's agent on a fresh session while keeping its workspace.
+// ABOUTME: Drops the warm session and any question the old session was waiting on.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::SessionStore,
+    warm_session::SharedWarmSessionManager,
+};
+
+/// `!clear`: forget the agent's conversation in this channel
+pub(crate) async fn handle_clear_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    sender: &str,
+    locale: &str,
+) -> Result<()> {
+    // A DM can have a channel attached on some platforms, so look it up either way
+    let Some(ch) = session_store.get_by_room(channel.id())? else {
+        channel
+            .send(MessageContent::plain(t(locale, "command.no_channel")))
+            .await?;
+        return Ok(());
+    };
+
+    let new_session_id = uuid::Uuid::new_v4().to_string();
+    session_store.reset_session(&ch.channel_name, &new_session_id)?;
+    // A question the old session asked has nobody left to answer it
+    session_store.set_awaiting_reply(&ch.channel_name, None)?;
+    let evicted = warm_manager
+        .write()
+        .await
+        .invalidate_session(&ch.channel_name)
+        .is_some();
+    tracing::info!(
+        channel = %ch.channel_name,
+        old_session = %ch.session_id,
+        new_session = %new_session_id,
+        evicted,
+        sender,
+        "Session cleared via command"
+    );
+    let reply = tf(locale, "clear.done", &[("channel", &ch.channel_name)]);
+    channel.send(MessageContent::plain(reply)).await
+}
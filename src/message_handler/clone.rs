@@ -0,0 +1,417 @@
+// ABOUTME: !clone - replicate a channel on another platform through its ChannelCreator.
+// ABOUTME: Forks copy settings, templates, .gorp/ files and schedules; shares bind to the same session.
+
+use anyhow::{bail, Context, Result};
+use gorp_core::traits::MessagingPlatform;
+
+use crate::bus::MessageBus;
+use crate::scheduler::{ScheduleStatus, ScheduledPrompt, SchedulerStore};
+use crate::session::{Channel, SessionStore};
+
+use super::helpers::validate_channel_name;
+
+pub const USAGE: &str = "Usage: !clone <channel> --to <platform> [--fork|--share]\n\
+--fork (default) makes an independent copy with its own session.\n\
+--share joins the new channel to the original session, so both continue one conversation.";
+
+/// How the clone relates to the original channel's conversation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneMode {
+    /// A new channel with its own session, configured like the original
+    Fork,
+    /// The new platform channel is bound to the original channel's session
+    Share,
+}
+
+/// A parsed `!clone` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloneRequest {
+    pub source: String,
+    pub platform: String,
+    pub mode: CloneMode,
+}
+
+/// What `clone_channel` created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClonedChannel {
+    pub platform: String,
+    /// Platform's ID for the new channel
+    pub channel_id: String,
+    /// gorp channel the new platform channel talks to: the fork, or the original when shared
+    pub channel_name: String,
+    pub mode: CloneMode,
+    pub schedules_copied: usize,
+}
+
+/// Parse the arguments of `!clone <channel> --to <platform> [--fork|--share]`
+pub fn parse_clone_args(args: &[&str]) -> Result<CloneRequest> {
+    let mut source = None;
+    let mut platform = None;
+    let mut mode = CloneMode::Fork;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.to_lowercase().as_str() {
+            "--to" => match args.next() {
+                Some(target) => platform = Some(target.to_lowercase()),
+                None => bail!("--to needs a platform, e.g. --to slack"),
+            },
+            "--fork" => mode = CloneMode::Fork,
+            "--share" => mode = CloneMode::Share,
+            flag if flag.starts_with("--") => bail!("Unknown option {}", arg),
+            name if source.is_none() => source = Some(name.to_string()),
+            _ => bail!("Only one channel can be cloned at a time"),
+        }
+    }
+
+    let Some(source) = source else {
+        bail!("Which channel should be cloned?");
+    };
+    let Some(platform) = platform else {
+        bail!(
+            "Which platform should {} be cloned to? Add --to <platform>",
+            source
+        );
+    };
+    Ok(CloneRequest {
+        source,
+        platform,
+        mode,
+    })
+}
+
+/// Name of the forked channel: the original's name with the platform appended
+pub fn fork_name(source: &str, platform: &str) -> String {
+    format!("{}-{}", source, platform)
+}
+
+/// Create a channel on `target` that mirrors the `request.source` channel.
+///
+/// The new platform channel is bound to its gorp channel in the store and,
+/// when given, on the bus, so it routes without a restart.
+pub async fn clone_channel(
+    request: &CloneRequest,
+    target: &dyn MessagingPlatform,
+    session_store: &SessionStore,
+    scheduler_store: &SchedulerStore,
+    bus: Option<&MessageBus>,
+) -> Result<ClonedChannel> {
+    let source = session_store
+        .get_by_name(&request.source)?
+        .with_context(|| format!("No channel named '{}'", request.source))?;
+    if source.is_dispatch_room {
+        bail!("DISPATCH rooms can't be cloned");
+    }
+    let platform_id = target.platform_id();
+    let creator = target
+        .channel_creator()
+        .with_context(|| format!("{} can't create channels", platform_id))?;
+
+    let (channel_name, channel_id, schedules_copied) = match request.mode {
+        CloneMode::Share => {
+            // Matrix finds a room's channel by room ID, not through bindings
+            if platform_id == "matrix" {
+                bail!("A Matrix room can't share another channel's session; clone with --fork");
+            }
+            let channel_id = creator.create_channel(&source.channel_name).await?;
+            (source.channel_name.clone(), channel_id, 0)
+        }
+        CloneMode::Fork => {
+            let name = fork_name(&source.channel_name, platform_id);
+            if let Err(reason) = validate_channel_name(&name) {
+                bail!("Can't fork to '{}': {}", name, reason);
+            }
+            if session_store.get_by_name(&name)?.is_some() {
+                bail!("A channel named '{}' already exists", name);
+            }
+            let channel_id = creator.create_channel(&name).await?;
+            let forked = session_store.create_channel_on(platform_id, &name, &channel_id)?;
+            session_store.copy_channel_config(&source, &forked)?;
+            let copied = copy_schedules(scheduler_store, &source, &forked)?;
+            (name, channel_id, copied)
+        }
+    };
+
+    session_store.bind_channel(platform_id, &channel_id, &channel_name)?;
+    if let Some(bus) = bus {
+        bus.bind_channel_async(platform_id, &channel_id, &channel_name)
+            .await;
+    }
+
+    tracing::info!(
+        source = %source.channel_name,
+        platform = %platform_id,
+        channel_id = %channel_id,
+        channel_name = %channel_name,
+        mode = ?request.mode,
+        schedules_copied,
+        "Cloned channel"
+    );
+
+    Ok(ClonedChannel {
+        platform: platform_id.to_string(),
+        channel_id,
+        channel_name,
+        mode: request.mode,
+        schedules_copied,
+    })
+}
+
+/// Copy the source channel's active and paused schedules onto the fork.
+/// The copies start fresh: new IDs, no run history.
+fn copy_schedules(
+    scheduler_store: &SchedulerStore,
+    source: &Channel,
+    target: &Channel,
+) -> Result<usize> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut copied = 0;
+    for schedule in scheduler_store.list_by_channel(&source.channel_name)? {
+        if !matches!(
+            schedule.status,
+            ScheduleStatus::Active | ScheduleStatus::Paused
+        ) {
+            continue;
+        }
+        scheduler_store.create_schedule(&ScheduledPrompt {
+            id: uuid::Uuid::new_v4().to_string(),
+            channel_name: target.channel_name.clone(),
+            room_id: target.room_id.clone(),
+            created_at: now.clone(),
+            last_executed_at: None,
+            error_message: None,
+            execution_count: 0,
+            ..schedule
+        })?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Reply describing a finished clone
+pub fn describe(request: &CloneRequest, cloned: &ClonedChannel) -> String {
+    match cloned.mode {
+        CloneMode::Fork => format!(
+            "📋 Cloned {} to {} as {} ({}).\n\
+            Copied its settings, templates, .gorp/ files and {} schedule(s). The clone has its own session.",
+            request.source,
+            cloned.platform,
+            cloned.channel_name,
+            cloned.channel_id,
+            cloned.schedules_copied
+        ),
+        CloneMode::Share => format!(
+            "📋 Cloned {} to {} ({}).\n\
+            Both channels share {}'s session, settings and schedules, so they continue one conversation.",
+            request.source, cloned.platform, cloned.channel_id, cloned.channel_name
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_handler::traits::MockPlatform;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn stores() -> (SessionStore, SchedulerStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let session_store = SessionStore::new(dir.path()).unwrap();
+        let scheduler_store = SchedulerStore::new(session_store.db_connection());
+        scheduler_store.initialize_schema().unwrap();
+        (session_store, scheduler_store, dir)
+    }
+
+    fn schedule(channel: &Channel, prompt: &str, status: ScheduleStatus) -> ScheduledPrompt {
+        ScheduledPrompt {
+            id: uuid::Uuid::new_v4().to_string(),
+            channel_name: channel.channel_name.clone(),
+            room_id: channel.room_id.clone(),
+            prompt: prompt.to_string(),
+            created_by: "@alice:m.org".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            execute_at: None,
+            cron_expression: Some("0 9 * * *".to_string()),
+            last_executed_at: Some(chrono::Utc::now().to_rfc3339()),
+            next_execution_at: chrono::Utc::now().to_rfc3339(),
+            status,
+            error_message: None,
+            execution_count: 12,
+        }
+    }
+
+    fn request(args: &[&str]) -> CloneRequest {
+        parse_clone_args(args).unwrap()
+    }
+
+    #[test]
+    fn test_parse_clone_args() {
+        assert_eq!(
+            request(&["research", "--to", "Slack"]),
+            CloneRequest {
+                source: "research".to_string(),
+                platform: "slack".to_string(),
+                mode: CloneMode::Fork,
+            }
+        );
+        assert_eq!(
+            request(&["--share", "research", "--to", "slack"]).mode,
+            CloneMode::Share
+        );
+
+        for (args, error) in [
+            (&["research"][..], "Add --to <platform>"),
+            (&["--to", "slack"][..], "Which channel"),
+            (&["research", "--to"][..], "--to needs a platform"),
+            (
+                &["research", "news", "--to", "slack"][..],
+                "Only one channel",
+            ),
+            (
+                &["research", "--to", "slack", "--copy"][..],
+                "Unknown option",
+            ),
+        ] {
+            let err = parse_clone_args(args).unwrap_err().to_string();
+            assert!(err.contains(error), "{:?}: {}", args, err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fork_copies_config_prompts_and_schedules() {
+        let (session_store, scheduler_store, _dir) = stores();
+        let source = session_store
+            .create_channel("research", "!research:m.org")
+            .unwrap();
+        session_store
+            .update_backend_type("research", Some("mux"))
+            .unwrap();
+        session_store
+            .save_template("research", "daily", "Summarize arXiv", "@alice:m.org")
+            .unwrap();
+        let gorp_dir = Path::new(&source.directory).join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        std::fs::write(gorp_dir.join("warmup_prompt"), "You are a researcher").unwrap();
+        scheduler_store
+            .create_schedule(&schedule(&source, "morning digest", ScheduleStatus::Active))
+            .unwrap();
+        scheduler_store
+            .create_schedule(&schedule(&source, "old report", ScheduleStatus::Completed))
+            .unwrap();
+        let slack = MockPlatform::new("slack");
+
+        let request = request(&["research", "--to", "slack"]);
+        let cloned = clone_channel(&request, &slack, &session_store, &scheduler_store, None)
+            .await
+            .unwrap();
+
+        assert_eq!(slack.created_channels(), vec!["research-slack"]);
+        assert_eq!(cloned.channel_id, "slack-channel-1");
+        assert_eq!(cloned.channel_name, "research-slack");
+        assert_eq!(cloned.schedules_copied, 1);
+
+        let fork = session_store
+            .get_by_name("research-slack")
+            .unwrap()
+            .unwrap();
+        assert_eq!(fork.room_id, "slack-channel-1");
+        assert_eq!(fork.backend_type.as_deref(), Some("mux"));
+        assert_ne!(fork.session_id, source.session_id);
+        assert!(session_store
+            .get_template("research-slack", "daily")
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            std::fs::read_to_string(Path::new(&fork.directory).join(".gorp/warmup_prompt"))
+                .unwrap(),
+            "You are a researcher"
+        );
+        assert_eq!(
+            session_store
+                .resolve_binding("slack", "slack-channel-1")
+                .unwrap()
+                .as_deref(),
+            Some("research-slack")
+        );
+
+        let schedules = scheduler_store.list_by_channel("research-slack").unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].prompt, "morning digest");
+        assert_eq!(schedules[0].room_id, "slack-channel-1");
+        assert_eq!(schedules[0].execution_count, 0);
+        assert!(schedules[0].last_executed_at.is_none());
+        assert_eq!(
+            scheduler_store.list_by_channel("research").unwrap().len(),
+            2
+        );
+
+        let err = clone_channel(&request, &slack, &session_store, &scheduler_store, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert_eq!(slack.created_channels().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_share_binds_the_new_channel_to_the_original_session() {
+        let (session_store, scheduler_store, _dir) = stores();
+        session_store
+            .create_channel("research", "!research:m.org")
+            .unwrap();
+        let slack = MockPlatform::new("slack");
+        let bus = MessageBus::new(16);
+
+        let request = request(&["research", "--to", "slack", "--share"]);
+        let cloned = clone_channel(
+            &request,
+            &slack,
+            &session_store,
+            &scheduler_store,
+            Some(&bus),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(slack.created_channels(), vec!["research"]);
+        assert_eq!(cloned.channel_name, "research");
+        assert!(session_store
+            .get_by_name("research-slack")
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            session_store
+                .resolve_binding("slack", "slack-channel-1")
+                .unwrap()
+                .as_deref(),
+            Some("research")
+        );
+        assert!(matches!(
+            bus.resolve_target_async("slack", "slack-channel-1").await,
+            crate::bus::SessionTarget::Session { name } if name == "research"
+        ));
+        assert!(describe(&request, &cloned).contains("share research's session"));
+    }
+
+    #[tokio::test]
+    async fn test_clone_refuses_unknown_channels_and_share_to_matrix() {
+        let (session_store, scheduler_store, _dir) = stores();
+        session_store
+            .create_channel("research", "!research:m.org")
+            .unwrap();
+        let matrix = MockPlatform::new("matrix");
+
+        let missing = request(&["nonesuch", "--to", "matrix"]);
+        let err = clone_channel(&missing, &matrix, &session_store, &scheduler_store, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No channel named 'nonesuch'"));
+
+        let share = request(&["research", "--to", "matrix", "--share"]);
+        let err = clone_channel(&share, &matrix, &session_store, &scheduler_store, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("clone with --fork"));
+        assert!(matrix.created_channels().is_empty());
+    }
+}
@@ -4,13 +4,47 @@
 use anyhow::Result;
 use gorp_core::traits::{ChatChannel, MessageContent};
 use matrix_sdk::Client;
+use std::time::Duration;
 
 use crate::{
-    commands::Command, config::Config, metrics, scheduler::SchedulerStore, session::SessionStore,
-    utils::markdown_to_html, warm_session::SharedWarmSessionManager,
+    command_catalog::CommandCatalog,
+    commands::{Command, DEFAULT_COMMAND_PREFIX},
+    config::Config,
+    i18n::{self, t, tf},
+    metrics,
+    runtime_mode::{self, Gate},
+    scheduler::SchedulerStore,
+    session::{Channel, SessionStore},
+    utils::markdown_to_html,
+    warm_session::SharedWarmSessionManager,
 };
 
+use super::approvals::handle_approvals_command;
+use super::ask::handle_ask_command;
+use super::attachments::handle_attachments_command;
+use super::clear::handle_clear_command;
+use super::compare::handle_compare_command;
+use super::context::handle_context_command;
+use super::deliver::handle_deliver_command;
+use super::health::{handle_health_command, handle_safemode_command};
 use super::helpers::is_debug_enabled;
+use super::history::handle_history_command;
+use super::invite::handle_invite_command;
+use super::locale::handle_locale_command;
+use super::models::{handle_model_command, handle_models_command};
+use super::network::handle_network_command;
+use super::pins::{handle_pin_command, handle_pins_command};
+use super::prompt_templates::handle_template_command;
+use super::repo::handle_repo_command;
+use super::response_cache::handle_cache_command;
+use super::response_length::handle_length_command;
+use super::send_guard::{handle_append_command, handle_discard_command, handle_sendguard_command};
+use super::spend::{handle_budget_command, handle_usage_command};
+use super::status_reactions::handle_reactions_command;
+use super::streaming::handle_stream_command;
+use super::summarize::handle_summarize_command;
+use super::telegram_topics::{self, handle_topic_create_command};
+use super::transcription_language::handle_transcription_command;
 
 /// Help documentation loaded at compile time
 const HELP_MD: &str = include_str!("../../docs/HELP.md");
@@ -31,7 +65,7 @@ pub async fn handle_command<C: ChatChannel>(
     session_store: &SessionStore,
     _scheduler_store: &SchedulerStore,
     _client: Option<&Client>,
-    _sender: &str,
+    sender: &str,
     is_dm: bool,
     config: &Config,
     warm_manager: &SharedWarmSessionManager,
@@ -45,12 +79,16 @@ pub async fn handle_command<C: ChatChannel>(
         let help_msg = if is_dm {
             "💬 Orchestrator Commands:\n\
             !create <name> - Create new channel\n\
+            !clone <name> --to <platform> - Copy a channel to another platform\n\
             !join <name> - Get invited to a channel\n\
             !delete <name> - Remove channel (keeps workspace)\n\
             !reset <name> - Reset channel session remotely\n\
             !cleanup - Leave orphaned rooms\n\
             !restore-rooms - Restore channels from workspace directories\n\
             !list - Show all channels\n\
+            !backend [name] - Show or switch the default backend\n\
+            !locale [code] - Set your language\n\
+            !sendguard - Hold long messages until you !send them\n\
             !help - Show detailed help"
         } else {
             "Available commands:\n\
@@ -58,20 +96,69 @@ pub async fn handle_command<C: ChatChannel>(
             !help - Show detailed help\n\
             !status - Show current channel info\n\
             !backend - View/change backend for this channel\n\
+            !model - View/change model for this channel\n\
+            !repo - Check out a git repo into the workspace\n\
+            !network - Allow or block the agent's web tools\n\
+            !clear - Start a fresh agent session, keeping the workspace\n\
             !debug - Toggle tool usage display\n\
+            !stream - Toggle streaming responses\n\
+            !reactions - Toggle status reactions\n\
+            !usage - Show token usage and cost\n\
+            !length - Set brief/normal/detailed answers\n\
+            !cache - Reuse answers to repeat questions\n\
+            !attachments - Show or change attachment limits\n\
+            !transcription - Set the language voice notes are in\n\
+            !locale [code] - Set this channel's language\n\
+            !sendguard - Hold long messages until you !send them\n\
+            !pin [note] - Save the last response (or reply to a message)\n\
+            !pins - List saved responses\n\
+            !context - View or extend the MCP context file\n\
+            !compare <a> <b> <prompt> - Run a prompt on two backends\n\
+            !ask <question> - One-off answer outside the main session\n\
+            !invite <user> - Invite someone into this room\n\
+            !summarize - Bullet recap of this channel's history\n\
+            !topic <text> - Change this room's topic\n\
+            !promote <user> - Raise someone's power level in this room\n\
             !leave - Bot leaves this room"
         };
         channel.send(MessageContent::plain(help_msg)).await?;
         return Ok(());
     }
 
+    let locale = command_locale(session_store, channel.id(), sender, is_dm)?;
+
+    // Same check for every platform: roles are keyed by the sender ID it reports,
+    // with the platform's own [access] lists taking precedence
+    let platform_id = channel.platform_id();
+    if !config.can_run(platform_id, sender, command) {
+        tracing::info!(
+            sender,
+            platform = platform_id,
+            command,
+            "Refusing admin command from non-admin"
+        );
+        let refusal = tf(&locale, "command.admin_only", &[("command", command)]);
+        reply_privately(channel, sender, MessageContent::plain(refusal)).await?;
+        return Ok(());
+    }
+
+    let runtime = warm_manager.read().await.runtime_mode();
+    if let Err(refusal) = runtime.check(Gate::Command {
+        name: command,
+        args: &cmd.args,
+    }) {
+        reply_privately(channel, sender, MessageContent::plain(refusal.message())).await?;
+        return Ok(());
+    }
+
     metrics::record_command(command);
 
     match command {
         "help" => {
-            let help_html = markdown_to_html(HELP_MD);
+            let help = help_with_aliases(&config.command_catalog(), &cmd.prefix);
+            let help_html = markdown_to_html(&help);
             channel
-                .send(MessageContent::html(HELP_MD, &help_html))
+                .send(MessageContent::html(&help, &help_html))
                 .await?;
         }
         "changelog" => {
@@ -93,10 +180,23 @@ pub async fn handle_command<C: ChatChannel>(
                 } else {
                     "🔇 Disabled (tool usage hidden)"
                 };
-                let backend_display = ch
-                    .backend_type
-                    .as_deref()
-                    .unwrap_or(&config.backend.backend_type);
+                let default_backend = warm_manager.read().await.backend_type().to_string();
+                let backend_display = ch.backend_type.as_deref().unwrap_or(&default_backend);
+                let members = session_store.list_channel_members(&ch.channel_name)?;
+                let members_line = if members.is_empty() {
+                    String::new()
+                } else {
+                    let ids: Vec<&str> = members.iter().map(|m| m.user_id.as_str()).collect();
+                    format!("\nInvited: {}", ids.join(", "))
+                };
+                let awaiting_line = if session_store
+                    .get_awaiting_reply(&ch.channel_name)?
+                    .is_some()
+                {
+                    "\nAwaiting reply: Yes (the agent asked a question; your next message answers it)"
+                } else {
+                    ""
+                };
                 let status = format!(
                     "📊 Channel Status\n\n\
                     Channel: {}\n\
@@ -104,10 +204,10 @@ pub async fn handle_command<C: ChatChannel>(
                     Directory: {}\n\
                     Backend: {}\n\
                     Started: {}\n\
-                    Debug Mode: {}\n\n\
+                    Debug Mode: {}{}{}\n\n\
                     Webhook URL:\n\
                     POST http://{}:{}/webhook/session/{}\n\n\
-                    This room is backed by a persistent Claude session.",
+                    This room is backed by a persistent Claude session.{}",
                     ch.channel_name,
                     ch.session_id,
                     ch.directory,
@@ -118,9 +218,16 @@ pub async fn handle_command<C: ChatChannel>(
                         "No (first message will start it)"
                     },
                     debug_status,
+                    members_line,
+                    awaiting_line,
                     config.webhook.host,
                     config.webhook.port,
-                    ch.session_id
+                    ch.session_id,
+                    if runtime.is_safe() {
+                        format!("\n\n{}", runtime_mode::BANNER)
+                    } else {
+                        String::new()
+                    }
                 );
                 channel.send(MessageContent::plain(&status)).await?;
             } else {
@@ -133,6 +240,12 @@ pub async fn handle_command<C: ChatChannel>(
                     .await?;
             }
         }
+        "health" => {
+            handle_health_command(channel, session_store, warm_manager, &runtime, &locale).await?
+        }
+        "safemode" => {
+            handle_safemode_command(channel, &cmd.args, &runtime, sender, &locale).await?
+        }
         "list" => {
             if !is_dm {
                 channel
@@ -244,13 +357,53 @@ pub async fn handle_command<C: ChatChannel>(
                 }
             }
         }
+        "stream" => {
+            handle_stream_command(channel, &cmd.args, session_store, is_dm, &locale).await?
+        }
+        "reactions" => {
+            handle_reactions_command(channel, &cmd.args, session_store, is_dm, &locale).await?
+        }
         "backend" => {
+            // In a DM, !backend manages the default that new sessions start on
             if is_dm {
+                let Some(name) = command_parts.get(1).map(|s| s.to_lowercase()) else {
+                    let (current, registry) = {
+                        let mgr = warm_manager.read().await;
+                        (mgr.backend_type().to_string(), mgr.registry())
+                    };
+                    let mut available = registry.available();
+                    available.sort_unstable();
+                    channel
+                        .send(MessageContent::plain(format!(
+                            "🔌 Default Backend\n\n\
+                            Current: {}\n\
+                            Configured: {}\n\
+                            Available: {}\n\n\
+                            Use `!backend <name>` to start new sessions on another backend.",
+                            current,
+                            config.backend.backend_type,
+                            available.join(", ")
+                        )))
+                        .await?;
+                    return Ok(());
+                };
+
+                if let Err(e) = warm_manager.write().await.set_default_backend(&name) {
+                    channel
+                        .send(MessageContent::plain(format!("❌ {}", e)))
+                        .await?;
+                    return Ok(());
+                }
                 channel
-                    .send(MessageContent::plain(
-                        "❌ The !backend command only works in channel rooms.",
-                    ))
+                    .send(MessageContent::plain(format!(
+                        "✅ Default backend switched to: {}\n\n\
+                        New sessions start on it. Warm sessions finish on the backend they \
+                        started with, and channels with their own !backend keep it.\n\
+                        This lasts until restart; set [backend] type in config to keep it.",
+                        name
+                    )))
                     .await?;
+                tracing::info!(backend = %name, sender = %sender, "Default backend changed via DM");
                 return Ok(());
             }
 
@@ -278,13 +431,16 @@ pub async fn handle_command<C: ChatChannel>(
                 }
                 Some("set") => {
                     let Some(new_backend) = command_parts.get(2) else {
-                        channel
-                            .send(MessageContent::plain(
+                        reply_privately(
+                            channel,
+                            sender,
+                            MessageContent::plain(
                                 "Usage: !backend set <name>\n\n\
                             Available: acp, mux, direct\n\n\
                             Example: !backend set mux",
-                            ))
-                            .await?;
+                            ),
+                        )
+                        .await?;
                         return Ok(());
                     };
 
@@ -338,7 +494,7 @@ pub async fn handle_command<C: ChatChannel>(
                 }
                 _ => {
                     let current = ch.backend_type.as_deref().unwrap_or("(global default)");
-                    let global_default = &config.backend.backend_type;
+                    let global_default = warm_manager.read().await.backend_type().to_string();
                     channel
                         .send(MessageContent::plain(format!(
                             "🔌 Backend Status\n\n\
@@ -354,10 +510,194 @@ pub async fn handle_command<C: ChatChannel>(
                 }
             }
         }
+        "model" => {
+            handle_model_command(
+                channel,
+                &cmd.args,
+                session_store,
+                warm_manager,
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "models" => {
+            handle_models_command(channel, session_store, warm_manager, is_dm, &locale).await?
+        }
+        "repo" => {
+            handle_repo_command(channel, &cmd.args, session_store, sender, is_dm, &locale).await?
+        }
+        "deliver" => {
+            handle_deliver_command(
+                channel,
+                &cmd.args,
+                session_store,
+                &config.scheduler.timezone,
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "usage" => handle_usage_command(channel, session_store, is_dm, &locale).await?,
+        "budget" => {
+            handle_budget_command(channel, &cmd.args, session_store, is_dm, &locale).await?
+        }
+        "template" => {
+            handle_template_command(channel, cmd, session_store, sender, is_dm, &locale).await?
+        }
+        "approvals" => {
+            handle_approvals_command(
+                channel,
+                &cmd.args,
+                session_store,
+                &config.tool_approval,
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "network" => {
+            handle_network_command(
+                channel,
+                &cmd.args,
+                session_store,
+                warm_manager,
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "clear" => {
+            handle_clear_command(channel, session_store, warm_manager, sender, &locale).await?
+        }
+        "invite" => {
+            handle_invite_command(
+                channel,
+                &cmd.args,
+                session_store,
+                config,
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "cache" => {
+            handle_cache_command(channel, &cmd.args, session_store, sender, is_dm, &locale).await?
+        }
+        "length" => {
+            handle_length_command(channel, &cmd.args, session_store, sender, is_dm, &locale).await?
+        }
+        "attachments" => {
+            handle_attachments_command(
+                channel,
+                &cmd.args,
+                session_store,
+                &config.attachments,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "locale" => {
+            handle_locale_command(channel, &cmd.args, session_store, sender, is_dm, &locale).await?
+        }
+        "sendguard" => {
+            handle_sendguard_command(
+                channel,
+                &cmd.args,
+                session_store,
+                config.send_guard.threshold_chars,
+                sender,
+                &locale,
+            )
+            .await?
+        }
+        "append" => {
+            handle_append_command(channel, &cmd.raw_args, session_store, sender, &locale).await?
+        }
+        "discard" => handle_discard_command(channel, session_store, sender, &locale).await?,
+        "pin" => {
+            handle_pin_command(
+                channel,
+                &cmd.raw_args,
+                session_store,
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "pins" => handle_pins_command(channel, session_store, is_dm, &locale).await?,
+        "history" => {
+            handle_history_command(channel, &cmd.args, session_store, sender, &locale).await?
+        }
+        "context" => {
+            handle_context_command(channel, &cmd.args, session_store, sender, is_dm, &locale)
+                .await?
+        }
+        "compare" => {
+            handle_compare_command(
+                channel,
+                &cmd.args,
+                session_store,
+                warm_manager,
+                Duration::from_secs(config.backend.timeout_secs),
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "ask" => {
+            handle_ask_command(
+                channel,
+                &cmd.args,
+                session_store,
+                warm_manager,
+                Duration::from_secs(config.backend.timeout_secs),
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "summarize" => {
+            handle_summarize_command(
+                channel,
+                session_store,
+                warm_manager,
+                config.maintenance.summarize_max_chars,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        "transcription" => {
+            handle_transcription_command(
+                channel,
+                &cmd.args,
+                session_store,
+                config.transcription.is_some(),
+                sender,
+                is_dm,
+                &locale,
+            )
+            .await?
+        }
+        // A Telegram forum topic (channel ID `<chat_id>:<thread_id>`) can't be
+        // created by the bot, but it can become a channel of its own
+        "create" if telegram_topics::is_topic(channel) => {
+            handle_topic_create_command(channel, &cmd.args, session_store, &locale).await?
+        }
         // Commands that need Matrix client operations are delegated back
         // For now, return a placeholder - these will be handled in mod.rs
         "create" | "join" | "delete" | "leave" | "cleanup" | "restore-rooms" | "setup"
-        | "schedule" | "reset" => {
+        | "schedule" | "reset" | "approve" | "clone" | "topic" | "promote" => {
             // These commands need the Matrix client for room operations
             // or have more complete implementations in matrix_commands.rs
             // Reset is delegated to ensure consistent use of reset_session (which resets started flag)
@@ -379,6 +719,10 @@ pub async fn handle_command<C: ChatChannel>(
                 !create <name> - Create new channel\n\
                 !status - Show channel info\n\
                 !debug - Toggle tool usage display\n\
+                !stream - Toggle streaming responses\n\
+                !reactions - Toggle status reactions\n\
+                !length <brief|normal|detailed> - Set answer length\n\
+                !pin / !pins - Save and list important responses\n\
                 !reset - Reset Claude session (reload MCP tools)\n\
                 !schedule <time> <prompt> - Schedule a prompt\n\
                 !schedule list - View schedules\n\
@@ -386,22 +730,118 @@ pub async fn handle_command<C: ChatChannel>(
                 !leave - Bot leaves room\n\
                 !help - Show detailed help"
             };
-            channel.send(MessageContent::plain(help_msg)).await?;
+            reply_privately(channel, sender, MessageContent::plain(help_msg)).await?;
         }
     }
 
     Ok(())
 }
 
+/// Send something only `sender` needs to see, such as a refusal or a usage hint:
+/// privately where the platform can, in the channel otherwise
+pub(crate) async fn reply_privately<C: ChatChannel>(
+    channel: &C,
+    sender: &str,
+    content: MessageContent,
+) -> Result<()> {
+    match channel.ephemeral() {
+        Some(ephemeral) if !channel.is_direct().await => {
+            ephemeral
+                .send_ephemeral(channel.id(), sender, content)
+                .await
+        }
+        _ => channel.send(content).await,
+    }
+}
+
+/// The channel attached to this room, for commands that only make sense in one.
+/// Tells the sender why not and returns None in a DM or an unattached room.
+pub(crate) async fn channel_for_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    command: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<Option<Channel>> {
+    if is_dm {
+        let reply = tf(locale, "command.room_only", &[("command", command)]);
+        channel.send(MessageContent::plain(reply)).await?;
+        return Ok(None);
+    }
+    let ch = session_store.get_by_room(channel.id())?;
+    if ch.is_none() {
+        channel
+            .send(MessageContent::plain(t(locale, "command.no_channel")))
+            .await?;
+    }
+    Ok(ch)
+}
+
+/// The language to reply in: the channel's in a room, the sender's own in a DM
+fn command_locale(
+    session_store: &SessionStore,
+    room_id: &str,
+    sender: &str,
+    is_dm: bool,
+) -> Result<String> {
+    if !is_dm {
+        if let Some(ch) = session_store.get_by_room(room_id)? {
+            return Ok(i18n::channel_locale(
+                session_store,
+                &ch.channel_name,
+                sender,
+            ));
+        }
+    }
+    Ok(i18n::user_locale(session_store, sender))
+}
+
+/// HELP.md with the active command aliases filled in under its Aliases heading,
+/// and commands shown with the prefix help was asked for with
+fn help_with_aliases(catalog: &CommandCatalog, prefix: &str) -> String {
+    let mut section = String::new();
+    for (alias, command) in catalog.aliases() {
+        section.push_str(&format!("- `!{}` → `!{}`\n", alias, command));
+    }
+    section.push('\n');
+
+    let help = match HELP_MD.find("## DISPATCH Control Plane") {
+        Some(at) => format!("{}{}{}", &HELP_MD[..at], section, &HELP_MD[at..]),
+        None => format!("{}\n{}", HELP_MD, section),
+    };
+    if prefix == DEFAULT_COMMAND_PREFIX {
+        help
+    } else {
+        // Every command in HELP.md is written as `!name ...`
+        help.replace("`!", &format!("`{}", prefix))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::RiskLevel;
+    use crate::git_seed;
+    use crate::message_handler::helpers::{is_status_reactions_enabled, is_streaming_enabled};
+    use crate::message_handler::history;
+    use crate::message_handler::pins;
+    use crate::message_handler::response_cache;
+    use crate::message_handler::response_length::{
+        get_response_length, LengthSetting, ResponseLength,
+    };
     use crate::message_handler::traits::MockChannel;
     use crate::scheduler::SchedulerStore;
     use crate::session::SessionStore;
-    use crate::warm_session::create_shared_manager;
+    use crate::usage::InvocationOrigin;
+    use crate::utils::MAX_CHUNK_SIZE;
+    use crate::warm_session::{
+        create_shared_manager, is_network_allowed, read_channel_model, write_channel_model,
+    };
     use gorp_core::config::{
-        BackendConfig, MatrixConfig, SchedulerConfig, WebhookConfig, WorkspaceConfig,
+        AccessConfig, AttachmentsConfig, BackendConfig, CommandsConfig, DedupConfig, EditsConfig,
+        I18nConfig, LimitsConfig, LoggingConfig, MaintenanceConfig, MatrixConfig, MetricsConfig,
+        PlatformAccess, RolesConfig, RuntimeConfig, SchedulerConfig, SendGuardConfig,
+        ShutdownConfig, SigningConfig, ToolApprovalConfig, WebhookConfig, WorkspaceConfig,
     };
     use gorp_core::warm_session::WarmConfig;
     use std::time::Duration;
@@ -413,6 +853,7 @@ mod tests {
             name: name.to_string(),
             args: args.into_iter().map(String::from).collect(),
             raw_args,
+            prefix: "!".to_string(),
         }
     }
 
@@ -427,23 +868,60 @@ mod tests {
                 allowed_users: vec!["@user:matrix.example.com".to_string()],
                 room_prefix: "Test".to_string(),
                 recovery_key: None,
+                reconnect: Default::default(),
+                invite_policy: Default::default(),
+                admin_room: None,
+                command_prefix: "!".to_string(),
+                management_room: None,
+                announce_startup: true,
+                notify_ready: true,
+                space_id: None,
+                auto_create_space: false,
+                room_avatar: None,
+                member_power_level: 0,
             }),
             telegram: None,
             slack: None,
             whatsapp: None,
+            irc: None,
+            zulip: None,
             coven: None,
             backend: BackendConfig::default(),
             webhook: WebhookConfig {
                 port: 13000,
                 api_key: None,
+                secret: None,
                 host: "localhost".to_string(),
+                coalesce: Default::default(),
             },
             workspace: WorkspaceConfig {
                 path: workspace_path.to_string(),
+                channel_scope: Default::default(),
             },
             scheduler: SchedulerConfig {
                 timezone: "UTC".to_string(),
+                templates: Default::default(),
+                quiet_hours: None,
+                run_history: 20,
             },
+            dedup: DedupConfig::default(),
+            edits: EditsConfig::default(),
+            i18n: I18nConfig::default(),
+            send_guard: SendGuardConfig::default(),
+            limits: LimitsConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            transcription: None,
+            logging: LoggingConfig::default(),
+            commands: CommandsConfig::default(),
+            shutdown: ShutdownConfig::default(),
+            tool_approval: ToolApprovalConfig::default(),
+            roles: RolesConfig::default(),
+            access: AccessConfig::default(),
+            preprocess: Default::default(),
+            metrics: MetricsConfig::default(),
+            runtime: RuntimeConfig::default(),
+            signing: SigningConfig::default(),
         }
     }
 
@@ -471,6 +949,8 @@ mod tests {
                 max_tokens: None,
                 global_system_prompt_path: None,
                 mcp_servers: vec![],
+                max_response_chars: 0,
+                save_truncated_responses: false,
             };
             let warm_manager = create_shared_manager(warm_config);
 
@@ -569,6 +1049,59 @@ mod tests {
         assert_eq!(room.get_messages().len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_help_lists_active_aliases() {
+        let mut ctx = TestContext::new();
+        ctx.config
+            .commands
+            .aliases
+            .insert("sched".to_string(), "schedule".to_string());
+        let room = MockChannel::new("!channel:matrix.org");
+        let cmd = make_command("help", vec![]);
+
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("`!new` → `!create`"));
+        assert!(room.has_message_containing("`!sched` → `!schedule`"));
+    }
+
+    #[tokio::test]
+    async fn test_help_shows_the_prefix_it_was_asked_with() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        let cmd = make_command("help", vec![]).with_prefix("gorp!");
+
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("`gorp!create <name>`"));
+        assert!(room.has_message_containing("`gorp!new` → `gorp!create`"));
+        assert!(!room.has_message_containing("`!help`"));
+    }
+
     #[tokio::test]
     async fn test_changelog_command() {
         let ctx = TestContext::new();
@@ -821,41 +1354,37 @@ mod tests {
         assert!(room.has_message_containing("Debug mode"));
     }
 
-    // =========================================================================
-    // Backend Command Tests
-    // =========================================================================
-
     #[tokio::test]
-    async fn test_backend_rejected_in_dm() {
+    async fn test_stream_toggle() {
         let ctx = TestContext::new();
-        let room = MockChannel::dm("!dm:matrix.org");
-        let cmd = make_command("backend", vec![]);
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
 
-        let result = handle_command(
+        let cmd = make_command("stream", vec!["on"]);
+        handle_command(
             &room,
             &cmd,
             &ctx.session_store,
             &ctx.scheduler_store,
             None,
             "@user:matrix.org",
-            true,
+            false,
             &ctx.config,
             &ctx.warm_manager,
         )
-        .await;
-
-        assert!(result.is_ok());
-        assert!(room.has_message_containing("only works in channel rooms"));
-    }
-
-    #[tokio::test]
-    async fn test_backend_status() {
-        let ctx = TestContext::new();
-        let room = MockChannel::new("!channel:matrix.org");
-        ctx.create_channel("test-channel", "!channel:matrix.org");
-        let cmd = make_command("backend", vec![]);
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Streaming ENABLED"));
+        assert!(is_streaming_enabled(&dir));
 
-        let result = handle_command(
+        let cmd = make_command("stream", vec!["off"]);
+        handle_command(
             &room,
             &cmd,
             &ctx.session_store,
@@ -866,18 +1395,55 @@ mod tests {
             &ctx.config,
             &ctx.warm_manager,
         )
-        .await;
-
-        assert!(result.is_ok());
-        assert!(room.has_message_containing("Backend Status"));
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Streaming DISABLED"));
+        assert!(!is_streaming_enabled(&dir));
     }
 
     #[tokio::test]
-    async fn test_backend_list() {
+    async fn test_reactions_toggle() {
         let ctx = TestContext::new();
         let room = MockChannel::new("!channel:matrix.org");
         ctx.create_channel("test-channel", "!channel:matrix.org");
-        let cmd = make_command("backend", vec!["list"]);
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        for (arg, expected, enabled) in [
+            ("on", "Status reactions ENABLED", true),
+            ("off", "Status reactions DISABLED", false),
+        ] {
+            handle_command(
+                &room,
+                &make_command("reactions", vec![arg]),
+                &ctx.session_store,
+                &ctx.scheduler_store,
+                None,
+                "@user:matrix.org",
+                false,
+                &ctx.config,
+                &ctx.warm_manager,
+            )
+            .await
+            .unwrap();
+            assert!(room.has_message_containing(expected));
+            assert_eq!(is_status_reactions_enabled(&dir), enabled);
+        }
+    }
+
+    // =========================================================================
+    // Backend Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_backend_in_dm_shows_default() {
+        let ctx = TestContext::new();
+        let room = MockChannel::dm("!dm:matrix.org");
+        let cmd = make_command("backend", vec![]);
 
         let result = handle_command(
             &room,
@@ -886,59 +1452,46 @@ mod tests {
             &ctx.scheduler_store,
             None,
             "@user:matrix.org",
-            false,
+            true,
             &ctx.config,
             &ctx.warm_manager,
         )
         .await;
 
         assert!(result.is_ok());
-        assert!(room.has_message_containing("Available Backends"));
-        assert!(room.has_message_containing("acp, mux, direct"));
+        assert!(room.has_message_containing("Default Backend"));
+        assert!(room.has_message_containing("Current: acp"));
+        assert!(room.has_message_containing("mock"));
     }
 
     #[tokio::test]
-    async fn test_backend_set_valid() {
+    async fn test_backend_in_dm_switches_default() {
         let ctx = TestContext::new();
-        let room = MockChannel::new("!channel:matrix.org");
-        ctx.create_channel("test-channel", "!channel:matrix.org");
-        let cmd = make_command("backend", vec!["set", "mux"]);
+        let room = MockChannel::dm("!dm:matrix.org");
+        let cmd = make_command("backend", vec!["Mock"]);
 
-        let result = handle_command(
+        handle_command(
             &room,
             &cmd,
             &ctx.session_store,
             &ctx.scheduler_store,
             None,
             "@user:matrix.org",
-            false,
+            true,
             &ctx.config,
             &ctx.warm_manager,
         )
-        .await;
-
-        assert!(result.is_ok());
-        assert!(room.has_message_containing("Backend changed to: mux"));
+        .await
+        .unwrap();
 
-        // Verify it was actually saved
-        let channel = ctx
-            .session_store
-            .get_by_name("test-channel")
-            .unwrap()
-            .unwrap();
-        assert_eq!(channel.backend_type, Some("mux".to_string()));
+        assert!(room.has_message_containing("Default backend switched to: mock"));
+        assert_eq!(ctx.warm_manager.read().await.backend_type(), "mock");
     }
 
-    #[tokio::test]
-    async fn test_backend_set_invalid() {
-        let ctx = TestContext::new();
-        let room = MockChannel::new("!channel:matrix.org");
-        ctx.create_channel("test-channel", "!channel:matrix.org");
-        let cmd = make_command("backend", vec!["set", "invalid"]);
-
-        let result = handle_command(
-            &room,
-            &cmd,
+    async fn run_model(ctx: &TestContext, room: &MockChannel, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command("model", args),
             &ctx.session_store,
             &ctx.scheduler_store,
             None,
@@ -947,26 +1500,80 @@ mod tests {
             &ctx.config,
             &ctx.warm_manager,
         )
-        .await;
+        .await
+        .unwrap();
+    }
 
-        assert!(result.is_ok());
-        assert!(room.has_message_containing("Unknown backend"));
+    #[tokio::test]
+    async fn test_model_set_show_and_reset() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("research", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_name("research")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        // The test default backend is acp, which has no model to pick
+        run_model(&ctx, &room, vec!["set", "claude-opus-4-1"]).await;
+        assert!(room.has_message_containing("The acp backend doesn't take a model"));
+        assert_eq!(read_channel_model(&dir), None);
+
+        ctx.session_store
+            .update_backend_type("research", Some("mux"))
+            .unwrap();
+        run_model(&ctx, &room, vec!["set", "opus; rm -rf /"]).await;
+        assert!(room.has_message_containing("Invalid model name"));
+
+        run_model(&ctx, &room, vec!["set", "claude-opus-4-1"]).await;
+        assert!(room.has_message_containing("research now uses claude-opus-4-1"));
+        assert_eq!(read_channel_model(&dir).as_deref(), Some("claude-opus-4-1"));
+
+        run_model(&ctx, &room, vec!["show"]).await;
+        let shown = room.last_message().unwrap().plain;
+        assert!(shown.contains("Channel model: claude-opus-4-1"));
+        assert!(shown.contains("Backend: mux"));
+
+        run_model(&ctx, &room, vec!["reset"]).await;
+        assert!(room.has_message_containing("research is back on the default model"));
+        assert_eq!(read_channel_model(&dir), None);
     }
 
     #[tokio::test]
-    async fn test_backend_reset() {
+    async fn test_models_lists_backend_models_and_marks_active() {
         let ctx = TestContext::new();
         let room = MockChannel::new("!channel:matrix.org");
-        ctx.create_channel("test-channel", "!channel:matrix.org");
-        // First set a backend
+        ctx.create_channel("research", "!channel:matrix.org");
         ctx.session_store
-            .update_backend_type("test-channel", Some("mux"))
+            .update_backend_type("research", Some("mock"))
             .unwrap();
-        let cmd = make_command("backend", vec!["reset"]);
+        let dir = ctx
+            .session_store
+            .get_by_name("research")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        run_model_list(&ctx, &room).await;
+        let listed = room.last_message().unwrap().plain;
+        assert!(listed.contains("Models on mock"));
+        assert!(listed.contains("• mock-small (32K context)"));
+        assert!(listed.contains("• mock-large (1M context)"));
+        assert!(listed.contains("Active: the backend's own default"));
+
+        write_channel_model(&dir, Some("mock-large")).unwrap();
+        run_model_list(&ctx, &room).await;
+        let listed = room.last_message().unwrap().plain;
+        assert!(listed.contains("▶ mock-large (1M context)"));
+        assert!(listed.contains("• mock-small"));
+    }
 
-        let result = handle_command(
-            &room,
-            &cmd,
+    async fn run_model_list(ctx: &TestContext, room: &MockChannel) {
+        handle_command(
+            room,
+            &make_command("models", vec![]),
             &ctx.session_store,
             &ctx.scheduler_store,
             None,
@@ -975,12 +1582,256 @@ mod tests {
             &ctx.config,
             &ctx.warm_manager,
         )
-        .await;
+        .await
+        .unwrap();
+    }
 
-        assert!(result.is_ok());
-        assert!(room.has_message_containing("reset to global default"));
+    #[tokio::test]
+    async fn test_network_toggle_is_per_channel() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("vault", "!channel:matrix.org");
+        ctx.create_channel("research", "!research:matrix.org");
+        let dir = |name: &str| {
+            ctx.session_store
+                .get_by_name(name)
+                .unwrap()
+                .unwrap()
+                .directory
+        };
 
-        // Verify it was reset
+        run_in_room(&ctx, &room, "network", vec![]).await;
+        assert!(room.has_message_containing("Network access is on in vault"));
+
+        run_in_room(&ctx, &room, "network", vec!["off"]).await;
+        assert!(room.has_message_containing("Network access is off in vault"));
+        assert!(!is_network_allowed(&dir("vault")));
+        assert!(is_network_allowed(&dir("research")));
+        run_in_room(&ctx, &room, "network", vec![]).await;
+        assert!(room
+            .last_message()
+            .unwrap()
+            .plain
+            .contains("web tools are removed or refused"));
+
+        run_in_room(&ctx, &room, "network", vec!["on"]).await;
+        assert!(is_network_allowed(&dir("vault")));
+    }
+
+    #[tokio::test]
+    async fn test_repo_set_keeps_setting_when_clone_fails() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("code", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_name("code")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        run_in_room(
+            &ctx,
+            &room,
+            "repo",
+            vec!["set", "/nonexistent/api.git", "--depth"],
+        )
+        .await;
+        assert!(room.has_message_containing("Unknown option: --depth"));
+        run_in_room(
+            &ctx,
+            &room,
+            "repo",
+            vec!["set", "/nonexistent/api.git", "--dir", "../up"],
+        )
+        .await;
+        assert!(room.has_message_containing("--dir must stay inside the workspace"));
+        assert_eq!(git_seed::read_git_repo(&dir), None);
+
+        run_in_room(
+            &ctx,
+            &room,
+            "repo",
+            vec![
+                "set",
+                "/nonexistent/api.git",
+                "--branch",
+                "main",
+                "--pull-on-start",
+            ],
+        )
+        .await;
+        assert!(room.has_message_containing("⚠️ Couldn't sync /nonexistent/api.git"));
+        let repo = git_seed::read_git_repo(&dir).unwrap();
+        assert_eq!(repo.branch.as_deref(), Some("main"));
+        assert!(repo.pull_on_session_start);
+
+        run_in_room(&ctx, &room, "repo", vec![]).await;
+        let shown = room.last_message().unwrap().plain;
+        assert!(shown.contains("Checkout: api/ (not cloned yet)"));
+        assert!(shown.contains("Pull on session start: on"));
+
+        run_in_room(&ctx, &room, "repo", vec!["clear"]).await;
+        assert_eq!(git_seed::read_git_repo(&dir), None);
+    }
+
+    #[tokio::test]
+    async fn test_backend_in_dm_rejects_unknown_name() {
+        let ctx = TestContext::new();
+        let room = MockChannel::dm("!dm:matrix.org");
+        let cmd = make_command("backend", vec!["nonesuch"]);
+
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("Unknown backend: nonesuch"));
+        assert_eq!(ctx.warm_manager.read().await.backend_type(), "acp");
+    }
+
+    #[tokio::test]
+    async fn test_backend_status() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let cmd = make_command("backend", vec![]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("Backend Status"));
+    }
+
+    #[tokio::test]
+    async fn test_backend_list() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let cmd = make_command("backend", vec!["list"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("Available Backends"));
+        assert!(room.has_message_containing("acp, mux, direct"));
+    }
+
+    #[tokio::test]
+    async fn test_backend_set_valid() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let cmd = make_command("backend", vec!["set", "mux"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("Backend changed to: mux"));
+
+        // Verify it was actually saved
+        let channel = ctx
+            .session_store
+            .get_by_name("test-channel")
+            .unwrap()
+            .unwrap();
+        assert_eq!(channel.backend_type, Some("mux".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_backend_set_invalid() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let cmd = make_command("backend", vec!["set", "invalid"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("Unknown backend"));
+    }
+
+    #[tokio::test]
+    async fn test_backend_reset() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        // First set a backend
+        ctx.session_store
+            .update_backend_type("test-channel", Some("mux"))
+            .unwrap();
+        let cmd = make_command("backend", vec!["reset"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("reset to global default"));
+
+        // Verify it was reset
         let channel = ctx
             .session_store
             .get_by_name("test-channel")
@@ -989,15 +1840,1265 @@ mod tests {
         assert_eq!(channel.backend_type, None);
     }
 
-    // =========================================================================
-    // Reset Command Tests
-    // =========================================================================
+    // =========================================================================
+    // Usage Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_usage_splits_overhead_from_conversation() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let usage = gorp_agent::Usage {
+            input_tokens: 120,
+            output_tokens: 30,
+            cost_usd: Some(0.25),
+            ..Default::default()
+        };
+        ctx.session_store
+            .record_usage("test-channel", InvocationOrigin::User, Some(&usage))
+            .unwrap();
+        ctx.session_store
+            .record_usage("test-channel", InvocationOrigin::Internal, None)
+            .unwrap();
+
+        let cmd = make_command("usage", vec![]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room
+            .has_message_containing("Conversation: 1 invocation · 120 in / 30 out tokens · $0.25"));
+        assert!(room.has_message_containing("Overhead: 1 invocation · 0 in / 0 out tokens"));
+        assert!(room.has_message_containing("This month: $0.25 so far"));
+    }
+
+    async fn run_budget(ctx: &TestContext, room: &MockChannel, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command("budget", args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_budget_set_show_and_clear() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        run_budget(&ctx, &room, vec![]).await;
+        assert!(room.has_message_containing("test-channel has no monthly budget"));
+
+        run_budget(&ctx, &room, vec!["set", "lots"]).await;
+        assert!(room.has_message_containing("Usage: !budget set <amount>"));
+
+        run_budget(&ctx, &room, vec!["set", "$12.50"]).await;
+        assert!(room.has_message_containing("Monthly budget for test-channel set to $12.50"));
+        assert_eq!(
+            ctx.session_store.get_budget_cents("test-channel").unwrap(),
+            Some(1250)
+        );
+
+        let usage = gorp_agent::Usage {
+            cost_usd: Some(20.0),
+            ..Default::default()
+        };
+        ctx.session_store
+            .record_usage("test-channel", InvocationOrigin::User, Some(&usage))
+            .unwrap();
+        run_budget(&ctx, &room, vec![]).await;
+        assert!(room.has_message_containing("Budget: $12.50 per month, already exceeded"));
+
+        run_budget(&ctx, &room, vec!["clear"]).await;
+        assert!(room.has_message_containing("Removed the monthly budget"));
+        assert_eq!(
+            ctx.session_store.get_budget_cents("test-channel").unwrap(),
+            None
+        );
+    }
+
+    // =========================================================================
+    // Template Command Tests
+    // =========================================================================
+
+    async fn run_template(ctx: &TestContext, room: &MockChannel, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command("template", args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_template_save_list_and_delete() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        run_template(&ctx, &room, vec!["list"]).await;
+        assert!(room.has_message_containing("No templates saved"));
+
+        run_template(&ctx, &room, vec!["save", "bad name!", "x"]).await;
+        assert!(room.has_message_containing("can only contain letters"));
+
+        let save = "save inbox Summarize unread email about {{args}}";
+        run_template(&ctx, &room, save.split(' ').collect()).await;
+        assert!(room.has_message_containing("Saved template 'inbox'"));
+        let saved = ctx
+            .session_store
+            .get_template("test-channel", "inbox")
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.body, "Summarize unread email about {{args}}");
+        assert_eq!(saved.created_by, "@user:matrix.org");
+
+        run_template(&ctx, &room, vec!["list"]).await;
+        assert!(room.has_message_containing("• inbox - Summarize unread email about {{args}}"));
+
+        run_template(&ctx, &room, vec!["delete", "inbox"]).await;
+        assert!(room.has_message_containing("Deleted template 'inbox'"));
+        run_template(&ctx, &room, vec!["delete", "inbox"]).await;
+        assert!(room.has_message_containing("No template named 'inbox'"));
+    }
+
+    #[tokio::test]
+    async fn test_template_save_captures_next_message() {
+        use crate::message_handler::prompt_templates::capture_body;
+
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        run_template(&ctx, &room, vec!["save", "standup"]).await;
+        assert!(room.has_message_containing("as your next message"));
+
+        // Someone else's message isn't captured
+        let other = capture_body(
+            &ctx.session_store,
+            "!channel:matrix.org",
+            "@other:matrix.org",
+            "hello",
+        )
+        .unwrap();
+        assert!(other.is_none());
+
+        let reply = capture_body(
+            &ctx.session_store,
+            "!channel:matrix.org",
+            "@user:matrix.org",
+            "What changed yesterday?\n- {{args}}",
+        )
+        .unwrap()
+        .unwrap();
+        assert!(reply.contains("Saved template 'standup'"));
+        assert_eq!(
+            ctx.session_store
+                .get_template("test-channel", "standup")
+                .unwrap()
+                .unwrap()
+                .body,
+            "What changed yesterday?\n- {{args}}"
+        );
+
+        // The capture is used up
+        let again = capture_body(
+            &ctx.session_store,
+            "!channel:matrix.org",
+            "@user:matrix.org",
+            "just chatting",
+        )
+        .unwrap();
+        assert!(again.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_template_run_renders_prompt() {
+        use crate::message_handler::prompt_templates::{is_run, resolve_run, TemplateRun};
+
+        let ctx = TestContext::new();
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        ctx.session_store
+            .save_template(
+                "test-channel",
+                "inbox",
+                "Summarize email about {{args}}.",
+                "@user:matrix.org",
+            )
+            .unwrap();
+
+        let cmd = make_command("template", vec!["run", "inbox", "the", "launch"]);
+        assert!(is_run(&cmd));
+        match resolve_run(&ctx.session_store, "!channel:matrix.org", &cmd).unwrap() {
+            TemplateRun::Prompt { channel, prompt } => {
+                assert_eq!(channel.channel_name, "test-channel");
+                assert_eq!(prompt, "Summarize email about the launch.");
+            }
+            TemplateRun::Reply(text) => panic!("expected a prompt, got {}", text),
+        }
+
+        let missing = make_command("template", vec!["run", "nope"]);
+        match resolve_run(&ctx.session_store, "!channel:matrix.org", &missing).unwrap() {
+            TemplateRun::Reply(text) => assert!(text.contains("No template named 'nope'")),
+            TemplateRun::Prompt { .. } => panic!("missing template should not run"),
+        }
+        assert!(!is_run(&make_command("template", vec!["list"])));
+    }
+
+    // =========================================================================
+    // Cache Command Tests
+    // =========================================================================
+
+    async fn run_cache(ctx: &TestContext, room: &MockChannel, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command("cache", args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cache_answers_repeats_until_workspace_changes() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        ctx.session_store
+            .update_backend_type("test-channel", Some("mock"))
+            .unwrap();
+
+        run_cache(&ctx, &room, vec![]).await;
+        assert!(room.has_message_containing("Response cache is off in test-channel"));
+        run_cache(&ctx, &room, vec!["on"]).await;
+        assert!(room.has_message_containing("Response cache on in test-channel"));
+
+        let ch = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap();
+        let ask = || {
+            super::super::handle_text(
+                "what does main.rs do?",
+                &ch,
+                &ctx.session_store,
+                &ctx.warm_manager,
+                InvocationOrigin::User,
+            )
+        };
+        let first = ask().await.unwrap();
+        assert!(!first.starts_with(response_cache::CACHED_MARKER));
+        assert_eq!(
+            ask().await.unwrap(),
+            format!("{} {}", response_cache::CACHED_MARKER, first)
+        );
+
+        std::fs::write(std::path::Path::new(&ch.directory).join("main.rs"), "").unwrap();
+        assert_eq!(ask().await.unwrap(), first);
+
+        run_cache(&ctx, &room, vec![]).await;
+        assert!(room.has_message_containing("(1 cached)"));
+        run_cache(&ctx, &room, vec!["clear"]).await;
+        assert!(room.has_message_containing("Dropped 1 cached answer in test-channel"));
+        run_cache(&ctx, &room, vec!["off"]).await;
+        assert!(!ctx
+            .session_store
+            .is_response_cache_enabled("test-channel")
+            .unwrap());
+        run_cache(&ctx, &room, vec!["sometimes"]).await;
+        assert!(room.has_message_containing("Usage:\n  !cache"));
+    }
+
+    // =========================================================================
+    // Invite Command Tests
+    // =========================================================================
+
+    async fn run_in_room(ctx: &TestContext, room: &MockChannel, name: &str, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command(name, args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.example.com",
+            room.is_dm,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invite_records_member_shown_in_status() {
+        let mut ctx = TestContext::new();
+        ctx.config.access.users = vec!["@bob:matrix.org".to_string()];
+        let mut room = MockChannel::new("!channel:matrix.org");
+        room.platform_id = "matrix".to_string();
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        run_in_room(&ctx, &room, "invite", vec!["@bob:matrix.org"]).await;
+        assert!(room.has_message_containing("Invited @bob:matrix.org to test-channel"));
+        assert_eq!(
+            *room.invites.lock().unwrap(),
+            vec![(
+                "!channel:matrix.org".to_string(),
+                "@bob:matrix.org".to_string()
+            )]
+        );
+        let members = ctx
+            .session_store
+            .list_channel_members("test-channel")
+            .unwrap();
+        assert_eq!(members[0].invited_by, "@user:matrix.example.com");
+
+        run_in_room(&ctx, &room, "status", vec![]).await;
+        assert!(room.has_message_containing("Invited: @bob:matrix.org"));
+    }
+
+    #[tokio::test]
+    async fn test_invite_refuses_bad_ids_and_outsiders() {
+        let mut ctx = TestContext::new();
+        let mut room = MockChannel::new("!channel:matrix.org");
+        room.platform_id = "matrix".to_string();
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        run_in_room(&ctx, &room, "invite", vec!["bob"]).await;
+        assert!(room.has_message_containing("bob isn't a Matrix user ID"));
+
+        run_in_room(&ctx, &room, "invite", vec!["@eve:matrix.org"]).await;
+        assert!(room.has_message_containing("@eve:matrix.org isn't allowed to talk to the bot"));
+        assert!(room.invites.lock().unwrap().is_empty());
+
+        ctx.config.access.allow_invite_outside_allowlist = true;
+        run_in_room(&ctx, &room, "invite", vec!["@eve:matrix.org"]).await;
+        assert!(room.has_message_containing("Invited @eve:matrix.org to test-channel"));
+
+        let dm = MockChannel::dm("!dm:matrix.org");
+        run_in_room(&ctx, &dm, "invite", vec!["@eve:matrix.org"]).await;
+        assert!(dm.has_message_containing("only works in channel rooms"));
+    }
+
+    // =========================================================================
+    // Approvals Command Tests
+    // =========================================================================
+
+    async fn run_approvals(ctx: &TestContext, room: &MockChannel, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command("approvals", args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_approvals_on_level_and_off() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        run_approvals(&ctx, &room, vec![]).await;
+        assert!(room.has_message_containing("Tool approval is off in test-channel"));
+
+        run_approvals(&ctx, &room, vec!["on"]).await;
+        assert_eq!(
+            ctx.session_store
+                .get_approval_level("test-channel")
+                .unwrap(),
+            Some(RiskLevel::High)
+        );
+
+        run_approvals(&ctx, &room, vec!["Medium"]).await;
+        assert!(
+            room.has_message_containing("medium risk and above in test-channel now need approval")
+        );
+        run_approvals(&ctx, &room, vec![]).await;
+        assert!(room.has_message_containing("medium risk tools and above wait for a yes"));
+        assert!(room.has_message_containing("denied after 120s"));
+
+        run_approvals(&ctx, &room, vec!["sometimes"]).await;
+        assert!(room.has_message_containing("Usage:"));
+        assert_eq!(
+            ctx.session_store
+                .get_approval_level("test-channel")
+                .unwrap(),
+            Some(RiskLevel::Medium)
+        );
+
+        run_approvals(&ctx, &room, vec!["off"]).await;
+        assert_eq!(
+            ctx.session_store
+                .get_approval_level("test-channel")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approvals_rejected_in_dm() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!dm:matrix.org");
+
+        handle_command(
+            &room,
+            &make_command("approvals", vec!["on"]),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("only works in channel rooms"));
+    }
+
+    // =========================================================================
+    // Length Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_length_sets_and_reports_setting() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        let cmd = make_command("length", vec!["brief", "strict"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("Response length set to brief"));
+        assert_eq!(
+            get_response_length(&dir),
+            LengthSetting {
+                length: ResponseLength::Brief,
+                strict: true
+            }
+        );
+
+        let cmd = make_command("length", vec![]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Response length: brief (strict)"));
+
+        let cmd = make_command("length", vec!["verbose"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Usage: !length"));
+        assert_eq!(get_response_length(&dir).length, ResponseLength::Brief);
+    }
+
+    // =========================================================================
+    // Locale Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_locale_sets_channel_language_and_rejects_unknown() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        for args in [vec!["klingon"], vec!["es"], vec![]] {
+            handle_command(
+                &room,
+                &make_command("locale", args),
+                &ctx.session_store,
+                &ctx.scheduler_store,
+                None,
+                "@user:matrix.org",
+                false,
+                &ctx.config,
+                &ctx.warm_manager,
+            )
+            .await
+            .unwrap();
+        }
+
+        assert!(room.has_message_containing("Unknown language: klingon"));
+        assert!(room.has_message_containing("Idioma cambiado a es"));
+        assert!(room.has_message_containing("🌐 Idioma: es"));
+        assert_eq!(
+            ctx.session_store
+                .get_channel_locale("test-channel")
+                .unwrap()
+                .as_deref(),
+            Some("es")
+        );
+        // The channel choice doesn't leak into the sender's own DM language
+        assert_eq!(
+            ctx.session_store
+                .get_user_locale("@user:matrix.org")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_locale_in_dm_sets_user_language() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!dm:matrix.org");
+
+        handle_command(
+            &room,
+            &make_command("locale", vec!["es"]),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            ctx.session_store
+                .get_user_locale("@user:matrix.org")
+                .unwrap()
+                .as_deref(),
+            Some("es")
+        );
+
+        handle_command(
+            &room,
+            &make_command("locale", vec!["reset"]),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Language reset to the default (en)"));
+        assert_eq!(
+            ctx.session_store
+                .get_user_locale("@user:matrix.org")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commands_reply_in_the_channel_or_dm_language() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        ctx.session_store
+            .set_channel_locale("test-channel", Some("es"))
+            .unwrap();
+        run_in_room(&ctx, &room, "cache", vec!["clear"]).await;
+        assert!(
+            room.has_message_containing("Se descartaron 0 respuestas guardadas en test-channel")
+        );
+
+        let dm = MockChannel::dm("!dm:matrix.org");
+        ctx.session_store
+            .set_user_locale("@user:matrix.example.com", Some("es"))
+            .unwrap();
+        run_in_room(&ctx, &dm, "pins", vec![]).await;
+        assert!(dm.has_message_containing("El comando !pins solo funciona en salas de canal"));
+    }
+
+    // =========================================================================
+    // Send Guard Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_sendguard_toggle_is_per_user() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+
+        for (arg, enabled) in [("on", true), ("off", false)] {
+            handle_command(
+                &room,
+                &make_command("sendguard", vec![arg]),
+                &ctx.session_store,
+                &ctx.scheduler_store,
+                None,
+                "@user:matrix.org",
+                false,
+                &ctx.config,
+                &ctx.warm_manager,
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                ctx.session_store
+                    .is_send_guard_enabled("@user:matrix.org")
+                    .unwrap(),
+                enabled
+            );
+        }
+        assert!(room.has_message_containing("Send guard ENABLED"));
+        assert!(room.has_message_containing("Send guard DISABLED"));
+    }
+
+    #[tokio::test]
+    async fn test_append_and_discard_draft() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        let run = |cmd: Command| {
+            let ctx = &ctx;
+            let room = &room;
+            async move {
+                handle_command(
+                    room,
+                    &cmd,
+                    &ctx.session_store,
+                    &ctx.scheduler_store,
+                    None,
+                    "@user:matrix.org",
+                    false,
+                    &ctx.config,
+                    &ctx.warm_manager,
+                )
+                .await
+                .unwrap();
+            }
+        };
+
+        run(make_command("append", vec!["more"])).await;
+        assert!(room.has_message_containing("No queued draft here"));
+
+        ctx.session_store
+            .save_draft(
+                "matrix",
+                "!channel:matrix.org",
+                "@user:matrix.org",
+                "first part",
+            )
+            .unwrap();
+        run(make_command("append", vec!["and", "the", "rest"])).await;
+        assert!(room.has_message_containing("Added to your draft"));
+        assert_eq!(
+            ctx.session_store
+                .get_draft("!channel:matrix.org", "@user:matrix.org")
+                .unwrap()
+                .unwrap()
+                .body,
+            "first part and the rest"
+        );
+
+        run(make_command("discard", vec![])).await;
+        assert!(room.has_message_containing("Draft discarded"));
+        assert!(ctx
+            .session_store
+            .get_draft("!channel:matrix.org", "@user:matrix.org")
+            .unwrap()
+            .is_none());
+    }
+
+    // =========================================================================
+    // Pin Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_pin_saves_last_response_and_lists_it() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        handle_command(
+            &room,
+            &make_command("pin", vec![]),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Nothing to pin yet"));
+
+        let gorp_dir = std::path::Path::new(&dir).join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        std::fs::write(
+            gorp_dir.join("matrix-messages.jsonl"),
+            r#"{"message_type":"response","content":"Deploy with `make ship`"}"#,
+        )
+        .unwrap();
+
+        handle_command(
+            &room,
+            &make_command("pin", vec!["deploy", "recipe"]),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("📌 Pinned: Deploy with `make ship`"));
+
+        let pins = pins::list_pins(&dir).unwrap();
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].pinned_by, "@user:matrix.org");
+        assert_eq!(
+            pins[0].context.as_deref(),
+            Some("Last response - deploy recipe")
+        );
+
+        handle_command(
+            &room,
+            &make_command("pins", vec![]),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Pins for test-channel (1)"));
+        assert!(room.has_message_containing("1. ["));
+        assert!(room.has_message_containing("Last response - deploy recipe"));
+    }
+
+    #[tokio::test]
+    async fn test_pins_empty() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        let cmd = make_command("pins", vec![]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("No pins yet"));
+    }
+
+    // =========================================================================
+    // History Command Tests
+    // =========================================================================
+
+    async fn run_history(ctx: &TestContext, room: &MockChannel, args: Vec<&str>, is_dm: bool) {
+        handle_command(
+            room,
+            &make_command("history", args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            is_dm,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_history_shows_recent_turns() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
+
+        run_history(&ctx, &room, vec![], false).await;
+        assert!(room.has_message_containing("No history yet in test-channel"));
+
+        let gorp_dir = std::path::Path::new(&dir).join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        std::fs::write(
+            gorp_dir.join("matrix-messages.jsonl"),
+            [
+                r#"{"timestamp":"2026-03-01T09:00:00Z","message_type":"prompt","content":"how do I deploy?"}"#,
+                r#"{"timestamp":"2026-03-01T09:00:30Z","message_type":"response","content":"Run `make ship`"}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        run_history(&ctx, &room, vec!["2"], false).await;
+        assert!(room.has_message_containing("# 📜 test-channel: last 1 exchange"));
+        assert!(room.has_message_containing("**👤 You** · 2026-03-01 09:00\n\nhow do I deploy?"));
+        assert!(room.has_message_containing("**🤖 Agent** · 2026-03-01 09:00\n\nRun `make ship`"));
+
+        run_history(&ctx, &room, vec!["lots"], false).await;
+        assert!(room.has_message_containing("Usage: !history [n]"));
+    }
+
+    #[tokio::test]
+    async fn test_history_attaches_long_transcripts_and_exports() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
+        let gorp_dir = std::path::Path::new(&dir).join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        let answer = "x".repeat(MAX_CHUNK_SIZE);
+        let log: Vec<String> = (0..3)
+            .flat_map(|i| {
+                [
+                    format!(r#"{{"message_type":"prompt","content":"question {}"}}"#, i),
+                    format!(r#"{{"message_type":"response","content":"{}"}}"#, answer),
+                ]
+            })
+            .collect();
+        std::fs::write(gorp_dir.join("matrix-messages.jsonl"), log.join("\n")).unwrap();
+
+        run_history(&ctx, &room, vec!["2"], false).await;
+        assert_eq!(
+            room.last_message().unwrap().plain,
+            "📜 Last 2 exchanges in test-channel (attached, too long to post)"
+        );
+
+        run_history(&ctx, &room, vec!["export"], false).await;
+        let filename = history::transcript_filename(chrono::Utc::now());
+        assert!(room.has_message_containing(&format!(
+            "📜 Saved 3 exchanges to {} in the workspace.",
+            filename
+        )));
+        let transcript =
+            std::fs::read_to_string(std::path::Path::new(&dir).join(&filename)).unwrap();
+        assert!(transcript.starts_with("# 📜 test-channel: last 3 exchanges"));
+        assert!(transcript.contains("question 0"));
+        assert!(transcript.contains(&answer));
+    }
+
+    #[tokio::test]
+    async fn test_history_in_dm_without_channel() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!dm:matrix.org");
+
+        run_history(&ctx, &room, vec![], true).await;
+        assert!(room.has_message_containing("No history for this room"));
+    }
+
+    // =========================================================================
+    // Context Command Tests
+    // =========================================================================
+
+    async fn run_context(ctx: &TestContext, room: &MockChannel, args: Vec<&str>) {
+        handle_command(
+            room,
+            &make_command("context", args),
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_context_set_show_and_clear() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("ops", "!channel:matrix.org");
+
+        run_context(&ctx, &room, vec!["show"]).await;
+        assert!(room.has_message_containing("No context file yet"));
+
+        run_context(&ctx, &room, vec!["set", "project", "apollo", "launch"]).await;
+        assert!(room.has_message_containing("Context project = apollo launch"));
+        assert_eq!(
+            ctx.session_store
+                .get_custom_context("ops")
+                .unwrap()
+                .get("project")
+                .map(String::as_str),
+            Some("apollo launch")
+        );
+
+        room.clear();
+        run_context(&ctx, &room, vec!["show"]).await;
+        assert!(room.has_message_containing("\"project\": \"apollo launch\""));
+        assert!(room.has_message_containing("\"room_id\": \"!channel:matrix.org\""));
+
+        run_context(&ctx, &room, vec!["clear"]).await;
+        assert!(room.has_message_containing("Removed 1 custom context key"));
+        assert!(ctx
+            .session_store
+            .get_custom_context("ops")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_context_rejects_reserved_keys() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("ops", "!channel:matrix.org");
+
+        for key in ["room_id", "session_id"] {
+            run_context(&ctx, &room, vec!["set", key, "spoofed"]).await;
+        }
+
+        assert!(room.has_message_containing("'room_id' is reserved"));
+        assert!(room.has_message_containing("'session_id' is reserved"));
+        assert!(ctx
+            .session_store
+            .get_custom_context("ops")
+            .unwrap()
+            .is_empty());
+    }
+
+    // =========================================================================
+    // Deliver Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_deliver_window_set_and_status() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("ops", "!channel:matrix.org");
+        let cmd = make_command(
+            "deliver",
+            vec!["window", "weekdays", "08:00-18:00", "notice"],
+        );
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("Delivery window set: weekdays 08:00-18:00 UTC notice"));
+        let window = ctx
+            .session_store
+            .get_delivery_window("ops")
+            .unwrap()
+            .unwrap();
+        assert!(window.announce);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_window_invalid() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("ops", "!channel:matrix.org");
+        let cmd = make_command("deliver", vec!["window", "someday", "8-6"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert!(room.has_message_containing("Invalid delivery window"));
+        assert!(ctx
+            .session_store
+            .get_delivery_window("ops")
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deliver_now_flushes_held_messages_in_order() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("ops", "!channel:matrix.org");
+        ctx.session_store
+            .hold_message("ops", None, "first")
+            .unwrap();
+        ctx.session_store
+            .hold_message("ops", Some(("matrix", "!channel:matrix.org")), "second")
+            .unwrap();
+        let cmd = make_command("deliver", vec!["now"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        let messages = room.get_messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].plain, "first");
+        assert_eq!(messages[1].plain, "second");
+        assert!(ctx
+            .session_store
+            .list_held_messages("ops")
+            .unwrap()
+            .is_empty());
+    }
+
+    // =========================================================================
+    // Reset Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_reset_delegated_in_dm() {
+        let ctx = TestContext::new();
+        let room = MockChannel::dm("!dm:matrix.org");
+        let cmd = make_command("reset", vec!["channel-name"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true, // is_dm
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        // Should delegate to Matrix handler
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("DELEGATE_TO_MATRIX:reset"));
+    }
+
+    #[tokio::test]
+    async fn test_clear_starts_a_fresh_session_and_keeps_files() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        let before = ctx
+            .session_store
+            .get_by_name("test-channel")
+            .unwrap()
+            .unwrap();
+        ctx.session_store
+            .mark_started("!channel:matrix.org")
+            .unwrap();
+        ctx.session_store
+            .set_awaiting_reply("test-channel", Some("Which branch?"))
+            .unwrap();
+        let notes = std::path::Path::new(&before.directory).join("notes.md");
+        std::fs::create_dir_all(&before.directory).unwrap();
+        std::fs::write(&notes, "keep me").unwrap();
+
+        run_in_room(&ctx, &room, "clear", vec![]).await;
+
+        let after = ctx
+            .session_store
+            .get_by_name("test-channel")
+            .unwrap()
+            .unwrap();
+        assert_ne!(after.session_id, before.session_id);
+        assert!(!after.started);
+        assert_eq!(
+            ctx.session_store
+                .get_awaiting_reply("test-channel")
+                .unwrap(),
+            None
+        );
+        assert_eq!(std::fs::read_to_string(&notes).unwrap(), "keep me");
+        assert!(room.has_message_containing("files in the workspace are untouched"));
+
+        // A DM without a channel has nothing to clear
+        let dm = MockChannel::dm("!dm:matrix.org");
+        run_in_room(&ctx, &dm, "clear", vec![]).await;
+        assert!(dm.has_message_containing("No channel attached"));
+    }
+
+    // =========================================================================
+    // Delegated Command Tests
+    // Note: All reset commands (both local and remote) are delegated to matrix_commands.rs
+    // to ensure consistent use of reset_session (which also resets the started flag)
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_create_delegated() {
+        let ctx = TestContext::new();
+        let room = MockChannel::dm("!dm:matrix.org");
+        let cmd = make_command("create", vec!["new-channel"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("DELEGATE_TO_MATRIX:create"));
+    }
+
+    #[tokio::test]
+    async fn test_clone_delegated() {
+        let ctx = TestContext::new();
+        let room = MockChannel::dm("!dm:matrix.org");
+        let cmd = make_command("clone", vec!["research", "--to", "slack"]);
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("DELEGATE_TO_MATRIX:clone"));
+    }
 
     #[tokio::test]
-    async fn test_reset_delegated_in_dm() {
+    async fn test_join_delegated() {
         let ctx = TestContext::new();
         let room = MockChannel::dm("!dm:matrix.org");
-        let cmd = make_command("reset", vec!["channel-name"]);
+        let cmd = make_command("join", vec!["channel-name"]);
 
         let result = handle_command(
             &room,
@@ -1006,31 +3107,24 @@ mod tests {
             &ctx.scheduler_store,
             None,
             "@user:matrix.org",
-            true, // is_dm
+            true,
             &ctx.config,
             &ctx.warm_manager,
         )
         .await;
 
-        // Should delegate to Matrix handler
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("DELEGATE_TO_MATRIX:reset"));
+            .contains("DELEGATE_TO_MATRIX:join"));
     }
 
-    // =========================================================================
-    // Delegated Command Tests
-    // Note: All reset commands (both local and remote) are delegated to matrix_commands.rs
-    // to ensure consistent use of reset_session (which also resets the started flag)
-    // =========================================================================
-
     #[tokio::test]
-    async fn test_create_delegated() {
+    async fn test_schedule_delegated() {
         let ctx = TestContext::new();
-        let room = MockChannel::dm("!dm:matrix.org");
-        let cmd = make_command("create", vec!["new-channel"]);
+        let room = MockChannel::new("!channel:matrix.org");
+        let cmd = make_command("schedule", vec!["in", "1", "hour", "test"]);
 
         let result = handle_command(
             &room,
@@ -1039,13 +3133,128 @@ mod tests {
             &ctx.scheduler_store,
             None,
             "@user:matrix.org",
-            true,
+            false,
             &ctx.config,
             &ctx.warm_manager,
         )
         .await;
 
         assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("DELEGATE_TO_MATRIX:schedule"));
+    }
+
+    #[tokio::test]
+    async fn test_admin_commands_refused_for_users() {
+        let mut ctx = TestContext::new();
+        ctx.config.access.admins = vec!["@admin:matrix.org".to_string()];
+        let room = MockChannel::new("!dm:matrix.org");
+        ctx.create_channel("research", "!research:matrix.org");
+
+        let cmd = make_command("delete", vec!["research"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("!delete requires admin"));
+        assert!(ctx.session_store.get_by_name("research").unwrap().is_some());
+
+        // Admins get through to the Matrix handler
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@admin:matrix.org",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("DELEGATE_TO_MATRIX:delete"));
+
+        let cmd = make_command("promote", vec!["@user:matrix.org"]);
+        let room = MockChannel::new("!research:matrix.org");
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("!promote requires admin"));
+    }
+
+    #[tokio::test]
+    async fn test_platform_admins_gate_management_commands() {
+        let mut ctx = TestContext::new();
+        ctx.config.access.admins = vec!["@admin:matrix.org".to_string()];
+        ctx.config.access.platforms.insert(
+            "slack".to_string(),
+            PlatformAccess {
+                admins: Some(vec!["U0LEAD".to_string()]),
+                users: None,
+            },
+        );
+        let mut room = MockChannel::dm("D0SLACK");
+        room.platform_id = "slack".to_string();
+        let cmd = make_command("create", vec!["research"]);
+
+        // Admins from other platforms don't carry over to Slack
+        for sender in ["U0DEV", "@admin:matrix.org"] {
+            handle_command(
+                &room,
+                &cmd,
+                &ctx.session_store,
+                &ctx.scheduler_store,
+                None,
+                sender,
+                true,
+                &ctx.config,
+                &ctx.warm_manager,
+            )
+            .await
+            .unwrap();
+            assert!(room
+                .last_message()
+                .unwrap()
+                .plain
+                .contains("Permission denied: !create requires admin"));
+        }
+
+        let result = handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "U0LEAD",
+            true,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await;
         assert!(result
             .unwrap_err()
             .to_string()
@@ -1053,10 +3262,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_join_delegated() {
+    async fn test_approve_delegated() {
         let ctx = TestContext::new();
-        let room = MockChannel::dm("!dm:matrix.org");
-        let cmd = make_command("join", vec!["channel-name"]);
+        let room = MockChannel::new("!admin:matrix.org");
+        let cmd = make_command("approve", vec!["!invited:matrix.org"]);
 
         let result = handle_command(
             &room,
@@ -1065,25 +3274,67 @@ mod tests {
             &ctx.scheduler_store,
             None,
             "@user:matrix.org",
-            true,
+            false,
             &ctx.config,
             &ctx.warm_manager,
         )
         .await;
 
-        assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("DELEGATE_TO_MATRIX:join"));
+            .contains("DELEGATE_TO_MATRIX:approve"));
     }
 
     #[tokio::test]
-    async fn test_schedule_delegated() {
+    async fn test_safe_mode_allows_only_read_only_commands() {
         let ctx = TestContext::new();
-        let room = MockChannel::new("!channel:matrix.org");
-        let cmd = make_command("schedule", vec!["in", "1", "hour", "test"]);
+        ctx.warm_manager
+            .write()
+            .await
+            .set_runtime_mode(crate::runtime_mode::RuntimeMode::new(true));
+        let room = MockChannel::new("!research:matrix.org");
+        ctx.create_channel("research", "!research:matrix.org");
+
+        for (name, args) in [("status", vec![]), ("health", vec![])] {
+            let cmd = make_command(name, args);
+            handle_command(
+                &room,
+                &cmd,
+                &ctx.session_store,
+                &ctx.scheduler_store,
+                None,
+                "@user:matrix.org",
+                false,
+                &ctx.config,
+                &ctx.warm_manager,
+            )
+            .await
+            .unwrap();
+        }
+        assert!(room.has_message_containing("Channel: research"));
+        assert!(room.has_message_containing("Mode: 🛟 Safe mode"));
 
+        let cmd = make_command("debug", vec!["on"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+        assert!(room.has_message_containing("Safe mode is active, so !debug is unavailable"));
+        let ch = ctx.session_store.get_by_name("research").unwrap().unwrap();
+        assert!(!is_debug_enabled(&ch.directory));
+
+        // `schedule list` passes the gate and goes on to the Matrix handler
+        let cmd = make_command("schedule", vec!["list"]);
         let result = handle_command(
             &room,
             &cmd,
@@ -1096,14 +3347,47 @@ mod tests {
             &ctx.warm_manager,
         )
         .await;
-
-        assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
             .contains("DELEGATE_TO_MATRIX:schedule"));
     }
 
+    #[tokio::test]
+    async fn test_safemode_off_needs_confirmation() {
+        let ctx = TestContext::new();
+        let mode = crate::runtime_mode::RuntimeMode::new(true);
+        ctx.warm_manager
+            .write()
+            .await
+            .set_runtime_mode(mode.clone());
+        let room = MockChannel::new("!dm:matrix.org");
+
+        for (args, reply) in [
+            (vec![], "Safe mode is active"),
+            (vec!["off"], "Run !safemode off confirm"),
+            (vec!["off", "confirm"], "Safe mode is off"),
+        ] {
+            assert!(mode.is_safe());
+            let cmd = make_command("safemode", args);
+            handle_command(
+                &room,
+                &cmd,
+                &ctx.session_store,
+                &ctx.scheduler_store,
+                None,
+                "@admin:matrix.org",
+                true,
+                &ctx.config,
+                &ctx.warm_manager,
+            )
+            .await
+            .unwrap();
+            assert!(room.has_message_containing(reply), "{}", reply);
+        }
+        assert!(!mode.is_safe());
+    }
+
     // =========================================================================
     // Unknown Command Tests
     // =========================================================================
@@ -1155,4 +3439,239 @@ mod tests {
         assert!(room.has_message_containing("Unknown command"));
         assert!(room.has_message_containing("!status"));
     }
+
+    // =========================================================================
+    // Compare Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_compare_reports_both_sides_and_records_experiment() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        let cmd = make_command("compare", vec!["mock", "nonesuch", "hello", "there"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("⚖️ Comparison: mock vs nonesuch"));
+        assert!(room.has_message_containing("Mock: no expectation for 'hello there'"));
+        assert!(room.has_message_containing(
+            "❌ Failed: Failed to start backend: Unknown backend: nonesuch"
+        ));
+
+        let experiments = ctx.session_store.list_experiments("test-channel").unwrap();
+        assert_eq!(experiments.len(), 1);
+        assert_eq!(experiments[0].kind, "compare");
+        assert_eq!(experiments[0].prompt, "hello there");
+        assert_eq!(experiments[0].details["a"]["ok"], true);
+        assert_eq!(experiments[0].details["b"]["ok"], false);
+
+        // Only the side that ran counts toward usage
+        let usage = ctx.session_store.get_channel_usage("test-channel").unwrap();
+        assert_eq!(usage.conversation.invocations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_compare_requires_prompt() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        let cmd = make_command("compare", vec!["mock", "mock"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("Usage: !compare"));
+        assert!(ctx
+            .session_store
+            .list_experiments("test-channel")
+            .unwrap()
+            .is_empty());
+    }
+
+    // =========================================================================
+    // Ask Command Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_ask_leaves_main_session_untouched() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        ctx.session_store
+            .update_backend_type("test-channel", Some("mock"))
+            .unwrap();
+        ctx.session_store
+            .update_session_id("!channel:matrix.org", "main-session")
+            .unwrap();
+        ctx.session_store
+            .mark_started("!channel:matrix.org")
+            .unwrap();
+        let before = ctx
+            .session_store
+            .get_by_name("test-channel")
+            .unwrap()
+            .unwrap();
+        let transcript = std::path::Path::new(&before.directory)
+            .join(".gorp")
+            .join("matrix-messages.jsonl");
+
+        let cmd = make_command("ask", vec!["what", "is", "this?"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("(one-off) Mock: no expectation for"));
+        assert!(room.has_message_containing("what is this?"));
+
+        let after = ctx
+            .session_store
+            .get_by_name("test-channel")
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.session_id, "main-session");
+        assert!(after.started);
+        assert!(!ctx.warm_manager.read().await.has_session("test-channel"));
+        assert!(!transcript.exists());
+
+        // Counted, but as overhead rather than conversation
+        let usage = ctx.session_store.get_channel_usage("test-channel").unwrap();
+        assert_eq!(usage.overhead.invocations, 1);
+        assert_eq!(usage.conversation.invocations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_ask_requires_question() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+
+        let cmd = make_command("ask", vec!["--write"]);
+        handle_command(
+            &room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+
+        assert!(room.has_message_containing("Usage: !ask"));
+    }
+
+    // =========================================================================
+    // Summarize Command Tests
+    // =========================================================================
+
+    async fn run_summarize(ctx: &TestContext, room: &MockChannel) {
+        let cmd = make_command("summarize", vec![]);
+        handle_command(
+            room,
+            &cmd,
+            &ctx.session_store,
+            &ctx.scheduler_store,
+            None,
+            "@user:matrix.org",
+            false,
+            &ctx.config,
+            &ctx.warm_manager,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_summarize_sends_log_to_agent() {
+        let mut ctx = TestContext::new();
+        let room = MockChannel::new("!channel:matrix.org");
+        ctx.create_channel("test-channel", "!channel:matrix.org");
+        ctx.session_store
+            .update_backend_type("test-channel", Some("mock"))
+            .unwrap();
+
+        run_summarize(&ctx, &room).await;
+        assert!(room.has_message_containing("Nothing to summarize yet in test-channel"));
+
+        let dir = ctx
+            .session_store
+            .get_by_room("!channel:matrix.org")
+            .unwrap()
+            .unwrap()
+            .directory;
+        let gorp_dir = std::path::Path::new(&dir).join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        std::fs::write(
+            gorp_dir.join("matrix-messages.jsonl"),
+            [
+                r#"{"message_type":"prompt","content":"old question"}"#,
+                r#"{"message_type":"response","content":"old answer"}"#,
+                r#"{"message_type":"prompt","content":"how do I deploy?"}"#,
+                r#"{"message_type":"response","content":"Run make ship"}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        run_summarize(&ctx, &room).await;
+        assert!(room.has_message_containing("📝 Summary of test-channel (2 exchanges)"));
+        // The mock backend echoes the prompt it was given
+        assert!(room.has_message_containing("concise bulleted list"));
+        assert!(room.has_message_containing("how do I deploy?"));
+        assert!(!room.has_message_containing("left out"));
+
+        ctx.config.maintenance.summarize_max_chars = 60;
+        run_summarize(&ctx, &room).await;
+        let reply = room.last_message().unwrap().plain;
+        assert!(reply.starts_with("📝 Summary of test-channel (1 exchange)"));
+        assert!(reply.contains("Run make ship"));
+        assert!(!reply.contains("old question"));
+        assert!(reply.ends_with("_Older history was left out to fit the summary budget._"));
+    }
+
+    #[tokio::test]
+    async fn test_summarize_only_in_channel_rooms() {
+        let ctx = TestContext::new();
+        let room = MockChannel::new("!other:matrix.org");
+
+        run_summarize(&ctx, &room).await;
+        assert!(room.has_message_containing("No channel attached to this room."));
+    }
 }
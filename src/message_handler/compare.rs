@@ -0,0 +1,484 @@
+// ABOUTME: Runs one prompt against two backends concurrently for !compare and formats both answers.
+// ABOUTME: Each side works in a throwaway copy of the channel workspace so the agents can't collide.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use gorp_agent::{AgentEvent, AgentHandle, Usage};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::tf,
+    session::SessionStore,
+    usage::InvocationOrigin,
+    utils::{chunk_message, MAX_CHUNK_SIZE},
+    warm_session::{SharedWarmSessionManager, WarmSessionManager},
+};
+
+use super::commands::{channel_for_command, reply_privately};
+
+/// Experiment kind recorded for !compare runs
+pub const COMPARE_EXPERIMENT: &str = "compare";
+
+/// Temporary copy of a channel workspace, removed when dropped
+pub struct ScratchWorkspace {
+    path: PathBuf,
+}
+
+impl ScratchWorkspace {
+    /// Copy `source` into a fresh temp directory.
+    /// Bot state in .gorp stays behind; symlinks are skipped rather than followed.
+    pub fn copy_of(source: &Path, label: &str) -> Result<Self> {
+        let path =
+            std::env::temp_dir().join(format!("gorp-compare-{}-{}", label, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        // Construct first so a failed copy still cleans up
+        let scratch = Self { path };
+        copy_tree(source, &scratch.path, true)?;
+        Ok(scratch)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchWorkspace {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            tracing::warn!(path = %self.path.display(), error = %e, "Failed to remove compare workspace");
+        }
+    }
+}
+
+fn copy_tree(src: &Path, dst: &Path, top_level: bool) -> Result<()> {
+    for entry in
+        std::fs::read_dir(src).with_context(|| format!("Failed to read {}", src.display()))?
+    {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let name = entry.file_name();
+        if top_level && name == ".gorp" {
+            continue;
+        }
+        let target = dst.join(&name);
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            copy_tree(&entry.path(), &target, false)?;
+        } else if file_type.is_file() {
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Result of running the prompt against one backend profile
+#[derive(Debug, Clone)]
+pub struct SideResult {
+    pub profile: String,
+    pub latency: Duration,
+    pub usage: Option<Usage>,
+    /// The response text, or why this side failed
+    pub outcome: Result<String, String>,
+}
+
+/// Run the prompt against both sides at once.
+/// A side whose handle couldn't be created fails on its own without stopping the other.
+pub async fn run_comparison(
+    prompt: &str,
+    a: (&str, Result<AgentHandle>),
+    b: (&str, Result<AgentHandle>),
+    timeout: Duration,
+) -> (SideResult, SideResult) {
+    tokio::join!(
+        run_side(a.0, a.1, prompt, timeout),
+        run_side(b.0, b.1, prompt, timeout)
+    )
+}
+
+async fn run_side(
+    profile: &str,
+    handle: Result<AgentHandle>,
+    prompt: &str,
+    timeout: Duration,
+) -> SideResult {
+    let started = Instant::now();
+    let result = match handle {
+        Ok(handle) => {
+            match tokio::time::timeout(timeout, collect_response(&handle, prompt)).await {
+                Ok(result) => result,
+                Err(_) => Err(anyhow::anyhow!("Timed out after {}s", timeout.as_secs())),
+            }
+        }
+        Err(e) => Err(e.context("Failed to start backend")),
+    };
+
+    let (outcome, usage) = match result {
+        Ok((text, usage)) => (Ok(text), usage),
+        Err(e) => (Err(format!("{:#}", e)), None),
+    };
+    SideResult {
+        profile: profile.to_string(),
+        latency: started.elapsed(),
+        usage,
+        outcome,
+    }
+}
+
+/// Run `prompt` in a fresh session on `handle`, returning the answer and its usage
+pub async fn collect_response(
+    handle: &AgentHandle,
+    prompt: &str,
+) -> Result<(String, Option<Usage>)> {
+    let session_id = handle.new_session().await?;
+    let mut receiver = handle.prompt(&session_id, prompt).await?;
+    let mut streamed = String::new();
+
+    while let Some(event) = receiver.recv().await {
+        match event {
+            AgentEvent::Text(text) => streamed.push_str(&text),
+            AgentEvent::Result { text, usage, .. } => {
+                let text = if text.is_empty() { streamed } else { text };
+                return Ok((text, usage));
+            }
+            AgentEvent::Error { message, .. } => anyhow::bail!(message),
+            AgentEvent::SessionInvalid { reason } => anyhow::bail!("Session invalid: {}", reason),
+            _ => {}
+        }
+    }
+    anyhow::bail!("Backend finished without a result")
+}
+
+fn stats_line(side: &SideResult) -> String {
+    let mut line = format!("⏱ {:.1}s", side.latency.as_secs_f64());
+    match &side.usage {
+        Some(usage) => {
+            line.push_str(&format!(
+                " · {} in / {} out tokens",
+                usage.input_tokens, usage.output_tokens
+            ));
+            if let Some(cost) = usage.cost_usd {
+                line.push_str(&format!(" · ${:.4}", cost));
+            }
+        }
+        None => line.push_str(" · usage not reported"),
+    }
+    line
+}
+
+/// Format both sides as labeled sections, one after the other
+pub fn format_comparison(a: &SideResult, b: &SideResult) -> String {
+    let section = |label: &str, side: &SideResult| {
+        let body = match &side.outcome {
+            Ok(text) => text.trim().to_string(),
+            Err(e) => format!("❌ Failed: {}", e),
+        };
+        format!(
+            "━━ {}: {} ━━\n{}\n\n{}",
+            label,
+            side.profile,
+            stats_line(side),
+            body
+        )
+    };
+    format!(
+        "⚖️ Comparison: {} vs {}\n\n{}\n\n{}",
+        a.profile,
+        b.profile,
+        section("A", a),
+        section("B", b)
+    )
+}
+
+/// Per-side results stored with the experiment record
+pub fn comparison_details(a: &SideResult, b: &SideResult) -> serde_json::Value {
+    let side = |side: &SideResult| {
+        serde_json::json!({
+            "profile": side.profile,
+            "latency_ms": side.latency.as_millis() as u64,
+            "input_tokens": side.usage.as_ref().map(|u| u.input_tokens),
+            "output_tokens": side.usage.as_ref().map(|u| u.output_tokens),
+            "cost_usd": side.usage.as_ref().and_then(|u| u.cost_usd),
+            "ok": side.outcome.is_ok(),
+            "error": side.outcome.as_ref().err(),
+        })
+    };
+    serde_json::json!({ "a": side(a), "b": side(b) })
+}
+
+/// `!compare <backend-a> <backend-b> <prompt>`: run the prompt on both backends and post the answers
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn handle_compare_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    timeout: Duration,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "compare", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let (warm_config, registry) = {
+        let mgr = warm_manager.read().await;
+        (mgr.config(), mgr.registry())
+    };
+    let mut available: Vec<&str> = registry.available();
+    available.sort_unstable();
+
+    let prompt = args.get(2..).unwrap_or_default().join(" ");
+    if prompt.trim().is_empty() {
+        let usage = tf(
+            locale,
+            "compare.usage",
+            &[("available", &available.join(", "))],
+        );
+        return reply_privately(channel, sender, MessageContent::plain(usage)).await;
+    }
+    let profile_a = args[0].to_lowercase();
+    let profile_b = args[1].to_lowercase();
+
+    let starting = tf(
+        locale,
+        "compare.starting",
+        &[("a", &profile_a), ("b", &profile_b)],
+    );
+    channel.send(MessageContent::plain(starting)).await?;
+
+    // Each side gets its own copy of the workspace; the copies live until both finish
+    let channel_dir = Path::new(&ch.directory);
+    let scratch_a = ScratchWorkspace::copy_of(channel_dir, &profile_a)?;
+    let scratch_b = ScratchWorkspace::copy_of(channel_dir, &profile_b)?;
+    let handle_for = |profile: &str, scratch: &ScratchWorkspace| {
+        if !available.contains(&profile) {
+            return Err(anyhow::anyhow!("Unknown backend: {}", profile));
+        }
+        WarmSessionManager::create_agent_handle_with_config(
+            &registry,
+            &scratch.path().to_string_lossy(),
+            &warm_config,
+            Some(profile),
+        )
+    };
+
+    let (a, b) = run_comparison(
+        &prompt,
+        (&profile_a, handle_for(&profile_a, &scratch_a)),
+        (&profile_b, handle_for(&profile_b, &scratch_b)),
+        timeout,
+    )
+    .await;
+    drop((scratch_a, scratch_b));
+
+    for side in [&a, &b] {
+        if side.outcome.is_ok() {
+            session_store.record_usage(
+                &ch.channel_name,
+                InvocationOrigin::User,
+                side.usage.as_ref(),
+            )?;
+        }
+    }
+    session_store.record_experiment(
+        &ch.channel_name,
+        COMPARE_EXPERIMENT,
+        &prompt,
+        &comparison_details(&a, &b),
+    )?;
+    tracing::info!(
+        channel = %ch.channel_name,
+        a = %profile_a,
+        b = %profile_b,
+        a_ok = a.outcome.is_ok(),
+        b_ok = b.outcome.is_ok(),
+        "Backend comparison finished"
+    );
+
+    for chunk in chunk_message(&format_comparison(&a, &b), MAX_CHUNK_SIZE) {
+        channel.send(MessageContent::plain(chunk)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gorp_agent::backends::mock::MockBackend;
+    use gorp_agent::handle::Command;
+    use gorp_agent::ErrorCode;
+    use tempfile::TempDir;
+    use tokio::sync::mpsc;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn scripted(text: &str, input: u64, output: u64, cost: f64) -> AgentHandle {
+        MockBackend::new()
+            .on_prompt("")
+            .respond_with(vec![AgentEvent::Result {
+                text: text.to_string(),
+                usage: Some(Usage {
+                    input_tokens: input,
+                    output_tokens: output,
+                    cost_usd: Some(cost),
+                    ..Default::default()
+                }),
+                metadata: serde_json::json!({}),
+            }])
+            .into_handle()
+    }
+
+    /// Backend that waits before answering, to show both sides run at the same time
+    fn slow(delay: Duration, text: &'static str) -> AgentHandle {
+        let (tx, mut rx) = mpsc::channel::<Command>(8);
+        tokio::spawn(async move {
+            while let Some(cmd) = rx.recv().await {
+                match cmd {
+                    Command::NewSession { reply } => {
+                        let _ = reply.send(Ok("slow-session".to_string()));
+                    }
+                    Command::Prompt {
+                        event_tx, reply, ..
+                    } => {
+                        let _ = reply.send(Ok(()));
+                        tokio::spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            let _ = event_tx
+                                .send(AgentEvent::Result {
+                                    text: text.to_string(),
+                                    usage: None,
+                                    metadata: serde_json::json!({}),
+                                })
+                                .await;
+                        });
+                    }
+                    Command::LoadSession { reply, .. } | Command::Cancel { reply, .. } => {
+                        let _ = reply.send(Ok(()));
+                    }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(Vec::new()));
+                    }
+                }
+            }
+        });
+        AgentHandle::new(tx, "slow")
+    }
+
+    #[tokio::test]
+    async fn test_sides_run_concurrently() {
+        let delay = Duration::from_millis(300);
+        let started = Instant::now();
+        let (a, b) = run_comparison(
+            "hello",
+            ("left", Ok(slow(delay, "from left"))),
+            ("right", Ok(slow(delay, "from right"))),
+            TIMEOUT,
+        )
+        .await;
+
+        assert_eq!(a.outcome.as_deref(), Ok("from left"));
+        assert_eq!(b.outcome.as_deref(), Ok("from right"));
+        // Sequential runs would take at least 2x the delay
+        assert!(started.elapsed() < delay * 2, "{:?}", started.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_differently_scripted_backends_are_labeled() {
+        let (a, b) = run_comparison(
+            "explain lifetimes",
+            ("acp", Ok(scripted("Answer from A", 120, 40, 0.0123))),
+            ("mux", Ok(scripted("Answer from B", 90, 75, 0.02))),
+            TIMEOUT,
+        )
+        .await;
+
+        assert_eq!(a.usage.as_ref().map(|u| u.output_tokens), Some(40));
+        assert_eq!(b.usage.as_ref().map(|u| u.output_tokens), Some(75));
+
+        let text = format_comparison(&a, &b);
+        assert!(text.starts_with("⚖️ Comparison: acp vs mux"));
+        let a_at = text.find("━━ A: acp ━━").unwrap();
+        let b_at = text.find("━━ B: mux ━━").unwrap();
+        assert!(a_at < text.find("Answer from A").unwrap());
+        assert!(b_at < text.find("Answer from B").unwrap());
+        assert!(text.contains("120 in / 40 out tokens · $0.0123"));
+        assert!(text.contains("90 in / 75 out tokens · $0.0200"));
+    }
+
+    #[tokio::test]
+    async fn test_one_failing_side_still_reports_the_other() {
+        let failing = MockBackend::new()
+            .on_prompt("")
+            .respond_error(ErrorCode::BackendError, "model overloaded")
+            .into_handle();
+        let (a, b) = run_comparison(
+            "hi",
+            ("good", Ok(scripted("still here", 1, 1, 0.0))),
+            ("bad", Ok(failing)),
+            TIMEOUT,
+        )
+        .await;
+
+        assert!(a.outcome.is_ok());
+        assert_eq!(b.outcome, Err("model overloaded".to_string()));
+        let text = format_comparison(&a, &b);
+        assert!(text.contains("still here"));
+        assert!(text.contains("❌ Failed: model overloaded"));
+
+        let details = comparison_details(&a, &b);
+        assert_eq!(details["a"]["ok"], true);
+        assert_eq!(details["b"]["ok"], false);
+        assert_eq!(details["b"]["error"], "model overloaded");
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_backend_fails_its_side_only() {
+        let (a, b) = run_comparison(
+            "hi",
+            ("nope", Err(anyhow::anyhow!("Unknown backend: nope"))),
+            ("good", Ok(scripted("fine", 1, 1, 0.0))),
+            TIMEOUT,
+        )
+        .await;
+
+        assert!(a.outcome.unwrap_err().contains("Unknown backend: nope"));
+        assert_eq!(b.outcome.as_deref(), Ok("fine"));
+    }
+
+    #[test]
+    fn test_scratch_workspaces_are_isolated_copies() {
+        let source = TempDir::new().unwrap();
+        std::fs::write(source.path().join("README.md"), "original").unwrap();
+        std::fs::create_dir_all(source.path().join("src")).unwrap();
+        std::fs::write(source.path().join("src/lib.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir_all(source.path().join(".gorp")).unwrap();
+        std::fs::write(source.path().join(".gorp/pins.md"), "state").unwrap();
+
+        let left = ScratchWorkspace::copy_of(source.path(), "left").unwrap();
+        let right = ScratchWorkspace::copy_of(source.path(), "right").unwrap();
+        assert_ne!(left.path(), right.path());
+        assert!(left.path().join("src/lib.rs").exists());
+        assert!(!left.path().join(".gorp").exists());
+
+        // Writes on one side touch neither the other side nor the channel
+        std::fs::write(left.path().join("README.md"), "changed by left").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(right.path().join("README.md")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            std::fs::read_to_string(source.path().join("README.md")).unwrap(),
+            "original"
+        );
+
+        let left_path = left.path().to_path_buf();
+        drop(left);
+        assert!(!left_path.exists());
+    }
+}
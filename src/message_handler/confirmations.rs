@@ -0,0 +1,295 @@
+// ABOUTME: Confirm / Cancel buttons for !delete and !cleanup on platforms without Matrix's DM flow.
+// ABOUTME: Holds the command until its requester presses Confirm, then runs it through the platform.
+
+use anyhow::Result;
+use gorp_core::traits::{ConfirmationProvider, IncomingMessage, MessageContent, MessagingPlatform};
+
+use crate::{
+    commands::Command,
+    confirmations::{PendingConfirmation, Resolution, CANCEL_COMMAND, CONFIRM_COMMAND},
+    metrics,
+    runtime_mode::Gate,
+    server::ServerState,
+};
+
+use super::{archive, send_reply};
+
+/// Commands that only run once their requester confirms them
+pub const DESTRUCTIVE_COMMANDS: &[&str] = &["delete", "cleanup"];
+
+/// Whether `cmd` is a destructive command to hold for confirmation
+pub fn needs_confirmation(cmd: &Command) -> bool {
+    DESTRUCTIVE_COMMANDS.contains(&cmd.name.as_str())
+}
+
+/// Whether `cmd` is a Confirm or Cancel press coming back from the platform
+pub fn is_answer(cmd: &Command) -> bool {
+    cmd.name == CONFIRM_COMMAND || cmd.name == CANCEL_COMMAND
+}
+
+/// Check `cmd` can run, then hold it and post its Confirm / Cancel buttons
+pub async fn ask(
+    provider: &dyn ConfirmationProvider,
+    platform: &dyn MessagingPlatform,
+    state: &ServerState,
+    msg: &IncomingMessage,
+    cmd: &Command,
+) -> Result<()> {
+    // The same gates handle_command applies, checked before anything is held
+    if !state
+        .config
+        .can_run(&msg.platform_id, &msg.sender.id, &cmd.name)
+    {
+        let notice = format!(
+            "⛔ Permission denied: {}{} requires admin.",
+            cmd.prefix, cmd.name
+        );
+        send_reply(platform, msg, MessageContent::plain(notice)).await?;
+        return Ok(());
+    }
+    let runtime = state.warm_manager.read().await.runtime_mode();
+    if let Err(refusal) = runtime.check(Gate::Command {
+        name: &cmd.name,
+        args: &cmd.args,
+    }) {
+        send_reply(platform, msg, MessageContent::plain(refusal.message())).await?;
+        return Ok(());
+    }
+    metrics::record_command(&cmd.name);
+
+    if !msg.is_direct {
+        let notice = format!(
+            "❌ The {}{} command only works in DMs.",
+            cmd.prefix, cmd.name
+        );
+        send_reply(platform, msg, MessageContent::plain(notice)).await?;
+        return Ok(());
+    }
+
+    let (question, args) = match cmd.name.as_str() {
+        "delete" => {
+            let Some(name) = cmd.args.first().map(|name| name.to_lowercase()) else {
+                let usage = format!(
+                    "Usage: {}delete <channel-name>\n\n\
+                    This removes the channel from the database and the bot leaves its chat.\n\
+                    The workspace directory is preserved.",
+                    cmd.prefix
+                );
+                send_reply(platform, msg, MessageContent::plain(usage)).await?;
+                return Ok(());
+            };
+            if state
+                .session_store
+                .get_by_name_on(&msg.platform_id, &name)?
+                .is_none()
+            {
+                let notice = format!(
+                    "❌ Channel '{}' not found.\n\nUse {}list to see all channels.",
+                    name, cmd.prefix
+                );
+                send_reply(platform, msg, MessageContent::plain(notice)).await?;
+                return Ok(());
+            }
+            (
+                format!(
+                    "⚠️ Delete channel '{}'? The bot leaves its chat and forgets the channel; \
+                    the workspace is kept.",
+                    name
+                ),
+                vec![name],
+            )
+        }
+        _ => {
+            let stale = stale_channels(platform, state).await?;
+            if stale.is_empty() {
+                send_reply(
+                    platform,
+                    msg,
+                    MessageContent::plain(
+                        "✨ Nothing to clean up: every channel's chat is reachable.",
+                    ),
+                )
+                .await?;
+                return Ok(());
+            }
+            (
+                format!(
+                    "⚠️ The bot can no longer reach the chats of these channels:\n{}\n\n\
+                    Remove them from the database? Their workspaces are kept.",
+                    stale
+                        .iter()
+                        .map(|name| format!("  • {}", name))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ),
+                stale,
+            )
+        }
+    };
+
+    let token = state.confirmations.insert(PendingConfirmation {
+        platform_id: msg.platform_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        requester_id: msg.sender.id.clone(),
+        command: cmd.name.clone(),
+        args,
+    });
+    let question = format!("{}\n\nThese buttons expire in 2 minutes.", question);
+    provider
+        .ask_confirmation(&msg.channel_id, &msg.sender.id, &question, &token)
+        .await
+}
+
+/// Settle a Confirm / Cancel press, running the held command on Confirm
+pub async fn answer(
+    platform: &dyn MessagingPlatform,
+    state: &ServerState,
+    msg: &IncomingMessage,
+    cmd: &Command,
+) -> Result<()> {
+    let token = cmd.args.first().map(String::as_str).unwrap_or_default();
+    let confirmed = cmd.name == CONFIRM_COMMAND;
+    let resolution = state.confirmations.resolve(
+        token,
+        &msg.platform_id,
+        &msg.channel_id,
+        &msg.sender.id,
+        confirmed,
+    );
+    let reply = match resolution {
+        Resolution::Confirmed(pending) => {
+            tracing::info!(
+                platform = %pending.platform_id,
+                requester = %pending.requester_id,
+                command = %pending.command,
+                "Destructive command confirmed"
+            );
+            match pending.command.as_str() {
+                "delete" => delete_channel(platform, state, &pending).await?,
+                _ => remove_stale_channels(state, &pending).await?,
+            }
+        }
+        Resolution::Cancelled(_) => "Cancelled; nothing was changed.".to_string(),
+        Resolution::NotRequester => {
+            "Only the person who ran the command can confirm it.".to_string()
+        }
+        Resolution::Unknown => {
+            "⌛ That confirmation expired or was already answered. Run the command again."
+                .to_string()
+        }
+    };
+    send_reply(platform, msg, MessageContent::plain(reply)).await
+}
+
+/// Delete the channel named in `pending`: recap it if configured, leave its
+/// chat, drop its warm session and remove it from the database
+async fn delete_channel(
+    platform: &dyn MessagingPlatform,
+    state: &ServerState,
+    pending: &PendingConfirmation,
+) -> Result<String> {
+    let name = pending.args.first().map(String::as_str).unwrap_or_default();
+    let Some(channel) = state
+        .session_store
+        .get_by_name_on(&pending.platform_id, name)?
+    else {
+        return Ok(format!("❌ Channel '{}' no longer exists.", name));
+    };
+
+    // Recap the conversation while the agent can still see it
+    let summary_line = match archive::summarize_before_archive(
+        &state.config.maintenance,
+        &channel,
+        &state.session_store,
+        &state.warm_manager,
+    )
+    .await
+    {
+        Ok(Some(path)) => format!("\n- Summary saved: {}", path.display()),
+        Ok(None) => String::new(),
+        Err(e) => {
+            tracing::warn!(error = %e, channel = %name, "Archive summary failed");
+            format!("\n- ⚠️ Summary failed: {}", e)
+        }
+    };
+
+    let mut lines = vec![format!("✅ Deleted channel: {}\n", name)];
+    if let Some(manager) = platform.channel_manager() {
+        match manager.leave(&channel.room_id).await {
+            Ok(()) => lines.push("- Bot left the chat".to_string()),
+            Err(e) => {
+                tracing::warn!(error = %e, chat = %channel.room_id, "Failed to leave chat");
+                lines.push(format!("- ⚠️ Couldn't leave the chat: {}", e));
+            }
+        }
+    }
+
+    // Drop the warm session too, so a recreated channel starts fresh
+    state
+        .warm_manager
+        .write()
+        .await
+        .evict(&channel.channel_name);
+    state.session_store.delete_channel(&channel.channel_name)?;
+    metrics::decrement_active_channels();
+
+    tracing::info!(
+        channel_name = %name,
+        chat = %channel.room_id,
+        platform = %pending.platform_id,
+        "Channel deleted by user"
+    );
+    lines.push("- Removed from database".to_string());
+    lines.push(format!(
+        "- Workspace preserved: {}{}",
+        channel.directory, summary_line
+    ));
+    Ok(lines.join("\n"))
+}
+
+/// Channels on this platform whose chat the bot can no longer reach
+async fn stale_channels(
+    platform: &dyn MessagingPlatform,
+    state: &ServerState,
+) -> Result<Vec<String>> {
+    let Some(manager) = platform.channel_manager() else {
+        return Ok(Vec::new());
+    };
+    let mut stale = Vec::new();
+    for channel in state.session_store.list_on(platform.platform_id())? {
+        if let Err(e) = manager.members(&channel.room_id).await {
+            tracing::debug!(
+                channel = %channel.channel_name,
+                error = %e,
+                "Channel's chat is unreachable"
+            );
+            stale.push(channel.channel_name);
+        }
+    }
+    Ok(stale)
+}
+
+/// Remove the channels `!cleanup` listed when it asked
+async fn remove_stale_channels(
+    state: &ServerState,
+    pending: &PendingConfirmation,
+) -> Result<String> {
+    let mut removed = Vec::new();
+    for name in &pending.args {
+        if state.session_store.get_by_name(name)?.is_none() {
+            continue;
+        }
+        state.warm_manager.write().await.evict(name);
+        state.session_store.delete_channel(name)?;
+        metrics::decrement_active_channels();
+        removed.push(name.as_str());
+    }
+    if removed.is_empty() {
+        return Ok("✨ Nothing left to clean up.".to_string());
+    }
+    Ok(format!(
+        "🧹 Removed {} stale channel(s) from the database: {}",
+        removed.len(),
+        removed.join(", ")
+    ))
+}
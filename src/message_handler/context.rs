@@ -1,30 +1,70 @@
 // ABOUTME: Context file and dispatch event routing
-// ABOUTME: MCP context files and DISPATCH control plane event handling
+// ABOUTME: MCP context files (viewed and extended with !context) and DISPATCH control plane event handling
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use gorp_core::traits::{ChatChannel, MessageContent};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::i18n::{t, tf, tn};
 use crate::session::SessionStore;
 
+use super::commands::{channel_for_command, reply_privately};
+
+/// Keys gorp writes into the context file itself; custom context can't override them
+pub const RESERVED_CONTEXT_KEYS: &[&str] = &["room_id", "channel_name", "session_id", "updated_at"];
+
+/// Longest custom context key accepted from chat
+const MAX_CONTEXT_KEY_CHARS: usize = 64;
+
+/// Path of the context file inside a channel directory
+pub fn context_file_path(channel_dir: &str) -> std::path::PathBuf {
+    Path::new(channel_dir).join(".gorp").join("context.json")
+}
+
+/// Check a custom context key before it is stored
+pub fn validate_context_key(key: &str) -> Result<()> {
+    if RESERVED_CONTEXT_KEYS.contains(&key) {
+        bail!("'{}' is reserved and set by gorp", key);
+    }
+    if key.is_empty() || key.chars().count() > MAX_CONTEXT_KEY_CHARS {
+        bail!("Keys must be 1-{} characters", MAX_CONTEXT_KEY_CHARS);
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+    {
+        bail!("Keys may only use letters, digits, '_', '-' and '.'");
+    }
+    Ok(())
+}
+
 /// Write context file for MCP tools to read
-/// This tells tools like gorp_schedule_prompt which channel/room they're operating in
+/// This tells tools like gorp_schedule_prompt which channel/room they're operating in.
+/// Custom keys set with !context are merged in; reserved keys always win.
 pub async fn write_context_file(
     channel_dir: &str,
     room_id: &str,
     channel_name: &str,
     session_id: &str,
+    custom: &BTreeMap<String, String>,
 ) -> Result<()> {
     let gorp_dir = Path::new(channel_dir).join(".gorp");
     tokio::fs::create_dir_all(&gorp_dir).await?;
 
-    let context = serde_json::json!({
-        "room_id": room_id,
-        "channel_name": channel_name,
-        "session_id": session_id,
-        "updated_at": chrono::Utc::now().to_rfc3339()
-    });
+    let mut context = serde_json::Map::new();
+    for (key, value) in custom {
+        context.insert(key.clone(), serde_json::Value::String(value.clone()));
+    }
+    context.insert("room_id".to_string(), room_id.into());
+    context.insert("channel_name".to_string(), channel_name.into());
+    context.insert("session_id".to_string(), session_id.into());
+    context.insert(
+        "updated_at".to_string(),
+        chrono::Utc::now().to_rfc3339().into(),
+    );
 
-    let context_path = gorp_dir.join("context.json");
+    let context_path = context_file_path(channel_dir);
     tokio::fs::write(&context_path, serde_json::to_string_pretty(&context)?).await?;
 
     tracing::debug!(path = %context_path.display(), "Wrote MCP context file");
@@ -63,6 +103,96 @@ pub async fn route_to_dispatch(
     Ok(())
 }
 
+/// `!context [show|set <key> <value>|clear [key]]`: view the MCP context file or edit its custom keys
+pub(crate) async fn handle_context_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "context", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let mut custom = session_store.get_custom_context(&ch.channel_name)?;
+    let mut changed = false;
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        None | Some("show") => {
+            match tokio::fs::read_to_string(context_file_path(&ch.directory)).await {
+                Ok(json) => tf(locale, "context.file", &[("json", json.trim_end())]),
+                Err(_) => {
+                    let mut reply = t(locale, "context.no_file");
+                    if !custom.is_empty() {
+                        reply.push_str(&tf(
+                            locale,
+                            "context.custom_keys",
+                            &[("keys", &serde_json::to_string_pretty(&custom)?)],
+                        ));
+                    }
+                    reply
+                }
+            }
+        }
+        Some("set") => {
+            let value = args.get(2..).unwrap_or_default().join(" ");
+            let Some(key) = args.get(1).filter(|_| !value.is_empty()) else {
+                let usage = MessageContent::plain(t(locale, "context.usage_set"));
+                return reply_privately(channel, sender, usage).await;
+            };
+            if let Err(e) = validate_context_key(key) {
+                let reply = tf(locale, "command.failed", &[("error", &e.to_string())]);
+                return channel.send(MessageContent::plain(reply)).await;
+            }
+            custom.insert(key.clone(), value.clone());
+            session_store.set_custom_context(&ch.channel_name, &custom)?;
+            changed = true;
+            tracing::info!(channel = %ch.channel_name, key = %key, "Custom context key set");
+            tf(locale, "context.set", &[("key", key), ("value", &value)])
+        }
+        Some("clear") => match args.get(1) {
+            Some(key) => {
+                if custom.remove(key).is_none() {
+                    let reply = tf(locale, "context.no_key", &[("key", key)]);
+                    return channel.send(MessageContent::plain(reply)).await;
+                }
+                session_store.set_custom_context(&ch.channel_name, &custom)?;
+                changed = true;
+                tf(locale, "context.removed", &[("key", key)])
+            }
+            None => {
+                let removed = custom.len();
+                custom.clear();
+                session_store.set_custom_context(&ch.channel_name, &custom)?;
+                changed = true;
+                tn(locale, "context.cleared", removed, &[])
+            }
+        },
+        Some(_) => {
+            let usage = MessageContent::plain(t(locale, "context.usage"));
+            return reply_privately(channel, sender, usage).await;
+        }
+    };
+
+    // Rewrite the file now so `!context show` and running tools see the change
+    if changed {
+        if let Err(e) = write_context_file(
+            &ch.directory,
+            &ch.room_id,
+            &ch.channel_name,
+            &ch.session_id,
+            &custom,
+        )
+        .await
+        {
+            tracing::warn!(error = %e, "Failed to rewrite MCP context file");
+        }
+    }
+    channel.send(MessageContent::plain(reply)).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +208,7 @@ mod tests {
             "!test:matrix.org",
             "test-channel",
             "session-123",
+            &BTreeMap::new(),
         )
         .await;
 
@@ -94,4 +225,40 @@ mod tests {
         assert_eq!(json["channel_name"], "test-channel");
         assert_eq!(json["session_id"], "session-123");
     }
+
+    #[tokio::test]
+    async fn test_custom_context_is_merged_without_overriding_reserved_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let channel_dir = temp_dir.path().to_str().unwrap();
+        let custom = BTreeMap::from([
+            ("project".to_string(), "apollo".to_string()),
+            ("room_id".to_string(), "!spoofed:matrix.org".to_string()),
+        ]);
+
+        write_context_file(
+            channel_dir,
+            "!test:matrix.org",
+            "test-channel",
+            "session-123",
+            &custom,
+        )
+        .await
+        .unwrap();
+
+        let content = std::fs::read_to_string(context_file_path(channel_dir)).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["project"], "apollo");
+        assert_eq!(json["room_id"], "!test:matrix.org");
+    }
+
+    #[test]
+    fn test_validate_context_key() {
+        assert!(validate_context_key("project").is_ok());
+        assert!(validate_context_key("ticket.prefix").is_ok());
+        assert!(validate_context_key("room_id").is_err());
+        assert!(validate_context_key("session_id").is_err());
+        assert!(validate_context_key("").is_err());
+        assert!(validate_context_key("has space").is_err());
+        assert!(validate_context_key(&"k".repeat(65)).is_err());
+    }
 }
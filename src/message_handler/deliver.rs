@@ -0,0 +1,87 @@
+// ABOUTME: !deliver: a channel's delivery window, outside which agent output is held until it opens.
+// ABOUTME: Sets or clears the window and flushes held messages on demand with !deliver now.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    delivery::DeliveryWindow,
+    i18n::{t, tf},
+    session::SessionStore,
+    utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
+};
+
+use super::commands::{channel_for_command, reply_privately};
+
+/// `!deliver [window <spec>|now|off]`: show or change the delivery window
+pub(crate) async fn handle_deliver_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    timezone: &str,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "deliver", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    match args.first().map(|s| s.to_lowercase()).as_deref() {
+        Some("window") => match DeliveryWindow::parse(&args[1..].join(" "), timezone) {
+            Ok(window) => {
+                session_store.set_delivery_window(&ch.channel_name, &window)?;
+                tracing::info!(channel = %ch.channel_name, window = %window, "Delivery window set");
+                let reply = tf(locale, "deliver.set", &[("window", &window.to_string())]);
+                channel.send(MessageContent::plain(reply)).await?;
+            }
+            Err(e) => {
+                let reply = tf(locale, "deliver.invalid", &[("error", &e.to_string())]);
+                reply_privately(channel, sender, MessageContent::plain(reply)).await?;
+            }
+        },
+        Some("off") | Some("clear") => {
+            session_store.clear_delivery_window(&ch.channel_name)?;
+            tracing::info!(channel = %ch.channel_name, "Delivery window cleared");
+            channel
+                .send(MessageContent::plain(t(locale, "deliver.cleared")))
+                .await?;
+        }
+        Some("now") => {
+            let held = session_store.list_held_messages(&ch.channel_name)?;
+            if held.is_empty() {
+                channel
+                    .send(MessageContent::plain(t(locale, "deliver.none_held")))
+                    .await?;
+                return Ok(());
+            }
+            for message in &held {
+                for chunk in chunk_message(&message.body, MAX_CHUNK_SIZE) {
+                    let html = markdown_to_html(&chunk);
+                    channel.send(MessageContent::html(&chunk, &html)).await?;
+                }
+                session_store.remove_held_message(message.id)?;
+            }
+            tracing::info!(
+                channel = %ch.channel_name,
+                delivered = held.len(),
+                "Held messages delivered via !deliver now"
+            );
+        }
+        _ => {
+            let window = match session_store.get_delivery_window(&ch.channel_name)? {
+                Some(window) => window.to_string(),
+                None => t(locale, "deliver.no_window"),
+            };
+            let held = session_store.list_held_messages(&ch.channel_name)?.len();
+            let reply = tf(
+                locale,
+                "deliver.status",
+                &[("window", &window), ("held", &held.to_string())],
+            );
+            channel.send(MessageContent::plain(reply)).await?;
+        }
+    }
+    Ok(())
+}
@@ -4,19 +4,24 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use gorp_core::traits::{
-    AttachmentHandler, ChatChannel, MessageContent, MessagingPlatform, TypingIndicator,
+    AttachmentHandler, ChannelManager, ChatChannel, EphemeralSender, MessageContent,
+    MessagingPlatform, TypingIndicator,
 };
 
 /// A platform-agnostic channel implementation that wraps a `MessagingPlatform`.
 ///
 /// Allows the command handler (which requires `ChatChannel`) to work with any
-/// platform through the `MessagingPlatform::send()` method. Features like typing
-/// indicators and attachments gracefully degrade to no-ops.
+/// platform through the `MessagingPlatform::send()` method, or into a thread via
+/// the platform's `ThreadedPlatform` when one is set. Attachments and
+/// ephemeral replies use the platform's handlers when it has them; typing
+/// indicators degrade to no-ops.
 #[derive(Clone)]
 pub struct GenericChannel<'a> {
     platform: &'a dyn MessagingPlatform,
     channel_id: String,
     is_dm: bool,
+    /// Thread that sends go to, if the platform threads
+    thread_id: Option<String>,
 }
 
 impl<'a> GenericChannel<'a> {
@@ -25,8 +30,15 @@ impl<'a> GenericChannel<'a> {
             platform,
             channel_id: channel_id.to_string(),
             is_dm,
+            thread_id: None,
         }
     }
+
+    /// Send into `thread_id` rather than the channel's top level
+    pub fn in_thread(mut self, thread_id: Option<&str>) -> Self {
+        self.thread_id = thread_id.map(str::to_string);
+        self
+    }
 }
 
 impl<'a> std::fmt::Debug for GenericChannel<'a> {
@@ -35,6 +47,7 @@ impl<'a> std::fmt::Debug for GenericChannel<'a> {
             .field("platform_id", &self.platform.platform_id())
             .field("channel_id", &self.channel_id)
             .field("is_dm", &self.is_dm)
+            .field("thread_id", &self.thread_id)
             .finish()
     }
 }
@@ -45,6 +58,10 @@ impl<'a> ChatChannel for GenericChannel<'a> {
         &self.channel_id
     }
 
+    fn platform_id(&self) -> &str {
+        self.platform.platform_id()
+    }
+
     fn name(&self) -> Option<String> {
         None
     }
@@ -54,7 +71,14 @@ impl<'a> ChatChannel for GenericChannel<'a> {
     }
 
     async fn send(&self, content: MessageContent) -> Result<()> {
-        self.platform.send(&self.channel_id, content).await
+        match (&self.thread_id, self.platform.threading()) {
+            (Some(thread_id), Some(threading)) => {
+                threading
+                    .send_threaded(&self.channel_id, thread_id, content)
+                    .await
+            }
+            _ => self.platform.send(&self.channel_id, content).await,
+        }
     }
 
     fn typing_indicator(&self) -> Option<&dyn TypingIndicator> {
@@ -62,7 +86,15 @@ impl<'a> ChatChannel for GenericChannel<'a> {
     }
 
     fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
-        None
+        self.platform.attachment_handler()
+    }
+
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        self.platform.channel_manager()
+    }
+
+    fn ephemeral(&self) -> Option<&dyn EphemeralSender> {
+        self.platform.ephemeral()
     }
 
     async fn member_count(&self) -> Result<usize> {
@@ -152,6 +184,16 @@ mod tests {
         assert!(matches!(&msgs[0].1, MessageContent::Plain(s) if s == "hello"));
     }
 
+    #[tokio::test]
+    async fn test_generic_channel_without_threading_sends_top_level() {
+        let platform = TestPlatform::new();
+        let channel = GenericChannel::new(&platform, "chan-123", false).in_thread(Some("t1"));
+
+        channel.send(MessageContent::plain("hello")).await.unwrap();
+
+        assert_eq!(platform.sent_messages().len(), 1);
+    }
+
     #[test]
     fn test_generic_channel_typing_indicator_none() {
         let platform = TestPlatform::new();
@@ -166,6 +208,13 @@ mod tests {
         assert!(channel.attachment_handler().is_none());
     }
 
+    #[test]
+    fn test_generic_channel_capabilities_follow_platform() {
+        let platform = TestPlatform::new();
+        let channel = GenericChannel::new(&platform, "chan-123", false);
+        assert!(channel.ephemeral().is_none());
+    }
+
     #[tokio::test]
     async fn test_generic_channel_member_count_zero() {
         let platform = TestPlatform::new();
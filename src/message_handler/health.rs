@@ -0,0 +1,67 @@
+// ABOUTME: !health and !safemode: a quick look at the bot's state and the way out of safe mode.
+// ABOUTME: Both work in any room; leaving safe mode asks for confirmation first.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    runtime_mode::{self, RuntimeMode},
+    session::SessionStore,
+    warm_session::SharedWarmSessionManager,
+};
+
+/// `!health`: runtime mode, default backend, warm sessions and channel count
+pub(crate) async fn handle_health_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    runtime: &RuntimeMode,
+    locale: &str,
+) -> Result<()> {
+    let (backend, warm_sessions) = {
+        let manager = warm_manager.read().await;
+        (manager.backend_type().to_string(), manager.session_count())
+    };
+    let mode = if runtime.is_safe() {
+        t(locale, "health.mode_safe")
+    } else {
+        t(locale, "health.mode_normal")
+    };
+    let health = tf(
+        locale,
+        "health.report",
+        &[
+            ("mode", &mode),
+            ("backend", &backend),
+            ("warm_sessions", &warm_sessions.to_string()),
+            ("channels", &session_store.list_all()?.len().to_string()),
+        ],
+    );
+    channel.send(MessageContent::plain(health)).await
+}
+
+/// `!safemode [off [confirm]]`: show safe mode, or leave it once confirmed
+pub(crate) async fn handle_safemode_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    runtime: &RuntimeMode,
+    sender: &str,
+    locale: &str,
+) -> Result<()> {
+    let args: Vec<String> = args.iter().map(|a| a.to_lowercase()).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let reply = match (runtime.is_safe(), args.as_slice()) {
+        (false, []) => t(locale, "safemode.off"),
+        (false, ["off", ..]) => t(locale, "safemode.not_active"),
+        (true, []) => runtime_mode::BANNER.to_string(),
+        (true, ["off"]) => t(locale, "safemode.confirm"),
+        (true, ["off", "confirm"]) => {
+            runtime.leave_safe_mode();
+            tracing::warn!(sender, "Safe mode turned off from chat");
+            t(locale, "safemode.left")
+        }
+        _ => t(locale, "safemode.usage"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
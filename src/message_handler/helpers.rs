@@ -1,8 +1,10 @@
 // ABOUTME: Pure helper functions for message handling
-// ABOUTME: Channel validation, string truncation, cron detection - all testable without Matrix
+// ABOUTME: Channel flags, tool notices, validation, truncation, cron detection - all testable without Matrix
 
 use std::path::Path;
 
+use crate::{metrics, utils::truncate_response};
+
 /// Check if debug mode is enabled for a channel directory
 /// Debug mode is enabled by creating an empty file: .gorp/enable-debug
 pub fn is_debug_enabled(channel_dir: &str) -> bool {
@@ -10,6 +12,44 @@ pub fn is_debug_enabled(channel_dir: &str) -> bool {
     debug_path.exists()
 }
 
+/// Debug-mode notice for a tool the agent started, as (plain, html).
+/// Shows the command, file path or search pattern when the input has one.
+pub fn format_tool_notice(name: &str, input: &serde_json::Value) -> (String, String) {
+    let input_preview: String = input
+        .as_object()
+        .and_then(|o| o.get("command").or(o.get("file_path")).or(o.get("pattern")))
+        .and_then(|v| v.as_str())
+        .map(|s| s.chars().take(50).collect())
+        .unwrap_or_default();
+
+    if input_preview.is_empty() {
+        (format!("🔧 {}", name), format!("🔧 <code>{}</code>", name))
+    } else {
+        (
+            format!("🔧 {} · {}", name, input_preview),
+            format!("🔧 <code>{}</code> · <code>{}</code>", name, input_preview),
+        )
+    }
+}
+
+/// Check if streaming responses are enabled for a channel directory
+/// Streaming is enabled by creating an empty file: .gorp/stream-responses
+pub fn is_streaming_enabled(channel_dir: &str) -> bool {
+    Path::new(channel_dir)
+        .join(".gorp")
+        .join("stream-responses")
+        .exists()
+}
+
+/// Check if status reactions are enabled for a channel directory
+/// Status reactions are enabled by creating an empty file: .gorp/status-reactions
+pub fn is_status_reactions_enabled(channel_dir: &str) -> bool {
+    Path::new(channel_dir)
+        .join(".gorp")
+        .join("status-reactions")
+        .exists()
+}
+
 /// Validate a channel name
 /// Returns Ok(()) if valid, Err with message if invalid
 /// Rules: alphanumeric, dashes, underscores only, max 50 chars, non-empty
@@ -54,9 +94,56 @@ pub fn looks_like_cron(s: &str) -> bool {
         })
 }
 
+/// Enforce the configured response cap (`max_chars` of 0 means unlimited).
+/// Over-long responses are cut with a notice; with `save_full` the complete text is
+/// written to .gorp/responses/ in the channel workspace and the notice points to it.
+pub fn cap_response(
+    response: String,
+    channel_dir: &str,
+    max_chars: usize,
+    save_full: bool,
+) -> String {
+    let Some((kept, omitted)) = truncate_response(&response, max_chars) else {
+        return response;
+    };
+    metrics::record_response_truncated();
+
+    let mut notice = format!("…response truncated ({} chars omitted)", omitted);
+    if save_full {
+        let relative = format!(
+            ".gorp/responses/{}.md",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S-%3f")
+        );
+        let path = Path::new(channel_dir).join(&relative);
+        let saved = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, &response));
+        match saved {
+            Ok(()) => notice.push_str(&format!("\nFull response: {}", relative)),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to save full response")
+            }
+        }
+    }
+
+    format!("{}\n\n{}", kept, notice)
+}
+
+/// `response` with the configured `signing.footer` on its own line at the end.
+/// Empty responses and an empty footer leave it as it is.
+pub fn with_footer(response: &str, footer: &str) -> String {
+    let footer = footer.trim();
+    if response.is_empty() || footer.is_empty() {
+        return response.to_string();
+    }
+    format!("{}\n\n{}", response, footer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn test_validate_channel_name_valid() {
@@ -83,4 +170,54 @@ mod tests {
         assert!(looks_like_cron("0 9 * * *"));
         assert!(!looks_like_cron("in 5 minutes"));
     }
+
+    #[test]
+    fn test_format_tool_notice() {
+        let (plain, html) = format_tool_notice("Bash", &serde_json::json!({"command": "ls -la"}));
+        assert_eq!(plain, "🔧 Bash · ls -la");
+        assert_eq!(html, "🔧 <code>Bash</code> · <code>ls -la</code>");
+
+        let (plain, _) = format_tool_notice("TodoWrite", &serde_json::json!({"todos": []}));
+        assert_eq!(plain, "🔧 TodoWrite");
+    }
+
+    #[test]
+    fn test_with_footer() {
+        assert_eq!(with_footer("Done.", "— 🤖 gorp"), "Done.\n\n— 🤖 gorp");
+        assert_eq!(with_footer("Done.", "  "), "Done.");
+        assert_eq!(with_footer("", "— 🤖 gorp"), "");
+    }
+
+    #[test]
+    fn test_cap_response_unlimited_when_zero() {
+        let long = "word ".repeat(500);
+        assert_eq!(cap_response(long.clone(), "/nonexistent", 0, true), long);
+    }
+
+    #[test]
+    fn test_cap_response_truncates_and_saves_full_text() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        let long = format!("{}\n\n{}", "a".repeat(80), "b".repeat(80));
+
+        let capped = cap_response(long.clone(), dir, 100, true);
+        assert!(capped.starts_with(&"a".repeat(80)));
+        assert!(capped.contains("…response truncated (82 chars omitted)"));
+
+        let relative = capped.rsplit("Full response: ").next().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join(relative)).unwrap(),
+            long
+        );
+    }
+
+    #[test]
+    fn test_cap_response_without_saving() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        let capped = cap_response("x ".repeat(100), dir, 50, false);
+        assert!(capped.contains("…response truncated"));
+        assert!(!capped.contains("Full response"));
+        assert!(!tmp.path().join(".gorp").exists());
+    }
 }
@@ -0,0 +1,424 @@
+// ABOUTME: Recent user/assistant turns read back from a channel's .gorp/matrix-messages.jsonl.
+// ABOUTME: Backs !history, !summarize and scheduled exports: reassembles chunked responses and renders exchanges as a markdown transcript.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf, tn},
+    session::SessionStore,
+    utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
+};
+
+use super::commands::reply_privately;
+
+/// Schedule prompt that saves the channel transcript instead of running the agent
+pub const EXPORT_SCHEDULE_PROMPT: &str = "export:transcript";
+
+/// Exchanges shown by `!history` without an argument
+pub const DEFAULT_HISTORY_EXCHANGES: usize = 5;
+/// Most exchanges `!history n` will show
+pub const MAX_HISTORY_EXCHANGES: usize = 100;
+
+/// Who a logged turn came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// One prompt or (reassembled) response from the message log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    pub role: Role,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub content: String,
+}
+
+/// A prompt and the responses that followed it. The first exchange in a log
+/// may start with a response if its prompt was logged before the log began.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    pub turns: Vec<Turn>,
+}
+
+/// Every turn in the channel's message log, oldest first.
+/// Tool notifications are skipped; responses sent in chunks count as one turn.
+fn read_turns(channel_dir: &str) -> Result<Vec<Turn>> {
+    let path = Path::new(channel_dir)
+        .join(".gorp")
+        .join("matrix-messages.jsonl");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut turns: Vec<Turn> = Vec::new();
+    for line in text.lines() {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let role = match entry["message_type"].as_str() {
+            Some("prompt") => Role::User,
+            Some("response") => Role::Assistant,
+            _ => continue,
+        };
+        let content = entry["content"].as_str().unwrap_or_default().to_string();
+
+        // Later chunks extend the response started by chunk 0
+        let continues =
+            role == Role::Assistant && entry["chunk_index"].as_u64().is_some_and(|index| index > 0);
+        if continues {
+            if let Some(last) = turns.last_mut().filter(|t| t.role == Role::Assistant) {
+                last.content.push_str("\n\n");
+                last.content.push_str(&content);
+                continue;
+            }
+        }
+
+        let timestamp = entry["timestamp"]
+            .as_str()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        turns.push(Turn {
+            role,
+            timestamp,
+            content,
+        });
+    }
+    Ok(turns)
+}
+
+/// The last `n` turns in the channel's message log, oldest first
+pub fn recent_turns(channel_dir: &str, n: usize) -> Result<Vec<Turn>> {
+    let mut turns = read_turns(channel_dir)?;
+    let skip = turns.len().saturating_sub(n);
+    Ok(turns.split_off(skip))
+}
+
+/// The last `n` exchanges in the channel's message log, oldest first
+pub fn recent_exchanges(channel_dir: &str, n: usize) -> Result<Vec<Exchange>> {
+    let mut exchanges: Vec<Exchange> = Vec::new();
+    for turn in read_turns(channel_dir)? {
+        match exchanges.last_mut() {
+            Some(exchange) if turn.role == Role::Assistant => exchange.turns.push(turn),
+            _ => exchanges.push(Exchange { turns: vec![turn] }),
+        }
+    }
+    let skip = exchanges.len().saturating_sub(n);
+    Ok(exchanges.split_off(skip))
+}
+
+/// Render exchanges as a markdown transcript, full text included
+pub fn format_transcript(channel_name: &str, exchanges: &[Exchange]) -> String {
+    let mut out = format!(
+        "# 📜 {}: last {} exchange{}\n",
+        channel_name,
+        exchanges.len(),
+        if exchanges.len() == 1 { "" } else { "s" }
+    );
+    for exchange in exchanges {
+        out.push_str("\n---\n");
+        out.push_str(&format_exchange(exchange));
+    }
+    out
+}
+
+/// Render one exchange's turns as markdown, each with its speaker and time
+pub fn format_exchange(exchange: &Exchange) -> String {
+    let mut out = String::new();
+    for turn in &exchange.turns {
+        let who = match turn.role {
+            Role::User => "👤 You",
+            Role::Assistant => "🤖 Agent",
+        };
+        let when = turn
+            .timestamp
+            .map(|t| format!(" · {}", t.format("%Y-%m-%d %H:%M")))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "\n**{}**{}\n\n{}\n",
+            who,
+            when,
+            turn.content.trim()
+        ));
+    }
+    out
+}
+
+/// File name for a transcript written on `date`, e.g. transcript-2026-03-01.md
+pub fn transcript_filename(date: DateTime<Utc>) -> String {
+    format!("transcript-{}.md", date.format("%Y-%m-%d"))
+}
+
+/// Write a transcript into the channel workspace, where the agent can read it.
+/// A second export on the same day replaces the first.
+pub fn export_transcript(
+    channel_dir: &str,
+    channel_name: &str,
+    exchanges: &[Exchange],
+    date: DateTime<Utc>,
+) -> Result<PathBuf> {
+    let path = Path::new(channel_dir).join(transcript_filename(date));
+    std::fs::write(&path, format_transcript(channel_name, exchanges))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Whether a schedule prompt is a transcript export rather than a prompt for the agent
+pub fn is_export_schedule(prompt: &str) -> bool {
+    prompt.trim().eq_ignore_ascii_case(EXPORT_SCHEDULE_PROMPT)
+}
+
+/// Save the channel's whole transcript for a scheduled export: under
+/// `export_dir/<channel>/` when one is configured, else in the workspace like
+/// `!history export`. None when the channel has no history yet.
+pub fn export_scheduled(
+    channel_dir: &str,
+    channel_name: &str,
+    export_dir: Option<&str>,
+    date: DateTime<Utc>,
+) -> Result<Option<PathBuf>> {
+    let exchanges = recent_exchanges(channel_dir, usize::MAX)?;
+    if exchanges.is_empty() {
+        return Ok(None);
+    }
+    let target = match export_dir {
+        Some(dir) => Path::new(dir).join(channel_name),
+        None => PathBuf::from(channel_dir),
+    };
+    std::fs::create_dir_all(&target)
+        .with_context(|| format!("Failed to create {}", target.display()))?;
+    let target = target.to_string_lossy();
+    export_transcript(&target, channel_name, &exchanges, date).map(Some)
+}
+
+/// `!history [export] [n]`: post the last n exchanges, or save them to the workspace
+pub(crate) async fn handle_history_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    sender: &str,
+    locale: &str,
+) -> Result<()> {
+    let export = args
+        .first()
+        .is_some_and(|arg| arg.eq_ignore_ascii_case("export"));
+    let exchanges = match args.get(usize::from(export)) {
+        // An export without a count covers the whole log
+        None if export => usize::MAX,
+        None => DEFAULT_HISTORY_EXCHANGES,
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(n) if (1..=MAX_HISTORY_EXCHANGES).contains(&n) => n,
+            _ => {
+                let usage = tf(
+                    locale,
+                    "history.usage",
+                    &[
+                        ("max", &MAX_HISTORY_EXCHANGES.to_string()),
+                        ("default", &DEFAULT_HISTORY_EXCHANGES.to_string()),
+                    ],
+                );
+                return reply_privately(channel, sender, MessageContent::plain(usage)).await;
+            }
+        },
+    };
+
+    // DMs have no channel log unless a channel is attached
+    let Some(ch) = session_store.get_by_room(channel.id())? else {
+        return channel
+            .send(MessageContent::plain(t(locale, "history.no_room")))
+            .await;
+    };
+
+    let recent = recent_exchanges(&ch.directory, exchanges)?;
+    if recent.is_empty() {
+        let reply = tf(locale, "history.empty", &[("channel", &ch.channel_name)]);
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+
+    let now = Utc::now();
+    if export {
+        export_transcript(&ch.directory, &ch.channel_name, &recent, now)?;
+        let reply = tn(
+            locale,
+            "history.exported",
+            recent.len(),
+            &[("file", &transcript_filename(now))],
+        );
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+
+    let transcript = format_transcript(&ch.channel_name, &recent);
+    if chunk_message(&transcript, MAX_CHUNK_SIZE).len() <= 1 {
+        let html = markdown_to_html(&transcript);
+        channel.send(MessageContent::html(&transcript, &html)).await
+    } else {
+        // Too long for one message, so send it whole as a file
+        let caption = tf(
+            locale,
+            "history.attached",
+            &[
+                ("count", &recent.len().to_string()),
+                ("channel", &ch.channel_name),
+            ],
+        );
+        channel
+            .send(MessageContent::Attachment {
+                filename: transcript_filename(now),
+                data: transcript.into_bytes(),
+                mime_type: "text/markdown".to_string(),
+                caption: Some(caption),
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn write_log(lines: &[&str]) -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        let gorp = tmp.path().join(".gorp");
+        std::fs::create_dir_all(&gorp).unwrap();
+        std::fs::write(gorp.join("matrix-messages.jsonl"), lines.join("\n")).unwrap();
+        tmp
+    }
+
+    #[test]
+    fn test_recent_turns_reassembles_chunks_and_skips_tools() {
+        let tmp = write_log(&[
+            r#"{"timestamp":"2026-03-01T09:00:00Z","message_type":"prompt","content":"first question"}"#,
+            r#"{"timestamp":"2026-03-01T09:00:05Z","message_type":"tool_notification","content":"🔧 Read"}"#,
+            r#"{"timestamp":"2026-03-01T09:00:09Z","message_type":"response","content":"part one","chunk_index":0,"total_chunks":2}"#,
+            r#"{"timestamp":"2026-03-01T09:00:09Z","message_type":"response","content":"part two","chunk_index":1,"total_chunks":2}"#,
+            "not json",
+            r#"{"timestamp":"2026-03-01T09:01:00Z","message_type":"prompt","content":"second question"}"#,
+        ]);
+
+        let turns = recent_turns(tmp.path().to_str().unwrap(), 5).unwrap();
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[0].role, Role::User);
+        assert_eq!(
+            turns[0].timestamp,
+            Some(Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap())
+        );
+        assert_eq!(turns[1].role, Role::Assistant);
+        assert_eq!(turns[1].content, "part one\n\npart two");
+        assert_eq!(turns[2].content, "second question");
+
+        let last = recent_turns(tmp.path().to_str().unwrap(), 1).unwrap();
+        assert_eq!(last, vec![turns[2].clone()]);
+    }
+
+    #[test]
+    fn test_recent_turns_empty_without_log() {
+        let tmp = TempDir::new().unwrap();
+        assert!(recent_turns(tmp.path().to_str().unwrap(), 5)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_recent_exchanges_pair_prompts_with_responses() {
+        let tmp = write_log(&[
+            r#"{"timestamp":"2026-03-01T08:59:00Z","message_type":"response","content":"left over"}"#,
+            r#"{"timestamp":"2026-03-01T09:00:00Z","message_type":"prompt","content":"first question"}"#,
+            r#"{"timestamp":"2026-03-01T09:00:09Z","message_type":"response","content":"part one","chunk_index":0,"total_chunks":2}"#,
+            r#"{"timestamp":"2026-03-01T09:00:09Z","message_type":"response","content":"part two","chunk_index":1,"total_chunks":2}"#,
+            r#"{"timestamp":"2026-03-01T09:01:00Z","message_type":"prompt","content":"second question"}"#,
+            r#"{"timestamp":"2026-03-01T09:02:00Z","message_type":"prompt","content":"third question"}"#,
+            r#"{"timestamp":"2026-03-01T09:02:30Z","message_type":"response","content":"third answer"}"#,
+        ]);
+        let dir = tmp.path().to_str().unwrap();
+
+        let all = recent_exchanges(dir, 10).unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].turns.len(), 1);
+        assert_eq!(all[0].turns[0].role, Role::Assistant);
+        assert_eq!(all[1].turns.len(), 2);
+        assert_eq!(all[1].turns[1].content, "part one\n\npart two");
+        assert_eq!(all[2].turns.len(), 1);
+
+        let last = recent_exchanges(dir, 2).unwrap();
+        assert_eq!(last, all[2..].to_vec());
+    }
+
+    #[test]
+    fn test_format_transcript_keeps_full_text() {
+        let long_answer = format!("{}\n\n```sh\nmake ship\n```", "é".repeat(500));
+        let exchanges = vec![Exchange {
+            turns: vec![
+                Turn {
+                    role: Role::User,
+                    timestamp: Some(Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap()),
+                    content: "how do I deploy?".to_string(),
+                },
+                Turn {
+                    role: Role::Assistant,
+                    timestamp: None,
+                    content: long_answer.clone(),
+                },
+            ],
+        }];
+
+        let md = format_transcript("research", &exchanges);
+        assert!(md.starts_with("# 📜 research: last 1 exchange\n"));
+        assert!(md.contains("\n**👤 You** · 2026-03-01 09:00\n\nhow do I deploy?\n"));
+        assert!(md.contains(&format!("\n**🤖 Agent**\n\n{}\n", long_answer)));
+    }
+
+    #[test]
+    fn test_export_transcript_writes_dated_file() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        let date = Utc.with_ymd_and_hms(2026, 3, 1, 18, 30, 0).unwrap();
+        let exchanges = vec![Exchange {
+            turns: vec![Turn {
+                role: Role::User,
+                timestamp: None,
+                content: "hello".to_string(),
+            }],
+        }];
+
+        let path = export_transcript(dir, "research", &exchanges, date).unwrap();
+        assert_eq!(path, tmp.path().join("transcript-2026-03-01.md"));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            format_transcript("research", &exchanges)
+        );
+    }
+
+    #[test]
+    fn test_scheduled_export_goes_to_the_export_dir_per_channel() {
+        assert!(is_export_schedule(" Export:Transcript "));
+        assert!(!is_export_schedule("export the transcript"));
+
+        let tmp = write_log(&[
+            r#"{"timestamp":"2026-03-01T09:00:00Z","message_type":"prompt","content":"status?"}"#,
+        ]);
+        let dir = tmp.path().to_str().unwrap();
+        let exports = TempDir::new().unwrap();
+        let date = Utc.with_ymd_and_hms(2026, 3, 1, 23, 0, 0).unwrap();
+
+        let path = export_scheduled(dir, "ops", exports.path().to_str(), date)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            path,
+            exports.path().join("ops").join("transcript-2026-03-01.md")
+        );
+        assert!(!tmp.path().join("transcript-2026-03-01.md").exists());
+
+        let empty = TempDir::new().unwrap();
+        let none = export_scheduled(empty.path().to_str().unwrap(), "ops", None, date).unwrap();
+        assert_eq!(none, None);
+    }
+}
@@ -0,0 +1,107 @@
+// ABOUTME: !invite: bring someone into a channel's room and record them as a member.
+// ABOUTME: Invitees must be on the allowlist unless [access] allows inviting outside it.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    config::Config,
+    i18n::{t, tf},
+    session::SessionStore,
+};
+
+use super::commands::{channel_for_command, reply_privately};
+
+/// `!invite <user>`: invite a user into this channel's room
+pub(crate) async fn handle_invite_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    config: &Config,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "invite", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let Some(arg) = args.first() else {
+        let usage = MessageContent::plain(t(locale, "invite.usage"));
+        return reply_privately(channel, sender, usage).await;
+    };
+
+    let platform_id = channel.platform_id();
+    let invitee = match invitee_id(platform_id, arg, locale) {
+        Ok(id) => id,
+        Err(reason) => {
+            let reply = tf(locale, "command.failed", &[("error", &reason)]);
+            return channel.send(MessageContent::plain(reply)).await;
+        }
+    };
+
+    if !config.access.allow_invite_outside_allowlist
+        && !config.is_user_allowed(platform_id, &invitee)
+    {
+        let reply = tf(locale, "invite.not_allowed", &[("user", &invitee)]);
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+
+    let Some(manager) = channel.channel_manager() else {
+        return channel
+            .send(MessageContent::plain(t(locale, "invite.unsupported")))
+            .await;
+    };
+
+    let reply = match manager.invite(channel.id(), &invitee).await {
+        Ok(()) => {
+            session_store.add_channel_member(&ch.channel_name, &invitee, sender)?;
+            tracing::info!(channel = %ch.channel_name, invitee, sender, "Invited user to channel");
+            tf(
+                locale,
+                "invite.done",
+                &[("user", &invitee), ("channel", &ch.channel_name)],
+            )
+        }
+        Err(e) => tf(
+            locale,
+            "invite.failed",
+            &[("user", &invitee), ("error", &format!("{:#}", e))],
+        ),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// The platform user ID `!invite` was given, or why it isn't one. Matrix needs a
+/// full MXID; Slack mentions (`<@U0123|bob>`) are unwrapped to the user ID.
+fn invitee_id(platform_id: &str, arg: &str, locale: &str) -> std::result::Result<String, String> {
+    match platform_id {
+        "matrix" => arg
+            .parse::<matrix_sdk::ruma::OwnedUserId>()
+            .map(|id| id.to_string())
+            .map_err(|_| tf(locale, "invite.not_mxid", &[("user", arg)])),
+        "slack" => {
+            let id = arg
+                .strip_prefix("<@")
+                .and_then(|mention| mention.strip_suffix('>'))
+                .map_or(arg, |mention| mention.split('|').next().unwrap_or(mention));
+            Ok(id.to_string())
+        }
+        _ => Ok(arg.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invitee_id_unwraps_slack_mentions() {
+        assert_eq!(invitee_id("slack", "<@U0BOB|bob>", "en").unwrap(), "U0BOB");
+        assert_eq!(invitee_id("slack", "<@U0BOB>", "en").unwrap(), "U0BOB");
+        assert_eq!(invitee_id("slack", "U0BOB", "en").unwrap(), "U0BOB");
+        assert!(invitee_id("matrix", "@bob:matrix.org", "en").is_ok());
+        assert!(invitee_id("matrix", "@bob", "en").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+// ABOUTME: !locale: the language gorp replies in, per channel in a room and per user in a DM.
+// ABOUTME: Replies come back in the language just chosen, so the change is visible straight away.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{self, t, tf},
+    session::SessionStore,
+};
+
+/// `!locale [<code>|reset]`: show or change the reply language
+pub(crate) async fn handle_locale_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    // In a channel the language applies to everyone there; in a DM it's the sender's own
+    let ch = if is_dm {
+        None
+    } else {
+        let Some(ch) = session_store.get_by_room(channel.id())? else {
+            channel
+                .send(MessageContent::plain(t(locale, "command.no_channel")))
+                .await?;
+            return Ok(());
+        };
+        Some(ch)
+    };
+    let store_locale = |locale: Option<&str>| match &ch {
+        Some(ch) => session_store.set_channel_locale(&ch.channel_name, locale),
+        None => session_store.set_user_locale(sender, locale),
+    };
+    let resolve_locale = || match &ch {
+        Some(ch) => i18n::channel_locale(session_store, &ch.channel_name, sender),
+        None => i18n::user_locale(session_store, sender),
+    };
+
+    let catalog = i18n::catalog();
+    let available = catalog.languages().join(", ");
+    let current = resolve_locale();
+    let reply = match args.first().map(|s| s.to_lowercase()) {
+        None => tf(
+            &current,
+            "locale.status",
+            &[("locale", &current), ("available", &available)],
+        ),
+        Some(arg) if arg == "reset" => {
+            store_locale(None)?;
+            let locale = resolve_locale();
+            tf(&locale, "locale.reset", &[("locale", &locale)])
+        }
+        Some(arg) if !catalog.has_language(&arg) => tf(
+            &current,
+            "locale.unknown",
+            &[("locale", &arg), ("available", &available)],
+        ),
+        Some(arg) => {
+            store_locale(Some(&arg))?;
+            tracing::info!(locale = %arg, dm = is_dm, sender, "Locale set");
+            tf(&arg, "locale.set", &[("locale", &arg)])
+        }
+    };
+    channel.send(MessageContent::plain(&reply)).await
+}
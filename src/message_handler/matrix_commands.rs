@@ -2,23 +2,46 @@
 // ABOUTME: Handles setup, create, join, delete, schedule, cleanup, etc. that need room/client access.
 
 use anyhow::Result;
-use matrix_sdk::{room::Room, ruma::events::room::message::RoomMessageEventContent, Client};
+use matrix_sdk::{
+    room::Room,
+    ruma::{
+        events::{
+            room::message::RoomMessageEventContent, AnySyncMessageLikeEvent, AnySyncTimelineEvent,
+        },
+        EventId,
+    },
+    Client,
+};
 
 use crate::{
+    bus::MessageBus,
     config::Config,
+    git_seed,
+    i18n::{self, t, tf},
     matrix_client, metrics, onboarding,
+    platform::SharedPlatformRegistry,
     scheduler::{
-        parse_time_expression, ParsedSchedule, ScheduleStatus, ScheduledPrompt, SchedulerStore,
+        parse_time_expression, ParsedSchedule, QuietPolicy, ScheduleStatus, ScheduledPrompt,
+        SchedulerStore,
     },
     session::SessionStore,
+    templates,
     warm_session::SharedWarmSessionManager,
 };
 
+use super::archive;
+use super::clone;
 use super::helpers::{looks_like_cron, truncate_str};
-use super::schedule_import::parse_schedule_input;
+use super::pins::{self, Pin};
+use super::schedule_import::{
+    expand_time_template, parse_schedule_edit, parse_schedule_input, time_template_for_cron,
+};
 
 use chrono::Utc;
 
+/// Runs shown by `!schedule log`
+const SCHEDULE_LOG_RUNS: usize = 10;
+
 /// Handle Matrix-dependent commands that were delegated from the testable command handler.
 ///
 /// These commands require access to the Matrix client for room operations,
@@ -35,6 +58,8 @@ pub async fn handle_matrix_command(
     is_dm: bool,
     config: &Config,
     warm_manager: &SharedWarmSessionManager,
+    platforms: &SharedPlatformRegistry,
+    bus: &MessageBus,
 ) -> Result<()> {
     match command {
         "setup" => {
@@ -51,13 +76,12 @@ pub async fn handle_matrix_command(
             onboarding::reset_and_start(room, session_store, sender).await?;
         }
         "create" => {
+            let locale = i18n::user_locale(session_store, sender);
             if command_parts.len() < 2 {
-                room.send(RoomMessageEventContent::text_plain(
-                    "Usage: !create <channel-name>\n\n\
-                    Example: !create PA\n\
-                    Example: !create dev-help\n\n\
-                    This will create a workspace directory and Matrix room for the channel.",
-                ))
+                room.send(RoomMessageEventContent::text_plain(t(
+                    &locale,
+                    "create.usage",
+                )))
                 .await?;
                 return Ok(());
             }
@@ -70,26 +94,34 @@ pub async fn handle_matrix_command(
                 .chars()
                 .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
             {
-                room.send(RoomMessageEventContent::text_plain(
-                    "❌ Channel name can only contain letters, numbers, dashes, and underscores.\n\n\
-                    Example: PA, dev-help, my_channel",
-                ))
+                room.send(RoomMessageEventContent::text_plain(t(
+                    &locale,
+                    "create.invalid_name",
+                )))
                 .await?;
                 return Ok(());
             }
 
             // Check if channel already exists (case-insensitive)
-            if session_store.get_by_name(&channel_name)?.is_some() {
-                room.send(RoomMessageEventContent::text_plain(format!(
-                    "❌ Channel '{}' already exists.\n\nUse !list to see all channels.",
-                    channel_name
+            if session_store
+                .get_by_name_on("matrix", &channel_name)?
+                .is_some()
+            {
+                room.send(RoomMessageEventContent::text_plain(tf(
+                    &locale,
+                    "create.exists",
+                    &[("channel", &channel_name)],
                 )))
                 .await?;
                 return Ok(());
             }
 
             // Create Matrix room
-            let room_prefix = config.matrix.as_ref().map(|m| m.room_prefix.as_str()).unwrap_or("Claude");
+            let room_prefix = config
+                .matrix
+                .as_ref()
+                .map(|m| m.room_prefix.as_str())
+                .unwrap_or("Claude");
             let room_name = format!("{}: {}", room_prefix, channel_name);
             let new_room_id = matrix_client::create_room(client, &room_name).await?;
             metrics::record_room_created();
@@ -98,24 +130,29 @@ pub async fn handle_matrix_command(
             matrix_client::invite_user(client, &new_room_id, sender).await?;
 
             // Create channel in database (this also creates the directory)
-            let channel = session_store.create_channel(&channel_name, new_room_id.as_str())?;
+            let channel =
+                session_store.create_channel_on("matrix", &channel_name, new_room_id.as_str())?;
             metrics::increment_active_channels();
+            matrix_client::apply_channel_topic(client, session_store, &new_room_id, &channel).await;
 
-            let response = format!(
-                "✅ Created Channel: {}\n\n\
-                Room: {}\n\
-                Session ID: {}\n\
-                Directory: {}\n\n\
-                Check your room list - I've invited you!\n\
-                Once you join, just send messages to start working.\n\n\
-                Webhook: POST http://{}:{}/webhook/session/{}",
-                channel_name,
-                room_name,
-                &channel.session_id[..8],
-                channel.directory,
-                config.webhook.host,
-                config.webhook.port,
-                channel.session_id
+            // A workspace template can name a git repo; check it out before the first prompt
+            if let Some(repo) = git_seed::read_git_repo(&channel.directory) {
+                let (name, dir) = (channel_name.clone(), channel.directory.clone());
+                tokio::spawn(async move { git_seed::sync_logged(&name, &dir, &repo).await });
+            }
+
+            let response = tf(
+                &locale,
+                "create.created",
+                &[
+                    ("channel", &channel_name),
+                    ("room", &room_name),
+                    ("session_short", &channel.session_id[..8]),
+                    ("directory", &channel.directory),
+                    ("host", &config.webhook.host),
+                    ("port", &config.webhook.port.to_string()),
+                    ("session_id", &channel.session_id),
+                ],
             );
             room.send(RoomMessageEventContent::text_plain(&response))
                 .await?;
@@ -151,7 +188,7 @@ pub async fn handle_matrix_command(
             let channel_name = command_parts[1].to_lowercase();
 
             // Find the channel
-            let Some(channel) = session_store.get_by_name(&channel_name)? else {
+            let Some(channel) = session_store.get_by_name_on("matrix", &channel_name)? else {
                 room.send(RoomMessageEventContent::text_plain(format!(
                     "❌ Channel '{}' not found.\n\nUse !list to see all channels.",
                     channel_name
@@ -221,7 +258,7 @@ pub async fn handle_matrix_command(
             let channel_name = command_parts[1].to_lowercase();
 
             // Find the channel
-            let Some(channel) = session_store.get_by_name(&channel_name)? else {
+            let Some(channel) = session_store.get_by_name_on("matrix", &channel_name)? else {
                 room.send(RoomMessageEventContent::text_plain(format!(
                     "❌ Channel '{}' not found.\n\nUse !list to see all channels.",
                     channel_name
@@ -230,27 +267,55 @@ pub async fn handle_matrix_command(
                 return Ok(());
             };
 
-            // Leave the room
+            // Recap the conversation while the agent can still see it
+            let summary_line = match archive::summarize_before_archive(
+                &config.maintenance,
+                &channel,
+                session_store,
+                warm_manager,
+            )
+            .await
+            {
+                Ok(Some(path)) => format!("\n- Summary saved: {}", path.display()),
+                Ok(None) => String::new(),
+                Err(e) => {
+                    tracing::warn!(error = %e, channel = %channel_name, "Archive summary failed");
+                    format!("\n- ⚠️ Summary failed: {}", e)
+                }
+            };
+
+            // Take the room out of the channel space, then leave it
             let room_id = channel.room_id.clone();
-            if let Some(target_room) = client.get_room(
-                <&matrix_sdk::ruma::RoomId>::try_from(room_id.as_str())
-                    .map_err(|e| anyhow::anyhow!("Invalid room ID: {}", e))?,
-            ) {
+            let target_id = <&matrix_sdk::ruma::RoomId>::try_from(room_id.as_str())
+                .map_err(|e| anyhow::anyhow!("Invalid room ID: {}", e))?;
+            if let Some(space) = matrix_client::space::channel_space() {
+                if let Err(e) = matrix_client::space::remove_child(client, space, target_id).await {
+                    tracing::warn!(
+                        error = %e,
+                        room_id = %room_id,
+                        "Failed to remove room from space"
+                    );
+                }
+            }
+            if let Some(target_room) = client.get_room(target_id) {
                 if let Err(e) = target_room.leave().await {
                     tracing::warn!(error = %e, room_id = %room_id, "Failed to leave room");
                 }
             }
 
+            // Drop the warm session too, so a recreated channel starts fresh
+            warm_manager.write().await.evict(&channel.channel_name);
+
             // Remove from database (keeps directory)
-            session_store.delete_channel(&channel_name)?;
+            session_store.delete_channel(&channel.channel_name)?;
             metrics::decrement_active_channels();
 
             let response = format!(
                 "✅ Deleted channel: {}\n\n\
                 - Bot left the room\n\
                 - Removed from database\n\
-                - Workspace preserved: {}",
-                channel_name, channel.directory
+                - Workspace preserved: {}{}",
+                channel_name, channel.directory, summary_line
             );
             room.send(RoomMessageEventContent::text_plain(&response))
                 .await?;
@@ -276,7 +341,7 @@ pub async fn handle_matrix_command(
             let channel_name = command_parts[1].to_lowercase();
 
             // Look up channel by name
-            let Some(channel) = session_store.get_by_name(&channel_name)? else {
+            let Some(channel) = session_store.get_by_name_on("matrix", &channel_name)? else {
                 room.send(RoomMessageEventContent::text_plain(format!(
                     "❌ Channel '{}' not found.\n\nUse !list to see all channels.",
                     channel_name
@@ -347,6 +412,87 @@ pub async fn handle_matrix_command(
                 );
             }
         }
+        "promote" => {
+            if is_dm {
+                room.send(RoomMessageEventContent::text_plain(
+                    "❌ The !promote command only works in channel rooms.",
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            let level = match command_parts.get(2) {
+                None => Some(matrix_client::MODERATOR_POWER_LEVEL),
+                Some(level) => level.parse::<i64>().ok(),
+            };
+            let (Some(user), Some(level)) = (command_parts.get(1), level) else {
+                room.send(RoomMessageEventContent::text_plain(format!(
+                    "Usage: !promote <user> [level]\n\n\
+                    Raises someone's power level in this room, to {} unless you give one \
+                    (below {}), e.g. !promote @bob:matrix.org",
+                    matrix_client::MODERATOR_POWER_LEVEL,
+                    matrix_client::BOT_POWER_LEVEL
+                )))
+                .await?;
+                return Ok(());
+            };
+
+            let reply =
+                match matrix_client::set_power_level(client, room.room_id(), user, level).await {
+                    Ok(()) => {
+                        tracing::info!(user, level, promoted_by = %sender, "Promoted room member");
+                        format!("✅ {} now has power level {} in this room.", user, level)
+                    }
+                    Err(e) => format!("⚠️ Couldn't promote {}: {:#}", user, e),
+                };
+            room.send(RoomMessageEventContent::text_plain(reply))
+                .await?;
+        }
+        "topic" => {
+            if is_dm {
+                room.send(RoomMessageEventContent::text_plain(
+                    "❌ The !topic command only works in channel rooms.",
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            let Some(channel) = session_store.get_by_room(room.room_id().as_str())? else {
+                room.send(RoomMessageEventContent::text_plain(
+                    "No channel attached to this room.",
+                ))
+                .await?;
+                return Ok(());
+            };
+
+            let topic = command_parts[1..].join(" ");
+            if topic.is_empty() {
+                let current = matrix_client::channel_topic(session_store, &channel)?;
+                room.send(RoomMessageEventContent::text_plain(format!(
+                    "Topic: {}
+
+Usage: !topic <text>",
+                    current
+                )))
+                .await?;
+                return Ok(());
+            }
+
+            let reply = match matrix_client::set_topic(client, room.room_id(), &topic).await {
+                Ok(()) => {
+                    session_store.set_room_topic(&channel.channel_name, Some(&topic))?;
+                    tracing::info!(
+                        channel = %channel.channel_name,
+                        user = %sender,
+                        "Room topic updated"
+                    );
+                    "✅ Topic updated.".to_string()
+                }
+                Err(e) => format!("⚠️ Couldn't set the topic: {:#}", e),
+            };
+            room.send(RoomMessageEventContent::text_plain(reply))
+                .await?;
+        }
         "cleanup" => {
             if !is_dm {
                 room.send(RoomMessageEventContent::text_plain(
@@ -536,7 +682,11 @@ pub async fn handle_matrix_command(
                 }
 
                 // Create Matrix room for this workspace
-                let room_prefix = config.matrix.as_ref().map(|m| m.room_prefix.as_str()).unwrap_or("Claude");
+                let room_prefix = config
+                    .matrix
+                    .as_ref()
+                    .map(|m| m.room_prefix.as_str())
+                    .unwrap_or("Claude");
                 let room_name = format!("{}: {}", room_prefix, channel_name);
                 match matrix_client::create_room(client, &room_name).await {
                     Ok(new_room_id) => {
@@ -556,8 +706,15 @@ pub async fn handle_matrix_command(
 
                         // Create channel in database (inherits existing directory)
                         match session_store.create_channel(&channel_name, new_room_id.as_str()) {
-                            Ok(_channel) => {
+                            Ok(channel) => {
                                 metrics::increment_active_channels();
+                                matrix_client::apply_channel_topic(
+                                    client,
+                                    session_store,
+                                    &new_room_id,
+                                    &channel,
+                                )
+                                .await;
                                 if invite_failed {
                                     restored.push(format!("{} (invite failed)", channel_name));
                                 } else {
@@ -620,9 +777,10 @@ pub async fn handle_matrix_command(
         "schedule" => {
             // Only allow in channels (not DMs)
             if is_dm {
-                room.send(RoomMessageEventContent::text_plain(
-                    "Scheduling is only available in channels. Create a channel first with !create <name>",
-                ))
+                room.send(RoomMessageEventContent::text_plain(t(
+                    &i18n::user_locale(session_store, sender),
+                    "schedule.channels_only",
+                )))
                 .await?;
                 return Ok(());
             }
@@ -631,13 +789,15 @@ pub async fn handle_matrix_command(
             let channel = match session_store.get_by_room(room.room_id().as_str())? {
                 Some(c) => c,
                 None => {
-                    room.send(RoomMessageEventContent::text_plain(
-                        "This room is not associated with a channel. Please set up a channel first.",
-                    ))
+                    room.send(RoomMessageEventContent::text_plain(t(
+                        &i18n::user_locale(session_store, sender),
+                        "schedule.no_channel",
+                    )))
                     .await?;
                     return Ok(());
                 }
             };
+            let locale = i18n::channel_locale(session_store, &channel.channel_name, sender);
 
             // Parse subcommand (args are command_parts[1..])
             let args = &command_parts[1..];
@@ -678,7 +838,7 @@ pub async fn handle_matrix_command(
                                 &sched.id[..8]
                             ));
                         }
-                        msg.push_str("Commands: !schedule delete <id>, !schedule pause <id>, !schedule resume <id>");
+                        msg.push_str("Commands: !schedule edit <id> <time and/or prompt>, !schedule delete <id>, !schedule pause <id>, !schedule resume <id>, !schedule quiet <id> defer|silent, !schedule log <id>");
                         room.send(RoomMessageEventContent::text_plain(&msg)).await?;
                     }
                 }
@@ -693,33 +853,37 @@ pub async fn handle_matrix_command(
                                 schedules.iter().filter(|s| s.id.starts_with(*id)).collect();
                             match matching.len() {
                                 0 => {
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "No schedule found matching ID '{}'",
-                                        id
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.not_found",
+                                        &[("id", id)],
                                     )))
                                     .await?;
                                 }
                                 1 => {
                                     scheduler_store.delete_schedule(&matching[0].id)?;
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "🗑️ Deleted schedule: {}",
-                                        truncate_str(&matching[0].prompt, 50)
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.deleted",
+                                        &[("prompt", &truncate_str(&matching[0].prompt, 50))],
                                     )))
                                     .await?;
                                 }
                                 _ => {
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "Multiple schedules match '{}'. Be more specific.",
-                                        id
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.ambiguous",
+                                        &[("id", id)],
                                     )))
                                     .await?;
                                 }
                             }
                         }
                         None => {
-                            room.send(RoomMessageEventContent::text_plain(
-                                "Usage: !schedule delete <id>\nUse !schedule list to see IDs",
-                            ))
+                            room.send(RoomMessageEventContent::text_plain(t(
+                                &locale,
+                                "schedule.usage_delete",
+                            )))
                             .await?;
                         }
                     }
@@ -738,33 +902,37 @@ pub async fn handle_matrix_command(
                                 .collect();
                             match matching.len() {
                                 0 => {
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "No active schedule found matching ID '{}'",
-                                        id
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.no_active",
+                                        &[("id", id)],
                                     )))
                                     .await?;
                                 }
                                 1 => {
                                     scheduler_store.pause_schedule(&matching[0].id)?;
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "⏸️ Paused schedule: {}",
-                                        truncate_str(&matching[0].prompt, 50)
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.paused",
+                                        &[("prompt", &truncate_str(&matching[0].prompt, 50))],
                                     )))
                                     .await?;
                                 }
                                 _ => {
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "Multiple schedules match '{}'. Be more specific.",
-                                        id
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.ambiguous",
+                                        &[("id", id)],
                                     )))
                                     .await?;
                                 }
                             }
                         }
                         None => {
-                            room.send(RoomMessageEventContent::text_plain(
-                                "Usage: !schedule pause <id>\nUse !schedule list to see IDs",
-                            ))
+                            room.send(RoomMessageEventContent::text_plain(t(
+                                &locale,
+                                "schedule.usage_pause",
+                            )))
                             .await?;
                         }
                     }
@@ -783,36 +951,244 @@ pub async fn handle_matrix_command(
                                 .collect();
                             match matching.len() {
                                 0 => {
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "No paused schedule found matching ID '{}'",
-                                        id
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.no_paused",
+                                        &[("id", id)],
                                     )))
                                     .await?;
                                 }
                                 1 => {
                                     scheduler_store.resume_schedule(&matching[0].id)?;
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "▶️ Resumed schedule: {}",
-                                        truncate_str(&matching[0].prompt, 50)
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.resumed",
+                                        &[("prompt", &truncate_str(&matching[0].prompt, 50))],
                                     )))
                                     .await?;
                                 }
                                 _ => {
-                                    room.send(RoomMessageEventContent::text_plain(format!(
-                                        "Multiple schedules match '{}'. Be more specific.",
-                                        id
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.ambiguous",
+                                        &[("id", id)],
                                     )))
                                     .await?;
                                 }
                             }
                         }
                         None => {
-                            room.send(RoomMessageEventContent::text_plain(
-                                "Usage: !schedule resume <id>\nUse !schedule list to see IDs",
-                            ))
+                            room.send(RoomMessageEventContent::text_plain(t(
+                                &locale,
+                                "schedule.usage_resume",
+                            )))
+                            .await?;
+                        }
+                    }
+                }
+                Some("quiet") => {
+                    let policy = args.get(2).and_then(|p| p.parse::<QuietPolicy>().ok());
+                    match (args.get(1), policy) {
+                        (Some(id), Some(policy)) => {
+                            let schedules =
+                                scheduler_store.list_by_room(room.room_id().as_str())?;
+                            let matching: Vec<_> =
+                                schedules.iter().filter(|s| s.id.starts_with(*id)).collect();
+                            match matching.len() {
+                                0 => {
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.not_found",
+                                        &[("id", id)],
+                                    )))
+                                    .await?;
+                                }
+                                1 => {
+                                    scheduler_store.set_quiet_policy(&matching[0].id, policy)?;
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.quiet",
+                                        &[
+                                            ("prompt", &truncate_str(&matching[0].prompt, 50)),
+                                            ("policy", &policy.to_string()),
+                                        ],
+                                    )))
+                                    .await?;
+                                }
+                                _ => {
+                                    room.send(RoomMessageEventContent::text_plain(tf(
+                                        &locale,
+                                        "schedule.ambiguous",
+                                        &[("id", id)],
+                                    )))
+                                    .await?;
+                                }
+                            }
+                        }
+                        _ => {
+                            room.send(RoomMessageEventContent::text_plain(t(
+                                &locale,
+                                "schedule.usage_quiet",
+                            )))
+                            .await?;
+                        }
+                    }
+                }
+                Some("log") => {
+                    let Some(id) = args.get(1) else {
+                        room.send(RoomMessageEventContent::text_plain(t(
+                            &locale,
+                            "schedule.usage_log",
+                        )))
+                        .await?;
+                        return Ok(());
+                    };
+                    let schedules = scheduler_store.list_by_room(room.room_id().as_str())?;
+                    let matching: Vec<_> =
+                        schedules.iter().filter(|s| s.id.starts_with(*id)).collect();
+                    let reply = match matching.as_slice() {
+                        [] => tf(&locale, "schedule.not_found", &[("id", id)]),
+                        [sched] => {
+                            let runs = scheduler_store.list_runs(&sched.id, SCHEDULE_LOG_RUNS)?;
+                            let prompt = truncate_str(&sched.prompt, 50);
+                            if runs.is_empty() {
+                                tf(&locale, "schedule.no_runs", &[("prompt", &prompt)])
+                            } else {
+                                let mut msg = tf(
+                                    &locale,
+                                    "schedule.log",
+                                    &[("prompt", &prompt), ("count", &runs.len().to_string())],
+                                );
+                                msg.push_str("\n\n");
+                                for run in &runs {
+                                    let took = std::time::Duration::from_millis(
+                                        run.duration_ms.max(0) as u64,
+                                    );
+                                    msg.push_str(&format!(
+                                        "{} {} ({})\n   {}\n",
+                                        if run.success { "✅" } else { "❌" },
+                                        &run.started_at[..16],
+                                        crate::admin::routes::format_duration(took),
+                                        run.preview.replace('\n', " "),
+                                    ));
+                                }
+                                msg
+                            }
+                        }
+                        _ => tf(&locale, "schedule.ambiguous", &[("id", id)]),
+                    };
+                    room.send(RoomMessageEventContent::text_plain(&reply))
+                        .await?;
+                }
+                Some("edit") => {
+                    let Some(id) = args.get(1) else {
+                        room.send(RoomMessageEventContent::text_plain(t(
+                            &locale,
+                            "schedule.usage_edit",
+                        )))
+                        .await?;
+                        return Ok(());
+                    };
+                    let schedules = scheduler_store.list_by_room(room.room_id().as_str())?;
+                    let matching: Vec<_> =
+                        schedules.iter().filter(|s| s.id.starts_with(*id)).collect();
+                    let schedule = match matching.as_slice() {
+                        [schedule] => *schedule,
+                        [] => {
+                            room.send(RoomMessageEventContent::text_plain(tf(
+                                &locale,
+                                "schedule.not_found",
+                                &[("id", id)],
+                            )))
+                            .await?;
+                            return Ok(());
+                        }
+                        _ => {
+                            room.send(RoomMessageEventContent::text_plain(tf(
+                                &locale,
+                                "schedule.ambiguous",
+                                &[("id", id)],
+                            )))
                             .await?;
+                            return Ok(());
                         }
+                    };
+                    if schedule.status == ScheduleStatus::Executing {
+                        room.send(RoomMessageEventContent::text_plain(t(
+                            &locale,
+                            "schedule.edit_running",
+                        )))
+                        .await?;
+                        return Ok(());
                     }
+
+                    // Validate everything before the row is touched
+                    let edit = match parse_schedule_edit(
+                        &args[2..].join(" "),
+                        &config.scheduler.timezone,
+                        &config.scheduler.templates,
+                    ) {
+                        Ok(edit) => edit,
+                        Err(e) => {
+                            room.send(RoomMessageEventContent::text_plain(format!("⚠️ {}", e)))
+                                .await?;
+                            return Ok(());
+                        }
+                    };
+                    if let Some(name) = edit
+                        .prompt
+                        .as_deref()
+                        .and_then(templates::schedule_reference)
+                    {
+                        if session_store
+                            .get_template(&channel.channel_name, name)?
+                            .is_none()
+                        {
+                            room.send(RoomMessageEventContent::text_plain(tf(
+                                &locale,
+                                "schedule.unknown_template",
+                                &[("name", name)],
+                            )))
+                            .await?;
+                            return Ok(());
+                        }
+                    }
+
+                    if !scheduler_store.update_schedule(
+                        &schedule.id,
+                        edit.time.as_ref(),
+                        edit.prompt.as_deref(),
+                    )? {
+                        room.send(RoomMessageEventContent::text_plain(t(
+                            &locale,
+                            "schedule.edit_running",
+                        )))
+                        .await?;
+                        return Ok(());
+                    }
+                    let updated = scheduler_store
+                        .get_by_id(&schedule.id)?
+                        .unwrap_or_else(|| schedule.clone());
+                    room.send(RoomMessageEventContent::text_plain(tf(
+                        &locale,
+                        "schedule.edited",
+                        &[
+                            ("prompt", &truncate_str(&updated.prompt, 100)),
+                            ("next", &updated.next_execution_at[..16]),
+                            ("timezone", &config.scheduler.timezone),
+                            ("id", &updated.id[..8]),
+                        ],
+                    )))
+                    .await?;
+
+                    tracing::info!(
+                        schedule_id = %updated.id,
+                        channel = %channel.channel_name,
+                        time_changed = edit.time.is_some(),
+                        prompt_changed = edit.prompt.is_some(),
+                        next_exec = %updated.next_execution_at,
+                        "Schedule edited"
+                    );
                 }
                 Some("export") => {
                     // Export schedules to .gorp/schedule.yaml
@@ -838,7 +1214,12 @@ pub async fn handle_matrix_command(
                     );
                     for sched in &active_schedules {
                         let time_str = if let Some(ref cron) = sched.cron_expression {
-                            cron.clone()
+                            time_template_for_cron(
+                                cron,
+                                &config.scheduler.templates,
+                                &config.scheduler.timezone,
+                            )
+                            .unwrap_or_else(|| cron.clone())
                         } else if let Some(ref exec_at) = sched.execute_at {
                             exec_at.clone()
                         } else {
@@ -980,15 +1361,18 @@ pub async fn handle_matrix_command(
                             if let (Some(time), Some(prompt)) =
                                 (current_time.take(), current_prompt.take())
                             {
-                                match import_schedule(
-                                    &time,
-                                    &prompt,
-                                    current_status == "paused",
-                                    &channel,
-                                    sender,
-                                    &config.scheduler.timezone,
-                                    scheduler_store,
-                                ) {
+                                match expand_time_template(&time, &config.scheduler.templates)
+                                    .and_then(|time| {
+                                        import_schedule(
+                                            time,
+                                            &prompt,
+                                            current_status == "paused",
+                                            &channel,
+                                            sender,
+                                            &config.scheduler.timezone,
+                                            scheduler_store,
+                                        )
+                                    }) {
                                     Ok(_) => imported_count += 1,
                                     Err(e) => errors.push(format!(
                                         "'{}': {}",
@@ -1030,14 +1414,18 @@ pub async fn handle_matrix_command(
                     // Don't forget the last one
                     if let (Some(time), Some(prompt)) = (current_time.take(), current_prompt.take())
                     {
-                        match import_schedule(
-                            &time,
-                            &prompt,
-                            current_status == "paused",
-                            &channel,
-                            sender,
-                            &config.scheduler.timezone,
-                            scheduler_store,
+                        match expand_time_template(&time, &config.scheduler.templates).and_then(
+                            |time| {
+                                import_schedule(
+                                    time,
+                                    &prompt,
+                                    current_status == "paused",
+                                    &channel,
+                                    sender,
+                                    &config.scheduler.timezone,
+                                    scheduler_store,
+                                )
+                            },
                         ) {
                             Ok(_) => imported_count += 1,
                             Err(e) => {
@@ -1063,7 +1451,7 @@ pub async fn handle_matrix_command(
                     // Parse time expression from the beginning of args
                     if args.is_empty() {
                         room.send(RoomMessageEventContent::text_plain(
-                            "Usage: !schedule <time> <prompt>\n\nExamples:\n  !schedule in 2 hours check my inbox\n  !schedule tomorrow 9am summarize my calendar\n  !schedule every monday 8am weekly standup\n\nOther commands:\n  !schedule list\n  !schedule delete <id>\n  !schedule pause <id>\n  !schedule resume <id>\n  !schedule export\n  !schedule import",
+                            "Usage: !schedule <time> <prompt>\n\nExamples:\n  !schedule in 2 hours check my inbox\n  !schedule tomorrow 9am summarize my calendar\n  !schedule every monday 8am weekly standup\n  !schedule @standup post the standup summary\n\nOther commands:\n  !schedule list\n  !schedule edit <id> <time and/or prompt>\n  !schedule delete <id>\n  !schedule pause <id>\n  !schedule resume <id>\n  !schedule quiet <id> defer|silent\n  !schedule export\n  !schedule import",
                         ))
                         .await?;
                         return Ok(());
@@ -1071,17 +1459,44 @@ pub async fn handle_matrix_command(
 
                     // Try to parse time expression greedily from start
                     let full_args = args.join(" ");
-                    let (parsed_schedule, prompt) =
-                        parse_schedule_input(&full_args, &config.scheduler.timezone)?;
+                    let (parsed_schedule, prompt) = match parse_schedule_input(
+                        &full_args,
+                        &config.scheduler.timezone,
+                        &config.scheduler.templates,
+                    ) {
+                        Ok(parsed) => parsed,
+                        Err(e) => {
+                            room.send(RoomMessageEventContent::text_plain(format!("⚠️ {}", e)))
+                                .await?;
+                            return Ok(());
+                        }
+                    };
 
                     if prompt.is_empty() {
-                        room.send(RoomMessageEventContent::text_plain(
-                            "Missing prompt. Usage: !schedule <time> <prompt>",
-                        ))
+                        room.send(RoomMessageEventContent::text_plain(t(
+                            &locale,
+                            "schedule.missing_prompt",
+                        )))
                         .await?;
                         return Ok(());
                     }
 
+                    // template:<name> runs a saved template, so it has to exist
+                    if let Some(name) = templates::schedule_reference(&prompt) {
+                        if session_store
+                            .get_template(&channel.channel_name, name)?
+                            .is_none()
+                        {
+                            room.send(RoomMessageEventContent::text_plain(tf(
+                                &locale,
+                                "schedule.unknown_template",
+                                &[("name", name)],
+                            )))
+                            .await?;
+                            return Ok(());
+                        }
+                    }
+
                     // Create the schedule
                     let schedule_id = uuid::Uuid::new_v4().to_string();
                     let now = Utc::now().to_rfc3339();
@@ -1113,19 +1528,21 @@ pub async fn handle_matrix_command(
 
                     scheduler_store.create_schedule(&scheduled_prompt)?;
 
-                    let schedule_type = if cron_expr.is_some() {
-                        "🔄 Recurring schedule"
+                    let confirmation_key = if cron_expr.is_some() {
+                        "schedule.created_recurring"
                     } else {
-                        "⏰ One-time schedule"
+                        "schedule.created_one_time"
                     };
 
-                    room.send(RoomMessageEventContent::text_plain(format!(
-                        "{} created!\n\n📝 Prompt: {}\n⏱️ Next execution: {} ({})\n🆔 ID: {}",
-                        schedule_type,
-                        truncate_str(&prompt, 100),
-                        &next_exec[..16],
-                        &config.scheduler.timezone,
-                        &schedule_id[..8]
+                    room.send(RoomMessageEventContent::text_plain(tf(
+                        &locale,
+                        confirmation_key,
+                        &[
+                            ("prompt", &truncate_str(&prompt, 100)),
+                            ("next", &next_exec[..16]),
+                            ("timezone", &config.scheduler.timezone),
+                            ("id", &schedule_id[..8]),
+                        ],
                     )))
                     .await?;
 
@@ -1178,6 +1595,127 @@ pub async fn handle_matrix_command(
                 "Session reset by user"
             );
         }
+        "approve" => {
+            // Pending invites are only visible from the admin room
+            let admin_room = config.matrix.as_ref().and_then(|m| m.admin_room.as_deref());
+            if admin_room != Some(room.room_id().as_str()) {
+                room.send(RoomMessageEventContent::text_plain(
+                    "❌ The !approve command only works in the admin room (matrix.admin_room).",
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            let Some(target) = command_parts.get(1) else {
+                let pending = session_store.list_pending_invites()?;
+                let text = if pending.is_empty() {
+                    "📨 No invites waiting for approval.".to_string()
+                } else {
+                    let mut text = String::from("📨 Invites waiting for approval:\n");
+                    for invite in &pending {
+                        text.push_str(&format!(
+                            "\n• {} - {} (invited by {})",
+                            invite.room_id,
+                            invite.room_name.as_deref().unwrap_or("unnamed room"),
+                            invite.inviter
+                        ));
+                    }
+                    text.push_str("\n\nJoin one with !approve <room_id>");
+                    text
+                };
+                room.send(RoomMessageEventContent::text_plain(text)).await?;
+                return Ok(());
+            };
+
+            let Some(invite) = session_store.take_pending_invite(target)? else {
+                room.send(RoomMessageEventContent::text_plain(format!(
+                    "No pending invite for {}. See !approve for the list.",
+                    target
+                )))
+                .await?;
+                return Ok(());
+            };
+            let room_id = matrix_sdk::ruma::RoomId::parse(invite.room_id.as_str())?;
+
+            match client.join_room_by_id(&room_id).await {
+                Ok(_) => {
+                    tracing::info!(room_id = %room_id, inviter = %invite.inviter, approved_by = sender, "Joined approved room invite");
+                    room.send(RoomMessageEventContent::text_plain(format!(
+                        "✅ Joined {} ({}), invited by {}.",
+                        invite.room_name.as_deref().unwrap_or("the room"),
+                        room_id,
+                        invite.inviter
+                    )))
+                    .await?;
+                }
+                Err(e) => {
+                    // Keep it queued so the join can be retried
+                    session_store.queue_invite(
+                        &invite.room_id,
+                        &invite.inviter,
+                        invite.room_name.as_deref(),
+                    )?;
+                    tracing::error!(error = %e, room_id = %room_id, "Failed to join approved room");
+                    room.send(RoomMessageEventContent::text_plain(format!(
+                        "⚠️ Couldn't join {}: {}",
+                        room_id, e
+                    )))
+                    .await?;
+                }
+            }
+        }
+        "clone" => {
+            let request = match clone::parse_clone_args(&command_parts[1..]) {
+                Ok(request) => request,
+                Err(e) => {
+                    room.send(RoomMessageEventContent::text_plain(format!(
+                        "{}\n\n{}",
+                        e,
+                        clone::USAGE
+                    )))
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            let registry = platforms.read().await;
+            let Some(target) = registry.get(&request.platform) else {
+                let mut connected = registry.platform_ids();
+                connected.sort();
+                room.send(RoomMessageEventContent::text_plain(format!(
+                    "❌ {} isn't connected. Connected platforms: {}",
+                    request.platform,
+                    connected.join(", ")
+                )))
+                .await?;
+                return Ok(());
+            };
+
+            match clone::clone_channel(&request, target, session_store, scheduler_store, Some(bus))
+                .await
+            {
+                Ok(cloned) => {
+                    // A new Matrix room is invite-only, so bring the requester along
+                    if cloned.platform == "matrix" {
+                        let room_id =
+                            matrix_sdk::ruma::OwnedRoomId::try_from(cloned.channel_id.as_str())?;
+                        matrix_client::invite_user(client, &room_id, sender).await?;
+                    }
+                    room.send(RoomMessageEventContent::text_plain(clone::describe(
+                        &request, &cloned,
+                    )))
+                    .await?;
+                }
+                Err(e) => {
+                    tracing::warn!(source = %request.source, platform = %request.platform, error = %e, "Failed to clone channel");
+                    room.send(RoomMessageEventContent::text_plain(format!(
+                        "❌ Couldn't clone {}: {}",
+                        request.source, e
+                    )))
+                    .await?;
+                }
+            }
+        }
         _ => {
             // This should not be reached - unknown commands are handled by commands module
             // and only specific delegated commands should reach here
@@ -1192,6 +1730,78 @@ pub async fn handle_matrix_command(
     Ok(())
 }
 
+/// Pin the message a user replied to with !pin.
+///
+/// The message is saved to the channel's .gorp/pins.md and pinned in the room.
+/// Native pinning needs permission to change m.room.pinned_events; if the bot
+/// lacks it the pin is still saved locally.
+pub async fn pin_replied_message(
+    room: &Room,
+    event_id: &EventId,
+    sender: &str,
+    note: &str,
+    session_store: &SessionStore,
+) -> Result<()> {
+    let Some(channel) = session_store.get_by_room(room.room_id().as_str())? else {
+        room.send(RoomMessageEventContent::text_plain(
+            "No channel attached to this room.",
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let replied = room.event(event_id, None).await?;
+    let message = match replied.raw().deserialize() {
+        Ok(AnySyncTimelineEvent::MessageLike(AnySyncMessageLikeEvent::RoomMessage(msg))) => {
+            msg.as_original().map(|original| {
+                (
+                    original.sender.to_string(),
+                    original.content.body().to_string(),
+                )
+            })
+        }
+        _ => None,
+    };
+    let Some((author, content)) = message else {
+        room.send(RoomMessageEventContent::text_plain(
+            "❌ Only text messages can be pinned.",
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let mut context = format!("Reply to {} from {}", event_id, author);
+    if !note.is_empty() {
+        context.push_str(&format!(" - {}", note));
+    }
+    pins::append_pin(
+        &channel.directory,
+        &Pin {
+            pinned_at: Utc::now(),
+            pinned_by: sender.to_string(),
+            context: Some(context),
+            content: content.clone(),
+        },
+    )?;
+
+    let room_note = match room.pin_event(event_id).await {
+        Ok(_) => "",
+        Err(e) => {
+            tracing::warn!(error = %e, event_id = %event_id, "Failed to pin event in room");
+            "\n\n(Saved to pins.md, but I couldn't pin it in the room - check my permissions.)"
+        }
+    };
+    tracing::info!(channel = %channel.channel_name, sender, event_id = %event_id, "Pinned replied message");
+
+    room.send(RoomMessageEventContent::text_plain(format!(
+        "📌 Pinned: {}{}",
+        truncate_str(content.lines().next().unwrap_or_default(), 80),
+        room_note
+    )))
+    .await?;
+    Ok(())
+}
+
 /// Import a single schedule from YAML data
 fn import_schedule(
     time: &str,
@@ -2,46 +2,93 @@
 // ABOUTME: Routes incoming messages through whitelist, command parsing, and Claude invocation.
 
 // Submodules
+pub mod approvals;
+pub mod archive;
+pub mod ask;
 pub mod attachments;
+pub mod awaiting;
 pub mod chat;
+pub mod clear;
+pub mod clone;
 pub mod commands;
+pub mod compare;
+pub mod confirmations;
 pub mod context;
+pub mod deliver;
 pub mod generic_channel;
+pub mod health;
 pub mod helpers;
+pub mod history;
+pub mod invite;
+pub mod locale;
 pub mod matrix_commands;
+pub mod models;
+pub mod network;
+pub mod pins;
+pub mod prompt_templates;
+pub mod repo;
+pub mod response_cache;
+pub mod response_length;
+pub mod rich_reply;
 pub mod schedule_import;
+pub mod send_guard;
+pub mod spend;
+pub mod status_reactions;
+pub mod streaming;
+pub mod summarize;
+pub mod telegram_topics;
+pub mod threads;
 pub mod traits;
+pub mod transcription_language;
 
 // Re-exports from submodules for backward compatibility
 pub use attachments::download_attachment;
 pub use context::{route_to_dispatch, write_context_file};
 pub use generic_channel::GenericChannel;
-pub use helpers::{is_debug_enabled, looks_like_cron, truncate_str, validate_channel_name};
+pub use helpers::{
+    is_debug_enabled, is_streaming_enabled, looks_like_cron, truncate_str, validate_channel_name,
+};
 pub use schedule_import::parse_schedule_input;
 pub use traits::MockChannel;
 
 use anyhow::Result;
 use gorp_core::traits::{IncomingMessage, MessageContent, MessagingPlatform};
 use matrix_sdk::{
-    room::Room, ruma::events::room::message::RoomMessageEventContent, Client, RoomState,
+    room::Room,
+    ruma::events::room::message::{MessageType, Relation, RoomMessageEventContent},
+    Client, RoomState,
 };
 
 use crate::{
-    commands::{parse_message, Command, ParseResult},
+    approvals::{parse_answer, PendingApprovals},
+    bus::MessageBus,
+    commands::{parse_message_with_aliases, Command, ParseResult},
     config::Config,
+    dedup::DedupCache,
+    drafts,
+    edits::{EditOutcome, EditTracker},
+    logging::loggable_content,
     matrix_client, metrics, onboarding,
-    platform::MatrixChannel,
+    platform::{MatrixChannel, SharedPlatformRegistry},
+    rate_limit::{is_exempt_command, limited_notice, RateDecision, RateLimiter},
+    redactions::ResponseTracker,
+    runtime_mode::SafeModeRefusal,
     scheduler::SchedulerStore,
     server::ServerState,
     session::SessionStore,
+    transcription::{is_audio, Transcriber, VOICE_UNSUPPORTED_NOTICE},
+    typing,
+    usage::InvocationOrigin,
     utils::markdown_to_html,
     warm_session::SharedWarmSessionManager,
 };
 
+use prompt_templates::TemplateRun;
+
 /// Platform-agnostic message handler entry point.
 ///
 /// Processes an incoming message from any platform:
-/// 1. Checks platform-aware whitelist
+/// 1. Drops events already handled and checks platform-aware whitelist
 /// 2. Parses commands and routes to appropriate handler
 /// 3. Gates platform-specific commands behind platform_id check
 /// 4. For chat messages, invokes Claude and sends response via the platform
@@ -52,8 +99,21 @@ pub async fn handle_incoming(
 ) -> Result<()> {
     let start_time = std::time::Instant::now();
 
+    // Drop events we've already handled (sync restarts, replayed update offsets)
+    if !state.dedup.check_and_mark(&msg.platform_id, &msg.event_id) {
+        tracing::debug!(
+            platform = %msg.platform_id,
+            event_id = %msg.event_id,
+            "Skipping duplicate event"
+        );
+        return Ok(());
+    }
+
     // Check platform-aware whitelist
-    if !state.config.is_user_allowed(&msg.platform_id, &msg.sender.id) {
+    if !state
+        .config
+        .is_user_allowed(&msg.platform_id, &msg.sender.id)
+    {
         tracing::debug!(
             sender = %msg.sender.id,
             platform = %msg.platform_id,
@@ -62,18 +122,120 @@ pub async fn handle_incoming(
         return Ok(());
     }
 
-    // Safe preview generation (respects UTF-8 boundaries)
-    let message_preview: String = msg.body.chars().take(50).collect();
+    // Before anything slow happens, let the sender see the message arrived
+    mark_seen(platform, &msg.channel_id, &msg.event_id).await;
+
+    // Edits rewrite a prompt still waiting to go out, or come back as a correction
+    let correction;
+    let msg = match &msg.edits_event_id {
+        Some(original_id) => {
+            match state
+                .edits
+                .apply_edit(&msg.platform_id, original_id, &msg.body)
+            {
+                EditOutcome::FollowUp(body) => {
+                    correction = IncomingMessage {
+                        body,
+                        edits_event_id: None,
+                        ..msg.clone()
+                    };
+                    &correction
+                }
+                outcome => {
+                    tracing::debug!(
+                        platform = %msg.platform_id,
+                        original = %original_id,
+                        ?outcome,
+                        "Applied message edit"
+                    );
+                    return Ok(());
+                }
+            }
+        }
+        None => msg,
+    };
+
     tracing::info!(
         sender = %msg.sender.id,
         platform = %msg.platform_id,
         channel = %msg.channel_id,
-        message_preview,
+        message = %loggable_content(&msg.body),
         "Processing incoming message"
     );
 
     // Parse message using gorp-core command parsing
-    let parse_result = parse_message(&msg.body, "!claude");
+    let catalog = state.config.command_catalog();
+    let prefixes = state.config.command_prefixes(&msg.platform_id);
+    let parse_result = parse_message_with_aliases(&msg.body, &prefixes, &catalog);
+
+    if spends_rate_limit(&parse_result) {
+        if let RateDecision::Limited {
+            retry_after,
+            notify,
+        } = state.rate_limiter.check(&msg.platform_id, &msg.sender.id)
+        {
+            metrics::record_rate_limited(&msg.platform_id);
+            tracing::info!(
+                sender = %msg.sender.id,
+                platform = %msg.platform_id,
+                retry_after_secs = retry_after.as_secs_f64(),
+                "Rate limited message"
+            );
+            if notify {
+                send_private_reply(
+                    platform,
+                    msg,
+                    MessageContent::plain(limited_notice(retry_after)),
+                )
+                .await?;
+            }
+            return Ok(());
+        }
+    }
+
+    // !send and !template run submit text through the chat path below
+    let submitted;
+    let (msg, parse_result, from_draft) = match parse_result {
+        ParseResult::Command(cmd) if cmd.name == "send" => {
+            metrics::record_command("send");
+            let Some(draft) = state
+                .session_store
+                .take_draft(&msg.channel_id, &msg.sender.id)?
+            else {
+                send_reply(
+                    platform,
+                    msg,
+                    MessageContent::plain("No queued draft to send."),
+                )
+                .await?;
+                return Ok(());
+            };
+            submitted = IncomingMessage {
+                body: draft.body,
+                ..msg.clone()
+            };
+            let body = submitted.body.clone();
+            (&submitted, ParseResult::Message(body), true)
+        }
+        ParseResult::Command(cmd) if prompt_templates::is_run(&cmd) => {
+            metrics::record_command("template");
+            let prompt =
+                match prompt_templates::resolve_run(&state.session_store, &msg.channel_id, &cmd)? {
+                    TemplateRun::Prompt { prompt, .. } => prompt,
+                    TemplateRun::Reply(text) => {
+                        send_reply(platform, msg, MessageContent::plain(text)).await?;
+                        return Ok(());
+                    }
+                };
+            submitted = IncomingMessage {
+                body: prompt,
+                ..msg.clone()
+            };
+            let body = submitted.body.clone();
+            (&submitted, ParseResult::Message(body), true)
+        }
+        other => (msg, other, false),
+    };
 
     if let ParseResult::Command(cmd) = parse_result {
         metrics::record_message_received("command");
@@ -88,13 +250,30 @@ pub async fn handle_incoming(
         return Ok(());
     }
 
+    // A pending `!template save <name>` takes this message as the template body
+    if !from_draft {
+        if let Some(reply) = prompt_templates::capture_body(
+            &state.session_store,
+            &msg.channel_id,
+            &msg.sender.id,
+            &msg.body,
+        )? {
+            send_reply(platform, msg, MessageContent::plain(reply)).await?;
+            return Ok(());
+        }
+    }
+
     // Non-command message handling
     metrics::record_message_received("chat");
+    state
+        .edits
+        .queue(&msg.platform_id, &msg.event_id, &msg.body);
 
     // Check if this is a DISPATCH activation (DM only)
     if msg.is_direct {
         let body_lower = msg.body.to_lowercase();
-        if body_lower.starts_with("!dispatch") || body_lower == "dispatch" {
+        let activation = format!("{}dispatch", state.config.command_prefix(&msg.platform_id));
+        if body_lower.starts_with(&activation) || body_lower == "dispatch" {
             tracing::info!(
                 channel = %msg.channel_id,
                 platform = %msg.platform_id,
@@ -119,22 +298,170 @@ pub async fn handle_incoming(
     // Check if channel is attached
     let session_store = &*state.session_store;
     if let Some(channel) = session_store.get_by_room(&msg.channel_id)? {
+        // Long messages from users with the send guard on wait as a draft for !send
+        if !from_draft && msg.attachments.is_empty() {
+            if let Some(notice) = drafts::hold_if_guarded(
+                session_store,
+                &state.config.send_guard,
+                &msg.platform_id,
+                &msg.channel_id,
+                &msg.sender.id,
+                &msg.body,
+            )? {
+                send_reply(
+                    platform,
+                    msg,
+                    MessageContent::html(&notice, markdown_to_html(&notice)),
+                )
+                .await?;
+                return Ok(());
+            }
+        }
+
         // Channel exists — invoke Claude via handle_text and send response
-        let response = handle_text(
-            &msg.body,
-            &channel,
-            session_store,
-            &state.warm_manager,
-        )
-        .await?;
+        let prompt = state
+            .edits
+            .take_for_send(&msg.platform_id, &msg.event_id)
+            .unwrap_or_else(|| msg.body.clone());
+        let has_voice = msg.attachments.iter().any(|a| is_audio(&a.mime_type));
+        if has_voice && state.transcriber.is_none() {
+            send_reply(
+                platform,
+                msg,
+                MessageContent::plain(VOICE_UNSUPPORTED_NOTICE),
+            )
+            .await?;
+            return Ok(());
+        }
+        let prompt = match platform.attachment_handler() {
+            Some(handler) if !msg.attachments.is_empty() => {
+                let limits = attachments::AttachmentLimits::for_channel(
+                    &state.config.attachments,
+                    session_store,
+                    &channel.channel_name,
+                );
+                match attachments::download_attachments(
+                    handler,
+                    &msg.attachments,
+                    &limits,
+                    &channel.directory,
+                )
+                .await
+                {
+                    Ok(saved) => {
+                        let language = session_store
+                            .get_transcription_language(&channel.channel_name)
+                            .unwrap_or_default();
+                        let transcripts = match &state.transcriber {
+                            Some(transcriber) => {
+                                attachments::transcribe_voice_notes(
+                                    &saved,
+                                    transcriber.as_ref(),
+                                    language.as_deref(),
+                                )
+                                .await
+                            }
+                            None => Ok(Vec::new()),
+                        };
+                        let transcripts = match transcripts {
+                            Ok(transcripts) => transcripts,
+                            Err(e) => {
+                                tracing::warn!(error = %e, "Failed to transcribe voice message");
+                                let notice =
+                                    format!("⚠️ Failed to transcribe voice message: {}", e);
+                                send_reply(platform, msg, MessageContent::plain(notice)).await?;
+                                return Ok(());
+                            }
+                        };
+                        let prompt = attachments::with_voice_transcripts(&transcripts, &prompt);
+                        attachments::with_attachment_preamble(&saved, &prompt)
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            count = msg.attachments.len(),
+                            "Failed to download attachments"
+                        );
+                        send_reply(
+                            platform,
+                            msg,
+                            MessageContent::plain(attachments::failure_notice(&e)),
+                        )
+                        .await?;
+                        return Ok(());
+                    }
+                }
+            }
+            _ => prompt,
+        };
+        let annotator = helpers::is_status_reactions_enabled(&channel.directory)
+            .then(|| platform.annotator())
+            .flatten();
+        let status =
+            status_reactions::StatusReactions::start(annotator, &msg.channel_id, &msg.event_id)
+                .await;
+        // Debug mode relays tool use as the agent runs (create .gorp/enable-debug to enable)
+        let (tool_tx, mut tool_rx) = tokio::sync::mpsc::unbounded_channel();
+        let tool_tx = helpers::is_debug_enabled(&channel.directory).then_some(tool_tx);
+        let relay_tool_notices = async {
+            while let Some((plain, html)) = tool_rx.recv().await {
+                if let Err(e) = send_reply(platform, msg, MessageContent::html(plain, html)).await {
+                    tracing::warn!(error = %e, "Failed to send tool notification");
+                }
+            }
+        };
+        // Typing also covers time spent queued behind another prompt in this channel
+        let (response, ()) = with_typing(platform, &msg.channel_id, async {
+            tokio::join!(
+                handle_text_with_tool_notices(
+                    &prompt,
+                    &channel,
+                    session_store,
+                    &state.warm_manager,
+                    InvocationOrigin::User,
+                    tool_tx,
+                ),
+                relay_tool_notices
+            )
+        })
+        .await;
+        status.finish(response.is_ok()).await;
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => match e.downcast_ref::<SafeModeRefusal>() {
+                Some(refusal) => {
+                    send_reply(platform, msg, MessageContent::plain(refusal.message())).await?;
+                    return Ok(());
+                }
+                None => return Err(e),
+            },
+        };
 
-        if !response.is_empty() {
-            let chunks = crate::utils::chunk_message(&response, crate::utils::MAX_CHUNK_SIZE);
-            for chunk in chunks {
-                let html = markdown_to_html(&chunk);
-                platform
-                    .send(&msg.channel_id, MessageContent::html(&chunk, &html))
-                    .await?;
+        // A manifest from the agent replaces its plain reply with text, files and actions
+        let workspace = std::path::Path::new(&channel.directory);
+        let rich = rich_reply::pending_response(workspace);
+        let queued_text = rich.as_ref().map(|rich| rich.to_plain_text());
+        let response_text = queued_text.as_deref().unwrap_or(&response);
+
+        let held = !response_text.is_empty()
+            && crate::delivery::hold_if_outside_window(
+                session_store,
+                &channel.channel_name,
+                Some((&msg.platform_id, &msg.channel_id)),
+                response_text,
+                crate::delivery::DeliveryPriority::Normal,
+            )?;
+
+        if !held {
+            if let Some(rich) = &rich {
+                rich_reply::send_rich_response(platform, msg, workspace, rich).await?;
+            } else if !response.is_empty() {
+                let response = helpers::with_footer(&response, &state.config.signing.footer);
+                let chunks = crate::utils::chunk_message(&response, crate::utils::MAX_CHUNK_SIZE);
+                for chunk in chunks {
+                    let html = markdown_to_html(&chunk);
+                    send_reply(platform, msg, MessageContent::html(&chunk, &html)).await?;
+                }
             }
         }
 
@@ -149,25 +476,86 @@ pub async fn handle_incoming(
         platform
             .send(
                 &msg.channel_id,
-                MessageContent::plain(
-                    "No channel attached. Use !help to see available commands.",
-                ),
+                MessageContent::plain("No channel attached. Use !help to see available commands."),
             )
             .await?;
     } else {
-        platform
-            .send(
-                &msg.channel_id,
-                MessageContent::plain(
-                    "No Claude channel attached to this room. Use !create <name> to create one.",
-                ),
-            )
-            .await?;
+        send_reply(
+            platform,
+            msg,
+            MessageContent::plain(
+                "No Claude channel attached to this room. Use !create <name> to create one.",
+            ),
+        )
+        .await?;
     }
 
     Ok(())
 }
 
+/// Whether a parsed message costs a rate-limit token.
+/// Escaped text is dropped anyway, and !help / !status stay available to a limited user.
+fn spends_rate_limit(parse_result: &ParseResult) -> bool {
+    match parse_result {
+        ParseResult::Ignore => false,
+        ParseResult::Command(cmd) => !is_exempt_command(&cmd.name),
+        _ => true,
+    }
+}
+
+/// Send a reply to `msg`, inside its thread when it has one and the platform threads
+async fn send_reply(
+    platform: &dyn MessagingPlatform,
+    msg: &IncomingMessage,
+    content: MessageContent,
+) -> Result<()> {
+    match (&msg.thread_id, platform.threading()) {
+        (Some(thread_id), Some(threading)) => {
+            threading
+                .send_threaded(&msg.channel_id, thread_id, content)
+                .await
+        }
+        _ => platform.send(&msg.channel_id, content).await,
+    }
+}
+
+/// Reply to `msg` so that only its sender sees it, where the platform can
+async fn send_private_reply(
+    platform: &dyn MessagingPlatform,
+    msg: &IncomingMessage,
+    content: MessageContent,
+) -> Result<()> {
+    let channel = GenericChannel::new(platform, &msg.channel_id, msg.is_direct)
+        .in_thread(msg.thread_id.as_deref());
+    commands::reply_privately(&channel, &msg.sender.id, content).await
+}
+
+/// Show the sender their message was seen (a read receipt, a 👀 reaction), where
+/// the platform can. Both the generic and the Matrix path go through here.
+async fn mark_seen(platform: &dyn MessagingPlatform, channel_id: &str, event_id: &str) {
+    let Some(receipts) = platform.receipts() else {
+        return;
+    };
+    if let Err(e) = receipts.send_read_receipt(channel_id, event_id).await {
+        tracing::warn!(error = %e, platform = platform.platform_id(), "Failed to send read receipt");
+    }
+}
+
+/// Run `work` with the platform's typing indicator on, if it has one
+async fn with_typing<F: std::future::Future>(
+    platform: &dyn MessagingPlatform,
+    channel_id: &str,
+    work: F,
+) -> F::Output {
+    match platform.typing() {
+        Some(channel_typing) => {
+            let indicator = typing::ChannelTypingIndicator::new(channel_typing, channel_id);
+            typing::with_typing(&indicator, work).await
+        }
+        None => work.await,
+    }
+}
+
 /// Handle a parsed command from any platform.
 async fn handle_incoming_command(
     msg: &IncomingMessage,
@@ -175,7 +563,18 @@ async fn handle_incoming_command(
     state: &ServerState,
     cmd: &Command,
 ) -> Result<()> {
-    let channel = GenericChannel::new(platform, &msg.channel_id, msg.is_direct);
+    // Where the platform has Confirm / Cancel buttons, destructive commands wait for a press
+    if let Some(provider) = platform.confirmations() {
+        if confirmations::is_answer(cmd) {
+            return confirmations::answer(platform, state, msg, cmd).await;
+        }
+        if confirmations::needs_confirmation(cmd) {
+            return confirmations::ask(provider, platform, state, msg, cmd).await;
+        }
+    }
+
+    let channel = GenericChannel::new(platform, &msg.channel_id, msg.is_direct)
+        .in_thread(msg.thread_id.as_deref());
 
     // Try the platform-agnostic command handler
     match commands::handle_command(
@@ -200,15 +599,15 @@ async fn handle_incoming_command(
                     let cmd_name = err_msg
                         .strip_prefix("DELEGATE_TO_MATRIX:")
                         .unwrap_or("unknown");
-                    platform
-                        .send(
-                            &msg.channel_id,
-                            MessageContent::plain(format!(
-                                "The !{} command is only available on Matrix.",
-                                cmd_name
-                            )),
-                        )
-                        .await?;
+                    commands::reply_privately(
+                        &channel,
+                        &msg.sender.id,
+                        MessageContent::plain(format!(
+                            "The !{} command is only available on Matrix.",
+                            cmd_name
+                        )),
+                    )
+                    .await?;
                     return Ok(());
                 }
                 // On Matrix, fall through — the caller should use handle_message directly
@@ -227,14 +626,80 @@ async fn handle_incoming_command(
 /// entry point shared by all platforms and the DISPATCH agent.
 ///
 /// Does NOT handle platform I/O (typing indicators, message sending).
+/// Usage is attributed to the bucket for `origin`, so internal setup prompts
+/// show up as overhead rather than conversation.
 pub async fn handle_text(
     content: &str,
     channel: &crate::session::Channel,
     session_store: &SessionStore,
     warm_manager: &SharedWarmSessionManager,
+    origin: InvocationOrigin,
+) -> Result<String> {
+    handle_text_with_tool_notices(content, channel, session_store, warm_manager, origin, None).await
+}
+
+/// [`handle_text`] that also reports each tool the agent starts.
+///
+/// A debug-mode notice (plain, html) goes out on `tool_notices` as soon as the
+/// tool starts, so callers can show progress while the response is still coming.
+pub async fn handle_text_with_tool_notices(
+    content: &str,
+    channel: &crate::session::Channel,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    origin: InvocationOrigin,
+    tool_notices: Option<tokio::sync::mpsc::UnboundedSender<(String, String)>>,
+) -> Result<String> {
+    run_text(
+        content,
+        channel,
+        session_store,
+        warm_manager,
+        origin,
+        tool_notices,
+        None,
+    )
+    .await
+}
+
+/// [`handle_text`] that also passes on the response text as the agent writes it.
+///
+/// Each chunk goes out on `text_chunks` as it arrives; the returned response is
+/// the finished text, after the same cleanup `handle_text` applies.
+pub async fn handle_text_streaming(
+    content: &str,
+    channel: &crate::session::Channel,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    origin: InvocationOrigin,
+    text_chunks: tokio::sync::mpsc::UnboundedSender<String>,
+) -> Result<String> {
+    run_text(
+        content,
+        channel,
+        session_store,
+        warm_manager,
+        origin,
+        None,
+        Some(text_chunks),
+    )
+    .await
+}
+
+async fn run_text(
+    content: &str,
+    channel: &crate::session::Channel,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    origin: InvocationOrigin,
+    tool_notices: Option<tokio::sync::mpsc::UnboundedSender<(String, String)>>,
+    text_chunks: Option<tokio::sync::mpsc::UnboundedSender<String>>,
 ) -> Result<String> {
     use gorp_agent::AgentEvent;
 
+    // One turn per channel at a time; a second prompt waits here for the first to finish
+    let _turn = crate::warm_session::acquire_turn(warm_manager, &channel.room_id).await;
+
     // Prepare session
     let (session_handle, session_id, is_new_session) =
         crate::warm_session::prepare_session_async(warm_manager, channel).await?;
@@ -246,9 +711,53 @@ pub async fn handle_text(
         }
     }
 
+    // Nobody is asked here, so with !network off web tools are simply refused
+    let _network_guard = if crate::warm_session::is_network_allowed(&channel.directory) {
+        None
+    } else {
+        let blocker = std::sync::Arc::new(crate::approvals::NetworkBlocker::new(None, None));
+        Some(crate::warm_session::set_tool_approver(&session_handle, blocker).await)
+    };
+
+    // A person's message may be the answer to a question the agent stopped to ask
+    let content = if origin == InvocationOrigin::User {
+        awaiting::continue_reply(session_store, &channel.channel_name, content)
+    } else {
+        content.to_string()
+    };
+
+    // Configured preprocessors (URL fetching and the like) add context; internal setup goes out as written
+    let content = if origin == InvocationOrigin::Internal {
+        content
+    } else {
+        let preprocessors = warm_manager.read().await.preprocessors();
+        preprocessors.run(content, channel).await
+    };
+
+    // The channel's length preference shapes every answer it will see; internal setup is exempt
+    let length = if origin == InvocationOrigin::Internal {
+        response_length::LengthSetting::default()
+    } else {
+        response_length::get_response_length(&channel.directory)
+    };
+    let prompt = response_length::apply_directive(&content, length);
+
+    // People asking the same thing of an unchanged workspace get the earlier answer (!cache on)
+    let cache = match origin {
+        InvocationOrigin::User => response_cache::lookup(session_store, channel, &prompt),
+        _ => response_cache::Lookup::Off,
+    };
+    let cache = match cache {
+        response_cache::Lookup::Hit(response) => {
+            return Ok(response_cache::cached_reply(&response));
+        }
+        response_cache::Lookup::Miss(miss) => Some(miss),
+        response_cache::Lookup::Off => None,
+    };
+
     // Send prompt and stream events
     let mut event_rx =
-        crate::warm_session::send_prompt_with_handle(&session_handle, &session_id, content)
+        crate::warm_session::send_prompt_with_handle(&session_handle, &session_id, &prompt, origin)
             .await?;
 
     let mut response_text = String::new();
@@ -257,9 +766,27 @@ pub async fn handle_text(
     while let Some(event) = event_rx.recv().await {
         match event {
             AgentEvent::Text(text) => {
+                if let Some(tx) = &text_chunks {
+                    // A closed receiver just means nobody is watching any more
+                    let _ = tx.send(text.clone());
+                }
                 response_text.push_str(&text);
             }
-            AgentEvent::Result { text, .. } => {
+            AgentEvent::Result { text, usage, .. } => {
+                if let Err(e) =
+                    session_store.record_usage(&channel.channel_name, origin, usage.as_ref())
+                {
+                    tracing::warn!(error = %e, "Failed to record channel usage");
+                }
+                if let Some(usage) = usage {
+                    metrics::record_claude_tokens(
+                        usage.input_tokens,
+                        usage.output_tokens,
+                        usage.cache_read_tokens.unwrap_or(0),
+                        usage.cache_write_tokens.unwrap_or(0),
+                        origin,
+                    );
+                }
                 if response_text.is_empty() {
                     response_text = text;
                 }
@@ -316,8 +843,12 @@ pub async fn handle_text(
             AgentEvent::SessionChanged { new_session_id } => {
                 session_id_from_event = Some(new_session_id);
             }
-            AgentEvent::ToolStart { name, .. } => {
+            AgentEvent::ToolStart { name, input, .. } => {
                 metrics::record_tool_used(&name);
+                if let Some(tx) = &tool_notices {
+                    // A closed receiver just means nobody is watching any more
+                    let _ = tx.send(helpers::format_tool_notice(&name, &input));
+                }
             }
             _ => {}
         }
@@ -338,10 +869,27 @@ pub async fn handle_text(
 
     // Strip XML function call blocks
     let response = crate::utils::strip_function_calls(&response_text);
+    let response = response_length::enforce_length(&response, length);
 
+    let (max_chars, save_full) = {
+        let mgr = warm_manager.read().await;
+        (mgr.max_response_chars(), mgr.save_truncated_responses())
+    };
+    let response = helpers::cap_response(response, &channel.directory, max_chars, save_full);
+    // Only a person can answer, so other origins just lose the marker
+    let (response, asked) = if origin == InvocationOrigin::User {
+        awaiting::settle_response(session_store, &channel.channel_name, &response)
+    } else {
+        awaiting::split_marker(&response)
+    };
+    // A clarifying question is about this conversation, not an answer worth reusing
+    if let (Some(miss), false) = (cache, asked) {
+        response_cache::remember(session_store, channel, miss, &response);
+    }
     Ok(response)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_message(
     room: Room,
     event: matrix_sdk::ruma::events::room::message::OriginalSyncRoomMessageEvent,
@@ -350,6 +898,14 @@ pub async fn handle_message(
     session_store: SessionStore,
     scheduler_store: SchedulerStore,
     warm_manager: SharedWarmSessionManager,
+    dedup: &DedupCache,
+    edits: &EditTracker,
+    responses: &ResponseTracker,
+    approvals: &PendingApprovals,
+    rate_limiter: &RateLimiter,
+    transcriber: Option<&dyn Transcriber>,
+    platforms: &SharedPlatformRegistry,
+    bus: &MessageBus,
 ) -> Result<()> {
     let start_time = std::time::Instant::now();
 
@@ -358,6 +914,12 @@ pub async fn handle_message(
         return Ok(());
     }
 
+    // A sync restart can redeliver events the main loop has already forgotten
+    if !dedup.check_and_mark("matrix", event.event_id.as_str()) {
+        tracing::debug!(event_id = %event.event_id, "Skipping duplicate Matrix event");
+        return Ok(());
+    }
+
     // Check if this is a DM (direct message)
     let is_dm = room.is_direct().await.unwrap_or(false);
 
@@ -374,21 +936,199 @@ pub async fn handle_message(
     }
 
     // Check whitelist
-    let allowed_users = config.allowed_users_set();
-    if !allowed_users.contains(sender) {
+    if !config.is_user_allowed("matrix", sender) {
         tracing::debug!(sender, "Ignoring message from unauthorized user");
         return Ok(());
     }
 
-    // Safe preview generation (respects UTF-8 boundaries)
-    let message_preview: String = body.chars().take(50).collect();
-    tracing::info!(sender, room_id = %room.room_id(), message_preview, "Processing message");
+    // The receipt shows the bot has the message even when the agent takes a while
+    if let Some(matrix) = platforms.read().await.get("matrix") {
+        mark_seen(matrix, room.room_id().as_str(), event.event_id.as_str()).await;
+    }
+
+    // Edits (m.replace) rewrite a prompt still waiting to go out, or come back as a
+    // correction that the chat path picks up under this event's ID
+    if let Some(Relation::Replacement(replacement)) = &event.content.relates_to {
+        let new_body = replacement.new_content.msgtype.body();
+        match edits.apply_edit("matrix", replacement.event_id.as_str(), new_body) {
+            EditOutcome::FollowUp(correction) => {
+                let Some(channel) = session_store.get_by_room(room.room_id().as_str())? else {
+                    return Ok(());
+                };
+                edits.queue("matrix", event.event_id.as_str(), &correction);
+                metrics::record_message_received("chat");
+                return chat::process_chat_message(
+                    room,
+                    event,
+                    client,
+                    channel,
+                    session_store,
+                    warm_manager,
+                    edits,
+                    responses,
+                    approvals,
+                    &config.attachments,
+                    &config.tool_approval,
+                    &config.signing,
+                    transcriber,
+                )
+                .await;
+            }
+            outcome => {
+                tracing::debug!(
+                    original = %replacement.event_id,
+                    ?outcome,
+                    "Applied Matrix message edit"
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    tracing::info!(
+        sender,
+        room_id = %room.room_id(),
+        message = %loggable_content(body),
+        "Processing message"
+    );
+
+    // A yes or no while the agent waits on a risky tool call answers it
+    let room_id = room.room_id().as_str();
+    if approvals.is_waiting(room_id) {
+        if let Some(approved) = parse_answer(body) {
+            let reply = if !config.tool_approval.can_approve(sender) {
+                "⛔ Only approvers can answer tool requests here."
+            } else if approvals.answer(room_id, approved) {
+                tracing::info!(sender, room_id, approved, "Answered tool approval");
+                if approved {
+                    "✅ Approved."
+                } else {
+                    "🚫 Denied."
+                }
+            } else {
+                "That request already timed out."
+            };
+            room.send(RoomMessageEventContent::text_plain(reply))
+                .await?;
+            return Ok(());
+        }
+    }
 
     // Parse message using gorp-core command parsing
-    let parse_result = parse_message(body, "!claude");
+    let catalog = config.command_catalog();
+    let prefixes = config.command_prefixes("matrix");
+    let parse_result = parse_message_with_aliases(body, &prefixes, &catalog);
+
+    if spends_rate_limit(&parse_result) {
+        if let RateDecision::Limited {
+            retry_after,
+            notify,
+        } = rate_limiter.check("matrix", sender)
+        {
+            metrics::record_rate_limited("matrix");
+            tracing::info!(
+                sender,
+                retry_after_secs = retry_after.as_secs_f64(),
+                "Rate limited Matrix message"
+            );
+            if notify {
+                room.send(RoomMessageEventContent::text_plain(limited_notice(
+                    retry_after,
+                )))
+                .await?;
+            }
+            return Ok(());
+        }
+    }
 
     if let ParseResult::Command(cmd) = parse_result {
         metrics::record_message_received("command");
+
+        // !send submits the sender's held draft as if it had just been typed
+        if cmd.name == "send" {
+            metrics::record_command("send");
+            let Some(draft) = session_store.take_draft(room.room_id().as_str(), sender)? else {
+                room.send(RoomMessageEventContent::text_plain(
+                    "No queued draft to send.",
+                ))
+                .await?;
+                return Ok(());
+            };
+            let Some(channel) = session_store.get_by_room(room.room_id().as_str())? else {
+                room.send(RoomMessageEventContent::text_plain(
+                    "No channel attached to this room.",
+                ))
+                .await?;
+                return Ok(());
+            };
+            edits.queue("matrix", event.event_id.as_str(), &draft.body);
+            metrics::record_message_received("chat");
+            return chat::process_chat_message(
+                room,
+                event,
+                client,
+                channel,
+                session_store,
+                warm_manager,
+                edits,
+                responses,
+                approvals,
+                &config.attachments,
+                &config.tool_approval,
+                &config.signing,
+                transcriber,
+            )
+            .await;
+        }
+
+        // !template run sends a saved prompt the same way
+        if prompt_templates::is_run(&cmd) {
+            metrics::record_command("template");
+            let run = prompt_templates::resolve_run(&session_store, room.room_id().as_str(), &cmd)?;
+            let (channel, prompt) = match run {
+                TemplateRun::Prompt { channel, prompt } => (channel, prompt),
+                TemplateRun::Reply(text) => {
+                    room.send(RoomMessageEventContent::text_plain(text)).await?;
+                    return Ok(());
+                }
+            };
+            edits.queue("matrix", event.event_id.as_str(), &prompt);
+            metrics::record_message_received("chat");
+            return chat::process_chat_message(
+                room,
+                event,
+                client,
+                channel,
+                session_store,
+                warm_manager,
+                edits,
+                responses,
+                approvals,
+                &config.attachments,
+                &config.tool_approval,
+                &config.signing,
+                transcriber,
+            )
+            .await;
+        }
+
+        // !pin sent as a reply pins that message rather than the last response
+        if cmd.name == "pin" && !is_dm {
+            if let Some(Relation::Reply { in_reply_to }) = &event.content.relates_to {
+                let result = matrix_commands::pin_replied_message(
+                    &room,
+                    &in_reply_to.event_id,
+                    sender,
+                    cmd.raw_args.trim(),
+                    &session_store,
+                )
+                .await;
+                let duration = start_time.elapsed().as_secs_f64();
+                metrics::record_message_processing_duration(duration);
+                return result;
+            }
+        }
+
         let result = handle_command(
             room,
             &cmd,
@@ -399,6 +1139,8 @@ pub async fn handle_message(
             is_dm,
             &config,
             &warm_manager,
+            platforms,
+            bus,
         )
         .await;
         let duration = start_time.elapsed().as_secs_f64();
@@ -411,6 +1153,15 @@ pub async fn handle_message(
         return Ok(());
     }
 
+    // A pending `!template save <name>` takes this message as the template body
+    if let Some(reply) =
+        prompt_templates::capture_body(&session_store, room.room_id().as_str(), sender, body)?
+    {
+        room.send(RoomMessageEventContent::text_plain(reply))
+            .await?;
+        return Ok(());
+    }
+
     // Check if this is the DISPATCH control plane room (only in DMs)
     if is_dm {
         // Check for existing DISPATCH channel
@@ -432,7 +1183,8 @@ pub async fn handle_message(
 
         // Check for DISPATCH activation command
         let body_lower = body.to_lowercase();
-        if body_lower.starts_with("!dispatch") || body_lower == "dispatch" {
+        let activation = format!("{}dispatch", config.command_prefix("matrix"));
+        if body_lower.starts_with(&activation) || body_lower == "dispatch" {
             // Create DISPATCH channel and route to handler
             tracing::info!(room_id = %room.room_id(), "DISPATCH channel activated via command");
             metrics::record_message_received("dispatch");
@@ -495,7 +1247,10 @@ pub async fn handle_message(
                 }
 
                 // Check if channel already exists
-                if session_store.get_by_name(&channel_name)?.is_some() {
+                if session_store
+                    .get_by_name_on("matrix", &channel_name)?
+                    .is_some()
+                {
                     let msg = format!(
                         "A channel named `{}` already exists! Try a different name.",
                         channel_name
@@ -505,7 +1260,11 @@ pub async fn handle_message(
                 }
 
                 // Create Matrix room
-                let room_prefix = config.matrix.as_ref().map(|m| m.room_prefix.as_str()).unwrap_or("Claude");
+                let room_prefix = config
+                    .matrix
+                    .as_ref()
+                    .map(|m| m.room_prefix.as_str())
+                    .unwrap_or("Claude");
                 let room_name = format!("{}: {}", room_prefix, channel_name);
                 let new_room_id = match matrix_client::create_room(&client, &room_name).await {
                     Ok(id) => id,
@@ -523,16 +1282,21 @@ pub async fn handle_message(
                 }
 
                 // Create channel in database (this also creates the directory)
-                let channel =
-                    match session_store.create_channel(&channel_name, new_room_id.as_str()) {
-                        Ok(c) => c,
-                        Err(e) => {
-                            let msg = format!("Failed to create channel: {}", e);
-                            room.send(RoomMessageEventContent::text_plain(&msg)).await?;
-                            return Ok(());
-                        }
-                    };
+                let channel = match session_store.create_channel_on(
+                    "matrix",
+                    &channel_name,
+                    new_room_id.as_str(),
+                ) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        let msg = format!("Failed to create channel: {}", e);
+                        room.send(RoomMessageEventContent::text_plain(&msg)).await?;
+                        return Ok(());
+                    }
+                };
                 metrics::increment_active_channels();
+                matrix_client::apply_channel_topic(&client, &session_store, &new_room_id, &channel)
+                    .await;
 
                 tracing::info!(
                     channel = %channel_name,
@@ -589,8 +1353,41 @@ pub async fn handle_message(
         }
     };
 
+    // Long messages from users with the send guard on wait as a draft for !send
+    if matches!(event.content.msgtype, MessageType::Text(_)) {
+        if let Some(notice) = drafts::hold_if_guarded(
+            &session_store,
+            &config.send_guard,
+            "matrix",
+            room.room_id().as_str(),
+            sender,
+            body,
+        )? {
+            let html = markdown_to_html(&notice);
+            room.send(RoomMessageEventContent::text_html(&notice, &html))
+                .await?;
+            return Ok(());
+        }
+    }
+
     // Delegate to chat module for actual Claude invocation and response streaming
-    chat::process_chat_message(room, event, client, channel, session_store, warm_manager).await
+    edits.queue("matrix", event.event_id.as_str(), body);
+    chat::process_chat_message(
+        room,
+        event,
+        client,
+        channel,
+        session_store,
+        warm_manager,
+        edits,
+        responses,
+        approvals,
+        &config.attachments,
+        &config.tool_approval,
+        &config.signing,
+        transcriber,
+    )
+    .await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -604,6 +1401,8 @@ async fn handle_command(
     is_dm: bool,
     config: &Config,
     warm_manager: &SharedWarmSessionManager,
+    platforms: &SharedPlatformRegistry,
+    bus: &MessageBus,
 ) -> Result<()> {
     // Wrap Room in MatrixChannel for testable command handler
     let matrix_channel = MatrixChannel::new(room.clone(), client.clone());
@@ -652,6 +1451,8 @@ async fn handle_command(
         is_dm,
         config,
         warm_manager,
+        platforms,
+        bus,
     )
     .await
 }
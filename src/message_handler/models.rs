@@ -0,0 +1,236 @@
+// ABOUTME: !model and !models: pick the model a channel's agent runs on and list what its backend offers.
+// ABOUTME: Only backends in MODEL_BACKENDS take a model; a change restarts the agent on the next message.
+
+use anyhow::Result;
+use gorp_agent::ModelInfo;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::SessionStore,
+    warm_session::{
+        is_valid_model_name, list_models_with_handle, read_channel_model, write_channel_model,
+        SharedWarmSessionManager, WarmSessionManager, MODEL_BACKENDS,
+    },
+};
+
+use super::commands::channel_for_command;
+
+/// How long !models waits for the backend; a direct-CLI backend answers between prompts
+const MODELS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// `!model [set <name>|reset]`: show or change this channel's model
+pub(crate) async fn handle_model_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "model", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let (default_backend, default_model) = {
+        let mgr = warm_manager.read().await;
+        (mgr.backend_type().to_string(), mgr.config().model)
+    };
+    let backend = ch.backend_type.clone().unwrap_or(default_backend);
+    let takes_model = MODEL_BACKENDS.contains(&backend.as_str());
+    let model_backends = MODEL_BACKENDS.join(" or ");
+
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        None | Some("show") => {
+            let current = read_channel_model(&ch.directory);
+            let default_model =
+                default_model.unwrap_or_else(|| t(locale, "model.backend_default_label"));
+            let mut text = tf(
+                locale,
+                "model.status",
+                &[
+                    ("channel", &ch.channel_name),
+                    (
+                        "model",
+                        &current.unwrap_or_else(|| t(locale, "model.default")),
+                    ),
+                    ("default", &default_model),
+                    ("backend", &backend),
+                ],
+            );
+            if !takes_model {
+                text.push_str(&tf(
+                    locale,
+                    "model.no_model_note",
+                    &[("backends", &model_backends)],
+                ));
+            }
+            text.push_str(&t(locale, "model.commands"));
+            text
+        }
+        Some("set") => match args.get(1) {
+            None => t(locale, "model.usage_set"),
+            Some(model) if !is_valid_model_name(model) => {
+                tf(locale, "model.invalid", &[("model", model)])
+            }
+            Some(_) if !takes_model => tf(
+                locale,
+                "model.backend_takes_none",
+                &[("backend", &backend), ("backends", &model_backends)],
+            ),
+            Some(model) => {
+                write_channel_model(&ch.directory, Some(model))?;
+                warm_manager
+                    .write()
+                    .await
+                    .invalidate_session(&ch.channel_name);
+                tracing::info!(channel = %ch.channel_name, model = %model, sender, "Model changed via command");
+                tf(
+                    locale,
+                    "model.set",
+                    &[
+                        ("channel", &ch.channel_name),
+                        ("model", model),
+                        ("backend", &backend),
+                    ],
+                )
+            }
+        },
+        Some("reset") | Some("default") => {
+            write_channel_model(&ch.directory, None)?;
+            warm_manager
+                .write()
+                .await
+                .invalidate_session(&ch.channel_name);
+            tracing::info!(channel = %ch.channel_name, sender, "Model reset via command");
+            let default_model = default_model.unwrap_or_else(|| t(locale, "model.backend_default"));
+            tf(
+                locale,
+                "model.reset",
+                &[("channel", &ch.channel_name), ("model", &default_model)],
+            )
+        }
+        Some(_) => t(locale, "model.usage"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// `!models`: list the models the channel's backend offers, the active one marked
+pub(crate) async fn handle_models_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "models", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let (default_backend, warm_config, registry, warm_session) = {
+        let mgr = warm_manager.read().await;
+        (
+            mgr.backend_type().to_string(),
+            mgr.config(),
+            mgr.registry(),
+            mgr.get_existing_session(&ch.channel_name),
+        )
+    };
+    let backend = ch.backend_type.clone().unwrap_or(default_backend);
+    // Same precedence as session creation: !model, then mux's configured model
+    let active = read_channel_model(&ch.directory)
+        .or_else(|| warm_config.model.clone().filter(|_| backend == "mux"));
+
+    // Ask the channel's running backend if it has one instead of starting another
+    let listing = async {
+        match warm_session {
+            Some(session) => list_models_with_handle(&session).await,
+            None => {
+                WarmSessionManager::create_ephemeral_handle(
+                    &registry,
+                    &ch.directory,
+                    &warm_config,
+                    ch.backend_type.as_deref(),
+                    true,
+                )?
+                .list_models()
+                .await
+            }
+        }
+    };
+    let reply = match tokio::time::timeout(MODELS_TIMEOUT, listing).await {
+        Ok(Ok(models)) => format_model_list(&backend, &models, active.as_deref(), locale),
+        Ok(Err(e)) => tf(
+            locale,
+            "models.failed",
+            &[("backend", &backend), ("error", &e.to_string())],
+        ),
+        Err(_) => tf(
+            locale,
+            "models.timed_out",
+            &[
+                ("backend", &backend),
+                ("secs", &MODELS_TIMEOUT.as_secs().to_string()),
+            ],
+        ),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// The `!models` listing: one model per line with its context window,
+/// the channel's active model marked
+fn format_model_list(
+    backend: &str,
+    models: &[ModelInfo],
+    active: Option<&str>,
+    locale: &str,
+) -> String {
+    let mut text = tf(locale, "models.header", &[("backend", backend)]);
+    if models.is_empty() {
+        text.push_str(&t(locale, "models.none"));
+    }
+    for model in models {
+        let marker = if active == Some(model.id.as_str()) {
+            "▶"
+        } else {
+            "•"
+        };
+        text.push_str(&tf(
+            locale,
+            "models.entry",
+            &[
+                ("marker", marker),
+                ("model", &model.id),
+                ("context", &model.context_label()),
+            ],
+        ));
+    }
+    match active {
+        Some(active) if !models.iter().any(|m| m.id == active) => {
+            text.push_str(&tf(locale, "models.active_unlisted", &[("model", active)]))
+        }
+        Some(_) => text.push_str(&t(locale, "models.active_marker")),
+        None => text.push_str(&t(locale, "models.active_default")),
+    }
+    text.push_str(&t(locale, "models.switch"));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_model_list_flags_unlisted_active_model() {
+        let models = vec![ModelInfo::new("claude-sonnet-4-5", 200_000)];
+        let text = format_model_list("direct", &models, Some("claude-opus-4-1"), "en");
+        assert!(text.contains("• claude-sonnet-4-5 (200K context)"));
+        assert!(text.contains("Active: claude-opus-4-1 (not in the list above)"));
+
+        let text = format_model_list("direct", &[], None, "en");
+        assert!(text.contains("didn't report any models"));
+    }
+}
@@ -0,0 +1,64 @@
+// ABOUTME: !network: whether a channel's agent may use web tools.
+// ABOUTME: The flag lives in the workspace; a change restarts the agent so its tool list is rebuilt.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::SessionStore,
+    warm_session::{is_network_allowed, set_network_allowed, SharedWarmSessionManager},
+};
+
+use super::commands::channel_for_command;
+
+/// `!network [on|off]`: show or change whether the agent may reach the network
+pub(crate) async fn handle_network_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "network", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let subcommand = args.first().map(|s| s.to_lowercase());
+    let reply = match subcommand.as_deref() {
+        None | Some("show") => {
+            if is_network_allowed(&ch.directory) {
+                tf(
+                    locale,
+                    "network.status_on",
+                    &[("channel", &ch.channel_name)],
+                )
+            } else {
+                tf(
+                    locale,
+                    "network.status_off",
+                    &[("channel", &ch.channel_name)],
+                )
+            }
+        }
+        Some(state @ ("on" | "off")) => {
+            let allowed = state == "on";
+            set_network_allowed(&ch.directory, allowed)?;
+            warm_manager
+                .write()
+                .await
+                .invalidate_session(&ch.channel_name);
+            tracing::info!(channel = %ch.channel_name, allowed, sender, "Network access changed via command");
+            if allowed {
+                tf(locale, "network.on", &[("channel", &ch.channel_name)])
+            } else {
+                tf(locale, "network.off", &[("channel", &ch.channel_name)])
+            }
+        }
+        Some(_) => t(locale, "network.usage"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
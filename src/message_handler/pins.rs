@@ -0,0 +1,344 @@
+// ABOUTME: Pinned responses saved per channel in .gorp/pins.md for later reference.
+// ABOUTME: Appends timestamped pin entries for !pin, parses them back for !pins, and finds the last logged response.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::SessionStore,
+    utils::{chunk_message, MAX_CHUNK_SIZE},
+};
+
+use super::commands::channel_for_command;
+use super::helpers::truncate_str;
+
+/// Marker that starts every pin entry in pins.md
+const PIN_HEADER: &str = "## 📌 ";
+/// Timestamp format used in pin headers
+const PIN_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// A saved response
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pin {
+    pub pinned_at: DateTime<Utc>,
+    pub pinned_by: String,
+    /// Where the pin came from and any note the user attached
+    pub context: Option<String>,
+    pub content: String,
+}
+
+fn pins_path(channel_dir: &str) -> PathBuf {
+    Path::new(channel_dir).join(".gorp").join("pins.md")
+}
+
+/// Append a pin to the channel's .gorp/pins.md
+pub fn append_pin(channel_dir: &str, pin: &Pin) -> Result<()> {
+    let path = pins_path(channel_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut entry = format!(
+        "{}{}\nPinned by: {}\n",
+        PIN_HEADER,
+        pin.pinned_at.format(PIN_TIME_FORMAT),
+        pin.pinned_by
+    );
+    if let Some(context) = &pin.context {
+        // Keep context on one line so the entry parses back cleanly
+        entry.push_str(&format!("Context: {}\n", context.replace('\n', " ")));
+    }
+    entry.push_str(&format!("\n{}\n\n---\n\n", pin.content.trim()));
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(entry.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Read all pins for a channel, oldest first
+pub fn list_pins(channel_dir: &str) -> Result<Vec<Pin>> {
+    let path = pins_path(channel_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(parse_pins(&text))
+}
+
+fn parse_pins(text: &str) -> Vec<Pin> {
+    let mut pins = Vec::new();
+    let mut lines = text.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let Some(stamp) = line.strip_prefix(PIN_HEADER) else {
+            continue;
+        };
+        let Ok(pinned_at) = NaiveDateTime::parse_from_str(stamp.trim(), PIN_TIME_FORMAT) else {
+            tracing::warn!(header = %line, "Skipping pin with unreadable timestamp");
+            continue;
+        };
+
+        let mut pinned_by = String::new();
+        let mut context = None;
+        let mut body = Vec::new();
+        let mut in_body = false;
+
+        while let Some(next) = lines.peek() {
+            if next.starts_with(PIN_HEADER) {
+                break;
+            }
+            let next = lines.next().unwrap_or_default();
+            if in_body {
+                body.push(next);
+            } else if let Some(by) = next.strip_prefix("Pinned by: ") {
+                pinned_by = by.to_string();
+            } else if let Some(ctx) = next.strip_prefix("Context: ") {
+                context = Some(ctx.to_string());
+            } else if next.trim().is_empty() {
+                in_body = true;
+            }
+        }
+
+        // Drop the entry separator
+        while body.last().is_some_and(|l| l.trim().is_empty()) {
+            body.pop();
+        }
+        if body.last().is_some_and(|l| l.trim() == "---") {
+            body.pop();
+        }
+
+        pins.push(Pin {
+            pinned_at: pinned_at.and_utc(),
+            pinned_by,
+            context,
+            content: body.join("\n").trim().to_string(),
+        });
+    }
+
+    pins
+}
+
+/// Find the most recent bot response in .gorp/matrix-messages.jsonl.
+/// Chunked responses are reassembled into a single text.
+pub fn last_logged_response(channel_dir: &str) -> Result<Option<String>> {
+    let path = Path::new(channel_dir)
+        .join(".gorp")
+        .join("matrix-messages.jsonl");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut last: Option<Vec<String>> = None;
+    for line in text.lines() {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if entry["message_type"] != "response" {
+            continue;
+        }
+        let content = entry["content"].as_str().unwrap_or_default().to_string();
+        match entry["chunk_index"].as_u64() {
+            // Later chunks extend the response started by chunk 0
+            Some(index) if index > 0 => match last.as_mut() {
+                Some(chunks) => chunks.push(content),
+                None => last = Some(vec![content]),
+            },
+            _ => last = Some(vec![content]),
+        }
+    }
+
+    Ok(last.map(|chunks| chunks.join("\n\n")))
+}
+
+/// `!pin [note]`: pin the last response logged in this channel
+pub(crate) async fn handle_pin_command<C: ChatChannel>(
+    channel: &C,
+    raw_args: &str,
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "pin", is_dm, locale).await? else {
+        return Ok(());
+    };
+
+    let Some(response) = last_logged_response(&ch.directory)? else {
+        return channel
+            .send(MessageContent::plain(t(locale, "pins.nothing_to_pin")))
+            .await;
+    };
+
+    let note = raw_args.trim();
+    let context = if note.is_empty() {
+        t(locale, "pins.last_response")
+    } else {
+        tf(locale, "pins.last_response_note", &[("note", note)])
+    };
+    append_pin(
+        &ch.directory,
+        &Pin {
+            pinned_at: Utc::now(),
+            pinned_by: sender.to_string(),
+            context: Some(context),
+            content: response.clone(),
+        },
+    )?;
+    tracing::info!(channel = %ch.channel_name, sender, "Pinned last response");
+
+    let reply = tf(
+        locale,
+        "pins.pinned",
+        &[(
+            "preview",
+            &truncate_str(response.lines().next().unwrap_or_default(), 80),
+        )],
+    );
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// `!pins`: list this channel's pins, newest last
+pub(crate) async fn handle_pins_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "pins", is_dm, locale).await? else {
+        return Ok(());
+    };
+
+    let pins = list_pins(&ch.directory)?;
+    if pins.is_empty() {
+        return channel
+            .send(MessageContent::plain(t(locale, "pins.none")))
+            .await;
+    }
+
+    let mut msg = tf(
+        locale,
+        "pins.header",
+        &[
+            ("channel", &ch.channel_name),
+            ("count", &pins.len().to_string()),
+        ],
+    );
+    for (i, pin) in pins.iter().enumerate() {
+        msg.push_str(&format!(
+            "{}. [{}] {}\n",
+            i + 1,
+            pin.pinned_at.format("%Y-%m-%d %H:%M"),
+            truncate_str(pin.content.lines().next().unwrap_or_default(), 80)
+        ));
+        if let Some(context) = &pin.context {
+            msg.push_str(&format!("   {}\n", context));
+        }
+    }
+    msg.push_str(&t(locale, "pins.footer"));
+
+    for chunk in chunk_message(&msg, MAX_CHUNK_SIZE) {
+        channel.send(MessageContent::plain(chunk)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn pin(minute: u32, content: &str, context: Option<&str>) -> Pin {
+        Pin {
+            pinned_at: Utc.with_ymd_and_hms(2026, 3, 1, 9, minute, 0).unwrap(),
+            pinned_by: "@alice:example.com".to_string(),
+            context: context.map(String::from),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_append_and_list_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+
+        let first = pin(
+            0,
+            "Use `cargo nextest` for faster runs.",
+            Some("Last response"),
+        );
+        let second = pin(
+            5,
+            "## Steps\n\n1. Build\n2. Ship\n\n---\n\nDone.",
+            Some("Reply to $abc: release checklist"),
+        );
+        append_pin(dir, &first).unwrap();
+        append_pin(dir, &second).unwrap();
+
+        let pins = list_pins(dir).unwrap();
+        assert_eq!(pins, vec![first, second]);
+
+        let raw = std::fs::read_to_string(tmp.path().join(".gorp/pins.md")).unwrap();
+        assert!(raw.contains("## 📌 2026-03-01 09:05:00 UTC"));
+        assert!(raw.contains("Pinned by: @alice:example.com"));
+    }
+
+    #[test]
+    fn test_list_pins_empty_without_file() {
+        let tmp = TempDir::new().unwrap();
+        assert!(list_pins(tmp.path().to_str().unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pin_without_context() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        append_pin(dir, &pin(1, "plain answer", None)).unwrap();
+
+        let pins = list_pins(dir).unwrap();
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].context, None);
+        assert_eq!(pins[0].content, "plain answer");
+    }
+
+    #[test]
+    fn test_last_logged_response_reassembles_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let gorp = tmp.path().join(".gorp");
+        std::fs::create_dir_all(&gorp).unwrap();
+        let log = [
+            r#"{"message_type":"response","content":"older answer"}"#,
+            r#"{"message_type":"tool_notification","content":"🔧 Read"}"#,
+            r#"{"message_type":"response","content":"part one","chunk_index":0,"total_chunks":2}"#,
+            r#"{"message_type":"response","content":"part two","chunk_index":1,"total_chunks":2}"#,
+        ]
+        .join("\n");
+        std::fs::write(gorp.join("matrix-messages.jsonl"), log).unwrap();
+
+        let last = last_logged_response(tmp.path().to_str().unwrap()).unwrap();
+        assert_eq!(last.as_deref(), Some("part one\n\npart two"));
+    }
+
+    #[test]
+    fn test_last_logged_response_none_without_log() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(
+            last_logged_response(tmp.path().to_str().unwrap()).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,169 @@
+// ABOUTME: Message-path side of !template: running a saved template as a prompt and capturing bodies.
+// ABOUTME: Also the save/list/delete subcommands, which are ordinary commands.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::commands::Command;
+use crate::i18n::{t, tf};
+use crate::session::{Channel, SessionStore};
+use crate::templates::{
+    render, rest_after_words, validate_name, ARGS_PLACEHOLDER, SCHEDULE_PREFIX,
+};
+
+use super::commands::channel_for_command;
+use super::helpers::truncate_str;
+
+/// What `!template run <name> [extra context]` turned into
+pub enum TemplateRun {
+    /// The rendered prompt, to send to the channel's agent like a typed message
+    Prompt { channel: Channel, prompt: String },
+    /// Nothing to run; tell the user why
+    Reply(String),
+}
+
+/// Whether a parsed command is `!template run ...`
+pub fn is_run(cmd: &Command) -> bool {
+    cmd.name == "template"
+        && cmd
+            .args
+            .first()
+            .is_some_and(|sub| sub.eq_ignore_ascii_case("run"))
+}
+
+/// Resolve `!template run` in `room_id` to the prompt it should send
+pub fn resolve_run(
+    session_store: &SessionStore,
+    room_id: &str,
+    cmd: &Command,
+) -> Result<TemplateRun> {
+    let Some(name) = cmd.args.get(1) else {
+        return Ok(TemplateRun::Reply(
+            "Usage: !template run <name> [extra context]".to_string(),
+        ));
+    };
+    let Some(channel) = session_store.get_by_room(room_id)? else {
+        return Ok(TemplateRun::Reply(
+            "❌ Templates only work in channel rooms.".to_string(),
+        ));
+    };
+    let Some(template) = session_store.get_template(&channel.channel_name, name)? else {
+        return Ok(TemplateRun::Reply(format!(
+            "❌ No template named '{}' in {}. See !template list.",
+            name, channel.channel_name
+        )));
+    };
+
+    let prompt = render(&template.body, rest_after_words(&cmd.raw_args, 2));
+    if prompt.is_empty() {
+        return Ok(TemplateRun::Reply(format!(
+            "❌ Template '{}' needs extra context: !template run {} <text>",
+            name, name
+        )));
+    }
+    tracing::info!(channel = %channel.channel_name, template = %name, "Running prompt template");
+    Ok(TemplateRun::Prompt { channel, prompt })
+}
+
+/// Save `body` as the template a pending `!template save <name>` is waiting for.
+/// Returns the confirmation to send, or None if the sender had no capture pending.
+pub fn capture_body(
+    session_store: &SessionStore,
+    room_id: &str,
+    sender: &str,
+    body: &str,
+) -> Result<Option<String>> {
+    let Some(name) = session_store.take_template_capture(room_id, sender)? else {
+        return Ok(None);
+    };
+    let Some(channel) = session_store.get_by_room(room_id)? else {
+        return Ok(Some(
+            "No channel attached to this room, so the template wasn't saved.".to_string(),
+        ));
+    };
+    let body = body.trim();
+    if body.is_empty() {
+        return Ok(Some(format!(
+            "Template '{}' not saved: it was empty.",
+            name
+        )));
+    }
+
+    session_store.save_template(&channel.channel_name, &name, body, sender)?;
+    tracing::info!(channel = %channel.channel_name, template = %name, "Saved prompt template");
+    Ok(Some(format!(
+        "💾 Saved template '{}'. Run it with !template run {}",
+        name, name
+    )))
+}
+
+/// `!template save|list|delete`: the bookkeeping subcommands. `!template run` is
+/// handled on the message path, which sends the rendered prompt to the agent.
+pub(crate) async fn handle_template_command<C: ChatChannel>(
+    channel: &C,
+    cmd: &Command,
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "template", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let subcommand = cmd.args.first().map(|s| s.to_lowercase());
+    let reply = match (subcommand.as_deref(), cmd.args.get(1)) {
+        (Some("list"), _) => {
+            let saved = session_store.list_templates(&ch.channel_name)?;
+            if saved.is_empty() {
+                t(locale, "template.none")
+            } else {
+                let mut text = tf(locale, "template.list", &[("channel", &ch.channel_name)]);
+                for template in &saved {
+                    text.push_str(&format!(
+                        "\n• {} - {}",
+                        template.name,
+                        truncate_str(template.body.lines().next().unwrap_or_default(), 80)
+                    ));
+                }
+                text
+            }
+        }
+        (Some("save"), Some(name)) => match validate_name(name) {
+            Err(e) => tf(locale, "command.failed", &[("error", &e.to_string())]),
+            Ok(()) => {
+                let body = rest_after_words(&cmd.raw_args, 2);
+                if body.is_empty() {
+                    session_store.set_template_capture(
+                        channel.id(),
+                        sender,
+                        Some(name.as_str()),
+                    )?;
+                    tf(locale, "template.send_body", &[("name", name)])
+                } else {
+                    session_store.set_template_capture(channel.id(), sender, None)?;
+                    session_store.save_template(&ch.channel_name, name, body, sender)?;
+                    tracing::info!(channel = %ch.channel_name, template = %name, sender, "Saved prompt template");
+                    tf(locale, "template.saved", &[("name", name)])
+                }
+            }
+        },
+        (Some("delete"), Some(name)) => {
+            if session_store.delete_template(&ch.channel_name, name)? {
+                tf(locale, "template.deleted", &[("name", name)])
+            } else {
+                tf(locale, "template.not_found", &[("name", name)])
+            }
+        }
+        _ => tf(
+            locale,
+            "template.usage",
+            &[
+                ("placeholder", ARGS_PLACEHOLDER),
+                ("prefix", SCHEDULE_PREFIX),
+            ],
+        ),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
@@ -0,0 +1,171 @@
+// ABOUTME: !repo: check a git repo out into a channel's workspace and keep it in sync.
+// ABOUTME: The setting lives in .gorp/git-repo.json; failed syncs keep it so the next session retries.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    git_seed,
+    i18n::{t, tf},
+    session::SessionStore,
+};
+
+use super::commands::{channel_for_command, reply_privately};
+
+const REPO_SET_USAGE: &str =
+    "!repo set <url> [--branch <name>] [--dir <name>] [--key <ssh key path>] [--pull-on-start]";
+
+/// `!repo [set <url> ...|sync|clear]`: show, set, sync or forget this channel's git repo
+pub(crate) async fn handle_repo_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "repo", is_dm, locale).await? else {
+        return Ok(());
+    };
+
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        None | Some("show") => match git_seed::read_git_repo(&ch.directory) {
+            None => tf(locale, "repo.none", &[("channel", &ch.channel_name)]),
+            Some(repo) => {
+                let checked_out = repo.checkout_path(&ch.directory).join(".git").exists();
+                tf(
+                    locale,
+                    "repo.status",
+                    &[
+                        ("channel", &ch.channel_name),
+                        ("url", &repo.url),
+                        (
+                            "branch",
+                            &repo
+                                .branch
+                                .clone()
+                                .unwrap_or_else(|| t(locale, "repo.remote_default")),
+                        ),
+                        ("dir", &repo.checkout_dir()),
+                        (
+                            "checkout",
+                            &if checked_out {
+                                t(locale, "repo.present")
+                            } else {
+                                t(locale, "repo.not_cloned")
+                            },
+                        ),
+                        (
+                            "pull",
+                            &if repo.pull_on_session_start {
+                                t(locale, "repo.on")
+                            } else {
+                                t(locale, "repo.off")
+                            },
+                        ),
+                        (
+                            "key",
+                            &repo
+                                .ssh_key_path
+                                .clone()
+                                .unwrap_or_else(|| t(locale, "repo.ssh_default")),
+                        ),
+                    ],
+                )
+            }
+        },
+        Some("set") => {
+            let repo = match parse_repo_args(&args[1..], locale) {
+                Ok(repo) => repo,
+                Err(message) => {
+                    let reply = tf(
+                        locale,
+                        "repo.invalid",
+                        &[("error", &message), ("usage", REPO_SET_USAGE)],
+                    );
+                    return channel.send(MessageContent::plain(reply)).await;
+                }
+            };
+            git_seed::write_git_repo(&ch.directory, Some(&repo))?;
+            tracing::info!(channel = %ch.channel_name, url = %repo.url, sender, "Git repo set via command");
+            let result = git_seed::sync(&ch.directory, &repo).await;
+            repo_sync_reply(&repo, result, locale)
+        }
+        Some("sync") | Some("pull") => match git_seed::read_git_repo(&ch.directory) {
+            Some(repo) => {
+                let result = git_seed::sync(&ch.directory, &repo).await;
+                repo_sync_reply(&repo, result, locale)
+            }
+            None => t(locale, "repo.not_set"),
+        },
+        Some("clear") | Some("off") => {
+            git_seed::write_git_repo(&ch.directory, None)?;
+            tracing::info!(channel = %ch.channel_name, sender, "Git repo cleared via command");
+            t(locale, "repo.cleared")
+        }
+        Some(_) => {
+            let usage = tf(locale, "repo.usage", &[("usage", REPO_SET_USAGE)]);
+            return reply_privately(channel, sender, MessageContent::plain(usage)).await;
+        }
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// The repo `!repo set` was given, or what's wrong with its arguments
+fn parse_repo_args(
+    args: &[String],
+    locale: &str,
+) -> std::result::Result<git_seed::GitRepoSetting, String> {
+    let Some((url, options)) = args.split_first() else {
+        return Err(t(locale, "repo.missing_url"));
+    };
+    let mut repo = git_seed::GitRepoSetting {
+        url: url.clone(),
+        branch: None,
+        dir: None,
+        pull_on_session_start: false,
+        ssh_key_path: None,
+    };
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let slot = match option.as_str() {
+            "--pull-on-start" => {
+                repo.pull_on_session_start = true;
+                continue;
+            }
+            "--branch" => &mut repo.branch,
+            "--dir" => &mut repo.dir,
+            "--key" => &mut repo.ssh_key_path,
+            other => return Err(tf(locale, "repo.unknown_option", &[("option", other)])),
+        };
+        let Some(value) = options.next() else {
+            return Err(tf(locale, "repo.missing_value", &[("option", option)]));
+        };
+        *slot = Some(value.clone());
+    }
+    if !repo.checks_out_inside_workspace() {
+        return Err(tf(
+            locale,
+            "repo.dir_outside",
+            &[("dir", &repo.checkout_dir())],
+        ));
+    }
+    Ok(repo)
+}
+
+/// How a `!repo set` or `!repo sync` went. Failures are reported, not raised:
+/// the setting is kept and the next session start tries again.
+fn repo_sync_reply(repo: &git_seed::GitRepoSetting, result: Result<()>, locale: &str) -> String {
+    match result {
+        Ok(()) => tf(
+            locale,
+            "repo.synced",
+            &[("url", &repo.url), ("dir", &repo.checkout_dir())],
+        ),
+        Err(e) => tf(
+            locale,
+            "repo.sync_failed",
+            &[("url", &repo.url), ("error", &format!("{:#}", e))],
+        ),
+    }
+}
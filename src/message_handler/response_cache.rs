@@ -0,0 +1,290 @@
+// ABOUTME: Per-channel cache of agent answers keyed by prompt and workspace fingerprint, for !cache.
+// ABOUTME: Any file added, removed or modified in the workspace changes the fingerprint and misses the cache.
+
+use std::hash::Hasher;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf, tn},
+    rich_response::RESPONSE_MANIFEST,
+    session::{Channel, SessionStore},
+};
+
+use super::commands::channel_for_command;
+
+/// Marker in front of answers served from the cache
+pub const CACHED_MARKER: &str = "(cached)";
+
+/// Directories left out of the fingerprint: gorp's own bookkeeping changes on
+/// every turn, and git internals change without the checked-out files changing
+const SKIPPED_DIRS: &[&str] = &[".gorp", ".git"];
+
+/// Cheap fingerprint of a workspace's files: path, size and modification time
+/// of each, never their contents.
+pub fn workspace_fingerprint(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = std::fs::read_dir(&current)
+            .with_context(|| format!("Failed to read {}", current.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                let skipped =
+                    current == dir && SKIPPED_DIRS.iter().any(|name| entry.file_name() == *name);
+                if !skipped {
+                    pending.push(path);
+                }
+            } else if file_type.is_file() {
+                let metadata = entry.metadata()?;
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_nanos());
+                files.push((path, metadata.len(), modified));
+            }
+        }
+    }
+    files.sort();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for (path, size, modified) in &files {
+        let relative = path.strip_prefix(dir).unwrap_or(path);
+        hasher.write(relative.to_string_lossy().as_bytes());
+        hasher.write_u64(*size);
+        hasher.write_u128(*modified);
+    }
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+/// A prompt that missed the cache, with the workspace state it will be answered against
+#[derive(Debug)]
+pub struct CacheMiss {
+    prompt: String,
+    fingerprint: String,
+}
+
+/// Outcome of looking a prompt up in the channel's cache
+#[derive(Debug)]
+pub enum Lookup {
+    /// The cache is off for this channel, or the workspace couldn't be fingerprinted
+    Off,
+    /// The prompt was answered before against the workspace as it is now
+    Hit(String),
+    /// No usable answer; hand the miss to [`remember`] once the agent replies
+    Miss(CacheMiss),
+}
+
+/// Look `prompt` up in the channel's cache, if the channel has it turned on.
+/// Failures are logged and treated as the cache being off.
+pub fn lookup(session_store: &SessionStore, channel: &Channel, prompt: &str) -> Lookup {
+    match try_lookup(session_store, channel, prompt) {
+        Ok(lookup) => lookup,
+        Err(e) => {
+            tracing::warn!(channel = %channel.channel_name, error = %e, "Response cache lookup failed");
+            Lookup::Off
+        }
+    }
+}
+
+fn try_lookup(session_store: &SessionStore, channel: &Channel, prompt: &str) -> Result<Lookup> {
+    if !session_store.is_response_cache_enabled(&channel.channel_name)? {
+        return Ok(Lookup::Off);
+    }
+    let fingerprint = workspace_fingerprint(Path::new(&channel.directory))?;
+    match session_store.get_cached_response(&channel.channel_name, prompt, &fingerprint)? {
+        Some(response) => {
+            tracing::info!(channel = %channel.channel_name, "Answered from response cache");
+            Ok(Lookup::Hit(response))
+        }
+        None => Ok(Lookup::Miss(CacheMiss {
+            prompt: prompt.to_string(),
+            fingerprint,
+        })),
+    }
+}
+
+/// Cache the agent's answer to a missed prompt. Answers from turns that changed
+/// the workspace are not cached: replaying them would describe work already done.
+/// Neither are multi-part replies, whose manifest is consumed when they are sent.
+pub fn remember(session_store: &SessionStore, channel: &Channel, miss: CacheMiss, response: &str) {
+    let workspace = Path::new(&channel.directory);
+    let unchanged = workspace_fingerprint(workspace).is_ok_and(|after| after == miss.fingerprint);
+    if !unchanged || response.trim().is_empty() || workspace.join(RESPONSE_MANIFEST).exists() {
+        return;
+    }
+    if let Err(e) = session_store.put_cached_response(
+        &channel.channel_name,
+        &miss.prompt,
+        &miss.fingerprint,
+        response,
+    ) {
+        tracing::warn!(channel = %channel.channel_name, error = %e, "Failed to cache response");
+    }
+}
+
+/// A cached answer as it is shown in chat
+pub fn cached_reply(response: &str) -> String {
+    format!("{} {}", CACHED_MARKER, response)
+}
+
+/// `!cache [on|off|clear]`: show or change whether repeat questions are answered from the cache
+pub(crate) async fn handle_cache_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "cache", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let arg = args.first().map(|s| s.to_lowercase());
+    let reply = match arg.as_deref() {
+        None => {
+            if session_store.is_response_cache_enabled(&ch.channel_name)? {
+                tf(
+                    locale,
+                    "cache.status_on",
+                    &[
+                        ("channel", &ch.channel_name),
+                        (
+                            "count",
+                            &session_store
+                                .count_cached_responses(&ch.channel_name)?
+                                .to_string(),
+                        ),
+                        ("marker", CACHED_MARKER),
+                    ],
+                )
+            } else {
+                tf(locale, "cache.status_off", &[("channel", &ch.channel_name)])
+            }
+        }
+        Some("on") => {
+            session_store.set_response_cache(&ch.channel_name, true)?;
+            tracing::info!(channel = %ch.channel_name, sender, "Response cache enabled");
+            tf(locale, "cache.on", &[("channel", &ch.channel_name)])
+        }
+        Some("off") => {
+            session_store.set_response_cache(&ch.channel_name, false)?;
+            tf(locale, "cache.off", &[("channel", &ch.channel_name)])
+        }
+        Some("clear") => {
+            let cleared = session_store.clear_response_cache(&ch.channel_name)?;
+            tn(
+                locale,
+                "cache.cleared",
+                cleared,
+                &[("channel", &ch.channel_name)],
+            )
+        }
+        Some(_) => t(locale, "cache.usage"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, SessionStore, Channel) {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+        let channel = store.create_channel("code", "!code:example.org").unwrap();
+        std::fs::write(
+            Path::new(&channel.directory).join("main.rs"),
+            "fn main() {}",
+        )
+        .unwrap();
+        store.set_response_cache("code", true).unwrap();
+        (tmp, store, channel)
+    }
+
+    fn miss(lookup: Lookup) -> CacheMiss {
+        match lookup {
+            Lookup::Miss(miss) => miss,
+            other => panic!("expected a miss, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_gorp_bookkeeping() {
+        let (_tmp, _store, channel) = setup();
+        let dir = Path::new(&channel.directory);
+        let before = workspace_fingerprint(dir).unwrap();
+
+        let gorp = dir.join(".gorp");
+        std::fs::create_dir_all(&gorp).unwrap();
+        std::fs::write(gorp.join("matrix-messages.jsonl"), "{}").unwrap();
+        assert_eq!(workspace_fingerprint(dir).unwrap(), before);
+
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src").join("lib.rs"), "").unwrap();
+        assert_ne!(workspace_fingerprint(dir).unwrap(), before);
+    }
+
+    #[test]
+    fn test_repeat_prompt_hits_until_a_file_changes() {
+        let (_tmp, store, channel) = setup();
+
+        let first = miss(lookup(&store, &channel, "what does main do?"));
+        remember(&store, &channel, first, "Nothing yet");
+        match lookup(&store, &channel, "what does main do?") {
+            Lookup::Hit(response) => assert_eq!(response, "Nothing yet"),
+            other => panic!("expected a hit, got {:?}", other),
+        }
+        assert!(matches!(
+            lookup(&store, &channel, "what does lib do?"),
+            Lookup::Miss(_)
+        ));
+
+        std::fs::write(
+            Path::new(&channel.directory).join("main.rs"),
+            "fn main() { run(); }",
+        )
+        .unwrap();
+        let after_edit = miss(lookup(&store, &channel, "what does main do?"));
+        remember(&store, &channel, after_edit, "It calls run");
+        match lookup(&store, &channel, "what does main do?") {
+            Lookup::Hit(response) => assert_eq!(response, "It calls run"),
+            other => panic!("expected a hit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_turns_that_change_the_workspace_are_not_cached() {
+        let (_tmp, store, channel) = setup();
+
+        let pending = miss(lookup(&store, &channel, "add a README"));
+        std::fs::write(Path::new(&channel.directory).join("README.md"), "# Code").unwrap();
+        remember(&store, &channel, pending, "Added README.md");
+
+        assert!(matches!(
+            lookup(&store, &channel, "add a README"),
+            Lookup::Miss(_)
+        ));
+        assert_eq!(store.count_cached_responses("code").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_lookup_is_off_when_disabled() {
+        let (_tmp, store, channel) = setup();
+        store.set_response_cache("code", false).unwrap();
+        assert!(matches!(
+            lookup(&store, &channel, "what does main do?"),
+            Lookup::Off
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+// ABOUTME: Per-channel response length preference (brief/normal/detailed) set with !length.
+// ABOUTME: Adds a length directive to prompts and optionally trims brief answers after the fact.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::SessionStore,
+};
+
+use super::commands::{channel_for_command, reply_privately};
+
+/// Longest answer a strict `brief` channel will post before trimming
+pub const BRIEF_MAX_CHARS: usize = 800;
+
+/// Note appended when a brief answer had to be cut
+pub const TRIMMED_NOTE: &str = "(trimmed)";
+
+/// How long the channel wants answers to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseLength {
+    Brief,
+    #[default]
+    Normal,
+    Detailed,
+}
+
+impl ResponseLength {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "brief" | "short" => Some(Self::Brief),
+            "normal" | "default" => Some(Self::Normal),
+            "detailed" | "long" => Some(Self::Detailed),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Brief => "brief",
+            Self::Normal => "normal",
+            Self::Detailed => "detailed",
+        }
+    }
+
+    /// Instruction prepended to prompts; `normal` leaves prompts untouched
+    pub fn directive(&self) -> Option<&'static str> {
+        match self {
+            Self::Brief => Some(
+                "[Response length: brief] Keep this answer short - a few sentences or a compact list. Skip preamble and recaps.",
+            ),
+            Self::Normal => None,
+            Self::Detailed => Some(
+                "[Response length: detailed] Give a thorough answer - explain your reasoning, cover edge cases, and include examples where they help.",
+            ),
+        }
+    }
+}
+
+/// A channel's length preference, stored in .gorp/response-length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LengthSetting {
+    pub length: ResponseLength,
+    /// Cut brief answers longer than BRIEF_MAX_CHARS instead of trusting the model
+    pub strict: bool,
+}
+
+fn setting_path(channel_dir: &str) -> PathBuf {
+    Path::new(channel_dir).join(".gorp").join("response-length")
+}
+
+/// Read the channel's length setting; a missing or unreadable file means `normal`
+pub fn get_response_length(channel_dir: &str) -> LengthSetting {
+    let Ok(raw) = std::fs::read_to_string(setting_path(channel_dir)) else {
+        return LengthSetting::default();
+    };
+    let mut words = raw.split_whitespace();
+    let length = words
+        .next()
+        .and_then(ResponseLength::parse)
+        .unwrap_or_default();
+    let strict = length == ResponseLength::Brief && words.any(|w| w == "strict");
+    LengthSetting { length, strict }
+}
+
+/// Persist the channel's length setting; `normal` removes the file
+pub fn set_response_length(channel_dir: &str, setting: LengthSetting) -> Result<()> {
+    let path = setting_path(channel_dir);
+    if setting.length == ResponseLength::Normal {
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let contents = if setting.strict && setting.length == ResponseLength::Brief {
+        format!("{} strict", setting.length.as_str())
+    } else {
+        setting.length.as_str().to_string()
+    };
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Prefix a prompt with the channel's length directive.
+/// Slash commands are left alone so the backend still recognizes them.
+pub fn apply_directive(prompt: &str, setting: LengthSetting) -> String {
+    match setting.length.directive() {
+        Some(directive) if !prompt.trim_start().starts_with('/') => {
+            format!("{}\n\n{}", directive, prompt)
+        }
+        _ => prompt.to_string(),
+    }
+}
+
+/// Trim an over-long answer for strict brief channels, noting that it was cut.
+/// Cuts at the last paragraph or sentence break that fits, falling back to a word break.
+pub fn enforce_length(response: &str, setting: LengthSetting) -> String {
+    if !setting.strict || setting.length != ResponseLength::Brief {
+        return response.to_string();
+    }
+    if response.chars().count() <= BRIEF_MAX_CHARS {
+        return response.to_string();
+    }
+
+    let cut = response
+        .char_indices()
+        .nth(BRIEF_MAX_CHARS)
+        .map(|(i, _)| i)
+        .unwrap_or(response.len());
+    let head = &response[..cut];
+    let end = head
+        .rfind("\n\n")
+        .or_else(|| head.rfind(". ").map(|i| i + 1))
+        .or_else(|| head.rfind(char::is_whitespace))
+        .filter(|&i| i > 0)
+        .unwrap_or(cut);
+
+    format!("{}\n\n{}", head[..end].trim_end(), TRIMMED_NOTE)
+}
+
+/// `!length [brief [strict]|normal|detailed]`: show or change the channel's answer length
+pub(crate) async fn handle_length_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "length", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let Some(arg) = args.first() else {
+        let current = get_response_length(&ch.directory);
+        let strict = if current.strict {
+            t(locale, "length.strict")
+        } else {
+            String::new()
+        };
+        let reply = tf(
+            locale,
+            "length.status",
+            &[
+                ("length", current.length.as_str()),
+                ("strict", &strict),
+                ("max", &BRIEF_MAX_CHARS.to_string()),
+            ],
+        );
+        return channel.send(MessageContent::plain(reply)).await;
+    };
+
+    let Some(length) = ResponseLength::parse(arg) else {
+        let usage = MessageContent::plain(t(locale, "length.usage"));
+        return reply_privately(channel, sender, usage).await;
+    };
+    let strict = length == ResponseLength::Brief
+        && args
+            .get(1)
+            .is_some_and(|s| s.eq_ignore_ascii_case("strict"));
+
+    if let Err(e) = set_response_length(&ch.directory, LengthSetting { length, strict }) {
+        let reply = tf(locale, "length.failed", &[("error", &e.to_string())]);
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+    tracing::info!(channel = %ch.channel_name, length = length.as_str(), strict, "Response length set");
+
+    let detail = match (length, strict) {
+        (ResponseLength::Brief, true) => tf(
+            locale,
+            "length.brief_strict",
+            &[("max", &BRIEF_MAX_CHARS.to_string())],
+        ),
+        (ResponseLength::Brief, false) => t(locale, "length.brief"),
+        (ResponseLength::Normal, _) => t(locale, "length.normal"),
+        (ResponseLength::Detailed, _) => t(locale, "length.detailed"),
+    };
+    let reply = tf(
+        locale,
+        "length.set",
+        &[("length", length.as_str()), ("detail", &detail)],
+    );
+    channel.send(MessageContent::plain(reply)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setting(length: ResponseLength, strict: bool) -> LengthSetting {
+        LengthSetting { length, strict }
+    }
+
+    #[test]
+    fn test_directive_per_setting() {
+        let prompt = "How do I rotate the logs?";
+
+        let brief = apply_directive(prompt, setting(ResponseLength::Brief, false));
+        assert!(brief.starts_with("[Response length: brief]"));
+        assert!(brief.ends_with(prompt));
+
+        let detailed = apply_directive(prompt, setting(ResponseLength::Detailed, false));
+        assert!(detailed.starts_with("[Response length: detailed]"));
+        assert!(detailed.ends_with(prompt));
+
+        let normal = apply_directive(prompt, setting(ResponseLength::Normal, false));
+        assert_eq!(normal, prompt);
+    }
+
+    #[test]
+    fn test_directive_skips_slash_commands() {
+        let prompt = "/review src/main.rs";
+        assert_eq!(
+            apply_directive(prompt, setting(ResponseLength::Brief, true)),
+            prompt
+        );
+    }
+
+    #[test]
+    fn test_setting_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        assert_eq!(get_response_length(dir), LengthSetting::default());
+
+        for expected in [
+            setting(ResponseLength::Brief, true),
+            setting(ResponseLength::Brief, false),
+            setting(ResponseLength::Detailed, false),
+            setting(ResponseLength::Normal, false),
+        ] {
+            set_response_length(dir, expected).unwrap();
+            assert_eq!(get_response_length(dir), expected);
+        }
+        assert!(!tmp.path().join(".gorp/response-length").exists());
+    }
+
+    #[test]
+    fn test_strict_only_applies_to_brief() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        set_response_length(dir, setting(ResponseLength::Detailed, true)).unwrap();
+        assert!(!get_response_length(dir).strict);
+    }
+
+    #[test]
+    fn test_enforce_length_trims_strict_brief() {
+        let long = "This sentence is filler. ".repeat(60);
+        let trimmed = enforce_length(&long, setting(ResponseLength::Brief, true));
+        assert!(trimmed.ends_with("\n\n(trimmed)"));
+        assert!(trimmed.chars().count() <= BRIEF_MAX_CHARS + TRIMMED_NOTE.len() + 2);
+        // Cut lands on a sentence boundary
+        assert!(trimmed
+            .trim_end_matches("\n\n(trimmed)")
+            .ends_with("filler."));
+    }
+
+    #[test]
+    fn test_enforce_length_leaves_others_alone() {
+        let long = "word ".repeat(400);
+        assert_eq!(
+            enforce_length(&long, setting(ResponseLength::Brief, false)),
+            long
+        );
+        assert_eq!(
+            enforce_length(&long, setting(ResponseLength::Detailed, true)),
+            long
+        );
+        assert_eq!(
+            enforce_length("short", setting(ResponseLength::Brief, true)),
+            "short"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+// ABOUTME: Sends multi-part agent responses (text, files, suggested actions) to chat platforms.
+// ABOUTME: Actions become buttons where the platform has them and a numbered list elsewhere.
+
+use anyhow::{bail, Result};
+use gorp_core::traits::{IncomingMessage, MessageContent, MessagingPlatform};
+use std::path::Path;
+
+use crate::{
+    rich_response::{
+        actions_as_list, resolve_file, take_manifest, ResponsePart, RichResponse,
+        DEFAULT_ACTIONS_TEXT,
+    },
+    utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
+};
+
+use super::send_reply;
+
+/// Largest file a manifest can attach (Telegram's bot upload limit)
+const MAX_ATTACHMENT_BYTES: u64 = 50 * 1024 * 1024;
+
+/// A manifest part with its file loaded, ready to send
+#[derive(Debug)]
+pub enum RenderedPart {
+    Content(MessageContent),
+    Actions { text: String, actions: Vec<String> },
+}
+
+/// The multi-part response the agent left in `workspace`, if any.
+/// A broken manifest is logged and dropped so the plain reply still goes out.
+pub fn pending_response(workspace: &Path) -> Option<RichResponse> {
+    match take_manifest(workspace) {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!(
+                workspace = %workspace.display(),
+                error = %e,
+                "Ignoring response manifest"
+            );
+            None
+        }
+    }
+}
+
+/// Load what each part refers to. A file that can't be attached becomes a
+/// short notice rather than failing the whole reply.
+pub async fn render_parts(workspace: &Path, response: &RichResponse) -> Vec<RenderedPart> {
+    let mut rendered = Vec::new();
+    for part in &response.parts {
+        match part {
+            ResponsePart::Text { text } => {
+                for chunk in chunk_message(text, MAX_CHUNK_SIZE) {
+                    let html = markdown_to_html(&chunk);
+                    rendered.push(RenderedPart::Content(MessageContent::html(chunk, html)));
+                }
+            }
+            ResponsePart::File { path, caption } => {
+                let content = match load_file(workspace, path, caption.clone()).await {
+                    Ok(content) => content,
+                    Err(e) => {
+                        tracing::warn!(path = %path, error = %e, "Failed to attach manifest file");
+                        MessageContent::plain(format!("⚠️ Couldn't attach {}: {}", path, e))
+                    }
+                };
+                rendered.push(RenderedPart::Content(content));
+            }
+            ResponsePart::Actions { text, actions } => rendered.push(RenderedPart::Actions {
+                text: text
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ACTIONS_TEXT.to_string()),
+                actions: actions.clone(),
+            }),
+        }
+    }
+    rendered
+}
+
+async fn load_file(
+    workspace: &Path,
+    path: &str,
+    caption: Option<String>,
+) -> Result<MessageContent> {
+    let resolved = resolve_file(workspace, path)?;
+    let size = tokio::fs::metadata(&resolved).await?.len();
+    if size > MAX_ATTACHMENT_BYTES {
+        bail!(
+            "file is {} MB, over the {} MB limit",
+            size / (1024 * 1024),
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        );
+    }
+    let data = tokio::fs::read(&resolved).await?;
+    let filename = resolved
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("attachment")
+        .to_string();
+    let mime_type = mime_guess::from_path(&resolved)
+        .first_or_octet_stream()
+        .to_string();
+    Ok(MessageContent::Attachment {
+        filename,
+        data,
+        mime_type,
+        caption,
+    })
+}
+
+/// Send a multi-part response as the reply to `msg`
+pub async fn send_rich_response(
+    platform: &dyn MessagingPlatform,
+    msg: &IncomingMessage,
+    workspace: &Path,
+    response: &RichResponse,
+) -> Result<()> {
+    for part in render_parts(workspace, response).await {
+        match part {
+            RenderedPart::Content(content) => send_reply(platform, msg, content).await?,
+            RenderedPart::Actions { text, actions } => match platform.action_buttons() {
+                Some(buttons) => {
+                    buttons
+                        .send_actions(&msg.channel_id, &text, &actions)
+                        .await?
+                }
+                None => {
+                    let list = actions_as_list(Some(&text), &actions);
+                    send_reply(platform, msg, MessageContent::plain(list)).await?
+                }
+            },
+        }
+    }
+    Ok(())
+}
@@ -1,12 +1,15 @@
 // ABOUTME: Schedule import and parsing utilities
 // ABOUTME: Handles YAML schedule imports and natural language time parsing
 
-use super::helpers::looks_like_cron;
 use crate::scheduler::{
-    compute_next_cron_execution_in_tz, parse_time_expression, ParsedSchedule, ScheduleStatus,
-    ScheduledPrompt, SchedulerStore,
+    parse_schedule_time, parse_time_expression, ParsedSchedule, ScheduleStatus, ScheduledPrompt,
+    SchedulerStore,
 };
 use crate::session::Channel;
+use std::collections::HashMap;
+
+/// Marks a time template name, as in `!schedule @standup <prompt>`
+pub const TIME_TEMPLATE_PREFIX: &str = "@";
 
 /// Import a single schedule from YAML data
 pub fn import_schedule(
@@ -18,18 +21,8 @@ pub fn import_schedule(
     timezone: &str,
     scheduler_store: &SchedulerStore,
 ) -> anyhow::Result<()> {
-    // Check if time is a raw cron expression (exported from recurring schedule)
-    let parsed = if looks_like_cron(time) {
-        // Parse as raw cron expression
-        let next = compute_next_cron_execution_in_tz(time, timezone)?;
-        ParsedSchedule::Recurring {
-            cron: time.to_string(),
-            next,
-        }
-    } else {
-        // Try parsing as natural language time expression
-        parse_time_expression(time, timezone)?
-    };
+    // Exports store recurring schedules as raw cron, one-time ones as natural language
+    let parsed = parse_schedule_time(time, timezone)?;
 
     let schedule_id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
@@ -65,14 +58,78 @@ pub fn import_schedule(
     Ok(())
 }
 
-/// Parse schedule input to extract time expression and prompt
-/// Uses greedy matching with a max lookahead to avoid consuming the entire prompt
+/// Replace a `@name` time with the expression configured for it under
+/// `[scheduler.templates]`. Any other time is returned unchanged.
+pub fn expand_time_template<'a>(
+    time: &'a str,
+    templates: &'a HashMap<String, String>,
+) -> anyhow::Result<&'a str> {
+    let Some(name) = time.trim().strip_prefix(TIME_TEMPLATE_PREFIX) else {
+        return Ok(time);
+    };
+    if let Some(expression) = templates.get(name) {
+        return Ok(expression);
+    }
+    if templates.is_empty() {
+        anyhow::bail!(
+            "Unknown time template '@{}': none are configured under [scheduler.templates]",
+            name
+        );
+    }
+    let mut known: Vec<String> = templates.keys().map(|n| format!("@{}", n)).collect();
+    known.sort();
+    anyhow::bail!(
+        "Unknown time template '@{}'. Known templates: {}",
+        name,
+        known.join(", ")
+    )
+}
+
+/// The `@name` of a recurring template that runs on exactly `cron`, so an export
+/// can refer to the template instead of repeating its expression
+pub fn time_template_for_cron(
+    cron: &str,
+    templates: &HashMap<String, String>,
+    timezone: &str,
+) -> Option<String> {
+    let mut names: Vec<&String> = templates.keys().collect();
+    names.sort();
+    names.into_iter().find_map(
+        |name| match parse_schedule_time(&templates[name], timezone) {
+            Ok(ParsedSchedule::Recurring { cron: ref c, .. }) if c == cron => {
+                Some(format!("{}{}", TIME_TEMPLATE_PREFIX, name))
+            }
+            _ => None,
+        },
+    )
+}
+
+/// Parse schedule input to extract time expression and prompt.
+/// A leading `@name` stands for the time template of that name; otherwise uses
+/// greedy matching with a max lookahead to avoid consuming the entire prompt
 pub fn parse_schedule_input(
     input: &str,
     timezone: &str,
+    templates: &HashMap<String, String>,
 ) -> anyhow::Result<(ParsedSchedule, String)> {
     let words: Vec<&str> = input.split_whitespace().collect();
 
+    if let Some(first) = words
+        .first()
+        .filter(|w| w.starts_with(TIME_TEMPLATE_PREFIX))
+    {
+        let time = expand_time_template(first, templates)?;
+        let schedule = parse_schedule_time(time, timezone).map_err(|e| {
+            anyhow::anyhow!(
+                "Time template {} = \"{}\" is not a valid time: {}",
+                first,
+                time,
+                e
+            )
+        })?;
+        return Ok((schedule, words[1..].join(" ")));
+    }
+
     // Require at least 1 word for prompt, limit time expression to 10 words max
     let max_time_words = std::cmp::min(words.len().saturating_sub(1), 10);
 
@@ -97,6 +154,62 @@ pub fn parse_schedule_input(
     }
 }
 
+/// Word that starts a prompt-only edit, as in `!schedule edit <id> prompt <new prompt>`
+pub const EDIT_PROMPT_KEYWORD: &str = "prompt";
+
+/// What `!schedule edit` changes; None keeps that part of the schedule as it is
+#[derive(Debug)]
+pub struct ScheduleEdit {
+    pub time: Option<ParsedSchedule>,
+    pub prompt: Option<String>,
+}
+
+/// Parse the part of `!schedule edit <id> ...` after the ID:
+/// `prompt <text>` replaces only the prompt, a bare time replaces only the time,
+/// and `<time> <prompt>` replaces both. The time is validated here, so nothing
+/// is saved when it doesn't parse.
+pub fn parse_schedule_edit(
+    input: &str,
+    timezone: &str,
+    templates: &HashMap<String, String>,
+) -> anyhow::Result<ScheduleEdit> {
+    let input = input.trim();
+    if input.is_empty() {
+        anyhow::bail!("Nothing to change. Give a new time, a new prompt, or both");
+    }
+
+    let mut words = input.splitn(2, char::is_whitespace);
+    if words
+        .next()
+        .is_some_and(|w| w.eq_ignore_ascii_case(EDIT_PROMPT_KEYWORD))
+    {
+        let prompt = words.next().unwrap_or_default().trim();
+        if prompt.is_empty() {
+            anyhow::bail!("Missing prompt after '{}'", EDIT_PROMPT_KEYWORD);
+        }
+        return Ok(ScheduleEdit {
+            time: None,
+            prompt: Some(prompt.to_string()),
+        });
+    }
+
+    // The whole input reads as a time: keep the prompt
+    if let Ok(time) =
+        expand_time_template(input, templates).and_then(|time| parse_schedule_time(time, timezone))
+    {
+        return Ok(ScheduleEdit {
+            time: Some(time),
+            prompt: None,
+        });
+    }
+
+    let (time, prompt) = parse_schedule_input(input, timezone, templates)?;
+    Ok(ScheduleEdit {
+        time: Some(time),
+        prompt: Some(prompt),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,7 +265,7 @@ mod tests {
 
     #[test]
     fn test_parse_schedule_input_relative() {
-        let result = parse_schedule_input("in 5 minutes do something", "UTC");
+        let result = parse_schedule_input("in 5 minutes do something", "UTC", &HashMap::new());
         assert!(result.is_ok());
         let (schedule, prompt) = result.unwrap();
         assert!(matches!(schedule, ParsedSchedule::OneTime(_)));
@@ -161,7 +274,7 @@ mod tests {
 
     #[test]
     fn test_parse_schedule_input_recurring() {
-        let result = parse_schedule_input("every day at 9am check server", "UTC");
+        let result = parse_schedule_input("every day at 9am check server", "UTC", &HashMap::new());
         assert!(result.is_ok());
         let (schedule, prompt) = result.unwrap();
         assert!(matches!(schedule, ParsedSchedule::Recurring { .. }));
@@ -170,22 +283,40 @@ mod tests {
 
     #[test]
     fn test_parse_schedule_input_invalid() {
-        let result = parse_schedule_input("banana apple cherry", "UTC");
+        let result = parse_schedule_input("banana apple cherry", "UTC", &HashMap::new());
         assert!(result.is_err());
     }
 
     #[test]
     fn test_parse_schedule_input_single_word() {
         // Need at least time + prompt, single word can't parse
-        let result = parse_schedule_input("tomorrow", "UTC");
+        let result = parse_schedule_input("tomorrow", "UTC", &HashMap::new());
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_export_names_the_matching_time_template() {
+        let templates = HashMap::from([
+            ("standup".to_string(), "every weekday 9am".to_string()),
+            ("review".to_string(), "every friday 4pm".to_string()),
+        ]);
+        assert_eq!(
+            time_template_for_cron("0 9 * * MON-FRI", &templates, "UTC").as_deref(),
+            Some("@standup")
+        );
+        assert_eq!(time_template_for_cron("0 8 * * *", &templates, "UTC"), None);
+        assert_eq!(
+            expand_time_template("every day 8am", &templates).unwrap(),
+            "every day 8am"
+        );
+    }
+
     #[test]
     fn test_parse_schedule_input_long_prompt() {
         let result = parse_schedule_input(
             "in 1 hour check the status of all running services and report back",
             "UTC",
+            &HashMap::new(),
         );
         assert!(result.is_ok());
         let (schedule, prompt) = result.unwrap();
@@ -368,4 +499,138 @@ mod tests {
         let schedules = store.list_by_channel(&channel.channel_name).unwrap();
         assert_eq!(schedules.len(), 3);
     }
+
+    /// A recurring schedule that has already run twice
+    fn schedule_with_history(store: &SchedulerStore, channel: &Channel) -> ScheduledPrompt {
+        import_schedule(
+            "0 9 * * 1",
+            "weekly report",
+            false,
+            channel,
+            "@user:example.org",
+            "UTC",
+            store,
+        )
+        .unwrap();
+        let id = store.list_by_channel(&channel.channel_name).unwrap()[0]
+            .id
+            .clone();
+        for _ in 0..2 {
+            let next = crate::scheduler::compute_next_cron_execution("0 9 * * 1").unwrap();
+            store.mark_executed(&id, Some(next)).unwrap();
+        }
+        store.get_by_id(&id).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_edit_time_only_keeps_prompt_and_history() {
+        let channel = make_test_channel();
+        let store = create_test_store(&channel.channel_name, &channel.room_id);
+        let before = schedule_with_history(&store, &channel);
+
+        let edit = parse_schedule_edit("0 17 * * 5", "UTC", &HashMap::new()).unwrap();
+        assert!(edit.prompt.is_none());
+        assert!(store
+            .update_schedule(&before.id, edit.time.as_ref(), edit.prompt.as_deref())
+            .unwrap());
+
+        let after = store.get_by_id(&before.id).unwrap().unwrap();
+        assert_eq!(after.prompt, "weekly report");
+        assert_eq!(after.cron_expression.as_deref(), Some("0 17 * * 5"));
+        assert_ne!(after.next_execution_at, before.next_execution_at);
+        assert_eq!(after.execution_count, 2);
+        assert_eq!(after.last_executed_at, before.last_executed_at);
+        assert_eq!(after.created_at, before.created_at);
+    }
+
+    #[test]
+    fn test_edit_prompt_only_keeps_the_cron() {
+        let channel = make_test_channel();
+        let store = create_test_store(&channel.channel_name, &channel.room_id);
+        let before = schedule_with_history(&store, &channel);
+
+        let edit =
+            parse_schedule_edit("prompt summarize open PRs", "UTC", &HashMap::new()).unwrap();
+        assert!(edit.time.is_none());
+        assert!(store
+            .update_schedule(&before.id, edit.time.as_ref(), edit.prompt.as_deref())
+            .unwrap());
+
+        let after = store.get_by_id(&before.id).unwrap().unwrap();
+        assert_eq!(after.prompt, "summarize open PRs");
+        assert_eq!(after.cron_expression, before.cron_expression);
+        assert_eq!(after.next_execution_at, before.next_execution_at);
+        assert_eq!(after.execution_count, 2);
+    }
+
+    #[test]
+    fn test_edit_time_and_prompt_together() {
+        let channel = make_test_channel();
+        let store = create_test_store(&channel.channel_name, &channel.room_id);
+        let before = schedule_with_history(&store, &channel);
+
+        let edit =
+            parse_schedule_edit("in 2 hours check the deploy", "UTC", &HashMap::new()).unwrap();
+        assert!(store
+            .update_schedule(&before.id, edit.time.as_ref(), edit.prompt.as_deref())
+            .unwrap());
+
+        let after = store.get_by_id(&before.id).unwrap().unwrap();
+        assert_eq!(after.prompt, "check the deploy");
+        // Now a one-time schedule
+        assert!(after.cron_expression.is_none());
+        assert_eq!(
+            after.execute_at.as_deref(),
+            Some(after.next_execution_at.as_str())
+        );
+        assert_eq!(after.execution_count, 2);
+        assert_eq!(after.id, before.id);
+    }
+
+    #[test]
+    fn test_edit_with_invalid_time_changes_nothing() {
+        let channel = make_test_channel();
+        let store = create_test_store(&channel.channel_name, &channel.room_id);
+        let before = schedule_with_history(&store, &channel);
+
+        assert!(parse_schedule_edit("every funday check in", "UTC", &HashMap::new()).is_err());
+        assert!(parse_schedule_edit("prompt", "UTC", &HashMap::new()).is_err());
+        assert!(parse_schedule_edit("  ", "UTC", &HashMap::new()).is_err());
+
+        let after = store.get_by_id(&before.id).unwrap().unwrap();
+        assert_eq!(after.prompt, before.prompt);
+        assert_eq!(after.next_execution_at, before.next_execution_at);
+    }
+
+    #[test]
+    fn test_new_time_reactivates_a_completed_schedule() {
+        let channel = make_test_channel();
+        let store = create_test_store(&channel.channel_name, &channel.room_id);
+        import_schedule(
+            "in 1 hour",
+            "one-off",
+            false,
+            &channel,
+            "@user:example.org",
+            "UTC",
+            &store,
+        )
+        .unwrap();
+        let id = store.list_by_channel(&channel.channel_name).unwrap()[0]
+            .id
+            .clone();
+        store.mark_executed(&id, None).unwrap();
+
+        // A prompt-only edit leaves it completed
+        store.update_schedule(&id, None, Some("again")).unwrap();
+        let edited = store.get_by_id(&id).unwrap().unwrap();
+        assert_eq!(edited.status, ScheduleStatus::Completed);
+
+        let time = parse_schedule_time("in 3 hours", "UTC").unwrap();
+        store.update_schedule(&id, Some(&time), None).unwrap();
+        let edited = store.get_by_id(&id).unwrap().unwrap();
+        assert_eq!(edited.status, ScheduleStatus::Active);
+        assert_eq!(edited.prompt, "again");
+        assert_eq!(edited.execution_count, 1);
+    }
 }
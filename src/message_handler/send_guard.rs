@@ -0,0 +1,89 @@
+// ABOUTME: !sendguard, !append and !discard: a user's long-message guard and the draft it holds.
+// ABOUTME: !send lives on the message path, since submitting a draft runs the agent.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    drafts::append_text,
+    i18n::{t, tf},
+    session::SessionStore,
+};
+
+use super::commands::reply_privately;
+
+/// `!sendguard [on|off]`: show or change whether the sender's long messages are held.
+/// The setting is per user, so it follows them into every channel.
+pub(crate) async fn handle_sendguard_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    threshold_chars: usize,
+    sender: &str,
+    locale: &str,
+) -> Result<()> {
+    let threshold = threshold_chars.to_string();
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        Some("on") | Some("enable") => {
+            session_store.set_send_guard(sender, true)?;
+            tracing::info!(sender, "Send guard enabled");
+            tf(locale, "sendguard.enabled", &[("threshold", &threshold)])
+        }
+        Some("off") | Some("disable") => {
+            session_store.set_send_guard(sender, false)?;
+            tracing::info!(sender, "Send guard disabled");
+            t(locale, "sendguard.disabled")
+        }
+        _ => {
+            let status = if session_store.is_send_guard_enabled(sender)? {
+                tf(locale, "sendguard.status_on", &[("threshold", &threshold)])
+            } else {
+                t(locale, "sendguard.status_off")
+            };
+            tf(locale, "sendguard.status", &[("status", &status)])
+        }
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// `!append <text>`: add to the sender's held draft in this room
+pub(crate) async fn handle_append_command<C: ChatChannel>(
+    channel: &C,
+    raw_args: &str,
+    session_store: &SessionStore,
+    sender: &str,
+    locale: &str,
+) -> Result<()> {
+    let text = raw_args.trim();
+    if text.is_empty() {
+        let usage = MessageContent::plain(t(locale, "sendguard.usage_append"));
+        return reply_privately(channel, sender, usage).await;
+    }
+    let Some(draft) = session_store.get_draft(channel.id(), sender)? else {
+        return channel
+            .send(MessageContent::plain(t(locale, "sendguard.no_draft_hint")))
+            .await;
+    };
+    let body = append_text(&draft.body, text);
+    session_store.save_draft(&draft.platform_id, channel.id(), sender, &body)?;
+    let reply = tf(
+        locale,
+        "sendguard.appended",
+        &[("chars", &body.chars().count().to_string())],
+    );
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// `!discard`: drop the sender's held draft in this room
+pub(crate) async fn handle_discard_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    sender: &str,
+    locale: &str,
+) -> Result<()> {
+    let reply = match session_store.take_draft(channel.id(), sender)? {
+        Some(_) => t(locale, "sendguard.discarded"),
+        None => t(locale, "sendguard.no_draft"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
@@ -0,0 +1,135 @@
+// ABOUTME: !usage and !budget: what a channel's agent has cost and the monthly budget it's held to.
+// ABOUTME: Conversation is reported apart from the bot's own overhead; forecasts come from crate::budget.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    budget,
+    i18n::{t, tf, tn},
+    session::SessionStore,
+    usage::UsageTotals,
+};
+
+use super::commands::channel_for_command;
+
+/// `!usage`: token usage and cost so far, with the budget runway when there's spend to project
+pub(crate) async fn handle_usage_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "usage", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let usage = session_store.get_channel_usage(&ch.channel_name)?;
+    let today = chrono::Utc::now().date_naive();
+    let runway = match session_store.get_spend_forecast(&ch.channel_name, today)? {
+        Some(forecast) => format!(
+            "\n\n{}",
+            budget::runway_summary(
+                &forecast,
+                session_store.get_budget_cents(&ch.channel_name)?,
+                today
+            )
+        ),
+        None => String::new(),
+    };
+    let line = |label: &str, totals: &UsageTotals| {
+        tn(
+            locale,
+            "usage.line",
+            totals.invocations as usize,
+            &[
+                ("label", label),
+                ("input", &totals.input_tokens.to_string()),
+                ("output", &totals.output_tokens.to_string()),
+                ("cost", &format!("{:.2}", totals.cost_cents as f64 / 100.0)),
+            ],
+        )
+    };
+    let reply = tf(
+        locale,
+        "usage.report",
+        &[
+            ("channel", &ch.channel_name),
+            (
+                "conversation",
+                &line(&t(locale, "usage.conversation"), &usage.conversation),
+            ),
+            (
+                "overhead",
+                &line(&t(locale, "usage.overhead"), &usage.overhead),
+            ),
+            ("runway", &runway),
+        ],
+    );
+    channel.send(MessageContent::plain(reply)).await
+}
+
+/// `!budget [set <amount>|clear]`: show or change the channel's monthly budget
+pub(crate) async fn handle_budget_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "budget", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let reply = match args.first().map(|s| s.as_str()) {
+        None => {
+            let today = chrono::Utc::now().date_naive();
+            let budget = session_store.get_budget_cents(&ch.channel_name)?;
+            let forecast = session_store.get_spend_forecast(&ch.channel_name, today)?;
+            match (budget, forecast) {
+                (None, _) => tf(locale, "budget.none", &[("channel", &ch.channel_name)]),
+                (Some(cents), None) => tf(
+                    locale,
+                    "budget.no_usage",
+                    &[
+                        ("channel", &ch.channel_name),
+                        ("amount", &budget::format_cents(cents)),
+                    ],
+                ),
+                (Some(cents), Some(forecast)) => tf(
+                    locale,
+                    "budget.status",
+                    &[
+                        ("channel", &ch.channel_name),
+                        (
+                            "runway",
+                            &budget::runway_summary(&forecast, Some(cents), today),
+                        ),
+                    ],
+                ),
+            }
+        }
+        Some("set") => match args.get(1).and_then(|a| budget::parse_budget(a)) {
+            Some(cents) => {
+                session_store.set_budget_cents(&ch.channel_name, Some(cents))?;
+                tf(
+                    locale,
+                    "budget.set",
+                    &[
+                        ("channel", &ch.channel_name),
+                        ("amount", &budget::format_cents(cents)),
+                    ],
+                )
+            }
+            None => t(locale, "budget.usage_set"),
+        },
+        Some("clear") => {
+            session_store.set_budget_cents(&ch.channel_name, None)?;
+            tf(locale, "budget.cleared", &[("channel", &ch.channel_name)])
+        }
+        Some(_) => t(locale, "budget.usage"),
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
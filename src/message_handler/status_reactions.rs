@@ -0,0 +1,258 @@
+// ABOUTME: Reaction-based progress on the user's message: ⏳ while working, 🔧 during tools, ✅/❌ at the end.
+// ABOUTME: A quieter alternative to debug-mode tool messages; annotation failures are logged, never surfaced.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageAnnotator, MessageContent};
+
+use crate::i18n::{t, tf};
+use crate::session::SessionStore;
+
+use super::commands::channel_for_command;
+use super::helpers::is_status_reactions_enabled;
+
+pub const WORKING: &str = "⏳";
+pub const TOOLS: &str = "🔧";
+pub const SUCCEEDED: &str = "✅";
+pub const FAILED: &str = "❌";
+
+/// Tracks the status reactions placed on one incoming message.
+/// Without an annotator (setting off, or the platform can't react) every call is a no-op.
+pub struct StatusReactions<'a> {
+    annotator: Option<&'a dyn MessageAnnotator>,
+    channel_id: String,
+    message_id: String,
+    working: Option<String>,
+    tools: Option<String>,
+    running_tools: usize,
+}
+
+impl<'a> StatusReactions<'a> {
+    /// Mark the message as being worked on
+    pub async fn start(
+        annotator: Option<&'a dyn MessageAnnotator>,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Self {
+        let mut status = Self {
+            annotator,
+            channel_id: channel_id.to_string(),
+            message_id: message_id.to_string(),
+            working: None,
+            tools: None,
+            running_tools: 0,
+        };
+        status.working = status.add(WORKING).await;
+        status
+    }
+
+    /// A tool started; 🔧 stays up until every running tool has finished
+    pub async fn tool_started(&mut self) {
+        self.running_tools += 1;
+        if self.tools.is_none() {
+            self.tools = self.add(TOOLS).await;
+        }
+    }
+
+    pub async fn tool_finished(&mut self) {
+        self.running_tools = self.running_tools.saturating_sub(1);
+        if self.running_tools == 0 {
+            let tools = self.tools.take();
+            self.remove(tools).await;
+        }
+    }
+
+    /// Replace the in-progress reactions with the outcome
+    pub async fn finish(mut self, succeeded: bool) {
+        let tools = self.tools.take();
+        self.remove(tools).await;
+        let working = self.working.take();
+        self.remove(working).await;
+        self.add(if succeeded { SUCCEEDED } else { FAILED }).await;
+    }
+
+    async fn add(&self, emoji: &str) -> Option<String> {
+        let annotator = self.annotator?;
+        match annotator
+            .add_reaction(&self.channel_id, &self.message_id, emoji)
+            .await
+        {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::warn!(error = %e, emoji, "Failed to add status reaction");
+                None
+            }
+        }
+    }
+
+    async fn remove(&self, reaction_id: Option<String>) {
+        let (Some(annotator), Some(reaction_id)) = (self.annotator, reaction_id) else {
+            return;
+        };
+        if let Err(e) = annotator
+            .remove_reaction(&self.channel_id, &reaction_id)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to remove status reaction");
+        }
+    }
+}
+
+/// `!reactions [on|off]`: show or change whether messages get status reactions
+pub(crate) async fn handle_reactions_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "reactions", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let gorp_dir = std::path::Path::new(&ch.directory).join(".gorp");
+    let reactions_file = gorp_dir.join("status-reactions");
+
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        Some("on") | Some("enable") => {
+            if let Err(e) =
+                std::fs::create_dir_all(&gorp_dir).and_then(|_| std::fs::write(&reactions_file, ""))
+            {
+                tf(
+                    locale,
+                    "reactions.enable_failed",
+                    &[("error", &e.to_string())],
+                )
+            } else {
+                tracing::info!(channel = %ch.channel_name, "Status reactions enabled");
+                t(locale, "reactions.enabled")
+            }
+        }
+        Some("off") | Some("disable") => match std::fs::remove_file(&reactions_file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => tf(
+                locale,
+                "reactions.disable_failed",
+                &[("error", &e.to_string())],
+            ),
+            _ => {
+                tracing::info!(channel = %ch.channel_name, "Status reactions disabled");
+                t(locale, "reactions.disabled")
+            }
+        },
+        _ => {
+            let status = if is_status_reactions_enabled(&ch.directory) {
+                t(locale, "reactions.status_on")
+            } else {
+                t(locale, "reactions.status_off")
+            };
+            tf(locale, "reactions.help", &[("status", &status)])
+        }
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Annotator that keeps the set of reactions currently on each message
+    #[derive(Default)]
+    struct RecordingAnnotator {
+        reactions: Mutex<Vec<(String, String)>>,
+        next_id: Mutex<usize>,
+    }
+
+    impl RecordingAnnotator {
+        fn emojis(&self) -> Vec<String> {
+            let reactions = self.reactions.lock().unwrap();
+            reactions.iter().map(|(_, emoji)| emoji.clone()).collect()
+        }
+    }
+
+    #[async_trait]
+    impl MessageAnnotator for RecordingAnnotator {
+        async fn add_reaction(
+            &self,
+            _channel_id: &str,
+            _message_id: &str,
+            emoji: &str,
+        ) -> Result<String> {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            let id = format!("$reaction{}", next_id);
+            self.reactions
+                .lock()
+                .unwrap()
+                .push((id.clone(), emoji.to_string()));
+            Ok(id)
+        }
+
+        async fn remove_reaction(&self, _channel_id: &str, reaction_id: &str) -> Result<()> {
+            self.reactions
+                .lock()
+                .unwrap()
+                .retain(|(id, _)| id != reaction_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_success_swaps_working_for_check() {
+        let annotator = RecordingAnnotator::default();
+        let status = StatusReactions::start(Some(&annotator), "!room", "$msg").await;
+        assert_eq!(annotator.emojis(), vec![WORKING]);
+
+        status.finish(true).await;
+        assert_eq!(annotator.emojis(), vec![SUCCEEDED]);
+    }
+
+    #[tokio::test]
+    async fn test_tool_reaction_lasts_until_last_tool_ends() {
+        let annotator = RecordingAnnotator::default();
+        let mut status = StatusReactions::start(Some(&annotator), "!room", "$msg").await;
+
+        status.tool_started().await;
+        status.tool_started().await;
+        assert_eq!(annotator.emojis(), vec![WORKING, TOOLS]);
+
+        status.tool_finished().await;
+        assert_eq!(annotator.emojis(), vec![WORKING, TOOLS]);
+        status.tool_finished().await;
+        assert_eq!(annotator.emojis(), vec![WORKING]);
+
+        status.tool_started().await;
+        status.finish(false).await;
+        assert_eq!(annotator.emojis(), vec![FAILED]);
+    }
+
+    #[tokio::test]
+    async fn test_annotation_errors_are_not_fatal() {
+        struct FailingAnnotator;
+
+        #[async_trait]
+        impl MessageAnnotator for FailingAnnotator {
+            async fn add_reaction(&self, _: &str, _: &str, _: &str) -> Result<String> {
+                anyhow::bail!("reactions unavailable")
+            }
+            async fn remove_reaction(&self, _: &str, _: &str) -> Result<()> {
+                anyhow::bail!("reactions unavailable")
+            }
+        }
+
+        let mut status = StatusReactions::start(Some(&FailingAnnotator), "!room", "$msg").await;
+        status.tool_started().await;
+        status.tool_finished().await;
+        status.finish(true).await;
+    }
+
+    #[tokio::test]
+    async fn test_without_annotator_nothing_is_sent() {
+        let mut status = StatusReactions::start(None, "!room", "$msg").await;
+        status.tool_started().await;
+        status.tool_finished().await;
+        status.finish(false).await;
+    }
+}
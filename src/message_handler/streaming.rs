@@ -0,0 +1,324 @@
+// ABOUTME: Streams agent output into Matrix by editing a placeholder message as text arrives.
+// ABOUTME: Throttles edits by time and size and rolls over at the chunk limit; !stream opts in.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+use matrix_sdk::{
+    room::Room,
+    ruma::{
+        events::room::message::{ReplacementMetadata, RoomMessageEventContent},
+        OwnedEventId,
+    },
+};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{
+    i18n::{t, tf},
+    metrics,
+    platform::matrix::{
+        fallback::{send_error, send_with_plain_fallback},
+        signing::{signed_event, Signer},
+    },
+    session::SessionStore,
+    usage::InvocationOrigin,
+    utils::{chunk_message, log_matrix_message, markdown_to_html, MAX_CHUNK_SIZE},
+};
+
+use super::commands::channel_for_command;
+use super::helpers::is_streaming_enabled;
+
+/// Body of the message sent before any agent text has arrived
+pub const STREAM_PLACEHOLDER: &str = "…thinking";
+/// Minimum time between two edits of the streamed message
+pub const STREAM_EDIT_INTERVAL: Duration = Duration::from_secs(2);
+/// Edit early once this many new characters have accumulated
+pub const STREAM_EDIT_CHARS: usize = 500;
+
+/// Whether enough new text or time has passed to justify another edit
+pub fn edit_due(pending_chars: usize, since_last_edit: Duration) -> bool {
+    pending_chars > 0
+        && (pending_chars >= STREAM_EDIT_CHARS || since_last_edit >= STREAM_EDIT_INTERVAL)
+}
+
+/// Byte index at which to cut `segment` so the head fits in `max_len`.
+///
+/// Prefers the last newline before the limit, then the last space, and falls
+/// back to a hard cut on a char boundary. Returns `segment.len()` if it fits.
+pub fn rollover_point(segment: &str, max_len: usize) -> usize {
+    if segment.len() <= max_len {
+        return segment.len();
+    }
+    let mut limit = max_len;
+    while !segment.is_char_boundary(limit) {
+        limit -= 1;
+    }
+    let head = &segment[..limit];
+    head.rfind('\n')
+        .or_else(|| head.rfind(' '))
+        .filter(|&i| i > 0)
+        .map(|i| i + 1)
+        .unwrap_or(limit)
+}
+
+/// A response being streamed into a room through message edits.
+pub struct ResponseStreamer {
+    room: Room,
+    /// Every message sent so far; the last one is the one being edited
+    event_ids: Vec<OwnedEventId>,
+    /// Every event sent, edits included, for redacting the response later
+    sent: Mutex<Vec<OwnedEventId>>,
+    /// Byte offset in the accumulated text where the current message starts
+    segment_start: usize,
+    /// Accumulated text length at the last edit
+    edited_len: usize,
+    last_edit: Instant,
+}
+
+impl ResponseStreamer {
+    /// Send the placeholder message that later edits will replace
+    pub async fn start(room: &Room) -> Result<Self> {
+        let sent = room
+            .send(RoomMessageEventContent::text_plain(STREAM_PLACEHOLDER))
+            .await?;
+        Ok(Self {
+            room: room.clone(),
+            event_ids: vec![sent.event_id.clone()],
+            sent: Mutex::new(vec![sent.event_id]),
+            segment_start: 0,
+            edited_len: 0,
+            last_edit: Instant::now(),
+        })
+    }
+
+    async fn edit(&self, event_id: &OwnedEventId, content: RoomMessageEventContent) -> Result<()> {
+        let replacement =
+            content.make_replacement(ReplacementMetadata::new(event_id.clone(), None));
+        let sent = self.room.send(replacement).await.map_err(send_error)?;
+        self.remember(sent.event_id);
+        Ok(())
+    }
+
+    fn remember(&self, event_id: OwnedEventId) {
+        if let Ok(mut sent) = self.sent.lock() {
+            sent.push(event_id);
+        }
+    }
+
+    /// Every event the streamer has sent so far, edits included
+    pub fn sent_event_ids(&self) -> Vec<OwnedEventId> {
+        self.sent
+            .lock()
+            .map(|sent| sent.clone())
+            .unwrap_or_default()
+    }
+
+    /// Reflect the accumulated response `text` in the room, if an edit is due
+    pub async fn update(&mut self, text: &str) -> Result<()> {
+        let mut segment = &text[self.segment_start..];
+
+        // Roll over to a fresh message instead of growing past the chunk limit
+        while segment.len() > MAX_CHUNK_SIZE {
+            let cut = rollover_point(segment, MAX_CHUNK_SIZE);
+            let current = self
+                .event_ids
+                .last()
+                .expect("streamer always has a message");
+            self.edit(
+                current,
+                RoomMessageEventContent::text_plain(&segment[..cut]),
+            )
+            .await?;
+            self.segment_start += cut;
+            segment = &text[self.segment_start..];
+            let sent = self
+                .room
+                .send(RoomMessageEventContent::text_plain(
+                    &segment[..rollover_point(segment, MAX_CHUNK_SIZE)],
+                ))
+                .await?;
+            self.remember(sent.event_id.clone());
+            self.event_ids.push(sent.event_id);
+            self.edited_len = text.len();
+            self.last_edit = Instant::now();
+        }
+
+        if !edit_due(text.len() - self.edited_len, self.last_edit.elapsed()) {
+            return Ok(());
+        }
+        let current = self
+            .event_ids
+            .last()
+            .expect("streamer always has a message");
+        self.edit(current, RoomMessageEventContent::text_plain(segment))
+            .await?;
+        self.edited_len = text.len();
+        self.last_edit = Instant::now();
+        Ok(())
+    }
+
+    /// Replace the streamed messages with the final markdown-rendered response,
+    /// signed when there's a `signer`. Earlier partial edits are never signed.
+    ///
+    /// Returns the number of messages the response occupies.
+    pub async fn finish(
+        &self,
+        response: &str,
+        channel_dir: &str,
+        signer: Option<&Signer>,
+    ) -> Result<usize> {
+        let chunks = chunk_message(response, MAX_CHUNK_SIZE);
+        let chunk_count = chunks.len();
+        let room_id = self.room.room_id().as_str();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let html = markdown_to_html(chunk);
+            let event_id = self.event_ids.get(i);
+            let content = MessageContent::html(chunk.as_str(), &html);
+            send_with_plain_fallback(content, |content| async move {
+                let content = signed_event(signer, room_id, content);
+                match event_id {
+                    Some(event_id) => self.edit(event_id, content).await,
+                    None => {
+                        let sent = self.room.send(content).await.map_err(send_error)?;
+                        self.remember(sent.event_id);
+                        Ok(())
+                    }
+                }
+            })
+            .await?;
+            metrics::record_message_sent();
+
+            log_matrix_message(
+                channel_dir,
+                room_id,
+                "response",
+                chunk,
+                Some(&html),
+                if chunk_count > 1 { Some(i) } else { None },
+                if chunk_count > 1 {
+                    Some(chunk_count)
+                } else {
+                    None
+                },
+                InvocationOrigin::User,
+            )
+            .await;
+        }
+
+        // Stream rolled over more often than the final chunking needs
+        for event_id in self.event_ids.iter().skip(chunk_count) {
+            self.edit(event_id, RoomMessageEventContent::text_plain("…"))
+                .await?;
+        }
+
+        Ok(chunk_count)
+    }
+
+    /// Mark the streamed message as abandoned when the agent fails mid-response
+    pub async fn cancel(self) {
+        let Some(current) = self.event_ids.last() else {
+            return;
+        };
+        if let Err(e) = self
+            .edit(
+                current,
+                RoomMessageEventContent::text_plain("⚠️ Response interrupted"),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to mark streamed response as interrupted");
+        }
+    }
+}
+
+/// `!stream [on|off]`: show or change whether responses stream in via message edits
+pub(crate) async fn handle_stream_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "stream", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let gorp_dir = std::path::Path::new(&ch.directory).join(".gorp");
+    let stream_file = gorp_dir.join("stream-responses");
+
+    let reply = match args.first().map(|s| s.to_lowercase()).as_deref() {
+        Some("on") | Some("enable") => {
+            if let Err(e) =
+                std::fs::create_dir_all(&gorp_dir).and_then(|_| std::fs::write(&stream_file, ""))
+            {
+                tf(locale, "stream.enable_failed", &[("error", &e.to_string())])
+            } else {
+                tracing::info!(channel = %ch.channel_name, "Response streaming enabled");
+                t(locale, "stream.enabled")
+            }
+        }
+        Some("off") | Some("disable") => match std::fs::remove_file(&stream_file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => tf(
+                locale,
+                "stream.disable_failed",
+                &[("error", &e.to_string())],
+            ),
+            _ => {
+                tracing::info!(channel = %ch.channel_name, "Response streaming disabled");
+                t(locale, "stream.disabled")
+            }
+        },
+        _ => {
+            let status = if is_streaming_enabled(&ch.directory) {
+                t(locale, "stream.status_on")
+            } else {
+                t(locale, "stream.status_off")
+            };
+            tf(locale, "stream.help", &[("status", &status)])
+        }
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_due_thresholds() {
+        assert!(!edit_due(0, Duration::from_secs(60)));
+        assert!(!edit_due(10, Duration::from_millis(100)));
+        assert!(edit_due(10, STREAM_EDIT_INTERVAL));
+        assert!(edit_due(STREAM_EDIT_CHARS, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_rollover_point_fits() {
+        assert_eq!(rollover_point("short", 100), 5);
+    }
+
+    #[test]
+    fn test_rollover_point_prefers_newline() {
+        let text = "first line\nsecond line that is long";
+        let cut = rollover_point(text, 20);
+        assert_eq!(&text[..cut], "first line\n");
+    }
+
+    #[test]
+    fn test_rollover_point_falls_back_to_space_then_hard_cut() {
+        let text = "alpha beta gamma";
+        assert_eq!(&text[..rollover_point(text, 12)], "alpha beta ");
+        let solid = "abcdefghij";
+        assert_eq!(rollover_point(solid, 4), 4);
+    }
+
+    #[test]
+    fn test_rollover_point_respects_char_boundaries() {
+        let text = "ééééé";
+        let cut = rollover_point(text, 3);
+        assert!(text.is_char_boundary(cut));
+        assert_eq!(cut, 2);
+    }
+}
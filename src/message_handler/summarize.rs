@@ -0,0 +1,244 @@
+// ABOUTME: On-demand recap of a channel's logged conversation for !summarize.
+// ABOUTME: Feeds the newest exchanges that fit a char budget to the channel's agent and returns its bullets.
+
+use anyhow::{Context, Result};
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf, tn},
+    session::{Channel, SessionStore},
+    usage::InvocationOrigin,
+    utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
+    warm_session::SharedWarmSessionManager,
+};
+
+use super::commands::channel_for_command;
+use super::history::{format_exchange, recent_exchanges, Exchange};
+
+/// Instruction placed ahead of the transcript the agent is asked to recap
+pub const SUMMARIZE_INSTRUCTION: &str = "Summarize the conversation transcript below as a \
+concise bulleted list: what was asked, what was done or decided, and anything left open. \
+Keep it to the points someone catching up would need. Reply with the bullets only, in Markdown.";
+
+/// A summarization prompt and how much of the log went into it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryPrompt {
+    pub prompt: String,
+    /// Exchanges included, in full or in part
+    pub exchanges: usize,
+    /// Whether older history was left out to fit the budget
+    pub truncated: bool,
+}
+
+/// The agent's recap and how much of the log it covers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub text: String,
+    pub exchanges: usize,
+    pub truncated: bool,
+}
+
+/// Build the prompt from the newest exchanges whose transcript fits in `max_chars`.
+///
+/// When even the newest exchange is over budget, only its end is kept.
+/// Returns None when there is nothing to summarize.
+pub fn build_summary_prompt(exchanges: &[Exchange], max_chars: usize) -> Option<SummaryPrompt> {
+    let mut kept: Vec<String> = Vec::new();
+    let mut used = 0;
+    let mut truncated = false;
+    for exchange in exchanges.iter().rev() {
+        let text = format_exchange(exchange);
+        let len = text.chars().count();
+        if used + len <= max_chars {
+            used += len;
+            kept.push(text);
+            continue;
+        }
+        truncated = true;
+        if kept.is_empty() && max_chars > 0 {
+            let tail: String = text.chars().skip(len - max_chars).collect();
+            kept.push(tail);
+        }
+        break;
+    }
+    if kept.is_empty() {
+        return None;
+    }
+
+    kept.reverse();
+    let note = if truncated {
+        "\n\nOnly the most recent part of the conversation fits; older history is left out."
+    } else {
+        ""
+    };
+    Some(SummaryPrompt {
+        prompt: format!(
+            "{}{}\n\n<transcript>\n{}\n</transcript>",
+            SUMMARIZE_INSTRUCTION,
+            note,
+            kept.join("\n---\n").trim()
+        ),
+        exchanges: kept.len(),
+        truncated,
+    })
+}
+
+/// Have the channel's agent recap its logged conversation.
+///
+/// Returns None when the channel has no logged history.
+pub async fn summarize_channel(
+    channel: &Channel,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    max_chars: usize,
+) -> Result<Option<Summary>> {
+    let exchanges = recent_exchanges(&channel.directory, usize::MAX)?;
+    let Some(request) = build_summary_prompt(&exchanges, max_chars) else {
+        return Ok(None);
+    };
+
+    let text = super::handle_text(
+        &request.prompt,
+        channel,
+        session_store,
+        warm_manager,
+        InvocationOrigin::User,
+    )
+    .await
+    .context("Agent failed to summarize the conversation")?;
+
+    Ok(Some(Summary {
+        text,
+        exchanges: request.exchanges,
+        truncated: request.truncated,
+    }))
+}
+
+/// `!summarize`: post a bullet recap of the channel's logged conversation
+pub(crate) async fn handle_summarize_command<C: ChatChannel>(
+    channel: &C,
+    session_store: &SessionStore,
+    warm_manager: &SharedWarmSessionManager,
+    max_chars: usize,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) = channel_for_command(channel, session_store, "summarize", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let typing = channel.typing_indicator();
+    if let Some(typing) = typing {
+        typing.set_typing(true).await?;
+    }
+    let summary = summarize_channel(&ch, session_store, warm_manager, max_chars).await;
+    if let Some(typing) = typing {
+        typing.set_typing(false).await?;
+    }
+
+    match summary {
+        Ok(Some(summary)) => {
+            let header = tn(
+                locale,
+                "summarize.header",
+                summary.exchanges,
+                &[("channel", &ch.channel_name)],
+            );
+            let mut text = format!("{}\n\n{}", header, summary.text.trim());
+            if summary.truncated {
+                text.push_str(&t(locale, "summarize.truncated"));
+            }
+            for chunk in chunk_message(&text, MAX_CHUNK_SIZE) {
+                let html = markdown_to_html(&chunk);
+                channel.send(MessageContent::html(&chunk, &html)).await?;
+            }
+            Ok(())
+        }
+        Ok(None) => {
+            let reply = tf(locale, "summarize.empty", &[("channel", &ch.channel_name)]);
+            channel.send(MessageContent::plain(reply)).await
+        }
+        Err(e) => {
+            tracing::warn!(
+                channel = %ch.channel_name,
+                error = %e,
+                "Summary failed"
+            );
+            let reply = tf(
+                locale,
+                "summarize.failed",
+                &[("error", &format!("{:#}", e))],
+            );
+            channel.send(MessageContent::plain(reply)).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_handler::history::{Role, Turn};
+
+    fn exchange(prompt: &str, answer: &str) -> Exchange {
+        let turn = |role, content: &str| Turn {
+            role,
+            timestamp: None,
+            content: content.to_string(),
+        };
+        Exchange {
+            turns: vec![turn(Role::User, prompt), turn(Role::Assistant, answer)],
+        }
+    }
+
+    #[test]
+    fn test_prompt_includes_whole_log_within_budget() {
+        let log = vec![exchange("deploy?", "deployed"), exchange("tests?", "green")];
+        let request = build_summary_prompt(&log, 10_000).unwrap();
+
+        assert_eq!(request.exchanges, 2);
+        assert!(!request.truncated);
+        assert!(request.prompt.starts_with(SUMMARIZE_INSTRUCTION));
+        assert!(!request.prompt.contains("older history is left out"));
+        let deploy = request.prompt.find("deploy?").unwrap();
+        let tests = request.prompt.find("tests?").unwrap();
+        assert!(deploy < tests, "exchanges stay oldest first");
+    }
+
+    #[test]
+    fn test_prompt_keeps_newest_exchanges_over_budget() {
+        let log = vec![
+            exchange("oldest question", &"x".repeat(500)),
+            exchange("newer question", "short"),
+            exchange("newest question", "short"),
+        ];
+        let budget = format_exchange(&log[2]).chars().count() * 2;
+        let request = build_summary_prompt(&log, budget).unwrap();
+
+        assert_eq!(request.exchanges, 2);
+        assert!(request.truncated);
+        assert!(request.prompt.contains("older history is left out"));
+        assert!(!request.prompt.contains("oldest question"));
+        assert!(request.prompt.contains("newest question"));
+    }
+
+    #[test]
+    fn test_prompt_cuts_an_oversized_newest_exchange() {
+        let log = vec![exchange(
+            "question",
+            &format!("{}tail end", "é".repeat(200)),
+        )];
+        let request = build_summary_prompt(&log, 50).unwrap();
+
+        assert_eq!(request.exchanges, 1);
+        assert!(request.truncated);
+        assert!(request.prompt.contains("tail end"));
+        assert!(!request.prompt.contains("question"));
+    }
+
+    #[test]
+    fn test_no_prompt_without_history() {
+        assert_eq!(build_summary_prompt(&[], 10_000), None);
+        assert_eq!(build_summary_prompt(&[exchange("q", "a")], 0), None);
+    }
+}
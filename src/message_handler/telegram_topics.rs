@@ -0,0 +1,62 @@
+// ABOUTME: !create inside a Telegram forum topic: binds the topic to a new channel of its own.
+// ABOUTME: The bot can't create topics, so the topic's `<chat_id>:<thread_id>` ID becomes the channel's room.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    metrics,
+    session::SessionStore,
+};
+
+use super::helpers::validate_channel_name;
+
+/// Whether `!create` in this channel should bind a Telegram forum topic
+pub(crate) fn is_topic<C: ChatChannel>(channel: &C) -> bool {
+    channel.platform_id() == "telegram" && channel.id().contains(':')
+}
+
+/// `!create <name>` in a Telegram topic: make the topic a channel
+pub(crate) async fn handle_topic_create_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    locale: &str,
+) -> Result<()> {
+    let Some(name) = args.first().map(|name| name.to_lowercase()) else {
+        return channel
+            .send(MessageContent::plain(t(locale, "create.topic_usage")))
+            .await;
+    };
+    if let Err(reason) = validate_channel_name(&name) {
+        let reply = tf(locale, "command.failed", &[("error", reason)]);
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+    if let Some(existing) = session_store.get_by_room(channel.id())? {
+        let reply = tf(
+            locale,
+            "create.topic_bound",
+            &[("channel", &existing.channel_name)],
+        );
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+    if session_store.get_by_name(&name)?.is_some() {
+        let reply = tf(locale, "create.topic_exists", &[("channel", &name)]);
+        return channel.send(MessageContent::plain(reply)).await;
+    }
+    let created = session_store.create_channel_on(channel.platform_id(), &name, channel.id())?;
+    metrics::increment_active_channels();
+    tracing::info!(
+        channel = %name,
+        topic = %channel.id(),
+        directory = %created.directory,
+        "Channel bound to Telegram topic"
+    );
+    let reply = tf(
+        locale,
+        "create.topic_created",
+        &[("channel", &name), ("directory", &created.directory)],
+    );
+    channel.send(MessageContent::plain(reply)).await
+}
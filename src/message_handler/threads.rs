@@ -0,0 +1,119 @@
+// ABOUTME: Matrix thread (m.thread) support for chat replies.
+// ABOUTME: Finds a message's thread root, adds the root as prompt context, and threads bot replies.
+
+use matrix_sdk::{
+    room::Room,
+    ruma::{
+        events::{
+            relation::Thread,
+            room::message::{OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent},
+            AnySyncMessageLikeEvent, AnySyncTimelineEvent,
+        },
+        EventId, OwnedEventId,
+    },
+};
+
+/// How much of the thread's root message is quoted back to the agent
+pub const THREAD_ROOT_PREVIEW_CHARS: usize = 500;
+
+/// The root event of the thread this message was sent in, if any
+pub fn thread_root(event: &OriginalSyncRoomMessageEvent) -> Option<OwnedEventId> {
+    match &event.content.relates_to {
+        Some(Relation::Thread(thread)) => Some(thread.event_id.clone()),
+        _ => None,
+    }
+}
+
+/// Body of a thread's root message, or None if it can't be fetched or isn't text
+pub async fn root_message_body(room: &Room, root: &EventId) -> Option<String> {
+    let event = match room.event(root, None).await {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::warn!(root = %root, error = %e, "Failed to fetch thread root");
+            return None;
+        }
+    };
+    match event.raw().deserialize() {
+        Ok(AnySyncTimelineEvent::MessageLike(AnySyncMessageLikeEvent::RoomMessage(msg))) => msg
+            .as_original()
+            .map(|original| original.content.body().to_string()),
+        _ => None,
+    }
+}
+
+/// Tell the agent which conversation a threaded reply belongs to.
+///
+/// The channel keeps one agent session, so each threaded prompt carries the
+/// thread's opening message to keep side discussions apart from the main one.
+pub fn with_thread_context(root_body: &str, prompt: &str) -> String {
+    let root = root_body.trim();
+    let mut preview: String = root.chars().take(THREAD_ROOT_PREVIEW_CHARS).collect();
+    if preview.len() < root.len() {
+        preview.push('…');
+    }
+    format!(
+        "[The user is replying in a thread about: {}]\n\n{}",
+        preview, prompt
+    )
+}
+
+/// Put a reply into the thread rooted at `root`.
+/// `latest` is the message being answered; clients without threads show it as the reply target.
+pub fn in_thread(
+    mut content: RoomMessageEventContent,
+    root: &EventId,
+    latest: &EventId,
+) -> RoomMessageEventContent {
+    content.relates_to = Some(Relation::Thread(Thread::plain(
+        root.to_owned(),
+        latest.to_owned(),
+    )));
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_thread_context_quotes_root() {
+        let prompt = with_thread_context("  Should we split the parser?\n", "yes, do it");
+        assert_eq!(
+            prompt,
+            "[The user is replying in a thread about: Should we split the parser?]\n\nyes, do it"
+        );
+    }
+
+    #[test]
+    fn test_with_thread_context_truncates_long_root() {
+        let root = "é".repeat(THREAD_ROOT_PREVIEW_CHARS + 10);
+        let prompt = with_thread_context(&root, "go on");
+        let quoted = prompt
+            .strip_prefix("[The user is replying in a thread about: ")
+            .and_then(|rest| rest.split_once(']'))
+            .map(|(quoted, _)| quoted)
+            .unwrap();
+        assert_eq!(quoted.chars().count(), THREAD_ROOT_PREVIEW_CHARS + 1);
+        assert!(quoted.ends_with('…'));
+    }
+
+    #[test]
+    fn test_in_thread_sets_thread_relation() {
+        let root: OwnedEventId = "$root:example.com".parse().unwrap();
+        let latest: OwnedEventId = "$latest:example.com".parse().unwrap();
+        let content = in_thread(
+            RoomMessageEventContent::text_plain("answer"),
+            &root,
+            &latest,
+        );
+
+        match content.relates_to {
+            Some(Relation::Thread(thread)) => {
+                assert_eq!(thread.event_id, root);
+                assert_eq!(thread.in_reply_to.map(|reply| reply.event_id), Some(latest));
+                assert!(thread.is_falling_back);
+            }
+            other => panic!("expected a thread relation, got {:?}", other),
+        }
+    }
+}
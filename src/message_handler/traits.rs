@@ -1,10 +1,18 @@
 // ABOUTME: Mock implementations for testing message handlers
-// ABOUTME: Provides MockChannel implementing ChatChannel for unit tests
+// ABOUTME: Provides MockChannel (ChatChannel) and MockPlatform (MessagingPlatform) for tests
 
 use anyhow::Result;
 use async_trait::async_trait;
-use gorp_core::traits::{ChatChannel, MessageContent, TypingIndicator};
+use gorp_core::traits::{
+    ActionButtons, AttachmentHandler, ChannelCreator, ChannelManager, ChannelTyping, ChatChannel,
+    ChatUser, ConfirmationProvider, EphemeralSender, EventStream, IncomingMessage, MessageContent,
+    MessagingPlatform, ReceiptSender, ThreadedPlatform, TypingIndicator,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 // =============================================================================
 // Mock Implementation for Testing
@@ -23,9 +31,13 @@ pub struct MockMessage {
 pub struct MockChannel {
     pub channel_id: String,
     pub channel_name: Option<String>,
+    /// Platform the channel claims to be on; "mock" unless a test sets it
+    pub platform_id: String,
     pub is_dm: bool,
     pub messages: Arc<Mutex<Vec<MockMessage>>>,
     pub typing_state: Arc<Mutex<bool>>,
+    /// Invites sent through the channel manager, as (channel_id, user_id)
+    pub invites: Arc<Mutex<Vec<(String, String)>>>,
 }
 
 impl MockChannel {
@@ -33,9 +45,11 @@ impl MockChannel {
         Self {
             channel_id: channel_id.to_string(),
             channel_name: None,
+            platform_id: "mock".to_string(),
             is_dm: false,
             messages: Arc::new(Mutex::new(Vec::new())),
             typing_state: Arc::new(Mutex::new(false)),
+            invites: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -43,9 +57,11 @@ impl MockChannel {
         Self {
             channel_id: channel_id.to_string(),
             channel_name: None,
+            platform_id: "mock".to_string(),
             is_dm: true,
             messages: Arc::new(Mutex::new(Vec::new())),
             typing_state: Arc::new(Mutex::new(false)),
+            invites: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -90,6 +106,7 @@ impl std::fmt::Debug for MockChannel {
         f.debug_struct("MockChannel")
             .field("channel_id", &self.channel_id)
             .field("channel_name", &self.channel_name)
+            .field("platform_id", &self.platform_id)
             .field("is_dm", &self.is_dm)
             .field("message_count", &message_count)
             .finish()
@@ -102,6 +119,10 @@ impl ChatChannel for MockChannel {
         &self.channel_id
     }
 
+    fn platform_id(&self) -> &str {
+        &self.platform_id
+    }
+
     fn name(&self) -> Option<String> {
         self.channel_name.clone()
     }
@@ -142,6 +163,40 @@ impl ChatChannel for MockChannel {
     fn attachment_handler(&self) -> Option<&dyn gorp_core::traits::AttachmentHandler> {
         None
     }
+
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ChannelManager for MockChannel {
+    async fn join(&self, _channel_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn leave(&self, _channel_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn invite(&self, channel_id: &str, user_id: &str) -> Result<()> {
+        self.invites
+            .lock()
+            .expect("MockChannel invites mutex poisoned")
+            .push((channel_id.to_string(), user_id.to_string()));
+        Ok(())
+    }
+
+    async fn members(&self, channel_id: &str) -> Result<Vec<ChatUser>> {
+        Ok(self
+            .invites
+            .lock()
+            .expect("MockChannel invites mutex poisoned")
+            .iter()
+            .filter(|(channel, _)| channel == channel_id)
+            .map(|(_, user)| ChatUser::new(user.clone()))
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -155,9 +210,435 @@ impl TypingIndicator for MockChannel {
     }
 }
 
+/// Mock messaging platform for driving `handle_incoming` end to end
+/// Records every outbound send; tests feed messages in through `inject`
+pub struct MockPlatform {
+    pub platform_id: &'static str,
+    pub bot_user_id: String,
+    /// Outbound messages as (channel_id, content), in send order
+    pub sent: Arc<Mutex<Vec<(String, MessageContent)>>>,
+    /// Thread each outbound message went to (None for top-level sends), parallel to `sent`
+    threads: Mutex<Vec<Option<String>>>,
+    /// Whether this platform offers action buttons (see `with_action_buttons`)
+    buttons: bool,
+    /// Button sets sent via ActionButtons, as (channel_id, text, actions)
+    actions: Mutex<Vec<(String, String, Vec<String>)>>,
+    /// Typing indicator changes as (channel_id, typing)
+    typing: Mutex<Vec<(String, bool)>>,
+    /// Attachment contents by source ID (see `with_file`)
+    files: HashMap<String, Vec<u8>>,
+    /// Names of channels created through ChannelCreator, in creation order
+    created: Mutex<Vec<String>>,
+    /// Read receipts as (channel_id, event_id)
+    receipts: Mutex<Vec<(String, String)>>,
+    /// Whether this platform sends ephemeral messages (see `with_ephemeral`)
+    ephemeral: bool,
+    /// Messages sent through EphemeralSender, as (channel_id, user_id, text)
+    ephemeral_sent: Mutex<Vec<(String, String, String)>>,
+    /// Whether this platform offers Confirm / Cancel buttons (see `with_confirmations`)
+    confirm: bool,
+    /// Confirmation prompts sent, as (channel_id, requester_id, text, token)
+    confirmations: Mutex<Vec<(String, String, String, String)>>,
+    incoming_tx: mpsc::UnboundedSender<IncomingMessage>,
+    incoming_rx: Mutex<Option<mpsc::UnboundedReceiver<IncomingMessage>>>,
+    next_event: AtomicU64,
+}
+
+impl MockPlatform {
+    pub fn new(platform_id: &'static str) -> Self {
+        let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+        Self {
+            platform_id,
+            bot_user_id: "@bot:example.com".to_string(),
+            sent: Arc::new(Mutex::new(Vec::new())),
+            threads: Mutex::new(Vec::new()),
+            buttons: false,
+            actions: Mutex::new(Vec::new()),
+            typing: Mutex::new(Vec::new()),
+            files: HashMap::new(),
+            created: Mutex::new(Vec::new()),
+            receipts: Mutex::new(Vec::new()),
+            ephemeral: false,
+            ephemeral_sent: Mutex::new(Vec::new()),
+            confirm: false,
+            confirmations: Mutex::new(Vec::new()),
+            incoming_tx,
+            incoming_rx: Mutex::new(Some(incoming_rx)),
+            next_event: AtomicU64::new(0),
+        }
+    }
+
+    /// Offer action buttons, like Telegram and Slack do
+    pub fn with_action_buttons(mut self) -> Self {
+        self.buttons = true;
+        self
+    }
+
+    /// Send messages only the addressed user sees, like Slack does
+    pub fn with_ephemeral(mut self) -> Self {
+        self.ephemeral = true;
+        self
+    }
+
+    /// Ask for Confirm / Cancel before destructive commands, like Telegram does
+    pub fn with_confirmations(mut self) -> Self {
+        self.confirm = true;
+        self
+    }
+
+    /// Serve `data` when an attachment with this source ID is downloaded
+    pub fn with_file(mut self, source_id: &str, data: &[u8]) -> Self {
+        self.files.insert(source_id.to_string(), data.to_vec());
+        self
+    }
+
+    /// Build a channel message from `sender` with a fresh event ID
+    pub fn message(&self, channel_id: &str, sender: &str, body: &str) -> IncomingMessage {
+        let n = self.next_event.fetch_add(1, Ordering::Relaxed) + 1;
+        IncomingMessage {
+            platform_id: self.platform_id.to_string(),
+            channel_id: channel_id.to_string(),
+            thread_id: None,
+            sender: ChatUser::new(sender),
+            body: body.to_string(),
+            is_direct: false,
+            formatted: false,
+            attachments: Vec::new(),
+            event_id: format!("$mock{}", n),
+            edits_event_id: None,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Queue a message for the stream returned by `event_stream`
+    pub fn inject(&self, msg: IncomingMessage) {
+        // The receiver lives in self until event_stream takes it, so this can't fail
+        let _ = self.incoming_tx.send(msg);
+    }
+
+    /// Get all outbound messages
+    pub fn sent(&self) -> Vec<(String, MessageContent)> {
+        self.sent
+            .lock()
+            .expect("MockPlatform sent mutex poisoned")
+            .clone()
+    }
+
+    /// Plain text of every outbound message, as (channel_id, text)
+    pub fn sent_text(&self) -> Vec<(String, String)> {
+        self.sent()
+            .into_iter()
+            .map(|(channel_id, content)| {
+                let text = match content {
+                    MessageContent::Plain(text) => text,
+                    MessageContent::Html { plain, .. } => plain,
+                    MessageContent::Attachment {
+                        filename, caption, ..
+                    } => caption.unwrap_or(filename),
+                };
+                (channel_id, text)
+            })
+            .collect()
+    }
+
+    /// Check if any outbound message contains the given text
+    pub fn has_sent_containing(&self, text: &str) -> bool {
+        self.sent_text().iter().any(|(_, sent)| sent.contains(text))
+    }
+
+    /// Thread of every outbound message in send order; None for top-level sends
+    pub fn sent_threads(&self) -> Vec<Option<String>> {
+        self.threads
+            .lock()
+            .expect("MockPlatform threads mutex poisoned")
+            .clone()
+    }
+
+    /// Button sets sent via ActionButtons, as (channel_id, text, actions)
+    pub fn sent_actions(&self) -> Vec<(String, String, Vec<String>)> {
+        self.actions
+            .lock()
+            .expect("MockPlatform actions mutex poisoned")
+            .clone()
+    }
+
+    /// Typing indicator changes as (channel_id, typing)
+    pub fn typing_events(&self) -> Vec<(String, bool)> {
+        self.typing
+            .lock()
+            .expect("MockPlatform typing mutex poisoned")
+            .clone()
+    }
+
+    /// Names of channels created through ChannelCreator, in creation order
+    pub fn created_channels(&self) -> Vec<String> {
+        self.created
+            .lock()
+            .expect("MockPlatform created mutex poisoned")
+            .clone()
+    }
+
+    /// Read receipts sent through ReceiptSender, as (channel_id, event_id)
+    pub fn read_receipts(&self) -> Vec<(String, String)> {
+        self.receipts
+            .lock()
+            .expect("MockPlatform receipts mutex poisoned")
+            .clone()
+    }
+
+    /// Messages sent through EphemeralSender, as (channel_id, user_id, text)
+    pub fn ephemeral_sent(&self) -> Vec<(String, String, String)> {
+        self.ephemeral_sent
+            .lock()
+            .expect("MockPlatform ephemeral mutex poisoned")
+            .clone()
+    }
+
+    /// Confirmation prompts sent, as (channel_id, requester_id, text, token)
+    pub fn sent_confirmations(&self) -> Vec<(String, String, String, String)> {
+        self.confirmations
+            .lock()
+            .expect("MockPlatform confirmations mutex poisoned")
+            .clone()
+    }
+
+    /// Clear all recorded sends
+    pub fn clear(&self) {
+        self.sent
+            .lock()
+            .expect("MockPlatform sent mutex poisoned")
+            .clear();
+        self.threads
+            .lock()
+            .expect("MockPlatform threads mutex poisoned")
+            .clear();
+        self.actions
+            .lock()
+            .expect("MockPlatform actions mutex poisoned")
+            .clear();
+        self.typing
+            .lock()
+            .expect("MockPlatform typing mutex poisoned")
+            .clear();
+    }
+
+    fn record(&self, channel_id: &str, thread_id: Option<&str>, content: MessageContent) {
+        self.sent
+            .lock()
+            .expect("MockPlatform sent mutex poisoned")
+            .push((channel_id.to_string(), content));
+        self.threads
+            .lock()
+            .expect("MockPlatform threads mutex poisoned")
+            .push(thread_id.map(str::to_string));
+    }
+}
+
+impl std::fmt::Debug for MockPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sent_count = self.sent.lock().map(|s| s.len()).unwrap_or(0);
+        f.debug_struct("MockPlatform")
+            .field("platform_id", &self.platform_id)
+            .field("bot_user_id", &self.bot_user_id)
+            .field("sent_count", &sent_count)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl MessagingPlatform for MockPlatform {
+    async fn event_stream(&self) -> Result<EventStream> {
+        let rx = self
+            .incoming_rx
+            .lock()
+            .expect("MockPlatform incoming mutex poisoned")
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("MockPlatform event stream already taken"))?;
+        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+    }
+
+    async fn send(&self, channel_id: &str, content: MessageContent) -> Result<()> {
+        self.record(channel_id, None, content);
+        Ok(())
+    }
+
+    fn bot_user_id(&self) -> &str {
+        &self.bot_user_id
+    }
+
+    fn platform_id(&self) -> &'static str {
+        self.platform_id
+    }
+
+    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
+        Some(self)
+    }
+
+    fn action_buttons(&self) -> Option<&dyn ActionButtons> {
+        if self.buttons {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn typing(&self) -> Option<&dyn ChannelTyping> {
+        Some(self)
+    }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        Some(self)
+    }
+
+    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
+        Some(self)
+    }
+
+    fn receipts(&self) -> Option<&dyn ReceiptSender> {
+        Some(self)
+    }
+
+    fn ephemeral(&self) -> Option<&dyn EphemeralSender> {
+        if self.ephemeral {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn confirmations(&self) -> Option<&dyn ConfirmationProvider> {
+        if self.confirm {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl ConfirmationProvider for MockPlatform {
+    async fn ask_confirmation(
+        &self,
+        channel_id: &str,
+        requester_id: &str,
+        text: &str,
+        token: &str,
+    ) -> Result<()> {
+        self.confirmations
+            .lock()
+            .expect("MockPlatform confirmations mutex poisoned")
+            .push((
+                channel_id.to_string(),
+                requester_id.to_string(),
+                text.to_string(),
+                token.to_string(),
+            ));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReceiptSender for MockPlatform {
+    async fn send_read_receipt(&self, channel_id: &str, message_id: &str) -> Result<()> {
+        self.receipts
+            .lock()
+            .expect("MockPlatform receipts mutex poisoned")
+            .push((channel_id.to_string(), message_id.to_string()));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EphemeralSender for MockPlatform {
+    async fn send_ephemeral(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        content: MessageContent,
+    ) -> Result<()> {
+        let text = match content {
+            MessageContent::Plain(text) | MessageContent::Html { plain: text, .. } => text,
+            MessageContent::Attachment { filename, .. } => filename,
+        };
+        self.ephemeral_sent
+            .lock()
+            .expect("MockPlatform ephemeral mutex poisoned")
+            .push((channel_id.to_string(), user_id.to_string(), text));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ChannelCreator for MockPlatform {
+    /// Channel IDs are "<platform>-channel-<n>", numbered from 1
+    async fn create_channel(&self, name: &str) -> Result<String> {
+        let mut created = self
+            .created
+            .lock()
+            .expect("MockPlatform created mutex poisoned");
+        created.push(name.to_string());
+        Ok(format!("{}-channel-{}", self.platform_id, created.len()))
+    }
+
+    async fn create_dm(&self, user_id: &str) -> Result<String> {
+        Ok(format!("{}-dm-{}", self.platform_id, user_id))
+    }
+}
+
+#[async_trait]
+impl AttachmentHandler for MockPlatform {
+    async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
+        let data = self
+            .files
+            .get(source_id)
+            .ok_or_else(|| anyhow::anyhow!("No mock file {}", source_id))?;
+        Ok((
+            source_id.to_string(),
+            data.clone(),
+            "application/octet-stream".to_string(),
+        ))
+    }
+}
+
+#[async_trait]
+impl ChannelTyping for MockPlatform {
+    async fn set_typing(&self, channel_id: &str, typing: bool) -> Result<()> {
+        self.typing
+            .lock()
+            .expect("MockPlatform typing mutex poisoned")
+            .push((channel_id.to_string(), typing));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ActionButtons for MockPlatform {
+    async fn send_actions(&self, channel_id: &str, text: &str, actions: &[String]) -> Result<()> {
+        self.actions
+            .lock()
+            .expect("MockPlatform actions mutex poisoned")
+            .push((channel_id.to_string(), text.to_string(), actions.to_vec()));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ThreadedPlatform for MockPlatform {
+    async fn send_threaded(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        content: MessageContent,
+    ) -> Result<()> {
+        self.record(channel_id, Some(thread_ts), content);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio_stream::StreamExt;
 
     #[tokio::test]
     async fn test_mock_channel_send_text() {
@@ -222,4 +703,60 @@ mod tests {
                 .expect("MockChannel typing_state mutex poisoned"));
         }
     }
+
+    #[tokio::test]
+    async fn test_mock_platform_records_sends_in_order() {
+        let platform = MockPlatform::new("telegram");
+        platform
+            .send("chat-1", MessageContent::plain("first"))
+            .await
+            .unwrap();
+        platform
+            .send_threaded(
+                "chat-2",
+                "1700000000.1",
+                MessageContent::html("second", "<b>second</b>"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            platform.sent_text(),
+            vec![
+                ("chat-1".to_string(), "first".to_string()),
+                ("chat-2".to_string(), "second".to_string()),
+            ]
+        );
+        assert!(platform.has_sent_containing("sec"));
+        assert_eq!(
+            platform.sent_threads(),
+            vec![None, Some("1700000000.1".to_string())]
+        );
+
+        platform.clear();
+        assert!(platform.sent().is_empty());
+        assert!(platform.sent_threads().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_platform_event_stream_yields_injected_messages() {
+        let platform = MockPlatform::new("slack");
+        let first = platform.message("C1", "U1", "hello");
+        let second = platform.message("C1", "U1", "again");
+        assert_ne!(first.event_id, second.event_id);
+
+        platform.inject(first);
+        platform.inject(second);
+
+        let mut stream = platform.event_stream().await.unwrap();
+        assert_eq!(stream.next().await.unwrap().body, "hello");
+        assert_eq!(stream.next().await.unwrap().body, "again");
+        assert!(platform.event_stream().await.is_err());
+    }
+
+    #[test]
+    fn test_mock_platform_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MockPlatform>();
+    }
 }
@@ -0,0 +1,97 @@
+// ABOUTME: !transcription: the language hint voice notes in a channel are transcribed with.
+// ABOUTME: Unset means the transcription service detects the language itself.
+
+use anyhow::Result;
+use gorp_core::traits::{ChatChannel, MessageContent};
+
+use crate::{
+    i18n::{t, tf},
+    session::SessionStore,
+};
+
+use super::commands::{channel_for_command, reply_privately};
+
+/// Words that put a channel back on auto-detection
+const AUTO_DETECT: &[&str] = &["auto", "reset", "off"];
+
+/// An ISO-639-1 (or 639-3) code: two or three ASCII letters
+fn is_language_code(code: &str) -> bool {
+    (2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// `!transcription [lang <code|auto>]`: show or change the channel's transcription language
+pub(crate) async fn handle_transcription_command<C: ChatChannel>(
+    channel: &C,
+    args: &[String],
+    session_store: &SessionStore,
+    transcription_configured: bool,
+    sender: &str,
+    is_dm: bool,
+    locale: &str,
+) -> Result<()> {
+    let Some(ch) =
+        channel_for_command(channel, session_store, "transcription", is_dm, locale).await?
+    else {
+        return Ok(());
+    };
+
+    let subcommand = args.first().map(|s| s.to_lowercase());
+    let language = args.get(1).map(|s| s.to_lowercase());
+    let reply = match (subcommand.as_deref(), language.as_deref()) {
+        (None, _) => {
+            let mut reply = match session_store.get_transcription_language(&ch.channel_name)? {
+                Some(language) => tf(
+                    locale,
+                    "transcription.status",
+                    &[("channel", &ch.channel_name), ("language", &language)],
+                ),
+                None => tf(
+                    locale,
+                    "transcription.status_auto",
+                    &[("channel", &ch.channel_name)],
+                ),
+            };
+            if !transcription_configured {
+                reply.push_str(&t(locale, "transcription.not_configured"));
+            }
+            reply
+        }
+        (Some("lang"), Some(language)) if AUTO_DETECT.contains(&language) => {
+            session_store.set_transcription_language(&ch.channel_name, None)?;
+            tracing::info!(channel = %ch.channel_name, sender, "Transcription language reset");
+            tf(
+                locale,
+                "transcription.auto",
+                &[("channel", &ch.channel_name)],
+            )
+        }
+        (Some("lang"), Some(language)) if is_language_code(language) => {
+            session_store.set_transcription_language(&ch.channel_name, Some(language))?;
+            tracing::info!(channel = %ch.channel_name, language, sender, "Transcription language set");
+            tf(
+                locale,
+                "transcription.set",
+                &[("channel", &ch.channel_name), ("language", language)],
+            )
+        }
+        _ => {
+            let usage = MessageContent::plain(t(locale, "transcription.usage"));
+            return reply_privately(channel, sender, usage).await;
+        }
+    };
+    channel.send(MessageContent::plain(reply)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_language_code() {
+        assert!(is_language_code("es"));
+        assert!(is_language_code("yue"));
+        assert!(!is_language_code("e"));
+        assert!(!is_language_code("spanish"));
+        assert!(!is_language_code("e5"));
+    }
+}
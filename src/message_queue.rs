@@ -0,0 +1,180 @@
+// ABOUTME: The bounded queue incoming messages wait in until the handler task takes them.
+// ABOUTME: A full queue holds senders for a while, then drops with a reply to allowed users only.
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::mpsc::{
+    error::{SendTimeoutError, TrySendError},
+    Sender,
+};
+
+use crate::{config::Config, metrics};
+
+/// Reply to a message that was dropped because the handler queue stayed full
+pub const QUEUE_FULL_REPLY: &str =
+    "⏳ I'm handling too many messages right now and couldn't take yours. Please try again in a moment.";
+
+/// Queue a message for the handler. When the queue is full the message waits up to
+/// `wait` for room; only then is it dropped, with a warning and `reply` called to
+/// send [`QUEUE_FULL_REPLY`]. Strangers get no reply, same as when the handler
+/// ignores them. Returns whether the message was queued.
+#[allow(clippy::too_many_arguments)]
+pub async fn enqueue<T, F, Fut>(
+    tx: &Sender<T>,
+    message: T,
+    wait: Duration,
+    config: &Config,
+    platform_id: &str,
+    room_id: &str,
+    sender: &str,
+    reply: F,
+) -> bool
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let dropped = match tx.try_send(message) {
+        Ok(()) => None,
+        Err(TrySendError::Closed(_)) => {
+            tracing::warn!(room_id, sender, "Handler channel closed, dropping message");
+            return false;
+        }
+        Err(TrySendError::Full(message)) => {
+            metrics::record_queue_full();
+            // The handler only updates the gauge when it takes a message, so a
+            // queue that stays full would otherwise keep reporting an old depth
+            record_depth(tx);
+            tracing::warn!(
+                room_id,
+                sender,
+                capacity = tx.max_capacity(),
+                "Handler queue full, waiting for room"
+            );
+            match tx.send_timeout(message, wait).await {
+                Ok(()) => None,
+                Err(SendTimeoutError::Closed(_)) => {
+                    tracing::warn!(room_id, sender, "Handler channel closed, dropping message");
+                    return false;
+                }
+                Err(SendTimeoutError::Timeout(message)) => Some(message),
+            }
+        }
+    };
+    record_depth(tx);
+
+    let Some(message) = dropped else {
+        return true;
+    };
+    metrics::record_error("message_dropped");
+    tracing::warn!(
+        room_id,
+        sender,
+        waited_secs = wait.as_secs(),
+        "Handler queue still full, dropping message"
+    );
+    if config.is_user_allowed(platform_id, sender) {
+        if let Err(e) = reply(message).await {
+            tracing::warn!(error = %e, room_id, "Failed to tell sender their message was dropped");
+        }
+    }
+    false
+}
+
+/// Set the queue depth gauge from how many slots are taken
+fn record_depth<T>(tx: &Sender<T>) {
+    metrics::set_message_queue_depth(tx.max_capacity() - tx.capacity());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    fn test_config() -> Config {
+        toml::from_str(
+            r#"
+            [webhook]
+            port = 13000
+
+            [workspace]
+            path = "./workspace"
+
+            [access]
+            users = ["@alice:matrix.org"]
+        "#,
+        )
+        .unwrap()
+    }
+
+    /// Queue `message` from `sender`, returning whether it was queued and what was replied to
+    async fn offer(
+        tx: &mpsc::Sender<u32>,
+        message: u32,
+        wait: Duration,
+        sender: &str,
+    ) -> (bool, Vec<u32>) {
+        let replies = Mutex::new(Vec::new());
+        let queued = enqueue(
+            tx,
+            message,
+            wait,
+            &test_config(),
+            "matrix",
+            "!room:matrix.org",
+            sender,
+            |dropped| {
+                replies.lock().unwrap().push(dropped);
+                async { anyhow::Ok(()) }
+            },
+        )
+        .await;
+        (queued, replies.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_waits_for_room() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+        let (tx, mut rx) = mpsc::channel(1);
+        tx.send(1).await.unwrap();
+        // As if the handler last saw an empty queue
+        metrics::set_message_queue_depth(0);
+
+        let handler = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            // The waiting sender refreshed the gauge before the handler got to it
+            assert!(handle.render().contains("gorp_message_queue_depth 1"));
+            rx.recv().await
+        };
+        let (offered, first) = tokio::join!(
+            offer(&tx, 2, Duration::from_secs(5), "@alice:matrix.org"),
+            handler
+        );
+
+        assert_eq!(offered, (true, vec![]));
+        assert_eq!(first, Some(1));
+        assert_eq!(rx.recv().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_message_gets_a_reply() {
+        let (tx, _rx) = mpsc::channel(1);
+        tx.send(1).await.unwrap();
+
+        let offered = offer(&tx, 2, Duration::from_millis(10), "@alice:matrix.org").await;
+        assert_eq!(offered, (false, vec![2]));
+    }
+
+    #[tokio::test]
+    async fn test_strangers_get_no_reply_when_dropped() {
+        let (tx, _rx) = mpsc::channel(1);
+        tx.send(1).await.unwrap();
+
+        let offered = offer(&tx, 2, Duration::from_millis(10), "@eve:matrix.org").await;
+        assert_eq!(offered, (false, vec![]));
+    }
+}
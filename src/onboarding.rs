@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use matrix_sdk::{room::Room, ruma::events::room::message::RoomMessageEventContent};
 use serde::{Deserialize, Serialize};
 
+use crate::i18n::{self, t, tf};
 use crate::session::SessionStore;
 use crate::utils::markdown_to_html;
 
@@ -123,7 +124,8 @@ pub async fn start_with_sender<S: OnboardingSender>(
     let state = OnboardingState::new();
     save_state(session_store, user_id, &state)?;
 
-    send_welcome_message_with_sender(sender).await
+    let locale = i18n::user_locale(session_store, user_id);
+    send_welcome_message_with_sender(sender, &locale).await
 }
 
 /// Handle a message during onboarding
@@ -172,19 +174,20 @@ pub async fn handle_message_with_sender<S: OnboardingSender>(
 
 /// Send the welcome message
 #[allow(dead_code)]
-async fn send_welcome_message(room: &Room) -> Result<()> {
-    send_welcome_message_with_sender(&MatrixOnboardingRoom(room)).await
+async fn send_welcome_message(room: &Room, locale: &str) -> Result<()> {
+    send_welcome_message_with_sender(&MatrixOnboardingRoom(room), locale).await
 }
 
 /// Send the welcome message (trait-based for testing)
-async fn send_welcome_message_with_sender<S: OnboardingSender>(sender: &S) -> Result<()> {
-    let msg = "👋 **Welcome to gorp!**\n\n\
-        I'll help you set up in about a minute. I need to:\n\
-        1. Verify your API connection\n\
-        2. Create your first channel\n\
-        3. Set up your workspace\n\n\
-        Ready? Reply **yes** to begin (or **skip** to do this later)";
+async fn send_welcome_message_with_sender<S: OnboardingSender>(
+    sender: &S,
+    locale: &str,
+) -> Result<()> {
+    send_markdown(sender, &t(locale, "onboarding.welcome")).await
+}
 
+/// Send a catalog message rendered from markdown
+async fn send_markdown<S: OnboardingSender>(sender: &S, msg: &str) -> Result<()> {
     let html = markdown_to_html(msg);
     sender.send_html(msg, &html).await
 }
@@ -214,6 +217,7 @@ async fn handle_welcome_response_with_sender<S: OnboardingSender>(
     message: &str,
 ) -> Result<bool> {
     let msg_lower = message.to_lowercase().trim().to_string();
+    let locale = i18n::user_locale(session_store, user_id);
 
     if msg_lower == "skip" || msg_lower == "later" || msg_lower == "no" {
         // Mark as completed (skipped)
@@ -221,40 +225,24 @@ async fn handle_welcome_response_with_sender<S: OnboardingSender>(
         state.step = OnboardingStep::Completed;
         save_state(session_store, user_id, &state)?;
 
-        let msg = "No problem! You can run **!setup** anytime to go through setup.\n\n\
-            Quick start: **!create <name>** to create a channel.";
-        let html = markdown_to_html(msg);
-        sender.send_html(msg, &html).await?;
+        send_markdown(sender, &t(&locale, "onboarding.skipped")).await?;
         return Ok(true);
     }
 
     if msg_lower == "yes" || msg_lower == "y" || msg_lower == "setup" || msg_lower == "start" {
         // Skip API validation for now and go straight to channel creation
         // TODO: Add actual API key validation when we have a test channel
-        let msg = "✅ API connection looks good!\n\n\
-            Now let's create your first channel.\n\n\
-            Each channel is a dedicated workspace with:\n\
-            - Separate conversation history\n\
-            - Its own project directory\n\
-            - Custom settings and tools\n\n\
-            **What would you like to call your first channel?**\n\
-            Suggestions: `pa`, `research`, `dev`\n\n\
-            _(Just type a name - letters, numbers, dashes only)_";
-
         // Move to CreateChannel step
         let mut state = get_state(session_store, user_id)?.unwrap_or_default();
         state.step = OnboardingStep::CreateChannel;
         save_state(session_store, user_id, &state)?;
 
-        let html = markdown_to_html(msg);
-        sender.send_html(msg, &html).await?;
+        send_markdown(sender, &t(&locale, "onboarding.api_ok")).await?;
         return Ok(true);
     }
 
     // Unrecognized response, repeat the question
-    let msg = "I didn't catch that. Reply **yes** to begin setup, or **skip** to do it later.";
-    let html = markdown_to_html(msg);
-    sender.send_html(msg, &html).await?;
+    send_markdown(sender, &t(&locale, "onboarding.welcome_unrecognized")).await?;
     Ok(true)
 }
 
@@ -283,6 +271,7 @@ async fn handle_api_key_response_with_sender<S: OnboardingSender>(
     message: &str,
 ) -> Result<bool> {
     let msg_lower = message.to_lowercase().trim().to_string();
+    let locale = i18n::user_locale(session_store, user_id);
 
     if msg_lower == "skip" {
         // Move to channel creation
@@ -290,45 +279,40 @@ async fn handle_api_key_response_with_sender<S: OnboardingSender>(
         state.step = OnboardingStep::CreateChannel;
         save_state(session_store, user_id, &state)?;
 
-        send_channel_prompt_with_sender(sender).await?;
+        send_channel_prompt_with_sender(sender, &locale).await?;
         return Ok(true);
     }
 
     if msg_lower == "retry" {
         // TODO: Actually retry API validation
-        let msg = "Retrying API connection...\n\n✅ Connection successful!\n\n";
-        let html = markdown_to_html(msg);
-        sender.send_html(msg, &html).await?;
+        send_markdown(sender, &t(&locale, "onboarding.api_retry")).await?;
 
         // Move to channel creation
         let mut state = get_state(session_store, user_id)?.unwrap_or_default();
         state.step = OnboardingStep::CreateChannel;
         save_state(session_store, user_id, &state)?;
 
-        send_channel_prompt_with_sender(sender).await?;
+        send_channel_prompt_with_sender(sender, &locale).await?;
         return Ok(true);
     }
 
     // Unrecognized, remind them
-    let msg = "Reply **retry** to try the API connection again, or **skip** to continue anyway.";
-    let html = markdown_to_html(msg);
-    sender.send_html(msg, &html).await?;
+    send_markdown(sender, &t(&locale, "onboarding.api_unrecognized")).await?;
     Ok(true)
 }
 
 /// Send the channel name prompt
 #[allow(dead_code)]
-async fn send_channel_prompt(room: &Room) -> Result<()> {
-    send_channel_prompt_with_sender(&MatrixOnboardingRoom(room)).await
+async fn send_channel_prompt(room: &Room, locale: &str) -> Result<()> {
+    send_channel_prompt_with_sender(&MatrixOnboardingRoom(room), locale).await
 }
 
 /// Send the channel name prompt (trait-based for testing)
-async fn send_channel_prompt_with_sender<S: OnboardingSender>(sender: &S) -> Result<()> {
-    let msg = "**What would you like to call your first channel?**\n\
-        Suggestions: `pa`, `research`, `dev`\n\n\
-        _(Just type a name - letters, numbers, dashes only)_";
-    let html = markdown_to_html(msg);
-    sender.send_html(msg, &html).await
+async fn send_channel_prompt_with_sender<S: OnboardingSender>(
+    sender: &S,
+    locale: &str,
+) -> Result<()> {
+    send_markdown(sender, &t(locale, "onboarding.channel_prompt")).await
 }
 
 /// Complete the onboarding flow and show success message
@@ -362,20 +346,13 @@ pub async fn complete_with_sender<S: OnboardingSender>(
     state.step = OnboardingStep::Completed;
     save_state(session_store, user_id, &state)?;
 
-    let msg = format!(
-        "✅ **Setup complete!**\n\n\
-        **Channel:** `{}`\n\
-        **Workspace:** `{}`\n\n\
-        **Quick commands:**\n\
-        - `!create <name>` - Create more channels\n\
-        - `!list` - See all channels\n\
-        - `!help` - Full command reference\n\n\
-        Go chat in your new channel!",
-        channel_name, workspace_path
+    let locale = i18n::user_locale(session_store, user_id);
+    let msg = tf(
+        &locale,
+        "onboarding.complete",
+        &[("channel", channel_name), ("workspace", workspace_path)],
     );
-
-    let html = markdown_to_html(&msg);
-    sender.send_html(&msg, &html).await
+    send_markdown(sender, &msg).await
 }
 
 /// Check if we're waiting for a channel name (for integration with message_handler)
@@ -833,6 +810,23 @@ mod tests {
         assert_eq!(new_state.step, OnboardingStep::Completed);
     }
 
+    #[tokio::test]
+    async fn test_onboarding_uses_user_locale() {
+        let (store, _temp) = create_test_store();
+        let sender = MockSender::new();
+        let user_id = "@test:example.com";
+        store.set_user_locale(user_id, Some("es")).unwrap();
+
+        start_with_sender(&sender, &store, user_id).await.unwrap();
+        handle_message_with_sender(&sender, &store, user_id, "skip")
+            .await
+            .unwrap();
+
+        assert!(sender.has_message_containing("Bienvenido a gorp"));
+        assert!(sender.has_message_containing("!setup"));
+        assert!(!sender.has_message_containing("Welcome to gorp"));
+    }
+
     #[tokio::test]
     async fn test_reset_and_start() {
         let (store, _temp) = create_test_store();
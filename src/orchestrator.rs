@@ -2,14 +2,62 @@
 // ABOUTME: DISPATCH is a built-in command handler for session lifecycle and supervisor operations.
 
 use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::Mutex;
 
-use crate::bus::{BusMessage, BusResponse, MessageBus, MessageSource, ResponseContent, SessionTarget};
+use crate::bus::{
+    BusMessage, BusResponse, MessageBus, MessageSource, ResponseContent, SessionTarget,
+};
+use crate::delivery::should_hold;
 use gorp_core::session::SessionStore;
-use gorp_core::warm_session::{prepare_session_async, send_prompt_with_handle, SharedWarmSessionManager};
+use gorp_core::usage::InvocationOrigin;
+use gorp_core::warm_session::{
+    prepare_session_async, send_prompt_with_handle, SharedWarmSessionManager,
+};
+
+/// Origin an agent invocation is attributed to, based on where its message came from.
+///
+/// The scheduler publishes under the `scheduler` API token hint; every other
+/// API source is a webhook.
+pub fn invocation_origin(source: &MessageSource) -> InvocationOrigin {
+    match source {
+        MessageSource::Platform { .. } | MessageSource::Web { .. } => InvocationOrigin::User,
+        MessageSource::Api { token_hint } if token_hint == "scheduler" => {
+            InvocationOrigin::Schedule
+        }
+        MessageSource::Api { .. } => InvocationOrigin::Webhook,
+    }
+}
+
+/// Directory in a channel workspace where silent scheduled runs leave their responses
+pub const QUIET_RESULTS_DIR: &str = "quiet-hours";
+
+/// Save the response to a silent run under the channel workspace instead of posting it
+pub fn save_silent_response(
+    workspace: &str,
+    msg_id: &str,
+    text: &str,
+    at: DateTime<Utc>,
+) -> std::io::Result<PathBuf> {
+    let dir = Path::new(workspace).join(QUIET_RESULTS_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let name: String = msg_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = dir.join(format!("{}_{}.md", at.format("%Y%m%d_%H%M%S"), name));
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
 
 /// DISPATCH commands parsed from message bodies.
 ///
@@ -102,7 +150,9 @@ impl DispatchCommand {
                 let session = parts.get(1).map(|s| s.to_string());
                 let message = parts.get(2).map(|s| s.to_string());
                 match (session, message) {
-                    (Some(session), Some(message)) if !session.is_empty() && !message.is_empty() => {
+                    (Some(session), Some(message))
+                        if !session.is_empty() && !message.is_empty() =>
+                    {
                         Self::Tell { session, message }
                     }
                     _ => Self::Unknown(input.to_string()),
@@ -217,7 +267,10 @@ impl Orchestrator {
                     });
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                    tracing::warn!(skipped = n, "Orchestrator lagged on inbound bus, skipped messages");
+                    tracing::warn!(
+                        skipped = n,
+                        "Orchestrator lagged on inbound bus, skipped messages"
+                    );
                     continue;
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => {
@@ -286,45 +339,42 @@ impl Orchestrator {
                 }
             }
 
-            DispatchCommand::List => {
-                match self.session_store.list_all() {
-                    Ok(channels) => {
-                        if channels.is_empty() {
-                            "No active sessions. Use !create <name> to create one.".to_string()
-                        } else {
-                            let mut lines = vec!["Active sessions:".to_string()];
-                            for ch in &channels {
-                                let started_marker = if ch.started { "*" } else { " " };
-                                lines.push(format!(
-                                    "  {} {} (session: {})",
-                                    started_marker,
-                                    ch.channel_name,
-                                    &ch.session_id[..8.min(ch.session_id.len())]
-                                ));
-                            }
+            DispatchCommand::List => match self.session_store.list_all() {
+                Ok(channels) => {
+                    if channels.is_empty() {
+                        "No active sessions. Use !create <name> to create one.".to_string()
+                    } else {
+                        let mut lines = vec!["Active sessions:".to_string()];
+                        for ch in &channels {
+                            let started_marker = if ch.started { "*" } else { " " };
                             lines.push(format!(
-                                "\n{} session(s) total. (* = started)",
-                                channels.len()
+                                "  {} {} (session: {})",
+                                started_marker,
+                                ch.channel_name,
+                                &ch.session_id[..8.min(ch.session_id.len())]
                             ));
-                            lines.join("\n")
                         }
+                        lines.push(format!(
+                            "\n{} session(s) total. (* = started)",
+                            channels.len()
+                        ));
+                        lines.join("\n")
                     }
-                    Err(e) => format!("Failed to list sessions: {}", e),
                 }
-            }
+                Err(e) => format!("Failed to list sessions: {}", e),
+            },
 
-            DispatchCommand::Status { name } => {
-                match self.session_store.get_by_name(&name) {
-                    Ok(Some(ch)) => {
-                        let bindings = self
-                            .session_store
-                            .list_bindings_for_session(&name)
-                            .unwrap_or_default();
-                        let binding_lines: Vec<String> = bindings
-                            .iter()
-                            .map(|(pid, cid)| format!("    {}:{}", pid, cid))
-                            .collect();
-                        format!(
+            DispatchCommand::Status { name } => match self.session_store.get_by_name(&name) {
+                Ok(Some(ch)) => {
+                    let bindings = self
+                        .session_store
+                        .list_bindings_for_session(&name)
+                        .unwrap_or_default();
+                    let binding_lines: Vec<String> = bindings
+                        .iter()
+                        .map(|(pid, cid)| format!("    {}:{}", pid, cid))
+                        .collect();
+                    format!(
                             "Session '{}':\n  Session ID: {}\n  Directory: {}\n  Started: {}\n  Created: {}\n  Bindings:\n{}",
                             ch.channel_name,
                             ch.session_id,
@@ -337,11 +387,10 @@ impl Orchestrator {
                                 binding_lines.join("\n")
                             }
                         )
-                    }
-                    Ok(None) => format!("Session '{}' not found", name),
-                    Err(e) => format!("Failed to get status for '{}': {}", name, e),
                 }
-            }
+                Ok(None) => format!("Session '{}' not found", name),
+                Err(e) => format!("Failed to get status for '{}': {}", name, e),
+            },
 
             DispatchCommand::Join { name } => {
                 match self.session_store.get_by_name(&name) {
@@ -375,10 +424,7 @@ impl Orchestrator {
                 self.bus
                     .unbind_channel_async(&platform_id, &channel_id)
                     .await;
-                match self
-                    .session_store
-                    .unbind_channel(&platform_id, &channel_id)
-                {
+                match self.session_store.unbind_channel(&platform_id, &channel_id) {
                     Ok(()) => {
                         "Channel unbound. Messages here will now route to DISPATCH.".to_string()
                     }
@@ -399,6 +445,8 @@ impl Orchestrator {
                             sender: msg.sender.clone(),
                             body: message,
                             timestamp: Utc::now(),
+                            silent: false,
+                            urgent: false,
                         });
                         format!("Message sent to session '{}'", session)
                     }
@@ -407,44 +455,43 @@ impl Orchestrator {
                 }
             }
 
-            DispatchCommand::Read {
-                session,
-                count: _,
-            } => match self.session_store.get_by_name(&session) {
-                Ok(Some(ch)) => {
-                    format!(
+            DispatchCommand::Read { session, count: _ } => {
+                match self.session_store.get_by_name(&session) {
+                    Ok(Some(ch)) => {
+                        format!(
                         "Session '{}': session_id={}, started={}, created_at={}\n(Message history not yet available)",
                         ch.channel_name, ch.session_id, ch.started, ch.created_at
                     )
+                    }
+                    Ok(None) => format!("Session '{}' not found", session),
+                    Err(e) => format!("Error: {}", e),
                 }
-                Ok(None) => format!("Session '{}' not found", session),
-                Err(e) => format!("Error: {}", e),
-            },
+            }
 
-            DispatchCommand::Broadcast { message } => {
-                match self.session_store.list_all() {
-                    Ok(channels) => {
-                        let mut sent_count = 0;
-                        for ch in channels {
-                            if !ch.is_dispatch_room {
-                                self.bus.publish_inbound(BusMessage {
-                                    id: format!("{}-bc-{}", msg.id, ch.channel_name),
-                                    source: msg.source.clone(),
-                                    session_target: SessionTarget::Session {
-                                        name: ch.channel_name.clone(),
-                                    },
-                                    sender: msg.sender.clone(),
-                                    body: message.clone(),
-                                    timestamp: Utc::now(),
-                                });
-                                sent_count += 1;
-                            }
+            DispatchCommand::Broadcast { message } => match self.session_store.list_all() {
+                Ok(channels) => {
+                    let mut sent_count = 0;
+                    for ch in channels {
+                        if !ch.is_dispatch_room {
+                            self.bus.publish_inbound(BusMessage {
+                                id: format!("{}-bc-{}", msg.id, ch.channel_name),
+                                source: msg.source.clone(),
+                                session_target: SessionTarget::Session {
+                                    name: ch.channel_name.clone(),
+                                },
+                                sender: msg.sender.clone(),
+                                body: message.clone(),
+                                timestamp: Utc::now(),
+                                silent: false,
+                                urgent: false,
+                            });
+                            sent_count += 1;
                         }
-                        format!("Message broadcast to {} session(s)", sent_count)
                     }
-                    Err(e) => format!("Failed to broadcast: {}", e),
+                    format!("Message broadcast to {} session(s)", sent_count)
                 }
-            }
+                Err(e) => format!("Failed to broadcast: {}", e),
+            },
 
             DispatchCommand::Unknown(text) => {
                 format!(
@@ -473,9 +520,7 @@ impl Orchestrator {
             None => {
                 self.bus.publish_response(BusResponse {
                     session_name,
-                    content: ResponseContent::Error(
-                        "No agent backend configured".to_string(),
-                    ),
+                    content: ResponseContent::Error("No agent backend configured".to_string()),
                     timestamp: Utc::now(),
                 });
                 return;
@@ -506,15 +551,14 @@ impl Orchestrator {
         };
 
         // Prepare warm session
-        let (handle, session_id, is_new) = match prepare_session_async(&warm_manager, &channel).await {
+        let (handle, session_id, is_new) = match prepare_session_async(&warm_manager, &channel)
+            .await
+        {
             Ok(result) => result,
             Err(e) => {
                 self.bus.publish_response(BusResponse {
                     session_name,
-                    content: ResponseContent::Error(format!(
-                        "Failed to prepare session: {}",
-                        e
-                    )),
+                    content: ResponseContent::Error(format!("Failed to prepare session: {}", e)),
                     timestamp: Utc::now(),
                 });
                 return;
@@ -523,13 +567,28 @@ impl Orchestrator {
 
         // Update session ID in store if it changed
         if is_new {
-            if let Err(e) = self.session_store.update_session_id(&channel.room_id, &session_id) {
+            if let Err(e) = self
+                .session_store
+                .update_session_id(&channel.room_id, &session_id)
+            {
                 tracing::error!(error = %e, "Failed to update session ID in store");
             }
         }
 
+        // Output generated while the channel's delivery window is closed is
+        // held in the outgoing queue instead of streamed
+        let window = self
+            .session_store
+            .get_delivery_window(&channel.channel_name)
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to load delivery window, delivering immediately");
+                None
+            });
+        let hold = should_hold(window.as_ref(), msg.priority(), Utc::now());
+
         // Send prompt and stream response
-        match send_prompt_with_handle(&handle, &session_id, &msg.body).await {
+        let origin = invocation_origin(&msg.source);
+        match send_prompt_with_handle(&handle, &session_id, &msg.body, origin).await {
             Ok(mut receiver) => {
                 let mut response_text = String::new();
 
@@ -537,13 +596,22 @@ impl Orchestrator {
                     match event {
                         gorp_agent::AgentEvent::Text(text) => {
                             response_text.push_str(&text);
-                            self.bus.publish_response(BusResponse {
-                                session_name: session_name.clone(),
-                                content: ResponseContent::Chunk(text),
-                                timestamp: Utc::now(),
-                            });
+                            if !hold && !msg.silent {
+                                self.bus.publish_response(BusResponse {
+                                    session_name: session_name.clone(),
+                                    content: ResponseContent::Chunk(text),
+                                    timestamp: Utc::now(),
+                                });
+                            }
                         }
-                        gorp_agent::AgentEvent::Result { text, .. } => {
+                        gorp_agent::AgentEvent::Result { text, usage, .. } => {
+                            if let Err(e) = self.session_store.record_usage(
+                                &channel.channel_name,
+                                origin,
+                                usage.as_ref(),
+                            ) {
+                                tracing::warn!(error = %e, "Failed to record channel usage");
+                            }
                             if response_text.is_empty() {
                                 response_text = text.clone();
                             }
@@ -558,10 +626,10 @@ impl Orchestrator {
                             return;
                         }
                         gorp_agent::AgentEvent::SessionChanged { new_session_id } => {
-                            if let Err(e) = self.session_store.update_session_id(
-                                &channel.room_id,
-                                &new_session_id,
-                            ) {
+                            if let Err(e) = self
+                                .session_store
+                                .update_session_id(&channel.room_id, &new_session_id)
+                            {
                                 tracing::error!(error = %e, "Failed to update changed session ID");
                             }
                             let mut session = handle.lock().await;
@@ -571,12 +639,45 @@ impl Orchestrator {
                     }
                 }
 
-                // Publish complete response
-                self.bus.publish_response(BusResponse {
-                    session_name: session_name.clone(),
-                    content: ResponseContent::Complete(response_text),
-                    timestamp: Utc::now(),
-                });
+                // Publish complete response, or queue it until the window opens.
+                // Silent runs keep theirs in the workspace.
+                if msg.silent {
+                    match save_silent_response(
+                        &channel.directory,
+                        &msg.id,
+                        &response_text,
+                        Utc::now(),
+                    ) {
+                        Ok(path) => tracing::info!(
+                            session = %session_name,
+                            path = %path.display(),
+                            "Saved silent response"
+                        ),
+                        Err(e) => {
+                            tracing::error!(error = %e, session = %session_name, "Failed to save silent response")
+                        }
+                    }
+                } else if hold {
+                    if let Err(e) =
+                        self.session_store
+                            .hold_message(&channel.channel_name, None, &response_text)
+                    {
+                        tracing::error!(error = %e, "Failed to hold response, delivering now");
+                        self.bus.publish_response(BusResponse {
+                            session_name: session_name.clone(),
+                            content: ResponseContent::Complete(response_text),
+                            timestamp: Utc::now(),
+                        });
+                    } else {
+                        tracing::info!(session = %session_name, "Response held until delivery window opens");
+                    }
+                } else {
+                    self.bus.publish_response(BusResponse {
+                        session_name: session_name.clone(),
+                        content: ResponseContent::Complete(response_text),
+                        timestamp: Utc::now(),
+                    });
+                }
 
                 // Mark session as started
                 if let Err(e) = self.session_store.mark_started(&channel.room_id) {
@@ -586,10 +687,7 @@ impl Orchestrator {
             Err(e) => {
                 self.bus.publish_response(BusResponse {
                     session_name,
-                    content: ResponseContent::Error(format!(
-                        "Failed to send prompt: {}",
-                        e
-                    )),
+                    content: ResponseContent::Error(format!("Failed to send prompt: {}", e)),
                     timestamp: Utc::now(),
                 });
             }
@@ -1,17 +1,20 @@
 // ABOUTME: Platform factory for hot-connecting gateways at runtime
-// ABOUTME: Creates platform instances from config for Telegram and Slack
+// ABOUTME: Creates platform instances from config for Telegram, Slack, IRC and Zulip
 
 use anyhow::Result;
 use gorp_core::MessagingPlatform;
 
 use crate::config::Config;
+use crate::scheduler::SchedulerStore;
 
 /// Create a platform instance from the current config.
-/// Supports hot-connect for Telegram and Slack.
+/// Supports hot-connect for Telegram, Slack, IRC and Zulip.
 /// Matrix requires complex setup (encryption, device verification) and is not supported.
 /// WhatsApp uses a sidecar process and is not supported.
+/// Platforms that manage schedules themselves (Slack's list buttons) use `scheduler_store`.
 pub async fn create_platform(
     #[allow(unused_variables)] config: &Config,
+    #[allow(unused_variables)] scheduler_store: &SchedulerStore,
     platform_id: &str,
 ) -> Result<Box<dyn MessagingPlatform>> {
     match platform_id {
@@ -34,13 +37,41 @@ pub async fn create_platform(
                 .slack
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("Slack not configured. Save config first."))?;
-            let platform = super::SlackPlatform::new(slack_config.clone()).await?;
+            let platform = super::SlackPlatform::new(slack_config.clone())
+                .await?
+                .with_scheduler(scheduler_store.clone(), std::sync::Arc::new(config.clone()));
             Ok(Box::new(platform))
         }
         #[cfg(not(feature = "slack"))]
         "slack" => {
             anyhow::bail!("Slack support not compiled. Build with --features slack")
         }
+        #[cfg(feature = "irc")]
+        "irc" => {
+            let irc_config = config
+                .irc
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("IRC not configured. Save config first."))?;
+            let platform = super::IrcPlatform::new(irc_config.clone()).await?;
+            Ok(Box::new(platform))
+        }
+        #[cfg(not(feature = "irc"))]
+        "irc" => {
+            anyhow::bail!("IRC support not compiled. Build with --features irc")
+        }
+        #[cfg(feature = "zulip")]
+        "zulip" => {
+            let zulip_config = config
+                .zulip
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Zulip not configured. Save config first."))?;
+            let platform = super::ZulipPlatform::new(zulip_config.clone()).await?;
+            Ok(Box::new(platform))
+        }
+        #[cfg(not(feature = "zulip"))]
+        "zulip" => {
+            anyhow::bail!("Zulip support not compiled. Build with --features zulip")
+        }
         "matrix" => {
             anyhow::bail!(
                 "Matrix requires complex setup (encryption, device verification). \
@@ -75,10 +106,15 @@ mod tests {
         .unwrap()
     }
 
+    fn test_scheduler() -> SchedulerStore {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        SchedulerStore::new(std::sync::Arc::new(std::sync::Mutex::new(conn)))
+    }
+
     #[tokio::test]
     async fn test_factory_rejects_matrix() {
         let config = test_config();
-        let result = create_platform(&config, "matrix").await;
+        let result = create_platform(&config, &test_scheduler(), "matrix").await;
         let err = result.err().expect("should error for matrix");
         assert!(err.to_string().contains("restart gorp"));
     }
@@ -86,7 +122,7 @@ mod tests {
     #[tokio::test]
     async fn test_factory_rejects_whatsapp() {
         let config = test_config();
-        let result = create_platform(&config, "whatsapp").await;
+        let result = create_platform(&config, &test_scheduler(), "whatsapp").await;
         let err = result.err().expect("should error for whatsapp");
         assert!(err.to_string().contains("sidecar"));
     }
@@ -94,7 +130,7 @@ mod tests {
     #[tokio::test]
     async fn test_factory_rejects_unknown() {
         let config = test_config();
-        let result = create_platform(&config, "discord").await;
+        let result = create_platform(&config, &test_scheduler(), "discord").await;
         let err = result.err().expect("should error for unknown");
         assert!(err.to_string().contains("Unknown platform"));
     }
@@ -0,0 +1,528 @@
+// ABOUTME: IRC platform implementation for gorp chat abstraction
+// ABOUTME: Joins configured channels, maps PRIVMSG to IncomingMessage and sends short reply lines
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use futures_util::StreamExt;
+use gorp_core::config::IrcConfig;
+use gorp_core::connection::{ConnectionEvent, ConnectionTracker};
+use gorp_core::traits::{
+    AttachmentHandler, ChatChannel, ChatPlatform, ChatUser, EventStream, IncomingMessage,
+    MessageContent, MessagingPlatform, PlatformConnectionState, ThreadedPlatform,
+};
+use irc::client::prelude::{Capability, Client, Command, Message, Response, Sender};
+use irc::proto::CapSubCommand;
+use std::sync::Mutex;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::utils::chunk_message;
+
+/// Longest text sent in one PRIVMSG. Servers cap a whole line at 512 bytes,
+/// including the `:nick!user@host PRIVMSG #channel :` prefix relayed to others.
+const MAX_LINE_BYTES: usize = 400;
+
+// =============================================================================
+// IrcPlatform - Implements MessagingPlatform + ChatPlatform (Tier 2)
+// =============================================================================
+
+/// IRC platform implementation using the `irc` crate
+pub struct IrcPlatform {
+    config: IrcConfig,
+    /// Outgoing message queue of the connection
+    sender: Sender,
+    /// The connection itself, taken by `event_stream()` since it can only be read once
+    client: Mutex<Option<Client>>,
+    /// Connection state for health monitoring
+    connection: ConnectionTracker,
+}
+
+impl IrcPlatform {
+    /// Connect to the configured server. Registration (and SASL, when
+    /// configured) happens once `event_stream()` starts reading.
+    pub async fn new(config: IrcConfig) -> Result<Self> {
+        let client = Client::from_config(client_config(&config))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to connect to IRC server {}:{}",
+                    config.server, config.port
+                )
+            })?;
+        let sender = client.sender();
+
+        tracing::info!(
+            server = %config.server,
+            nickname = %config.nickname,
+            "Connected to IRC server"
+        );
+
+        Ok(Self {
+            config,
+            sender,
+            client: Mutex::new(Some(client)),
+            connection: ConnectionTracker::new("irc", PlatformConnectionState::Connecting),
+        })
+    }
+
+    /// Update the platform's connection state
+    pub fn set_connection_state(&self, state: PlatformConnectionState) {
+        self.connection.set(state);
+    }
+
+    /// Send NICK/USER, holding registration open with a CAP request when SASL is configured
+    fn register(&self, client: &Client) -> Result<()> {
+        if sasl_credentials(&self.config).is_none() {
+            client
+                .identify()
+                .context("Failed to register with IRC server")?;
+            return Ok(());
+        }
+        let nick = self.config.nickname.clone();
+        client
+            .send_cap_req(&[Capability::Sasl])
+            .context("Failed to request SASL capability")?;
+        client
+            .send(Command::NICK(nick.clone()))
+            .context("Failed to send NICK")?;
+        client
+            .send(Command::USER(nick.clone(), "0".to_string(), nick))
+            .context("Failed to send USER")?;
+        Ok(())
+    }
+}
+
+fn client_config(config: &IrcConfig) -> irc::client::prelude::Config {
+    irc::client::prelude::Config {
+        nickname: Some(config.nickname.clone()),
+        server: Some(config.server.clone()),
+        port: Some(config.port),
+        use_tls: Some(config.use_tls),
+        // Joined by the client once the server finishes sending its MOTD
+        channels: config.channels.clone(),
+        ..Default::default()
+    }
+}
+
+/// SASL username and password, when both are configured
+fn sasl_credentials(config: &IrcConfig) -> Option<(&str, &str)> {
+    match (&config.sasl_username, &config.sasl_password) {
+        (Some(user), Some(pass)) => Some((user, pass)),
+        _ => None,
+    }
+}
+
+/// The AUTHENTICATE payload for SASL PLAIN: `authzid \0 authcid \0 password`,
+/// with the authorization identity left empty
+fn sasl_plain_payload(username: &str, password: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(format!("\0{}\0{}", username, password))
+}
+
+/// Channel names start with one of these; any other target is a nick
+fn is_channel_name(target: &str) -> bool {
+    target.starts_with(['#', '&', '+', '!'])
+}
+
+/// Convert a PRIVMSG into an IncomingMessage. Messages addressed to the bot's
+/// nick are queries and get answered by nick; everything else belongs to the
+/// channel it was sent to. IRC has no message IDs, so `fallback_id` is used
+/// unless the server tags the message with an IRCv3 `msgid`.
+fn incoming_message(
+    message: &Message,
+    bot_nick: &str,
+    fallback_id: String,
+) -> Option<IncomingMessage> {
+    let Command::PRIVMSG(target, text) = &message.command else {
+        return None;
+    };
+    let nick = message.source_nickname()?;
+    if nick.eq_ignore_ascii_case(bot_nick) {
+        return None;
+    }
+    // CTCP requests (VERSION, ACTION, ...) are wrapped in \x01
+    if text.starts_with('\u{1}') {
+        return None;
+    }
+
+    let tag = |name: &str| {
+        message
+            .tags
+            .as_ref()?
+            .iter()
+            .find(|t| t.0 == name)
+            .and_then(|t| t.1.clone())
+    };
+    let timestamp = tag("time")
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+        .map(|t| t.timestamp())
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+    let is_direct = !is_channel_name(target);
+    Some(IncomingMessage {
+        platform_id: "irc".to_string(),
+        channel_id: if is_direct { nick } else { target.as_str() }.to_string(),
+        thread_id: None,
+        sender: ChatUser::new(nick),
+        body: text.clone(),
+        is_direct,
+        formatted: false,
+        attachments: Vec::new(),
+        event_id: tag("msgid").unwrap_or(fallback_id),
+        edits_event_id: None,
+        timestamp,
+    })
+}
+
+/// Split text into non-empty lines that each fit in one PRIVMSG
+fn irc_lines(text: &str) -> Vec<String> {
+    chunk_message(text, MAX_LINE_BYTES)
+        .iter()
+        .flat_map(|chunk| chunk.lines())
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[async_trait]
+impl MessagingPlatform for IrcPlatform {
+    async fn event_stream(&self) -> Result<EventStream> {
+        let mut client = self
+            .client
+            .lock()
+            .map_err(|_| anyhow::anyhow!("IRC client lock poisoned"))?
+            .take()
+            .context("IRC event stream already started")?;
+        let mut stream = client.stream().context("Failed to open IRC stream")?;
+        self.register(&client)?;
+
+        let (tx, rx) = mpsc::channel(256);
+        let sender = self.sender.clone();
+        let nickname = self.config.nickname.clone();
+        let allowed_users = self.config.allowed_users.clone();
+        let sasl_payload =
+            sasl_credentials(&self.config).map(|(user, pass)| sasl_plain_payload(user, pass));
+        let connection = self.connection.clone();
+        let set_state = move |state: PlatformConnectionState| {
+            connection.set(state);
+        };
+
+        tokio::spawn(async move {
+            // The client owns the connection; keep it alive as long as the stream
+            let _client = client;
+            let mut seq: u64 = 0;
+
+            while let Some(item) = stream.next().await {
+                let message = match item {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::warn!(platform = "irc", error = %e, "IRC connection error");
+                        set_state(PlatformConnectionState::Disconnected {
+                            reason: e.to_string(),
+                        });
+                        return;
+                    }
+                };
+
+                match &message.command {
+                    Command::CAP(_, CapSubCommand::ACK, _, _) if sasl_payload.is_some() => {
+                        if let Err(e) = sender.send_sasl_plain() {
+                            tracing::warn!(platform = "irc", error = %e, "Failed to start SASL");
+                        }
+                    }
+                    Command::AUTHENTICATE(challenge) if challenge == "+" => {
+                        if let Some(ref payload) = sasl_payload {
+                            if let Err(e) = sender.send_sasl(payload) {
+                                tracing::warn!(
+                                    platform = "irc",
+                                    error = %e,
+                                    "Failed to send SASL credentials"
+                                );
+                            }
+                        }
+                    }
+                    Command::Response(Response::RPL_SASLSUCCESS, _) => {
+                        tracing::info!(platform = "irc", "SASL authentication succeeded");
+                        if let Err(e) =
+                            sender.send(Command::CAP(None, CapSubCommand::END, None, None))
+                        {
+                            tracing::warn!(
+                                platform = "irc",
+                                error = %e,
+                                "Failed to end CAP negotiation"
+                            );
+                        }
+                    }
+                    Command::CAP(_, CapSubCommand::NAK, _, _)
+                    | Command::Response(Response::ERR_SASLFAIL, _)
+                    | Command::Response(Response::ERR_SASLTOOLONG, _) => {
+                        // Carrying on unauthenticated could mean running under
+                        // someone else's nick, so give up on the connection
+                        tracing::error!(platform = "irc", "SASL authentication failed");
+                        set_state(PlatformConnectionState::AuthRequired);
+                        let _ = sender.send_quit("SASL authentication failed");
+                        return;
+                    }
+                    Command::Response(Response::RPL_WELCOME, _) => {
+                        tracing::info!(
+                            platform = "irc",
+                            nickname = %nickname,
+                            "Registered with IRC server"
+                        );
+                        set_state(PlatformConnectionState::Connected);
+                    }
+                    Command::PRIVMSG(..) => {
+                        seq += 1;
+                        let fallback_id =
+                            format!("irc-{}-{}", chrono::Utc::now().timestamp_millis(), seq);
+                        let Some(msg) = incoming_message(&message, &nickname, fallback_id) else {
+                            continue;
+                        };
+
+                        // Check nick allowlist
+                        if !allowed_users.is_empty()
+                            && !allowed_users
+                                .iter()
+                                .any(|u| u.eq_ignore_ascii_case(&msg.sender.id))
+                        {
+                            tracing::debug!(
+                                platform = "irc",
+                                nick = %msg.sender.id,
+                                "Skipping message from non-allowed nick"
+                            );
+                            continue;
+                        }
+
+                        if tx.send(msg).await.is_err() {
+                            tracing::warn!(platform = "irc", "Event stream receiver dropped");
+                            return;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            tracing::warn!(platform = "irc", "IRC connection closed");
+            set_state(PlatformConnectionState::Disconnected {
+                reason: "connection closed".to_string(),
+            });
+        });
+
+        let stream = ReceiverStream::new(rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn send(&self, channel_id: &str, content: MessageContent) -> Result<()> {
+        IrcChannel::new(channel_id, self.sender.clone())
+            .send(content)
+            .await
+    }
+
+    fn bot_user_id(&self) -> &str {
+        &self.config.nickname
+    }
+
+    fn platform_id(&self) -> &'static str {
+        "irc"
+    }
+
+    fn is_self(&self, user_id: &str) -> bool {
+        user_id.eq_ignore_ascii_case(&self.config.nickname)
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        tracing::info!(platform = "irc", "Shutting down IRC platform");
+        if let Err(e) = self.sender.send_quit("Shutting down") {
+            tracing::debug!(platform = "irc", error = %e, "Failed to send QUIT");
+        }
+        self.set_connection_state(PlatformConnectionState::Disconnected {
+            reason: "shutdown".to_string(),
+        });
+        Ok(())
+    }
+
+    fn connection_state(&self) -> PlatformConnectionState {
+        self.connection.get()
+    }
+
+    fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+        Some(self.connection.subscribe())
+    }
+
+    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
+        // IRC has no threads
+        None
+    }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        // IRC messages are text only
+        None
+    }
+}
+
+#[async_trait]
+impl ChatPlatform for IrcPlatform {
+    type Channel = IrcChannel;
+
+    async fn get_channel(&self, id: &str) -> Option<Self::Channel> {
+        Some(IrcChannel::new(id, self.sender.clone()))
+    }
+
+    async fn joined_channels(&self) -> Vec<Self::Channel> {
+        self.config
+            .channels
+            .iter()
+            .map(|c| IrcChannel::new(c, self.sender.clone()))
+            .collect()
+    }
+}
+
+// =============================================================================
+// IrcChannel - a channel or a query with one nick
+// =============================================================================
+
+/// An IRC channel, or a private query addressed by nick
+#[derive(Debug, Clone)]
+pub struct IrcChannel {
+    target: String,
+    sender: Sender,
+}
+
+impl IrcChannel {
+    pub fn new(target: impl Into<String>, sender: Sender) -> Self {
+        Self {
+            target: target.into(),
+            sender,
+        }
+    }
+}
+
+#[async_trait]
+impl ChatChannel for IrcChannel {
+    fn id(&self) -> &str {
+        &self.target
+    }
+
+    fn platform_id(&self) -> &str {
+        "irc"
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.target.clone())
+    }
+
+    async fn is_direct(&self) -> bool {
+        !is_channel_name(&self.target)
+    }
+
+    async fn send(&self, content: MessageContent) -> Result<()> {
+        let text = match content {
+            MessageContent::Plain(text) => text,
+            MessageContent::Html { plain, .. } => plain,
+            // No file transfer the bot can use, so only say what was left out
+            MessageContent::Attachment {
+                filename, caption, ..
+            } => match caption {
+                Some(caption) => format!("{} [attachment not sent: {}]", caption, filename),
+                None => format!("[attachment not sent: {}]", filename),
+            },
+        };
+        for line in irc_lines(&text) {
+            self.sender
+                .send_privmsg(&self.target, &line)
+                .context("Failed to send IRC message")?;
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Message {
+        line.parse().unwrap()
+    }
+
+    #[test]
+    fn test_irc_platform_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<IrcPlatform>();
+        assert_send_sync::<IrcChannel>();
+    }
+
+    #[test]
+    fn test_channel_privmsg_is_not_direct() {
+        let message = parse(":alice!a@host PRIVMSG #gorp :hello there\r\n");
+        let msg = incoming_message(&message, "gorpbot", "fallback".to_string()).unwrap();
+        assert_eq!(msg.platform_id, "irc");
+        assert_eq!(msg.channel_id, "#gorp");
+        assert_eq!(msg.sender.id, "alice");
+        assert_eq!(msg.body, "hello there");
+        assert!(!msg.is_direct);
+        assert_eq!(msg.event_id, "fallback");
+    }
+
+    #[test]
+    fn test_query_is_direct_and_answered_by_nick() {
+        let message = parse(":alice!a@host PRIVMSG GorpBot :hi\r\n");
+        let msg = incoming_message(&message, "gorpbot", "x".to_string()).unwrap();
+        assert!(msg.is_direct);
+        assert_eq!(msg.channel_id, "alice");
+    }
+
+    #[test]
+    fn test_msgid_and_server_time_tags_are_used() {
+        let message = parse(
+            "@msgid=abc123;time=2026-03-04T10:02:05.000Z :alice!a@host PRIVMSG #gorp :hi\r\n",
+        );
+        let msg = incoming_message(&message, "gorpbot", "x".to_string()).unwrap();
+        assert_eq!(msg.event_id, "abc123");
+        assert_eq!(msg.timestamp, 1772618525);
+    }
+
+    #[test]
+    fn test_own_messages_ctcp_and_other_commands_are_skipped() {
+        let own = parse(":GorpBot!g@host PRIVMSG #gorp :echo\r\n");
+        assert!(incoming_message(&own, "gorpbot", "x".to_string()).is_none());
+
+        let action = parse(":alice!a@host PRIVMSG #gorp :\u{1}ACTION waves\u{1}\r\n");
+        assert!(incoming_message(&action, "gorpbot", "x".to_string()).is_none());
+
+        let join = parse(":alice!a@host JOIN #gorp\r\n");
+        assert!(incoming_message(&join, "gorpbot", "x".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_irc_lines_fit_and_skip_blank_lines() {
+        let text = format!("first\n\n{}\n   \nlast", "word ".repeat(200));
+        let lines = irc_lines(&text);
+        assert_eq!(lines.first().map(String::as_str), Some("first"));
+        assert_eq!(lines.last().map(String::as_str), Some("last"));
+        assert!(lines.len() > 3);
+        assert!(lines
+            .iter()
+            .all(|l| !l.trim().is_empty() && l.len() <= MAX_LINE_BYTES));
+    }
+
+    #[test]
+    fn test_sasl_plain_payload() {
+        // base64 of "\0gorp\0hunter2"
+        assert_eq!(
+            sasl_plain_payload("gorp", "hunter2"),
+            "AGdvcnAAaHVudGVyMg=="
+        );
+    }
+
+    #[test]
+    fn test_is_channel_name() {
+        assert!(is_channel_name("#gorp"));
+        assert!(is_channel_name("&local"));
+        assert!(!is_channel_name("alice"));
+    }
+}
@@ -1,32 +1,57 @@
 // ABOUTME: Matrix channel implementation wrapping matrix_sdk Room
-// ABOUTME: Implements ChatChannel, TypingIndicator, and AttachmentHandler traits
+// ABOUTME: Implements ChatChannel, TypingIndicator, AttachmentHandler and ChannelManager traits
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use gorp_core::traits::{
-    AttachmentHandler, ChatChannel, MessageContent, TypingIndicator,
+    AttachmentHandler, ChannelManager, ChatChannel, ChatUser, MessageContent, TypingIndicator,
 };
 use matrix_sdk::{
     media::{MediaFormat, MediaRequestParameters},
     room::Room,
-    ruma::events::room::{
-        message::{FileMessageEventContent, MessageType, RoomMessageEventContent},
-        MediaSource,
+    ruma::{
+        api::client::receipt::create_receipt::v3::ReceiptType,
+        events::{
+            receipt::ReceiptThread,
+            relation::Thread,
+            room::{
+                message::{
+                    FileMessageEventContent, ImageMessageEventContent, MessageType, Relation,
+                    RoomMessageEventContent,
+                },
+                ImageInfo, MediaSource,
+            },
+        },
+        OwnedEventId, OwnedRoomId, UInt,
     },
     Client,
 };
 use std::fmt;
 
+use super::fallback::{send_error, send_with_plain_fallback};
+use super::signing::{signed_event, Signer};
+
 /// Matrix-specific implementation of ChatChannel
 #[derive(Clone)]
 pub struct MatrixChannel {
     room: Room,
     client: Client,
+    signer: Option<Signer>,
 }
 
 impl MatrixChannel {
     pub fn new(room: Room, client: Client) -> Self {
-        Self { room, client }
+        Self {
+            room,
+            client,
+            signer: None,
+        }
+    }
+
+    /// Sign formatted messages sent through this channel with `signer`
+    pub fn signed_by(mut self, signer: Option<Signer>) -> Self {
+        self.signer = signer;
+        self
     }
 
     /// Get the underlying Matrix room
@@ -38,34 +63,14 @@ impl MatrixChannel {
     pub fn client(&self) -> &Client {
         &self.client
     }
-}
-
-impl fmt::Debug for MatrixChannel {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("MatrixChannel")
-            .field("room_id", &self.room.room_id().as_str())
-            .finish()
-    }
-}
-
-#[async_trait]
-impl ChatChannel for MatrixChannel {
-    fn id(&self) -> &str {
-        self.room.room_id().as_str()
-    }
-
-    fn name(&self) -> Option<String> {
-        self.room.name()
-    }
 
-    async fn is_direct(&self) -> bool {
-        self.room.is_direct().await.unwrap_or(false)
-    }
-
-    async fn send(&self, content: MessageContent) -> Result<()> {
+    /// Build the room message for `content`, uploading attachments first
+    async fn room_content(&self, content: MessageContent) -> Result<RoomMessageEventContent> {
         let msg_content = match content {
             MessageContent::Plain(text) => RoomMessageEventContent::text_plain(text),
-            MessageContent::Html { plain, html } => RoomMessageEventContent::text_html(plain, html),
+            content @ MessageContent::Html { .. } => {
+                signed_event(self.signer.as_ref(), self.room.room_id().as_str(), content)
+            }
             MessageContent::Attachment {
                 filename,
                 data,
@@ -75,6 +80,7 @@ impl ChatChannel for MatrixChannel {
                 let content_type: mime_guess::mime::Mime = mime_type
                     .parse()
                     .unwrap_or(mime_guess::mime::APPLICATION_OCTET_STREAM);
+                let size = data.len();
 
                 // Upload the file to Matrix media server
                 let response = self
@@ -84,20 +90,84 @@ impl ChatChannel for MatrixChannel {
                     .await
                     .context("Failed to upload attachment")?;
 
-                // Create file message with the MXC URI
-                let body = caption.unwrap_or_else(|| filename.clone());
                 let source = MediaSource::Plain(response.content_uri);
-                let file_content = FileMessageEventContent::new(body, source);
-
-                RoomMessageEventContent::new(MessageType::File(file_content))
+                RoomMessageEventContent::new(attachment_message(
+                    filename, &mime_type, size, caption, source,
+                ))
             }
         };
+        Ok(msg_content)
+    }
 
-        self.room
-            .send(msg_content)
-            .await
-            .context("Failed to send message")?;
+    /// Send `content` as a reply inside the thread rooted at `thread_root` (m.thread)
+    pub async fn send_in_thread(&self, thread_root: &str, content: MessageContent) -> Result<()> {
+        self.post(content, Some(thread_root)).await?;
+        Ok(())
+    }
+
+    /// Send `content`, in the thread rooted at `thread_root` if there is one,
+    /// and return the ID of the event it was sent as
+    pub async fn post(
+        &self,
+        content: MessageContent,
+        thread_root: Option<&str>,
+    ) -> Result<OwnedEventId> {
+        let root: Option<OwnedEventId> = thread_root
+            .map(|root| root.parse().context("Invalid thread root event ID"))
+            .transpose()?;
+        send_with_plain_fallback(content, |content| {
+            let root = root.clone();
+            async move {
+                let msg_content = self.room_content(content).await?;
+                let (msg_content, failure) = match root {
+                    Some(root) => (
+                        in_thread(msg_content, root),
+                        "Failed to send threaded message",
+                    ),
+                    None => (msg_content, "Failed to send message"),
+                };
+
+                let response = self
+                    .room
+                    .send(msg_content)
+                    .await
+                    .map_err(send_error)
+                    .context(failure)?;
+                Ok(response.event_id)
+            }
+        })
+        .await
+    }
+}
+
+impl fmt::Debug for MatrixChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatrixChannel")
+            .field("room_id", &self.room.room_id().as_str())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ChatChannel for MatrixChannel {
+    fn id(&self) -> &str {
+        self.room.room_id().as_str()
+    }
+
+    fn platform_id(&self) -> &str {
+        "matrix"
+    }
+
+    fn name(&self) -> Option<String> {
+        self.room.name()
+    }
 
+    async fn is_direct(&self) -> bool {
+        self.room.is_direct().await.unwrap_or(false)
+    }
+
+    async fn send(&self, content: MessageContent) -> Result<()> {
+        self.post(content, None).await?;
         Ok(())
     }
 
@@ -109,6 +179,10 @@ impl ChatChannel for MatrixChannel {
         Some(self)
     }
 
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        Some(self)
+    }
+
     async fn member_count(&self) -> Result<usize> {
         let members = self
             .room
@@ -133,33 +207,174 @@ impl TypingIndicator for MatrixChannel {
 #[async_trait]
 impl AttachmentHandler for MatrixChannel {
     async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
-        let source: MediaSource = serde_json::from_str(source_id)
-            .context("source_id must be a JSON-serialized MediaSource")?;
+        download_media(&self.client, source_id).await
+    }
+}
 
-        let request = MediaRequestParameters {
-            source: source.clone(),
-            format: MediaFormat::File,
-        };
+/// Room management through the channel's client, so commands handled with a
+/// MatrixChannel can invite people the same way the generic path does
+#[async_trait]
+impl ChannelManager for MatrixChannel {
+    async fn join(&self, channel_id: &str) -> Result<()> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        self.client
+            .join_room_by_id(&room_id)
+            .await
+            .context("Failed to join room")?;
+        Ok(())
+    }
+
+    async fn leave(&self, channel_id: &str) -> Result<()> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        if let Some(room) = self.client.get_room(&room_id) {
+            room.leave().await.context("Failed to leave room")?;
+        }
+        Ok(())
+    }
+
+    async fn invite(&self, channel_id: &str, user_id: &str) -> Result<()> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        super::client::invite_user(&self.client, &room_id, user_id).await
+    }
 
-        let data = self
-            .client
-            .media()
-            .get_media_content(&request, true)
+    async fn members(&self, channel_id: &str) -> Result<Vec<ChatUser>> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        let room = self.client.get_room(&room_id).context("Room not found")?;
+        let members = room
+            .members(matrix_sdk::RoomMemberships::ACTIVE)
             .await
-            .context("Failed to download attachment")?;
+            .context("Failed to get room members")?;
+        Ok(members
+            .into_iter()
+            .map(|m| ChatUser {
+                id: m.user_id().to_string(),
+                display_name: m.display_name().map(|n| n.to_string()),
+            })
+            .collect())
+    }
+}
 
-        // Extract filename from the source or use a default
-        let filename = match &source {
-            MediaSource::Plain(uri) => uri.as_str().rsplit('/').next().unwrap_or("attachment"),
-            MediaSource::Encrypted(file) => {
-                file.url.as_str().rsplit('/').next().unwrap_or("attachment")
-            }
+/// Message for an uploaded attachment. Images go out as m.image so clients
+/// show them inline; everything else is an m.file.
+pub(crate) fn attachment_message(
+    filename: String,
+    mime_type: &str,
+    size: usize,
+    caption: Option<String>,
+    source: MediaSource,
+) -> MessageType {
+    let body = caption.unwrap_or(filename);
+    if mime_type.starts_with("image/") {
+        let mut info = ImageInfo::new();
+        info.mimetype = Some(mime_type.to_string());
+        info.size = UInt::new(size as u64);
+        let mut image = ImageMessageEventContent::new(body, source);
+        image.info = Some(Box::new(info));
+        MessageType::Image(image)
+    } else {
+        MessageType::File(FileMessageEventContent::new(body, source))
+    }
+}
+
+/// Attach an m.thread relation to `root`.
+/// Clients without thread support show the message as a reply to the root.
+fn in_thread(mut content: RoomMessageEventContent, root: OwnedEventId) -> RoomMessageEventContent {
+    content.relates_to = Some(Relation::Thread(Thread::plain(root.clone(), root)));
+    content
+}
+
+/// Mark `event_id` and everything before it in `room` as read by the bot
+pub async fn send_read_receipt(room: &Room, event_id: OwnedEventId) -> Result<()> {
+    room.send_single_receipt(ReceiptType::Read, ReceiptThread::Unthreaded, event_id)
+        .await
+        .context("Failed to send read receipt")
+}
+
+/// Fetch media named by a JSON-serialized MediaSource, as produced by the event converter
+pub(crate) async fn download_media(
+    client: &Client,
+    source_id: &str,
+) -> Result<(String, Vec<u8>, String)> {
+    let source: MediaSource = serde_json::from_str(source_id)
+        .context("source_id must be a JSON-serialized MediaSource")?;
+
+    let request = MediaRequestParameters {
+        source: source.clone(),
+        format: MediaFormat::File,
+    };
+
+    let data = client
+        .media()
+        .get_media_content(&request, true)
+        .await
+        .context("Failed to download attachment")?;
+
+    // Extract filename from the source or use a default
+    let filename = match &source {
+        MediaSource::Plain(uri) => uri.as_str().rsplit('/').next().unwrap_or("attachment"),
+        MediaSource::Encrypted(file) => {
+            file.url.as_str().rsplit('/').next().unwrap_or("attachment")
         }
-        .to_string();
+    }
+    .to_string();
 
-        // Default mime type - caller should detect from content if needed
-        let mime_type = "application/octet-stream".to_string();
+    // Default mime type - caller should detect from content if needed
+    let mime_type = "application/octet-stream".to_string();
+
+    Ok((filename, data, mime_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> MediaSource {
+        MediaSource::Plain("mxc://example.org/abc".into())
+    }
+
+    #[test]
+    fn test_image_attachment_is_sent_as_image() {
+        let msgtype = attachment_message(
+            "chart.png".to_string(),
+            "image/png",
+            2048,
+            Some("Weekly totals".to_string()),
+            source(),
+        );
+        let MessageType::Image(image) = msgtype else {
+            panic!("expected m.image, got {:?}", msgtype);
+        };
+        assert_eq!(image.body, "Weekly totals");
+        let info = image.info.unwrap();
+        assert_eq!(info.mimetype.as_deref(), Some("image/png"));
+        assert_eq!(info.size, UInt::new(2048));
+    }
+
+    #[test]
+    fn test_other_attachment_is_sent_as_file() {
+        let msgtype = attachment_message(
+            "report.pdf".to_string(),
+            "application/pdf",
+            10,
+            None,
+            source(),
+        );
+        let MessageType::File(file) = msgtype else {
+            panic!("expected m.file, got {:?}", msgtype);
+        };
+        assert_eq!(file.body, "report.pdf");
+    }
 
-        Ok((filename, data, mime_type))
+    #[test]
+    fn test_thread_reply_carries_m_thread_relation() {
+        let root: OwnedEventId = "$root:example.org".parse().unwrap();
+        let content = in_thread(RoomMessageEventContent::text_plain("answer"), root);
+        let json = serde_json::to_value(&content).unwrap();
+        let relation = &json["m.relates_to"];
+        assert_eq!(relation["rel_type"], "m.thread");
+        assert_eq!(relation["event_id"], "$root:example.org");
+        assert_eq!(relation["is_falling_back"], true);
+        assert_eq!(relation["m.in_reply_to"]["event_id"], "$root:example.org");
+        assert_eq!(json["body"], "answer");
     }
 }
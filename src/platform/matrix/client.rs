@@ -1,18 +1,90 @@
 // ABOUTME: Matrix client initialization and authentication
 // ABOUTME: Handles client creation with crypto store and login via password or token
 
-use crate::paths;
+use crate::{
+    paths,
+    session::{Channel, SessionStore},
+};
 use anyhow::{Context, Result};
 use matrix_sdk::{
     authentication::{matrix::MatrixSession, SessionTokens},
     ruma::{
         api::client::room::create_room::v3::Request as CreateRoomRequest,
         assign,
-        events::{room::encryption::RoomEncryptionEventContent, InitialStateEvent},
-        OwnedRoomId, OwnedUserId,
+        events::{
+            room::{
+                avatar::RoomAvatarEventContent, encryption::RoomEncryptionEventContent,
+                topic::RoomTopicEventContent,
+            },
+            InitialStateEvent,
+        },
+        serde::Raw,
+        Int, OwnedMxcUri, OwnedRoomId, OwnedUserId, RoomId, UserId,
     },
     AuthSession, Client, SessionMeta,
 };
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Avatar given to new channel rooms, uploaded once at startup
+static ROOM_AVATAR: OnceLock<OwnedMxcUri> = OnceLock::new();
+
+/// Power level the bot holds in channel rooms; room state changes need it
+pub const BOT_POWER_LEVEL: i64 = 100;
+
+/// Power level `!promote` gives by default; enough to redact messages
+pub const MODERATOR_POWER_LEVEL: i64 = 50;
+
+/// Power level of people in new channel rooms, set once at startup
+static MEMBER_POWER_LEVEL: OnceLock<i64> = OnceLock::new();
+
+/// Give people in channel rooms created from now on `level`
+pub fn set_member_power_level(level: i64) {
+    if MEMBER_POWER_LEVEL.set(level).is_err() {
+        tracing::debug!("Member power level already set; keeping the first one");
+    }
+}
+
+/// Power levels for a channel room: everyone in it can talk, but only the bot
+/// (and users at or above BOT_POWER_LEVEL) can rename it, change its state or kick
+pub fn channel_power_levels(users: Map<String, Value>, member_level: i64) -> Value {
+    let state_events = [
+        "m.room.name",
+        "m.room.topic",
+        "m.room.avatar",
+        "m.room.power_levels",
+        "m.room.history_visibility",
+        "m.room.join_rules",
+        "m.room.encryption",
+        "m.room.canonical_alias",
+        "m.room.tombstone",
+        "m.room.server_acl",
+        "m.space.parent",
+    ];
+    let events: Map<String, Value> = state_events
+        .iter()
+        .map(|event| (event.to_string(), BOT_POWER_LEVEL.into()))
+        .collect();
+    json!({
+        "users": users,
+        "users_default": member_level,
+        "events": events,
+        "events_default": 0,
+        "state_default": BOT_POWER_LEVEL,
+        "invite": BOT_POWER_LEVEL,
+        "kick": BOT_POWER_LEVEL,
+        "ban": BOT_POWER_LEVEL,
+        "redact": MODERATOR_POWER_LEVEL,
+    })
+}
+
+/// The `users` entry of power levels that only raise the bot
+fn bot_power_level(bot: &UserId) -> Map<String, Value> {
+    let mut users = Map::new();
+    users.insert(bot.to_string(), BOT_POWER_LEVEL.into());
+    users
+}
 
 /// Convert a string to a filesystem-safe slug
 fn slugify(s: &str) -> String {
@@ -96,7 +168,32 @@ pub async fn login(
     Ok(())
 }
 
-/// Create a new private encrypted room and return its ID
+/// Upload the image at `path` and use it as the avatar of channel rooms
+/// created from now on
+pub async fn upload_room_avatar(client: &Client, path: &Path) -> Result<()> {
+    let mime_type = mime_guess::from_path(path)
+        .first()
+        .unwrap_or(mime_guess::mime::IMAGE_PNG);
+    if mime_type.type_() != "image" {
+        anyhow::bail!("{} is not an image", path.display());
+    }
+    let data = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let response = client
+        .media()
+        .upload(&mime_type, data, None)
+        .await
+        .context("Failed to upload room avatar")?;
+    if ROOM_AVATAR.set(response.content_uri).is_err() {
+        tracing::debug!("Room avatar already uploaded; keeping the first one");
+    }
+    Ok(())
+}
+
+/// Create a new private encrypted room and return its ID, adding it to the
+/// channel space when one is set and giving it the room avatar if uploaded
 pub async fn create_room(client: &Client, room_name: &str) -> Result<OwnedRoomId> {
     tracing::info!(room_name, "Creating new private encrypted room");
 
@@ -104,13 +201,24 @@ pub async fn create_room(client: &Client, room_name: &str) -> Result<OwnedRoomId
     let encryption_event = InitialStateEvent::with_empty_state_key(
         RoomEncryptionEventContent::with_recommended_defaults(),
     );
+    let mut initial_state = vec![encryption_event.to_raw_any()];
+    if let Some(url) = ROOM_AVATAR.get() {
+        let avatar = assign!(RoomAvatarEventContent::new(), { url: Some(url.clone()) });
+        initial_state.push(InitialStateEvent::with_empty_state_key(avatar).to_raw_any());
+    }
+
+    let bot = client.user_id().context("Not logged in")?;
+    let member_level = MEMBER_POWER_LEVEL.get().copied().unwrap_or(0);
+    let power_levels = channel_power_levels(bot_power_level(bot), member_level);
+    let power_levels = Raw::from_json(serde_json::value::to_raw_value(&power_levels)?);
 
     let request = assign!(CreateRoomRequest::new(), {
         name: Some(room_name.to_string()),
         is_direct: true,
         visibility: matrix_sdk::ruma::api::client::room::Visibility::Private,
         preset: Some(matrix_sdk::ruma::api::client::room::create_room::v3::RoomPreset::TrustedPrivateChat),
-        initial_state: vec![encryption_event.to_raw_any()],
+        initial_state,
+        power_level_content_override: Some(power_levels),
     });
 
     let room = client
@@ -121,9 +229,114 @@ pub async fn create_room(client: &Client, room_name: &str) -> Result<OwnedRoomId
     let room_id = room.room_id().to_owned();
     tracing::info!(%room_id, "Encrypted room created successfully");
 
+    // File the room under the channel space; the room works without it
+    if let Some(space) = super::space::channel_space() {
+        if let Err(e) = super::space::add_child(client, space, &room_id).await {
+            tracing::warn!(error = %e, %room_id, %space, "Failed to add room to space");
+        }
+    }
+
     Ok(room_id)
 }
 
+/// Topic a channel room gets until someone sets one with `!topic`
+pub fn default_topic(channel_name: &str, directory: &str) -> String {
+    format!("gorp channel '{}' — workspace: {}", channel_name, directory)
+}
+
+/// The topic stored for a channel with `!topic`, or its default one
+pub fn channel_topic(session_store: &SessionStore, channel: &Channel) -> Result<String> {
+    Ok(session_store
+        .get_room_topic(&channel.channel_name)?
+        .unwrap_or_else(|| default_topic(&channel.channel_name, &channel.directory)))
+}
+
+/// Send an `m.room.topic` state event to a room
+pub async fn set_topic(client: &Client, room_id: &RoomId, topic: &str) -> Result<()> {
+    let room = client.get_room(room_id).context("Room not found")?;
+    room.send_state_event(RoomTopicEventContent::new(topic.to_string()))
+        .await
+        .context("Failed to set room topic")?;
+    Ok(())
+}
+
+/// Give a channel's room its topic; the room works without one, so failures are only logged
+pub async fn apply_channel_topic(
+    client: &Client,
+    session_store: &SessionStore,
+    room_id: &RoomId,
+    channel: &Channel,
+) {
+    let result = match channel_topic(session_store, channel) {
+        Ok(topic) => set_topic(client, room_id, &topic).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        tracing::warn!(
+            error = %e,
+            %room_id,
+            channel = %channel.channel_name,
+            "Failed to set room topic"
+        );
+    }
+}
+
+/// Set `user_id`'s power level in a room. Levels at or above the bot's are
+/// refused, since the bot could never lower them again.
+pub async fn set_power_level(
+    client: &Client,
+    room_id: &RoomId,
+    user_id: &str,
+    level: i64,
+) -> Result<()> {
+    if !(0..BOT_POWER_LEVEL).contains(&level) {
+        anyhow::bail!("Power level must be between 0 and {}", BOT_POWER_LEVEL - 1);
+    }
+    let user_id: OwnedUserId = user_id.parse().context("Invalid user ID")?;
+    let level = Int::new(level).context("Invalid power level")?;
+    let room = client.get_room(room_id).context("Room not found")?;
+    room.update_power_levels(vec![(&*user_id, level)])
+        .await
+        .context("Failed to update power levels")?;
+    tracing::info!(%room_id, %user_id, %level, "Power level updated");
+    Ok(())
+}
+
+/// Retrofit the channel room power levels onto an existing room. People keep
+/// levels above `member_level` that the bot can still change; anyone at or above
+/// the bot's level is left alone and returned, since the bot can't demote them.
+pub async fn harden_room(
+    client: &Client,
+    room_id: &RoomId,
+    member_level: i64,
+) -> Result<Vec<OwnedUserId>> {
+    let bot = client.user_id().context("Not logged in")?;
+    let room = client.get_room(room_id).context("Room not found")?;
+
+    let mut users = bot_power_level(bot);
+    let mut untouchable = Vec::new();
+    for (user, level) in room.users_with_power_levels().await {
+        if user.as_str() == bot.as_str() {
+            continue;
+        }
+        if level >= BOT_POWER_LEVEL {
+            untouchable.push(user.clone());
+        }
+        if level > member_level {
+            users.insert(user.to_string(), level.into());
+        }
+    }
+
+    room.send_state_event_raw(
+        "m.room.power_levels",
+        "",
+        channel_power_levels(users, member_level),
+    )
+    .await
+    .context("Failed to update power levels")?;
+    Ok(untouchable)
+}
+
 /// Invite a user to a room
 pub async fn invite_user(client: &Client, room_id: &OwnedRoomId, user_id: &str) -> Result<()> {
     tracing::info!(%room_id, user_id, "Inviting user to room");
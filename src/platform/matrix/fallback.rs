@@ -0,0 +1,167 @@
+// ABOUTME: Plain-text fallback for Matrix sends whose formatted body the homeserver rejects.
+// ABOUTME: Tags content rejections on send errors and resends just the plain text so the reply still lands.
+
+use anyhow::Result;
+use gorp_core::traits::MessageContent;
+use matrix_sdk::{
+    room::Room,
+    ruma::{
+        api::client::error::ErrorKind, events::room::message::RoomMessageEventContent, OwnedEventId,
+    },
+};
+use std::future::Future;
+
+use super::signing::{signed_event, Signer};
+use crate::metrics;
+
+/// The homeserver refused an event because of what was in it, not how it was sent
+#[derive(Debug)]
+pub struct ContentRejected(pub String);
+
+impl std::fmt::Display for ContentRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Homeserver rejected message content: {}", self.0)
+    }
+}
+
+impl std::error::Error for ContentRejected {}
+
+/// Whether a send failed on the event's content. Network, auth and rate-limit
+/// failures don't qualify: resending the same text as plain wouldn't help.
+pub fn is_content_rejection(err: &matrix_sdk::Error) -> bool {
+    matches!(
+        err.client_api_error_kind(),
+        Some(
+            ErrorKind::BadJson | ErrorKind::NotJson | ErrorKind::InvalidParam | ErrorKind::TooLarge
+        )
+    )
+}
+
+/// Convert a room send error, tagging content rejections as [`ContentRejected`]
+pub fn send_error(err: matrix_sdk::Error) -> anyhow::Error {
+    if is_content_rejection(&err) {
+        ContentRejected(err.to_string()).into()
+    } else {
+        err.into()
+    }
+}
+
+/// Send `content` with `send`. If it's HTML and the server rejects its content,
+/// send the plain body instead so the user still gets the text.
+pub async fn send_with_plain_fallback<F, Fut, T>(content: MessageContent, send: F) -> Result<T>
+where
+    F: Fn(MessageContent) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let plain = match &content {
+        MessageContent::Html { plain, .. } => Some(plain.clone()),
+        _ => None,
+    };
+    match (send(content).await, plain) {
+        (Err(e), Some(plain)) if e.downcast_ref::<ContentRejected>().is_some() => {
+            tracing::warn!(error = %e, "HTML message rejected, resending as plain text");
+            metrics::record_error("html_rejected");
+            send(MessageContent::Plain(plain)).await
+        }
+        (result, _) => result,
+    }
+}
+
+/// Room message for text content; attachments need an upload first, so they
+/// only get their filename here
+pub fn text_event(content: MessageContent) -> RoomMessageEventContent {
+    match content {
+        MessageContent::Plain(text) => RoomMessageEventContent::text_plain(text),
+        MessageContent::Html { plain, html } => RoomMessageEventContent::text_html(plain, html),
+        MessageContent::Attachment { filename, .. } => {
+            RoomMessageEventContent::text_plain(filename)
+        }
+    }
+}
+
+/// Send a formatted message to `room`, falling back to plain text if the HTML is rejected.
+/// `relate` is applied to every attempt (thread or reply relations); the HTML is signed
+/// when there's a `signer`. Returns the sent event's ID.
+pub async fn send_html(
+    room: &Room,
+    plain: &str,
+    html: &str,
+    signer: Option<&Signer>,
+    relate: impl Fn(RoomMessageEventContent) -> RoomMessageEventContent,
+) -> Result<OwnedEventId> {
+    send_with_plain_fallback(MessageContent::html(plain, html), |content| {
+        let event = relate(signed_event(signer, room.room_id().as_str(), content));
+        async move { Ok(room.send(event).await.map_err(send_error)?.event_id) }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Sender that rejects HTML the way a strict homeserver would and accepts plain text.
+    /// Logs each attempt as "html: <plain>" or "plain: <text>".
+    fn strict_server(sent: &Mutex<Vec<String>>, content: MessageContent) -> Result<()> {
+        match content {
+            MessageContent::Html { plain, .. } => {
+                sent.lock().unwrap().push(format!("html: {}", plain));
+                Err(ContentRejected("M_BAD_JSON".to_string()).into())
+            }
+            MessageContent::Plain(text) => {
+                sent.lock().unwrap().push(format!("plain: {}", text));
+                Ok(())
+            }
+            MessageContent::Attachment { .. } => unreachable!("no attachments in these tests"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_html_is_resent_as_plain() {
+        let sent = Mutex::new(Vec::new());
+        let log = &sent;
+        let content = MessageContent::html("**done**", "<strong>done</strong>");
+
+        send_with_plain_fallback(content, move |c| async move { strict_server(log, c) })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sent.into_inner().unwrap(),
+            vec!["html: **done**".to_string(), "plain: **done**".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_other_failures_are_not_retried() {
+        let attempts = Mutex::new(0);
+        let count = &attempts;
+        let result: Result<()> = send_with_plain_fallback(
+            MessageContent::html("hi", "<b>hi</b>"),
+            move |_| async move {
+                *count.lock().unwrap() += 1;
+                Err(anyhow::anyhow!("connection reset"))
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_plain_text_is_not_retried() {
+        let sent = Mutex::new(Vec::new());
+        let log = &sent;
+        let result: Result<()> =
+            send_with_plain_fallback(MessageContent::plain("hi"), move |c| async move {
+                strict_server(log, c)?;
+                Err(ContentRejected("M_TOO_LARGE".to_string()).into())
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(sent.into_inner().unwrap().len(), 1);
+    }
+}
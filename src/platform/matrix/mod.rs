@@ -3,6 +3,9 @@
 
 pub mod channel;
 pub mod client;
+pub mod fallback;
+pub mod signing;
+pub mod space;
 
 // Re-export channel type
 pub use channel::MatrixChannel;
@@ -12,20 +15,27 @@ pub use client::{create_client, create_dm_room, create_room, invite_user, login}
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use gorp_core::connection::{ConnectionEvent, ConnectionTracker};
 use gorp_core::traits::{
-    AttachmentInfo, ChannelCreator, ChannelManager, ChatChannel, ChatPlatform, ChatUser,
-    EventStream, IncomingMessage, MessageContent, MessagingPlatform, PlatformConnectionState,
+    AttachmentHandler, AttachmentInfo, ChannelCreator, ChannelManager, ChatChannel, ChatPlatform,
+    ChatUser, EventStream, IncomingMessage, MessageAnnotator, MessageContent, MessagingPlatform,
+    PlatformConnectionState, Presence, PresenceProvider, ReceiptSender, ThreadedPlatform,
 };
 use matrix_sdk::{
     room::Room,
     ruma::{
-        events::room::message::MessageType,
-        OwnedRoomId, OwnedUserId,
+        api::client::presence::set_presence,
+        events::{
+            reaction::ReactionEventContent,
+            relation::Annotation,
+            room::message::{MessageType, Relation},
+        },
+        presence::PresenceState,
+        OwnedEventId, OwnedRoomId, OwnedUserId,
     },
     Client,
 };
-use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 
 // =============================================================================
@@ -38,7 +48,7 @@ pub struct MatrixPlatform {
     /// Cached user ID - stored at construction to avoid Option handling
     user_id: String,
     /// Tracked connection state for health monitoring
-    connection_state: Arc<Mutex<PlatformConnectionState>>,
+    connection: ConnectionTracker,
 }
 
 impl MatrixPlatform {
@@ -55,7 +65,7 @@ impl MatrixPlatform {
         Self {
             client,
             user_id,
-            connection_state: Arc::new(Mutex::new(PlatformConnectionState::Connected)),
+            connection: ConnectionTracker::new("matrix", PlatformConnectionState::Connected),
         }
     }
 
@@ -67,9 +77,12 @@ impl MatrixPlatform {
     /// Update the platform's connection state.
     /// Called by sync loop or error handlers to reflect actual connectivity.
     pub fn set_connection_state(&self, state: PlatformConnectionState) {
-        if let Ok(mut current) = self.connection_state.lock() {
-            *current = state;
-        }
+        self.connection.set(state);
+    }
+
+    /// The platform's connection state, for a sync loop that runs outside it
+    pub fn connection_tracker(&self) -> ConnectionTracker {
+        self.connection.clone()
     }
 
     /// Register the event stream handler and return a receiver for incoming messages.
@@ -87,7 +100,7 @@ impl MatrixPlatform {
                 let tx = tx.clone();
                 let bot_user_id = bot_user_id.clone();
                 async move {
-                    // Only process original events (not edits/redactions)
+                    // Skip redacted events; edits arrive as originals with a replacement relation
                     let Some(original) = event.as_original() else {
                         return;
                     };
@@ -97,22 +110,41 @@ impl MatrixPlatform {
                         return;
                     }
 
-                    // Convert to IncomingMessage
-                    let body = match &original.content.msgtype {
+                    // An edit's real text is in m.new_content; its own body is a "* ..." fallback
+                    let (msgtype, edits_event_id) = match &original.content.relates_to {
+                        Some(Relation::Replacement(replacement)) => (
+                            &replacement.new_content.msgtype,
+                            Some(replacement.event_id.to_string()),
+                        ),
+                        _ => (&original.content.msgtype, None),
+                    };
+
+                    // Replies inside an m.thread carry the thread root's event ID
+                    let thread_id = match &original.content.relates_to {
+                        Some(Relation::Thread(thread)) => Some(thread.event_id.to_string()),
+                        _ => None,
+                    };
+
+                    // Convert to IncomingMessage. A file's body is its caption only
+                    // when a separate filename is given; otherwise it's just the name.
+                    let body = match msgtype {
                         MessageType::Text(text) => text.body.clone(),
                         MessageType::Notice(notice) => notice.body.clone(),
                         MessageType::Emote(emote) => emote.body.clone(),
-                        _ => return, // Skip non-text messages for now
+                        MessageType::File(f) => caption(&f.body, f.filename.as_deref()),
+                        MessageType::Image(i) => caption(&i.body, i.filename.as_deref()),
+                        MessageType::Audio(a) => caption(&a.body, a.filename.as_deref()),
+                        _ => return, // Skip other message types for now
                     };
 
                     let is_formatted = matches!(
-                        &original.content.msgtype,
+                        msgtype,
                         MessageType::Text(t) if t.formatted.is_some()
                     );
 
-                    // Check for attachment
-                    let attachment = match &original.content.msgtype {
-                        MessageType::File(f) => Some(AttachmentInfo {
+                    // A Matrix event carries at most one file, image or audio clip
+                    let attachments: Vec<AttachmentInfo> = match msgtype {
+                        MessageType::File(f) => vec![AttachmentInfo {
                             source_id: serde_json::to_string(&f.source).unwrap_or_default(),
                             filename: f.filename.clone().unwrap_or_else(|| f.body.clone()),
                             mime_type: f
@@ -121,8 +153,8 @@ impl MatrixPlatform {
                                 .and_then(|i| i.mimetype.clone())
                                 .unwrap_or_else(|| "application/octet-stream".to_string()),
                             size: f.info.as_ref().and_then(|i| i.size.map(|s| s.into())),
-                        }),
-                        MessageType::Image(i) => Some(AttachmentInfo {
+                        }],
+                        MessageType::Image(i) => vec![AttachmentInfo {
                             source_id: serde_json::to_string(&i.source).unwrap_or_default(),
                             filename: i.filename.clone().unwrap_or_else(|| i.body.clone()),
                             mime_type: i
@@ -131,8 +163,18 @@ impl MatrixPlatform {
                                 .and_then(|info| info.mimetype.clone())
                                 .unwrap_or_else(|| "image/png".to_string()),
                             size: i.info.as_ref().and_then(|info| info.size.map(|s| s.into())),
-                        }),
-                        _ => None,
+                        }],
+                        MessageType::Audio(a) => vec![AttachmentInfo {
+                            source_id: serde_json::to_string(&a.source).unwrap_or_default(),
+                            filename: a.filename.clone().unwrap_or_else(|| a.body.clone()),
+                            mime_type: a
+                                .info
+                                .as_ref()
+                                .and_then(|info| info.mimetype.clone())
+                                .unwrap_or_else(|| "audio/ogg".to_string()),
+                            size: a.info.as_ref().and_then(|info| info.size.map(|s| s.into())),
+                        }],
+                        _ => Vec::new(),
                     };
 
                     let is_direct = room.is_direct().await.unwrap_or(false);
@@ -140,7 +182,7 @@ impl MatrixPlatform {
                     let msg = IncomingMessage {
                         platform_id: "matrix".to_string(),
                         channel_id: room.room_id().to_string(),
-                        thread_id: None,
+                        thread_id,
                         sender: ChatUser {
                             id: original.sender.to_string(),
                             display_name: room
@@ -153,8 +195,9 @@ impl MatrixPlatform {
                         body,
                         is_direct,
                         formatted: is_formatted,
-                        attachment,
+                        attachments,
                         event_id: original.event_id.to_string(),
+                        edits_event_id,
                         timestamp: {
                             let millis: u64 = original.origin_server_ts.0.into();
                             (millis / 1000) as i64
@@ -205,10 +248,73 @@ impl MessagingPlatform for MatrixPlatform {
     }
 
     fn connection_state(&self) -> PlatformConnectionState {
-        self.connection_state
-            .lock()
-            .map(|s| s.clone())
-            .unwrap_or(PlatformConnectionState::Connected)
+        self.connection.get()
+    }
+
+    fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+        Some(self.connection.subscribe())
+    }
+
+    fn annotator(&self) -> Option<&dyn MessageAnnotator> {
+        Some(self)
+    }
+
+    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
+        Some(self)
+    }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        Some(self)
+    }
+
+    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
+        Some(self)
+    }
+
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        Some(self)
+    }
+
+    fn receipts(&self) -> Option<&dyn ReceiptSender> {
+        Some(self)
+    }
+
+    fn presence(&self) -> Option<&dyn PresenceProvider> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ReceiptSender for MatrixPlatform {
+    async fn send_read_receipt(&self, channel_id: &str, message_id: &str) -> Result<()> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        let room = self.client.get_room(&room_id).context("Room not found")?;
+        let event_id: OwnedEventId = message_id.parse().context("Invalid event ID")?;
+        channel::send_read_receipt(&room, event_id).await
+    }
+}
+
+#[async_trait]
+impl PresenceProvider for MatrixPlatform {
+    async fn set_presence(&self, presence: Presence) -> Result<()> {
+        let user_id: OwnedUserId = self.user_id.parse().context("Invalid user ID")?;
+        let state = match presence {
+            Presence::Online => PresenceState::Online,
+            Presence::Unavailable => PresenceState::Unavailable,
+            Presence::Offline => PresenceState::Offline,
+        };
+        self.client
+            .send(set_presence::v3::Request::new(user_id, state))
+            .await
+            .context("Failed to set presence")?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AttachmentHandler for MatrixPlatform {
+    async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
+        channel::download_media(&self.client, source_id).await
     }
 }
 
@@ -229,14 +335,6 @@ impl ChatPlatform for MatrixPlatform {
             .map(|room| MatrixChannel::new(room, self.client.clone()))
             .collect()
     }
-
-    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
-        Some(self)
-    }
-
-    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
-        Some(self)
-    }
 }
 
 #[async_trait]
@@ -296,14 +394,81 @@ impl ChannelManager for MatrixPlatform {
     }
 }
 
+#[async_trait]
+impl ThreadedPlatform for MatrixPlatform {
+    async fn send_threaded(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        content: MessageContent,
+    ) -> Result<()> {
+        let channel = self
+            .get_channel(channel_id)
+            .await
+            .context("Channel not found")?;
+        channel.send_in_thread(thread_ts, content).await
+    }
+}
+
+#[async_trait]
+impl MessageAnnotator for MatrixPlatform {
+    async fn add_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> Result<String> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        let room = self.client.get_room(&room_id).context("Room not found")?;
+        let event_id: OwnedEventId = message_id.parse().context("Invalid event ID")?;
+
+        let reaction = ReactionEventContent::new(Annotation::new(event_id, emoji.to_string()));
+        let sent = room
+            .send(reaction)
+            .await
+            .context("Failed to send reaction")?;
+        Ok(sent.event_id.to_string())
+    }
+
+    async fn remove_reaction(&self, channel_id: &str, reaction_id: &str) -> Result<()> {
+        let room_id: OwnedRoomId = channel_id.parse().context("Invalid room ID")?;
+        let room = self.client.get_room(&room_id).context("Room not found")?;
+        let event_id: OwnedEventId = reaction_id.parse().context("Invalid event ID")?;
+
+        // Reactions are removed by redacting the reaction event itself
+        room.redact(&event_id, None, None)
+            .await
+            .context("Failed to redact reaction")?;
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
 
+/// Caption of a file or image event: its body, unless the body is just the filename
+fn caption(body: &str, filename: Option<&str>) -> String {
+    match filename {
+        Some(filename) if filename != body => body.to_string(),
+        _ => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_caption_only_when_filename_is_separate() {
+        assert_eq!(caption("screenshot.png", None), "");
+        assert_eq!(caption("screenshot.png", Some("screenshot.png")), "");
+        assert_eq!(
+            caption("what's wrong here?", Some("screenshot.png")),
+            "what's wrong here?"
+        );
+    }
+
     #[test]
     fn test_matrix_platform_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
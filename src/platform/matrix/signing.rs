@@ -0,0 +1,239 @@
+// ABOUTME: HMAC signatures hidden in the formatted body of agent responses sent to Matrix.
+// ABOUTME: Each covers the room, the signing time and both bodies, so a copied or edited message won't verify.
+
+use gorp_core::{config::SigningConfig, traits::MessageContent};
+use hmac::{Hmac, Mac};
+use matrix_sdk::ruma::events::room::message::RoomMessageEventContent;
+use sha2::Sha256;
+
+use super::fallback::text_event;
+
+/// Attribute of the empty span that carries a message's signature. Clients drop
+/// attributes they don't know when rendering, so readers never see it.
+pub const SIGNATURE_ATTRIBUTE: &str = "data-mx-gorp-signature";
+
+/// Furthest a signature's time may be from the event's `origin_server_ts`, in
+/// milliseconds. Covers clock skew and send retries; a message copied into a new
+/// event later than this no longer verifies.
+pub const MAX_SIGNATURE_AGE_MS: u64 = 5 * 60 * 1000;
+
+/// Signs agent responses with the deployment's `signing.secret`.
+///
+/// Only messages sent with a signer are signed: agent responses to chat messages
+/// (sent whole, streamed or as manifest parts), scheduled and dispatched task
+/// results, DISPATCH replies, agent reports to the management room and agent
+/// output relayed from the message bus. Command replies, notices, errors, tool
+/// notices, streaming placeholders, held responses flushed to the room by the
+/// delivery window and anything resent as plain text after the homeserver
+/// rejected its HTML are not.
+#[derive(Clone)]
+pub struct Signer {
+    secret: String,
+}
+
+impl Signer {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    /// Signer for `[signing]`, when a secret is configured
+    pub fn from_config(config: &SigningConfig) -> Option<Self> {
+        config.secret.as_deref().map(Self::new)
+    }
+
+    /// `html` with a signature of it and `body`, sent to `room_id` now, appended in a hidden span
+    pub fn sign_html(&self, room_id: &str, body: &str, html: &str) -> String {
+        let now = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        self.sign_html_at(room_id, now, body, html)
+    }
+
+    fn sign_html_at(&self, room_id: &str, signed_at: u64, body: &str, html: &str) -> String {
+        let digest = mac(&self.secret, room_id, signed_at, body, html).finalize();
+        format!(
+            "{}{}{},sha256={}{}",
+            html,
+            span_start(),
+            signed_at,
+            hex::encode(digest.into_bytes()),
+            SPAN_END
+        )
+    }
+
+    /// Formatted room message for `room_id`, signed
+    pub fn html_event(
+        &self,
+        room_id: &str,
+        plain: impl Into<String>,
+        html: impl Into<String>,
+    ) -> RoomMessageEventContent {
+        let (plain, html) = (plain.into(), html.into());
+        let html = self.sign_html(room_id, &plain, &html);
+        RoomMessageEventContent::text_html(plain, html)
+    }
+}
+
+/// Room message for `content` sent to `room_id`, its formatted text signed when
+/// there's a signer. Plain text and attachments are never signed.
+pub fn signed_event(
+    signer: Option<&Signer>,
+    room_id: &str,
+    content: MessageContent,
+) -> RoomMessageEventContent {
+    match (signer, content) {
+        (Some(signer), MessageContent::Html { plain, html }) => {
+            signer.html_event(room_id, plain, html)
+        }
+        (_, content) => text_event(content),
+    }
+}
+
+/// Closes the signature span, which always ends the formatted body
+const SPAN_END: &str = "\"></span>";
+
+/// Opens the signature span, up to its time
+fn span_start() -> String {
+    format!("<span {}=\"t=", SIGNATURE_ATTRIBUTE)
+}
+
+/// MAC over the room, the signing time, the plain body and the formatted body
+/// without its signature span. The first three are newline-separated and the
+/// plain body's length comes before it, so no field can run into the next.
+fn mac(secret: &str, room_id: &str, signed_at: u64, body: &str, html: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}\n{}\n{}\n", room_id, signed_at, body.len()).as_bytes());
+    mac.update(body.as_bytes());
+    mac.update(html.as_bytes());
+    mac
+}
+
+/// Whether `formatted_body` ends in a signature made with `secret` of `body` and
+/// the rest of `formatted_body` in `room_id`, signed within
+/// [`MAX_SIGNATURE_AGE_MS`] of the event's `origin_server_ts`. The MAC is
+/// compared in constant time; unsigned messages don't verify.
+pub fn verify(
+    secret: &str,
+    room_id: &str,
+    origin_server_ts: u64,
+    body: &str,
+    formatted_body: &str,
+) -> bool {
+    let marker = span_start();
+    let Some(start) = formatted_body.rfind(&marker) else {
+        return false;
+    };
+    let (html, span) = formatted_body.split_at(start);
+    let Some(value) = span[marker.len()..].strip_suffix(SPAN_END) else {
+        return false;
+    };
+    let Some((signed_at, hex_digest)) = value.split_once(",sha256=") else {
+        return false;
+    };
+    let (Ok(signed_at), Ok(digest)) = (signed_at.parse::<u64>(), hex::decode(hex_digest)) else {
+        return false;
+    };
+    if signed_at.abs_diff(origin_server_ts) > MAX_SIGNATURE_AGE_MS {
+        return false;
+    }
+    mac(secret, room_id, signed_at, body, html)
+        .verify_slice(&digest)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use matrix_sdk::ruma::events::room::message::MessageType;
+
+    const ROOM: &str = "!ops:example.org";
+    const SENT_AT: u64 = 1_760_000_000_000;
+
+    fn signed() -> String {
+        Signer::new("s3cret").sign_html_at(ROOM, SENT_AT, "**done**", "<strong>done</strong>")
+    }
+
+    #[test]
+    fn test_signed_html_verifies_against_its_room_and_body() {
+        let html = signed();
+
+        assert!(html.starts_with(&format!(
+            "<strong>done</strong><span data-mx-gorp-signature=\"t={},sha256=",
+            SENT_AT
+        )));
+        assert!(verify("s3cret", ROOM, SENT_AT + 300, "**done**", &html));
+    }
+
+    #[test]
+    fn test_tampered_or_unsigned_messages_do_not_verify() {
+        let html = signed();
+
+        assert!(!verify("s3cret", ROOM, SENT_AT, "**not done**", &html));
+        assert!(!verify("other", ROOM, SENT_AT, "**done**", &html));
+        assert!(!verify(
+            "s3cret",
+            ROOM,
+            SENT_AT,
+            "**done**",
+            "<strong>done</strong>"
+        ));
+        assert!(!verify(
+            "s3cret",
+            ROOM,
+            SENT_AT,
+            "**done**",
+            "<span data-mx-gorp-signature=\"t=1,sha256=zz\"></span>"
+        ));
+        // So is the HTML clients render
+        let swapped = html.replace(
+            "<strong>done</strong>",
+            "<a href=\"https://evil.example\">done</a>",
+        );
+        assert!(!verify("s3cret", ROOM, SENT_AT, "**done**", &swapped));
+        assert!(!verify(
+            "s3cret",
+            ROOM,
+            SENT_AT,
+            "**done**",
+            &format!("{}<p>more</p>", html)
+        ));
+        // The signing time is covered too
+        let moved = html.replace(&format!("t={}", SENT_AT), &format!("t={}", SENT_AT + 1));
+        assert!(!verify("s3cret", ROOM, SENT_AT, "**done**", &moved));
+    }
+
+    #[test]
+    fn test_replayed_messages_do_not_verify() {
+        let html = signed();
+
+        // Copied into another room
+        assert!(!verify(
+            "s3cret",
+            "!other:example.org",
+            SENT_AT,
+            "**done**",
+            &html
+        ));
+        // Reposted long after it was signed
+        let later = SENT_AT + MAX_SIGNATURE_AGE_MS + 1;
+        assert!(!verify("s3cret", ROOM, later, "**done**", &html));
+    }
+
+    #[test]
+    fn test_only_formatted_text_is_signed() {
+        let signer = Signer::new("s3cret");
+        let html = MessageContent::html("**done**", "<strong>done</strong>");
+
+        let formatted = |event: RoomMessageEventContent| match event.msgtype {
+            MessageType::Text(text) => text.formatted.map(|f| f.body),
+            other => panic!("Expected text, got {:?}", other),
+        };
+        let signed = formatted(signed_event(Some(&signer), ROOM, html.clone())).unwrap();
+        assert!(signed.contains(SIGNATURE_ATTRIBUTE));
+        let unsigned = formatted(signed_event(None, ROOM, html)).unwrap();
+        assert!(!unsigned.contains(SIGNATURE_ATTRIBUTE));
+        let plain = signed_event(Some(&signer), ROOM, MessageContent::plain("hi"));
+        assert!(formatted(plain).is_none());
+    }
+}
@@ -0,0 +1,128 @@
+// ABOUTME: Matrix Space that groups gorp's channel rooms in clients' sidebars
+// ABOUTME: Resolves or creates the space at startup and links rooms in and out of it
+
+use anyhow::{Context, Result};
+use gorp_core::config::MatrixConfig;
+use gorp_core::session::SessionStore;
+use matrix_sdk::{
+    ruma::{
+        api::client::room::create_room::v3::{CreationContent, Request as CreateRoomRequest},
+        assign,
+        events::space::{child::SpaceChildEventContent, parent::SpaceParentEventContent},
+        room::RoomType,
+        serde::Raw,
+        OwnedRoomId, OwnedServerName, RoomId,
+    },
+    Client,
+};
+use std::sync::OnceLock;
+
+/// Setting that remembers the space created by `auto_create_space`
+pub const SETTING_SPACE_ID: &str = "matrix_space_id";
+
+/// Space new channel rooms are filed under, resolved once at startup
+static CHANNEL_SPACE: OnceLock<OwnedRoomId> = OnceLock::new();
+
+/// The space channel rooms go in, if one was resolved at startup
+pub fn channel_space() -> Option<&'static RoomId> {
+    CHANNEL_SPACE.get().map(|id| id.as_ref())
+}
+
+/// Use `space` for the channel rooms created from now on
+pub fn set_channel_space(space: OwnedRoomId) {
+    if CHANNEL_SPACE.set(space).is_err() {
+        tracing::debug!("Channel space already set; keeping the first one");
+    }
+}
+
+/// Find the configured space, or with `auto_create_space` the one created on an
+/// earlier run, creating it on first use. None when no space is configured.
+pub async fn resolve(
+    client: &Client,
+    matrix: &MatrixConfig,
+    session_store: &SessionStore,
+) -> Result<Option<OwnedRoomId>> {
+    if let Some(space_id) = matrix.space_id.as_deref() {
+        let space: OwnedRoomId = space_id.parse().context("Invalid matrix.space_id")?;
+        if client.get_room(&space).is_none() {
+            client
+                .join_room_by_id(&space)
+                .await
+                .context("Failed to join matrix.space_id; invite the bot first")?;
+        }
+        return Ok(Some(space));
+    }
+    if !matrix.auto_create_space {
+        return Ok(None);
+    }
+
+    if let Some(stored) = session_store.get_setting(SETTING_SPACE_ID)? {
+        match stored.parse::<OwnedRoomId>() {
+            Ok(space) if client.get_room(&space).is_some() => return Ok(Some(space)),
+            _ => tracing::warn!(space = %stored, "Stored space is gone; creating a new one"),
+        }
+    }
+
+    let space = create_space(client, &matrix.room_prefix).await?;
+    session_store.set_setting(SETTING_SPACE_ID, space.as_str())?;
+    for user in &matrix.allowed_users {
+        if let Err(e) = super::client::invite_user(client, &space, user).await {
+            tracing::warn!(error = %e, user, "Failed to invite user to space");
+        }
+    }
+    Ok(Some(space))
+}
+
+/// Create a private space named after the room prefix
+async fn create_space(client: &Client, room_prefix: &str) -> Result<OwnedRoomId> {
+    let creation_content = assign!(CreationContent::new(), {
+        room_type: Some(RoomType::Space),
+    });
+    let request = assign!(CreateRoomRequest::new(), {
+        name: Some(format!("{} channels", room_prefix)),
+        visibility: matrix_sdk::ruma::api::client::room::Visibility::Private,
+        preset: Some(matrix_sdk::ruma::api::client::room::create_room::v3::RoomPreset::PrivateChat),
+        creation_content: Some(Raw::new(&creation_content).context("Invalid space creation content")?),
+    });
+
+    let room = client
+        .create_room(request)
+        .await
+        .context("Failed to create space")?;
+    let space = room.room_id().to_owned();
+    tracing::info!(%space, "Created Matrix space for channel rooms");
+    Ok(space)
+}
+
+/// Servers that can route to the bot's rooms, for the `via` of space links
+fn via(client: &Client) -> Vec<OwnedServerName> {
+    client
+        .user_id()
+        .map(|user| vec![user.server_name().to_owned()])
+        .unwrap_or_default()
+}
+
+/// List `room_id` in `space`, and mark `space` as the room's parent
+pub async fn add_child(client: &Client, space: &RoomId, room_id: &RoomId) -> Result<()> {
+    let space_room = client.get_room(space).context("Space not found")?;
+    space_room
+        .send_state_event_for_key(room_id, SpaceChildEventContent::new(via(client)))
+        .await
+        .context("Failed to add room to space")?;
+
+    let room = client.get_room(room_id).context("Room not found")?;
+    room.send_state_event_for_key(space, SpaceParentEventContent::new(via(client)))
+        .await
+        .context("Failed to set room's parent space")?;
+    Ok(())
+}
+
+/// Take `room_id` out of `space`. An empty `via` removes a child.
+pub async fn remove_child(client: &Client, space: &RoomId, room_id: &RoomId) -> Result<()> {
+    let space_room = client.get_room(space).context("Space not found")?;
+    space_room
+        .send_state_event_for_key(room_id, SpaceChildEventContent::new(Vec::new()))
+        .await
+        .context("Failed to remove room from space")?;
+    Ok(())
+}
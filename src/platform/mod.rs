@@ -1,13 +1,17 @@
 // ABOUTME: Platform abstraction module for gorp
-// ABOUTME: Re-exports platform implementations (Matrix, Telegram, Slack)
+// ABOUTME: Re-exports platform implementations (Matrix, Telegram, Slack, IRC, Zulip)
 
 pub mod factory;
+#[cfg(feature = "irc")]
+pub mod irc;
 pub mod matrix;
 pub mod registry;
 #[cfg(feature = "slack")]
 pub mod slack;
 #[cfg(feature = "telegram")]
 pub mod telegram;
+#[cfg(feature = "zulip")]
+pub mod zulip;
 
 // Re-export registry types
 pub use registry::{PlatformHealth, PlatformRegistry, SharedPlatformRegistry};
@@ -25,7 +29,11 @@ pub use matrix::{
     MatrixPlatform,
 };
 
+#[cfg(feature = "irc")]
+pub use irc::{IrcChannel, IrcPlatform};
 #[cfg(feature = "slack")]
 pub use slack::{SlackChannel, SlackPlatform};
 #[cfg(feature = "telegram")]
 pub use telegram::{TelegramChannel, TelegramPlatform};
+#[cfg(feature = "zulip")]
+pub use zulip::{ZulipChannel, ZulipPlatform};
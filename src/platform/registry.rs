@@ -3,10 +3,17 @@
 
 use anyhow::Result;
 use futures_util::stream::SelectAll;
-use gorp_core::{EventStream, IncomingMessage, MessagingPlatform, PlatformConnectionState};
+use gorp_core::{
+    ConnectionEvent, EventStream, IncomingMessage, MessagingPlatform, PlatformConnectionState,
+    Presence,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Connection events buffered for a subscriber that falls behind
+const CONNECTION_EVENT_CAPACITY: usize = 256;
 
 /// Thread-safe shared registry for use across admin, websocket, and main tasks
 pub type SharedPlatformRegistry = Arc<tokio::sync::RwLock<PlatformRegistry>>;
@@ -22,21 +29,47 @@ pub struct PlatformHealth {
 /// Holds platform instances, merges their event streams, and coordinates lifecycle.
 pub struct PlatformRegistry {
     platforms: HashMap<String, Box<dyn MessagingPlatform>>,
+    /// Connection changes of every registered platform that reports them
+    connection_events: broadcast::Sender<ConnectionEvent>,
 }
 
 impl PlatformRegistry {
     pub fn new() -> Self {
+        let (connection_events, _) = broadcast::channel(CONNECTION_EVENT_CAPACITY);
         Self {
             platforms: HashMap::new(),
+            connection_events,
         }
     }
 
     /// Register a platform. Uses platform_id() as the key.
+    /// Its connection events, if it has any, are forwarded to the registry's subscribers.
     pub fn register(&mut self, platform: Box<dyn MessagingPlatform>) {
         let id = platform.platform_id().to_string();
+        if let Some(mut events) = platform.connection_events() {
+            let forward = self.connection_events.clone();
+            tokio::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => {
+                            let _ = forward.send(event);
+                        }
+                        Err(broadcast::error::RecvError::Lagged(missed)) => {
+                            tracing::debug!(missed, "Connection event forwarder lagged");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
         self.platforms.insert(id, platform);
     }
 
+    /// Connection state changes of all platforms registered now or later
+    pub fn subscribe_connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection_events.subscribe()
+    }
+
     /// Get a platform by its ID.
     pub fn get(&self, platform_id: &str) -> Option<&dyn MessagingPlatform> {
         self.platforms.get(platform_id).map(|p| p.as_ref())
@@ -80,6 +113,18 @@ impl PlatformRegistry {
         Ok(Box::pin(select_all))
     }
 
+    /// Show `presence` on every platform that can; failures are only logged.
+    pub async fn set_presence(&self, presence: Presence) {
+        for (id, platform) in &self.platforms {
+            let Some(provider) = platform.presence() else {
+                continue;
+            };
+            if let Err(e) = provider.set_presence(presence).await {
+                tracing::warn!(platform = %id, ?presence, error = %e, "Failed to set presence");
+            }
+        }
+    }
+
     /// Gracefully shut down all platforms with a 10-second timeout.
     pub async fn shutdown(&self) {
         let futures: Vec<_> = self.platforms.values().map(|p| p.shutdown()).collect();
@@ -331,14 +376,23 @@ mod tests {
         let health = registry.health();
         assert_eq!(health.len(), 2);
 
-        let disconnected = health.iter().find(|h| h.platform_id == "disconnected_one").unwrap();
+        let disconnected = health
+            .iter()
+            .find(|h| h.platform_id == "disconnected_one")
+            .unwrap();
         assert!(matches!(
             disconnected.state,
             PlatformConnectionState::Disconnected { .. }
         ));
 
-        let connected = health.iter().find(|h| h.platform_id == "connected_one").unwrap();
-        assert!(matches!(connected.state, PlatformConnectionState::Connected));
+        let connected = health
+            .iter()
+            .find(|h| h.platform_id == "connected_one")
+            .unwrap();
+        assert!(matches!(
+            connected.state,
+            PlatformConnectionState::Connected
+        ));
     }
 
     #[tokio::test]
@@ -356,6 +410,59 @@ mod tests {
         assert!(registry.get("slack").is_some());
     }
 
+    struct MockPlatformWithTracker {
+        tracker: gorp_core::ConnectionTracker,
+    }
+
+    #[async_trait]
+    impl MessagingPlatform for MockPlatformWithTracker {
+        async fn event_stream(&self) -> Result<EventStream> {
+            Ok(Box::pin(tokio_stream::empty()))
+        }
+
+        async fn send(&self, _channel_id: &str, _content: MessageContent) -> Result<()> {
+            Ok(())
+        }
+
+        fn bot_user_id(&self) -> &str {
+            "bot"
+        }
+
+        fn platform_id(&self) -> &'static str {
+            "slack"
+        }
+
+        fn connection_state(&self) -> PlatformConnectionState {
+            self.tracker.get()
+        }
+
+        fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+            Some(self.tracker.subscribe())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_events_reach_registry_subscribers() {
+        let tracker =
+            gorp_core::ConnectionTracker::new("slack", PlatformConnectionState::Connected);
+        let mut registry = PlatformRegistry::new();
+        let mut events = registry.subscribe_connection_events();
+        registry.register(Box::new(MockPlatformWithTracker {
+            tracker: tracker.clone(),
+        }));
+
+        tracker.set(PlatformConnectionState::Disconnected {
+            reason: "socket closed".to_string(),
+        });
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.platform_id, "slack");
+        assert_eq!(event.reason(), Some("socket closed"));
+        assert_eq!(registry.health()[0].state.label(), "disconnected");
+    }
+
     #[tokio::test]
     async fn test_unregister_nonexistent_returns_none() {
         let mut registry = PlatformRegistry::new();
@@ -12,6 +12,21 @@ const MAX_CODE_BLOCK_CHARS: usize = 4000;
 /// Maximum blocks per message (Slack limit is 50)
 const MAX_BLOCKS: usize = 50;
 
+/// Maximum buttons in one actions block
+const MAX_ACTION_BUTTONS: usize = 25;
+
+/// Maximum characters of a button label
+const MAX_BUTTON_LABEL_CHARS: usize = 75;
+
+/// Maximum characters of a button value
+const MAX_BUTTON_VALUE_CHARS: usize = 2000;
+
+/// Maximum characters of an image block's alt text and title
+const MAX_IMAGE_TEXT_CHARS: usize = 2000;
+
+/// Prefix of the action_id on suggested-reply buttons, so clicks can be told apart
+pub const SUGGESTED_ACTION_PREFIX: &str = "gorp_suggest_";
+
 /// Convert markdown/plain text content into Slack Block Kit JSON blocks array.
 ///
 /// This function is infallible — it always returns valid Block Kit JSON,
@@ -87,6 +102,64 @@ pub fn markdown_to_blocks(content: &str) -> Value {
     Value::Array(blocks)
 }
 
+/// Build a section with `text` followed by one button per suggested reply.
+/// Each button's value is the reply itself, sent back as the user's message when clicked.
+pub fn suggested_action_blocks(text: &str, actions: &[String]) -> Value {
+    let buttons: Vec<Value> = actions
+        .iter()
+        .take(MAX_ACTION_BUTTONS)
+        .enumerate()
+        .map(|(i, action)| {
+            let label: String = action.chars().take(MAX_BUTTON_LABEL_CHARS).collect();
+            let value: String = action.chars().take(MAX_BUTTON_VALUE_CHARS).collect();
+            json!({
+                "type": "button",
+                "action_id": format!("{}{}", SUGGESTED_ACTION_PREFIX, i),
+                "text": {
+                    "type": "plain_text",
+                    "text": label
+                },
+                "value": value
+            })
+        })
+        .collect();
+
+    json!([
+        {
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": text
+            }
+        },
+        {
+            "type": "actions",
+            "elements": buttons
+        }
+    ])
+}
+
+/// Whether Slack can show an image of this type inline in an image block
+pub fn is_inline_image(mime_type: &str) -> bool {
+    matches!(mime_type, "image/png" | "image/jpeg" | "image/gif")
+}
+
+/// An image block showing an already uploaded file, titled with the caption if there is one
+pub fn image_blocks(file_id: &str, alt_text: &str, title: Option<&str>) -> Value {
+    let mut block = json!({
+        "type": "image",
+        "slack_file": {"id": file_id},
+        "alt_text": alt_text.chars().take(MAX_IMAGE_TEXT_CHARS).collect::<String>()
+    });
+    if let Some(title) = title {
+        block["title"] = json!({
+            "type": "plain_text",
+            "text": title.chars().take(MAX_IMAGE_TEXT_CHARS).collect::<String>()
+        });
+    }
+    json!([block])
+}
+
 // =============================================================================
 // Content segmentation
 // =============================================================================
@@ -124,18 +197,12 @@ fn split_code_blocks(content: &str) -> Vec<Segment> {
         // Find closing ```
         if let Some(end) = code_content.find("```") {
             let code = code_content[..end].trim_end().to_string();
-            segments.push(Segment::CodeBlock {
-                language,
-                code,
-            });
+            segments.push(Segment::CodeBlock { language, code });
             remaining = &code_content[end + 3..];
         } else {
             // Unclosed code block — treat rest as code
             let code = code_content.trim_end().to_string();
-            segments.push(Segment::CodeBlock {
-                language,
-                code,
-            });
+            segments.push(Segment::CodeBlock { language, code });
             remaining = "";
         }
     }
@@ -208,7 +275,10 @@ mod tests {
         let arr = blocks.as_array().unwrap();
         assert_eq!(arr.len(), 3);
         assert_eq!(arr[0]["text"]["text"], "Before code");
-        assert!(arr[1]["text"]["text"].as_str().unwrap().contains("fn main() {}"));
+        assert!(arr[1]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("fn main() {}"));
         assert_eq!(arr[2]["text"]["text"], "After code");
     }
 
@@ -279,6 +349,44 @@ mod tests {
         assert_eq!(arr.len(), 5);
     }
 
+    #[test]
+    fn test_suggested_action_blocks() {
+        let actions = vec!["Run the tests".to_string(), "x".repeat(100)];
+        let blocks = suggested_action_blocks("Next?", &actions);
+        let buttons = blocks[1]["elements"].as_array().unwrap();
+
+        assert_eq!(blocks[0]["text"]["text"], "Next?");
+        assert_eq!(buttons.len(), 2);
+        assert_eq!(buttons[0]["action_id"], "gorp_suggest_0");
+        assert_eq!(buttons[0]["value"], "Run the tests");
+        assert_eq!(
+            buttons[1]["text"]["text"].as_str().unwrap().len(),
+            MAX_BUTTON_LABEL_CHARS
+        );
+        assert_eq!(buttons[1]["value"].as_str().unwrap().len(), 100);
+    }
+
+    #[test]
+    fn test_image_attachment_becomes_image_block() {
+        assert!(is_inline_image("image/png"));
+        assert!(!is_inline_image("image/svg+xml"));
+        assert!(!is_inline_image("application/pdf"));
+
+        let blocks = image_blocks("F123", "chart.png", Some("Weekly totals"));
+        assert_eq!(blocks[0]["type"], "image");
+        assert_eq!(blocks[0]["slack_file"]["id"], "F123");
+        assert_eq!(blocks[0]["alt_text"], "chart.png");
+        assert_eq!(blocks[0]["title"]["text"], "Weekly totals");
+
+        // The block must be one slack-morphism can send
+        let parsed: Vec<slack_morphism::prelude::SlackBlock> =
+            serde_json::from_value(blocks).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert!(image_blocks("F123", "chart.png", None)[0]
+            .get("title")
+            .is_none());
+    }
+
     #[test]
     fn test_split_code_blocks_no_code() {
         let segments = split_code_blocks("just plain text");
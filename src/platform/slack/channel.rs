@@ -1,15 +1,22 @@
 // ABOUTME: Slack channel implementation wrapping a Slack channel for the ChatChannel trait
-// ABOUTME: Handles message sending via Slack Web API with 4K-char chunking and mrkdwn formatting
+// ABOUTME: Handles message sending via Slack Web API with 4K-char chunking, file uploads and downloads
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use gorp_core::traits::{ChatChannel, MessageContent, TypingIndicator};
+use gorp_core::traits::{
+    AttachmentHandler, ChatChannel, EphemeralSender, MessageContent, TypingIndicator,
+};
 use slack_morphism::prelude::*;
 use std::sync::Arc;
 
+use super::blocks;
+
 /// Maximum message length for a single Slack mrkdwn text block
 const MAX_MESSAGE_LENGTH: usize = 4000;
 
+/// Reaction that tells a sender their message was picked up
+const SEEN_REACTION: &str = "eyes";
+
 /// A Slack channel wrapped as a ChatChannel
 #[derive(Debug, Clone)]
 pub struct SlackChannel {
@@ -75,6 +82,84 @@ impl SlackChannel {
         }
         Ok(())
     }
+
+    /// Upload a file's bytes the way files.uploadV2 does: get an upload URL, send
+    /// the data to it, then complete the upload. With `share` the file is posted
+    /// into this channel, with the caption as its comment; otherwise it stays unshared.
+    async fn upload_file(
+        &self,
+        filename: &str,
+        data: Vec<u8>,
+        mime_type: &str,
+        share: bool,
+        caption: Option<String>,
+    ) -> Result<SlackFileId> {
+        let session = self.client.open_session(&self.bot_token);
+
+        let upload = session
+            .get_upload_url_external(&SlackApiFilesGetUploadUrlExternalRequest::new(
+                filename.to_string(),
+                data.len(),
+            ))
+            .await
+            .context("Failed to start Slack file upload")?;
+        session
+            .files_upload_via_url(&SlackApiFilesUploadViaUrlRequest::new(
+                upload.upload_url,
+                data,
+                mime_type.to_string(),
+            ))
+            .await
+            .context("Failed to upload file to Slack")?;
+
+        let mut complete =
+            SlackApiFilesCompleteUploadExternalRequest::new(vec![SlackApiFilesComplete::new(
+                upload.file_id.clone(),
+            )]);
+        if share {
+            complete = complete.with_channel_id(self.channel_id.clone());
+            if let Some(caption) = caption {
+                complete = complete.with_initial_comment(caption);
+            }
+        }
+        session
+            .files_complete_upload_external(&complete)
+            .await
+            .context("Failed to finish Slack file upload")?;
+        Ok(upload.file_id)
+    }
+
+    /// Upload an image and post it in an image block, so it shows inline
+    async fn send_image(
+        &self,
+        filename: String,
+        data: Vec<u8>,
+        mime_type: &str,
+        caption: Option<String>,
+    ) -> Result<()> {
+        let file_id = self
+            .upload_file(&filename, data, mime_type, false, None)
+            .await?;
+
+        let image_blocks: Vec<SlackBlock> = serde_json::from_value(blocks::image_blocks(
+            &file_id.to_string(),
+            &filename,
+            caption.as_deref(),
+        ))
+        .context("Failed to build Slack image block")?;
+        let req = SlackApiChatPostMessageRequest::new(
+            self.channel_id.clone(),
+            SlackMessageContent::new()
+                .with_text(caption.unwrap_or(filename))
+                .with_blocks(image_blocks),
+        );
+        self.client
+            .open_session(&self.bot_token)
+            .chat_post_message(&req)
+            .await
+            .context("Failed to send Slack image")?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -83,6 +168,10 @@ impl ChatChannel for SlackChannel {
         &self.channel_id_str
     }
 
+    fn platform_id(&self) -> &str {
+        "slack"
+    }
+
     fn name(&self) -> Option<String> {
         self.channel_name.clone()
     }
@@ -103,24 +192,19 @@ impl ChatChannel for SlackChannel {
             MessageContent::Attachment {
                 filename,
                 data,
+                mime_type,
+                caption,
+            } if blocks::is_inline_image(&mime_type) => {
+                self.send_image(filename, data, &mime_type, caption).await?;
+            }
+            MessageContent::Attachment {
+                filename,
+                data,
+                mime_type,
                 caption,
-                ..
             } => {
-                let session = self.client.open_session(&self.bot_token);
-
-                // Upload file via files.uploadV2
-                // For simplicity, post a message with the file info
-                // Full file upload requires multipart form which slack-morphism handles
-                let caption_text = caption.unwrap_or_else(|| filename.clone());
-                let req = SlackApiChatPostMessageRequest::new(
-                    self.channel_id.clone(),
-                    SlackMessageContent::new()
-                        .with_text(format!("[Attachment: {} ({} bytes)]", caption_text, data.len())),
-                );
-                session
-                    .chat_post_message(&req)
-                    .await
-                    .context("Failed to send attachment message")?;
+                self.upload_file(&filename, data, &mime_type, true, caption)
+                    .await?;
             }
         }
         Ok(())
@@ -130,6 +214,139 @@ impl ChatChannel for SlackChannel {
         // Slack doesn't have a "typing indicator" API for bots
         None
     }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        Some(self)
+    }
+
+    fn ephemeral(&self) -> Option<&dyn EphemeralSender> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl AttachmentHandler for SlackChannel {
+    async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
+        download_file(&self.bot_token.token_value.0, source_id).await
+    }
+}
+
+#[async_trait]
+impl EphemeralSender for SlackChannel {
+    async fn send_ephemeral(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        content: MessageContent,
+    ) -> Result<()> {
+        match content {
+            MessageContent::Plain(text) | MessageContent::Html { plain: text, .. } => {
+                post_ephemeral(&self.client, &self.bot_token, channel_id, user_id, &text).await
+            }
+            // Files can't be ephemeral
+            attachment => self.send(attachment).await,
+        }
+    }
+}
+
+/// Post `text` in `channel_id` visibly only to `user_id`. Slack drops it when
+/// the user isn't in the channel, and keeps it out of the channel's history.
+pub(crate) async fn post_ephemeral(
+    client: &SlackHyperClient,
+    bot_token: &SlackApiToken,
+    channel_id: &str,
+    user_id: &str,
+    text: &str,
+) -> Result<()> {
+    let req = SlackApiChatPostEphemeralRequest::new(
+        channel_id.into(),
+        user_id.into(),
+        SlackMessageContent::new().with_text(text.to_string()),
+    );
+    client
+        .open_session(bot_token)
+        .chat_post_ephemeral(&req)
+        .await
+        .context("Failed to send Slack ephemeral message")?;
+    Ok(())
+}
+
+/// React with 👀 to the message with timestamp `message_id`. Slash commands and
+/// button clicks carry made-up IDs rather than a message timestamp, and are skipped.
+pub(crate) async fn add_seen_reaction(
+    client: &SlackHyperClient,
+    bot_token: &SlackApiToken,
+    channel_id: &str,
+    message_id: &str,
+) -> Result<()> {
+    if !is_message_ts(message_id) {
+        return Ok(());
+    }
+    let req = SlackApiReactionsAddRequest::new(
+        channel_id.into(),
+        SlackReactionName(SEEN_REACTION.to_string()),
+        SlackTs(message_id.to_string()),
+    );
+    client
+        .open_session(bot_token)
+        .reactions_add(&req)
+        .await
+        .context("Failed to add Slack reaction")?;
+    Ok(())
+}
+
+/// Whether `id` looks like a Slack message timestamp, e.g. "1712345678.000100"
+fn is_message_ts(id: &str) -> bool {
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    id.split_once('.')
+        .is_some_and(|(secs, micros)| digits(secs) && digits(micros))
+}
+
+/// Fetch a shared file. Files are private: the source ID is the file's
+/// `url_private_download`, fetched with the bot token, which is only ever
+/// sent to Slack's own hosts.
+pub(crate) async fn download_file(
+    bot_token: &str,
+    source_id: &str,
+) -> Result<(String, Vec<u8>, String)> {
+    let url = reqwest::Url::parse(source_id).context("Invalid Slack file URL")?;
+    if !is_slack_file_url(&url) {
+        anyhow::bail!("Refusing to download a Slack file from {}", url);
+    }
+
+    let response = reqwest::Client::new()
+        .get(url.clone())
+        .bearer_auth(bot_token)
+        .send()
+        .await
+        .context("Failed to download Slack file")?
+        .error_for_status()
+        .context("Slack refused the file download")?;
+    let mime_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    let data = response
+        .bytes()
+        .await
+        .context("Failed to read Slack file")?;
+    let filename = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or("attachment")
+        .to_string();
+
+    Ok((filename, data.to_vec(), mime_type))
+}
+
+/// Whether the bot token may be sent to `url`
+fn is_slack_file_url(url: &reqwest::Url) -> bool {
+    url.scheme() == "https"
+        && url
+            .host_str()
+            .is_some_and(|host| host == "slack.com" || host.ends_with(".slack.com"))
 }
 
 /// Split text into chunks at line boundaries, falling back to character boundaries
@@ -214,6 +431,28 @@ mod tests {
         assert_eq!(id.to_string(), "C12345");
     }
 
+    #[test]
+    fn test_token_only_goes_to_slack_hosts() {
+        let ok = |url: &str| is_slack_file_url(&reqwest::Url::parse(url).unwrap());
+        assert!(ok(
+            "https://files.slack.com/files-pri/T1-F1/download/one.png"
+        ));
+        assert!(ok("https://slack.com/files/x"));
+        assert!(!ok(
+            "http://files.slack.com/files-pri/T1-F1/download/one.png"
+        ));
+        assert!(!ok("https://files.slack.com.evil.example/one.png"));
+        assert!(!ok("https://evilslack.com/one.png"));
+    }
+
+    #[test]
+    fn test_only_message_timestamps_get_a_reaction() {
+        assert!(is_message_ts("1712345678.000100"));
+        assert!(!is_message_ts("cmd_1712345678000"));
+        assert!(!is_message_ts("action_123.4_gorp_suggest_0"));
+        assert!(!is_message_ts("1712345678"));
+    }
+
     #[test]
     fn test_dm_channel_detection() {
         // DM channels start with "D"
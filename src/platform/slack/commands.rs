@@ -3,7 +3,9 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
-use gorp_core::traits::{MessageContent, SlashCommandDef, SlashCommandInvocation, SlashCommandProvider};
+use gorp_core::traits::{
+    MessageContent, SlashCommandDef, SlashCommandInvocation, SlashCommandProvider,
+};
 
 /// Slash command handler for the Slack platform.
 ///
@@ -40,19 +42,29 @@ impl SlashCommandProvider for SlackCommandHandler {
                 // This returns the immediate ACK response shown to the user.
                 Ok(MessageContent::plain("Working on it..."))
             }
-            "/gorp-status" => {
-                Ok(MessageContent::plain("Checking status..."))
-            }
-            _ => {
-                Ok(MessageContent::plain(format!(
-                    "Unknown command: {}",
-                    cmd.command
-                )))
-            }
+            "/gorp-status" => Ok(MessageContent::plain("Checking status...")),
+            _ => Ok(MessageContent::plain(format!(
+                "Unknown command: {}",
+                cmd.command
+            ))),
         }
     }
 }
 
+/// The chat message a slash command stands for. `/gorp <text>` is sent as if
+/// typed in the channel, so `/gorp !status` runs a command and anything else
+/// is a prompt; `/gorp` alone asks for help and `/gorp-status` for status.
+pub fn slash_command_body(command: &str, text: &str, command_prefix: &str) -> String {
+    let text = text.trim();
+    match command {
+        "/gorp" if text.is_empty() => format!("{}help", command_prefix),
+        "/gorp" => text.to_string(),
+        "/gorp-status" => format!("{}status", command_prefix),
+        _ if text.is_empty() => command.to_string(),
+        _ => format!("{} {}", command, text),
+    }
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -112,6 +124,24 @@ mod tests {
         assert!(matches!(result, MessageContent::Plain(text) if text.contains("Unknown")));
     }
 
+    #[test]
+    fn test_slash_command_body_uses_command_prefix() {
+        assert_eq!(slash_command_body("/gorp", "", "gorp!"), "gorp!help");
+        assert_eq!(
+            slash_command_body("/gorp-status", "", "gorp!"),
+            "gorp!status"
+        );
+        assert_eq!(
+            slash_command_body("/gorp", " gorp!list ", "gorp!"),
+            "gorp!list"
+        );
+        assert_eq!(
+            slash_command_body("/gorp", "summarize this thread", "!"),
+            "summarize this thread"
+        );
+        assert_eq!(slash_command_body("/other", "x", "!"), "/other x");
+    }
+
     #[test]
     fn test_command_handler_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
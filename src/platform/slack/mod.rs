@@ -4,19 +4,29 @@
 pub mod blocks;
 pub mod channel;
 pub mod commands;
+pub mod names;
+pub mod schedules;
+mod threads;
 
 pub use channel::SlackChannel;
+pub use names::SlackNameResolver;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use gorp_core::config::Config;
+use gorp_core::connection::{ConnectionEvent, ConnectionTracker};
+use gorp_core::reconnect::{BackoffConfig, BackoffState};
+use gorp_core::scheduler::SchedulerStore;
 use gorp_core::traits::{
-    ChannelCreator, ChannelManager, ChatChannel, ChatPlatform, ChatUser, EventStream,
-    IncomingMessage, MessageContent, MessagingPlatform, PlatformConnectionState, RichFormatter,
-    SlashCommandProvider, ThreadedPlatform,
+    ActionButtons, AttachmentHandler, AttachmentInfo, ChannelCreator, ChannelManager, ChatChannel,
+    ChatPlatform, ChatUser, EphemeralSender, EventStream, IncomingMessage, MessageContent,
+    MessagingPlatform, PlatformConnectionState, ReceiptSender, RichFormatter, SlashCommandProvider,
+    ThreadedPlatform,
 };
 use slack_morphism::prelude::*;
-use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 
 use self::commands::SlackCommandHandler;
@@ -39,6 +49,63 @@ struct SlackBridgeState {
     allowed_users: Vec<String>,
     /// Allowed channel IDs (empty = allow all)
     allowed_channels: Vec<String>,
+    /// Command prefix slash commands are translated to
+    command_prefix: String,
+    /// Bot token for replies sent from inside callbacks
+    bot_token: SlackApiToken,
+    /// Schedules behind the `!schedule list` buttons; without it the list isn't interactive
+    scheduler: Option<schedules::ScheduleControls>,
+    /// User and channel names, shared with SlackPlatform
+    names: Arc<SlackNameResolver>,
+    /// Answer channel messages in threads (`thread_in_channels`)
+    thread_in_channels: bool,
+    /// Recent top-level posters per channel, to tell when a channel is busy
+    activity: Arc<threads::ChannelActivity>,
+}
+
+impl SlackBridgeState {
+    /// Whether the allowlists let `sender_id` reach the bot from `channel_id`
+    fn is_allowed(&self, sender_id: &str, channel_id: &str) -> bool {
+        (self.allowed_users.is_empty() || self.allowed_users.iter().any(|u| u == sender_id))
+            && (self.allowed_channels.is_empty()
+                || self.allowed_channels.iter().any(|c| c == channel_id))
+    }
+
+    /// The schedule store, if `sender_id` may manage schedules in `channel_id`:
+    /// the same user, channel and role checks `!schedule` goes through
+    fn schedules_for(&self, sender_id: &str, channel_id: &str) -> Option<&SchedulerStore> {
+        let controls = self.scheduler.as_ref()?;
+        (self.is_allowed(sender_id, channel_id) && controls.may_manage(sender_id))
+            .then_some(&controls.store)
+    }
+
+    /// The schedule store if `body` is `!schedule list` from someone who may see it
+    fn schedule_list_request(
+        &self,
+        sender_id: &str,
+        channel_id: &str,
+        body: &str,
+    ) -> Option<&SchedulerStore> {
+        if !schedules::is_list_command(body, &self.command_prefix) {
+            return None;
+        }
+        self.schedules_for(sender_id, channel_id)
+    }
+
+    /// Apply a clicked schedule button, if `sender_id` may manage schedules here.
+    /// Returns the store, to redraw the list from, and the line telling them what happened.
+    fn schedule_click(
+        &self,
+        sender_id: &str,
+        channel_id: &str,
+        action: schedules::ScheduleAction,
+        schedule_id: &str,
+    ) -> Option<(&SchedulerStore, String)> {
+        let store = self.schedules_for(sender_id, channel_id)?;
+        let notice = schedules::apply(store, channel_id, action, schedule_id)
+            .unwrap_or_else(|e| format!("⚠️ {}", e));
+        Some((store, notice))
+    }
 }
 
 // =============================================================================
@@ -48,7 +115,7 @@ struct SlackBridgeState {
 /// Handle push events (messages, app mentions) from Socket Mode
 async fn handle_push_event(
     event: SlackPushEventCallback,
-    _client: Arc<SlackHyperClient>,
+    client: Arc<SlackHyperClient>,
     states: SlackClientEventsUserState,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let bridge = {
@@ -61,10 +128,10 @@ async fn handle_push_event(
 
     match event.event {
         SlackEventCallbackBody::Message(msg_event) => {
-            handle_message_event(&bridge, &msg_event).await;
+            handle_message_event(&bridge, &client, &msg_event).await;
         }
         SlackEventCallbackBody::AppMention(mention_event) => {
-            handle_mention_event(&bridge, &mention_event).await;
+            handle_mention_event(&bridge, &client, &mention_event).await;
         }
         _ => {
             // Ignore other event types
@@ -76,7 +143,7 @@ async fn handle_push_event(
 /// Handle slash command events from Socket Mode
 async fn handle_command_event(
     event: SlackCommandEvent,
-    _client: Arc<SlackHyperClient>,
+    client: Arc<SlackHyperClient>,
     states: SlackClientEventsUserState,
 ) -> Result<SlackCommandEventResponse, Box<dyn std::error::Error + Send + Sync>> {
     let bridge = {
@@ -88,26 +155,41 @@ async fn handle_command_event(
     };
 
     // Route slash command as a message through the event stream
-    let command_text = event.command.to_string();
-    let body = match &event.text {
-        Some(text) if !text.is_empty() => format!("{} {}", command_text, text),
-        _ => command_text,
-    };
+    let body = commands::slash_command_body(
+        &event.command.to_string(),
+        event.text.as_deref().unwrap_or_default(),
+        &bridge.command_prefix,
+    );
+    let channel_id = event.channel_id.to_string();
+    let sender_id = event.user_id.to_string();
+    if post_schedule_list_for(&bridge, &client, &sender_id, &channel_id, &body).await {
+        return Ok(SlackCommandEventResponse::new(
+            SlackMessageContent::new().with_text("📅 Here are this channel's schedules.".into()),
+        ));
+    }
 
     let msg = IncomingMessage {
         platform_id: "slack".to_string(),
-        channel_id: event.channel_id.to_string(),
+        channel_id,
         thread_id: None,
-        sender: ChatUser::new(event.user_id.to_string()),
+        sender: ChatUser::new(sender_id),
         body,
         is_direct: false,
         formatted: false,
-        attachment: None,
+        attachments: Vec::new(),
         event_id: format!("cmd_{}", chrono::Utc::now().timestamp_millis()),
+        edits_event_id: None,
         timestamp: chrono::Utc::now().timestamp(),
     };
 
-    let _ = bridge.tx.send(msg).await;
+    if bridge.tx.send(msg).await.is_err() {
+        tracing::warn!(platform = "slack", "Event stream receiver dropped");
+        return Ok(SlackCommandEventResponse::new(
+            SlackMessageContent::new()
+                .with_text("⚠️ gorp isn't taking requests right now. Try again shortly.".into()),
+        )
+        .with_response_type(SlackMessageResponseType::Ephemeral));
+    }
 
     // Return immediate ACK response
     Ok(SlackCommandEventResponse::new(
@@ -115,8 +197,169 @@ async fn handle_command_event(
     ))
 }
 
+/// Handle interactive events from Socket Mode.
+/// A clicked suggested-reply button comes back as a message from the user;
+/// a schedule button changes the schedule and redraws the list it was in.
+async fn handle_interaction_event(
+    event: SlackInteractionEvent,
+    client: Arc<SlackHyperClient>,
+    states: SlackClientEventsUserState,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let SlackInteractionEvent::BlockActions(event) = event else {
+        return Ok(());
+    };
+    let bridge = {
+        let guard = states.read().await;
+        guard
+            .get_user_state::<SlackBridgeState>()
+            .cloned()
+            .ok_or_else(|| "SlackBridgeState not found in user state")?
+    };
+
+    let (Some(user), Some(channel)) = (&event.user, &event.channel) else {
+        return Ok(());
+    };
+    let sender_id = user.id.to_string();
+    let channel_id = channel.id.to_string();
+
+    if !bridge.is_allowed(&sender_id, &channel_id) {
+        return Ok(());
+    }
+
+    for action in event.actions.iter().flatten() {
+        let action_id = action.action_id.to_string();
+        if let Some(schedule_action) = schedules::ScheduleAction::from_action_id(&action_id) {
+            let Some(schedule_id) = &action.value else {
+                continue;
+            };
+            let Some((scheduler, notice)) =
+                bridge.schedule_click(&sender_id, &channel_id, schedule_action, schedule_id)
+            else {
+                tracing::info!(
+                    platform = "slack",
+                    user_id = %sender_id,
+                    schedule_id = %schedule_id,
+                    "Ignoring schedule button from someone who can't manage schedules"
+                );
+                continue;
+            };
+            tracing::info!(
+                platform = "slack",
+                user_id = %sender_id,
+                schedule_id = %schedule_id,
+                outcome = %notice,
+                "Schedule button clicked"
+            );
+            let message_ts = event.message.as_ref().map(|m| m.origin.ts.clone());
+            if let Err(e) = send_schedule_list(
+                &bridge,
+                &client,
+                scheduler,
+                &channel_id,
+                Some(&notice),
+                message_ts,
+            )
+            .await
+            {
+                tracing::warn!(platform = "slack", error = %e, "Failed to redraw schedule list");
+            }
+            continue;
+        }
+        if !action_id.starts_with(blocks::SUGGESTED_ACTION_PREFIX) {
+            continue;
+        }
+        let Some(body) = action.value.clone() else {
+            continue;
+        };
+
+        let msg = IncomingMessage {
+            platform_id: "slack".to_string(),
+            channel_id: channel_id.clone(),
+            thread_id: None,
+            sender: ChatUser::new(sender_id.clone()),
+            body,
+            is_direct: channel_id.starts_with('D'),
+            formatted: false,
+            attachments: Vec::new(),
+            event_id: format!("action_{}_{}", event.trigger_id, action_id),
+            edits_event_id: None,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+
+        if bridge.tx.send(msg).await.is_err() {
+            tracing::warn!(platform = "slack", "Event stream receiver dropped");
+        }
+    }
+    Ok(())
+}
+
+/// Post the channel's schedules with buttons if `body` is `!schedule list` from
+/// someone who may manage them. Returns whether it was, so the message isn't
+/// handled a second time; anyone else's goes on to the usual command checks.
+async fn post_schedule_list_for(
+    bridge: &SlackBridgeState,
+    client: &SlackHyperClient,
+    sender_id: &str,
+    channel_id: &str,
+    body: &str,
+) -> bool {
+    let Some(scheduler) = bridge.schedule_list_request(sender_id, channel_id, body) else {
+        return false;
+    };
+    if let Err(e) = send_schedule_list(bridge, client, scheduler, channel_id, None, None).await {
+        tracing::warn!(platform = "slack", error = %e, "Failed to send schedule list");
+    }
+    true
+}
+
+/// Send the schedule list of `channel_id`, or with `replace` rewrite that message
+/// in place, so the buttons always act on what the list shows
+async fn send_schedule_list(
+    bridge: &SlackBridgeState,
+    client: &SlackHyperClient,
+    scheduler: &SchedulerStore,
+    channel_id: &str,
+    notice: Option<&str>,
+    replace: Option<SlackTs>,
+) -> Result<()> {
+    let listed = scheduler.list_by_room(channel_id)?;
+    let list_blocks: Vec<SlackBlock> =
+        serde_json::from_value(schedules::list_blocks(&listed, notice))
+            .context("Failed to build Slack schedule list")?;
+    let content = SlackMessageContent::new()
+        .with_text(schedules::list_text(&listed))
+        .with_blocks(list_blocks);
+    let session = client.open_session(&bridge.bot_token);
+    match replace {
+        Some(ts) => {
+            session
+                .chat_update(&SlackApiChatUpdateRequest::new(
+                    channel_id.into(),
+                    content,
+                    ts,
+                ))
+                .await
+                .context("Failed to update Slack schedule list")?;
+        }
+        None => {
+            session
+                .chat_post_message(&SlackApiChatPostMessageRequest::new(
+                    channel_id.into(),
+                    content,
+                ))
+                .await
+                .context("Failed to send Slack schedule list")?;
+        }
+    }
+    Ok(())
+}
+
 /// Process a Slack message event into an IncomingMessage
-async fn handle_message_event(bridge: &SlackBridgeState, msg_event: &SlackMessageEvent) {
+async fn handle_message_event(
+    bridge: &SlackBridgeState,
+    client: &SlackHyperClient,
+    msg_event: &SlackMessageEvent,
+) {
     // Extract sender user ID
     let sender_id = match &msg_event.sender.user {
         Some(user_id) => user_id.to_string(),
@@ -129,9 +372,7 @@ async fn handle_message_event(bridge: &SlackBridgeState, msg_event: &SlackMessag
     }
 
     // Skip if user not allowed
-    if !bridge.allowed_users.is_empty()
-        && !bridge.allowed_users.iter().any(|u| u == &sender_id)
-    {
+    if !bridge.allowed_users.is_empty() && !bridge.allowed_users.iter().any(|u| u == &sender_id) {
         tracing::debug!(
             platform = "slack",
             user_id = %sender_id,
@@ -166,21 +407,39 @@ async fn handle_message_event(bridge: &SlackBridgeState, msg_event: &SlackMessag
         .map(|t| t.to_string())
         .unwrap_or_default();
 
-    if body.is_empty() {
+    let attachments = message_files(msg_event.content.as_ref());
+
+    if body.is_empty() && attachments.is_empty() {
+        return;
+    }
+    if attachments.is_empty()
+        && post_schedule_list_for(bridge, client, &sender_id, &channel_id, &body).await
+    {
         return;
     }
-
-    // Extract thread_ts for threading
-    let thread_id = msg_event
-        .origin
-        .thread_ts
-        .as_ref()
-        .map(|ts| ts.to_string());
 
     // Detect DM vs channel (DM channel IDs start with "D")
     let is_direct = channel_id.starts_with('D');
 
-    let display_name = msg_event.sender.username.clone();
+    let thread_id = reply_thread(
+        bridge,
+        &channel_id,
+        &sender_id,
+        &msg_event.origin,
+        is_direct,
+    );
+
+    let display_name = match bridge.names.user_name(client, &sender_id).await {
+        Some(name) => Some(name),
+        None => msg_event.sender.username.clone(),
+    };
+    let channel_name = bridge.names.channel_name(client, &channel_id).await;
+    tracing::debug!(
+        platform = "slack",
+        channel = channel_name.as_deref().unwrap_or(&channel_id),
+        sender = display_name.as_deref().unwrap_or(&sender_id),
+        "Received Slack message"
+    );
 
     let timestamp = parse_slack_ts(&msg_event.origin.ts);
 
@@ -195,8 +454,9 @@ async fn handle_message_event(bridge: &SlackBridgeState, msg_event: &SlackMessag
         body,
         is_direct,
         formatted: false,
-        attachment: None,
+        attachments,
         event_id: msg_event.origin.ts.to_string(),
+        edits_event_id: None,
         timestamp,
     };
 
@@ -206,16 +466,18 @@ async fn handle_message_event(bridge: &SlackBridgeState, msg_event: &SlackMessag
 }
 
 /// Process a Slack app mention event into an IncomingMessage
-async fn handle_mention_event(bridge: &SlackBridgeState, mention_event: &SlackAppMentionEvent) {
+async fn handle_mention_event(
+    bridge: &SlackBridgeState,
+    client: &SlackHyperClient,
+    mention_event: &SlackAppMentionEvent,
+) {
     let sender_id = mention_event.user.to_string();
 
     if sender_id == bridge.bot_user_id {
         return;
     }
 
-    if !bridge.allowed_users.is_empty()
-        && !bridge.allowed_users.iter().any(|u| u == &sender_id)
-    {
+    if !bridge.allowed_users.is_empty() && !bridge.allowed_users.iter().any(|u| u == &sender_id) {
         return;
     }
 
@@ -229,13 +491,16 @@ async fn handle_mention_event(bridge: &SlackBridgeState, mention_event: &SlackAp
         .map(|t| t.to_string())
         .unwrap_or_default();
 
-    let thread_id = mention_event
-        .origin
-        .thread_ts
-        .as_ref()
-        .map(|ts| ts.to_string());
+    let thread_id = reply_thread(
+        bridge,
+        &channel_id,
+        &sender_id,
+        &mention_event.origin,
+        false,
+    );
 
     let timestamp = parse_slack_ts(&mention_event.origin.ts);
+    let display_name = bridge.names.user_name(client, &sender_id).await;
 
     let msg = IncomingMessage {
         platform_id: "slack".to_string(),
@@ -243,13 +508,14 @@ async fn handle_mention_event(bridge: &SlackBridgeState, mention_event: &SlackAp
         thread_id,
         sender: ChatUser {
             id: sender_id,
-            display_name: None,
+            display_name,
         },
         body,
         is_direct: false,
         formatted: false,
-        attachment: None,
+        attachments: Vec::new(),
         event_id: mention_event.origin.ts.to_string(),
+        edits_event_id: None,
         timestamp,
     };
 
@@ -258,6 +524,51 @@ async fn handle_mention_event(bridge: &SlackBridgeState, mention_event: &SlackAp
     }
 }
 
+/// The thread a reply to this message goes in, per `thread_in_channels`
+fn reply_thread(
+    bridge: &SlackBridgeState,
+    channel_id: &str,
+    sender_id: &str,
+    origin: &SlackMessageOrigin,
+    is_direct: bool,
+) -> Option<String> {
+    let thread_ts = origin.thread_ts.as_ref().map(|ts| ts.to_string());
+    let busy = thread_ts.is_none() && !is_direct && bridge.activity.record(channel_id, sender_id);
+    threads::reply_thread(
+        &threads::Origin {
+            ts: &origin.ts.to_string(),
+            thread_ts: thread_ts.as_deref(),
+            is_direct,
+        },
+        bridge.thread_in_channels,
+        busy,
+    )
+}
+
+/// A Socket Mode listener whose callbacks deliver into `bridge_state`
+fn socket_mode_listener(
+    client: Arc<SlackHyperClient>,
+    bridge_state: SlackBridgeState,
+) -> SlackClientSocketModeListener<SlackClientHyperHttpsConnector> {
+    // Callbacks are fn pointers, not closures
+    let callbacks = SlackSocketModeListenerCallbacks::new()
+        .with_push_events(handle_push_event)
+        .with_command_events(handle_command_event)
+        .with_interaction_events(handle_interaction_event);
+
+    let listener_environment = Arc::new(
+        SlackClientEventsListenerEnvironment::new(client)
+            .with_error_handler(socket_mode_error_handler)
+            .with_user_state(bridge_state),
+    );
+
+    SlackClientSocketModeListener::new(
+        &SlackClientSocketModeConfig::new(),
+        listener_environment,
+        callbacks,
+    )
+}
+
 /// Socket Mode error handler
 fn socket_mode_error_handler(
     err: Box<dyn std::error::Error + Send + Sync>,
@@ -285,9 +596,15 @@ pub struct SlackPlatform {
     /// Configuration for allowed users/channels
     config: gorp_core::config::SlackConfig,
     /// Connection state for health monitoring
-    connection_state: Arc<Mutex<PlatformConnectionState>>,
+    connection: ConnectionTracker,
     /// Slash command handler
     command_handler: SlackCommandHandler,
+    /// Schedules managed from `!schedule list` buttons
+    scheduler: Option<schedules::ScheduleControls>,
+    /// Cached user and channel names, also used by the Socket Mode callbacks
+    names: Arc<SlackNameResolver>,
+    /// Set by shutdown() so the Socket Mode task stops instead of reconnecting
+    shutting_down: Arc<AtomicBool>,
 }
 
 impl SlackPlatform {
@@ -296,8 +613,7 @@ impl SlackPlatform {
     /// Resolves the bot's user ID via the `auth.test` API call.
     pub async fn new(config: gorp_core::config::SlackConfig) -> Result<Self> {
         let client = Arc::new(SlackClient::new(
-            SlackClientHyperConnector::new()
-                .context("Failed to create Slack HTTP connector")?,
+            SlackClientHyperConnector::new().context("Failed to create Slack HTTP connector")?,
         ));
 
         let bot_token = SlackApiToken::new(SlackApiTokenValue(config.bot_token.clone()));
@@ -319,21 +635,29 @@ impl SlackPlatform {
         );
 
         Ok(Self {
+            names: Arc::new(SlackNameResolver::new(bot_token.clone())),
             client,
             bot_token,
             app_token,
             bot_user_id,
             config,
-            connection_state: Arc::new(Mutex::new(PlatformConnectionState::Connected)),
+            connection: ConnectionTracker::new("slack", PlatformConnectionState::Connected),
             command_handler: SlackCommandHandler::new(),
+            scheduler: None,
+            shutting_down: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Answer `!schedule list` with Pause/Resume/Delete buttons that act on `store`,
+    /// for the people `config` lets run `!schedule`
+    pub fn with_scheduler(mut self, store: SchedulerStore, config: Arc<Config>) -> Self {
+        self.scheduler = Some(schedules::ScheduleControls { store, config });
+        self
+    }
+
     /// Update the platform's connection state
     fn set_connection_state(&self, state: PlatformConnectionState) {
-        if let Ok(mut current) = self.connection_state.lock() {
-            *current = state;
-        }
+        self.connection.set(state);
     }
 }
 
@@ -343,62 +667,82 @@ impl MessagingPlatform for SlackPlatform {
         let (tx, rx) = mpsc::channel(256);
         let client = Arc::clone(&self.client);
         let app_token = self.app_token.clone();
-        let connection_state = Arc::clone(&self.connection_state);
+        let connection = self.connection.clone();
+        let shutting_down = Arc::clone(&self.shutting_down);
+        let mut backoff = BackoffState::new(BackoffConfig {
+            max_retries: self.config.max_reconnect_retries,
+            ..BackoffConfig::from(&self.config.reconnect)
+        });
 
-        // Create bridge state for callbacks
+        // Create bridge state for callbacks. Every listener gets a clone, so
+        // the stream keeps its sender across reconnects.
         let bridge_state = SlackBridgeState {
             tx: Arc::new(tx),
             bot_user_id: self.bot_user_id.clone(),
             allowed_users: self.config.allowed_users.clone(),
             allowed_channels: self.config.allowed_channels.clone(),
+            command_prefix: self.config.command_prefix.clone(),
+            bot_token: self.bot_token.clone(),
+            scheduler: self.scheduler.clone(),
+            names: Arc::clone(&self.names),
+            thread_in_channels: self.config.thread_in_channels,
+            activity: Arc::new(threads::ChannelActivity::default()),
         };
 
         // Spawn Socket Mode listener
         tokio::spawn(async move {
-            // Set up Socket Mode callbacks (fn pointers, not closures)
-            let socket_mode_callbacks = SlackSocketModeListenerCallbacks::new()
-                .with_push_events(handle_push_event)
-                .with_command_events(handle_command_event);
-
-            let listener_environment = Arc::new(
-                SlackClientEventsListenerEnvironment::new(client.clone())
-                    .with_error_handler(socket_mode_error_handler)
-                    .with_user_state(bridge_state),
-            );
-
-            let socket_mode_listener = SlackClientSocketModeListener::new(
-                &SlackClientSocketModeConfig::new(),
-                listener_environment,
-                socket_mode_callbacks,
-            );
-
-            // Update connection state
-            if let Ok(mut state) = connection_state.lock() {
-                *state = PlatformConnectionState::Connecting;
-            }
+            let mut has_connected = false;
 
-            match socket_mode_listener.listen_for(&app_token).await {
-                Ok(_) => {
-                    if let Ok(mut state) = connection_state.lock() {
-                        *state = PlatformConnectionState::Connected;
+            // A fresh listener per attempt; when it stops serving without a
+            // shutdown the socket dropped, and it is rebuilt with backoff
+            loop {
+                if shutting_down.load(Ordering::SeqCst) {
+                    break;
+                }
+                connection.set(PlatformConnectionState::Connecting);
+                let listener = socket_mode_listener(Arc::clone(&client), bridge_state.clone());
+
+                let reason = match listener.listen_for(&app_token).await {
+                    Ok(_) => {
+                        backoff.record_success();
+                        if has_connected {
+                            gorp_core::metrics::record_reconnect("slack");
+                        }
+                        has_connected = true;
+                        connection.set(PlatformConnectionState::Connected);
+                        tracing::info!(platform = "slack", "Socket Mode connected");
+
+                        // serve() blocks until the listener is shut down
+                        listener.serve().await;
+                        if shutting_down.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        "Socket Mode connection closed".to_string()
                     }
-                    tracing::info!(platform = "slack", "Socket Mode connected");
+                    Err(e) => e.to_string(),
+                };
 
-                    // serve() blocks until the listener is shut down
-                    socket_mode_listener.serve().await;
-                }
-                Err(e) => {
+                let Some(delay) = backoff.next_delay() else {
                     tracing::error!(
                         platform = "slack",
-                        error = %e,
-                        "Failed to start Socket Mode listener"
+                        reason = %reason,
+                        "Max Socket Mode reconnection retries exceeded, giving up"
                     );
-                    if let Ok(mut state) = connection_state.lock() {
-                        *state = PlatformConnectionState::Disconnected {
-                            reason: e.to_string(),
-                        };
-                    }
-                }
+                    connection.set(PlatformConnectionState::Disconnected {
+                        reason: format!("gave up reconnecting: {}", reason),
+                    });
+                    // Dropping the sender ends the event stream
+                    break;
+                };
+                tracing::warn!(
+                    platform = "slack",
+                    reason = %reason,
+                    retry_in_secs = delay.as_secs_f64(),
+                    attempt = backoff.consecutive_failures(),
+                    "Socket Mode disconnected, reconnecting"
+                );
+                connection.set(PlatformConnectionState::Disconnected { reason });
+                tokio::time::sleep(delay).await;
             }
         });
 
@@ -427,6 +771,7 @@ impl MessagingPlatform for SlackPlatform {
 
     async fn shutdown(&self) -> Result<()> {
         tracing::info!(platform = "slack", "Shutting down Slack platform");
+        self.shutting_down.store(true, Ordering::SeqCst);
         self.set_connection_state(PlatformConnectionState::Disconnected {
             reason: "shutdown".to_string(),
         });
@@ -434,10 +779,72 @@ impl MessagingPlatform for SlackPlatform {
     }
 
     fn connection_state(&self) -> PlatformConnectionState {
-        self.connection_state
-            .lock()
-            .map(|s| s.clone())
-            .unwrap_or(PlatformConnectionState::Connected)
+        self.connection.get()
+    }
+
+    fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+        Some(self.connection.subscribe())
+    }
+
+    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
+        Some(self)
+    }
+
+    fn action_buttons(&self) -> Option<&dyn ActionButtons> {
+        Some(self)
+    }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        Some(self)
+    }
+
+    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
+        Some(self)
+    }
+
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        Some(self)
+    }
+
+    fn receipts(&self) -> Option<&dyn ReceiptSender> {
+        Some(self)
+    }
+
+    fn ephemeral(&self) -> Option<&dyn EphemeralSender> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl AttachmentHandler for SlackPlatform {
+    async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
+        channel::download_file(&self.config.bot_token, source_id).await
+    }
+}
+
+#[async_trait]
+impl EphemeralSender for SlackPlatform {
+    async fn send_ephemeral(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        content: MessageContent,
+    ) -> Result<()> {
+        match content {
+            MessageContent::Plain(text) | MessageContent::Html { plain: text, .. } => {
+                channel::post_ephemeral(&self.client, &self.bot_token, channel_id, user_id, &text)
+                    .await
+            }
+            attachment => self.send(channel_id, attachment).await,
+        }
+    }
+}
+
+/// Slack has no read receipts for bots, so a 👀 reaction shows the message was seen
+#[async_trait]
+impl ReceiptSender for SlackPlatform {
+    async fn send_read_receipt(&self, channel_id: &str, message_id: &str) -> Result<()> {
+        channel::add_seen_reaction(&self.client, &self.bot_token, channel_id, message_id).await
     }
 }
 
@@ -447,11 +854,12 @@ impl ChatPlatform for SlackPlatform {
 
     async fn get_channel(&self, id: &str) -> Option<Self::Channel> {
         let is_dm = id.starts_with('D');
+        let name = self.names.channel_name(&self.client, id).await;
         Some(SlackChannel::new(
             id.into(),
             Arc::clone(&self.client),
             self.bot_token.clone(),
-            None,
+            name,
             is_dm,
         ))
     }
@@ -462,18 +870,6 @@ impl ChatPlatform for SlackPlatform {
         vec![]
     }
 
-    fn channel_creator(&self) -> Option<&dyn ChannelCreator> {
-        Some(self)
-    }
-
-    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
-        Some(self)
-    }
-
-    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
-        Some(self)
-    }
-
     fn slash_commands(&self) -> Option<&dyn SlashCommandProvider> {
         Some(&self.command_handler)
     }
@@ -500,9 +896,9 @@ impl ThreadedPlatform for SlackPlatform {
         let text = match &content {
             MessageContent::Plain(t) => t.clone(),
             MessageContent::Html { plain, .. } => plain.clone(),
-            MessageContent::Attachment { caption, filename, .. } => {
-                caption.clone().unwrap_or_else(|| filename.clone())
-            }
+            MessageContent::Attachment {
+                caption, filename, ..
+            } => caption.clone().unwrap_or_else(|| filename.clone()),
         };
 
         let req = SlackApiChatPostMessageRequest::new(
@@ -526,6 +922,30 @@ impl RichFormatter for SlackPlatform {
     }
 }
 
+#[async_trait]
+impl ActionButtons for SlackPlatform {
+    async fn send_actions(&self, channel_id: &str, text: &str, actions: &[String]) -> Result<()> {
+        let session = self.client.open_session(&self.bot_token);
+
+        let blocks: Vec<SlackBlock> =
+            serde_json::from_value(blocks::suggested_action_blocks(text, actions))
+                .context("Failed to build Slack action buttons")?;
+        let req = SlackApiChatPostMessageRequest::new(
+            channel_id.into(),
+            SlackMessageContent::new()
+                .with_text(text.into())
+                .with_blocks(blocks),
+        );
+
+        session
+            .chat_post_message(&req)
+            .await
+            .context("Failed to send Slack action buttons")?;
+
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Channel management
 // =============================================================================
@@ -547,8 +967,7 @@ impl ChannelCreator for SlackPlatform {
     async fn create_dm(&self, user_id: &str) -> Result<String> {
         let session = self.client.open_session(&self.bot_token);
 
-        let req = SlackApiConversationsOpenRequest::new()
-            .with_users(vec![user_id.into()]);
+        let req = SlackApiConversationsOpenRequest::new().with_users(vec![user_id.into()]);
         let resp = session
             .conversations_open(&req)
             .await
@@ -587,10 +1006,7 @@ impl ChannelManager for SlackPlatform {
     async fn invite(&self, channel_id: &str, user_id: &str) -> Result<()> {
         let session = self.client.open_session(&self.bot_token);
 
-        let req = SlackApiConversationsInviteRequest::new(
-            channel_id.into(),
-            vec![user_id.into()],
-        );
+        let req = SlackApiConversationsInviteRequest::new(channel_id.into(), vec![user_id.into()]);
         session
             .conversations_invite(&req)
             .await
@@ -602,8 +1018,7 @@ impl ChannelManager for SlackPlatform {
     async fn members(&self, channel_id: &str) -> Result<Vec<ChatUser>> {
         let session = self.client.open_session(&self.bot_token);
 
-        let req = SlackApiConversationsMembersRequest::new()
-            .with_channel(channel_id.into());
+        let req = SlackApiConversationsMembersRequest::new().with_channel(channel_id.into());
         let resp = session
             .conversations_members(&req)
             .await
@@ -623,6 +1038,31 @@ impl ChannelManager for SlackPlatform {
 // Utility functions
 // =============================================================================
 
+/// Files shared with a message, downloadable through SlackPlatform's attachment handler
+fn message_files(content: Option<&SlackMessageContent>) -> Vec<AttachmentInfo> {
+    content
+        .and_then(|c| c.files.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|file| {
+            let url = file
+                .url_private_download
+                .as_ref()
+                .or(file.url_private.as_ref())?;
+            Some(AttachmentInfo {
+                source_id: url.to_string(),
+                filename: file.name.clone().unwrap_or_else(|| file.id.to_string()),
+                mime_type: file
+                    .mimetype
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_string()),
+                size: None,
+            })
+        })
+        .collect()
+}
+
 /// Parse a Slack timestamp (e.g., "1700000000.000100") into Unix seconds
 fn parse_slack_ts(ts: &SlackTs) -> i64 {
     let ts_str = ts.to_string();
@@ -653,6 +1093,37 @@ mod tests {
         assert_send_sync::<SlackChannel>();
     }
 
+    #[test]
+    fn test_message_files_lists_every_shared_file() {
+        let content: SlackMessageContent = serde_json::from_value(serde_json::json!({
+            "text": "what do these show?",
+            "files": [
+                {
+                    "id": "F1",
+                    "name": "one.png",
+                    "mimetype": "image/png",
+                    "url_private_download": "https://files.slack.com/files-pri/T1-F1/download/one.png"
+                },
+                {
+                    "id": "F2",
+                    "name": "report.pdf",
+                    "mimetype": "application/pdf",
+                    "url_private": "https://files.slack.com/files-pri/T1-F2/report.pdf"
+                },
+                {"id": "F3", "name": "external.doc"}
+            ]
+        }))
+        .unwrap();
+
+        let files = message_files(Some(&content));
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].filename, "one.png");
+        assert_eq!(files[0].mime_type, "image/png");
+        assert!(files[0].source_id.ends_with("/download/one.png"));
+        assert_eq!(files[1].filename, "report.pdf");
+        assert!(message_files(None).is_empty());
+    }
+
     #[test]
     fn test_parse_slack_ts() {
         let ts: SlackTs = "1700000000.000100".into();
@@ -695,6 +1166,9 @@ mod tests {
             allowed_users: vec![],
             allowed_channels: vec![],
             thread_in_channels: true,
+            reconnect: Default::default(),
+            max_reconnect_retries: 0,
+            command_prefix: "!".to_string(),
         };
         assert!(config.allowed_users.is_empty());
     }
@@ -708,6 +1182,9 @@ mod tests {
             allowed_users: vec![],
             allowed_channels: vec![],
             thread_in_channels: true,
+            reconnect: Default::default(),
+            max_reconnect_retries: 0,
+            command_prefix: "!".to_string(),
         };
         assert!(config.allowed_channels.is_empty());
     }
@@ -720,17 +1197,101 @@ mod tests {
         assert!(commands.iter().any(|c| c.name == "/gorp"));
     }
 
-    #[test]
-    fn test_bridge_state_clone() {
+    fn bridge_state(
+        allowed_users: Vec<String>,
+        scheduler: Option<schedules::ScheduleControls>,
+    ) -> SlackBridgeState {
         let (tx, _rx) = mpsc::channel(1);
-        let state = SlackBridgeState {
+        SlackBridgeState {
             tx: Arc::new(tx),
             bot_user_id: "U123".to_string(),
-            allowed_users: vec!["U456".to_string()],
-            allowed_channels: vec![],
-        };
+            allowed_users,
+            allowed_channels: vec!["C1".to_string()],
+            command_prefix: "!".to_string(),
+            bot_token: SlackApiToken::new(SlackApiTokenValue("xoxb-test".to_string())),
+            scheduler,
+            names: Arc::new(SlackNameResolver::new(SlackApiToken::new(
+                SlackApiTokenValue("xoxb-test".to_string()),
+            ))),
+            thread_in_channels: true,
+            activity: Arc::default(),
+        }
+    }
+
+    #[test]
+    fn test_bridge_state_clone() {
+        let state = bridge_state(vec!["U456".to_string()], None);
         let cloned = state.clone();
         assert_eq!(cloned.bot_user_id, "U123");
         assert_eq!(cloned.allowed_users.len(), 1);
     }
+
+    #[test]
+    fn test_schedule_list_and_buttons_check_user_channel_and_role() {
+        use gorp_core::scheduler::{ScheduleStatus, ScheduledPrompt};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let sessions = gorp_core::session::SessionStore::new(dir.path()).unwrap();
+        let store = SchedulerStore::new(sessions.db_connection());
+        store.initialize_schema().unwrap();
+        store
+            .create_schedule(&ScheduledPrompt {
+                id: "s1".to_string(),
+                channel_name: "ops".to_string(),
+                room_id: "C1".to_string(),
+                prompt: "check the deploy".to_string(),
+                created_by: "U0ADMIN".to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                execute_at: None,
+                cron_expression: Some("0 9 * * *".to_string()),
+                last_executed_at: None,
+                next_execution_at: "2026-01-02T09:00:00Z".to_string(),
+                status: ScheduleStatus::Active,
+                error_message: None,
+                execution_count: 0,
+            })
+            .unwrap();
+        let config: Config = toml::from_str(
+            r#"
+            [webhook]
+            port = 13000
+
+            [workspace]
+            path = "./workspace"
+
+            [access]
+            admins = ["U0ADMIN"]
+
+            [roles.commands]
+            schedule = "admin"
+        "#,
+        )
+        .unwrap();
+        let bridge = bridge_state(
+            vec!["U0ADMIN".to_string(), "U0USER".to_string()],
+            Some(schedules::ScheduleControls {
+                store: store.clone(),
+                config: Arc::new(config),
+            }),
+        );
+        let pause = schedules::ScheduleAction::Pause;
+
+        // Not on the allowlist, a user without the role, and an unlisted channel
+        for (sender, channel) in [("U0EVE", "C1"), ("U0USER", "C1"), ("U0ADMIN", "C9")] {
+            assert!(bridge
+                .schedule_list_request(sender, channel, "!schedule list")
+                .is_none());
+            assert!(bridge
+                .schedule_click(sender, channel, pause, "s1")
+                .is_none());
+        }
+        let untouched = store.get_schedule("s1").unwrap().unwrap();
+        assert_eq!(untouched.status, ScheduleStatus::Active);
+
+        assert!(bridge
+            .schedule_list_request("U0ADMIN", "C1", "!schedule list")
+            .is_some());
+        let (_, notice) = bridge.schedule_click("U0ADMIN", "C1", pause, "s1").unwrap();
+        assert!(notice.starts_with("⏸️ Paused"));
+    }
 }
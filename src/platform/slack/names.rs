@@ -0,0 +1,190 @@
+// ABOUTME: Resolves Slack user and channel IDs to display names via users.info and
+// ABOUTME: conversations.info, caching answers for a while in bounded per-kind caches.
+
+use slack_morphism::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a looked-up name is trusted before asking Slack again
+const NAME_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Names remembered per kind; the oldest lookups are dropped first
+const MAX_CACHED_NAMES: usize = 1000;
+
+/// Bounded cache of lookups, oldest first out. A failed lookup is cached as
+/// `None` too, so an ID Slack won't name isn't asked about on every message.
+pub(crate) struct NameCache {
+    entries: HashMap<String, (Option<String>, Instant)>,
+    /// Insertion order, oldest first
+    order: VecDeque<String>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl NameCache {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+            ttl,
+        }
+    }
+
+    /// The cached answer for `id`: `Some(None)` when Slack had no name for it,
+    /// `None` when it has to be looked up
+    pub(crate) fn get(&self, id: &str) -> Option<Option<String>> {
+        self.entries
+            .get(id)
+            .filter(|(_, at)| at.elapsed() < self.ttl)
+            .map(|(name, _)| name.clone())
+    }
+
+    pub(crate) fn insert(&mut self, id: &str, name: Option<String>) {
+        if self
+            .entries
+            .insert(id.to_string(), (name, Instant::now()))
+            .is_some()
+        {
+            self.order.retain(|queued| queued != id);
+        }
+        self.order.push_back(id.to_string());
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+/// Shared by SlackPlatform and the Socket Mode callbacks, behind an `Arc`
+pub struct SlackNameResolver {
+    bot_token: SlackApiToken,
+    users: Mutex<NameCache>,
+    channels: Mutex<NameCache>,
+}
+
+impl SlackNameResolver {
+    pub fn new(bot_token: SlackApiToken) -> Self {
+        Self {
+            bot_token,
+            users: Mutex::new(NameCache::new(MAX_CACHED_NAMES, NAME_TTL)),
+            channels: Mutex::new(NameCache::new(MAX_CACHED_NAMES, NAME_TTL)),
+        }
+    }
+
+    /// The name a user shows as: their display name, else their real name
+    pub async fn user_name(&self, client: &SlackHyperClient, user_id: &str) -> Option<String> {
+        if let Some(cached) = lock(&self.users).get(user_id) {
+            return cached;
+        }
+        let session = client.open_session(&self.bot_token);
+        let name = match session
+            .users_info(&SlackApiUsersInfoRequest::new(user_id.into()))
+            .await
+        {
+            Ok(response) => user_display_name(&response.user),
+            Err(e) => {
+                tracing::debug!(platform = "slack", user_id, error = %e, "users.info failed");
+                None
+            }
+        };
+        lock(&self.users).insert(user_id, name.clone());
+        name
+    }
+
+    /// A channel's name without the `#`; DMs have none
+    pub async fn channel_name(
+        &self,
+        client: &SlackHyperClient,
+        channel_id: &str,
+    ) -> Option<String> {
+        if let Some(cached) = lock(&self.channels).get(channel_id) {
+            return cached;
+        }
+        let session = client.open_session(&self.bot_token);
+        let name = match session
+            .conversations_info(&SlackApiConversationsInfoRequest::new(channel_id.into()))
+            .await
+        {
+            Ok(response) => response.channel.name,
+            Err(e) => {
+                tracing::debug!(
+                    platform = "slack",
+                    channel_id,
+                    error = %e,
+                    "conversations.info failed"
+                );
+                None
+            }
+        };
+        lock(&self.channels).insert(channel_id, name.clone());
+        name
+    }
+}
+
+fn lock(cache: &Mutex<NameCache>) -> std::sync::MutexGuard<'_, NameCache> {
+    cache.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Profile display name, then real name, then the legacy username; blanks are skipped
+fn user_display_name(user: &SlackUser) -> Option<String> {
+    let profile = user.profile.as_ref();
+    [
+        profile.and_then(|p| p.display_name.clone()),
+        profile.and_then(|p| p.real_name.clone()),
+        user.name.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|name| !name.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_drops_oldest_lookups_past_capacity() {
+        let mut cache = NameCache::new(2, NAME_TTL);
+        cache.insert("U1", Some("ada".to_string()));
+        cache.insert("U2", None);
+        cache.insert("U1", Some("ada l".to_string()));
+        cache.insert("U3", Some("grace".to_string()));
+
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get("U2"), None);
+        assert_eq!(cache.get("U1"), Some(Some("ada l".to_string())));
+        assert_eq!(cache.get("U3"), Some(Some("grace".to_string())));
+    }
+
+    #[test]
+    fn test_cached_names_expire() {
+        let mut cache = NameCache::new(10, Duration::from_millis(20));
+        cache.insert("C1", Some("ops".to_string()));
+        assert_eq!(cache.get("C1"), Some(Some("ops".to_string())));
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get("C1"), None);
+    }
+
+    #[test]
+    fn test_user_display_name_prefers_profile_display_name() {
+        let user: SlackUser = serde_json::from_value(serde_json::json!({
+            "id": "U1",
+            "name": "ada.l",
+            "profile": {"display_name": "", "real_name": "Ada Lovelace"}
+        }))
+        .unwrap();
+        assert_eq!(user_display_name(&user).as_deref(), Some("Ada Lovelace"));
+
+        let user: SlackUser = serde_json::from_value(serde_json::json!({
+            "id": "U2",
+            "name": "grace",
+            "profile": {"display_name": "Grace", "real_name": "Grace Hopper"}
+        }))
+        .unwrap();
+        assert_eq!(user_display_name(&user).as_deref(), Some("Grace"));
+    }
+}
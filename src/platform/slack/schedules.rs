@@ -0,0 +1,328 @@
+// ABOUTME: Interactive `!schedule list` for Slack: one Block Kit entry per schedule with
+// ABOUTME: Pause/Resume/Delete buttons, and the SchedulerStore changes those buttons make.
+
+use anyhow::Result;
+use gorp_core::config::Config;
+use gorp_core::scheduler::{ScheduleStatus, ScheduledPrompt, SchedulerStore};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::message_handler::helpers::truncate_str;
+
+/// Prefix of the action_id on schedule buttons; the rest names the action
+pub const SCHEDULE_ACTION_PREFIX: &str = "gorp_schedule_";
+
+/// Schedules shown in one message: two blocks each, within Slack's 50-block limit
+const MAX_LISTED: usize = 20;
+
+/// The store the schedule list and its buttons act on, with the config whose
+/// `[roles]` decide who may use them
+#[derive(Clone)]
+pub struct ScheduleControls {
+    pub store: SchedulerStore,
+    pub config: Arc<Config>,
+}
+
+impl ScheduleControls {
+    /// Whether `sender` has the role `!schedule` needs on Slack
+    pub fn may_manage(&self, sender: &str) -> bool {
+        self.config.can_run("slack", sender, "schedule")
+    }
+}
+
+/// What a schedule button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleAction {
+    Pause,
+    Resume,
+    Delete,
+}
+
+impl ScheduleAction {
+    fn name(self) -> &'static str {
+        match self {
+            ScheduleAction::Pause => "pause",
+            ScheduleAction::Resume => "resume",
+            ScheduleAction::Delete => "delete",
+        }
+    }
+
+    fn action_id(self) -> String {
+        format!("{}{}", SCHEDULE_ACTION_PREFIX, self.name())
+    }
+
+    /// The action a clicked button's action_id stands for, if it is a schedule button
+    pub fn from_action_id(action_id: &str) -> Option<Self> {
+        match action_id.strip_prefix(SCHEDULE_ACTION_PREFIX)? {
+            "pause" => Some(ScheduleAction::Pause),
+            "resume" => Some(ScheduleAction::Resume),
+            "delete" => Some(ScheduleAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `body` is `!schedule list`, which Slack answers with buttons
+pub fn is_list_command(body: &str, command_prefix: &str) -> bool {
+    let Some(command) = body.trim().strip_prefix(command_prefix) else {
+        return false;
+    };
+    let words: Vec<String> = command.split_whitespace().map(str::to_lowercase).collect();
+    words == ["schedule", "list"]
+}
+
+/// Apply a clicked button to the schedule it belongs to, using the same store
+/// calls as the text commands. Schedules of other channels are left alone.
+/// Returns a line telling the user what happened.
+pub fn apply(
+    store: &SchedulerStore,
+    channel_id: &str,
+    action: ScheduleAction,
+    schedule_id: &str,
+) -> Result<String> {
+    let Some(schedule) = store
+        .get_schedule(schedule_id)?
+        .filter(|s| s.room_id == channel_id)
+    else {
+        return Ok("⚠️ That schedule no longer exists.".to_string());
+    };
+    let prompt = truncate_str(&schedule.prompt, 50);
+    let note = match action {
+        ScheduleAction::Pause if store.pause_schedule(&schedule.id)? => {
+            format!("⏸️ Paused: {}", prompt)
+        }
+        ScheduleAction::Resume if store.resume_schedule(&schedule.id)? => {
+            format!("▶️ Resumed: {}", prompt)
+        }
+        ScheduleAction::Delete if store.delete_schedule(&schedule.id)? => {
+            format!("🗑️ Deleted: {}", prompt)
+        }
+        _ => format!(
+            "⚠️ Can't {} a schedule that is {}: {}",
+            action.name(),
+            schedule.status,
+            prompt
+        ),
+    };
+    Ok(note)
+}
+
+/// The schedule list as Block Kit: a header, the outcome of the last click if
+/// there was one, then each schedule with the buttons that apply to it
+pub fn list_blocks(schedules: &[ScheduledPrompt], notice: Option<&str>) -> Value {
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": {"type": "mrkdwn", "text": "📅 *Scheduled Prompts*"}
+    })];
+    if let Some(notice) = notice {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{"type": "mrkdwn", "text": notice}]
+        }));
+    }
+    if schedules.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": "No scheduled prompts. Create one with `!schedule <time> <prompt>`."
+            }
+        }));
+        return Value::Array(blocks);
+    }
+
+    for schedule in schedules.iter().take(MAX_LISTED) {
+        blocks.push(json!({
+            "type": "section",
+            "text": {"type": "mrkdwn", "text": describe(schedule)}
+        }));
+        blocks.push(json!({
+            "type": "actions",
+            "elements": buttons(schedule)
+        }));
+    }
+    if schedules.len() > MAX_LISTED {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!("…and {} more", schedules.len() - MAX_LISTED)
+            }]
+        }));
+    }
+    Value::Array(blocks)
+}
+
+/// Fallback text for notifications and clients that can't show blocks
+pub fn list_text(schedules: &[ScheduledPrompt]) -> String {
+    format!("📅 {} scheduled prompt(s)", schedules.len())
+}
+
+fn describe(schedule: &ScheduledPrompt) -> String {
+    let status_icon = match schedule.status {
+        ScheduleStatus::Active => "🟢",
+        ScheduleStatus::Paused => "⏸️",
+        ScheduleStatus::Completed => "✅",
+        ScheduleStatus::Failed => "❌",
+        ScheduleStatus::Executing => "⏳",
+        ScheduleStatus::Cancelled => "🚫",
+    };
+    let schedule_type = if schedule.cron_expression.is_some() {
+        "🔄 recurring"
+    } else {
+        "⏰ one-time"
+    };
+    let next: String = schedule.next_execution_at.chars().take(16).collect();
+    format!(
+        "{} {} [{}]\n📝 {}\n⏱️ Next: {}",
+        status_icon,
+        schedule_type,
+        schedule.status,
+        truncate_str(&schedule.prompt, 50),
+        next
+    )
+}
+
+fn buttons(schedule: &ScheduledPrompt) -> Vec<Value> {
+    let toggle = match schedule.status {
+        ScheduleStatus::Active => Some((ScheduleAction::Pause, "Pause")),
+        ScheduleStatus::Paused => Some((ScheduleAction::Resume, "Resume")),
+        _ => None,
+    };
+    toggle
+        .into_iter()
+        .chain([(ScheduleAction::Delete, "Delete")])
+        .map(|(action, label)| {
+            let mut button = json!({
+                "type": "button",
+                "action_id": action.action_id(),
+                "text": {"type": "plain_text", "text": label},
+                "value": schedule.id
+            });
+            if action == ScheduleAction::Delete {
+                button["style"] = json!("danger");
+            }
+            button
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gorp_core::session::SessionStore;
+    use tempfile::TempDir;
+
+    fn schedule(id: &str, room_id: &str, status: ScheduleStatus) -> ScheduledPrompt {
+        ScheduledPrompt {
+            id: id.to_string(),
+            channel_name: "ops".to_string(),
+            room_id: room_id.to_string(),
+            prompt: "check the deploy".to_string(),
+            created_by: "U1".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            execute_at: None,
+            cron_expression: Some("0 9 * * *".to_string()),
+            last_executed_at: None,
+            next_execution_at: "2026-01-02T09:00:00Z".to_string(),
+            status,
+            error_message: None,
+            execution_count: 0,
+        }
+    }
+
+    fn store() -> (SchedulerStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let sessions = SessionStore::new(dir.path()).unwrap();
+        let store = SchedulerStore::new(sessions.db_connection());
+        store.initialize_schema().unwrap();
+        (store, dir)
+    }
+
+    #[test]
+    fn test_list_command_detection() {
+        assert!(is_list_command("!schedule list", "!"));
+        assert!(is_list_command("  !Schedule   LIST ", "!"));
+        assert!(!is_list_command("!schedule list extra", "!"));
+        assert!(!is_list_command("schedule list", "!"));
+        assert!(!is_list_command("!schedule pause abc", "!"));
+    }
+
+    #[test]
+    fn test_buttons_match_each_schedule_status() {
+        let blocks = list_blocks(
+            &[
+                schedule("s1", "C1", ScheduleStatus::Active),
+                schedule("s2", "C1", ScheduleStatus::Paused),
+                schedule("s3", "C1", ScheduleStatus::Completed),
+            ],
+            Some("⏸️ Paused: x"),
+        );
+        let blocks = blocks.as_array().unwrap();
+        assert_eq!(blocks[1]["type"], "context");
+
+        let action_ids = |i: usize| -> Vec<String> {
+            blocks[i]["elements"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|b| b["action_id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(
+            action_ids(3),
+            ["gorp_schedule_pause", "gorp_schedule_delete"]
+        );
+        assert_eq!(
+            action_ids(5),
+            ["gorp_schedule_resume", "gorp_schedule_delete"]
+        );
+        assert_eq!(action_ids(7), ["gorp_schedule_delete"]);
+        assert_eq!(blocks[3]["elements"][0]["value"], "s1");
+    }
+
+    #[test]
+    fn test_apply_changes_only_this_channels_schedules() {
+        let (store, _dir) = store();
+        store
+            .create_schedule(&schedule("s1", "C1", ScheduleStatus::Active))
+            .unwrap();
+        store
+            .create_schedule(&schedule("s2", "C2", ScheduleStatus::Active))
+            .unwrap();
+
+        let note = apply(&store, "C1", ScheduleAction::Pause, "s1").unwrap();
+        assert!(note.starts_with("⏸️ Paused"));
+        let paused = store.get_schedule("s1").unwrap().unwrap();
+        assert_eq!(paused.status, ScheduleStatus::Paused);
+
+        // A stale button for a state that has moved on changes nothing
+        let note = apply(&store, "C1", ScheduleAction::Pause, "s1").unwrap();
+        assert!(note.contains("Can't pause a schedule that is paused"));
+
+        let note = apply(&store, "C1", ScheduleAction::Resume, "s1").unwrap();
+        assert!(note.starts_with("▶️ Resumed"));
+
+        let note = apply(&store, "C1", ScheduleAction::Delete, "s2").unwrap();
+        assert!(note.contains("no longer exists"));
+        assert!(store.get_schedule("s2").unwrap().is_some());
+
+        apply(&store, "C1", ScheduleAction::Delete, "s1").unwrap();
+        assert!(store.get_schedule("s1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_action_ids_round_trip() {
+        for action in [
+            ScheduleAction::Pause,
+            ScheduleAction::Resume,
+            ScheduleAction::Delete,
+        ] {
+            assert_eq!(
+                ScheduleAction::from_action_id(&action.action_id()),
+                Some(action)
+            );
+        }
+        assert_eq!(ScheduleAction::from_action_id("gorp_suggest_0"), None);
+    }
+}
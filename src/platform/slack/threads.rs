@@ -0,0 +1,110 @@
+// ABOUTME: Decides which Slack thread a reply goes in, following `thread_in_channels`:
+// ABOUTME: the thread a message was asked in, or a new one when the channel is busy.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How recently someone else must have posted for a channel to count as busy
+const BUSY_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Channels remembered at once; the quietest are forgotten first
+const MAX_TRACKED_CHANNELS: usize = 500;
+
+/// Who last posted at top level in each channel, and when
+#[derive(Default)]
+pub(crate) struct ChannelActivity {
+    last_post: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl ChannelActivity {
+    /// Record a top-level post by `sender` and report whether the channel is busy:
+    /// someone else posted there within the last BUSY_WINDOW
+    pub(crate) fn record(&self, channel_id: &str, sender: &str) -> bool {
+        let mut last_post = self.last_post.lock().unwrap_or_else(|e| e.into_inner());
+        let busy = last_post
+            .get(channel_id)
+            .is_some_and(|(other, at)| other != sender && at.elapsed() < BUSY_WINDOW);
+        last_post.insert(channel_id.to_string(), (sender.to_string(), Instant::now()));
+        if last_post.len() > MAX_TRACKED_CHANNELS {
+            last_post.retain(|_, (_, at)| at.elapsed() < BUSY_WINDOW);
+        }
+        busy
+    }
+}
+
+/// Where a message is
+pub(crate) struct Origin<'a> {
+    pub ts: &'a str,
+    pub thread_ts: Option<&'a str>,
+    pub is_direct: bool,
+}
+
+/// The thread_ts replies to a message go under, or None for the channel top level.
+/// DMs are answered inline; with `thread_in_channels` off, so is everything else.
+pub(crate) fn reply_thread(
+    origin: &Origin<'_>,
+    thread_in_channels: bool,
+    busy: bool,
+) -> Option<String> {
+    if origin.is_direct || !thread_in_channels {
+        return None;
+    }
+    match origin.thread_ts {
+        Some(thread_ts) => Some(thread_ts.to_string()),
+        None if busy => Some(origin.ts.to_string()),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(thread_ts: Option<&'static str>, is_direct: bool) -> Origin<'static> {
+        Origin {
+            ts: "1700000002.000200",
+            thread_ts,
+            is_direct,
+        }
+    }
+
+    #[test]
+    fn test_threaded_messages_are_answered_in_their_thread() {
+        let asked = origin(Some("1700000001.000100"), false);
+        assert_eq!(
+            reply_thread(&asked, true, false).as_deref(),
+            Some("1700000001.000100")
+        );
+        assert_eq!(reply_thread(&asked, false, false), None);
+    }
+
+    #[test]
+    fn test_busy_channels_get_a_thread_rooted_at_the_message() {
+        let top_level = origin(None, false);
+        assert_eq!(
+            reply_thread(&top_level, true, true).as_deref(),
+            Some("1700000002.000200")
+        );
+        assert_eq!(reply_thread(&top_level, true, false), None);
+        assert_eq!(reply_thread(&top_level, false, true), None);
+    }
+
+    #[test]
+    fn test_dms_stay_unthreaded() {
+        assert_eq!(reply_thread(&origin(None, true), true, true), None);
+        assert_eq!(
+            reply_thread(&origin(Some("1700000001.000100"), true), true, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_channel_is_busy_when_someone_else_just_posted() {
+        let activity = ChannelActivity::default();
+        assert!(!activity.record("C1", "U1"));
+        assert!(!activity.record("C1", "U1"));
+        assert!(activity.record("C1", "U2"));
+        assert!(!activity.record("C2", "U1"));
+    }
+}
@@ -1,21 +1,54 @@
 // ABOUTME: Telegram channel implementation wrapping a chat for the ChatChannel trait
-// ABOUTME: Handles message sending with 4096-char chunking and typing indicators
+// ABOUTME: Handles message sending with 4096-char chunking, HTML formatting and typing indicators
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use gorp_core::traits::{AttachmentHandler, ChatChannel, MessageContent, TypingIndicator};
 use teloxide::net::Download;
 use teloxide::prelude::*;
-use teloxide::types::{ChatAction, FileId, InputFile, ParseMode};
+use teloxide::types::{ChatAction, FileId, InputFile, ParseMode, ThreadId};
+use teloxide::RequestError;
+
+use super::format::markdown_to_telegram_html;
+use super::topic_channel_id;
 
 /// Maximum message length for Telegram Bot API
 const MAX_MESSAGE_LENGTH: usize = 4096;
 
-/// A Telegram chat wrapped as a ChatChannel
+/// Largest image sendPhoto accepts; bigger ones go out as documents
+const MAX_PHOTO_BYTES: usize = 10 * 1024 * 1024;
+
+/// How often to re-send the typing action; Telegram's `sendChatAction` lasts five seconds
+pub(crate) const TYPING_REFRESH: std::time::Duration = std::time::Duration::from_secs(4);
+
+/// How an outbound attachment is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Upload {
+    /// sendPhoto, shown inline in the chat
+    Photo,
+    /// sendDocument, shown as a file to download
+    Document,
+}
+
+/// Images Telegram can show as photos go through sendPhoto; GIFs, SVGs and
+/// oversized images would be mangled or rejected, so they stay documents
+fn upload_kind(mime_type: &str, size: usize) -> Upload {
+    let photo = matches!(mime_type, "image/jpeg" | "image/png" | "image/webp");
+    if photo && size <= MAX_PHOTO_BYTES {
+        Upload::Photo
+    } else {
+        Upload::Document
+    }
+}
+
+/// A Telegram chat, or one forum topic of it, wrapped as a ChatChannel
 #[derive(Debug, Clone)]
 pub struct TelegramChannel {
     chat_id: ChatId,
-    /// String representation of chat_id for the id() accessor
+    /// Forum topic everything is sent into; None for the chat itself
+    thread_id: Option<ThreadId>,
+    /// Channel ID for the id() accessor: the chat ID, or `<chat_id>:<thread_id>` for a topic
     chat_id_str: String,
     bot: Bot,
     /// Cached chat title or user display name
@@ -29,11 +62,19 @@ impl TelegramChannel {
         Self {
             chat_id_str: chat_id.0.to_string(),
             chat_id,
+            thread_id: None,
             bot,
             chat_name,
             is_private,
         }
     }
+
+    /// Send into forum topic `thread_id` rather than the chat's General topic
+    pub fn in_topic(mut self, thread_id: Option<ThreadId>) -> Self {
+        self.chat_id_str = topic_channel_id(self.chat_id, thread_id);
+        self.thread_id = thread_id;
+        self
+    }
 }
 
 #[async_trait]
@@ -42,6 +83,10 @@ impl ChatChannel for TelegramChannel {
         &self.chat_id_str
     }
 
+    fn platform_id(&self) -> &str {
+        "telegram"
+    }
+
     fn name(&self) -> Option<String> {
         self.chat_name.clone()
     }
@@ -53,10 +98,11 @@ impl ChatChannel for TelegramChannel {
     async fn send(&self, content: MessageContent) -> Result<()> {
         match content {
             MessageContent::Plain(text) => {
-                self.send_chunked(&text, None).await?;
+                self.send_chunked(&text).await?;
             }
-            MessageContent::Html { html, .. } => {
-                self.send_chunked(&html, Some(ParseMode::Html)).await?;
+            MessageContent::Html { plain, .. } => {
+                // The provided HTML uses tags Telegram rejects; rebuild it from the markdown
+                self.send_formatted(&plain).await?;
             }
             MessageContent::Attachment {
                 filename,
@@ -64,15 +110,22 @@ impl ChatChannel for TelegramChannel {
                 mime_type,
                 caption,
             } => {
+                let upload = upload_kind(&mime_type, data.len());
                 let input_file = InputFile::memory(data).file_name(filename);
-                if mime_type.starts_with("image/") {
+                if upload == Upload::Photo {
                     let mut req = self.bot.send_photo(self.chat_id, input_file);
+                    if let Some(thread_id) = self.thread_id {
+                        req = req.message_thread_id(thread_id);
+                    }
                     if let Some(cap) = caption {
                         req = req.caption(cap);
                     }
                     req.await.context("Failed to send photo")?;
                 } else {
                     let mut req = self.bot.send_document(self.chat_id, input_file);
+                    if let Some(thread_id) = self.thread_id {
+                        req = req.message_thread_id(thread_id);
+                    }
                     if let Some(cap) = caption {
                         req = req.caption(cap);
                     }
@@ -103,23 +156,40 @@ impl ChatChannel for TelegramChannel {
 
 impl TelegramChannel {
     /// Send a text message, splitting into chunks if it exceeds Telegram's limit
-    async fn send_chunked(&self, text: &str, parse_mode: Option<ParseMode>) -> Result<()> {
-        if text.len() <= MAX_MESSAGE_LENGTH {
-            let mut req = self.bot.send_message(self.chat_id, text);
-            if let Some(pm) = parse_mode {
-                req = req.parse_mode(pm);
+    async fn send_chunked(&self, text: &str) -> Result<()> {
+        // Split at line boundaries when possible
+        for chunk in chunk_text(text, MAX_MESSAGE_LENGTH) {
+            let mut req = self.bot.send_message(self.chat_id, chunk);
+            if let Some(thread_id) = self.thread_id {
+                req = req.message_thread_id(thread_id);
             }
             req.await.context("Failed to send message")?;
-            return Ok(());
         }
+        Ok(())
+    }
 
-        // Split at line boundaries when possible
-        for chunk in chunk_text(text, MAX_MESSAGE_LENGTH) {
-            let mut req = self.bot.send_message(self.chat_id, chunk);
-            if let Some(pm) = parse_mode {
-                req = req.parse_mode(pm);
+    /// Send markdown as Telegram HTML, chunked on the markdown so code blocks
+    /// survive the split. A chunk Telegram refuses to parse (unbalanced or
+    /// too many entities) is resent as its plain markdown.
+    async fn send_formatted(&self, markdown: &str) -> Result<()> {
+        for chunk in crate::utils::chunk_message(markdown, MAX_MESSAGE_LENGTH) {
+            let html = markdown_to_telegram_html(&chunk);
+            let mut req = self
+                .bot
+                .send_message(self.chat_id, html)
+                .parse_mode(ParseMode::Html);
+            if let Some(thread_id) = self.thread_id {
+                req = req.message_thread_id(thread_id);
+            }
+            let sent = req.await;
+            match sent {
+                Ok(_) => {}
+                Err(RequestError::Api(e)) => {
+                    tracing::warn!(error = %e, "Telegram rejected HTML message, sending as plain text");
+                    self.send_chunked(&chunk).await?;
+                }
+                Err(e) => return Err(e).context("Failed to send message"),
             }
-            req.await.context("Failed to send message chunk")?;
         }
         Ok(())
     }
@@ -129,45 +199,73 @@ impl TelegramChannel {
 impl TypingIndicator for TelegramChannel {
     async fn set_typing(&self, typing: bool) -> Result<()> {
         if typing {
-            self.bot
-                .send_chat_action(self.chat_id, ChatAction::Typing)
-                .await
-                .context("Failed to send typing action")?;
+            let mut req = self.bot.send_chat_action(self.chat_id, ChatAction::Typing);
+            if let Some(thread_id) = self.thread_id {
+                req = req.message_thread_id(thread_id);
+            }
+            req.await.context("Failed to send typing action")?;
         }
         // Telegram typing indicators auto-expire; no explicit "stop typing" API
         Ok(())
     }
+
+    fn refresh_interval(&self) -> std::time::Duration {
+        TYPING_REFRESH
+    }
 }
 
 #[async_trait]
 impl AttachmentHandler for TelegramChannel {
     async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
-        let file = self
-            .bot
-            .get_file(FileId(source_id.to_string()))
-            .await
-            .context("Failed to get file info from Telegram")?;
+        download_file(&self.bot, source_id).await
+    }
+}
 
-        let mut data = Vec::new();
-        self.bot
-            .download_file(&file.path, &mut data)
-            .await
-            .context("Failed to download file from Telegram")?;
-
-        // Telegram doesn't always provide filename or mime_type in the file object,
-        // so we use sensible defaults
-        let filename = file
-            .path
-            .split('/')
-            .last()
-            .unwrap_or("attachment")
-            .to_string();
-        let mime_type = mime_guess::from_path(&filename)
-            .first_or_octet_stream()
-            .to_string();
-
-        Ok((filename, data, mime_type))
+/// Largest file getFile lets a bot download
+const MAX_DOWNLOAD_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Fetch a file by its Telegram file ID: getFile for its path, then the file
+/// itself from the bot-token download URL, read into memory up to the Bot API's cap
+pub(crate) async fn download_file(bot: &Bot, file_id: &str) -> Result<(String, Vec<u8>, String)> {
+    let file = bot
+        .get_file(FileId(file_id.to_string()))
+        .await
+        .context("Failed to get file info from Telegram")?;
+    check_download_size(file.size as u64)?;
+
+    // Sizes in the file object are advisory; count the bytes as they arrive
+    let mut data = Vec::with_capacity(file.size as usize);
+    let mut stream = bot.download_file_stream(&file.path);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Failed to download file from Telegram")?;
+        check_download_size((data.len() + chunk.len()) as u64)?;
+        data.extend_from_slice(&chunk);
+    }
+
+    // Telegram doesn't always provide filename or mime_type in the file object,
+    // so we use sensible defaults
+    let filename = file
+        .path
+        .rsplit('/')
+        .next()
+        .unwrap_or("attachment")
+        .to_string();
+    let mime_type = mime_guess::from_path(&filename)
+        .first_or_octet_stream()
+        .to_string();
+
+    Ok((filename, data, mime_type))
+}
+
+/// Refuse files the Bot API won't hand out, before or while downloading
+fn check_download_size(size: u64) -> Result<()> {
+    if size > MAX_DOWNLOAD_BYTES {
+        anyhow::bail!(
+            "file is over the {} MB Telegram lets bots download",
+            MAX_DOWNLOAD_BYTES / (1024 * 1024)
+        );
     }
+    Ok(())
 }
 
 /// Split text into chunks at line boundaries, falling back to character boundaries
@@ -206,6 +304,26 @@ fn chunk_text(text: &str, max_len: usize) -> Vec<&str> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_image_attachment_is_sent_as_photo() {
+        assert_eq!(upload_kind("image/png", 50_000), Upload::Photo);
+        assert_eq!(upload_kind("image/jpeg", 50_000), Upload::Photo);
+        assert_eq!(upload_kind("application/pdf", 50_000), Upload::Document);
+        assert_eq!(upload_kind("image/svg+xml", 500), Upload::Document);
+        assert_eq!(
+            upload_kind("image/png", MAX_PHOTO_BYTES + 1),
+            Upload::Document
+        );
+    }
+
+    #[test]
+    fn test_download_size_cap() {
+        assert!(check_download_size(0).is_ok());
+        assert!(check_download_size(MAX_DOWNLOAD_BYTES).is_ok());
+        let err = check_download_size(MAX_DOWNLOAD_BYTES + 1).unwrap_err();
+        assert!(err.to_string().contains("20 MB"));
+    }
+
     #[test]
     fn test_chunk_text_short() {
         let chunks = chunk_text("hello", 4096);
@@ -248,6 +366,15 @@ mod tests {
         assert_eq!(chunks, vec![""]);
     }
 
+    #[test]
+    fn test_topic_channel_id() {
+        let bot = Bot::new("fake_token");
+        let chat = TelegramChannel::new(ChatId(-100123), bot, None, false);
+        assert_eq!(chat.id(), "-100123");
+        let topic = chat.in_topic(Some(ThreadId(teloxide::types::MessageId(7))));
+        assert_eq!(topic.id(), "-100123:7");
+    }
+
     #[test]
     fn test_telegram_channel_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
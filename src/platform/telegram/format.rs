@@ -0,0 +1,179 @@
+// ABOUTME: Markdown to Telegram HTML conversion for outgoing messages
+// ABOUTME: Emits only the tags Telegram's parse_mode=HTML accepts, escaping everything else
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+/// Escape text for Telegram HTML, including `"` so the result is safe inside attributes
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Convert markdown to the HTML subset Telegram renders.
+///
+/// Telegram has no paragraphs, headings or lists, so headings become bold
+/// lines and list items get bullets or numbers. Fenced code keeps its
+/// language as `<pre><code class="language-x">`. Raw HTML in the markdown is
+/// shown as text.
+pub fn markdown_to_telegram_html(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len() + markdown.len() / 4);
+    // Next number for each open list; None for bullet lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Heading { .. } | Tag::Strong => out.push_str("<b>"),
+                Tag::Emphasis => out.push_str("<i>"),
+                Tag::Strikethrough => out.push_str("<s>"),
+                Tag::BlockQuote(_) => out.push_str("<blockquote>"),
+                Tag::CodeBlock(kind) => {
+                    let lang = match &kind {
+                        CodeBlockKind::Fenced(info) => info.split_whitespace().next(),
+                        CodeBlockKind::Indented => None,
+                    };
+                    match lang {
+                        Some(lang) => out.push_str(&format!(
+                            "<pre><code class=\"language-{}\">",
+                            escape_html(lang)
+                        )),
+                        None => out.push_str("<pre><code>"),
+                    }
+                }
+                Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                    out.push_str(&format!("<a href=\"{}\">", escape_html(&dest_url)));
+                }
+                Tag::List(start) => {
+                    // A list nested in an item starts on its own line
+                    if !lists.is_empty() && !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    lists.push(start);
+                }
+                Tag::Item => {
+                    let depth = lists.len().saturating_sub(1);
+                    out.push_str(&"  ".repeat(depth));
+                    match lists.last_mut() {
+                        Some(Some(n)) => {
+                            out.push_str(&format!("{}. ", n));
+                            *n += 1;
+                        }
+                        _ => out.push_str("• "),
+                    }
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Heading(_) => out.push_str("</b>\n\n"),
+                TagEnd::Strong => out.push_str("</b>"),
+                TagEnd::Emphasis => out.push_str("</i>"),
+                TagEnd::Strikethrough => out.push_str("</s>"),
+                TagEnd::BlockQuote(_) => {
+                    trim_trailing_newlines(&mut out);
+                    out.push_str("</blockquote>\n\n");
+                }
+                TagEnd::CodeBlock => {
+                    trim_trailing_newlines(&mut out);
+                    out.push_str("</code></pre>\n\n");
+                }
+                TagEnd::Link | TagEnd::Image => out.push_str("</a>"),
+                TagEnd::Paragraph => {
+                    // Paragraphs inside list items stay on the item's line
+                    out.push_str(if lists.is_empty() { "\n\n" } else { "\n" });
+                }
+                TagEnd::Item => {
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                }
+                TagEnd::List(_) => {
+                    lists.pop();
+                    if lists.is_empty() {
+                        out.push('\n');
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) => out.push_str(&escape_html(&text)),
+            Event::Code(code) => {
+                out.push_str("<code>");
+                out.push_str(&escape_html(&code));
+                out.push_str("</code>");
+            }
+            Event::Html(html) | Event::InlineHtml(html) => out.push_str(&escape_html(&html)),
+            Event::SoftBreak | Event::HardBreak => out.push('\n'),
+            Event::Rule => out.push_str("──────────\n\n"),
+            _ => {}
+        }
+    }
+
+    trim_trailing_newlines(&mut out);
+    out
+}
+
+fn trim_trailing_newlines(out: &mut String) {
+    while out.ends_with('\n') {
+        out.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escapes_special_characters() {
+        assert_eq!(
+            markdown_to_telegram_html("if a < b && b > c"),
+            "if a &lt; b &amp;&amp; b &gt; c"
+        );
+        assert_eq!(
+            markdown_to_telegram_html("<script>alert(1)</script>"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn test_inline_formatting() {
+        assert_eq!(
+            markdown_to_telegram_html("**bold**, *italic*, ~~gone~~ and `a<b`"),
+            "<b>bold</b>, <i>italic</i>, <s>gone</s> and <code>a&lt;b</code>"
+        );
+        assert_eq!(
+            markdown_to_telegram_html("[docs](https://example.com/?a=1&b=2)"),
+            "<a href=\"https://example.com/?a=1&amp;b=2\">docs</a>"
+        );
+    }
+
+    #[test]
+    fn test_code_fence_keeps_language() {
+        let html = markdown_to_telegram_html("Run:\n\n```rust\nlet x = a < b;\n```\n");
+        assert_eq!(
+            html,
+            "Run:\n\n<pre><code class=\"language-rust\">let x = a &lt; b;</code></pre>"
+        );
+        let html = markdown_to_telegram_html("```\nplain\n```");
+        assert_eq!(html, "<pre><code>plain</code></pre>");
+    }
+
+    #[test]
+    fn test_headings_and_lists() {
+        let html = markdown_to_telegram_html("# Title\n\n- one\n- two\n\n1. first\n2. second");
+        assert_eq!(html, "<b>Title</b>\n\n• one\n• two\n\n1. first\n2. second");
+    }
+
+    #[test]
+    fn test_nested_list_is_indented() {
+        let html = markdown_to_telegram_html("- outer\n  - inner\n- next");
+        assert_eq!(html, "• outer\n  • inner\n• next");
+    }
+}
@@ -1,27 +1,76 @@
 // ABOUTME: Telegram platform implementation for gorp chat abstraction
-// ABOUTME: Implements Tier 2 ChatPlatform with long polling, typing indicators, and file handling
+// ABOUTME: Implements Tier 2 ChatPlatform with long polling or webhook updates, typing indicators, and file handling
 
 pub mod channel;
+pub mod format;
 
 pub use channel::TelegramChannel;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use gorp_core::config::TelegramMode;
+use gorp_core::connection::{ConnectionEvent, ConnectionTracker};
+use gorp_core::reconnect::{BackoffConfig, BackoffState};
 use gorp_core::traits::{
-    AttachmentInfo, ChannelManager, ChatChannel, ChatPlatform, ChatUser, EventStream,
-    IncomingMessage, MessageContent, MessagingPlatform, PlatformConnectionState,
+    ActionButtons, AttachmentHandler, AttachmentInfo, ChannelManager, ChannelTyping, ChatChannel,
+    ChatPlatform, ChatUser, ConfirmationProvider, EventStream, IncomingMessage, MessageContent,
+    MessagingPlatform, PlatformConnectionState, TypingIndicator, WebhookIngress,
 };
-use std::sync::{Arc, Mutex};
+use sha2::{Digest, Sha256};
 use teloxide::prelude::*;
-use teloxide::types::{ChatKind, MediaKind, MessageKind, UpdateKind};
-use tokio::sync::mpsc;
+use teloxide::types::{
+    CallbackQuery, ChatKind, InlineKeyboardButton, InlineKeyboardMarkup, KeyboardButton,
+    KeyboardMarkup, MediaKind, MessageId, MessageKind, ThreadId, Update, UpdateKind,
+};
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 
+/// Header Telegram puts the webhook's secret token in
+pub const SECRET_TOKEN_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
+
+/// Start of the callback data on Confirm / Cancel buttons: `gorp:<y|n>:<token>:<requester>`
+const CONFIRMATION_DATA_PREFIX: &str = "gorp:";
+
+/// Separates the chat from the forum topic in a topic's channel ID
+const TOPIC_SEPARATOR: char = ':';
+
+/// Channel ID for a chat, or for one topic of a forum supergroup. Each topic is
+/// its own gorp channel, keyed `<chat_id>:<thread_id>`.
+pub fn topic_channel_id(chat_id: ChatId, thread_id: Option<ThreadId>) -> String {
+    match thread_id {
+        Some(thread_id) => format!("{}{}{}", chat_id.0, TOPIC_SEPARATOR, thread_id.0 .0),
+        None => chat_id.0.to_string(),
+    }
+}
+
+/// The chat, and forum topic if any, that a channel ID from [`topic_channel_id`] points at
+pub fn parse_channel_id(channel_id: &str) -> Result<(ChatId, Option<ThreadId>)> {
+    let (chat, thread) = match channel_id.split_once(TOPIC_SEPARATOR) {
+        Some((chat, thread)) => (chat, Some(thread)),
+        None => (channel_id, None),
+    };
+    let chat_id = chat
+        .parse::<i64>()
+        .with_context(|| format!("Invalid Telegram chat ID: {}", channel_id))?;
+    let thread_id = thread
+        .map(|thread| thread.parse::<i32>())
+        .transpose()
+        .with_context(|| format!("Invalid Telegram topic in channel ID: {}", channel_id))?
+        .map(|id| ThreadId(MessageId(id)));
+    Ok((ChatId(chat_id), thread_id))
+}
+
+/// The forum topic a message was sent in. Replies in ordinary supergroups
+/// carry a thread ID as well, so only topic messages count.
+fn message_topic(message: &Message) -> Option<ThreadId> {
+    message.thread_id.filter(|_| message.is_topic_message)
+}
+
 // =============================================================================
 // TelegramPlatform - Implements MessagingPlatform + ChatPlatform (Tier 2)
 // =============================================================================
 
-/// Telegram platform implementation using teloxide with long polling
+/// Telegram platform implementation using teloxide with long polling or a webhook
 pub struct TelegramPlatform {
     bot: Bot,
     /// Bot's numeric user ID as a string
@@ -29,18 +78,26 @@ pub struct TelegramPlatform {
     /// Configuration for allowed users/chats
     config: gorp_core::config::TelegramConfig,
     /// Connection state for health monitoring
-    connection_state: Arc<Mutex<PlatformConnectionState>>,
+    connection: ConnectionTracker,
+    /// Secret in the webhook path and Telegram's secret-token header
+    webhook_secret: String,
+    /// In webhook mode, where pushed updates go: the current event stream
+    webhook_tx: std::sync::Mutex<Option<mpsc::Sender<IncomingMessage>>>,
 }
 
 impl TelegramPlatform {
     /// Create a new TelegramPlatform from config.
     ///
-    /// Resolves the bot's user ID via the `getMe` API call.
+    /// Resolves the bot's user ID via the `getMe` API call, then registers the
+    /// webhook in webhook mode or removes any left over from it when polling.
     pub async fn new(config: gorp_core::config::TelegramConfig) -> Result<Self> {
         let bot = Bot::new(&config.bot_token);
 
         // Resolve bot user ID via getMe
-        let me = bot.get_me().await.context("Failed to call Telegram getMe")?;
+        let me = bot
+            .get_me()
+            .await
+            .context("Failed to call Telegram getMe")?;
         let bot_user_id = me.id.0.to_string();
 
         tracing::info!(
@@ -49,19 +106,50 @@ impl TelegramPlatform {
             "Telegram bot authenticated"
         );
 
+        let webhook_secret = config
+            .webhook_secret
+            .clone()
+            .unwrap_or_else(|| derive_webhook_secret(&config.bot_token));
+        match config.mode {
+            TelegramMode::Webhook => {
+                let base = config
+                    .webhook_url
+                    .as_deref()
+                    .context("Telegram webhook mode needs webhook_url")?;
+                let url = webhook_endpoint(base, &webhook_secret)?;
+                bot.set_webhook(url)
+                    .secret_token(webhook_secret.clone())
+                    .await
+                    .context("Failed to register Telegram webhook")?;
+                tracing::info!(platform = "telegram", base_url = %base, "Telegram webhook registered");
+            }
+            TelegramMode::Polling => {
+                // getUpdates is refused while a webhook is set
+                bot.delete_webhook()
+                    .await
+                    .context("Failed to remove Telegram webhook")?;
+            }
+        }
+
         Ok(Self {
             bot,
             bot_user_id,
             config,
-            connection_state: Arc::new(Mutex::new(PlatformConnectionState::Connected)),
+            connection: ConnectionTracker::new("telegram", PlatformConnectionState::Connected),
+            webhook_secret,
+            webhook_tx: std::sync::Mutex::new(None),
         })
     }
 
+    /// The chat or forum topic `channel_id` names, ready to send to
+    fn channel(&self, channel_id: &str) -> Result<TelegramChannel> {
+        let (chat_id, thread_id) = parse_channel_id(channel_id)?;
+        Ok(TelegramChannel::new(chat_id, self.bot.clone(), None, false).in_topic(thread_id))
+    }
+
     /// Update the platform's connection state
     pub fn set_connection_state(&self, state: PlatformConnectionState) {
-        if let Ok(mut current) = self.connection_state.lock() {
-            *current = state;
-        }
+        self.connection.set(state);
     }
 
     /// Check if a user is allowed to interact with the bot
@@ -87,45 +175,51 @@ impl TelegramPlatform {
 impl MessagingPlatform for TelegramPlatform {
     async fn event_stream(&self) -> Result<EventStream> {
         let (tx, rx) = mpsc::channel(256);
+        if self.config.mode == TelegramMode::Webhook {
+            // Updates arrive through push(); nothing to poll
+            *self
+                .webhook_tx
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Webhook sender mutex poisoned: {}", e))? = Some(tx);
+            return Ok(Box::pin(ReceiverStream::new(rx)));
+        }
+
         let bot = self.bot.clone();
         let bot_user_id = self.bot_user_id.clone();
         let allowed_users = self.config.allowed_users.clone();
         let allowed_chats = self.config.allowed_chats.clone();
-        let connection_state = Arc::clone(&self.connection_state);
+        let command_prefix = self.config.command_prefix.clone();
+        let connection = self.connection.clone();
+        let mut backoff = BackoffState::new(BackoffConfig::from(&self.config.reconnect));
 
         // Spawn long polling task
         tokio::spawn(async move {
             let mut offset: i32 = 0;
 
             loop {
-                let updates = match bot
-                    .get_updates()
-                    .offset(offset)
-                    .timeout(30)
-                    .await
-                {
+                let updates = match bot.get_updates().offset(offset).timeout(30).await {
                     Ok(updates) => {
                         // Connected successfully
-                        if let Ok(mut state) = connection_state.lock() {
-                            if !matches!(*state, PlatformConnectionState::Connected) {
-                                *state = PlatformConnectionState::Connected;
-                                tracing::info!(platform = "telegram", "Reconnected");
-                            }
+                        backoff.record_success();
+                        if connection.set(PlatformConnectionState::Connected) {
+                            tracing::info!(platform = "telegram", "Reconnected");
                         }
                         updates
                     }
                     Err(e) => {
+                        // Unlimited retries: next_delay always has a delay
+                        let delay = backoff.next_delay().unwrap_or_default();
                         tracing::warn!(
                             platform = "telegram",
                             error = %e,
-                            "Long polling error, retrying in 5s"
+                            retry_in_secs = delay.as_secs_f64(),
+                            attempt = backoff.consecutive_failures(),
+                            "Long polling error, retrying"
                         );
-                        if let Ok(mut state) = connection_state.lock() {
-                            *state = PlatformConnectionState::Disconnected {
-                                reason: e.to_string(),
-                            };
-                        }
-                        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        connection.set(PlatformConnectionState::Disconnected {
+                            reason: e.to_string(),
+                        });
+                        tokio::time::sleep(delay).await;
                         continue;
                     }
                 };
@@ -133,120 +227,29 @@ impl MessagingPlatform for TelegramPlatform {
                 for update in &updates {
                     offset = update.id.as_offset();
 
-                    // Extract message from update kind
-                    let message = match &update.kind {
-                        UpdateKind::Message(msg) => msg,
-                        _ => continue,
-                    };
-
-                    // Extract text content from the message
-                    let body = match &message.kind {
-                        MessageKind::Common(common) => match &common.media_kind {
-                            MediaKind::Text(text) => text.text.clone(),
-                            _ => continue,
-                        },
+                    // Messages, and presses of our Confirm / Cancel buttons
+                    let msg = match &update.kind {
+                        UpdateKind::Message(message) => {
+                            incoming_message(message, &bot_user_id, &allowed_users, &allowed_chats)
+                        }
+                        UpdateKind::CallbackQuery(query) => {
+                            confirmation_press(
+                                &bot,
+                                query,
+                                &allowed_users,
+                                &allowed_chats,
+                                &command_prefix,
+                            )
+                            .await
+                        }
                         _ => continue,
                     };
-
-                    let Some(from) = message.from.as_ref() else {
-                        continue;
-                    };
-
-                    // Skip messages from the bot itself
-                    if from.id.0.to_string() == bot_user_id {
-                        continue;
-                    }
-
-                    // Check user allowlist
-                    if !allowed_users.is_empty()
-                        && !allowed_users.contains(&(from.id.0 as i64))
-                    {
-                        tracing::debug!(
-                            platform = "telegram",
-                            user_id = from.id.0,
-                            "Skipping message from non-allowed user"
-                        );
-                        continue;
-                    }
-
-                    // Check chat allowlist
-                    if !allowed_chats.is_empty()
-                        && !allowed_chats.contains(&message.chat.id.0)
-                    {
-                        tracing::debug!(
-                            platform = "telegram",
-                            chat_id = message.chat.id.0,
-                            "Skipping message from non-allowed chat"
-                        );
+                    let Some(msg) = msg else {
                         continue;
-                    }
-
-                    let is_private = matches!(message.chat.kind, ChatKind::Private(_));
-
-                    let display_name = {
-                        let mut parts: Vec<String> = Vec::new();
-                        parts.push(from.first_name.clone());
-                        if let Some(ref last) = from.last_name {
-                            parts.push(last.clone());
-                        }
-                        Some(parts.join(" "))
-                    };
-
-                    // Check for attachment
-                    let attachment = match &message.kind {
-                        MessageKind::Common(common) => match &common.media_kind {
-                            MediaKind::Document(doc) => Some(AttachmentInfo {
-                                source_id: doc.document.file.id.to_string(),
-                                filename: doc
-                                    .document
-                                    .file_name
-                                    .clone()
-                                    .unwrap_or_else(|| "document".to_string()),
-                                mime_type: doc
-                                    .document
-                                    .mime_type
-                                    .as_ref()
-                                    .map(|m| m.to_string())
-                                    .unwrap_or_else(|| {
-                                        "application/octet-stream".to_string()
-                                    }),
-                                size: Some(doc.document.file.size as u64),
-                            }),
-                            MediaKind::Photo(photo) => {
-                                // Use the largest photo size
-                                photo.photo.last().map(|p| AttachmentInfo {
-                                    source_id: p.file.id.to_string(),
-                                    filename: "photo.jpg".to_string(),
-                                    mime_type: "image/jpeg".to_string(),
-                                    size: Some(p.file.size as u64),
-                                })
-                            }
-                            _ => None,
-                        },
-                        _ => None,
-                    };
-
-                    let msg = IncomingMessage {
-                        platform_id: "telegram".to_string(),
-                        channel_id: message.chat.id.0.to_string(),
-                        thread_id: None,
-                        sender: ChatUser {
-                            id: from.id.0.to_string(),
-                            display_name,
-                        },
-                        body,
-                        is_direct: is_private,
-                        formatted: false,
-                        attachment,
-                        event_id: message.id.0.to_string(),
-                        timestamp: message.date.timestamp(),
                     };
 
                     if tx.send(msg).await.is_err() {
-                        tracing::warn!(
-                            platform = "telegram",
-                            "Event stream receiver dropped"
-                        );
+                        tracing::warn!(platform = "telegram", "Event stream receiver dropped");
                         return;
                     }
                 }
@@ -258,13 +261,7 @@ impl MessagingPlatform for TelegramPlatform {
     }
 
     async fn send(&self, channel_id: &str, content: MessageContent) -> Result<()> {
-        let chat_id: ChatId = ChatId(
-            channel_id
-                .parse::<i64>()
-                .context("Invalid Telegram chat ID")?,
-        );
-        let channel = TelegramChannel::new(chat_id, self.bot.clone(), None, false);
-        channel.send(content).await
+        self.channel(channel_id)?.send(content).await
     }
 
     fn bot_user_id(&self) -> &str {
@@ -284,10 +281,399 @@ impl MessagingPlatform for TelegramPlatform {
     }
 
     fn connection_state(&self) -> PlatformConnectionState {
-        self.connection_state
+        self.connection.get()
+    }
+
+    fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+        Some(self.connection.subscribe())
+    }
+
+    fn action_buttons(&self) -> Option<&dyn ActionButtons> {
+        Some(self)
+    }
+
+    fn typing(&self) -> Option<&dyn ChannelTyping> {
+        Some(self)
+    }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        Some(self)
+    }
+
+    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
+        Some(self)
+    }
+
+    fn webhook_ingress(&self) -> Option<&dyn WebhookIngress> {
+        (self.config.mode == TelegramMode::Webhook).then_some(self as &dyn WebhookIngress)
+    }
+
+    fn confirmations(&self) -> Option<&dyn ConfirmationProvider> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl WebhookIngress for TelegramPlatform {
+    fn verify(&self, path_secret: &str, token: Option<&str>) -> bool {
+        secrets_match(path_secret, &self.webhook_secret)
+            && token.is_some_and(|token| secrets_match(token, &self.webhook_secret))
+    }
+
+    async fn push(&self, body: &[u8]) -> Result<()> {
+        let update: Update = serde_json::from_slice(body).context("Invalid Telegram update")?;
+        let msg = match &update.kind {
+            UpdateKind::Message(message) => incoming_message(
+                message,
+                &self.bot_user_id,
+                &self.config.allowed_users,
+                &self.config.allowed_chats,
+            ),
+            UpdateKind::CallbackQuery(query) => {
+                confirmation_press(
+                    &self.bot,
+                    query,
+                    &self.config.allowed_users,
+                    &self.config.allowed_chats,
+                    &self.config.command_prefix,
+                )
+                .await
+            }
+            _ => None,
+        };
+        let Some(msg) = msg else {
+            return Ok(());
+        };
+
+        let tx = self
+            .webhook_tx
             .lock()
-            .map(|s| s.clone())
-            .unwrap_or(PlatformConnectionState::Connected)
+            .map_err(|e| anyhow::anyhow!("Webhook sender mutex poisoned: {}", e))?
+            .clone()
+            .context("Telegram event stream not started")?;
+        tx.send(msg)
+            .await
+            .map_err(|_| anyhow::anyhow!("Telegram event stream closed"))
+    }
+}
+
+/// Webhook secret used when none is configured: stable across restarts, and
+/// made only of characters Telegram allows in a secret token
+fn derive_webhook_secret(bot_token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"gorp-telegram-webhook:");
+    hasher.update(bot_token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// The URL Telegram pushes updates to: `<base>/telegram/webhook/<secret>`
+fn webhook_endpoint(base: &str, secret: &str) -> Result<reqwest::Url> {
+    let url = format!("{}/telegram/webhook/{}", base.trim_end_matches('/'), secret);
+    reqwest::Url::parse(&url).with_context(|| format!("Invalid Telegram webhook_url: {}", base))
+}
+
+/// Compare secrets without leaking where they differ
+fn secrets_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// The message to hand on for `message`, or None for the bot's own messages,
+/// senders or chats outside the allowlists, and kinds the bot doesn't act on
+fn incoming_message(
+    message: &Message,
+    bot_user_id: &str,
+    allowed_users: &[i64],
+    allowed_chats: &[i64],
+) -> Option<IncomingMessage> {
+    // Text, or a document/photo with its caption
+    let (body, attachments) = message_content(message)?;
+    let from = message.from.as_ref()?;
+
+    // Skip messages from the bot itself
+    if from.id.0.to_string() == bot_user_id {
+        return None;
+    }
+
+    // Check user allowlist
+    if !allowed_users.is_empty() && !allowed_users.contains(&(from.id.0 as i64)) {
+        tracing::debug!(
+            platform = "telegram",
+            user_id = from.id.0,
+            "Skipping message from non-allowed user"
+        );
+        return None;
+    }
+
+    // Check chat allowlist
+    if !allowed_chats.is_empty() && !allowed_chats.contains(&message.chat.id.0) {
+        tracing::debug!(
+            platform = "telegram",
+            chat_id = message.chat.id.0,
+            "Skipping message from non-allowed chat"
+        );
+        return None;
+    }
+
+    let is_private = matches!(message.chat.kind, ChatKind::Private(_));
+    let topic = message_topic(message);
+
+    let display_name = {
+        let mut parts: Vec<String> = Vec::new();
+        parts.push(from.first_name.clone());
+        if let Some(ref last) = from.last_name {
+            parts.push(last.clone());
+        }
+        Some(parts.join(" "))
+    };
+
+    Some(IncomingMessage {
+        platform_id: "telegram".to_string(),
+        channel_id: topic_channel_id(message.chat.id, topic),
+        thread_id: topic.map(|thread_id| thread_id.0 .0.to_string()),
+        sender: ChatUser {
+            id: from.id.0.to_string(),
+            display_name,
+        },
+        body,
+        is_direct: is_private,
+        formatted: false,
+        attachments,
+        event_id: message.id.0.to_string(),
+        edits_event_id: None,
+        timestamp: message.date.timestamp(),
+    })
+}
+
+#[async_trait]
+impl AttachmentHandler for TelegramPlatform {
+    async fn download(&self, source_id: &str) -> Result<(String, Vec<u8>, String)> {
+        channel::download_file(&self.bot, source_id).await
+    }
+}
+
+/// Body and attachments of a message the bot should act on: plain text, or a
+/// document, photo or voice note with its (possibly empty) caption. Other kinds are skipped.
+fn message_content(message: &Message) -> Option<(String, Vec<AttachmentInfo>)> {
+    let MessageKind::Common(common) = &message.kind else {
+        return None;
+    };
+    match &common.media_kind {
+        MediaKind::Text(text) => Some((text.text.clone(), Vec::new())),
+        MediaKind::Document(doc) => Some((
+            doc.caption.clone().unwrap_or_default(),
+            vec![AttachmentInfo {
+                source_id: doc.document.file.id.to_string(),
+                filename: doc
+                    .document
+                    .file_name
+                    .clone()
+                    .unwrap_or_else(|| "document".to_string()),
+                mime_type: doc
+                    .document
+                    .mime_type
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_string()),
+                size: Some(doc.document.file.size as u64),
+            }],
+        )),
+        // Use the largest photo size
+        MediaKind::Photo(photo) => Some((
+            photo.caption.clone().unwrap_or_default(),
+            photo
+                .photo
+                .last()
+                .map(|p| AttachmentInfo {
+                    source_id: p.file.id.to_string(),
+                    filename: "photo.jpg".to_string(),
+                    mime_type: "image/jpeg".to_string(),
+                    size: Some(p.file.size as u64),
+                })
+                .into_iter()
+                .collect(),
+        )),
+        MediaKind::Voice(voice) => Some((
+            voice.caption.clone().unwrap_or_default(),
+            vec![AttachmentInfo {
+                source_id: voice.voice.file.id.to_string(),
+                filename: "voice.ogg".to_string(),
+                mime_type: voice
+                    .voice
+                    .mime_type
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "audio/ogg".to_string()),
+                size: Some(voice.voice.file.size as u64),
+            }],
+        )),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl ActionButtons for TelegramPlatform {
+    async fn send_actions(&self, channel_id: &str, text: &str, actions: &[String]) -> Result<()> {
+        let (chat_id, thread_id) = parse_channel_id(channel_id)?;
+        // A reply keyboard sends the tapped label as an ordinary message,
+        // so suggestions come back through the normal update loop
+        let keyboard = KeyboardMarkup::new(
+            actions
+                .iter()
+                .map(|action| vec![KeyboardButton::new(action.clone())]),
+        )
+        .resize_keyboard()
+        .one_time_keyboard();
+
+        let mut req = self.bot.send_message(chat_id, text).reply_markup(keyboard);
+        if let Some(thread_id) = thread_id {
+            req = req.message_thread_id(thread_id);
+        }
+        req.await.context("Failed to send action buttons")?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ConfirmationProvider for TelegramPlatform {
+    async fn ask_confirmation(
+        &self,
+        channel_id: &str,
+        requester_id: &str,
+        text: &str,
+        token: &str,
+    ) -> Result<()> {
+        let (chat_id, thread_id) = parse_channel_id(channel_id)?;
+        // Inline buttons answer with a callback query rather than a message, so
+        // the requester rides along in the data and other pressers can be turned away
+        let keyboard = InlineKeyboardMarkup::new([[
+            InlineKeyboardButton::callback(
+                "✅ Confirm",
+                confirmation_data(true, token, requester_id),
+            ),
+            InlineKeyboardButton::callback(
+                "✖️ Cancel",
+                confirmation_data(false, token, requester_id),
+            ),
+        ]]);
+
+        let mut req = self.bot.send_message(chat_id, text).reply_markup(keyboard);
+        if let Some(thread_id) = thread_id {
+            req = req.message_thread_id(thread_id);
+        }
+        req.await.context("Failed to send confirmation buttons")?;
+        Ok(())
+    }
+}
+
+/// Callback data for a Confirm or Cancel button
+fn confirmation_data(confirmed: bool, token: &str, requester_id: &str) -> String {
+    let answer = if confirmed { "y" } else { "n" };
+    format!(
+        "{}{}:{}:{}",
+        CONFIRMATION_DATA_PREFIX, answer, token, requester_id
+    )
+}
+
+/// Read back callback data made by [`confirmation_data`]: (confirmed, token, requester)
+fn parse_confirmation_data(data: &str) -> Option<(bool, &str, &str)> {
+    let rest = data.strip_prefix(CONFIRMATION_DATA_PREFIX)?;
+    let mut parts = rest.splitn(3, ':');
+    let confirmed = match parts.next()? {
+        "y" => true,
+        "n" => false,
+        _ => return None,
+    };
+    let token = parts.next().filter(|t| !t.is_empty())?;
+    let requester = parts.next().filter(|r| !r.is_empty())?;
+    Some((confirmed, token, requester))
+}
+
+/// The answer command for a press of a Confirm / Cancel button, or None for
+/// presses to ignore. Every press is acknowledged so the button stops spinning;
+/// only the requester, while allowed to use the bot, gets their press through,
+/// and then the buttons are removed.
+async fn confirmation_press(
+    bot: &Bot,
+    query: &CallbackQuery,
+    allowed_users: &[i64],
+    allowed_chats: &[i64],
+    command_prefix: &str,
+) -> Option<IncomingMessage> {
+    let (confirmed, token, requester) = parse_confirmation_data(query.data.as_deref()?)?;
+    let message = query.message.as_ref()?;
+    let chat = message.chat();
+    let topic = message.regular_message().and_then(message_topic);
+    let presser = query.from.id.0;
+
+    let allowed = (allowed_users.is_empty() || allowed_users.contains(&(presser as i64)))
+        && (allowed_chats.is_empty() || allowed_chats.contains(&chat.id.0));
+    if !allowed || presser.to_string() != requester {
+        tracing::debug!(
+            platform = "telegram",
+            user_id = presser,
+            "Ignoring confirmation press from someone other than the requester"
+        );
+        if let Err(e) = bot
+            .answer_callback_query(query.id.clone())
+            .text("Only the person who ran the command can answer this.")
+            .await
+        {
+            tracing::warn!(platform = "telegram", error = %e, "Failed to answer callback query");
+        }
+        return None;
+    }
+
+    if let Err(e) = bot.answer_callback_query(query.id.clone()).await {
+        tracing::warn!(platform = "telegram", error = %e, "Failed to answer callback query");
+    }
+    // One answer per question: take the buttons away
+    if let Err(e) = bot.edit_message_reply_markup(chat.id, message.id()).await {
+        tracing::debug!(platform = "telegram", error = %e, "Failed to remove confirmation buttons");
+    }
+
+    let display_name = match &query.from.last_name {
+        Some(last) => format!("{} {}", query.from.first_name, last),
+        None => query.from.first_name.clone(),
+    };
+    Some(IncomingMessage {
+        platform_id: "telegram".to_string(),
+        channel_id: topic_channel_id(chat.id, topic),
+        thread_id: topic.map(|thread_id| thread_id.0 .0.to_string()),
+        sender: ChatUser {
+            id: presser.to_string(),
+            display_name: Some(display_name),
+        },
+        body: format!(
+            "{}{}",
+            command_prefix,
+            gorp_core::confirmations::answer_command(confirmed, token)
+        ),
+        is_direct: matches!(chat.kind, ChatKind::Private(_)),
+        formatted: false,
+        attachments: vec![],
+        event_id: format!("callback:{}", query.id),
+        edits_event_id: None,
+        timestamp: chrono::Utc::now().timestamp(),
+    })
+}
+
+#[async_trait]
+impl ChannelTyping for TelegramPlatform {
+    async fn set_typing(&self, channel_id: &str, typing: bool) -> Result<()> {
+        if typing {
+            self.channel(channel_id)?.set_typing(true).await?;
+        }
+        // Telegram typing indicators auto-expire; no explicit "stop typing" API
+        Ok(())
+    }
+
+    fn refresh_interval(&self) -> std::time::Duration {
+        channel::TYPING_REFRESH
     }
 }
 
@@ -296,15 +682,9 @@ impl ChatPlatform for TelegramPlatform {
     type Channel = TelegramChannel;
 
     async fn get_channel(&self, id: &str) -> Option<Self::Channel> {
-        let chat_id: i64 = id.parse().ok()?;
         // Telegram doesn't have a "get chat" that always works without prior interaction,
         // so we construct the channel directly with minimal info
-        Some(TelegramChannel::new(
-            ChatId(chat_id),
-            self.bot.clone(),
-            None,
-            false,
-        ))
+        self.channel(id).ok()
     }
 
     async fn joined_channels(&self) -> Vec<Self::Channel> {
@@ -312,15 +692,6 @@ impl ChatPlatform for TelegramPlatform {
         // Channels are discovered through incoming messages.
         vec![]
     }
-
-    fn channel_creator(&self) -> Option<&dyn gorp_core::traits::ChannelCreator> {
-        // Telegram bots cannot create groups
-        None
-    }
-
-    fn channel_manager(&self) -> Option<&dyn ChannelManager> {
-        Some(self)
-    }
 }
 
 #[async_trait]
@@ -331,11 +702,13 @@ impl ChannelManager for TelegramPlatform {
     }
 
     async fn leave(&self, channel_id: &str) -> Result<()> {
-        let chat_id = ChatId(
-            channel_id
-                .parse::<i64>()
-                .context("Invalid Telegram chat ID")?,
-        );
+        let (chat_id, thread_id) = parse_channel_id(channel_id)?;
+        if thread_id.is_some() {
+            // Leaving would take the bot out of every other topic too
+            anyhow::bail!(
+                "A forum topic can't be left on its own; remove the bot from the group instead"
+            );
+        }
         self.bot
             .leave_chat(chat_id)
             .await
@@ -349,11 +722,8 @@ impl ChannelManager for TelegramPlatform {
     }
 
     async fn members(&self, channel_id: &str) -> Result<Vec<ChatUser>> {
-        let chat_id = ChatId(
-            channel_id
-                .parse::<i64>()
-                .context("Invalid Telegram chat ID")?,
-        );
+        // A topic has the members of its chat
+        let (chat_id, _) = parse_channel_id(channel_id)?;
         let count = self
             .bot
             .get_chat_member_count(chat_id)
@@ -399,12 +769,8 @@ mod tests {
     #[test]
     fn test_telegram_channel_name() {
         let bot = Bot::new("fake_token");
-        let channel = TelegramChannel::new(
-            ChatId(12345),
-            bot,
-            Some("Test Chat".to_string()),
-            false,
-        );
+        let channel =
+            TelegramChannel::new(ChatId(12345), bot, Some("Test Chat".to_string()), false);
         assert_eq!(channel.name(), Some("Test Chat".to_string()));
     }
 
@@ -439,6 +805,38 @@ mod tests {
         assert!(channel.typing_indicator().is_some());
     }
 
+    #[test]
+    fn test_confirmation_data_round_trips_within_telegram_limit() {
+        let token = "0123456789abcdef";
+        let data = confirmation_data(true, token, "1234567890123");
+        assert!(data.len() <= 64, "callback data too long: {}", data);
+        assert_eq!(
+            parse_confirmation_data(&data),
+            Some((true, token, "1234567890123"))
+        );
+        assert_eq!(
+            parse_confirmation_data(&confirmation_data(false, token, "7")),
+            Some((false, token, "7"))
+        );
+        assert_eq!(parse_confirmation_data("gorp:x:abc:7"), None);
+        assert_eq!(parse_confirmation_data("gorp:y::7"), None);
+        assert_eq!(parse_confirmation_data("other:y:abc:7"), None);
+    }
+
+    #[test]
+    fn test_topic_channel_id_round_trips() {
+        let topic = Some(ThreadId(MessageId(42)));
+        let id = topic_channel_id(ChatId(-1001234567890), topic);
+        assert_eq!(id, "-1001234567890:42");
+        assert_eq!(
+            parse_channel_id(&id).unwrap(),
+            (ChatId(-1001234567890), topic)
+        );
+        assert_eq!(parse_channel_id("12345").unwrap(), (ChatId(12345), None));
+        assert!(parse_channel_id("-100123:topic").is_err());
+        assert!(parse_channel_id("!room:matrix.org").is_err());
+    }
+
     #[test]
     fn test_telegram_channel_attachment_handler_present() {
         let bot = Bot::new("fake_token");
@@ -453,6 +851,11 @@ mod tests {
             bot_token: "fake".to_string(),
             allowed_users: vec![],
             allowed_chats: vec![],
+            reconnect: Default::default(),
+            command_prefix: "!".to_string(),
+            mode: Default::default(),
+            webhook_url: None,
+            webhook_secret: None,
         };
         // We can't construct TelegramPlatform without a real bot, so test the logic directly
         assert!(config.allowed_users.is_empty());
@@ -464,7 +867,141 @@ mod tests {
             bot_token: "fake".to_string(),
             allowed_users: vec![],
             allowed_chats: vec![],
+            reconnect: Default::default(),
+            command_prefix: "!".to_string(),
+            mode: Default::default(),
+            webhook_url: None,
+            webhook_secret: None,
         };
         assert!(config.allowed_chats.is_empty());
     }
+
+    /// A platform in webhook mode, built without calling the Bot API
+    fn webhook_platform(secret: &str) -> TelegramPlatform {
+        TelegramPlatform {
+            bot: Bot::new("fake_token"),
+            bot_user_id: "999".to_string(),
+            config: gorp_core::config::TelegramConfig {
+                bot_token: "fake_token".to_string(),
+                allowed_users: vec![42],
+                allowed_chats: vec![],
+                reconnect: Default::default(),
+                command_prefix: "!".to_string(),
+                mode: TelegramMode::Webhook,
+                webhook_url: Some("https://bot.example.com".to_string()),
+                webhook_secret: Some(secret.to_string()),
+            },
+            connection: ConnectionTracker::new("telegram", PlatformConnectionState::Connected),
+            webhook_secret: secret.to_string(),
+            webhook_tx: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn text_update(update_id: i32, user_id: i64, text: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "update_id": update_id,
+            "message": {
+                "message_id": update_id,
+                "date": 1700000000,
+                "chat": {"id": user_id, "type": "private", "first_name": "Ada"},
+                "from": {"id": user_id, "is_bot": false, "first_name": "Ada"},
+                "text": text
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_derived_webhook_secret_is_stable_and_accepted_by_telegram() {
+        let secret = derive_webhook_secret("123456:ABC-DEF");
+        assert_eq!(secret, derive_webhook_secret("123456:ABC-DEF"));
+        assert_ne!(secret, derive_webhook_secret("654321:ABC-DEF"));
+        assert!(secret.len() <= 256);
+        assert!(secret.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn test_webhook_endpoint() {
+        let url = webhook_endpoint("https://bot.example.com/", "s3cret").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://bot.example.com/telegram/webhook/s3cret"
+        );
+        assert!(webhook_endpoint("not a url", "s3cret").is_err());
+    }
+
+    #[test]
+    fn test_webhook_verify_needs_path_and_header_secret() {
+        let platform = webhook_platform("s3cret");
+        assert!(platform.webhook_ingress().is_some());
+        assert!(platform.verify("s3cret", Some("s3cret")));
+        assert!(!platform.verify("s3cret", None));
+        assert!(!platform.verify("s3cret", Some("wrong")));
+        assert!(!platform.verify("wrong", Some("s3cret")));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_push_reaches_event_stream() {
+        use futures_util::StreamExt;
+
+        let platform = webhook_platform("s3cret");
+        let mut stream = platform.event_stream().await.unwrap();
+
+        // Outside the allowlist: accepted but dropped
+        platform.push(&text_update(1, 7, "ignored")).await.unwrap();
+        platform.push(&text_update(2, 42, "hello")).await.unwrap();
+        assert!(platform.push(b"not json").await.is_err());
+
+        let msg = stream.next().await.unwrap();
+        assert_eq!(msg.body, "hello");
+        assert_eq!(msg.channel_id, "42");
+        assert!(msg.is_direct);
+    }
+
+    #[test]
+    fn test_message_content_passes_captioned_photo_through() {
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "message_id": 7,
+            "date": 1700000000,
+            "chat": {"id": 42, "type": "private", "first_name": "Ada"},
+            "from": {"id": 42, "is_bot": false, "first_name": "Ada"},
+            "photo": [
+                {"file_id": "small", "file_unique_id": "s", "width": 90, "height": 90, "file_size": 1000},
+                {"file_id": "large", "file_unique_id": "l", "width": 1280, "height": 1280, "file_size": 90000}
+            ],
+            "caption": "what's this?"
+        }))
+        .unwrap();
+
+        let (body, attachments) = message_content(&message).unwrap();
+        assert_eq!(body, "what's this?");
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].source_id, "large");
+        assert_eq!(attachments[0].mime_type, "image/jpeg");
+    }
+
+    #[test]
+    fn test_message_content_maps_voice_note_to_audio_attachment() {
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "message_id": 8,
+            "date": 1700000000,
+            "chat": {"id": 42, "type": "private", "first_name": "Ada"},
+            "from": {"id": 42, "is_bot": false, "first_name": "Ada"},
+            "voice": {
+                "file_id": "voice-1",
+                "file_unique_id": "v",
+                "duration": 4,
+                "mime_type": "audio/ogg",
+                "file_size": 12000
+            }
+        }))
+        .unwrap();
+
+        let (body, attachments) = message_content(&message).unwrap();
+        assert_eq!(body, "");
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].source_id, "voice-1");
+        assert_eq!(attachments[0].mime_type, "audio/ogg");
+        assert_eq!(attachments[0].size, Some(12000));
+    }
 }
@@ -0,0 +1,751 @@
+// ABOUTME: Zulip platform implementation for gorp chat abstraction
+// ABOUTME: Long-polls the events API; streams are channels and topics are threads
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use gorp_core::config::ZulipConfig;
+use gorp_core::connection::{ConnectionEvent, ConnectionTracker};
+use gorp_core::reconnect::{BackoffConfig, BackoffState};
+use gorp_core::traits::{
+    AttachmentHandler, ChatChannel, ChatPlatform, ChatUser, EventStream, IncomingMessage,
+    MessageContent, MessagingPlatform, PlatformConnectionState, ThreadedPlatform,
+};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::utils::chunk_message;
+
+/// Zulip rejects message bodies longer than this many characters
+const MAX_MESSAGE_CHARS: usize = 10_000;
+
+/// How long one events request may stay open. The server answers with a
+/// heartbeat well inside this when nothing happens.
+const POLL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Timeout for every other API call
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+// =============================================================================
+// ZulipClient - thin wrapper over the REST API
+// =============================================================================
+
+/// An error the Zulip API reported in its `{"result": "error"}` body
+#[derive(Debug, Clone)]
+struct ApiError {
+    code: String,
+    msg: String,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Zulip API error {}: {}", self.code, self.msg)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Whether `error` means the event queue expired and must be registered again
+fn is_bad_queue(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<ApiError>()
+        .is_some_and(|e| e.code == "BAD_EVENT_QUEUE_ID")
+}
+
+#[derive(Clone)]
+struct ZulipClient {
+    http: reqwest::Client,
+    /// API root, e.g. "https://gorp.zulipchat.com/api/v1"
+    api_url: String,
+    email: String,
+    api_key: String,
+}
+
+impl ZulipClient {
+    fn new(config: &ZulipConfig) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: format!("{}/api/v1", config.site.trim_end_matches('/')),
+            email: config.bot_email.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.http
+            .request(method, format!("{}/{}", self.api_url, path))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .timeout(REQUEST_TIMEOUT)
+    }
+
+    /// Send a request and decode its body, turning `"result": "error"` into an ApiError
+    async fn call<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let body: serde_json::Value = request
+            .send()
+            .await
+            .context("Zulip request failed")?
+            .json()
+            .await
+            .context("Zulip returned a malformed response")?;
+        if body.get("result").and_then(|r| r.as_str()) != Some("success") {
+            let field = |key: &str| {
+                body.get(key)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            return Err(ApiError {
+                code: field("code"),
+                msg: field("msg"),
+            }
+            .into());
+        }
+        serde_json::from_value(body).context("Unexpected Zulip response")
+    }
+
+    async fn me(&self) -> Result<Me> {
+        self.call(self.request(reqwest::Method::GET, "users/me"))
+            .await
+    }
+
+    /// Open an event queue for new messages, with content left as markdown
+    async fn register(&self) -> Result<Queue> {
+        let form = [
+            ("event_types", r#"["message"]"#),
+            ("apply_markdown", "false"),
+        ];
+        self.call(self.request(reqwest::Method::POST, "register").form(&form))
+            .await
+    }
+
+    /// Wait for events after `last_event_id`
+    async fn events(&self, queue_id: &str, last_event_id: i64) -> Result<Vec<Event>> {
+        let request = self
+            .request(reqwest::Method::GET, "events")
+            .query(&[
+                ("queue_id", queue_id.to_string()),
+                ("last_event_id", last_event_id.to_string()),
+            ])
+            .timeout(POLL_TIMEOUT);
+        let events: Events = self.call(request).await?;
+        Ok(events.events)
+    }
+
+    async fn subscriptions(&self) -> Result<Vec<String>> {
+        let subscriptions: Subscriptions = self
+            .call(self.request(reqwest::Method::GET, "users/me/subscriptions"))
+            .await?;
+        Ok(subscriptions
+            .subscriptions
+            .into_iter()
+            .map(|s| s.name)
+            .collect())
+    }
+
+    /// Post `text` to a stream topic, or as a direct message when `target` is a list of emails
+    async fn send_message(&self, target: &str, topic: &str, text: &str) -> Result<()> {
+        let form = if is_direct_target(target) {
+            let emails: Vec<&str> = target.split(',').map(str::trim).collect();
+            vec![
+                ("type", "private".to_string()),
+                ("to", serde_json::to_string(&emails)?),
+                ("content", text.to_string()),
+            ]
+        } else {
+            vec![
+                ("type", "stream".to_string()),
+                ("to", target.to_string()),
+                ("topic", topic.to_string()),
+                ("content", text.to_string()),
+            ]
+        };
+        let _: serde_json::Value = self
+            .call(self.request(reqwest::Method::POST, "messages").form(&form))
+            .await
+            .context("Failed to send Zulip message")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Me {
+    user_id: u64,
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Queue {
+    queue_id: String,
+    last_event_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Events {
+    events: Vec<Event>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Event {
+    id: i64,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    message: Option<ZulipMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Subscriptions {
+    subscriptions: Vec<Subscription>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Subscription {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ZulipMessage {
+    id: u64,
+    /// "stream" for stream messages, "private" for direct messages
+    #[serde(rename = "type")]
+    kind: String,
+    sender_email: String,
+    sender_full_name: String,
+    /// The stream name, or everyone in a direct message (the bot included)
+    display_recipient: Recipient,
+    /// The topic; empty for direct messages
+    #[serde(default)]
+    subject: String,
+    content: String,
+    timestamp: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Recipient {
+    Stream(String),
+    Users(Vec<RecipientUser>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RecipientUser {
+    email: String,
+}
+
+// =============================================================================
+// Message conversion
+// =============================================================================
+
+/// Direct conversations are addressed by the other participants' emails
+/// (comma-separated); anything else is a stream name
+fn is_direct_target(target: &str) -> bool {
+    target.contains('@')
+}
+
+/// Drop a leading `@**Bot Name**` mention so commands like `!help` are seen as such
+fn strip_mention<'a>(content: &'a str, bot_name: &str) -> &'a str {
+    let mention = format!("@**{}**", bot_name);
+    content
+        .trim_start()
+        .strip_prefix(&mention)
+        .map(str::trim_start)
+        .unwrap_or(content)
+}
+
+/// Convert a Zulip message into an IncomingMessage. Stream messages keep the
+/// stream as the channel and the topic as the thread; direct messages are
+/// answered to everyone in them except the bot.
+fn incoming_message(
+    message: &ZulipMessage,
+    bot_email: &str,
+    bot_name: &str,
+) -> Option<IncomingMessage> {
+    if message.sender_email.eq_ignore_ascii_case(bot_email) {
+        return None;
+    }
+
+    let (channel_id, thread_id, is_direct) = match &message.display_recipient {
+        Recipient::Stream(stream) if message.kind == "stream" => {
+            (stream.clone(), Some(message.subject.clone()), false)
+        }
+        Recipient::Users(users) => {
+            let mut others: Vec<&str> = users
+                .iter()
+                .map(|u| u.email.as_str())
+                .filter(|email| !email.eq_ignore_ascii_case(bot_email))
+                .collect();
+            others.sort_unstable();
+            if others.is_empty() {
+                return None;
+            }
+            (others.join(","), None, true)
+        }
+        Recipient::Stream(_) => return None,
+    };
+
+    let body = strip_mention(&message.content, bot_name);
+    if body.is_empty() {
+        return None;
+    }
+
+    Some(IncomingMessage {
+        platform_id: "zulip".to_string(),
+        channel_id,
+        thread_id,
+        sender: ChatUser::with_name(&message.sender_email, &message.sender_full_name),
+        body: body.to_string(),
+        is_direct,
+        formatted: false,
+        attachments: Vec::new(),
+        event_id: message.id.to_string(),
+        edits_event_id: None,
+        timestamp: message.timestamp,
+    })
+}
+
+/// Whether the allowlists let this message through. Empty lists allow everyone.
+fn is_allowed(msg: &IncomingMessage, allowed_users: &[String], allowed_streams: &[String]) -> bool {
+    let user_ok = allowed_users.is_empty()
+        || allowed_users
+            .iter()
+            .any(|u| u.eq_ignore_ascii_case(&msg.sender.id));
+    let stream_ok =
+        msg.is_direct || allowed_streams.is_empty() || allowed_streams.contains(&msg.channel_id);
+    user_ok && stream_ok
+}
+
+fn message_text(content: MessageContent) -> String {
+    match content {
+        MessageContent::Plain(text) => text,
+        // Zulip renders markdown itself
+        MessageContent::Html { plain, .. } => plain,
+        MessageContent::Attachment {
+            filename, caption, ..
+        } => match caption {
+            Some(caption) => format!("{}\n*(attachment not sent: {})*", caption, filename),
+            None => format!("*(attachment not sent: {})*", filename),
+        },
+    }
+}
+
+// =============================================================================
+// ZulipPlatform - Implements MessagingPlatform + ChatPlatform (Tier 2)
+// =============================================================================
+
+/// Zulip platform implementation using the REST and long-polling events APIs
+pub struct ZulipPlatform {
+    config: ZulipConfig,
+    client: ZulipClient,
+    /// Bot's numeric user ID as a string
+    bot_user_id: String,
+    /// Bot's full name, as it appears in @-mentions
+    bot_name: String,
+    /// Connection state for health monitoring
+    connection: ConnectionTracker,
+}
+
+impl ZulipPlatform {
+    /// Create a new ZulipPlatform from config.
+    ///
+    /// Checks the credentials and resolves the bot's identity via `users/me`.
+    pub async fn new(config: ZulipConfig) -> Result<Self> {
+        let client = ZulipClient::new(&config);
+        let me = client
+            .me()
+            .await
+            .with_context(|| format!("Failed to authenticate with Zulip at {}", config.site))?;
+
+        tracing::info!(
+            site = %config.site,
+            bot_email = %config.bot_email,
+            bot_id = me.user_id,
+            "Zulip bot authenticated"
+        );
+
+        Ok(Self {
+            client,
+            bot_user_id: me.user_id.to_string(),
+            bot_name: me.full_name,
+            config,
+            connection: ConnectionTracker::new("zulip", PlatformConnectionState::Connecting),
+        })
+    }
+
+    /// Update the platform's connection state
+    pub fn set_connection_state(&self, state: PlatformConnectionState) {
+        self.connection.set(state);
+    }
+
+    fn channel(&self, id: &str) -> ZulipChannel {
+        ZulipChannel::new(id, self.client.clone(), &self.config.default_topic)
+    }
+}
+
+#[async_trait]
+impl MessagingPlatform for ZulipPlatform {
+    async fn event_stream(&self) -> Result<EventStream> {
+        let (tx, rx) = mpsc::channel(256);
+        let client = self.client.clone();
+        let bot_email = self.config.bot_email.clone();
+        let bot_name = self.bot_name.clone();
+        let allowed_users = self.config.allowed_users.clone();
+        let allowed_streams = self.config.allowed_streams.clone();
+        let connection = self.connection.clone();
+        let set_state = move |state: PlatformConnectionState| {
+            connection.set(state);
+        };
+        let mut backoff = BackoffState::new(BackoffConfig::from(&self.config.reconnect));
+
+        tokio::spawn(async move {
+            // Queue we are reading from, and the last event seen on it
+            let mut queue: Option<(String, i64)> = None;
+
+            loop {
+                let (queue_id, last_event_id) = match queue.clone() {
+                    Some(q) => q,
+                    None => match client.register().await {
+                        Ok(registered) => {
+                            tracing::info!(platform = "zulip", "Registered event queue");
+                            let q = (registered.queue_id, registered.last_event_id);
+                            queue = Some(q.clone());
+                            q
+                        }
+                        Err(e) => {
+                            // Unlimited retries: next_delay always has a delay
+                            let delay = backoff.next_delay().unwrap_or_default();
+                            tracing::warn!(
+                                platform = "zulip",
+                                error = %e,
+                                retry_in_secs = delay.as_secs_f64(),
+                                "Failed to register event queue, retrying"
+                            );
+                            set_state(PlatformConnectionState::Disconnected {
+                                reason: e.to_string(),
+                            });
+                            tokio::time::sleep(delay).await;
+                            continue;
+                        }
+                    },
+                };
+
+                let events = match client.events(&queue_id, last_event_id).await {
+                    Ok(events) => {
+                        backoff.record_success();
+                        set_state(PlatformConnectionState::Connected);
+                        events
+                    }
+                    Err(e) if is_bad_queue(&e) => {
+                        // Queues expire after about ten minutes without a poll
+                        tracing::info!(
+                            platform = "zulip",
+                            "Event queue expired, registering again"
+                        );
+                        queue = None;
+                        continue;
+                    }
+                    Err(e) => {
+                        let delay = backoff.next_delay().unwrap_or_default();
+                        tracing::warn!(
+                            platform = "zulip",
+                            error = %e,
+                            retry_in_secs = delay.as_secs_f64(),
+                            attempt = backoff.consecutive_failures(),
+                            "Long polling error, retrying"
+                        );
+                        set_state(PlatformConnectionState::Disconnected {
+                            reason: e.to_string(),
+                        });
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                };
+
+                for event in events {
+                    if let Some((_, last)) = queue.as_mut() {
+                        *last = (*last).max(event.id);
+                    }
+                    if event.kind != "message" {
+                        continue;
+                    }
+                    let Some(msg) = event
+                        .message
+                        .as_ref()
+                        .and_then(|m| incoming_message(m, &bot_email, &bot_name))
+                    else {
+                        continue;
+                    };
+
+                    if !is_allowed(&msg, &allowed_users, &allowed_streams) {
+                        tracing::debug!(
+                            platform = "zulip",
+                            sender = %msg.sender.id,
+                            channel = %msg.channel_id,
+                            "Skipping message from non-allowed user or stream"
+                        );
+                        continue;
+                    }
+
+                    if tx.send(msg).await.is_err() {
+                        tracing::warn!(platform = "zulip", "Event stream receiver dropped");
+                        return;
+                    }
+                }
+            }
+        });
+
+        let stream = ReceiverStream::new(rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn send(&self, channel_id: &str, content: MessageContent) -> Result<()> {
+        self.channel(channel_id).send(content).await
+    }
+
+    fn bot_user_id(&self) -> &str {
+        &self.bot_user_id
+    }
+
+    fn platform_id(&self) -> &'static str {
+        "zulip"
+    }
+
+    fn is_self(&self, user_id: &str) -> bool {
+        user_id == self.bot_user_id || user_id.eq_ignore_ascii_case(&self.config.bot_email)
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        tracing::info!(platform = "zulip", "Shutting down Zulip platform");
+        self.set_connection_state(PlatformConnectionState::Disconnected {
+            reason: "shutdown".to_string(),
+        });
+        Ok(())
+    }
+
+    fn connection_state(&self) -> PlatformConnectionState {
+        self.connection.get()
+    }
+
+    fn connection_events(&self) -> Option<broadcast::Receiver<ConnectionEvent>> {
+        Some(self.connection.subscribe())
+    }
+
+    fn threading(&self) -> Option<&dyn ThreadedPlatform> {
+        Some(self)
+    }
+
+    fn attachment_handler(&self) -> Option<&dyn AttachmentHandler> {
+        None
+    }
+}
+
+#[async_trait]
+impl ThreadedPlatform for ZulipPlatform {
+    /// Post to the topic `thread_ts` of the stream `channel_id`
+    async fn send_threaded(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        content: MessageContent,
+    ) -> Result<()> {
+        let topic = if thread_ts.is_empty() {
+            self.config.default_topic.as_str()
+        } else {
+            thread_ts
+        };
+        ZulipChannel::new(channel_id, self.client.clone(), topic)
+            .send(content)
+            .await
+    }
+}
+
+#[async_trait]
+impl ChatPlatform for ZulipPlatform {
+    type Channel = ZulipChannel;
+
+    async fn get_channel(&self, id: &str) -> Option<Self::Channel> {
+        Some(self.channel(id))
+    }
+
+    async fn joined_channels(&self) -> Vec<Self::Channel> {
+        match self.client.subscriptions().await {
+            Ok(streams) => streams.iter().map(|s| self.channel(s)).collect(),
+            Err(e) => {
+                tracing::warn!(platform = "zulip", error = %e, "Failed to list subscriptions");
+                Vec::new()
+            }
+        }
+    }
+}
+
+// =============================================================================
+// ZulipChannel - a stream, or a direct conversation
+// =============================================================================
+
+/// A Zulip stream (posting to one topic), or a direct conversation addressed by email
+#[derive(Clone)]
+pub struct ZulipChannel {
+    target: String,
+    topic: String,
+    client: ZulipClient,
+}
+
+impl ZulipChannel {
+    fn new(target: impl Into<String>, client: ZulipClient, topic: &str) -> Self {
+        Self {
+            target: target.into(),
+            topic: topic.to_string(),
+            client,
+        }
+    }
+}
+
+impl std::fmt::Debug for ZulipChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZulipChannel")
+            .field("target", &self.target)
+            .field("topic", &self.topic)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ChatChannel for ZulipChannel {
+    fn id(&self) -> &str {
+        &self.target
+    }
+
+    fn platform_id(&self) -> &str {
+        "zulip"
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.target.clone())
+    }
+
+    async fn is_direct(&self) -> bool {
+        is_direct_target(&self.target)
+    }
+
+    async fn send(&self, content: MessageContent) -> Result<()> {
+        for chunk in chunk_message(&message_text(content), MAX_MESSAGE_CHARS) {
+            self.client
+                .send_message(&self.target, &self.topic, &chunk)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOT: &str = "gorp-bot@zulip.example.com";
+
+    fn message(json: serde_json::Value) -> ZulipMessage {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn stream_message(content: &str) -> ZulipMessage {
+        message(serde_json::json!({
+            "id": 4242,
+            "type": "stream",
+            "sender_email": "alice@example.com",
+            "sender_full_name": "Alice",
+            "display_recipient": "engineering",
+            "subject": "deploys",
+            "content": content,
+            "timestamp": 1772618525
+        }))
+    }
+
+    #[test]
+    fn test_zulip_platform_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ZulipPlatform>();
+        assert_send_sync::<ZulipChannel>();
+    }
+
+    #[test]
+    fn test_stream_message_uses_topic_as_thread() {
+        let msg = incoming_message(&stream_message("ship it"), BOT, "gorp").unwrap();
+        assert_eq!(msg.platform_id, "zulip");
+        assert_eq!(msg.channel_id, "engineering");
+        assert_eq!(msg.thread_id.as_deref(), Some("deploys"));
+        assert_eq!(msg.sender.id, "alice@example.com");
+        assert_eq!(msg.sender.display_name.as_deref(), Some("Alice"));
+        assert_eq!(msg.body, "ship it");
+        assert_eq!(msg.event_id, "4242");
+        assert_eq!(msg.timestamp, 1772618525);
+        assert!(!msg.is_direct);
+    }
+
+    #[test]
+    fn test_private_message_is_direct_and_answered_by_email() {
+        let zulip = message(serde_json::json!({
+            "id": 7,
+            "type": "private",
+            "sender_email": "alice@example.com",
+            "sender_full_name": "Alice",
+            "display_recipient": [
+                { "email": BOT, "full_name": "gorp", "id": 1 },
+                { "email": "alice@example.com", "full_name": "Alice", "id": 2 }
+            ],
+            "subject": "",
+            "content": "hi",
+            "timestamp": 1772618525
+        }));
+        let msg = incoming_message(&zulip, BOT, "gorp").unwrap();
+        assert!(msg.is_direct);
+        assert_eq!(msg.channel_id, "alice@example.com");
+        assert!(msg.thread_id.is_none());
+        assert!(is_direct_target(&msg.channel_id));
+    }
+
+    #[test]
+    fn test_own_messages_are_skipped() {
+        let mut own = stream_message("echo");
+        own.sender_email = BOT.to_uppercase();
+        assert!(incoming_message(&own, BOT, "gorp").is_none());
+    }
+
+    #[test]
+    fn test_leading_bot_mention_is_stripped() {
+        let msg = incoming_message(&stream_message("@**gorp** !help"), BOT, "gorp").unwrap();
+        assert_eq!(msg.body, "!help");
+        assert!(incoming_message(&stream_message("@**gorp**"), BOT, "gorp").is_none());
+        assert_eq!(
+            strip_mention("ask @**gorp** later", "gorp"),
+            "ask @**gorp** later"
+        );
+    }
+
+    #[test]
+    fn test_allowlists() {
+        let msg = incoming_message(&stream_message("hi"), BOT, "gorp").unwrap();
+        let alice = vec!["Alice@Example.com".to_string()];
+        let eng = vec!["engineering".to_string()];
+        let ops = vec!["ops".to_string()];
+        assert!(is_allowed(&msg, &[], &[]));
+        assert!(is_allowed(&msg, &alice, &eng));
+        assert!(!is_allowed(&msg, &["bob@example.com".to_string()], &[]));
+        assert!(!is_allowed(&msg, &alice, &ops));
+    }
+
+    #[test]
+    fn test_bad_queue_error_is_recognized() {
+        let expired: anyhow::Error = ApiError {
+            code: "BAD_EVENT_QUEUE_ID".to_string(),
+            msg: "Bad event queue ID".to_string(),
+        }
+        .into();
+        assert!(is_bad_queue(&expired));
+        assert!(!is_bad_queue(&anyhow::anyhow!("connection reset")));
+    }
+}
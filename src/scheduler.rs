@@ -4,37 +4,71 @@
 // Re-export all core scheduler types and functions from gorp-core
 // This ensures type consistency across the codebase
 pub use gorp_core::scheduler::{
-    compute_next_cron_execution, compute_next_cron_execution_in_tz, parse_time_expression,
-    ParsedSchedule, ScheduleStatus, ScheduledPrompt, SchedulerCallback, SchedulerStore,
+    compute_next_cron_execution, compute_next_cron_execution_after,
+    compute_next_cron_execution_in_tz, parse_time_expression, ParsedSchedule, QuietHours,
+    QuietPolicy, ScheduleRun, ScheduleStatus, ScheduledPrompt, SchedulerCallback, SchedulerStore,
 };
 
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration as StdDuration;
-use tokio::time::interval;
+use std::time::{Duration as StdDuration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
 
 use crate::{
-    bus::{BusMessage, MessageBus, MessageSource, SessionTarget},
+    bus::{BusMessage, BusResponse, MessageBus, MessageSource, ResponseContent, SessionTarget},
     config::Config,
+    logging::loggable_content,
+    message_handler::history::{export_scheduled, is_export_schedule},
     metrics,
+    runtime_mode::Gate,
     session::{Channel, SessionStore},
+    templates,
     utils::expand_slash_command,
     warm_session::{prepare_session_async, SharedWarmSessionManager},
 };
 
-/// Write context file for MCP tools (used by scheduler before Claude invocation)
-async fn write_context_file(channel: &Channel) -> Result<()> {
+/// The text a schedule sends: its own prompt, or for `template:<name>` the
+/// channel's saved template as it reads at execution time.
+fn resolve_template_prompt(
+    session_store: &SessionStore,
+    channel_name: &str,
+    prompt: &str,
+) -> Result<String> {
+    let Some(name) = templates::schedule_reference(prompt) else {
+        return Ok(prompt.to_string());
+    };
+    match session_store.get_template(channel_name, name)? {
+        Some(template) => Ok(templates::render(&template.body, "")),
+        None => anyhow::bail!("Template '{}' not found", name),
+    }
+}
+
+/// How often a scheduler suspended by safe mode checks whether it may resume
+const SAFE_MODE_RECHECK: StdDuration = StdDuration::from_secs(5);
+
+/// How long a run waits for the agent's response before it is logged as failed
+const RUN_RESULT_TIMEOUT: StdDuration = StdDuration::from_secs(15 * 60);
+
+/// Write context file for MCP tools (used by scheduler before Claude invocation).
+/// Custom keys from !context are merged in underneath the reserved ones.
+async fn write_context_file(channel: &Channel, custom: &BTreeMap<String, String>) -> Result<()> {
     let gorp_dir = Path::new(&channel.directory).join(".gorp");
     tokio::fs::create_dir_all(&gorp_dir).await?;
 
-    let context = serde_json::json!({
-        "room_id": channel.room_id,
-        "channel_name": channel.channel_name,
-        "session_id": channel.session_id,
-        "updated_at": Utc::now().to_rfc3339()
-    });
+    let mut context: serde_json::Map<String, serde_json::Value> = custom
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone().into()))
+        .collect();
+    context.insert("room_id".to_string(), channel.room_id.clone().into());
+    context.insert(
+        "channel_name".to_string(),
+        channel.channel_name.clone().into(),
+    );
+    context.insert("session_id".to_string(), channel.session_id.clone().into());
+    context.insert("updated_at".to_string(), Utc::now().to_rfc3339().into());
 
     let context_path = gorp_dir.join("context.json");
     tokio::fs::write(&context_path, serde_json::to_string_pretty(&context)?).await?;
@@ -43,7 +77,7 @@ async fn write_context_file(channel: &Channel) -> Result<()> {
     Ok(())
 }
 
-/// Start the background scheduler task that checks for and executes due schedules.
+/// Start the background scheduler task that executes schedules as they fall due.
 ///
 /// When a schedule fires, the scheduler publishes a `BusMessage` to the message bus.
 /// The orchestrator handles routing the message to the appropriate agent session,
@@ -53,20 +87,55 @@ pub async fn start_scheduler(
     session_store: SessionStore,
     bus: Arc<MessageBus>,
     config: Arc<Config>,
-    check_interval: StdDuration,
+    max_sleep: StdDuration,
     warm_manager: SharedWarmSessionManager,
 ) {
+    run_scheduler(
+        scheduler_store,
+        session_store,
+        bus,
+        config,
+        max_sleep,
+        warm_manager,
+        Utc::now,
+    )
+    .await
+}
+
+/// Scheduler loop with an injectable wall clock.
+///
+/// Sleeps until the earliest `next_execution_at` of any active schedule (waking
+/// `pre_warm_secs` before it to warm the session), runs what is due, and recomputes.
+/// Changes made through the store wake it early; `max_sleep` bounds the wait so
+/// changes from other processes (the `schedule` CLI) are still noticed.
+pub async fn run_scheduler<F>(
+    scheduler_store: SchedulerStore,
+    session_store: SessionStore,
+    bus: Arc<MessageBus>,
+    config: Arc<Config>,
+    max_sleep: StdDuration,
+    warm_manager: SharedWarmSessionManager,
+    clock: F,
+) where
+    F: Fn() -> DateTime<Utc>,
+{
     tracing::info!(
-        interval_secs = check_interval.as_secs(),
+        max_sleep_secs = max_sleep.as_secs(),
         "Starting scheduler background task"
     );
-
-    let mut ticker = interval(check_interval);
+    let pre_warm_lead = chrono::Duration::seconds(config.backend.pre_warm_secs as i64);
+    let runtime = warm_manager.read().await.runtime_mode();
 
     loop {
-        ticker.tick().await;
+        // Due schedules stay due while suspended and run once safe mode ends
+        if let Err(refusal) = runtime.check(Gate::Scheduler) {
+            tracing::debug!(reason = %refusal, "Scheduler suspended");
+            tokio::time::sleep(SAFE_MODE_RECHECK.min(max_sleep)).await;
+            continue;
+        }
 
-        let now = Utc::now();
+        let now = clock();
+        let mut claim_failed = false;
         // Use claim_due_schedules to atomically mark schedules as 'executing'
         // This prevents race conditions where a slow execution could cause duplicates
         match scheduler_store.claim_due_schedules(now) {
@@ -88,60 +157,121 @@ pub async fn start_scheduler(
                     // Execute each due schedule concurrently
                     // Publishing to the bus is Send-safe, so tokio::spawn works
                     tokio::spawn(async move {
-                        execute_schedule(schedule, store, sess_store, bus_clone, cfg).await;
+                        execute_schedule(schedule, store, sess_store, bus_clone, cfg, now).await;
                     });
                 }
             }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to fetch due schedules");
+                claim_failed = true;
             }
         }
 
-        // Check for schedules that should be pre-warmed
-        // Pre-warm if within pre_warm_secs of execution
-        let pre_warm_duration = chrono::Duration::seconds(config.backend.pre_warm_secs as i64);
-        let pre_warm_cutoff = now + pre_warm_duration;
+        pre_warm_upcoming(
+            &scheduler_store,
+            &session_store,
+            &config,
+            &warm_manager,
+            now,
+        )
+        .await;
 
-        if let Ok(all_schedules) = scheduler_store.list_all() {
-            for schedule in all_schedules {
-                // Only pre-warm active schedules
-                if schedule.status != ScheduleStatus::Active {
-                    continue;
-                }
+        let next_due = scheduler_store.next_due_at().unwrap_or_else(|e| {
+            tracing::error!(error = %e, "Failed to find the next schedule deadline");
+            None
+        });
+        // Wake early enough to pre-warm the next schedule's session, then again when it's due
+        let now = clock();
+        let wake_at = next_due.map(|due| {
+            let warm_at = due - pre_warm_lead;
+            if warm_at > now {
+                warm_at
+            } else {
+                due
+            }
+        });
+        // A deadline already past would otherwise be retried in a tight loop
+        let sleep_for = if claim_failed {
+            max_sleep
+        } else {
+            time_until_next_check(wake_at, now, max_sleep)
+        };
+        tracing::debug!(
+            sleep_ms = sleep_for.as_millis() as u64,
+            next_due = ?next_due,
+            "Scheduler sleeping until next deadline"
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(sleep_for) => {}
+            _ = scheduler_store.wait_for_change() => {
+                tracing::debug!("Schedules changed, recomputing next deadline");
+            }
+        }
+    }
+}
 
-                // Parse next execution time
-                if let Ok(next_exec) =
-                    chrono::DateTime::parse_from_rfc3339(&schedule.next_execution_at)
-                {
-                    let next_exec_utc = next_exec.with_timezone(&Utc);
-
-                    // Pre-warm if within the window
-                    if next_exec_utc > now && next_exec_utc <= pre_warm_cutoff {
-                        // Get channel for this schedule
-                        if let Ok(Some(channel)) = session_store.get_by_name(&schedule.channel_name)
-                        {
-                            let channel_name = schedule.channel_name.clone();
-
-                            // Pre-warm using prepare_session_async which minimizes lock holding
-                            // This allows concurrent pre-warming without blocking other channels
-                            match prepare_session_async(&warm_manager, &channel).await {
-                                Ok(_) => {
-                                    tracing::debug!(
-                                        channel = %channel_name,
-                                        "Pre-warmed session for upcoming schedule"
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::warn!(
-                                        channel = %channel_name,
-                                        error = %e,
-                                        "Pre-warm failed for upcoming schedule"
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
+/// How long to sleep before the next check: until `next_due`, at most `max_sleep`,
+/// and not at all when it has already passed
+pub fn time_until_next_check(
+    next_due: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    max_sleep: StdDuration,
+) -> StdDuration {
+    match next_due {
+        Some(due) => (due - now).to_std().unwrap_or_default().min(max_sleep),
+        None => max_sleep,
+    }
+}
+
+/// Warm the sessions of channels whose schedules fire within `pre_warm_secs`
+async fn pre_warm_upcoming(
+    scheduler_store: &SchedulerStore,
+    session_store: &SessionStore,
+    config: &Config,
+    warm_manager: &SharedWarmSessionManager,
+    now: DateTime<Utc>,
+) {
+    let pre_warm_duration = chrono::Duration::seconds(config.backend.pre_warm_secs as i64);
+    let pre_warm_cutoff = now + pre_warm_duration;
+
+    let Ok(all_schedules) = scheduler_store.list_all() else {
+        return;
+    };
+    for schedule in all_schedules {
+        // Only pre-warm active schedules, and only those that run the agent
+        if schedule.status != ScheduleStatus::Active || is_export_schedule(&schedule.prompt) {
+            continue;
+        }
+
+        // Parse next execution time
+        let Ok(next_exec) = DateTime::parse_from_rfc3339(&schedule.next_execution_at) else {
+            continue;
+        };
+        let next_exec_utc = next_exec.with_timezone(&Utc);
+
+        // Pre-warm if within the window
+        if next_exec_utc <= now || next_exec_utc > pre_warm_cutoff {
+            continue;
+        }
+        let Ok(Some(channel)) = session_store.get_by_name(&schedule.channel_name) else {
+            continue;
+        };
+
+        // Pre-warm using prepare_session_async which minimizes lock holding
+        // This allows concurrent pre-warming without blocking other channels
+        match prepare_session_async(warm_manager, &channel).await {
+            Ok(_) => {
+                tracing::debug!(
+                    channel = %schedule.channel_name,
+                    "Pre-warmed session for upcoming schedule"
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    channel = %schedule.channel_name,
+                    error = %e,
+                    "Pre-warm failed for upcoming schedule"
+                );
             }
         }
     }
@@ -159,14 +289,15 @@ async fn execute_schedule(
     session_store: SessionStore,
     bus: Arc<MessageBus>,
     config: Arc<Config>,
+    claimed_at: DateTime<Utc>,
 ) {
-    let prompt_preview: String = schedule.prompt.chars().take(50).collect();
     tracing::info!(
         schedule_id = %schedule.id,
         channel = %schedule.channel_name,
-        prompt_preview = %prompt_preview,
+        prompt = %loggable_content(&schedule.prompt),
         "Executing scheduled prompt via message bus"
     );
+    let started = Instant::now();
 
     // Get channel info (needed for directory, context file, slash command expansion)
     let channel = match session_store.get_by_name(&schedule.channel_name) {
@@ -177,9 +308,14 @@ async fn execute_schedule(
                 channel = %schedule.channel_name,
                 "Channel no longer exists"
             );
-            if let Err(e) = scheduler_store.mark_failed(&schedule.id, "Channel no longer exists") {
-                tracing::error!(error = %e, schedule_id = %schedule.id, "Failed to mark schedule failed");
-            }
+            fail_run(
+                &scheduler_store,
+                &config,
+                &schedule,
+                claimed_at,
+                started,
+                "Channel no longer exists",
+            );
             return;
         }
         Err(e) => {
@@ -188,21 +324,138 @@ async fn execute_schedule(
                 error = %e,
                 "Failed to get channel"
             );
-            if let Err(e) = scheduler_store.mark_failed(&schedule.id, &e.to_string()) {
-                tracing::error!(error = %e, schedule_id = %schedule.id, "Failed to mark schedule failed");
-            }
+            fail_run(
+                &scheduler_store,
+                &config,
+                &schedule,
+                claimed_at,
+                started,
+                &e.to_string(),
+            );
             return;
         }
     };
 
+    // An export saves the transcript itself; the agent isn't involved
+    if is_export_schedule(&schedule.prompt) {
+        let export_dir = config.maintenance.transcript_export_dir.as_deref();
+        let outcome = match export_scheduled(
+            &channel.directory,
+            &channel.channel_name,
+            export_dir,
+            claimed_at,
+        ) {
+            Ok(Some(path)) => {
+                tracing::info!(
+                    schedule_id = %schedule.id,
+                    path = %path.display(),
+                    "Exported channel transcript"
+                );
+                format!("Exported transcript to {}", path.display())
+            }
+            Ok(None) => {
+                tracing::info!(
+                    schedule_id = %schedule.id,
+                    channel = %schedule.channel_name,
+                    "No history to export yet"
+                );
+                "No history to export yet".to_string()
+            }
+            Err(e) => {
+                tracing::error!(schedule_id = %schedule.id, error = %e, "Transcript export failed");
+                fail_run(
+                    &scheduler_store,
+                    &config,
+                    &schedule,
+                    claimed_at,
+                    started,
+                    &e.to_string(),
+                );
+                return;
+            }
+        };
+        record_run(
+            &scheduler_store,
+            &config,
+            &schedule,
+            claimed_at,
+            started,
+            Ok(&outcome),
+        );
+        finish_execution(&schedule, &scheduler_store, &config, claimed_at);
+        return;
+    }
+
+    // During quiet hours a schedule either waits for them to end or runs without posting
+    let mut silent = false;
+    if let Some(until) = config
+        .scheduler
+        .quiet_hours()
+        .and_then(|quiet| quiet.next_allowed(claimed_at, &config.scheduler.timezone))
+    {
+        let policy = scheduler_store
+            .quiet_policy(&schedule.id)
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, schedule_id = %schedule.id, "Failed to read quiet policy");
+                QuietPolicy::default()
+            });
+        match policy {
+            QuietPolicy::Defer => {
+                tracing::info!(
+                    schedule_id = %schedule.id,
+                    until = %until.to_rfc3339(),
+                    "Deferring scheduled prompt until quiet hours end"
+                );
+                if let Err(e) = scheduler_store.defer_schedule(&schedule.id, until) {
+                    tracing::error!(error = %e, schedule_id = %schedule.id, "Failed to defer schedule");
+                }
+                return;
+            }
+            QuietPolicy::Silent => {
+                tracing::info!(
+                    schedule_id = %schedule.id,
+                    "Running scheduled prompt silently during quiet hours"
+                );
+                silent = true;
+            }
+        }
+    }
+
     // Write context file for MCP tools before publishing to the bus
-    if let Err(e) = write_context_file(&channel).await {
+    let custom_context = session_store
+        .get_custom_context(&channel.channel_name)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load custom context");
+            BTreeMap::new()
+        });
+    if let Err(e) = write_context_file(&channel, &custom_context).await {
         tracing::warn!(error = %e, "Failed to write context file for scheduled task");
         // Non-fatal - continue without context file
     }
 
+    let prompt =
+        match resolve_template_prompt(&session_store, &channel.channel_name, &schedule.prompt) {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::error!(
+                    schedule_id = %schedule.id,
+                    error = %e,
+                    "Failed to resolve prompt template"
+                );
+                fail_run(
+                    &scheduler_store,
+                    &config,
+                    &schedule,
+                    claimed_at,
+                    started,
+                    &e.to_string(),
+                );
+                return;
+            }
+        };
+
     // Expand slash commands at execution time (so updates to commands are picked up)
-    let prompt = match expand_slash_command(&schedule.prompt, &channel.directory) {
+    let prompt = match expand_slash_command(&prompt, &channel.directory) {
         Ok(p) => p,
         Err(e) => {
             tracing::error!(
@@ -210,9 +463,14 @@ async fn execute_schedule(
                 error = %e,
                 "Failed to expand slash command"
             );
-            if let Err(e) = scheduler_store.mark_failed(&schedule.id, &e.to_string()) {
-                tracing::error!(error = %e, schedule_id = %schedule.id, "Failed to mark schedule failed");
-            }
+            fail_run(
+                &scheduler_store,
+                &config,
+                &schedule,
+                claimed_at,
+                started,
+                &e.to_string(),
+            );
             return;
         }
     };
@@ -233,6 +491,8 @@ async fn execute_schedule(
         },
         body: prompt,
         timestamp: Utc::now(),
+        silent,
+        urgent: false,
     };
 
     tracing::info!(
@@ -241,11 +501,123 @@ async fn execute_schedule(
         channel = %schedule.channel_name,
         "Publishing scheduled prompt to message bus"
     );
+    // Subscribe before publishing so the response can't slip past
+    let mut responses = bus.subscribe_responses();
     bus.publish_inbound(msg);
 
-    // Calculate next execution for recurring schedules
+    finish_execution(&schedule, &scheduler_store, &config, claimed_at);
+
+    let outcome = await_response(&mut responses, &schedule.channel_name, silent).await;
+    if let Err(e) = &outcome {
+        tracing::warn!(schedule_id = %schedule.id, error = %e, "Scheduled run failed");
+    }
+    record_run(
+        &scheduler_store,
+        &config,
+        &schedule,
+        claimed_at,
+        started,
+        outcome.as_deref().map_err(|e| e.as_str()),
+    );
+}
+
+/// Wait for the agent's answer to a prompt the scheduler published to `session_name`.
+///
+/// Silent runs only post errors, so one that ends without an error is taken to
+/// have succeeded once the wait is over.
+async fn await_response(
+    responses: &mut broadcast::Receiver<BusResponse>,
+    session_name: &str,
+    silent: bool,
+) -> Result<String, String> {
+    // The complete response repeats the streamed chunks, so only it is kept
+    let wait = async {
+        loop {
+            match responses.recv().await {
+                Ok(resp) if resp.session_name == session_name => match resp.content {
+                    ResponseContent::Complete(text) => break Ok(text),
+                    ResponseContent::Error(err) => break Err(err),
+                    ResponseContent::Chunk(_) | ResponseContent::SystemNotice(_) => {}
+                },
+                Ok(_) => {}
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!(skipped = n, "Scheduled run response listener lagged");
+                }
+                Err(RecvError::Closed) => break Err("Message bus closed".to_string()),
+            }
+        }
+    };
+    match tokio::time::timeout(RUN_RESULT_TIMEOUT, wait).await {
+        Ok(outcome) => outcome,
+        Err(_) if silent => Ok(format!(
+            "Ran silently; the response is in {}/",
+            crate::orchestrator::QUIET_RESULTS_DIR
+        )),
+        Err(_) => Err(format!(
+            "No response within {} minutes (it may be held for the delivery window)",
+            RUN_RESULT_TIMEOUT.as_secs() / 60
+        )),
+    }
+}
+
+/// Mark a schedule as failed and log the failed run
+fn fail_run(
+    scheduler_store: &SchedulerStore,
+    config: &Config,
+    schedule: &ScheduledPrompt,
+    claimed_at: DateTime<Utc>,
+    started: Instant,
+    error: &str,
+) {
+    if let Err(e) = scheduler_store.mark_failed(&schedule.id, error) {
+        tracing::error!(error = %e, schedule_id = %schedule.id, "Failed to mark schedule failed");
+    }
+    record_run(
+        scheduler_store,
+        config,
+        schedule,
+        claimed_at,
+        started,
+        Err(error),
+    );
+}
+
+/// Remember how a run went for `!schedule log`
+fn record_run(
+    scheduler_store: &SchedulerStore,
+    config: &Config,
+    schedule: &ScheduledPrompt,
+    claimed_at: DateTime<Utc>,
+    started: Instant,
+    outcome: Result<&str, &str>,
+) {
+    let (success, preview) = match outcome {
+        Ok(text) => (true, text),
+        Err(error) => (false, error),
+    };
+    let run = ScheduleRun {
+        schedule_id: schedule.id.clone(),
+        started_at: claimed_at.to_rfc3339(),
+        duration_ms: started.elapsed().as_millis() as i64,
+        success,
+        preview: preview.to_string(),
+    };
+    if let Err(e) = scheduler_store.record_run(&run, config.scheduler.run_history) {
+        tracing::warn!(error = %e, schedule_id = %schedule.id, "Failed to record schedule run");
+    }
+}
+
+/// Mark a schedule that has run as executed, rescheduling it if it recurs
+fn finish_execution(
+    schedule: &ScheduledPrompt,
+    scheduler_store: &SchedulerStore,
+    config: &Config,
+    claimed_at: DateTime<Utc>,
+) {
+    // Calculate next execution for recurring schedules. Counting from the claim time
+    // fires a schedule that was overslept once, then resumes at its next real slot.
     let next_execution = if let Some(ref cron_expr) = schedule.cron_expression {
-        match compute_next_cron_execution_in_tz(cron_expr, &config.scheduler.timezone) {
+        match compute_next_cron_execution_after(cron_expr, &config.scheduler.timezone, claimed_at) {
             Ok(next) => Some(next),
             Err(e) => {
                 // Log the error and mark schedule as failed instead of silently completing
@@ -3,8 +3,15 @@
 
 use crate::bus::MessageBus;
 use crate::config::Config;
+use crate::confirmations::PendingConfirmations;
+use crate::dedup::DedupCache;
+use crate::edits::EditTracker;
+use crate::rate_limit::RateLimiter;
+use crate::redactions::ResponseTracker;
+use crate::runtime_mode::RuntimeMode;
 use crate::scheduler::SchedulerStore;
 use crate::session::SessionStore;
+use crate::transcription::{HttpTranscriber, Transcriber};
 use crate::warm_session::SharedWarmSessionManager;
 use anyhow::Result;
 use futures_util::FutureExt;
@@ -21,6 +28,18 @@ pub struct ServerState {
     pub warm_manager: SharedWarmSessionManager,
     /// Shared message bus for platform-agnostic message routing
     pub bus: Arc<MessageBus>,
+    /// Recently handled (platform, event) pairs, to drop replayed events
+    pub dedup: Arc<DedupCache>,
+    /// Recent prompts, so message edits can be applied as corrections
+    pub edits: Arc<EditTracker>,
+    /// Bot messages answering recent prompts, redacted along with their prompt
+    pub responses: Arc<ResponseTracker>,
+    /// Destructive commands waiting for their Confirm / Cancel button
+    pub confirmations: Arc<PendingConfirmations>,
+    /// Per-sender message budget, shared by the Matrix and generic paths
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Speech-to-text for voice messages; None when `[transcription]` isn't configured
+    pub transcriber: Option<Arc<dyn Transcriber>>,
     /// Sync token from initial sync - used by headless mode to continue syncing
     /// None when running without Matrix
     pub sync_token: Option<String>,
@@ -35,6 +54,15 @@ impl std::fmt::Debug for ServerState {
             .field("scheduler_store", &"<SchedulerStore>")
             .field("warm_manager", &"<WarmSessionManager>")
             .field("bus", &"<MessageBus>")
+            .field("dedup", &"<DedupCache>")
+            .field("edits", &"<EditTracker>")
+            .field("responses", &"<ResponseTracker>")
+            .field("confirmations", &"<PendingConfirmations>")
+            .field("rate_limiter", &"<RateLimiter>")
+            .field(
+                "transcriber",
+                &self.transcriber.as_ref().map(|_| "<Transcriber>"),
+            )
             .field("sync_token", &"<token>")
             .finish()
     }
@@ -88,6 +116,9 @@ impl ServerState {
         use matrix_sdk::config::SyncSettings;
         use std::time::Duration;
 
+        // Before anything can log a prompt
+        crate::logging::set_content_policy(config.logging.content_policy);
+
         // Create warm session manager
         let warm_config = WarmConfig {
             keep_alive_duration: Duration::from_secs(config.backend.keep_alive_secs),
@@ -102,8 +133,26 @@ impl ServerState {
             max_tokens: config.backend.max_tokens,
             global_system_prompt_path: config.backend.global_system_prompt_path.clone(),
             mcp_servers: config.backend.mcp_servers.clone(),
+            max_response_chars: config.backend.max_response_chars,
+            save_truncated_responses: config.backend.save_truncated_responses,
         };
         let warm_manager = create_shared_manager(warm_config);
+        // The pipeline, scheduler and webhook server all read the mode from here
+        if config.runtime.safe_mode {
+            tracing::warn!(
+                "Starting in safe mode: agents, schedules, webhooks and notifications are paused"
+            );
+        }
+        warm_manager
+            .write()
+            .await
+            .set_runtime_mode(RuntimeMode::new(config.runtime.safe_mode));
+        let preprocessors = crate::preprocess::PreprocessPipeline::from_config(&config.preprocess)
+            .context("Failed to set up prompt preprocessing")?;
+        if !preprocessors.is_empty() {
+            tracing::info!(steps = ?preprocessors.names(), "Prompt preprocessing enabled");
+        }
+        warm_manager.write().await.set_preprocessors(preprocessors);
 
         // Spawn cleanup task
         let cleanup_manager = warm_manager.clone();
@@ -121,8 +170,37 @@ impl ServerState {
         let bus = Arc::new(MessageBus::new(256));
         tracing::info!("Message bus initialized");
 
+        let dedup = Arc::new(DedupCache::new(
+            config.dedup.cache_size,
+            Duration::from_secs(config.dedup.ttl_secs),
+        ));
+        let edits = Arc::new(EditTracker::new(Duration::from_secs(
+            config.edits.correction_window_mins * 60,
+        )));
+        let responses = Arc::new(ResponseTracker::new(Duration::from_secs(
+            config.edits.redaction_window_mins * 60,
+        )));
+        let rate_limiter = Arc::new(RateLimiter::new(
+            config.limits.messages_per_minute,
+            config.limits.burst,
+        ));
+
+        let transcriber = match &config.transcription {
+            Some(transcription) => {
+                let transcriber = HttpTranscriber::new(transcription)?;
+                tracing::info!(
+                    provider = ?transcription.provider,
+                    url = %transcription.url,
+                    "Voice transcription enabled"
+                );
+                Some(Arc::new(transcriber) as Arc<dyn Transcriber>)
+            }
+            None => None,
+        };
+
         // Initialize session store
-        let session_store = SessionStore::new(&config.workspace.path)?;
+        let session_store = SessionStore::new(&config.workspace.path)?
+            .with_channel_scope(config.workspace.channel_scope);
         tracing::info!(workspace = %config.workspace.path, "Session store initialized");
 
         // Load persisted channel bindings into the in-memory bus
@@ -187,6 +265,12 @@ impl ServerState {
             scheduler_store,
             warm_manager,
             bus,
+            dedup,
+            edits,
+            responses,
+            confirmations: Arc::new(PendingConfirmations::default()),
+            rate_limiter,
+            transcriber,
             sync_token,
         })
     }
@@ -0,0 +1,50 @@
+// ABOUTME: Graceful shutdown for `gorp start`: wait for SIGTERM/ctrl-c, then drain agent turns.
+// ABOUTME: In-flight message handlers are tracked so shutdown can wait for them up to a deadline.
+
+use std::time::Duration;
+
+use tokio_util::task::TaskTracker;
+
+/// Resolves on SIGTERM (how containers and systemd stop us) or ctrl-c
+pub async fn signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await
+    }
+}
+
+/// Stop `tracker` taking new tasks and wait up to `timeout` for the running ones.
+/// Returns false if some were still running when time ran out.
+pub async fn drain(tracker: &TaskTracker, timeout: Duration) -> bool {
+    tracker.close();
+    let running = tracker.len();
+    tracing::info!(
+        tasks = running,
+        timeout_secs = timeout.as_secs(),
+        "Shutdown: draining {} tasks",
+        running
+    );
+
+    match tokio::time::timeout(timeout, tracker.wait()).await {
+        Ok(()) => {
+            tracing::info!("Shutdown: all in-flight tasks finished");
+            true
+        }
+        Err(_) => {
+            tracing::warn!(
+                remaining = tracker.len(),
+                "Shutdown: drain timed out, abandoning unfinished tasks"
+            );
+            false
+        }
+    }
+}
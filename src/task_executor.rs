@@ -10,6 +10,9 @@ use tokio::time::{interval, Duration};
 
 use crate::{
     config::Config,
+    logging::loggable_content,
+    platform::matrix::signing::Signer,
+    usage::InvocationOrigin,
     utils::{chunk_message, markdown_to_html, MAX_CHUNK_SIZE},
     warm_session::{prepare_session_async, send_prompt_with_handle, SharedWarmSessionManager},
 };
@@ -85,7 +88,7 @@ async fn run_executor_loop(
             tracing::info!(
                 task_id = %task.id,
                 target_room = %task.target_room_id,
-                prompt_preview = %task.prompt.chars().take(50).collect::<String>(),
+                prompt = %loggable_content(&task.prompt),
                 "Executing dispatch task"
             );
 
@@ -275,7 +278,7 @@ async fn execute_task(
     prompt: &str,
     client: &Option<Client>,
     session_store: &SessionStore,
-    _config: Arc<Config>,
+    config: Arc<Config>,
     warm_manager: SharedWarmSessionManager,
 ) -> Result<String> {
     // Get channel info for the target room
@@ -330,7 +333,10 @@ async fn execute_task(
     }
 
     // Send prompt and get event receiver
-    let mut rx = send_prompt_with_handle(&session_handle, &session_id, prompt).await?;
+    // Tasks are delegated by DISPATCH on a user's behalf, so they count as conversation
+    let mut rx =
+        send_prompt_with_handle(&session_handle, &session_id, prompt, InvocationOrigin::User)
+            .await?;
 
     // Collect response from stream
     let mut response = String::new();
@@ -385,13 +391,15 @@ async fn execute_task(
     // Send response to room (chunk if needed, when Matrix is available)
     if let Some(ref room) = room {
         if !response.is_empty() {
+            let signer = Signer::from_config(&config.signing);
             let chunks = chunk_message(&response, MAX_CHUNK_SIZE);
             for chunk in chunks {
                 let html = markdown_to_html(&chunk);
-                if let Err(e) = room
-                    .send(RoomMessageEventContent::text_html(&chunk, &html))
-                    .await
-                {
+                let content = match &signer {
+                    Some(signer) => signer.html_event(room.room_id().as_str(), chunk, html),
+                    None => RoomMessageEventContent::text_html(chunk, html),
+                };
+                if let Err(e) = room.send(content).await {
                     tracing::error!(task_id = %task_id, error = %e, "Failed to send response chunk");
                 }
             }
@@ -6,7 +6,7 @@ use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 use std::collections::VecDeque;
 
-use super::event::TuiEvent;
+use super::event::{ChatUpdate, TuiEvent};
 use super::sidebar;
 use super::theme;
 use super::views;
@@ -47,6 +47,11 @@ impl View {
 pub enum EventResult {
     Continue,
     Quit,
+    /// Send a prompt typed into the chat view to a channel's agent
+    SendChat {
+        channel_name: String,
+        body: String,
+    },
 }
 
 // =============================================================================
@@ -132,6 +137,22 @@ pub struct ChatMessage {
     pub timestamp: i64,
 }
 
+/// Sender shown on prompts typed into the chat view
+pub const CHAT_USER: &str = "you";
+
+/// Sender shown on the agent's responses in the chat view
+pub const CHAT_AGENT: &str = "agent";
+
+// =============================================================================
+// Channel info for the chat view's channel picker
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelInfo {
+    pub name: String,
+    pub platform_id: String,
+}
+
 // =============================================================================
 // Gateway info for gateways view
 // =============================================================================
@@ -177,6 +198,12 @@ pub struct TuiApp {
     pub chat_messages: Vec<ChatMessage>,
     pub chat_scroll: usize,
     pub chat_channel_name: Option<String>,
+    pub chat_channels: Vec<ChannelInfo>,
+    pub chat_channel_selected: usize,
+    /// Why the chat view can't send yet: still connecting, or the startup error
+    pub chat_status: Option<String>,
+    /// A response is streaming into the open chat
+    pub chat_streaming: bool,
     pub gateway_infos: Vec<GatewayInfo>,
     pub gateway_selected: usize,
 }
@@ -217,6 +244,10 @@ impl TuiApp {
             chat_messages: Vec::new(),
             chat_scroll: 0,
             chat_channel_name: None,
+            chat_channels: Vec::new(),
+            chat_channel_selected: 0,
+            chat_status: Some("Connecting to gorp…".to_string()),
+            chat_streaming: false,
             gateway_infos: Vec::new(),
             gateway_selected: 0,
         }
@@ -224,7 +255,15 @@ impl TuiApp {
 
     /// Navigation items in order
     pub fn nav_items() -> &'static [&'static str] {
-        &["Dashboard", "Feed", "Workspace", "Channels", "Gateways", "Schedules", "Logs"]
+        &[
+            "Dashboard",
+            "Feed",
+            "Workspace",
+            "Channels",
+            "Gateways",
+            "Schedules",
+            "Logs",
+        ]
     }
 
     /// Handle a TUI event and return whether to continue
@@ -244,8 +283,29 @@ impl TuiApp {
                 self.add_feed_message(msg);
                 EventResult::Continue
             }
-            TuiEvent::PlatformStatus { name, connected } => {
-                self.update_platform_status(name, connected);
+            TuiEvent::PlatformStatus {
+                name,
+                connected,
+                reason,
+            } => {
+                self.update_platform_status(name, connected, reason);
+                EventResult::Continue
+            }
+            TuiEvent::BridgeReady(result) => {
+                match result {
+                    Ok(bridge) => {
+                        self.chat_channels = bridge.channels();
+                        self.chat_status = None;
+                    }
+                    Err(e) => self.chat_status = Some(format!("Chat unavailable: {}", e)),
+                }
+                EventResult::Continue
+            }
+            TuiEvent::ChatResponse {
+                channel_name,
+                update,
+            } => {
+                self.apply_chat_update(&channel_name, update);
                 EventResult::Continue
             }
         }
@@ -269,7 +329,10 @@ impl TuiApp {
                     self.input_mode = false;
                 }
                 KeyCode::Enter => {
-                    // Submit input (handled by workspace/chat views later)
+                    if matches!(self.view, View::Channels) {
+                        return self.submit_chat();
+                    }
+                    // Submit input (handled by the workspace view later)
                     self.input_buffer.clear();
                 }
                 KeyCode::Backspace => {
@@ -283,6 +346,27 @@ impl TuiApp {
             return EventResult::Continue;
         }
 
+        // The chat view's channel picker and open chat take a few keys first
+        if matches!(self.view, View::Channels) {
+            match (key.code, self.chat_channel_name.is_some()) {
+                (KeyCode::Tab, false) if !self.chat_channels.is_empty() => {
+                    self.chat_channel_selected =
+                        (self.chat_channel_selected + 1) % self.chat_channels.len();
+                    return EventResult::Continue;
+                }
+                (KeyCode::Enter, false) if !self.chat_channels.is_empty() => {
+                    self.open_chat(self.chat_channel_selected);
+                    return EventResult::Continue;
+                }
+                (KeyCode::Esc, true) => {
+                    // Back to the sidebar and channel picker
+                    self.chat_channel_name = None;
+                    return EventResult::Continue;
+                }
+                _ => {}
+            }
+        }
+
         // Navigation keybindings (not in input mode)
         match key.code {
             KeyCode::Char('d') | KeyCode::Char('D') => {
@@ -326,9 +410,12 @@ impl TuiApp {
                 self.navigate_to_selected();
             }
             KeyCode::Char('i') => {
-                // Enter input mode (for workspace/chat views)
-                if matches!(self.view, View::Workspace { .. } | View::Channels) {
-                    self.input_mode = true;
+                // Enter input mode (for workspace and open chat views)
+                let chat_open = self.chat_channel_name.is_some();
+                match self.view {
+                    View::Workspace { .. } => self.input_mode = true,
+                    View::Channels if chat_open => self.input_mode = true,
+                    _ => {}
                 }
             }
             KeyCode::Char('1') => {
@@ -386,52 +473,48 @@ impl TuiApp {
                     _ => {}
                 }
             }
-            KeyCode::PageUp => {
-                match &self.view {
-                    View::Feed => {
-                        self.feed_scroll = self.feed_scroll.saturating_sub(10);
-                    }
-                    View::Workspace { .. } => {
-                        self.conversation_scroll = self.conversation_scroll.saturating_sub(10);
-                    }
-                    View::Channels => {
-                        self.chat_scroll = self.chat_scroll.saturating_sub(10);
-                    }
-                    View::Logs => {
-                        self.log_scroll = self.log_scroll.saturating_sub(10);
-                    }
-                    _ => {}
+            KeyCode::PageUp => match &self.view {
+                View::Feed => {
+                    self.feed_scroll = self.feed_scroll.saturating_sub(10);
                 }
-            }
-            KeyCode::PageDown => {
-                match &self.view {
-                    View::Feed => {
-                        self.feed_scroll = self
-                            .feed_scroll
-                            .saturating_add(10)
-                            .min(self.feed_messages.len().saturating_sub(1));
-                    }
-                    View::Workspace { .. } => {
-                        self.conversation_scroll = self
-                            .conversation_scroll
-                            .saturating_add(10)
-                            .min(self.conversation_messages.len().saturating_sub(1));
-                    }
-                    View::Channels => {
-                        self.chat_scroll = self
-                            .chat_scroll
-                            .saturating_add(10)
-                            .min(self.chat_messages.len().saturating_sub(1));
-                    }
-                    View::Logs => {
-                        self.log_scroll = self
-                            .log_scroll
-                            .saturating_add(10)
-                            .min(self.log_entries.len().saturating_sub(1));
-                    }
-                    _ => {}
+                View::Workspace { .. } => {
+                    self.conversation_scroll = self.conversation_scroll.saturating_sub(10);
                 }
-            }
+                View::Channels => {
+                    self.chat_scroll = self.chat_scroll.saturating_sub(10);
+                }
+                View::Logs => {
+                    self.log_scroll = self.log_scroll.saturating_sub(10);
+                }
+                _ => {}
+            },
+            KeyCode::PageDown => match &self.view {
+                View::Feed => {
+                    self.feed_scroll = self
+                        .feed_scroll
+                        .saturating_add(10)
+                        .min(self.feed_messages.len().saturating_sub(1));
+                }
+                View::Workspace { .. } => {
+                    self.conversation_scroll = self
+                        .conversation_scroll
+                        .saturating_add(10)
+                        .min(self.conversation_messages.len().saturating_sub(1));
+                }
+                View::Channels => {
+                    self.chat_scroll = self
+                        .chat_scroll
+                        .saturating_add(10)
+                        .min(self.chat_messages.len().saturating_sub(1));
+                }
+                View::Logs => {
+                    self.log_scroll = self
+                        .log_scroll
+                        .saturating_add(10)
+                        .min(self.log_entries.len().saturating_sub(1));
+                }
+                _ => {}
+            },
             _ => {}
         }
 
@@ -462,6 +545,77 @@ impl TuiApp {
             .map(|ws| ws.name.as_str())
     }
 
+    /// Open the picker's `index`th channel in the chat view, ready to type
+    fn open_chat(&mut self, index: usize) {
+        let Some(channel) = self.chat_channels.get(index) else {
+            return;
+        };
+        self.chat_channel_name = Some(channel.name.clone());
+        self.chat_messages.clear();
+        self.chat_scroll = 0;
+        self.input_mode = true;
+    }
+
+    /// Platform of the channel open in the chat view
+    fn chat_platform_id(&self) -> String {
+        self.chat_channels
+            .iter()
+            .find(|ch| Some(&ch.name) == self.chat_channel_name.as_ref())
+            .map(|ch| ch.platform_id.clone())
+            .unwrap_or_default()
+    }
+
+    /// Show the typed prompt and an empty response for it to stream into, then
+    /// hand the prompt to the run loop to send
+    fn submit_chat(&mut self) -> EventResult {
+        let body = self.input_buffer.trim().to_string();
+        let Some(channel_name) = self.chat_channel_name.clone() else {
+            return EventResult::Continue;
+        };
+        // One prompt at a time; the typed text waits in the box
+        if body.is_empty() || self.chat_streaming {
+            return EventResult::Continue;
+        }
+        self.input_buffer.clear();
+
+        let platform_id = self.chat_platform_id();
+        let timestamp = chrono::Utc::now().timestamp();
+        for (sender, text) in [(CHAT_USER, body.clone()), (CHAT_AGENT, String::new())] {
+            self.chat_messages.push(ChatMessage {
+                platform_id: platform_id.clone(),
+                sender: sender.to_string(),
+                body: text,
+                timestamp,
+            });
+        }
+        self.chat_streaming = true;
+        self.chat_scroll = self.chat_messages.len();
+        EventResult::SendChat { channel_name, body }
+    }
+
+    /// Fold streamed response progress into the open chat's last message
+    fn apply_chat_update(&mut self, channel_name: &str, update: ChatUpdate) {
+        if matches!(update, ChatUpdate::Done(_) | ChatUpdate::Failed(_)) {
+            self.chat_streaming = false;
+        }
+        if self.chat_channel_name.as_deref() != Some(channel_name) {
+            return;
+        }
+        let Some(last) = self
+            .chat_messages
+            .last_mut()
+            .filter(|msg| msg.sender == CHAT_AGENT)
+        else {
+            return;
+        };
+        match update {
+            ChatUpdate::Chunk(text) => last.body.push_str(&text),
+            ChatUpdate::Done(text) => last.body = text,
+            ChatUpdate::Failed(reason) => last.body = format!("⚠️ {}", reason),
+        }
+        self.chat_scroll = self.chat_messages.len();
+    }
+
     /// Add a message to the feed
     fn add_feed_message(&mut self, msg: FeedMessage) {
         if self.feed_messages.len() >= MAX_FEED_MESSAGES {
@@ -471,21 +625,27 @@ impl TuiApp {
     }
 
     /// Update platform connection status
-    fn update_platform_status(&mut self, name: String, connected: bool) {
+    fn update_platform_status(&mut self, name: String, connected: bool, reason: Option<String>) {
         if let Some(status) = self.platform_statuses.iter_mut().find(|s| s.name == name) {
             status.connected = connected;
         } else {
-            self.platform_statuses
-                .push(PlatformStatus { name: name.clone(), connected });
+            self.platform_statuses.push(PlatformStatus {
+                name: name.clone(),
+                connected,
+            });
         }
 
         // Keep gateway_infos in sync
-        let state_text = if connected {
-            "Connected".to_string()
-        } else {
-            "Disconnected".to_string()
+        let state_text = match (connected, reason) {
+            (true, _) => "Connected".to_string(),
+            (false, Some(reason)) => format!("Disconnected: {}", reason),
+            (false, None) => "Disconnected".to_string(),
         };
-        if let Some(gw) = self.gateway_infos.iter_mut().find(|g| g.platform_id == name) {
+        if let Some(gw) = self
+            .gateway_infos
+            .iter_mut()
+            .find(|g| g.platform_id == name)
+        {
             gw.connected = connected;
             gw.state_text = state_text;
         } else {
@@ -547,11 +707,7 @@ impl TuiApp {
         };
 
         let status = Paragraph::new(format!(" {} | {} ", mode_indicator, help_text))
-            .style(
-                Style::default()
-                    .fg(Color::White)
-                    .bg(theme::STATUS_BAR_BG),
-            );
+            .style(Style::default().fg(Color::White).bg(theme::STATUS_BAR_BG));
 
         frame.render_widget(status, area);
     }
@@ -669,13 +825,25 @@ mod tests {
     #[test]
     fn test_platform_status_update() {
         let mut app = TuiApp::new();
-        app.update_platform_status("matrix".to_string(), true);
+        app.update_platform_status("matrix".to_string(), true, None);
         assert_eq!(app.platform_statuses.len(), 1);
         assert!(app.platform_statuses[0].connected);
 
-        app.update_platform_status("matrix".to_string(), false);
+        app.update_platform_status(
+            "matrix".to_string(),
+            false,
+            Some("sync timed out".to_string()),
+        );
         assert_eq!(app.platform_statuses.len(), 1);
         assert!(!app.platform_statuses[0].connected);
+        assert_eq!(
+            app.gateway_infos
+                .iter()
+                .find(|g| g.platform_id == "matrix")
+                .unwrap()
+                .state_text,
+            "Disconnected: sync timed out"
+        );
     }
 
     #[test]
@@ -730,6 +898,64 @@ mod tests {
         assert_eq!(app.input_buffer, "q");
     }
 
+    #[test]
+    fn test_chat_sends_and_streams_response() {
+        let mut app = TuiApp::new();
+        app.view = View::Channels;
+        app.chat_channels = vec![
+            ChannelInfo {
+                name: "news".to_string(),
+                platform_id: "matrix".to_string(),
+            },
+            ChannelInfo {
+                name: "research".to_string(),
+                platform_id: "telegram".to_string(),
+            },
+        ];
+        let key = |code| TuiEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        // Tab picks the next channel and Enter opens it, ready to type
+        app.handle_event(key(KeyCode::Tab));
+        app.handle_event(key(KeyCode::Enter));
+        assert_eq!(app.chat_channel_name.as_deref(), Some("research"));
+        assert!(app.input_mode);
+
+        for c in "hi".chars() {
+            app.handle_event(key(KeyCode::Char(c)));
+        }
+        match app.handle_event(key(KeyCode::Enter)) {
+            EventResult::SendChat { channel_name, body } => {
+                assert_eq!(channel_name, "research");
+                assert_eq!(body, "hi");
+            }
+            _ => panic!("expected the prompt to be sent"),
+        }
+        // The prompt shows at once, with an empty response to stream into
+        assert_eq!(app.chat_messages.len(), 2);
+        assert_eq!(app.chat_messages[0].sender, CHAT_USER);
+        assert_eq!(app.chat_messages[0].platform_id, "telegram");
+        assert!(app.input_buffer.is_empty());
+        assert!(app.chat_streaming);
+
+        let update = |update| TuiEvent::ChatResponse {
+            channel_name: "research".to_string(),
+            update,
+        };
+        app.handle_event(update(ChatUpdate::Chunk("Hel".to_string())));
+        app.handle_event(update(ChatUpdate::Chunk("lo".to_string())));
+        assert_eq!(app.chat_messages[1].body, "Hello");
+        app.handle_event(update(ChatUpdate::Done("Hello!".to_string())));
+        assert_eq!(app.chat_messages[1].body, "Hello!");
+        assert!(!app.chat_streaming);
+
+        // Esc leaves typing, then closes the chat
+        app.handle_event(key(KeyCode::Esc));
+        assert!(!app.input_mode);
+        assert!(app.chat_channel_name.is_some());
+        app.handle_event(key(KeyCode::Esc));
+        assert!(app.chat_channel_name.is_none());
+    }
+
     #[test]
     fn test_nav_items_count() {
         assert_eq!(TuiApp::nav_items().len(), 7);
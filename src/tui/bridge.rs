@@ -0,0 +1,139 @@
+// ABOUTME: Bridge from the TUI chat view to gorp's agent pipeline
+// ABOUTME: Sends typed prompts through handle_text and streams the response back as TuiEvents
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use gorp_core::traits::{MessageContent, MessagingPlatform};
+use tokio::sync::mpsc;
+
+use super::app::ChannelInfo;
+use super::event::{ChatUpdate, TuiEvent};
+use crate::config::Config;
+use crate::server::ServerState;
+use crate::usage::InvocationOrigin;
+
+/// Platforms whose channels the chat view lists
+const PLATFORM_IDS: &[&str] = &["matrix", "telegram", "slack", "whatsapp", "irc", "zulip"];
+
+/// The backend the chat view talks to: the same server state `gorp start` runs on
+#[derive(Debug)]
+pub struct ChatBridge {
+    state: Arc<ServerState>,
+}
+
+impl ChatBridge {
+    /// Load the config and bring up the server state, Matrix login included
+    pub async fn connect() -> Result<Self> {
+        dotenvy::dotenv().ok();
+        let config = Config::load()?;
+        let state = ServerState::initialize(config).await?;
+        Ok(Self {
+            state: Arc::new(state),
+        })
+    }
+
+    /// Every channel the chat view can open, grouped by platform
+    pub fn channels(&self) -> Vec<ChannelInfo> {
+        let mut channels = Vec::new();
+        for platform_id in PLATFORM_IDS {
+            match self.state.session_store.list_on(platform_id) {
+                Ok(found) => channels.extend(
+                    found
+                        .into_iter()
+                        .filter(|ch| !ch.is_dispatch_room)
+                        .map(|ch| ChannelInfo {
+                            name: ch.channel_name,
+                            platform_id: platform_id.to_string(),
+                        }),
+                ),
+                Err(e) => tracing::warn!(error = %e, platform_id, "Failed to list channels"),
+            }
+        }
+        channels
+    }
+
+    /// Post `body` to the channel and run it past the channel's agent, sending
+    /// the response to `tx` as it streams in
+    pub fn send(&self, channel_name: String, body: String, tx: mpsc::Sender<TuiEvent>) {
+        let state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            let update = match run_prompt(&state, &channel_name, &body, &tx).await {
+                Ok(response) => ChatUpdate::Done(response),
+                Err(e) => ChatUpdate::Failed(format!("{:#}", e)),
+            };
+            let _ = tx
+                .send(TuiEvent::ChatResponse {
+                    channel_name,
+                    update,
+                })
+                .await;
+        });
+    }
+}
+
+/// Handle one prompt typed into the chat view, returning the finished response
+async fn run_prompt(
+    state: &ServerState,
+    channel_name: &str,
+    body: &str,
+    tx: &mpsc::Sender<TuiEvent>,
+) -> Result<String> {
+    let channel = state
+        .session_store
+        .get_by_name(channel_name)?
+        .ok_or_else(|| anyhow::anyhow!("Channel '{}' no longer exists", channel_name))?;
+
+    // People in the room see what was asked from the terminal, and the answer
+    let platform = state
+        .matrix_client
+        .clone()
+        .filter(|_| channel.room_id.starts_with('!'))
+        .map(crate::platform::matrix::MatrixPlatform::new);
+    if let Some(platform) = &platform {
+        platform
+            .send(
+                &channel.room_id,
+                MessageContent::plain(format!("💻 {}", body)),
+            )
+            .await?;
+    }
+
+    let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
+    let forward_tx = tx.clone();
+    let forward_name = channel_name.to_string();
+    let forward = tokio::spawn(async move {
+        while let Some(chunk) = chunk_rx.recv().await {
+            let event = TuiEvent::ChatResponse {
+                channel_name: forward_name.clone(),
+                update: ChatUpdate::Chunk(chunk),
+            };
+            if forward_tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let response = crate::message_handler::handle_text_streaming(
+        body,
+        &channel,
+        &state.session_store,
+        &state.warm_manager,
+        InvocationOrigin::User,
+        chunk_tx,
+    )
+    .await;
+    // The sender is gone once handle_text_streaming returns, so this ends promptly
+    let _ = forward.await;
+    let response = response?;
+
+    if let Some(platform) = &platform {
+        if let Err(e) = platform
+            .send(&channel.room_id, MessageContent::plain(&response))
+            .await
+        {
+            tracing::warn!(error = %e, channel = %channel_name, "Failed to post TUI response");
+        }
+    }
+    Ok(response)
+}
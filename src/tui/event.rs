@@ -2,10 +2,13 @@
 // ABOUTME: Three async event sources feed into a single mpsc channel
 
 use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+use gorp_core::{ConnectionEvent, PlatformConnectionState};
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 
 use super::app::FeedMessage;
+use super::bridge::ChatBridge;
 
 // =============================================================================
 // TuiEvent — unified event type for the TUI event loop
@@ -19,8 +22,30 @@ pub enum TuiEvent {
     Tick,
     /// Incoming message from any platform
     PlatformMessage(FeedMessage),
-    /// Platform connection status change
-    PlatformStatus { name: String, connected: bool },
+    /// Platform connection status change, with the reason on disconnects
+    PlatformStatus {
+        name: String,
+        connected: bool,
+        reason: Option<String>,
+    },
+    /// The chat bridge finished starting up, or the reason it couldn't
+    BridgeReady(Result<Arc<ChatBridge>, String>),
+    /// Progress on the response to a prompt sent from the chat view
+    ChatResponse {
+        channel_name: String,
+        update: ChatUpdate,
+    },
+}
+
+/// A step in an agent response streaming into the chat view
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatUpdate {
+    /// More response text as the agent writes it
+    Chunk(String),
+    /// The finished response
+    Done(String),
+    /// Why the prompt got no response
+    Failed(String),
 }
 
 // =============================================================================
@@ -35,7 +60,8 @@ pub fn spawn_event_tasks(tx: mpsc::Sender<TuiEvent>) {
     // Tick task (100ms render interval)
     spawn_tick_task(tx);
 
-    // Platform event bridging is handled externally when PlatformRegistry is wired
+    // Platform event bridging is handled externally when PlatformRegistry is wired;
+    // spawn_connection_task bridges its connection events
 }
 
 /// Spawn keyboard input polling task
@@ -65,6 +91,34 @@ fn spawn_keyboard_task(tx: mpsc::Sender<TuiEvent>) {
     });
 }
 
+/// Forward platform connection changes, such as those from
+/// `PlatformRegistry::subscribe_connection_events`, as status events
+pub fn spawn_connection_task(
+    tx: mpsc::Sender<TuiEvent>,
+    mut events: broadcast::Receiver<ConnectionEvent>,
+) {
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if tx.send(status_event(&event)).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn status_event(event: &ConnectionEvent) -> TuiEvent {
+    TuiEvent::PlatformStatus {
+        name: event.platform_id.clone(),
+        connected: event.state == PlatformConnectionState::Connected,
+        reason: event.reason().map(str::to_string),
+    }
+}
+
 /// Spawn tick task for periodic re-renders
 fn spawn_tick_task(tx: mpsc::Sender<TuiEvent>) {
     tokio::spawn(async move {
@@ -112,7 +166,30 @@ mod tests {
         let event = TuiEvent::PlatformStatus {
             name: "matrix".to_string(),
             connected: true,
+            reason: None,
         };
         assert!(format!("{:?}", event).contains("PlatformStatus"));
     }
+
+    #[test]
+    fn test_connection_event_becomes_status_event() {
+        let event = status_event(&ConnectionEvent {
+            platform_id: "slack".to_string(),
+            state: PlatformConnectionState::Disconnected {
+                reason: "socket closed".to_string(),
+            },
+        });
+        match event {
+            TuiEvent::PlatformStatus {
+                name,
+                connected,
+                reason,
+            } => {
+                assert_eq!(name, "slack");
+                assert!(!connected);
+                assert_eq!(reason.as_deref(), Some("socket closed"));
+            }
+            other => panic!("expected a status event, got {:?}", other),
+        }
+    }
 }
@@ -2,6 +2,7 @@
 // ABOUTME: Provides run_tui() for starting the terminal interface via `gorp tui`
 
 pub mod app;
+pub mod bridge;
 pub mod event;
 pub mod sidebar;
 pub mod theme;
@@ -15,6 +16,7 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 use std::io;
+use std::sync::Arc;
 
 /// Run the TUI application. Entry point for `gorp tui` command.
 pub async fn run_tui() -> Result<()> {
@@ -51,11 +53,7 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
 /// Restore the terminal to its original state
 fn restore_terminal() -> Result<()> {
     disable_raw_mode()?;
-    execute!(
-        io::stdout(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
     Ok(())
 }
 
@@ -63,9 +61,20 @@ fn restore_terminal() -> Result<()> {
 async fn run_app(mut terminal: Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
     let (event_tx, mut event_rx) = tokio::sync::mpsc::channel(256);
     let mut app = app::TuiApp::new();
+    let mut chat_bridge: Option<Arc<bridge::ChatBridge>> = None;
 
     // Start event collection tasks
-    event::spawn_event_tasks(event_tx);
+    event::spawn_event_tasks(event_tx.clone());
+
+    // Bring up the chat backend without holding up the first frame
+    let ready_tx = event_tx.clone();
+    tokio::spawn(async move {
+        let result = bridge::ChatBridge::connect()
+            .await
+            .map(Arc::new)
+            .map_err(|e| format!("{:#}", e));
+        let _ = ready_tx.send(event::TuiEvent::BridgeReady(result)).await;
+    });
 
     loop {
         // Render
@@ -73,9 +82,23 @@ async fn run_app(mut terminal: Terminal<CrosstermBackend<io::Stdout>>) -> Result
 
         // Handle events
         if let Some(event) = event_rx.recv().await {
+            if let event::TuiEvent::BridgeReady(Ok(ready)) = &event {
+                chat_bridge = Some(Arc::clone(ready));
+            }
             match app.handle_event(event) {
                 app::EventResult::Continue => {}
                 app::EventResult::Quit => break,
+                app::EventResult::SendChat { channel_name, body } => match &chat_bridge {
+                    Some(chat_bridge) => chat_bridge.send(channel_name, body, event_tx.clone()),
+                    None => {
+                        app.handle_event(event::TuiEvent::ChatResponse {
+                            channel_name,
+                            update: event::ChatUpdate::Failed(
+                                "gorp isn't connected yet".to_string(),
+                            ),
+                        });
+                    }
+                },
             }
         } else {
             // All event senders dropped
@@ -12,7 +12,10 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &TuiApp) {
     // Split sidebar into nav section and platform status section
     let layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(10), Constraint::Length(app.platform_statuses.len() as u16 + 3)])
+        .constraints([
+            Constraint::Min(10),
+            Constraint::Length(app.platform_statuses.len() as u16 + 3),
+        ])
         .split(area);
 
     render_navigation(frame, layout[0], app);
@@ -45,7 +48,11 @@ fn render_navigation(frame: &mut Frame, area: Rect, app: &TuiApp) {
     let nav_block = Block::default()
         .borders(Borders::ALL)
         .title(" gorp ")
-        .title_style(Style::default().fg(theme::NAV_HEADER).add_modifier(Modifier::BOLD))
+        .title_style(
+            Style::default()
+                .fg(theme::NAV_HEADER)
+                .add_modifier(Modifier::BOLD),
+        )
         .border_style(Style::default().fg(theme::BORDER_COLOR));
 
     let nav_list = List::new(nav_items).block(nav_block);
@@ -113,6 +120,10 @@ mod tests {
         let mut unique = shortcuts.clone();
         unique.sort();
         unique.dedup();
-        assert_eq!(shortcuts.len(), unique.len(), "Navigation shortcuts must be unique");
+        assert_eq!(
+            shortcuts.len(),
+            unique.len(),
+            "Navigation shortcuts must be unique"
+        );
     }
 }
@@ -4,11 +4,16 @@
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
 
-use crate::tui::app::TuiApp;
+use crate::tui::app::{TuiApp, CHAT_AGENT};
 use crate::tui::theme;
 
 /// Render the chat view (platform channel conversation) in the given area
 pub fn render_chat(frame: &mut Frame, area: Rect, app: &TuiApp) {
+    if app.chat_channel_name.is_none() {
+        render_channel_picker(frame, area, app);
+        return;
+    }
+
     // Split into conversation and input
     let layout = Layout::default()
         .direction(Direction::Vertical)
@@ -22,11 +27,65 @@ pub fn render_chat(frame: &mut Frame, area: Rect, app: &TuiApp) {
     render_chat_input(frame, layout[1], app);
 }
 
+/// Render the list of channels to open, with the picked one highlighted
+fn render_channel_picker(frame: &mut Frame, area: Rect, app: &TuiApp) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Chat — pick a channel ")
+        .border_style(Style::default().fg(theme::BORDER_COLOR));
+
+    let hint = match &app.chat_status {
+        Some(status) => Some(format!("  {}", status)),
+        None if app.chat_channels.is_empty() => {
+            Some("  No channels yet. Create one with !create.".to_string())
+        }
+        None => None,
+    };
+    if let Some(hint) = hint {
+        let paragraph = Paragraph::new(hint)
+            .style(Style::default().fg(theme::DIM_TEXT))
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let mut items: Vec<ListItem> = app
+        .chat_channels
+        .iter()
+        .enumerate()
+        .map(|(i, channel)| {
+            let style = if i == app.chat_channel_selected {
+                Style::default()
+                    .fg(theme::NAV_HEADER)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT_COLOR)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("[{}] ", channel.platform_id),
+                    Style::default().fg(theme::platform_color(&channel.platform_id)),
+                ),
+                Span::styled(format!("#{}", channel.name), style),
+            ]))
+        })
+        .collect();
+    items.push(ListItem::new(""));
+    items.push(ListItem::new(Span::styled(
+        "  Tab: next channel | Enter: open",
+        Style::default().fg(theme::DIM_TEXT),
+    )));
+
+    frame.render_widget(List::new(items).block(block), area);
+}
+
 /// Render the chat message history
 fn render_chat_messages(frame: &mut Frame, area: Rect, app: &TuiApp) {
     let title = match &app.chat_channel_name {
+        Some(name) if app.chat_streaming => format!(" #{} — agent is responding… ", name),
         Some(name) => format!(" #{} ", name),
-        None => " Chat — select a channel from Feed ".to_string(),
+        None => " Chat ".to_string(),
     };
 
     let block = Block::default()
@@ -35,12 +94,7 @@ fn render_chat_messages(frame: &mut Frame, area: Rect, app: &TuiApp) {
         .border_style(Style::default().fg(theme::BORDER_COLOR));
 
     if app.chat_messages.is_empty() {
-        let hint = if app.chat_channel_name.is_some() {
-            "  No messages yet. Press i to start typing."
-        } else {
-            "  Select a message in Feed and press Enter to open its channel."
-        };
-        let paragraph = Paragraph::new(hint)
+        let paragraph = Paragraph::new("  No messages yet. Press i to start typing.")
             .style(Style::default().fg(theme::DIM_TEXT))
             .block(block);
         frame.render_widget(paragraph, area);
@@ -48,20 +102,21 @@ fn render_chat_messages(frame: &mut Frame, area: Rect, app: &TuiApp) {
     }
 
     let visible_height = area.height.saturating_sub(2) as usize;
-    let total = app.chat_messages.len();
-    let scroll = app
-        .chat_scroll
-        .min(total.saturating_sub(visible_height));
 
     let items: Vec<ListItem> = app
         .chat_messages
         .iter()
-        .skip(scroll)
-        .take(visible_height)
         .map(|msg| {
             let platform_color = theme::platform_color(&msg.platform_id);
-
-            let line = Line::from(vec![
+            // A response that hasn't started arriving yet
+            let body = if msg.body.is_empty() && msg.sender == CHAT_AGENT {
+                "…"
+            } else {
+                msg.body.as_str()
+            };
+            let mut body_lines = body.lines();
+
+            let mut lines = vec![Line::from(vec![
                 Span::styled(
                     format!("[{}] ", msg.platform_id),
                     Style::default().fg(platform_color),
@@ -73,19 +128,41 @@ fn render_chat_messages(frame: &mut Frame, area: Rect, app: &TuiApp) {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
-                    &msg.body,
+                    body_lines.next().unwrap_or_default(),
                     Style::default().fg(theme::TEXT_COLOR),
                 ),
-            ]);
+            ])];
+            // Later lines of a multi-line message sit under the first
+            lines.extend(body_lines.map(|line| {
+                Line::from(Span::styled(
+                    format!("  {}", line),
+                    Style::default().fg(theme::TEXT_COLOR),
+                ))
+            }));
 
-            ListItem::new(line)
+            ListItem::new(lines)
         })
         .collect();
+    let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
+    let scroll = first_visible(&heights, app.chat_scroll, visible_height);
 
-    let list = List::new(items).block(block);
+    let list = List::new(items.into_iter().skip(scroll).collect::<Vec<_>>()).block(block);
     frame.render_widget(list, area);
 }
 
+/// Index of the first message to show when scrolled to `scroll`, stopping
+/// once the last message reaches the bottom of `visible` lines
+fn first_visible(heights: &[usize], scroll: usize, visible: usize) -> usize {
+    let mut first = heights.len();
+    let mut used = 0;
+    while first > 0 && used + heights[first - 1] <= visible {
+        first -= 1;
+        used += heights[first];
+    }
+    // A last message taller than the view still shows its start
+    scroll.min(first.min(heights.len().saturating_sub(1)))
+}
+
 /// Render the chat input area
 fn render_chat_input(frame: &mut Frame, area: Rect, app: &TuiApp) {
     if app.input_mode {
@@ -102,12 +179,9 @@ fn render_chat_input(frame: &mut Frame, area: Rect, app: &TuiApp) {
 
         frame.render_widget(paragraph, area);
     } else {
-        let hint = " i: type message | PgUp/PgDn: scroll | Esc: back to Feed";
-        let paragraph = Paragraph::new(hint).style(
-            Style::default()
-                .fg(Color::White)
-                .bg(theme::STATUS_BAR_BG),
-        );
+        let hint = " i: type message | PgUp/PgDn: scroll | Esc: close chat";
+        let paragraph =
+            Paragraph::new(hint).style(Style::default().fg(Color::White).bg(theme::STATUS_BAR_BG));
         frame.render_widget(paragraph, area);
     }
 }
@@ -125,4 +199,17 @@ mod tests {
         // Verify the render function signature compiles
         let _f: fn(&mut Frame, Rect, &TuiApp) = render_chat;
     }
+
+    #[test]
+    fn test_first_visible_keeps_the_latest_lines_in_view() {
+        // Scrolled to the end, the last messages fill the view
+        assert_eq!(first_visible(&[1, 1, 3, 2], usize::MAX, 5), 2);
+        // Scrolled back, the chosen message comes first
+        assert_eq!(first_visible(&[1, 1, 3, 2], 1, 5), 1);
+        // Everything fits
+        assert_eq!(first_visible(&[1, 2], usize::MAX, 10), 0);
+        // A response taller than the view still shows
+        assert_eq!(first_visible(&[1, 20], usize::MAX, 5), 1);
+        assert_eq!(first_visible(&[], 3, 5), 0);
+    }
 }
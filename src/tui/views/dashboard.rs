@@ -13,7 +13,7 @@ pub fn render_dashboard(frame: &mut Frame, area: Rect, app: &TuiApp) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),  // Header with version and uptime
+            Constraint::Length(5), // Header with version and uptime
             Constraint::Length(app.platform_statuses.len() as u16 + 4), // Platform status
             Constraint::Min(6),    // Recent activity / stats
         ])
@@ -30,8 +30,16 @@ fn render_header(frame: &mut Frame, area: Rect, app: &TuiApp) {
 
     let text = vec![
         Line::from(vec![
-            Span::styled("gorp ", Style::default().fg(theme::NAV_HEADER).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("v{}", env!("CARGO_PKG_VERSION")), Style::default().fg(theme::DIM_TEXT)),
+            Span::styled(
+                "gorp ",
+                Style::default()
+                    .fg(theme::NAV_HEADER)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("v{}", env!("CARGO_PKG_VERSION")),
+                Style::default().fg(theme::DIM_TEXT),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -88,10 +96,15 @@ fn render_platforms(frame: &mut Frame, area: Rect, app: &TuiApp) {
             };
 
             Row::new(vec![
-                Cell::from(Span::styled(indicator, Style::default().fg(indicator_color))),
+                Cell::from(Span::styled(
+                    indicator,
+                    Style::default().fg(indicator_color),
+                )),
                 Cell::from(Span::styled(
                     status.name.clone(),
-                    Style::default().fg(platform_color).add_modifier(Modifier::BOLD),
+                    Style::default()
+                        .fg(platform_color)
+                        .add_modifier(Modifier::BOLD),
                 )),
                 Cell::from(Span::styled(
                     state_text,
@@ -149,7 +162,9 @@ fn render_stats(frame: &mut Frame, area: Rect, app: &TuiApp) {
                 ),
                 Span::styled(
                     format!("{}: ", msg.sender),
-                    Style::default().fg(theme::TEXT_COLOR).add_modifier(Modifier::BOLD),
+                    Style::default()
+                        .fg(theme::TEXT_COLOR)
+                        .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     truncate_str(&msg.body, 60),
@@ -233,7 +248,10 @@ mod tests {
 
     #[test]
     fn test_truncate_str_long() {
-        assert_eq!(truncate_str("hello world, this is a long message", 15), "hello world,...");
+        assert_eq!(
+            truncate_str("hello world, this is a long message", 15),
+            "hello world,..."
+        );
     }
 
     #[test]
@@ -122,11 +122,7 @@ fn render_filter_bar(frame: &mut Frame, area: Rect, app: &TuiApp) {
         "{}j/k: scroll | g/G: top/bottom | r: reply | Enter: open",
         filter_text,
     ))
-    .style(
-        Style::default()
-            .fg(Color::White)
-            .bg(theme::STATUS_BAR_BG),
-    );
+    .style(Style::default().fg(Color::White).bg(theme::STATUS_BAR_BG));
 
     frame.render_widget(status, area);
 }
@@ -172,7 +168,10 @@ mod tests {
 
     #[test]
     fn test_truncate_body_newlines() {
-        assert_eq!(truncate_body("line1\nline2\nline3", 50), "line1 line2 line3");
+        assert_eq!(
+            truncate_body("line1\nline2\nline3", 50),
+            "line1 line2 line3"
+        );
     }
 
     #[test]
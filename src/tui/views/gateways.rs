@@ -23,10 +23,7 @@ pub fn render_gateways(frame: &mut Frame, area: Rect, app: &TuiApp) {
     }
 
     let header = Row::new(vec![
-        Cell::from(Span::styled(
-            "  ",
-            Style::default().fg(theme::DIM_TEXT),
-        )),
+        Cell::from(Span::styled("  ", Style::default().fg(theme::DIM_TEXT))),
         Cell::from(Span::styled(
             "Platform",
             Style::default()
@@ -40,7 +40,9 @@ fn render_log_list(frame: &mut Frame, area: Rect, app: &TuiApp) {
     let title = if total > visible_height {
         format!(
             " Logs [level: {}] [{}/{}] ",
-            app.log_level_filter, scroll + 1, total
+            app.log_level_filter,
+            scroll + 1,
+            total
         )
     } else {
         format!(" Logs [level: {}] [{}] ", app.log_level_filter, total)
@@ -113,11 +115,8 @@ fn render_filter_bar(frame: &mut Frame, area: Rect, app: &TuiApp) {
         app.log_level_filter, ws_filter,
     );
 
-    let bar = Paragraph::new(text).style(
-        Style::default()
-            .fg(Color::White)
-            .bg(theme::STATUS_BAR_BG),
-    );
+    let bar =
+        Paragraph::new(text).style(Style::default().fg(Color::White).bg(theme::STATUS_BAR_BG));
     frame.render_widget(bar, area);
 }
 
@@ -251,18 +250,12 @@ mod tests {
 
     #[test]
     fn test_truncate_target_module_path() {
-        assert_eq!(
-            truncate_target("gorp::platform::matrix", 10),
-            "matrix"
-        );
+        assert_eq!(truncate_target("gorp::platform::matrix", 10), "matrix");
     }
 
     #[test]
     fn test_truncate_target_long_segment() {
-        assert_eq!(
-            truncate_target("very_long_module_name", 10),
-            "very_lo..."
-        );
+        assert_eq!(truncate_target("very_long_module_name", 10), "very_lo...");
     }
 
     #[test]
@@ -100,7 +100,10 @@ fn render_schedule_table(frame: &mut Frame, area: Rect, app: &TuiApp) {
                     format_next_run(&sched.next_run),
                     Style::default().fg(theme::DIM_TEXT),
                 )),
-                Cell::from(Span::styled(status_text, Style::default().fg(indicator_color))),
+                Cell::from(Span::styled(
+                    status_text,
+                    Style::default().fg(indicator_color),
+                )),
             ])
             .style(row_style)
         })
@@ -123,12 +126,8 @@ fn render_schedule_table(frame: &mut Frame, area: Rect, app: &TuiApp) {
 
 /// Render the action bar at the bottom
 fn render_action_bar(frame: &mut Frame, area: Rect) {
-    let actions =
-        Paragraph::new(" Enter: edit | d: toggle | n: new | x: delete | j/k: navigate").style(
-            Style::default()
-                .fg(Color::White)
-                .bg(theme::STATUS_BAR_BG),
-        );
+    let actions = Paragraph::new(" Enter: edit | d: toggle | n: new | x: delete | j/k: navigate")
+        .style(Style::default().fg(Color::White).bg(theme::STATUS_BAR_BG));
     frame.render_widget(actions, area);
 }
 
@@ -214,10 +213,7 @@ mod tests {
 
     #[test]
     fn test_truncate_prompt_whitespace() {
-        assert_eq!(
-            truncate_prompt("line1\nline2\ttab", 30),
-            "line1 line2 tab"
-        );
+        assert_eq!(truncate_prompt("line1\nline2\ttab", 30), "line1 line2 tab");
     }
 
     #[test]
@@ -227,18 +223,12 @@ mod tests {
 
     #[test]
     fn test_format_next_run_rfc3339() {
-        assert_eq!(
-            format_next_run("2026-02-15T18:00:00Z"),
-            "18:00"
-        );
+        assert_eq!(format_next_run("2026-02-15T18:00:00Z"), "18:00");
     }
 
     #[test]
     fn test_format_next_run_with_offset() {
-        assert_eq!(
-            format_next_run("2026-02-15T06:30:00+05:00"),
-            "06:30"
-        );
+        assert_eq!(format_next_run("2026-02-15T06:30:00+05:00"), "06:30");
     }
 
     #[test]
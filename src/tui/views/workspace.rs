@@ -55,7 +55,11 @@ fn render_workspace_list(frame: &mut Frame, area: Rect, app: &TuiApp) {
         .iter()
         .enumerate()
         .map(|(i, ws)| {
-            let prefix = if i == app.workspace_selected { ">" } else { " " };
+            let prefix = if i == app.workspace_selected {
+                ">"
+            } else {
+                " "
+            };
             let active = if ws.active { "*" } else { " " };
             let text = format!("{}{} {}", prefix, active, ws.name);
 
@@ -65,8 +69,7 @@ fn render_workspace_list(frame: &mut Frame, area: Rect, app: &TuiApp) {
                     .bg(theme::SELECTED_BG)
                     .add_modifier(Modifier::BOLD)
             } else if ws.active {
-                Style::default()
-                    .fg(theme::CONNECTED_COLOR)
+                Style::default().fg(theme::CONNECTED_COLOR)
             } else {
                 Style::default().fg(theme::TEXT_COLOR)
             };
@@ -133,10 +136,7 @@ fn render_conversation(frame: &mut Frame, area: Rect, app: &TuiApp) {
                         .add_modifier(Modifier::BOLD),
                     "Claude",
                 ),
-                _ => (
-                    Style::default().fg(theme::DIM_TEXT),
-                    msg.role.as_str(),
-                ),
+                _ => (Style::default().fg(theme::DIM_TEXT), msg.role.as_str()),
             };
 
             let line = Line::from(vec![
@@ -175,11 +175,8 @@ fn render_input(frame: &mut Frame, area: Rect, app: &TuiApp) {
             " i: input mode | Tab: workspaces | PgUp/PgDn: scroll"
         };
 
-        let paragraph = Paragraph::new(hint).style(
-            Style::default()
-                .fg(Color::White)
-                .bg(theme::STATUS_BAR_BG),
-        );
+        let paragraph =
+            Paragraph::new(hint).style(Style::default().fg(Color::White).bg(theme::STATUS_BAR_BG));
 
         frame.render_widget(paragraph, area);
     }
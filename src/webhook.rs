@@ -1,18 +1,22 @@
 // ABOUTME: HTTP webhook server for injecting prompts into Claude sessions
-// ABOUTME: Provides POST /webhook/session/{id} endpoint for external triggers like cron jobs
+// ABOUTME: Provides POST /webhook/session/{id} for external triggers, optionally HMAC-signed
 
 use anyhow::{Context, Result};
+#[cfg(feature = "admin")]
+use axum::response::Redirect;
 use axum::{
-    extract::{Path, State},
+    body::Body,
+    extract::{Path, Request, State},
     http::StatusCode,
-    response::IntoResponse,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-#[cfg(feature = "admin")]
-use axum::{middleware, response::Redirect};
 use chrono::Utc;
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::sync::Arc;
 use tower_http::trace::TraceLayer;
 
@@ -24,18 +28,33 @@ use crate::admin::{
 use crate::{
     bus::{BusMessage, MessageBus, MessageSource, ResponseContent, SessionTarget},
     config::Config,
+    logging::loggable_content,
     mcp::{mcp_handler, McpState},
     metrics,
+    platform::matrix::signing::Signer,
+    runtime_mode::{Gate, RuntimeMode},
     scheduler::SchedulerStore,
     session::SessionStore,
+    usage::InvocationOrigin,
+    webhook_batch::{group_key, sample_of, WebhookBatch},
 };
 use metrics_exporter_prometheus::PrometheusHandle;
 
+/// How often pending webhook batches are checked for a closed window
+const BATCH_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the raw body>` when `webhook.secret` is set
+pub const SIGNATURE_HEADER: &str = "X-Gorp-Signature";
+
+/// Largest body read to check a signature; bigger requests are refused unread
+const MAX_SIGNED_BODY_BYTES: usize = 1024 * 1024;
+
 #[derive(Clone)]
 struct WebhookState {
     session_store: SessionStore,
     bus: Arc<MessageBus>,
     config: Arc<Config>,
+    runtime: RuntimeMode,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,6 +62,12 @@ pub struct WebhookRequest {
     pub prompt: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    /// Structured data about the event; coalescing channels group deliveries by a field of it
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+    /// Answer at once: skips coalescing and the channel's delivery window
+    #[serde(default)]
+    pub urgent: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -55,30 +80,47 @@ pub struct WebhookResponse {
 pub async fn start_webhook_server(
     port: u16,
     session_store: SessionStore,
+    scheduler_store: SchedulerStore,
     bus: Arc<MessageBus>,
     config: Arc<Config>,
     registry: crate::platform::SharedPlatformRegistry,
+    warm_manager: crate::warm_session::SharedWarmSessionManager,
 ) -> Result<()> {
     // Initialize Prometheus metrics
     let metrics_handle =
         metrics::init_metrics().context("Failed to initialize Prometheus metrics")?;
+    if let Some(path) = &config.metrics.snapshot_path {
+        let interval = std::time::Duration::from_secs(config.metrics.snapshot_interval_secs.max(1));
+        tracing::info!(path = %path, interval_secs = interval.as_secs(), "Writing metrics snapshots");
+        metrics::spawn_snapshot_task(metrics_handle.clone(), path.into(), interval);
+    }
 
     let admin_bus = Arc::clone(&bus);
+    let runtime = warm_manager.read().await.runtime_mode();
+    #[cfg(not(feature = "admin"))]
+    let _ = warm_manager;
     let state = WebhookState {
         session_store,
         bus,
         config,
+        runtime,
     };
 
-    let webhook_routes = Router::new()
-        .route("/webhook/session/{session_id}", post(webhook_handler))
-        .with_state(Arc::new(state.clone()));
+    tokio::spawn(run_batch_flusher(
+        state.session_store.clone(),
+        Arc::clone(&state.bus),
+    ));
+
+    let webhook_routes = webhook_router(
+        state.session_store.clone(),
+        Arc::clone(&state.bus),
+        Arc::clone(&state.config),
+        state.runtime.clone(),
+    );
 
-    // Create scheduler store here because it needs the database connection from session_store.
     // The scheduler_store is shared between admin routes (for viewing/managing schedules)
-    // and MCP routes (for creating schedules via Claude). It must be created after
-    // session_store is available but before admin_state and mcp_state are constructed.
-    let scheduler_store = SchedulerStore::new(state.session_store.db_connection());
+    // and MCP routes (for creating schedules via Claude). It is the scheduler loop's own
+    // store, so changes made here wake the loop straight away.
 
     // Initialize gauge metrics from current state (default to 0 on error)
     let channel_count = state
@@ -123,48 +165,40 @@ pub async fn start_webhook_server(
         ws_hub: ws_hub.clone(),
         registry: Some(registry.clone()),
         bus: Some(admin_bus),
+        warm_manager: Some(warm_manager),
     };
 
-    // Spawn platform status monitor — polls registry every 5 seconds
-    // and broadcasts status changes via WebSocket
+    // Spawn platform status monitor — sends every platform's state once, then
+    // each change as the platforms report it, via WebSocket
     #[cfg(feature = "admin")]
     {
         let monitor_registry = registry.clone();
         let monitor_hub = ws_hub;
         tokio::spawn(async move {
             use crate::admin::websocket::{PlatformStatusData, ServerMessage};
-            use gorp_core::PlatformConnectionState;
-
-            let mut prev_states: std::collections::HashMap<String, String> =
-                std::collections::HashMap::new();
+            use tokio::sync::broadcast::error::RecvError;
+
+            let (mut events, health) = {
+                let registry = monitor_registry.read().await;
+                (registry.subscribe_connection_events(), registry.health())
+            };
+            let send_all = |health: Vec<crate::platform::PlatformHealth>| {
+                for h in health {
+                    monitor_hub.broadcast(ServerMessage::StatusPlatform {
+                        data: PlatformStatusData::from_state(&h.platform_id, &h.state),
+                    });
+                }
+            };
+            send_all(health);
 
             loop {
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-
-                let health = monitor_registry.read().await.health();
-                for h in &health {
-                    let state_str = match &h.state {
-                        PlatformConnectionState::Connected => "connected",
-                        PlatformConnectionState::Connecting => "connecting",
-                        PlatformConnectionState::Disconnected { .. } => "disconnected",
-                        PlatformConnectionState::AuthRequired => "auth_required",
-                        PlatformConnectionState::RateLimited { .. } => "rate_limited",
-                    };
-
-                    let changed = prev_states
-                        .get(&h.platform_id)
-                        .map_or(true, |prev| prev != state_str);
-
-                    if changed {
-                        prev_states
-                            .insert(h.platform_id.clone(), state_str.to_string());
-                        monitor_hub.broadcast(ServerMessage::StatusPlatform {
-                            data: PlatformStatusData {
-                                platform: h.platform_id.clone(),
-                                state: state_str.to_string(),
-                            },
-                        });
-                    }
+                match events.recv().await {
+                    Ok(event) => monitor_hub.broadcast(ServerMessage::StatusPlatform {
+                        data: PlatformStatusData::from_state(&event.platform_id, &event.state),
+                    }),
+                    // Missed changes: the current states replace them
+                    Err(RecvError::Lagged(_)) => send_all(monitor_registry.read().await.health()),
+                    Err(RecvError::Closed) => break,
                 }
             }
         });
@@ -189,13 +223,32 @@ pub async fn start_webhook_server(
         matrix_client: None,
         timezone: state.config.scheduler.timezone.clone(),
         workspace_path: state.config.workspace.path.clone(),
-        room_prefix: state.config.matrix.as_ref().map(|m| m.room_prefix.clone()).unwrap_or_else(|| "Claude".to_string()),
+        room_prefix: state
+            .config
+            .matrix
+            .as_ref()
+            .map(|m| m.room_prefix.clone())
+            .unwrap_or_else(|| "Claude".to_string()),
+        management_room: state
+            .config
+            .matrix
+            .as_ref()
+            .and_then(|m| m.management_room.clone()),
+        signer: Signer::from_config(&state.config.signing),
     };
 
     let mcp_routes = Router::new()
         .route("/mcp", post(mcp_handler))
         .with_state(Arc::new(mcp_state));
 
+    // Updates Telegram pushes in webhook mode, fed to the registered platform's event stream
+    #[cfg(feature = "telegram")]
+    let telegram_routes = Router::new()
+        .route("/telegram/webhook/{secret}", post(telegram_webhook_handler))
+        .with_state(registry.clone());
+    #[cfg(not(feature = "telegram"))]
+    let telegram_routes = Router::new();
+
     // Metrics endpoint - renders Prometheus text format
     let metrics_routes = Router::new()
         .route("/metrics", get(metrics_handler))
@@ -265,6 +318,7 @@ pub async fn start_webhook_server(
         .merge(ws_routes)
         .merge(mcp_routes)
         .merge(webhook_routes)
+        .merge(telegram_routes)
         .merge(metrics_routes)
         .layer(session_layer)
         .layer(TraceLayer::new_for_http());
@@ -282,6 +336,7 @@ pub async fn start_webhook_server(
         )
         .merge(mcp_routes)
         .merge(webhook_routes)
+        .merge(telegram_routes)
         .merge(metrics_routes)
         .layer(TraceLayer::new_for_http());
 
@@ -301,6 +356,130 @@ pub async fn start_webhook_server(
     Ok(())
 }
 
+/// Routes for `POST /webhook/session/{session_id}`
+pub fn webhook_router(
+    session_store: SessionStore,
+    bus: Arc<MessageBus>,
+    config: Arc<Config>,
+    runtime: RuntimeMode,
+) -> Router {
+    let state = WebhookState {
+        session_store,
+        bus,
+        config,
+        runtime,
+    };
+    let state = Arc::new(state);
+    Router::new()
+        .route("/webhook/session/{session_id}", post(webhook_handler))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            signature_middleware,
+        ))
+        .with_state(state)
+}
+
+/// `POST /telegram/webhook/{secret}`: one update pushed by Telegram in webhook mode.
+/// Anything but a 2xx makes Telegram retry, so updates the bot ignores still get 200.
+#[cfg(feature = "telegram")]
+async fn telegram_webhook_handler(
+    State(registry): State<crate::platform::SharedPlatformRegistry>,
+    Path(secret): Path<String>,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
+) -> StatusCode {
+    let registry = registry.read().await;
+    let Some(ingress) = registry
+        .get("telegram")
+        .and_then(|platform| platform.webhook_ingress())
+    else {
+        return StatusCode::NOT_FOUND;
+    };
+
+    let token = headers
+        .get(crate::platform::telegram::SECRET_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok());
+    if !ingress.verify(&secret, token) {
+        tracing::warn!(
+            platform = "telegram",
+            "Rejected webhook update with a wrong secret"
+        );
+        metrics::record_error("telegram_webhook_secret");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    match ingress.push(&body).await {
+        Ok(()) => StatusCode::OK,
+        Err(e) => {
+            tracing::warn!(platform = "telegram", error = %e, "Failed to take webhook update");
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+/// The value of SIGNATURE_HEADER for `body` signed with `secret`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Whether `signature` is `body` signed with `secret`, compared in constant time
+fn signature_matches(secret: &str, body: &[u8], signature: &str) -> bool {
+    let hex_digest = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let Ok(digest) = hex::decode(hex_digest.trim()) else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&digest).is_ok()
+}
+
+/// Refuse deliveries without a valid signature when `webhook.secret` is set.
+/// Without a secret every request passes through as before.
+async fn signature_middleware(
+    State(state): State<Arc<WebhookState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(secret) = state.config.webhook.secret.as_deref() else {
+        return next.run(request).await;
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES).await else {
+        return signature_rejection("Request body too large to verify");
+    };
+    let signature = parts
+        .headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok());
+    match signature {
+        Some(signature) if signature_matches(secret, &bytes, signature) => {
+            next.run(Request::from_parts(parts, Body::from(bytes)))
+                .await
+        }
+        Some(_) => signature_rejection("Invalid signature"),
+        None => signature_rejection("Missing signature"),
+    }
+}
+
+fn signature_rejection(reason: &str) -> Response {
+    tracing::warn!(reason = %reason, "Webhook signature check failed");
+    metrics::record_webhook_request("auth_failed");
+    metrics::record_error("webhook_signature");
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(WebhookResponse {
+            success: false,
+            message: reason.to_string(),
+        }),
+    )
+        .into_response()
+}
+
 /// Handle webhook POST requests
 async fn webhook_handler(
     State(state): State<Arc<WebhookState>>,
@@ -309,10 +488,9 @@ async fn webhook_handler(
 ) -> (StatusCode, Json<WebhookResponse>) {
     let start_time = std::time::Instant::now();
 
-    let prompt_preview: String = payload.prompt.chars().take(50).collect();
     tracing::info!(
         session_id = %session_id,
-        prompt_preview = %prompt_preview,
+        prompt = %loggable_content(&payload.prompt),
         "Webhook received"
     );
 
@@ -337,6 +515,18 @@ async fn webhook_handler(
         }
     }
 
+    if let Err(refusal) = state.runtime.check(Gate::Webhook) {
+        tracing::warn!(session_id = %session_id, "Webhook rejected in safe mode");
+        metrics::record_webhook_request("safe_mode");
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(WebhookResponse {
+                success: false,
+                message: refusal.to_string(),
+            }),
+        );
+    }
+
     let prompt_text = payload.prompt;
 
     // Validate prompt is not empty
@@ -401,17 +591,75 @@ async fn webhook_handler(
         }
     };
 
+    // Bursts to a coalescing channel wait in a batch; the flusher sends them as one prompt.
+    // Urgent deliveries skip the batch.
+    let coalesce = state
+        .config
+        .webhook
+        .coalesce
+        .get(&channel.channel_name)
+        .filter(|_| !payload.urgent);
+    if let Some(rule) = coalesce {
+        let key = group_key(
+            &prompt_text,
+            payload.payload.as_ref(),
+            rule.group_by.as_deref(),
+        );
+        let sample = payload.payload.as_ref().map(sample_of);
+        let window = chrono::Duration::seconds(rule.coalesce_secs as i64);
+        return match state.session_store.add_webhook_delivery(
+            &channel.channel_name,
+            &key,
+            &prompt_text,
+            sample.as_deref(),
+            window,
+            Utc::now(),
+        ) {
+            Ok(batch) => {
+                metrics::record_webhook_request("coalesced");
+                (
+                    StatusCode::ACCEPTED,
+                    Json(WebhookResponse {
+                        success: true,
+                        message: format!(
+                            "Batched as delivery {} for {}; the reply will be posted to the channel",
+                            batch.count, key
+                        ),
+                    }),
+                )
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to store webhook delivery in batch");
+                metrics::record_webhook_request("error");
+                metrics::record_error("webhook_database");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(WebhookResponse {
+                        success: false,
+                        message: format!("Database error: {}", e),
+                    }),
+                )
+            }
+        };
+    }
+
     // Publish to the message bus
     let msg = BusMessage {
         id: uuid::Uuid::new_v4().to_string(),
-        source: MessageSource::Api { token_hint: "webhook".to_string() },
-        session_target: SessionTarget::Session { name: channel.channel_name.clone() },
+        source: MessageSource::Api {
+            token_hint: "webhook".to_string(),
+        },
+        session_target: SessionTarget::Session {
+            name: channel.channel_name.clone(),
+        },
         sender: "webhook".to_string(),
         body: prompt_text,
         timestamp: Utc::now(),
+        silent: false,
+        urgent: payload.urgent,
     };
 
-    metrics::record_claude_invocation("webhook");
+    metrics::record_claude_invocation("webhook", InvocationOrigin::Webhook);
 
     // Subscribe to responses BEFORE publishing to avoid races
     let mut response_rx = state.bus.subscribe_responses();
@@ -457,7 +705,9 @@ async fn webhook_handler(
                 }
             }
         }
-    }).await {
+    })
+    .await
+    {
         Ok(Ok(text)) => text,
         Ok(Err(e)) => {
             // Agent/bus error
@@ -499,6 +749,90 @@ async fn webhook_handler(
     )
 }
 
+/// Send each batch to its channel as a single prompt. Replies go out through the
+/// gateways like any other session response.
+fn publish_batches(bus: &MessageBus, batches: Vec<WebhookBatch>) -> usize {
+    let count = batches.len();
+    for batch in batches {
+        tracing::info!(
+            channel = %batch.channel_name,
+            group_key = %batch.group_key,
+            deliveries = batch.count,
+            "Flushing coalesced webhook batch"
+        );
+        metrics::record_claude_invocation("webhook", InvocationOrigin::Webhook);
+        bus.publish_inbound(BusMessage {
+            id: format!("webhook-batch-{}", batch.id),
+            source: MessageSource::Api {
+                token_hint: "webhook".to_string(),
+            },
+            session_target: SessionTarget::Session {
+                name: batch.channel_name.clone(),
+            },
+            sender: "webhook".to_string(),
+            body: batch.render(),
+            timestamp: Utc::now(),
+            silent: false,
+            urgent: false,
+        });
+    }
+    count
+}
+
+/// Flush batches whose window has closed by `now`. Nothing is taken until
+/// something is subscribed to the bus, so batches are never dropped unheard.
+pub fn flush_due_batches(
+    session_store: &SessionStore,
+    bus: &MessageBus,
+    now: chrono::DateTime<Utc>,
+) -> Result<usize> {
+    if !bus.has_inbound_subscribers() {
+        return Ok(0);
+    }
+    Ok(publish_batches(
+        bus,
+        session_store.take_due_webhook_batches(now)?,
+    ))
+}
+
+/// Flush every batch left over from before a restart, without waiting out its window
+pub fn recover_batches(session_store: &SessionStore, bus: &MessageBus) -> Result<usize> {
+    if !bus.has_inbound_subscribers() {
+        return Ok(0);
+    }
+    Ok(publish_batches(
+        bus,
+        session_store.take_all_webhook_batches()?,
+    ))
+}
+
+/// Background loop: recover leftover batches once the orchestrator is
+/// listening, then flush batches as their windows close
+async fn run_batch_flusher(session_store: SessionStore, bus: Arc<MessageBus>) {
+    let mut recovered = false;
+    loop {
+        tokio::time::sleep(BATCH_FLUSH_INTERVAL).await;
+        let result = if recovered {
+            flush_due_batches(&session_store, &bus, Utc::now())
+        } else if bus.has_inbound_subscribers() {
+            recovered = true;
+            recover_batches(&session_store, &bus).inspect(|&n| {
+                if n > 0 {
+                    tracing::info!(
+                        batches = n,
+                        "Flushed webhook batches left from before restart"
+                    );
+                }
+            })
+        } else {
+            Ok(0)
+        };
+        if let Err(e) = result {
+            tracing::error!(error = %e, "Failed to flush webhook batches");
+        }
+    }
+}
+
 /// Handle GET /metrics - returns Prometheus text format
 async fn metrics_handler(State(handle): State<Arc<PrometheusHandle>>) -> impl IntoResponse {
     handle.render()
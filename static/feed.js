@@ -11,7 +11,8 @@
 
     // Update connection indicator when socket connects
     window.gorp.on('status.platform', function(msg) {
-        status.textContent = msg.data.platform + ': ' + msg.data.state;
+        status.textContent = msg.data.platform + ': ' + msg.data.state +
+            (msg.data.reason ? ' (' + msg.data.reason + ')' : '');
     });
 
     // Append new feed messages
@@ -13,6 +13,7 @@
         if (!card) return;
 
         var isConnected = (state === 'connected');
+        card.title = msg.data.reason || '';
         var configured = card.dataset.configured === 'true';
 
         // Update card border/background
@@ -46,6 +46,23 @@ fn test_format_file_size_gigabytes() {
     assert_eq!(format_file_size(2 * 1024 * 1024 * 1024), "2.00 GB");
 }
 
+#[test]
+fn test_format_duration() {
+    use gorp::admin::routes::format_duration;
+    use std::time::Duration;
+
+    assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+    assert_eq!(format_duration(Duration::from_secs(12 * 60 + 30)), "12m");
+    assert_eq!(
+        format_duration(Duration::from_secs(3 * 3600 + 5 * 60)),
+        "3h 5m"
+    );
+    assert_eq!(
+        format_duration(Duration::from_secs(2 * 86400 + 4 * 3600)),
+        "2d 4h"
+    );
+}
+
 // =============================================================================
 // Path Validation Tests
 // =============================================================================
@@ -0,0 +1,88 @@
+// ABOUTME: Tests for the summary written to .gorp/archive-summary.md when a channel is archived.
+// ABOUTME: A mock backend answers the summary prompt; the file is checked with the option on and off.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use gorp::config::MaintenanceConfig;
+use gorp::message_handler::archive::{archive_summary_path, summarize_before_archive};
+use gorp::session::SessionStore;
+use gorp::warm_session::{SharedWarmSessionManager, WarmConfig, WarmSessionManager};
+use gorp_agent::backends::mock::MockBackend;
+use gorp_agent::AgentRegistry;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+fn summarizing_manager() -> SharedWarmSessionManager {
+    let registry = AgentRegistry::new().register("mock", |_config| {
+        Ok(MockBackend::new()
+            .on_prompt("This channel is being archived")
+            .respond_text("We migrated the billing cron to the new scheduler.")
+            .into_handle())
+    });
+    Arc::new(RwLock::new(WarmSessionManager::with_registry(
+        WarmConfig {
+            keep_alive_duration: Duration::from_secs(60),
+            pre_warm_lead_time: Duration::from_secs(30),
+            agent_binary: "claude".to_string(),
+            backend_type: "mock".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: vec![],
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        },
+        registry,
+    )))
+}
+
+/// A channel with a conversation behind it, re-read so `started` is set
+fn started_channel(store: &SessionStore) -> gorp::session::Channel {
+    let channel = store
+        .create_channel("billing", "!billing:example.com")
+        .unwrap();
+    std::fs::create_dir_all(&channel.directory).unwrap();
+    store.mark_started(&channel.room_id).unwrap();
+    store.get_by_name("billing").unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_archiving_writes_summary_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = started_channel(&store);
+    let config = MaintenanceConfig {
+        summarize_on_archive: true,
+        ..Default::default()
+    };
+
+    let path = summarize_before_archive(&config, &channel, &store, &summarizing_manager())
+        .await
+        .unwrap()
+        .expect("summary should be written");
+
+    assert_eq!(path, archive_summary_path(&channel.directory));
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# Archive summary: billing\n"));
+    assert!(content.contains("We migrated the billing cron to the new scheduler."));
+}
+
+#[tokio::test]
+async fn test_archiving_skips_summary_when_disabled() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = started_channel(&store);
+
+    let written = summarize_before_archive(
+        &MaintenanceConfig::default(),
+        &channel,
+        &store,
+        &summarizing_manager(),
+    )
+    .await
+    .unwrap();
+
+    assert!(written.is_none());
+    assert!(!archive_summary_path(&channel.directory).exists());
+}
@@ -15,6 +15,8 @@ fn test_bus_message_dispatch_target() {
         sender: "harper".to_string(),
         body: "!create research".to_string(),
         timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
     };
     assert!(matches!(msg.session_target, SessionTarget::Dispatch));
     assert_eq!(msg.sender, "harper");
@@ -34,12 +36,36 @@ fn test_bus_message_session_target() {
         sender: "harper".to_string(),
         body: "summarize the paper".to_string(),
         timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
     };
     assert!(
         matches!(msg.session_target, SessionTarget::Session { ref name } if name == "research")
     );
 }
 
+#[test]
+fn test_bus_message_priority_follows_urgent() {
+    let mut msg = BusMessage {
+        id: "evt-3".to_string(),
+        source: MessageSource::Api {
+            token_hint: "webhook".to_string(),
+        },
+        session_target: SessionTarget::Session {
+            name: "ops".to_string(),
+        },
+        sender: "webhook".to_string(),
+        body: "disk full on db1".to_string(),
+        timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
+    };
+    assert_eq!(msg.priority(), gorp::delivery::DeliveryPriority::Normal);
+
+    msg.urgent = true;
+    assert_eq!(msg.priority(), gorp::delivery::DeliveryPriority::Urgent);
+}
+
 #[test]
 fn test_bus_response_chunk() {
     let resp = BusResponse {
@@ -93,6 +119,8 @@ async fn test_message_bus_publish_and_receive() {
         sender: "harper".to_string(),
         body: "hello".to_string(),
         timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
     };
     bus.publish_inbound(msg);
     let received = rx.recv().await.unwrap();
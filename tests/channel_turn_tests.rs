@@ -0,0 +1,152 @@
+// ABOUTME: Tests that overlapping prompts in one channel take turns instead of running at once.
+// ABOUTME: Uses a slow backend that logs when each prompt starts and ends.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use gorp::message_handler::handle_text;
+use gorp::session::SessionStore;
+use gorp::usage::InvocationOrigin;
+use gorp::warm_session::{SharedWarmSessionManager, WarmConfig, WarmSessionManager};
+use gorp_agent::handle::Command;
+use gorp_agent::{AgentEvent, AgentHandle, AgentRegistry};
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+/// How long the slow backend takes to answer each prompt
+const PROMPT_DELAY: Duration = Duration::from_millis(100);
+
+/// Manager whose "slow" backend answers prompts concurrently after `PROMPT_DELAY`,
+/// logging "start <prompt>" and "end <prompt>" as it goes
+fn slow_manager(log: Arc<Mutex<Vec<String>>>) -> SharedWarmSessionManager {
+    let registry = AgentRegistry::new().register("slow", move |_config| {
+        let log = Arc::clone(&log);
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Command>(8);
+        tokio::spawn(async move {
+            while let Some(cmd) = rx.recv().await {
+                match cmd {
+                    Command::NewSession { reply } => {
+                        let _ = reply.send(Ok("slow-session".to_string()));
+                    }
+                    Command::LoadSession { reply, .. } => {
+                        let _ = reply.send(Ok(()));
+                    }
+                    Command::Prompt {
+                        text,
+                        event_tx,
+                        reply,
+                        ..
+                    } => {
+                        log.lock().unwrap().push(format!("start {}", text));
+                        let _ = reply.send(Ok(()));
+                        // Answer in the background so the backend itself never serializes
+                        let log = Arc::clone(&log);
+                        tokio::spawn(async move {
+                            tokio::time::sleep(PROMPT_DELAY).await;
+                            log.lock().unwrap().push(format!("end {}", text));
+                            let _ = event_tx
+                                .send(AgentEvent::Result {
+                                    text: format!("done: {}", text),
+                                    usage: None,
+                                    metadata: serde_json::json!({}),
+                                })
+                                .await;
+                        });
+                    }
+                    Command::Cancel { reply, .. } => {
+                        let _ = reply.send(Ok(()));
+                    }
+                    Command::ListModels { reply } => {
+                        let _ = reply.send(Ok(gorp_agent::model::default_models()));
+                    }
+                }
+            }
+        });
+        Ok(AgentHandle::new(tx, "slow"))
+    });
+
+    Arc::new(RwLock::new(WarmSessionManager::with_registry(
+        WarmConfig {
+            keep_alive_duration: Duration::from_secs(60),
+            pre_warm_lead_time: Duration::from_secs(30),
+            agent_binary: "claude".to_string(),
+            backend_type: "slow".to_string(),
+            model: None,
+            max_tokens: None,
+            global_system_prompt_path: None,
+            mcp_servers: vec![],
+            max_response_chars: 0,
+            save_truncated_responses: false,
+        },
+        registry,
+    )))
+}
+
+#[tokio::test]
+async fn test_overlapping_prompts_in_one_channel_serialize() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = store.create_channel("turns", "!turns:example.com").unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let warm_manager = slow_manager(Arc::clone(&log));
+
+    let spawn_prompt = |text: &'static str| {
+        let (channel, store, warm_manager) =
+            (channel.clone(), store.clone(), Arc::clone(&warm_manager));
+        tokio::spawn(async move {
+            handle_text(
+                text,
+                &channel,
+                &store,
+                &warm_manager,
+                InvocationOrigin::User,
+            )
+            .await
+        })
+    };
+    let first = spawn_prompt("first");
+    let second = spawn_prompt("second");
+
+    assert_eq!(first.await.unwrap().unwrap(), "done: first");
+    assert_eq!(second.await.unwrap().unwrap(), "done: second");
+
+    // Whichever prompt got the channel first, the other only started after it ended
+    let log = log.lock().unwrap().clone();
+    assert_eq!(log.len(), 4, "unexpected log: {:?}", log);
+    let (a, b) = (&log[0]["start ".len()..], &log[2]["start ".len()..]);
+    assert_eq!(
+        log,
+        vec![
+            format!("start {}", a),
+            format!("end {}", a),
+            format!("start {}", b),
+            format!("end {}", b),
+        ]
+    );
+    assert_ne!(a, b);
+}
+
+#[tokio::test]
+async fn test_prompts_in_different_channels_overlap() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let one = store.create_channel("one", "!one:example.com").unwrap();
+    let two = store.create_channel("two", "!two:example.com").unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let warm_manager = slow_manager(Arc::clone(&log));
+
+    let (first, second) = tokio::join!(
+        handle_text("one", &one, &store, &warm_manager, InvocationOrigin::User),
+        handle_text("two", &two, &store, &warm_manager, InvocationOrigin::User),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    // Both started before either finished
+    let log = log.lock().unwrap().clone();
+    assert!(
+        log[..2].iter().all(|entry| entry.starts_with("start ")),
+        "channels should not wait on each other: {:?}",
+        log
+    );
+}
@@ -0,0 +1,137 @@
+// ABOUTME: Golden-file tests for `gorp commands export` in every format.
+// ABOUTME: Uses a small fixture catalog and CLI so the goldens only change when rendering does.
+
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, Command};
+use gorp::command_catalog::{CommandCatalog, CommandSpec};
+use gorp::command_export::{export, ExportFormat};
+
+/// Set to rewrite the goldens instead of comparing against them
+const UPDATE_ENV: &str = "UPDATE_GOLDEN";
+
+fn fixture_catalog() -> CommandCatalog {
+    let mut catalog = CommandCatalog::default();
+    catalog
+        .register(
+            CommandSpec::new("length", "Set how long answers should be")
+                .room_only()
+                .arg("mode", true, "brief, normal or detailed")
+                .flag("strict", "Also trim answers that run long")
+                .example("!length brief strict"),
+        )
+        .unwrap();
+    catalog
+        .register(
+            CommandSpec::new("locale", "Show or set your language")
+                .alias("lang")
+                .arg("code", false, "Language code, or reset")
+                .example("!locale fr")
+                .example("!lang reset"),
+        )
+        .unwrap();
+    catalog
+        .register(
+            CommandSpec::new("cleanup", "Leave orphaned rooms")
+                .dm_only()
+                .admin()
+                .example("!cleanup"),
+        )
+        .unwrap();
+    catalog
+}
+
+fn fixture_cli() -> Command {
+    Command::new("gorp")
+        .subcommand(Command::new("start").about("Start the bridge"))
+        .subcommand(
+            Command::new("config")
+                .about("Configuration management")
+                .subcommand(
+                    Command::new("init").about("Initialize config").arg(
+                        Arg::new("force")
+                            .short('f')
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .help("Overwrite existing config file"),
+                    ),
+                )
+                .subcommand(Command::new("path").about("Show path to config file")),
+        )
+        .subcommand(
+            Command::new("commands")
+                .about("Chat command reference")
+                .subcommand(
+                    Command::new("export")
+                        .about("Export the command reference")
+                        .arg(
+                            Arg::new("format")
+                                .long("format")
+                                .value_parser(["json", "markdown"])
+                                .help("Output format"),
+                        ),
+                ),
+        )
+}
+
+fn assert_golden(file: &str, format: ExportFormat) {
+    let actual = export(&fixture_catalog(), &fixture_cli(), format).unwrap();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(file);
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    assert_eq!(
+        actual, expected,
+        "{} is out of date; rerun with {}=1 if the change is intended",
+        file, UPDATE_ENV
+    );
+}
+
+#[test]
+fn test_export_json_golden() {
+    assert_golden("commands.json", ExportFormat::Json);
+}
+
+#[test]
+fn test_export_markdown_golden() {
+    assert_golden("commands.md", ExportFormat::Markdown);
+}
+
+#[test]
+fn test_export_bash_golden() {
+    assert_golden("gorp.bash", ExportFormat::Bash);
+}
+
+#[test]
+fn test_export_zsh_golden() {
+    assert_golden("_gorp.zsh", ExportFormat::Zsh);
+}
+
+#[test]
+fn test_export_fish_golden() {
+    assert_golden("gorp.fish", ExportFormat::Fish);
+}
+
+#[test]
+fn test_builtin_catalog_covers_help_doc() {
+    let catalog = CommandCatalog::builtin();
+    let help = include_str!("../docs/HELP.md");
+
+    for (_, rest) in help.match_indices("`!").map(|(i, _)| help.split_at(i + 2)) {
+        let name: String = rest
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect();
+        assert!(
+            catalog.get(&name).is_some(),
+            "!{} is documented in HELP.md but missing from the command catalog",
+            name
+        );
+    }
+}
@@ -0,0 +1,230 @@
+// ABOUTME: Shared fixture for integration tests that drive handle_incoming end to end.
+// ABOUTME: TestState builds a Config with one allowed user and the ServerState around it.
+
+// Each test binary compiles this module on its own and uses only part of it
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use gorp::bus::MessageBus;
+use gorp::config::{
+    AccessConfig, AttachmentsConfig, BackendConfig, CommandsConfig, Config, DedupConfig,
+    EditsConfig, I18nConfig, LimitsConfig, LoggingConfig, MaintenanceConfig, MatrixConfig,
+    MetricsConfig, RolesConfig, RuntimeConfig, SchedulerConfig, SendGuardConfig, ShutdownConfig,
+    SigningConfig, TelegramConfig, ToolApprovalConfig, WebhookConfig, WorkspaceConfig,
+};
+use gorp::confirmations::PendingConfirmations;
+use gorp::dedup::DedupCache;
+use gorp::edits::EditTracker;
+use gorp::message_handler::handle_incoming;
+use gorp::message_handler::traits::MockPlatform;
+use gorp::rate_limit::RateLimiter;
+use gorp::redactions::ResponseTracker;
+use gorp::scheduler::SchedulerStore;
+use gorp::server::ServerState;
+use gorp::session::SessionStore;
+use gorp::traits::EventStream;
+use gorp::transcription::Transcriber;
+use gorp::warm_session::{create_shared_manager, SharedWarmSessionManager, WarmConfig};
+use tokio_stream::StreamExt;
+
+/// Builds the ServerState handle_incoming runs against, rooted in a temp directory.
+/// Starts from defaults with one allowed user; tests adjust the config with `config`.
+pub struct TestState {
+    dir: PathBuf,
+    config: Config,
+    warm_manager: Option<SharedWarmSessionManager>,
+    transcriber: Option<Arc<dyn Transcriber>>,
+}
+
+impl TestState {
+    /// Telegram configured, with `user_id` (a numeric Telegram ID) the only allowed user
+    pub fn telegram(dir: &Path, user_id: &str) -> Self {
+        let mut config = base_config(dir);
+        config.telegram = Some(TelegramConfig {
+            bot_token: "test-token".to_string(),
+            allowed_users: vec![user_id.parse().expect("numeric Telegram user ID")],
+            allowed_chats: vec![],
+            reconnect: Default::default(),
+            command_prefix: "!".to_string(),
+            mode: Default::default(),
+            webhook_url: None,
+            webhook_secret: None,
+        });
+        Self::new(dir, config)
+    }
+
+    /// Matrix configured, with `user_id` the only allowed user
+    pub fn matrix(dir: &Path, user_id: &str) -> Self {
+        let mut config = base_config(dir);
+        config.matrix = Some(MatrixConfig {
+            home_server: "https://matrix.example.com".to_string(),
+            user_id: "@bot:matrix.example.com".to_string(),
+            password: None,
+            access_token: Some("test_token".to_string()),
+            device_name: "test-device".to_string(),
+            allowed_users: vec![user_id.to_string()],
+            room_prefix: "Test".to_string(),
+            recovery_key: None,
+            reconnect: Default::default(),
+            invite_policy: Default::default(),
+            admin_room: None,
+            command_prefix: "!".to_string(),
+            management_room: None,
+            announce_startup: true,
+            notify_ready: true,
+            space_id: None,
+            auto_create_space: false,
+            room_avatar: None,
+            member_power_level: 0,
+        });
+        Self::new(dir, config)
+    }
+
+    fn new(dir: &Path, config: Config) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            config,
+            warm_manager: None,
+            transcriber: None,
+        }
+    }
+
+    /// Change the config before the state is built
+    pub fn config(mut self, adjust: impl FnOnce(&mut Config)) -> Self {
+        adjust(&mut self.config);
+        self
+    }
+
+    /// Run agents through this manager instead of one on the mock backend
+    pub fn warm_manager(mut self, warm_manager: SharedWarmSessionManager) -> Self {
+        self.warm_manager = Some(warm_manager);
+        self
+    }
+
+    /// Transcribe voice messages with this instead of leaving them unsupported
+    pub fn transcriber(mut self, transcriber: Arc<dyn Transcriber>) -> Self {
+        self.transcriber = Some(transcriber);
+        self
+    }
+
+    pub fn build(self) -> ServerState {
+        let config = self.config;
+        let session_store = SessionStore::new(&self.dir).unwrap();
+        let scheduler_store = SchedulerStore::new(session_store.db_connection());
+        let warm_manager = self
+            .warm_manager
+            .unwrap_or_else(|| create_shared_manager(warm_config("mock")));
+
+        ServerState {
+            dedup: Arc::new(DedupCache::new(
+                config.dedup.cache_size,
+                Duration::from_secs(config.dedup.ttl_secs),
+            )),
+            edits: Arc::new(EditTracker::new(Duration::from_secs(600))),
+            responses: Arc::new(ResponseTracker::new(Duration::from_secs(600))),
+            confirmations: Arc::new(PendingConfirmations::default()),
+            rate_limiter: Arc::new(RateLimiter::new(
+                config.limits.messages_per_minute,
+                config.limits.burst,
+            )),
+            transcriber: self.transcriber,
+            config: Arc::new(config),
+            matrix_client: None,
+            session_store: Arc::new(session_store),
+            scheduler_store,
+            warm_manager,
+            bus: Arc::new(MessageBus::new(16)),
+            sync_token: None,
+        }
+    }
+}
+
+/// Handle the next `count` messages from the stream, like the server's event loop
+pub async fn pump(
+    stream: &mut EventStream,
+    platform: &MockPlatform,
+    state: &ServerState,
+    count: usize,
+) {
+    for _ in 0..count {
+        let msg = stream.next().await.expect("injected message");
+        handle_incoming(&msg, platform, state).await.unwrap();
+    }
+}
+
+/// Warm session settings for agents on `backend_type`, without response caps
+pub fn warm_config(backend_type: &str) -> WarmConfig {
+    WarmConfig {
+        keep_alive_duration: Duration::from_secs(60),
+        pre_warm_lead_time: Duration::from_secs(30),
+        agent_binary: "claude".to_string(),
+        backend_type: backend_type.to_string(),
+        model: None,
+        max_tokens: None,
+        global_system_prompt_path: None,
+        mcp_servers: vec![],
+        max_response_chars: 0,
+        save_truncated_responses: false,
+    }
+}
+
+/// Text of every message `platform` sent, in send order
+pub fn sent_texts(platform: &MockPlatform) -> Vec<String> {
+    platform
+        .sent_text()
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect()
+}
+
+/// Defaults for everything, with no chat platform configured
+fn base_config(dir: &Path) -> Config {
+    Config {
+        matrix: None,
+        telegram: None,
+        slack: None,
+        whatsapp: None,
+        irc: None,
+        zulip: None,
+        coven: None,
+        backend: BackendConfig::default(),
+        webhook: WebhookConfig {
+            port: 13000,
+            api_key: None,
+            secret: None,
+            host: "localhost".to_string(),
+            coalesce: Default::default(),
+        },
+        workspace: WorkspaceConfig {
+            path: dir.to_str().unwrap().to_string(),
+            channel_scope: Default::default(),
+        },
+        scheduler: SchedulerConfig {
+            timezone: "UTC".to_string(),
+            templates: Default::default(),
+            quiet_hours: None,
+            run_history: 20,
+        },
+        dedup: DedupConfig::default(),
+        edits: EditsConfig::default(),
+        i18n: I18nConfig::default(),
+        send_guard: SendGuardConfig::default(),
+        limits: LimitsConfig::default(),
+        attachments: AttachmentsConfig::default(),
+        maintenance: MaintenanceConfig::default(),
+        transcription: None,
+        logging: LoggingConfig::default(),
+        commands: CommandsConfig::default(),
+        shutdown: ShutdownConfig::default(),
+        tool_approval: ToolApprovalConfig::default(),
+        roles: RolesConfig::default(),
+        access: AccessConfig::default(),
+        preprocess: Default::default(),
+        metrics: MetricsConfig::default(),
+        runtime: RuntimeConfig::default(),
+        signing: SigningConfig::default(),
+    }
+}
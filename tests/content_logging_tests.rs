@@ -0,0 +1,107 @@
+// ABOUTME: Checks that [logging] content_policy governs what of a user's message reaches the logs.
+// ABOUTME: Captures every log line while a message goes through handle_incoming and greps for a secret.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use gorp::config::ContentPolicy;
+use gorp::logging::set_content_policy;
+use gorp::message_handler::handle_incoming;
+use gorp::message_handler::traits::MockPlatform;
+use gorp::server::ServerState;
+use tempfile::TempDir;
+
+mod common;
+
+use common::TestState;
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+
+/// Past the first 50 characters, so a preview never reaches it
+const SENTINEL: &str = "sentinel-7f3c9a-correct-horse-battery-staple";
+
+/// Log output collected by the test subscriber
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Captured {
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+    }
+}
+
+fn test_state(tmp: &TempDir, content_policy: ContentPolicy) -> ServerState {
+    TestState::telegram(tmp.path(), USER_ID)
+        .config(|config| config.logging.content_policy = content_policy)
+        .build()
+}
+
+/// Send one message carrying the sentinel through a channel and return everything logged
+async fn logs_for_message(content_policy: ContentPolicy) -> String {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp, content_policy);
+    set_content_policy(state.config.logging.content_policy);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let body = format!(
+        "Please keep this between us, the vault passphrase is {}",
+        SENTINEL
+    );
+    let msg = platform.message(CHAT_ID, USER_ID, &body);
+    handle_incoming(&msg, &platform, &state).await.unwrap();
+
+    captured.text()
+}
+
+// One test for every policy: the policy is process-wide
+#[tokio::test]
+async fn test_content_policy_controls_what_reaches_the_logs() {
+    let full = logs_for_message(ContentPolicy::Full).await;
+    assert!(
+        full.contains(SENTINEL),
+        "full policy should log the message"
+    );
+
+    let preview = logs_for_message(ContentPolicy::Preview).await;
+    assert!(
+        preview.contains("Please keep this between us"),
+        "{}",
+        preview
+    );
+    assert!(!preview.contains(SENTINEL), "{}", preview);
+
+    let hashed = logs_for_message(ContentPolicy::Hashed).await;
+    assert!(hashed.contains("hash:"), "{}", hashed);
+    assert!(!hashed.contains("Please keep this"), "{}", hashed);
+    assert!(!hashed.contains(SENTINEL), "{}", hashed);
+
+    let none = logs_for_message(ContentPolicy::None).await;
+    assert!(none.contains("chars omitted"), "{}", none);
+    assert!(!none.contains("Please keep this"), "{}", none);
+    assert!(!none.contains(SENTINEL), "{}", none);
+}
@@ -0,0 +1,139 @@
+// ABOUTME: Tests for DISPATCH's per-user conversation history on disk.
+// ABOUTME: Covers persistence across instances, per-user separation, pruning and context trimming.
+
+use chrono::Utc;
+use gorp::dispatch_history::{
+    dispatch_workspace, format_history, trim_for_context, DispatchHistory, HistoryEntry,
+    HistoryRole, MAX_STORED_ENTRIES,
+};
+use tempfile::TempDir;
+
+const ALICE: &str = "@alice:example.com";
+const BOB: &str = "@bob:example.com";
+
+fn entry(role: HistoryRole, text: &str) -> HistoryEntry {
+    HistoryEntry {
+        role,
+        text: text.to_string(),
+        timestamp: Utc::now(),
+    }
+}
+
+#[test]
+fn test_history_survives_a_new_instance() {
+    let tmp = TempDir::new().unwrap();
+    let workspace = dispatch_workspace(tmp.path());
+    DispatchHistory::new(&workspace)
+        .record_exchange(ALICE, "Which room handles billing?", "The billing room.")
+        .unwrap();
+
+    // A fresh instance stands in for a restart
+    let entries = DispatchHistory::new(&workspace).load(ALICE).unwrap();
+    assert_eq!(
+        entries
+            .iter()
+            .map(|e| (e.role, e.text.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (HistoryRole::User, "Which room handles billing?"),
+            (HistoryRole::Dispatch, "The billing room."),
+        ]
+    );
+    assert!(workspace
+        .join("history")
+        .join("alice_example.com.jsonl")
+        .exists());
+}
+
+#[test]
+fn test_history_is_kept_per_user() {
+    let tmp = TempDir::new().unwrap();
+    let history = DispatchHistory::new(&dispatch_workspace(tmp.path()));
+    history
+        .record_exchange(ALICE, "alice asks", "for alice")
+        .unwrap();
+    history.record_exchange(BOB, "bob asks", "for bob").unwrap();
+
+    let alice = history.load(ALICE).unwrap();
+    assert_eq!(alice.len(), 2);
+    assert!(alice.iter().all(|e| !e.text.contains("bob")));
+    assert!(history.load("@carol:example.com").unwrap().is_empty());
+}
+
+#[test]
+fn test_stored_history_is_pruned_to_the_newest_entries() {
+    let tmp = TempDir::new().unwrap();
+    let history = DispatchHistory::new(&dispatch_workspace(tmp.path()));
+    let exchanges = MAX_STORED_ENTRIES / 2 + 5;
+    for i in 0..exchanges {
+        history
+            .record_exchange(ALICE, &format!("question {}", i), &format!("answer {}", i))
+            .unwrap();
+    }
+
+    let entries = history.load(ALICE).unwrap();
+    assert_eq!(entries.len(), MAX_STORED_ENTRIES);
+    assert_eq!(entries[0].text, "question 5");
+    assert_eq!(
+        entries.last().unwrap().text,
+        format!("answer {}", exchanges - 1)
+    );
+}
+
+#[test]
+fn test_bad_lines_are_skipped() {
+    let tmp = TempDir::new().unwrap();
+    let history = DispatchHistory::new(&dispatch_workspace(tmp.path()));
+    history.record_exchange(ALICE, "first", "one").unwrap();
+    let path = history.path_for(ALICE);
+    let mut content = std::fs::read_to_string(&path).unwrap();
+    content.push_str("{not json\n");
+    std::fs::write(&path, content).unwrap();
+    history.record_exchange(ALICE, "second", "two").unwrap();
+
+    assert_eq!(history.load(ALICE).unwrap().len(), 4);
+}
+
+#[test]
+fn test_trim_keeps_newest_entries_within_count() {
+    let entries: Vec<_> = (0..10)
+        .map(|i| entry(HistoryRole::User, &format!("m{}", i)))
+        .collect();
+
+    let trimmed = trim_for_context(&entries, 3, 1_000);
+    assert_eq!(
+        trimmed.iter().map(|e| e.text.as_str()).collect::<Vec<_>>(),
+        vec!["m7", "m8", "m9"]
+    );
+}
+
+#[test]
+fn test_trim_respects_character_budget() {
+    let entries = vec![
+        entry(HistoryRole::User, &"a".repeat(50)),
+        entry(HistoryRole::Dispatch, &"b".repeat(30)),
+        entry(HistoryRole::User, &"c".repeat(30)),
+    ];
+
+    let trimmed = trim_for_context(&entries, 10, 70);
+    assert_eq!(trimmed.len(), 2);
+    assert!(trimmed[0].text.starts_with('b'));
+
+    // A single entry over budget leaves nothing rather than a partial message
+    assert!(trim_for_context(&entries, 10, 20).is_empty());
+}
+
+#[test]
+fn test_format_history_labels_speakers() {
+    assert!(format_history(&[]).is_none());
+
+    let block = format_history(&[
+        entry(HistoryRole::User, "status of api?"),
+        entry(HistoryRole::Dispatch, "api is idle"),
+    ])
+    .unwrap();
+    assert!(block.starts_with("<conversation_history>\n"));
+    assert!(block.contains("User: status of api?\n"));
+    assert!(block.contains("DISPATCH: api is idle\n"));
+    assert!(block.ends_with("</conversation_history>"));
+}
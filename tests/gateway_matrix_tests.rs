@@ -5,12 +5,15 @@
 mod tests {
     use gorp::bus::*;
     use gorp::gateway::matrix::{matrix_event_to_bus_message, response_to_matrix_message};
+    use gorp::platform::matrix::signing::{self, Signer};
     use matrix_sdk::ruma::events::room::message::MessageType;
 
+    const ROOM: &str = "!room:example.com";
+
     #[test]
     fn test_response_chunk_to_matrix_message() {
         let content = ResponseContent::Chunk("hello **world**".to_string());
-        let msg = response_to_matrix_message(&content);
+        let msg = response_to_matrix_message(&content, ROOM, None);
         match &msg.msgtype {
             MessageType::Text(text) => {
                 assert!(text.body.contains("hello"));
@@ -25,7 +28,7 @@ mod tests {
     #[test]
     fn test_response_complete_to_matrix_message() {
         let content = ResponseContent::Complete("final answer".to_string());
-        let msg = response_to_matrix_message(&content);
+        let msg = response_to_matrix_message(&content, ROOM, None);
         match &msg.msgtype {
             MessageType::Text(text) => {
                 assert!(text.body.contains("final"));
@@ -35,10 +38,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_agent_output_is_signed_but_notices_are_not() {
+        let signer = Signer::new("s3cret");
+        let formatted = |content: ResponseContent| {
+            let msg = response_to_matrix_message(&content, ROOM, Some(&signer));
+            match msg.msgtype {
+                MessageType::Text(text) => (text.body, text.formatted.unwrap().body),
+                other => panic!("Expected Text message type, got {:?}", other),
+            }
+        };
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        let (body, html) = formatted(ResponseContent::Complete("final answer".to_string()));
+        assert!(signing::verify("s3cret", ROOM, now, &body, &html));
+        assert!(!signing::verify(
+            "s3cret",
+            "!elsewhere:example.com",
+            now,
+            &body,
+            &html
+        ));
+
+        let (_, html) = formatted(ResponseContent::SystemNotice("Session created".to_string()));
+        assert!(!html.contains(signing::SIGNATURE_ATTRIBUTE));
+    }
+
     #[test]
     fn test_response_error_to_matrix_message() {
         let content = ResponseContent::Error("timeout".to_string());
-        let msg = response_to_matrix_message(&content);
+        let msg = response_to_matrix_message(&content, ROOM, None);
         match &msg.msgtype {
             MessageType::Text(text) => {
                 assert!(text.body.contains("Error"));
@@ -54,7 +83,7 @@ mod tests {
     #[test]
     fn test_response_system_notice_to_matrix_message() {
         let content = ResponseContent::SystemNotice("Session created".to_string());
-        let msg = response_to_matrix_message(&content);
+        let msg = response_to_matrix_message(&content, ROOM, None);
         match &msg.msgtype {
             MessageType::Text(text) => {
                 assert!(text.body.contains("Session created"));
@@ -135,7 +164,7 @@ mod tests {
     #[test]
     fn test_response_chunk_markdown_rendered_as_html() {
         let content = ResponseContent::Chunk("# Heading\n\n- item 1\n- item 2".to_string());
-        let msg = response_to_matrix_message(&content);
+        let msg = response_to_matrix_message(&content, ROOM, None);
         match &msg.msgtype {
             MessageType::Text(text) => {
                 let html = text.formatted.as_ref().unwrap().body.clone();
@@ -0,0 +1,36 @@
+# bash completion for gorp
+# Generated by `gorp commands export --format bash`
+
+_gorp() {
+    local cur prev cmd_path i opts
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+
+    case "$prev" in
+        --format)
+            COMPREPLY=($(compgen -W "json markdown" -- "$cur"))
+            return
+            ;;
+    esac
+
+    cmd_path="gorp"
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${COMP_WORDS[i]}" in
+            --format) ((i++)) ;;
+            -*) ;;
+            *) cmd_path="${cmd_path}__${COMP_WORDS[i]}" ;;
+        esac
+    done
+
+    opts=""
+    case "$cmd_path" in
+        gorp) opts="start config commands" ;;
+        gorp__config) opts="init path" ;;
+        gorp__config__init) opts="--force -f" ;;
+        gorp__commands) opts="export" ;;
+        gorp__commands__export) opts="--format" ;;
+    esac
+    COMPREPLY=($(compgen -W "$opts" -- "$cur"))
+}
+
+complete -F _gorp gorp
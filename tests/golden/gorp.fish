@@ -0,0 +1,12 @@
+# fish completion for gorp
+# Generated by `gorp commands export --format fish`
+
+complete -c gorp -f
+complete -c gorp -n "__fish_use_subcommand" -a start -d 'Start the bridge'
+complete -c gorp -n "__fish_use_subcommand" -a config -d 'Configuration management'
+complete -c gorp -n "__fish_use_subcommand" -a commands -d 'Chat command reference'
+complete -c gorp -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from init path" -a init -d 'Initialize config'
+complete -c gorp -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from init path" -a path -d 'Show path to config file'
+complete -c gorp -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from init" -s f -l force -d 'Overwrite existing config file'
+complete -c gorp -n "__fish_seen_subcommand_from commands; and not __fish_seen_subcommand_from export" -a export -d 'Export the command reference'
+complete -c gorp -n "__fish_seen_subcommand_from commands; and __fish_seen_subcommand_from export" -l format -r -a 'json markdown' -d 'Output format'
@@ -0,0 +1,74 @@
+// ABOUTME: Tests that handle_incoming drops replayed platform events via the ServerState dedup cache.
+// ABOUTME: Uses the mock agent backend and MockPlatform to count agent invocations.
+
+use gorp::message_handler::handle_incoming;
+use gorp::message_handler::traits::MockPlatform;
+use gorp::server::ServerState;
+use gorp::traits::{ChatUser, IncomingMessage};
+use tempfile::TempDir;
+
+mod common;
+
+use common::{sent_texts, TestState};
+
+const ROOM_ID: &str = "!room:matrix.example.com";
+const USER_ID: &str = "@user:matrix.example.com";
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::matrix(tmp.path(), USER_ID).build()
+}
+
+fn incoming(event_id: &str, body: &str) -> IncomingMessage {
+    IncomingMessage {
+        platform_id: "matrix".to_string(),
+        channel_id: ROOM_ID.to_string(),
+        thread_id: None,
+        sender: ChatUser::new(USER_ID),
+        body: body.to_string(),
+        is_direct: false,
+        formatted: false,
+        attachments: Vec::new(),
+        event_id: event_id.to_string(),
+        edits_event_id: None,
+        timestamp: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_replayed_event_invokes_agent_once() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("dedup", ROOM_ID)
+        .unwrap();
+    let platform = MockPlatform::new("matrix");
+
+    let msg = incoming("$event1", "hello agent");
+    handle_incoming(&msg, &platform, &state).await.unwrap();
+    handle_incoming(&msg, &platform, &state).await.unwrap();
+
+    let sent = sent_texts(&platform);
+    assert_eq!(sent.len(), 1, "agent should respond once, got {:?}", sent);
+    assert!(sent[0].contains("hello agent"));
+}
+
+#[tokio::test]
+async fn test_distinct_events_are_both_handled() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("dedup", ROOM_ID)
+        .unwrap();
+    let platform = MockPlatform::new("matrix");
+
+    handle_incoming(&incoming("$event1", "first"), &platform, &state)
+        .await
+        .unwrap();
+    handle_incoming(&incoming("$event2", "second"), &platform, &state)
+        .await
+        .unwrap();
+
+    assert_eq!(sent_texts(&platform).len(), 2);
+}
@@ -0,0 +1,98 @@
+// ABOUTME: Tests that edited chat messages reach the agent as corrections via handle_incoming.
+// ABOUTME: Uses the mock agent backend, which echoes the prompt it received, to inspect what was sent.
+
+use gorp::message_handler::handle_incoming;
+use gorp::message_handler::traits::MockPlatform;
+use gorp::server::ServerState;
+use gorp::traits::{ChatUser, IncomingMessage};
+use tempfile::TempDir;
+
+mod common;
+
+use common::{sent_texts, TestState};
+
+const ROOM_ID: &str = "!room:matrix.example.com";
+const USER_ID: &str = "@user:matrix.example.com";
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::matrix(tmp.path(), USER_ID).build()
+}
+
+fn incoming(event_id: &str, body: &str) -> IncomingMessage {
+    IncomingMessage {
+        platform_id: "matrix".to_string(),
+        channel_id: ROOM_ID.to_string(),
+        thread_id: None,
+        sender: ChatUser::new(USER_ID),
+        body: body.to_string(),
+        is_direct: false,
+        formatted: false,
+        attachments: Vec::new(),
+        event_id: event_id.to_string(),
+        edits_event_id: None,
+        timestamp: 0,
+    }
+}
+
+fn edit_of(original_id: &str, event_id: &str, body: &str) -> IncomingMessage {
+    IncomingMessage {
+        edits_event_id: Some(original_id.to_string()),
+        ..incoming(event_id, body)
+    }
+}
+
+#[tokio::test]
+async fn test_edit_after_answer_sends_correction() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("edits", ROOM_ID)
+        .unwrap();
+    let platform = MockPlatform::new("matrix");
+
+    handle_incoming(&incoming("$event1", "what is 2+2?"), &platform, &state)
+        .await
+        .unwrap();
+    handle_incoming(
+        &edit_of("$event1", "$edit1", "what is 2+3?"),
+        &platform,
+        &state,
+    )
+    .await
+    .unwrap();
+
+    let sent = sent_texts(&platform);
+    assert_eq!(
+        sent.len(),
+        2,
+        "expected answer and correction, got {:?}",
+        sent
+    );
+    assert!(
+        sent[1].contains("Correction to previous message:\n\nwhat is 2+3?"),
+        "{}",
+        sent[1]
+    );
+}
+
+#[tokio::test]
+async fn test_edit_of_unknown_message_is_ignored() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("edits", ROOM_ID)
+        .unwrap();
+    let platform = MockPlatform::new("matrix");
+
+    handle_incoming(
+        &edit_of("$unseen", "$edit1", "typo fixed"),
+        &platform,
+        &state,
+    )
+    .await
+    .unwrap();
+
+    assert!(sent_texts(&platform).is_empty());
+}
@@ -1,6 +1,7 @@
 // ABOUTME: Tests for message_handler helper functions
 // ABOUTME: Covers channel name validation, string truncation, cron detection, and schedule parsing
 
+use std::collections::HashMap;
 use tempfile::TempDir;
 
 // =============================================================================
@@ -244,7 +245,7 @@ fn test_parse_schedule_input_relative_time() {
     use gorp::message_handler::parse_schedule_input;
     use gorp::scheduler::ParsedSchedule;
 
-    let result = parse_schedule_input("in 5 minutes run the tests", "UTC");
+    let result = parse_schedule_input("in 5 minutes run the tests", "UTC", &HashMap::new());
     assert!(result.is_ok());
     let (schedule, prompt) = result.unwrap();
 
@@ -258,7 +259,7 @@ fn test_parse_schedule_input_recurring() {
     use gorp::message_handler::parse_schedule_input;
     use gorp::scheduler::ParsedSchedule;
 
-    let result = parse_schedule_input("every day at 9am check the server", "UTC");
+    let result = parse_schedule_input("every day at 9am check the server", "UTC", &HashMap::new());
     assert!(result.is_ok());
     let (schedule, prompt) = result.unwrap();
 
@@ -273,7 +274,7 @@ fn test_parse_schedule_input_no_prompt() {
 
     // Just a time expression with no prompt should still work
     // The greedy parser will use the longest valid time expression
-    let result = parse_schedule_input("in 5 minutes", "UTC");
+    let result = parse_schedule_input("in 5 minutes", "UTC", &HashMap::new());
     // This might parse "in 5" as time and "minutes" as prompt, or fail entirely
     // depending on what parse_time_expression accepts
     // The key is it shouldn't panic
@@ -285,7 +286,7 @@ fn test_parse_schedule_input_invalid_time() {
     use gorp::message_handler::parse_schedule_input;
 
     // Invalid time expression should fail
-    let result = parse_schedule_input("banana apple cherry", "UTC");
+    let result = parse_schedule_input("banana apple cherry", "UTC", &HashMap::new());
     assert!(result.is_err());
 }
 
@@ -295,7 +296,7 @@ fn test_parse_schedule_input_with_timezone() {
     use gorp::scheduler::ParsedSchedule;
 
     // Should respect timezone
-    let result = parse_schedule_input("tomorrow at 9am test", "America/New_York");
+    let result = parse_schedule_input("tomorrow at 9am test", "America/New_York", &HashMap::new());
     assert!(result.is_ok());
     let (schedule, prompt) = result.unwrap();
     assert!(matches!(schedule, ParsedSchedule::OneTime(_)));
@@ -308,13 +309,46 @@ fn test_parse_schedule_input_greedy_matching() {
 
     // Should use greedy matching to find longest valid time expression
     // "in 2 hours" is valid, "in 2 hours and" might not be
-    let result = parse_schedule_input("in 2 hours and then do something", "UTC");
+    let result = parse_schedule_input("in 2 hours and then do something", "UTC", &HashMap::new());
     assert!(result.is_ok());
     let (_, prompt) = result.unwrap();
     // The prompt should contain the non-time portion
     assert!(prompt.contains("something") || prompt.contains("and"));
 }
 
+#[test]
+fn test_parse_schedule_input_expands_time_template() {
+    use gorp::message_handler::parse_schedule_input;
+    use gorp::scheduler::ParsedSchedule;
+
+    let templates = HashMap::from([("standup".to_string(), "every weekday 9am".to_string())]);
+    let (schedule, prompt) =
+        parse_schedule_input("@standup post the standup summary", "UTC", &templates).unwrap();
+    match schedule {
+        ParsedSchedule::Recurring { cron, .. } => assert_eq!(cron, "0 9 * * MON-FRI"),
+        other => panic!("expected a recurring schedule, got {:?}", other),
+    }
+    assert_eq!(prompt, "post the standup summary");
+}
+
+#[test]
+fn test_parse_schedule_input_unknown_time_template() {
+    use gorp::message_handler::parse_schedule_input;
+
+    let templates = HashMap::from([
+        ("standup".to_string(), "every weekday 9am".to_string()),
+        ("eod".to_string(), "every weekday 5pm".to_string()),
+    ]);
+    let err = parse_schedule_input("@retro run the retro", "UTC", &templates).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown time template '@retro'. Known templates: @eod, @standup"
+    );
+
+    let err = parse_schedule_input("@retro run the retro", "UTC", &HashMap::new()).unwrap_err();
+    assert!(err.to_string().contains("none are configured"));
+}
+
 // =============================================================================
 // Edge Case Tests
 // =============================================================================
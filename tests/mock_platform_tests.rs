@@ -0,0 +1,540 @@
+// ABOUTME: End-to-end tests of handle_incoming driven through MockPlatform's event stream.
+// ABOUTME: Asserts the exact sequence of platform sends with no network or real backend.
+
+use std::sync::{Arc, Mutex};
+
+use gorp::message_handler::traits::MockPlatform;
+use gorp::server::ServerState;
+use gorp::traits::{AttachmentInfo, MessagingPlatform};
+use gorp::transcription::Transcriber;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{pump, TestState};
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::telegram(tmp.path(), USER_ID).build()
+}
+
+#[tokio::test]
+async fn test_create_on_telegram_sends_matrix_only_notice() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!create research"));
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![(
+            CHAT_ID.to_string(),
+            "The !create command is only available on Matrix.".to_string()
+        )]
+    );
+    assert!(state
+        .session_store
+        .get_by_name("research")
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_create_in_telegram_topic_binds_only_that_topic() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+    let topic = format!("{}:7", CHAT_ID);
+
+    platform.inject(platform.message(&topic, USER_ID, "!create research"));
+    pump(&mut stream, &platform, &state, 1).await;
+
+    let channel = state
+        .session_store
+        .get_by_name("research")
+        .unwrap()
+        .expect("topic should be bound");
+    assert_eq!(channel.room_id, topic);
+    assert!(state
+        .session_store
+        .get_by_room(&format!("{}:8", CHAT_ID))
+        .unwrap()
+        .is_none());
+    assert!(state.session_store.get_by_room(CHAT_ID).unwrap().is_none());
+
+    // The same topic can't be bound twice
+    platform.inject(platform.message(&topic, USER_ID, "!create other"));
+    pump(&mut stream, &platform, &state, 1).await;
+    assert!(platform
+        .sent_text()
+        .last()
+        .unwrap()
+        .1
+        .contains("already channel 'research'"));
+    assert!(state.session_store.get_by_name("other").unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_command_alias_resolves_to_canonical_command() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!new research"));
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![(
+            CHAT_ID.to_string(),
+            "The !create command is only available on Matrix.".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_command_errors_are_ephemeral_where_supported() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("slack").with_ephemeral();
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!frobnicate"));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!create research"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    assert!(platform.sent_text().is_empty());
+    let private = platform.ephemeral_sent();
+    assert_eq!(private.len(), 2);
+    assert!(private
+        .iter()
+        .all(|(channel, user, _)| channel == CHAT_ID && user == USER_ID));
+    assert!(private[0].2.starts_with("Unknown command"));
+    assert_eq!(
+        private[1].2,
+        "The !create command is only available on Matrix."
+    );
+}
+
+#[tokio::test]
+async fn test_conversation_sends_in_order() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "hello"));
+    platform.inject(platform.message(CHAT_ID, "99", "not on the allow list"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    platform.inject(platform.message(CHAT_ID, USER_ID, "ping"));
+    pump(&mut stream, &platform, &state, 1).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 2, "unexpected sends: {:?}", sent);
+    assert!(sent.iter().all(|(channel_id, _)| channel_id == CHAT_ID));
+    assert_eq!(
+        sent[0].1,
+        "No Claude channel attached to this room. Use !create <name> to create one."
+    );
+    assert!(sent[1].1.contains("Mock: no expectation for 'ping'"));
+}
+
+#[tokio::test]
+async fn test_replayed_event_is_sent_once() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let msg = platform.message(CHAT_ID, USER_ID, "hello");
+    platform.inject(msg.clone());
+    platform.inject(msg);
+    pump(&mut stream, &platform, &state, 2).await;
+
+    assert_eq!(platform.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn test_threaded_message_is_answered_in_its_thread() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut threaded = platform.message(CHAT_ID, USER_ID, "in the thread");
+    threaded.thread_id = Some("$root".to_string());
+    platform.inject(threaded);
+    platform.inject(platform.message(CHAT_ID, USER_ID, "top level"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 2, "unexpected sends: {:?}", sent);
+    assert!(sent[0]
+        .1
+        .contains("Mock: no expectation for 'in the thread'"));
+    assert_eq!(
+        platform.sent_threads(),
+        vec![Some("$root".to_string()), None]
+    );
+}
+
+#[tokio::test]
+async fn test_command_replies_follow_the_thread() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut threaded = platform.message(CHAT_ID, USER_ID, "!help");
+    threaded.thread_id = Some("$root".to_string());
+    platform.inject(threaded);
+    platform.inject(platform.message(CHAT_ID, USER_ID, "no channel here"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    let threads = platform.sent_threads();
+    assert_eq!(
+        threads.len(),
+        2,
+        "unexpected sends: {:?}",
+        platform.sent_text()
+    );
+    assert_eq!(threads[0].as_deref(), Some("$root"));
+    assert_eq!(threads[1], None);
+}
+
+#[tokio::test]
+async fn test_typing_indicator_covers_agent_turn() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, "99", "not on the allow list"));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "ping"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    assert_eq!(
+        platform.typing_events(),
+        vec![(CHAT_ID.to_string(), true), (CHAT_ID.to_string(), false)]
+    );
+}
+
+#[tokio::test]
+async fn test_allowed_messages_get_a_read_receipt() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let ignored = platform.message(CHAT_ID, "99", "not on the allow list");
+    let seen = platform.message(CHAT_ID, USER_ID, "ping");
+    let seen_id = seen.event_id.clone();
+    platform.inject(ignored);
+    platform.inject(seen);
+    pump(&mut stream, &platform, &state, 2).await;
+
+    assert_eq!(
+        platform.read_receipts(),
+        vec![(CHAT_ID.to_string(), seen_id)]
+    );
+}
+
+#[tokio::test]
+async fn test_commands_get_a_read_receipt_like_chat_messages() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let command = platform.message(CHAT_ID, USER_ID, "!help");
+    let chat = platform.message(CHAT_ID, USER_ID, "ping");
+    let seen = vec![
+        (CHAT_ID.to_string(), command.event_id.clone()),
+        (CHAT_ID.to_string(), chat.event_id.clone()),
+    ];
+    platform.inject(command);
+    platform.inject(chat);
+    pump(&mut stream, &platform, &state, 2).await;
+
+    // One receipt each, from a single call ahead of both the command and chat paths
+    assert_eq!(platform.read_receipts(), seen);
+}
+
+#[tokio::test]
+async fn test_all_attachments_in_a_message_reach_the_prompt() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let channel = state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram")
+        .with_file("shot1", b"first")
+        .with_file("shot2", b"second")
+        .with_file("notes", b"third");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut msg = platform.message(CHAT_ID, USER_ID, "what changed?");
+    msg.attachments = [
+        ("shot1", "screenshot.png", "image/png"),
+        ("shot2", "screenshot.png", "image/png"),
+        ("notes", "notes.txt", "text/plain"),
+    ]
+    .into_iter()
+    .map(|(source_id, filename, mime_type)| AttachmentInfo {
+        source_id: source_id.to_string(),
+        filename: filename.to_string(),
+        mime_type: mime_type.to_string(),
+        size: None,
+    })
+    .collect();
+    platform.inject(msg);
+    pump(&mut stream, &platform, &state, 1).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 1, "unexpected sends: {:?}", sent);
+    let echoed = &sent[0].1;
+    assert_eq!(echoed.matches("[Attached image: ").count(), 2);
+    assert_eq!(echoed.matches("[Attached file: ").count(), 1);
+    assert!(echoed.contains("what changed?"));
+
+    let saved = std::fs::read_dir(std::path::Path::new(&channel.directory).join("attachments"))
+        .unwrap()
+        .count();
+    assert_eq!(saved, 3);
+}
+
+#[tokio::test]
+async fn test_oversized_attachment_is_refused_with_the_limit() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let channel = state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    // No file registered: a download attempt would fail with a different message
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut msg = platform.message(CHAT_ID, USER_ID, "watch this");
+    msg.attachments = vec![AttachmentInfo {
+        source_id: "video".to_string(),
+        filename: "demo.mp4".to_string(),
+        mime_type: "video/mp4".to_string(),
+        size: Some(2 * 1024 * 1024 * 1024),
+    }];
+    platform.inject(msg);
+    pump(&mut stream, &platform, &state, 1).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 1, "unexpected sends: {:?}", sent);
+    assert_eq!(
+        sent[0].1,
+        "🚫 Attachment refused: demo.mp4 is 2 GB, over the 25 MB attachment limit"
+    );
+    assert!(!std::path::Path::new(&channel.directory)
+        .join("attachments")
+        .exists());
+}
+
+#[tokio::test]
+async fn test_delete_waits_for_the_requesters_confirmation() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel_on("telegram", "research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram").with_confirmations();
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut ask = platform.message(USER_ID, USER_ID, "!delete research");
+    ask.is_direct = true;
+    platform.inject(ask);
+    pump(&mut stream, &platform, &state, 1).await;
+
+    // Nothing happens until the press comes back
+    let asked = platform.sent_confirmations();
+    assert_eq!(asked.len(), 1);
+    let (channel_id, requester, text, token) = asked[0].clone();
+    assert_eq!(
+        (channel_id.as_str(), requester.as_str()),
+        (USER_ID, USER_ID)
+    );
+    assert!(text.contains("Delete channel 'research'?"), "{}", text);
+    assert!(platform.sent_text().is_empty());
+    assert!(state
+        .session_store
+        .get_by_name_on("telegram", "research")
+        .unwrap()
+        .is_some());
+
+    let mut press = platform.message(USER_ID, USER_ID, &format!("!confirm {}", token));
+    press.is_direct = true;
+    platform.inject(press.clone());
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert!(state
+        .session_store
+        .get_by_name_on("telegram", "research")
+        .unwrap()
+        .is_none());
+    assert!(platform.has_sent_containing("✅ Deleted channel: research"));
+
+    // A second press of the same button finds nothing to run
+    press.event_id = "$again".to_string();
+    platform.inject(press);
+    pump(&mut stream, &platform, &state, 1).await;
+    assert!(platform.has_sent_containing("expired or was already answered"));
+}
+
+#[tokio::test]
+async fn test_cancelled_delete_keeps_the_channel() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel_on("telegram", "research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram").with_confirmations();
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut ask = platform.message(USER_ID, USER_ID, "!delete research");
+    ask.is_direct = true;
+    platform.inject(ask);
+    pump(&mut stream, &platform, &state, 1).await;
+    let token = platform.sent_confirmations()[0].3.clone();
+
+    let mut press = platform.message(USER_ID, USER_ID, &format!("!cancel {}", token));
+    press.is_direct = true;
+    platform.inject(press);
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![(
+            USER_ID.to_string(),
+            "Cancelled; nothing was changed.".to_string()
+        )]
+    );
+    assert!(state
+        .session_store
+        .get_by_name_on("telegram", "research")
+        .unwrap()
+        .is_some());
+    assert!(state.confirmations.is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_of_unknown_channel_asks_nothing() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram").with_confirmations();
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let mut ask = platform.message(USER_ID, USER_ID, "!delete nowhere");
+    ask.is_direct = true;
+    platform.inject(ask);
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert!(platform.sent_confirmations().is_empty());
+    assert!(platform.has_sent_containing("Channel 'nowhere' not found"));
+    assert!(state.confirmations.is_empty());
+}
+
+/// Transcribes every clip as its bytes and remembers the language hint it was given
+#[derive(Default)]
+struct RecordingTranscriber {
+    languages: Mutex<Vec<Option<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Transcriber for RecordingTranscriber {
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        _filename: &str,
+        _mime_type: &str,
+        language: Option<&str>,
+    ) -> anyhow::Result<String> {
+        self.languages
+            .lock()
+            .unwrap()
+            .push(language.map(String::from));
+        Ok(String::from_utf8(audio)?)
+    }
+}
+
+#[tokio::test]
+async fn test_transcription_lang_sets_the_hint_voice_notes_use() {
+    let tmp = TempDir::new().unwrap();
+    let transcriber = Arc::new(RecordingTranscriber::default());
+    let state = TestState::telegram(tmp.path(), USER_ID)
+        .transcriber(transcriber.clone())
+        .build();
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram").with_file("clip", b"deploy the api");
+    let mut stream = platform.event_stream().await.unwrap();
+    let voice_note = || {
+        let mut msg = platform.message(CHAT_ID, USER_ID, "");
+        msg.attachments = vec![AttachmentInfo {
+            source_id: "clip".to_string(),
+            filename: "voice.ogg".to_string(),
+            mime_type: "audio/ogg".to_string(),
+            size: None,
+        }];
+        msg
+    };
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!transcription lang es"));
+    platform.inject(voice_note());
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!transcription lang auto"));
+    platform.inject(voice_note());
+    pump(&mut stream, &platform, &state, 4).await;
+
+    assert!(platform.has_sent_containing("Voice notes in research are now transcribed as es"));
+    assert!(platform.has_sent_containing("back to auto-detecting"));
+    assert!(platform.has_sent_containing("[Voice message transcript] deploy the api"));
+    assert_eq!(
+        *transcriber.languages.lock().unwrap(),
+        vec![Some("es".to_string()), None]
+    );
+    assert_eq!(
+        state
+            .session_store
+            .get_transcription_language("research")
+            .unwrap(),
+        None
+    );
+}
@@ -30,6 +30,8 @@ fn dispatch_msg(id: &str, body: &str) -> BusMessage {
         sender: "test-user".to_string(),
         body: body.to_string(),
         timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
     }
 }
 
@@ -509,6 +511,8 @@ async fn test_agent_message_no_warm_manager() {
         sender: "test-user".to_string(),
         body: "summarize the paper".to_string(),
         timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
     };
     bus.publish_inbound(msg);
 
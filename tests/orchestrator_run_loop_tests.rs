@@ -3,9 +3,9 @@
 
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use gorp::bus::{BusMessage, MessageBus, MessageSource, ResponseContent, SessionTarget};
-use gorp::orchestrator::Orchestrator;
+use gorp::orchestrator::{save_silent_response, Orchestrator, QUIET_RESULTS_DIR};
 use gorp_core::session::SessionStore;
 use tempfile::TempDir;
 use tokio::time::{timeout, Duration};
@@ -30,6 +30,8 @@ fn make_bus_message(id: &str, body: &str, target: SessionTarget) -> BusMessage {
         sender: "test-user".to_string(),
         body: body.to_string(),
         timestamp: Utc::now(),
+        silent: false,
+        urgent: false,
     }
 }
 
@@ -238,7 +240,10 @@ async fn test_orchestrator_unknown_command() {
                 text
             );
         }
-        other => panic!("Expected SystemNotice about unknown command, got {:?}", other),
+        other => panic!(
+            "Expected SystemNotice about unknown command, got {:?}",
+            other
+        ),
     }
 
     handle.abort();
@@ -319,3 +324,24 @@ async fn test_orchestrator_create_command() {
 
     handle.abort();
 }
+
+#[test]
+fn test_save_silent_response_writes_to_workspace() {
+    let tmp = TempDir::new().unwrap();
+    let at = Utc.with_ymd_and_hms(2026, 3, 2, 23, 30, 0).unwrap();
+    let path = save_silent_response(
+        tmp.path().to_str().unwrap(),
+        "sched-abc/1",
+        "Nightly summary",
+        at,
+    )
+    .unwrap();
+
+    assert_eq!(
+        path,
+        tmp.path()
+            .join(QUIET_RESULTS_DIR)
+            .join("20260302_233000_sched-abc_1.md")
+    );
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "Nightly summary");
+}
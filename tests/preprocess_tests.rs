@@ -0,0 +1,213 @@
+// ABOUTME: Tests for the prompt preprocessing pipeline and the URL-fetch preprocessor.
+// ABOUTME: Uses a canned page fetcher and the mock backend, which echoes unexpected prompts back.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use gorp::config::UrlFetchConfig;
+use gorp::message_handler::handle_text;
+use gorp::preprocess::{PageFetcher, PreprocessPipeline, PromptPreprocessor, UrlFetchPreprocessor};
+use gorp::session::{Channel, SessionStore};
+use gorp::usage::InvocationOrigin;
+use gorp::warm_session::{create_shared_manager, SharedWarmSessionManager, WarmConfig};
+use tempfile::TempDir;
+
+/// Serves pages from a map; anything else fails like an unreachable host
+#[derive(Default)]
+struct CannedFetcher {
+    pages: HashMap<String, String>,
+    requested: Mutex<Vec<String>>,
+}
+
+impl CannedFetcher {
+    fn with_page(mut self, url: &str, text: &str) -> Self {
+        self.pages.insert(url.to_string(), text.to_string());
+        self
+    }
+}
+
+#[async_trait]
+impl PageFetcher for CannedFetcher {
+    async fn fetch(&self, url: &str) -> Result<String> {
+        self.requested.lock().unwrap().push(url.to_string());
+        self.pages
+            .get(url)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("connection refused"))
+    }
+}
+
+/// Always fails, to check the pipeline keeps going without it
+struct Broken;
+
+#[async_trait]
+impl PromptPreprocessor for Broken {
+    fn name(&self) -> &str {
+        "broken"
+    }
+
+    async fn process(&self, _body: String, _channel: &Channel) -> Result<String> {
+        anyhow::bail!("preprocessor exploded")
+    }
+}
+
+/// Appends a marker, to check ordering and chaining
+struct Suffix(&'static str);
+
+#[async_trait]
+impl PromptPreprocessor for Suffix {
+    fn name(&self) -> &str {
+        "suffix"
+    }
+
+    async fn process(&self, body: String, _channel: &Channel) -> Result<String> {
+        Ok(format!("{}{}", body, self.0))
+    }
+}
+
+fn url_fetch(fetcher: Arc<CannedFetcher>, max_chars: usize) -> UrlFetchPreprocessor {
+    let config = UrlFetchConfig {
+        enabled: true,
+        max_urls: 2,
+        max_chars,
+        ..Default::default()
+    };
+    UrlFetchPreprocessor::new(fetcher, &config)
+}
+
+fn mock_manager() -> SharedWarmSessionManager {
+    create_shared_manager(WarmConfig {
+        keep_alive_duration: Duration::from_secs(60),
+        pre_warm_lead_time: Duration::from_secs(30),
+        agent_binary: "claude".to_string(),
+        backend_type: "mock".to_string(),
+        model: None,
+        max_tokens: None,
+        global_system_prompt_path: None,
+        mcp_servers: vec![],
+        max_response_chars: 0,
+        save_truncated_responses: false,
+    })
+}
+
+fn test_channel(tmp: &TempDir) -> (SessionStore, Channel) {
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = store.create_channel("links", "!links:example.com").unwrap();
+    (store, channel)
+}
+
+#[tokio::test]
+async fn test_url_fetch_appends_page_text() {
+    let tmp = TempDir::new().unwrap();
+    let (_store, channel) = test_channel(&tmp);
+    let fetcher = Arc::new(
+        CannedFetcher::default().with_page("https://example.com/notes", "Version 2 ships Friday."),
+    );
+    let pipeline = PreprocessPipeline::new().with(url_fetch(fetcher.clone(), 8_000));
+
+    let out = pipeline
+        .run("summarize https://example.com/notes.".to_string(), &channel)
+        .await;
+
+    assert!(
+        out.starts_with("summarize https://example.com/notes."),
+        "{}",
+        out
+    );
+    assert!(
+        out.contains("<page url=\"https://example.com/notes\">\nVersion 2 ships Friday.\n</page>"),
+        "{}",
+        out
+    );
+    assert_eq!(
+        *fetcher.requested.lock().unwrap(),
+        vec!["https://example.com/notes".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_url_fetch_caps_links_and_page_size() {
+    let tmp = TempDir::new().unwrap();
+    let (_store, channel) = test_channel(&tmp);
+    let fetcher = Arc::new(
+        CannedFetcher::default()
+            .with_page("https://a.example/", &"a".repeat(50))
+            .with_page("https://b.example/", "bee")
+            .with_page("https://c.example/", "sea"),
+    );
+    let pipeline = PreprocessPipeline::new().with(url_fetch(fetcher.clone(), 10));
+
+    let out = pipeline
+        .run(
+            "https://a.example/ https://b.example/ https://c.example/".to_string(),
+            &channel,
+        )
+        .await;
+
+    assert!(
+        out.contains(&format!("{}\n[…truncated]", "a".repeat(10))),
+        "{}",
+        out
+    );
+    assert!(out.contains("bee"), "{}", out);
+    assert!(!out.contains("sea"), "only two links are fetched: {}", out);
+    assert_eq!(fetcher.requested.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_failures_pass_the_prompt_through() {
+    let tmp = TempDir::new().unwrap();
+    let (_store, channel) = test_channel(&tmp);
+    let fetcher = Arc::new(CannedFetcher::default());
+    let pipeline = PreprocessPipeline::new()
+        .with(url_fetch(fetcher, 8_000))
+        .with(Broken)
+        .with(Suffix(" [checked]"));
+
+    let body = "what's at https://down.example/status?".to_string();
+    let out = pipeline.run(body.clone(), &channel).await;
+
+    // The dead link adds nothing, the broken step is skipped, later steps still run
+    assert_eq!(out, format!("{} [checked]", body));
+}
+
+#[tokio::test]
+async fn test_handle_text_sends_preprocessed_prompt() {
+    let tmp = TempDir::new().unwrap();
+    let (store, channel) = test_channel(&tmp);
+    let manager = mock_manager();
+    let fetcher = Arc::new(
+        CannedFetcher::default().with_page("https://example.com/spec", "The spec says 42."),
+    );
+    manager
+        .write()
+        .await
+        .set_preprocessors(PreprocessPipeline::new().with(url_fetch(fetcher.clone(), 8_000)));
+
+    let reply = handle_text(
+        "read https://example.com/spec",
+        &channel,
+        &store,
+        &manager,
+        InvocationOrigin::User,
+    )
+    .await
+    .unwrap();
+    assert!(reply.contains("The spec says 42."), "{}", reply);
+
+    // Internal setup prompts go to the agent exactly as written
+    let reply = handle_text(
+        "setup https://example.com/spec",
+        &channel,
+        &store,
+        &manager,
+        InvocationOrigin::Internal,
+    )
+    .await
+    .unwrap();
+    assert!(!reply.contains("The spec says 42."), "{}", reply);
+    assert_eq!(fetcher.requested.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,94 @@
+// ABOUTME: Tests for per-user rate limiting in handle_incoming.
+// ABOUTME: Drives a burst of messages through MockPlatform and checks what gets through.
+
+use std::sync::Arc;
+
+use gorp::config::LimitsConfig;
+use gorp::message_handler::traits::MockPlatform;
+use gorp::server::ServerState;
+use gorp::traits::MessagingPlatform;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{pump, TestState};
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::telegram(tmp.path(), USER_ID)
+        .config(|config| {
+            config.limits = LimitsConfig {
+                messages_per_minute: 1,
+                burst: 2,
+            }
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn test_burst_is_answered_then_limited_with_one_notice() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    for body in ["one", "two", "three", "four"] {
+        platform.inject(platform.message(CHAT_ID, USER_ID, body));
+    }
+    pump(&mut stream, &platform, &state, 4).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 3, "unexpected sends: {:?}", sent);
+    assert!(sent[0].1.contains("Mock: no expectation for 'one'"));
+    assert!(sent[1].1.contains("Mock: no expectation for 'two'"));
+    assert!(sent[2].1.starts_with("⏳ You're sending messages faster"));
+    assert!(!platform.has_sent_containing("'three'"));
+}
+
+#[tokio::test]
+async fn test_help_and_status_are_exempt() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    for body in ["!create a", "!create b", "!create c", "!help", "!status"] {
+        platform.inject(platform.message(CHAT_ID, USER_ID, body));
+    }
+    pump(&mut stream, &platform, &state, 5).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 5, "unexpected sends: {:?}", sent);
+    assert!(sent[2].1.starts_with("⏳"));
+    assert!(!sent[3].1.starts_with("⏳"));
+    assert!(!sent[4].1.starts_with("⏳"));
+}
+
+#[tokio::test]
+async fn test_senders_are_limited_independently() {
+    let tmp = TempDir::new().unwrap();
+    let mut state = test_state(&tmp);
+    let mut config = (*state.config).clone();
+    config.telegram.as_mut().unwrap().allowed_users.push(43);
+    state.config = Arc::new(config);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    for body in ["one", "two", "three"] {
+        platform.inject(platform.message(CHAT_ID, USER_ID, body));
+    }
+    platform.inject(platform.message(CHAT_ID, "43", "mine"));
+    pump(&mut stream, &platform, &state, 4).await;
+
+    assert!(platform.has_sent_containing("Mock: no expectation for 'mine'"));
+}
@@ -0,0 +1,70 @@
+// ABOUTME: Tests that handle_text enforces backend.max_response_chars with a truncation notice.
+// ABOUTME: Uses the mock backend, whose unscripted reply echoes the prompt, to produce long output.
+
+use std::time::Duration;
+
+use gorp::message_handler::handle_text;
+use gorp::session::SessionStore;
+use gorp::usage::InvocationOrigin;
+use gorp::warm_session::{create_shared_manager, SharedWarmSessionManager, WarmConfig};
+use tempfile::TempDir;
+
+fn mock_manager(max_response_chars: usize) -> SharedWarmSessionManager {
+    create_shared_manager(WarmConfig {
+        keep_alive_duration: Duration::from_secs(60),
+        pre_warm_lead_time: Duration::from_secs(30),
+        agent_binary: "claude".to_string(),
+        backend_type: "mock".to_string(),
+        model: None,
+        max_tokens: None,
+        global_system_prompt_path: None,
+        mcp_servers: vec![],
+        max_response_chars,
+        save_truncated_responses: true,
+    })
+}
+
+#[tokio::test]
+async fn test_long_response_is_truncated_and_saved() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = store.create_channel("cap", "!cap:example.com").unwrap();
+    let prompt = "lorem ipsum ".repeat(50);
+
+    let reply = handle_text(
+        &prompt,
+        &channel,
+        &store,
+        &mock_manager(100),
+        InvocationOrigin::User,
+    )
+    .await
+    .unwrap();
+
+    assert!(reply.contains("…response truncated ("), "{}", reply);
+    let relative = reply.rsplit("Full response: ").next().unwrap();
+    let full =
+        std::fs::read_to_string(std::path::Path::new(&channel.directory).join(relative)).unwrap();
+    assert!(full.contains(prompt.trim_end()));
+}
+
+#[tokio::test]
+async fn test_zero_limit_leaves_response_alone() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = store.create_channel("cap", "!cap:example.com").unwrap();
+    let prompt = "lorem ipsum ".repeat(50);
+
+    let reply = handle_text(
+        &prompt,
+        &channel,
+        &store,
+        &mock_manager(0),
+        InvocationOrigin::User,
+    )
+    .await
+    .unwrap();
+
+    assert!(!reply.contains("truncated"));
+    assert!(reply.contains(prompt.trim_end()));
+}
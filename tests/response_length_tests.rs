@@ -0,0 +1,69 @@
+// ABOUTME: Tests that a channel's !length setting reaches the agent as a prompt directive.
+// ABOUTME: Uses the mock backend, which echoes unexpected prompts back in its reply.
+
+use std::time::Duration;
+
+use gorp::message_handler::handle_text;
+use gorp::message_handler::response_length::{set_response_length, LengthSetting, ResponseLength};
+use gorp::session::SessionStore;
+use gorp::usage::InvocationOrigin;
+use gorp::warm_session::{create_shared_manager, SharedWarmSessionManager, WarmConfig};
+use tempfile::TempDir;
+
+fn mock_manager() -> SharedWarmSessionManager {
+    create_shared_manager(WarmConfig {
+        keep_alive_duration: Duration::from_secs(60),
+        pre_warm_lead_time: Duration::from_secs(30),
+        agent_binary: "claude".to_string(),
+        backend_type: "mock".to_string(),
+        model: None,
+        max_tokens: None,
+        global_system_prompt_path: None,
+        mcp_servers: vec![],
+        max_response_chars: 0,
+        save_truncated_responses: false,
+    })
+}
+
+async fn prompt_seen_with(length: ResponseLength, origin: InvocationOrigin) -> String {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = store
+        .create_channel("length", "!length:example.com")
+        .unwrap();
+    set_response_length(
+        &channel.directory,
+        LengthSetting {
+            length,
+            strict: false,
+        },
+    )
+    .unwrap();
+
+    handle_text("what changed?", &channel, &store, &mock_manager(), origin)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_directive_injected_per_setting() {
+    let brief = prompt_seen_with(ResponseLength::Brief, InvocationOrigin::User).await;
+    assert!(brief.contains("[Response length: brief]"), "{}", brief);
+
+    let detailed = prompt_seen_with(ResponseLength::Detailed, InvocationOrigin::User).await;
+    assert!(
+        detailed.contains("[Response length: detailed]"),
+        "{}",
+        detailed
+    );
+
+    let normal = prompt_seen_with(ResponseLength::Normal, InvocationOrigin::User).await;
+    assert!(!normal.contains("[Response length:"), "{}", normal);
+    assert!(normal.contains("what changed?"));
+}
+
+#[tokio::test]
+async fn test_internal_prompts_skip_directive() {
+    let reply = prompt_seen_with(ResponseLength::Brief, InvocationOrigin::Internal).await;
+    assert!(!reply.contains("[Response length:"), "{}", reply);
+}
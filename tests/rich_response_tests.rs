@@ -0,0 +1,137 @@
+// ABOUTME: Tests for multi-part responses (text, files, suggested actions) in handle_incoming.
+// ABOUTME: Leaves a .gorp/response.json manifest in the channel and checks what MockPlatform receives.
+
+use std::path::Path;
+
+use gorp::message_handler::traits::MockPlatform;
+use gorp::rich_response::RESPONSE_MANIFEST;
+use gorp::server::ServerState;
+use gorp::traits::{MessageContent, MessagingPlatform};
+use tempfile::TempDir;
+
+mod common;
+
+use common::{pump, TestState};
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::telegram(tmp.path(), USER_ID).build()
+}
+
+/// Send one message to a channel whose agent left `manifest` behind, with `files` in its workspace
+async fn reply_with_manifest(
+    platform: &MockPlatform,
+    manifest: &str,
+    files: &[(&str, &str)],
+) -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let channel = state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let workspace = Path::new(&channel.directory);
+    std::fs::create_dir_all(workspace.join(".gorp")).unwrap();
+    std::fs::write(workspace.join(RESPONSE_MANIFEST), manifest).unwrap();
+    for (path, contents) in files {
+        let path = workspace.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    let mut stream = platform.event_stream().await.unwrap();
+    platform.inject(platform.message(CHAT_ID, USER_ID, "make the report"));
+    pump(&mut stream, platform, &state, 1).await;
+
+    assert!(!workspace.join(RESPONSE_MANIFEST).exists());
+    tmp
+}
+
+const REPORT_MANIFEST: &str = r#"{
+    "parts": [
+        {"type": "text", "text": "Here's the **report**."},
+        {"type": "file", "path": "out/report.csv", "caption": "Q3 numbers"},
+        {"type": "actions", "text": "What next?", "actions": ["Chart it", "Email it"]}
+    ]
+}"#;
+
+#[tokio::test]
+async fn test_parts_are_sent_in_order_with_buttons() {
+    let platform = MockPlatform::new("telegram").with_action_buttons();
+    let _tmp = reply_with_manifest(
+        &platform,
+        REPORT_MANIFEST,
+        &[("out/report.csv", "q,n\n3,7")],
+    )
+    .await;
+
+    let sent = platform.sent();
+    assert_eq!(sent.len(), 2, "unexpected sends: {:?}", sent);
+    match &sent[0].1 {
+        MessageContent::Html { plain, html } => {
+            assert_eq!(plain, "Here's the **report**.");
+            assert!(html.contains("<strong>report</strong>"));
+        }
+        other => panic!("expected text first, got {:?}", other),
+    }
+    match &sent[1].1 {
+        MessageContent::Attachment {
+            filename,
+            data,
+            mime_type,
+            caption,
+        } => {
+            assert_eq!(filename, "report.csv");
+            assert_eq!(data, b"q,n\n3,7");
+            assert_eq!(mime_type, "text/csv");
+            assert_eq!(caption.as_deref(), Some("Q3 numbers"));
+        }
+        other => panic!("expected the file second, got {:?}", other),
+    }
+    assert_eq!(
+        platform.sent_actions(),
+        vec![(
+            CHAT_ID.to_string(),
+            "What next?".to_string(),
+            vec!["Chart it".to_string(), "Email it".to_string()],
+        )]
+    );
+    assert!(!platform.has_sent_containing("Mock: no expectation"));
+}
+
+#[tokio::test]
+async fn test_actions_become_a_list_without_buttons() {
+    let platform = MockPlatform::new("telegram");
+    let _tmp = reply_with_manifest(&platform, REPORT_MANIFEST, &[("out/report.csv", "q,n")]).await;
+
+    assert!(platform.sent_actions().is_empty());
+    assert_eq!(
+        platform.sent_text().last().map(|(_, text)| text.as_str()),
+        Some("What next?\n1. Chart it\n2. Email it")
+    );
+}
+
+#[tokio::test]
+async fn test_file_outside_workspace_is_not_attached() {
+    let platform = MockPlatform::new("telegram");
+    let manifest = r#"{"parts": [{"type": "file", "path": "../../secret.txt"}]}"#;
+    let _tmp = reply_with_manifest(&platform, manifest, &[]).await;
+
+    let sent = platform.sent();
+    assert_eq!(sent.len(), 1);
+    assert!(matches!(
+        &sent[0].1,
+        MessageContent::Plain(text) if text.starts_with("⚠️ Couldn't attach ../../secret.txt")
+    ));
+}
+
+#[tokio::test]
+async fn test_broken_manifest_falls_back_to_plain_reply() {
+    let platform = MockPlatform::new("telegram");
+    let _tmp = reply_with_manifest(&platform, "{not json", &[]).await;
+
+    assert_eq!(platform.sent().len(), 1);
+    assert!(platform.has_sent_containing("Mock: no expectation for 'make the report'"));
+}
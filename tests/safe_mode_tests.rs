@@ -0,0 +1,163 @@
+// ABOUTME: Tests that safe mode blocks every surface that acts: chat, commands, webhooks and notifiers.
+// ABOUTME: Each refusal must explain itself, and leaving safe mode must let the same request through.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use gorp::delivery::start_delivery_flusher;
+use gorp::message_handler::traits::MockPlatform;
+use gorp::platform::PlatformRegistry;
+use gorp::runtime_mode::RuntimeMode;
+use gorp::server::ServerState;
+use gorp::traits::MessagingPlatform;
+use gorp::webhook::webhook_router;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+mod common;
+
+use common::{pump, TestState};
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+
+/// State with a channel attached to CHAT_ID, running in `mode`
+async fn test_state(tmp: &TempDir, mode: &RuntimeMode) -> ServerState {
+    let state = TestState::telegram(tmp.path(), USER_ID)
+        .config(|config| config.runtime.safe_mode = mode.is_safe())
+        .build();
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    state
+        .warm_manager
+        .write()
+        .await
+        .set_runtime_mode(mode.clone());
+    state
+}
+
+#[tokio::test]
+async fn test_chat_message_is_refused_until_safe_mode_ends() {
+    let tmp = TempDir::new().unwrap();
+    let mode = RuntimeMode::new(true);
+    let state = test_state(&tmp, &mode).await;
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "summarize the repo"));
+    pump(&mut stream, &platform, &state, 1).await;
+    assert!(platform.has_sent_containing("Safe mode is active, so agents are not being run"));
+    assert!(!platform.has_sent_containing("Mock:"));
+
+    mode.leave_safe_mode();
+    platform.inject(platform.message(CHAT_ID, USER_ID, "summarize the repo"));
+    pump(&mut stream, &platform, &state, 1).await;
+    assert!(platform.has_sent_containing("Mock: no expectation for 'summarize the repo"));
+}
+
+#[tokio::test]
+async fn test_read_only_commands_answer_and_others_are_refused() {
+    let tmp = TempDir::new().unwrap();
+    let mode = RuntimeMode::new(true);
+    let state = test_state(&tmp, &mode).await;
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!status"));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!reset"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    assert!(platform.has_sent_containing("Channel: research"));
+    assert!(platform.has_sent_containing("Safe mode is active, so !reset is unavailable"));
+}
+
+#[tokio::test]
+async fn test_webhook_delivery_gets_503_in_safe_mode() {
+    let tmp = TempDir::new().unwrap();
+    let mode = RuntimeMode::new(true);
+    let state = test_state(&tmp, &mode).await;
+    let session_id = state
+        .session_store
+        .get_by_name("research")
+        .unwrap()
+        .unwrap()
+        .session_id;
+    let router = webhook_router(
+        (*state.session_store).clone(),
+        Arc::clone(&state.bus),
+        Arc::clone(&state.config),
+        mode.clone(),
+    );
+    let deliver = || {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/webhook/session/{}", session_id))
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"prompt": "new alert"}"#))
+            .unwrap()
+    };
+
+    let response = router.clone().oneshot(deliver()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["success"], false);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("webhook deliveries are rejected"));
+
+    mode.leave_safe_mode();
+    let response = router.oneshot(deliver()).await.unwrap();
+    assert_ne!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_held_messages_stay_queued_in_safe_mode() {
+    let tmp = TempDir::new().unwrap();
+    let mode = RuntimeMode::new(true);
+    let state = test_state(&tmp, &mode).await;
+    state
+        .session_store
+        .hold_message("research", Some(("telegram", CHAT_ID)), "overnight report")
+        .unwrap();
+
+    let platform = MockPlatform::new("telegram");
+    let sent = platform.sent.clone();
+    let mut registry = PlatformRegistry::new();
+    registry.register(Box::new(platform));
+    let registry = Arc::new(tokio::sync::RwLock::new(registry));
+    tokio::spawn(start_delivery_flusher(
+        (*state.session_store).clone(),
+        Arc::clone(&state.bus),
+        registry,
+        Duration::from_secs(60),
+        mode.clone(),
+    ));
+
+    tokio::time::sleep(Duration::from_secs(150)).await;
+    assert!(sent.lock().unwrap().is_empty());
+    assert_eq!(
+        state
+            .session_store
+            .list_held_messages("research")
+            .unwrap()
+            .len(),
+        1
+    );
+
+    mode.leave_safe_mode();
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    assert_eq!(sent.lock().unwrap().len(), 1);
+    assert!(state
+        .session_store
+        .list_held_messages("research")
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,400 @@
+// ABOUTME: Tests for when the scheduler loop fires: on the deadline, on new schedules, after oversleeping.
+// ABOUTME: Runs on tokio's paused clock with the scheduler's wall clock derived from it.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use gorp::bus::{BusMessage, BusResponse, MessageBus, ResponseContent};
+use gorp::config::{
+    AccessConfig, AttachmentsConfig, BackendConfig, CommandsConfig, Config, DedupConfig,
+    EditsConfig, I18nConfig, LimitsConfig, LoggingConfig, MaintenanceConfig, MetricsConfig,
+    RolesConfig, RuntimeConfig, SchedulerConfig, SendGuardConfig, ShutdownConfig, SigningConfig,
+    ToolApprovalConfig, WebhookConfig, WorkspaceConfig,
+};
+use gorp::runtime_mode::RuntimeMode;
+use gorp::scheduler::{
+    run_scheduler, QuietPolicy, ScheduleStatus, ScheduledPrompt, SchedulerStore,
+};
+use gorp::session::SessionStore;
+use gorp::warm_session::{create_shared_manager, WarmConfig};
+use tempfile::TempDir;
+use tokio::sync::broadcast::Receiver;
+
+const CHANNEL: &str = "reports";
+
+/// Wall-clock time at which each test starts, 20s past a whole minute
+fn base() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 20).unwrap()
+}
+
+/// Longest the loop may sleep; the tests' deadlines are all well inside it
+const MAX_SLEEP: StdDuration = StdDuration::from_secs(3600);
+
+struct Harness {
+    tmp: TempDir,
+    store: SchedulerStore,
+    bus: Arc<MessageBus>,
+    inbound: Receiver<BusMessage>,
+    clock: Clock,
+}
+
+/// Wall clock that advances with tokio's (paused) clock from a fixed start
+#[derive(Clone, Copy)]
+struct Clock {
+    base: DateTime<Utc>,
+    start: tokio::time::Instant,
+}
+
+impl Clock {
+    fn now(&self) -> DateTime<Utc> {
+        self.base + Duration::from_std(self.start.elapsed()).unwrap()
+    }
+}
+
+fn test_config(tmp: &TempDir, quiet_hours: Option<&str>) -> Config {
+    Config {
+        matrix: None,
+        telegram: None,
+        slack: None,
+        whatsapp: None,
+        irc: None,
+        zulip: None,
+        coven: None,
+        backend: BackendConfig::default(),
+        webhook: WebhookConfig {
+            port: 13000,
+            api_key: None,
+            secret: None,
+            host: "localhost".to_string(),
+            coalesce: Default::default(),
+        },
+        workspace: WorkspaceConfig {
+            path: tmp.path().to_str().unwrap().to_string(),
+            channel_scope: Default::default(),
+        },
+        scheduler: SchedulerConfig {
+            timezone: "UTC".to_string(),
+            templates: Default::default(),
+            quiet_hours: quiet_hours.map(String::from),
+            run_history: 20,
+        },
+        dedup: DedupConfig::default(),
+        edits: EditsConfig::default(),
+        i18n: I18nConfig::default(),
+        send_guard: SendGuardConfig::default(),
+        limits: LimitsConfig::default(),
+        attachments: AttachmentsConfig::default(),
+        maintenance: MaintenanceConfig::default(),
+        transcription: None,
+        logging: LoggingConfig::default(),
+        commands: CommandsConfig::default(),
+        shutdown: ShutdownConfig::default(),
+        tool_approval: ToolApprovalConfig::default(),
+        roles: RolesConfig::default(),
+        access: AccessConfig::default(),
+        preprocess: Default::default(),
+        metrics: MetricsConfig::default(),
+        runtime: RuntimeConfig::default(),
+        signing: SigningConfig::default(),
+    }
+}
+
+/// Start the scheduler loop against a fresh store holding `schedules`
+fn start(schedules: &[ScheduledPrompt]) -> Harness {
+    start_in_mode(schedules, RuntimeMode::default())
+}
+
+fn start_in_mode(schedules: &[ScheduledPrompt], mode: RuntimeMode) -> Harness {
+    start_with(schedules, mode, None)
+}
+
+fn start_with(
+    schedules: &[ScheduledPrompt],
+    mode: RuntimeMode,
+    quiet_hours: Option<&str>,
+) -> Harness {
+    let tmp = TempDir::new().unwrap();
+    let session_store = SessionStore::new(tmp.path()).unwrap();
+    session_store
+        .create_channel(CHANNEL, "!reports:example.com")
+        .unwrap();
+    let store = SchedulerStore::new(session_store.db_connection());
+    store.initialize_schema().unwrap();
+    for schedule in schedules {
+        store.create_schedule(schedule).unwrap();
+    }
+
+    let bus = Arc::new(MessageBus::new(64));
+    let inbound = bus.subscribe_inbound();
+    let warm_manager = create_shared_manager(WarmConfig {
+        keep_alive_duration: StdDuration::from_secs(60),
+        pre_warm_lead_time: StdDuration::from_secs(30),
+        agent_binary: "claude".to_string(),
+        backend_type: "mock".to_string(),
+        model: None,
+        max_tokens: None,
+        global_system_prompt_path: None,
+        mcp_servers: vec![],
+        max_response_chars: 0,
+        save_truncated_responses: false,
+    });
+    warm_manager.try_write().unwrap().set_runtime_mode(mode);
+    let clock = Clock {
+        base: base(),
+        start: tokio::time::Instant::now(),
+    };
+
+    tokio::spawn(run_scheduler(
+        store.clone(),
+        session_store,
+        Arc::clone(&bus),
+        Arc::new(test_config(&tmp, quiet_hours)),
+        MAX_SLEEP,
+        warm_manager,
+        move || clock.now(),
+    ));
+
+    Harness {
+        tmp,
+        store,
+        bus,
+        inbound,
+        clock,
+    }
+}
+
+fn schedule(id: &str, next: DateTime<Utc>, cron: Option<&str>) -> ScheduledPrompt {
+    ScheduledPrompt {
+        id: id.to_string(),
+        channel_name: CHANNEL.to_string(),
+        room_id: "!reports:example.com".to_string(),
+        prompt: format!("prompt {}", id),
+        created_by: "@user:example.com".to_string(),
+        created_at: next.to_rfc3339(),
+        execute_at: cron.is_none().then(|| next.to_rfc3339()),
+        cron_expression: cron.map(String::from),
+        last_executed_at: None,
+        next_execution_at: next.to_rfc3339(),
+        status: ScheduleStatus::Active,
+        error_message: None,
+        execution_count: 0,
+    }
+}
+
+/// Wait for the next published prompt and note when (on the test clock) it arrived
+async fn next_fire(harness: &mut Harness) -> (BusMessage, DateTime<Utc>) {
+    let msg = harness.inbound.recv().await.unwrap();
+    (msg, harness.clock.now())
+}
+
+fn assert_fired_at(fired: DateTime<Utc>, due: DateTime<Utc>) {
+    assert!(
+        fired >= due,
+        "fired at {} before its deadline {}",
+        fired,
+        due
+    );
+    assert!(
+        fired - due < Duration::seconds(1),
+        "fired at {}, more than a second after {}",
+        fired,
+        due
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_schedule_fires_on_its_deadline() {
+    // Deliberately off any whole minute or 60-second tick
+    let due = base() + Duration::milliseconds(95_500);
+    let mut harness = start(&[schedule("once", due, None)]);
+
+    let (msg, fired) = next_fire(&mut harness).await;
+    assert_eq!(msg.body, "prompt once");
+    assert_fired_at(fired, due);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_new_schedule_wakes_the_loop() {
+    let mut harness = start(&[]);
+    // Let the loop find nothing to do and settle into its long sleep
+    tokio::time::sleep(StdDuration::from_secs(10)).await;
+
+    let due = harness.clock.now() + Duration::seconds(5);
+    harness
+        .store
+        .create_schedule(&schedule("late", due, None))
+        .unwrap();
+
+    let (msg, fired) = next_fire(&mut harness).await;
+    assert_eq!(msg.body, "prompt late");
+    assert_fired_at(fired, due);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_overslept_recurring_schedule_fires_once_then_resumes() {
+    // Every minute, and the process was away for five of them
+    let missed = base() - Duration::minutes(5);
+    let mut harness = start(&[schedule("minutely", missed, Some("* * * * *"))]);
+
+    let (_, fired) = next_fire(&mut harness).await;
+    assert_fired_at(fired, base());
+    tokio::time::sleep(StdDuration::from_millis(100)).await;
+    assert!(
+        harness.inbound.try_recv().is_err(),
+        "missed runs should not be replayed"
+    );
+
+    // The next run lands on the following minute boundary
+    let (_, fired) = next_fire(&mut harness).await;
+    assert_fired_at(fired, Utc.with_ymd_and_hms(2026, 3, 2, 9, 1, 0).unwrap());
+
+    let stored = harness.store.get_by_id("minutely").unwrap().unwrap();
+    assert_eq!(stored.execution_count, 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_safe_mode_suspends_schedules_until_it_ends() {
+    let due = base() + Duration::seconds(5);
+    let mode = RuntimeMode::new(true);
+    let mut harness = start_in_mode(&[schedule("held", due, None)], mode.clone());
+
+    tokio::time::sleep(StdDuration::from_secs(60)).await;
+    assert!(
+        harness.inbound.try_recv().is_err(),
+        "nothing should fire in safe mode"
+    );
+    let stored = harness.store.get_by_id("held").unwrap().unwrap();
+    assert_eq!(stored.status, ScheduleStatus::Active);
+
+    mode.leave_safe_mode();
+    let (msg, _) = next_fire(&mut harness).await;
+    assert_eq!(msg.body, "prompt held");
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_export_schedule_writes_transcript_without_the_agent() {
+    let due = base() + Duration::seconds(5);
+    let mut export = schedule("export", due, None);
+    export.prompt = "export:transcript".to_string();
+    let mut harness = start(&[export]);
+
+    let channel = SessionStore::new(harness.tmp.path())
+        .unwrap()
+        .get_by_name(CHANNEL)
+        .unwrap()
+        .unwrap();
+    let gorp_dir = std::path::Path::new(&channel.directory).join(".gorp");
+    std::fs::create_dir_all(&gorp_dir).unwrap();
+    std::fs::write(
+        gorp_dir.join("matrix-messages.jsonl"),
+        [
+            r#"{"timestamp":"2026-03-02T08:00:00Z","message_type":"prompt","content":"weekly numbers?"}"#,
+            r#"{"timestamp":"2026-03-02T08:00:09Z","message_type":"response","content":"Up 4%."}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    tokio::time::sleep(StdDuration::from_secs(30)).await;
+    let transcript = std::path::Path::new(&channel.directory).join("transcript-2026-03-02.md");
+    let text = std::fs::read_to_string(&transcript).unwrap();
+    assert!(text.starts_with("# 📜 reports: last 1 exchange"));
+    assert!(text.contains("weekly numbers?"));
+    assert!(text.contains("Up 4%."));
+
+    assert!(
+        harness.inbound.try_recv().is_err(),
+        "an export should not prompt the agent"
+    );
+    let stored = harness.store.get_by_id("export").unwrap().unwrap();
+    assert_eq!(stored.status, ScheduleStatus::Completed);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_quiet_hours_defer_schedule_across_midnight() {
+    let due = Utc.with_ymd_and_hms(2026, 3, 2, 23, 30, 0).unwrap();
+    let mut harness = start_with(
+        &[schedule("nightly", due, None)],
+        RuntimeMode::default(),
+        Some("22:00-07:00"),
+    );
+
+    let (msg, fired) = next_fire(&mut harness).await;
+    assert_eq!(msg.body, "prompt nightly");
+    assert!(!msg.silent);
+    assert_fired_at(fired, Utc.with_ymd_and_hms(2026, 3, 3, 7, 0, 0).unwrap());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_schedule_just_outside_quiet_hours_fires_on_time() {
+    let due = Utc.with_ymd_and_hms(2026, 3, 2, 21, 59, 30).unwrap();
+    let mut harness = start_with(
+        &[schedule("evening", due, None)],
+        RuntimeMode::default(),
+        Some("22:00-07:00"),
+    );
+
+    let (msg, fired) = next_fire(&mut harness).await;
+    assert_eq!(msg.body, "prompt evening");
+    assert_fired_at(fired, due);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_silent_schedule_runs_during_quiet_hours() {
+    let due = Utc.with_ymd_and_hms(2026, 3, 2, 23, 30, 0).unwrap();
+    let mut harness = start_with(
+        &[schedule("silent", due, None)],
+        RuntimeMode::default(),
+        Some("22:00-07:00"),
+    );
+    harness
+        .store
+        .set_quiet_policy("silent", QuietPolicy::Silent)
+        .unwrap();
+
+    let (msg, fired) = next_fire(&mut harness).await;
+    assert!(msg.silent, "a silent schedule should not post its response");
+    assert_fired_at(fired, due);
+    let stored = harness.store.get_by_id("silent").unwrap().unwrap();
+    assert_eq!(stored.status, ScheduleStatus::Completed);
+}
+
+/// Answer the prompt the scheduler just published, as the orchestrator would
+fn respond(harness: &Harness, content: ResponseContent) {
+    harness.bus.publish_response(BusResponse {
+        session_name: CHANNEL.to_string(),
+        content,
+        timestamp: harness.clock.now(),
+    });
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runs_are_logged_with_their_outcome() {
+    let due = base() + Duration::seconds(5);
+    let mut harness = start(&[schedule("daily", due, Some("0 9 * * *"))]);
+
+    next_fire(&mut harness).await;
+    respond(&harness, ResponseContent::Chunk("All ".to_string()));
+    respond(&harness, ResponseContent::Complete("All good".to_string()));
+    tokio::time::sleep(StdDuration::from_secs(1)).await;
+
+    let runs = harness.store.list_runs("daily", 10).unwrap();
+    assert_eq!(runs.len(), 1);
+    assert!(runs[0].success);
+    assert_eq!(runs[0].preview, "All good");
+    assert_eq!(runs[0].started_at, due.to_rfc3339());
+
+    // The next morning's run fails
+    next_fire(&mut harness).await;
+    respond(
+        &harness,
+        ResponseContent::Error("backend unavailable".to_string()),
+    );
+    tokio::time::sleep(StdDuration::from_secs(1)).await;
+
+    let runs = harness.store.list_runs("daily", 10).unwrap();
+    assert_eq!(runs.len(), 2);
+    assert!(!runs[0].success, "newest run should come first");
+    assert_eq!(runs[0].preview, "backend unavailable");
+}
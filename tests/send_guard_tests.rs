@@ -0,0 +1,203 @@
+// ABOUTME: Tests for the send guard: long prompts held as drafts until !send, !append and !discard.
+// ABOUTME: Drives handle_incoming through MockPlatform and checks the draft sweeper's expiry reminders.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use gorp::config::SendGuardConfig;
+use gorp::drafts::{sweep_expired_drafts, QUEUED_NOTICE};
+use gorp::message_handler::traits::MockPlatform;
+use gorp::platform::PlatformRegistry;
+use gorp::server::ServerState;
+use gorp::traits::{MessageContent, MessagingPlatform};
+use tempfile::TempDir;
+
+mod common;
+
+use common::{pump, TestState};
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+const THRESHOLD: usize = 40;
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::telegram(tmp.path(), USER_ID)
+        .config(|config| {
+            config.send_guard = SendGuardConfig {
+                threshold_chars: THRESHOLD,
+                draft_expiry_mins: 60,
+            }
+        })
+        .build()
+}
+
+/// State with a channel attached to CHAT_ID and the send guard on for USER_ID
+fn guarded_state(tmp: &TempDir) -> ServerState {
+    let state = test_state(tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    state.session_store.set_send_guard(USER_ID, true).unwrap();
+    state
+}
+
+fn long_message(topic: &str) -> String {
+    format!("{} {}", topic, "please look into this carefully ".repeat(3))
+}
+
+#[tokio::test]
+async fn test_long_message_goes_straight_through_with_guard_off() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let body = long_message("refactor");
+    platform.inject(platform.message(CHAT_ID, USER_ID, &body));
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert!(platform.has_sent_containing("Mock: no expectation for 'refactor"));
+    assert!(state
+        .session_store
+        .get_draft(CHAT_ID, USER_ID)
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_long_message_is_held_and_short_one_is_not() {
+    let tmp = TempDir::new().unwrap();
+    let state = guarded_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let body = long_message("refactor");
+    platform.inject(platform.message(CHAT_ID, USER_ID, &body));
+    pump(&mut stream, &platform, &state, 1).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![(CHAT_ID.to_string(), QUEUED_NOTICE.to_string())]
+    );
+    let draft = state
+        .session_store
+        .get_draft(CHAT_ID, USER_ID)
+        .unwrap()
+        .expect("draft stored");
+    assert_eq!(draft.body, body);
+    assert_eq!(draft.platform_id, "telegram");
+
+    platform.clear();
+    platform.inject(platform.message(CHAT_ID, USER_ID, "ping"));
+    pump(&mut stream, &platform, &state, 1).await;
+    assert!(platform.has_sent_containing("Mock: no expectation for 'ping'"));
+}
+
+#[tokio::test]
+async fn test_append_then_send_submits_combined_text() {
+    let tmp = TempDir::new().unwrap();
+    let state = guarded_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    let body = long_message("refactor");
+    platform.inject(platform.message(CHAT_ID, USER_ID, &body));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!append and the tests too"));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!send"));
+    pump(&mut stream, &platform, &state, 3).await;
+
+    let expected = format!("{} and the tests too", body.trim_end());
+    assert!(
+        platform.has_sent_containing(&format!("Mock: no expectation for '{}'", expected)),
+        "unexpected sends: {:?}",
+        platform.sent_text()
+    );
+    assert!(state
+        .session_store
+        .get_draft(CHAT_ID, USER_ID)
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_commands_run_while_draft_pending() {
+    let tmp = TempDir::new().unwrap();
+    let state = guarded_state(&tmp);
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+
+    platform.inject(platform.message(CHAT_ID, USER_ID, &long_message("refactor")));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!status"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    let sent = platform.sent_text();
+    assert_eq!(sent.len(), 2, "unexpected sends: {:?}", sent);
+    assert_ne!(sent[1].1, QUEUED_NOTICE);
+    assert!(state
+        .session_store
+        .get_draft(CHAT_ID, USER_ID)
+        .unwrap()
+        .is_some());
+
+    platform.clear();
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!discard"));
+    platform.inject(platform.message(CHAT_ID, USER_ID, "!send"));
+    pump(&mut stream, &platform, &state, 2).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![
+            (CHAT_ID.to_string(), "🗑️ Draft discarded.".to_string()),
+            (CHAT_ID.to_string(), "No queued draft to send.".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_sweep_expires_old_drafts_with_reminder() {
+    let tmp = TempDir::new().unwrap();
+    let state = guarded_state(&tmp);
+    state
+        .session_store
+        .save_draft("telegram", CHAT_ID, USER_ID, "the forgotten prompt")
+        .unwrap();
+
+    let platform = MockPlatform::new("telegram");
+    let sent = platform.sent.clone();
+    let mut registry = PlatformRegistry::new();
+    registry.register(Box::new(platform));
+    let registry = Arc::new(tokio::sync::RwLock::new(registry));
+    let ttl = chrono::Duration::minutes(60);
+
+    // Fresh drafts are left alone
+    let expired = sweep_expired_drafts(&state.session_store, &registry, ttl, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(expired, 0);
+    assert!(sent.lock().unwrap().is_empty());
+
+    let later = Utc::now() + chrono::Duration::hours(2);
+    let expired = sweep_expired_drafts(&state.session_store, &registry, ttl, later)
+        .await
+        .unwrap();
+    assert_eq!(expired, 1);
+    assert!(state
+        .session_store
+        .get_draft(CHAT_ID, USER_ID)
+        .unwrap()
+        .is_none());
+
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].0, CHAT_ID);
+    match &sent[0].1 {
+        MessageContent::Plain(text) => assert!(text.contains("the forgotten prompt")),
+        other => panic!("expected a plain reminder, got {:?}", other),
+    }
+}
@@ -102,3 +102,40 @@ fn test_channel_validate_directory_accepts_valid() {
 
     assert!(channel.validate_directory().is_ok());
 }
+
+#[test]
+fn test_concurrent_stores_do_not_hit_lock_errors() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let setup = gorp::session::SessionStore::new(tmp.path()).unwrap();
+    let rooms: Vec<String> = (0..4).map(|i| format!("!room{}:example.com", i)).collect();
+    for (i, room_id) in rooms.iter().enumerate() {
+        setup
+            .create_channel(&format!("chan-{}", i), room_id)
+            .unwrap();
+    }
+
+    // Each thread opens its own store, so its own connection, like the
+    // scheduler and CLI commands do next to the running bot
+    let workers: Vec<_> = (0..8)
+        .map(|worker| {
+            let path = tmp.path().to_path_buf();
+            let rooms = rooms.clone();
+            std::thread::spawn(move || {
+                let store = gorp::session::SessionStore::new(&path).unwrap();
+                for i in 0..50 {
+                    let room_id = &rooms[(worker + i) % rooms.len()];
+                    store.get_by_room(room_id)?;
+                    store.mark_started(room_id)?;
+                }
+                anyhow::Ok(())
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        worker.join().unwrap().unwrap();
+    }
+    for room_id in &rooms {
+        assert!(setup.get_by_room(room_id).unwrap().unwrap().started);
+    }
+}
@@ -0,0 +1,35 @@
+// ABOUTME: Tests for draining in-flight message handlers at shutdown.
+// ABOUTME: Uses paused tokio time so the drain deadline is hit without real waiting.
+
+use std::time::Duration;
+
+use gorp::shutdown::drain;
+use tokio_util::task::TaskTracker;
+
+#[tokio::test(start_paused = true)]
+async fn test_drain_waits_for_running_tasks() {
+    let tracker = TaskTracker::new();
+    for secs in [1, 5] {
+        tracker.spawn(tokio::time::sleep(Duration::from_secs(secs)));
+    }
+
+    assert!(drain(&tracker, Duration::from_secs(30)).await);
+    assert!(tracker.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_drain_gives_up_after_timeout() {
+    let tracker = TaskTracker::new();
+    tracker.spawn(tokio::time::sleep(Duration::from_secs(120)));
+
+    assert!(!drain(&tracker, Duration::from_secs(30)).await);
+    assert_eq!(tracker.len(), 1);
+}
+
+#[tokio::test]
+async fn test_drain_closes_tracker() {
+    let tracker = TaskTracker::new();
+
+    assert!(drain(&tracker, Duration::from_secs(1)).await);
+    assert!(tracker.is_closed());
+}
@@ -0,0 +1,167 @@
+// ABOUTME: Tests that handle_incoming marks chat messages with status reactions when a channel opts in.
+// ABOUTME: A recording platform stands in for Matrix and tracks which reactions are on each message.
+
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use gorp::message_handler::handle_incoming;
+use gorp::server::ServerState;
+use gorp::traits::{
+    ChatUser, EventStream, IncomingMessage, MessageAnnotator, MessageContent, MessagingPlatform,
+};
+use tempfile::TempDir;
+
+mod common;
+
+use common::TestState;
+
+const ROOM_ID: &str = "!room:matrix.example.com";
+const USER_ID: &str = "@user:matrix.example.com";
+
+/// Platform that can react to messages and remembers which reactions are still up
+#[derive(Default)]
+struct ReactingPlatform {
+    /// (reaction id, message id, emoji)
+    reactions: Mutex<Vec<(String, String, String)>>,
+    /// Every emoji ever added, in order
+    history: Mutex<Vec<String>>,
+}
+
+impl ReactingPlatform {
+    fn reactions_on(&self, message_id: &str) -> Vec<String> {
+        self.reactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, target, _)| target == message_id)
+            .map(|(_, _, emoji)| emoji.clone())
+            .collect()
+    }
+
+    fn history(&self) -> Vec<String> {
+        self.history.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl MessagingPlatform for ReactingPlatform {
+    async fn event_stream(&self) -> Result<EventStream> {
+        anyhow::bail!("not used in tests")
+    }
+    async fn send(&self, _channel_id: &str, _content: MessageContent) -> Result<()> {
+        Ok(())
+    }
+    fn bot_user_id(&self) -> &str {
+        "@bot:matrix.example.com"
+    }
+    fn platform_id(&self) -> &'static str {
+        "matrix"
+    }
+    fn annotator(&self) -> Option<&dyn MessageAnnotator> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl MessageAnnotator for ReactingPlatform {
+    async fn add_reaction(
+        &self,
+        _channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> Result<String> {
+        let mut reactions = self.reactions.lock().unwrap();
+        let id = format!("$reaction{}", self.history.lock().unwrap().len());
+        reactions.push((id.clone(), message_id.to_string(), emoji.to_string()));
+        self.history.lock().unwrap().push(emoji.to_string());
+        Ok(id)
+    }
+
+    async fn remove_reaction(&self, _channel_id: &str, reaction_id: &str) -> Result<()> {
+        self.reactions
+            .lock()
+            .unwrap()
+            .retain(|(id, _, _)| id != reaction_id);
+        Ok(())
+    }
+}
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    TestState::matrix(tmp.path(), USER_ID).build()
+}
+
+fn incoming(event_id: &str, body: &str) -> IncomingMessage {
+    IncomingMessage {
+        platform_id: "matrix".to_string(),
+        channel_id: ROOM_ID.to_string(),
+        thread_id: None,
+        sender: ChatUser::new(USER_ID),
+        body: body.to_string(),
+        is_direct: false,
+        formatted: false,
+        attachments: Vec::new(),
+        event_id: event_id.to_string(),
+        edits_event_id: None,
+        timestamp: 0,
+    }
+}
+
+fn enable_reactions(state: &ServerState) {
+    let channel = state
+        .session_store
+        .create_channel("reactions", ROOM_ID)
+        .unwrap();
+    let gorp_dir = std::path::Path::new(&channel.directory).join(".gorp");
+    std::fs::create_dir_all(&gorp_dir).unwrap();
+    std::fs::write(gorp_dir.join("status-reactions"), "").unwrap();
+}
+
+#[tokio::test]
+async fn test_answered_message_ends_with_check() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    enable_reactions(&state);
+    let platform = ReactingPlatform::default();
+
+    handle_incoming(&incoming("$event1", "hello"), &platform, &state)
+        .await
+        .unwrap();
+
+    assert_eq!(platform.history(), vec!["⏳", "✅"]);
+    assert_eq!(platform.reactions_on("$event1"), vec!["✅"]);
+}
+
+#[tokio::test]
+async fn test_failed_message_ends_with_cross() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    enable_reactions(&state);
+    state
+        .session_store
+        .update_backend_type("reactions", Some("nonesuch"))
+        .unwrap();
+    let platform = ReactingPlatform::default();
+
+    let result = handle_incoming(&incoming("$event1", "hello"), &platform, &state).await;
+
+    assert!(result.is_err());
+    assert_eq!(platform.reactions_on("$event1"), vec!["❌"]);
+}
+
+#[tokio::test]
+async fn test_no_reactions_unless_channel_opts_in() {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    state
+        .session_store
+        .create_channel("reactions", ROOM_ID)
+        .unwrap();
+    let platform = ReactingPlatform::default();
+
+    handle_incoming(&incoming("$event1", "hello"), &platform, &state)
+        .await
+        .unwrap();
+
+    assert!(platform.history().is_empty());
+}
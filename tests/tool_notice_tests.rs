@@ -0,0 +1,101 @@
+// ABOUTME: Tests that debug mode relays tool use through the generic handle_incoming path.
+// ABOUTME: A scripted mock backend starts a tool; MockPlatform records what the user would see.
+
+use std::sync::Arc;
+
+use gorp::message_handler::traits::MockPlatform;
+use gorp::server::ServerState;
+use gorp::traits::MessagingPlatform;
+use gorp::warm_session::WarmSessionManager;
+use gorp::{AgentEvent, AgentRegistry};
+use gorp_agent::backends::mock::MockBackend;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+mod common;
+
+use common::{pump, TestState};
+
+const CHAT_ID: &str = "-100123";
+const USER_ID: &str = "42";
+
+fn test_state(tmp: &TempDir) -> ServerState {
+    let registry = AgentRegistry::new().register("scripted", |_config| {
+        Ok(MockBackend::new()
+            .on_prompt("what is in here?")
+            .respond_with(vec![
+                AgentEvent::ToolStart {
+                    id: "t1".to_string(),
+                    name: "Bash".to_string(),
+                    input: json!({"command": "ls"}),
+                },
+                AgentEvent::ToolEnd {
+                    id: "t1".to_string(),
+                    name: "Bash".to_string(),
+                    output: json!({"stdout": "notes.md"}),
+                    success: true,
+                    duration_ms: 5,
+                },
+                AgentEvent::Text("Just notes.md".to_string()),
+                AgentEvent::Result {
+                    text: String::new(),
+                    usage: None,
+                    metadata: json!({}),
+                },
+            ])
+            .into_handle())
+    });
+    let warm_manager = Arc::new(RwLock::new(WarmSessionManager::with_registry(
+        common::warm_config("scripted"),
+        registry,
+    )));
+
+    TestState::telegram(tmp.path(), USER_ID)
+        .warm_manager(warm_manager)
+        .build()
+}
+
+/// Ask the scripted question in a channel attached to CHAT_ID, optionally in debug mode
+async fn ask(debug: bool) -> MockPlatform {
+    let tmp = TempDir::new().unwrap();
+    let state = test_state(&tmp);
+    let channel = state
+        .session_store
+        .create_channel("research", CHAT_ID)
+        .unwrap();
+    if debug {
+        let gorp_dir = std::path::Path::new(&channel.directory).join(".gorp");
+        std::fs::create_dir_all(&gorp_dir).unwrap();
+        std::fs::write(gorp_dir.join("enable-debug"), "").unwrap();
+    }
+
+    let platform = MockPlatform::new("telegram");
+    let mut stream = platform.event_stream().await.unwrap();
+    platform.inject(platform.message(CHAT_ID, USER_ID, "what is in here?"));
+    pump(&mut stream, &platform, &state, 1).await;
+    platform
+}
+
+#[tokio::test]
+async fn test_debug_mode_sends_tool_notice_before_response() {
+    let platform = ask(true).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![
+            (CHAT_ID.to_string(), "🔧 Bash · ls".to_string()),
+            (CHAT_ID.to_string(), "Just notes.md".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_tool_use_is_hidden_without_debug_mode() {
+    let platform = ask(false).await;
+
+    assert_eq!(
+        platform.sent_text(),
+        vec![(CHAT_ID.to_string(), "Just notes.md".to_string())]
+    );
+}
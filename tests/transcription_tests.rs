@@ -0,0 +1,177 @@
+// ABOUTME: Tests for the HTTP transcriber against a local stand-in speech-to-text server.
+// ABOUTME: Checks the request shape for OpenAI-compatible and whisper.cpp providers and error handling.
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode, Uri},
+    routing::post,
+    Json, Router,
+};
+use gorp::config::{TranscriptionConfig, TranscriptionProvider};
+use gorp::transcription::{is_audio, HttpTranscriber, Transcriber};
+use serde_json::json;
+
+/// What the stand-in server saw of the last request
+#[derive(Default, Clone)]
+struct Seen {
+    path: String,
+    authorization: Option<String>,
+    body: String,
+}
+
+type Captured = Arc<Mutex<Seen>>;
+
+async fn transcribe(
+    State(captured): State<Captured>,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<serde_json::Value>) {
+    *captured.lock().unwrap() = Seen {
+        path: uri.path().to_string(),
+        authorization: headers
+            .get("authorization")
+            .map(|v| v.to_str().unwrap().to_string()),
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+    (
+        StatusCode::OK,
+        Json(json!({"text": " ship it on friday \n"})),
+    )
+}
+
+async fn overloaded() -> (StatusCode, &'static str) {
+    (StatusCode::SERVICE_UNAVAILABLE, "model is loading")
+}
+
+/// Serve `router` on a free local port and return its base URL
+async fn serve(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn config(
+    provider: TranscriptionProvider,
+    url: String,
+    api_key: Option<&str>,
+) -> TranscriptionConfig {
+    TranscriptionConfig {
+        provider,
+        url,
+        api_key: api_key.map(String::from),
+        model: "whisper-1".to_string(),
+        timeout_secs: 10,
+    }
+}
+
+fn has_field(body: &str, name: &str, value: &str) -> bool {
+    let header = format!("name=\"{}\"", name);
+    body.split(&header)
+        .nth(1)
+        .is_some_and(|rest| rest.trim_start().starts_with(value))
+}
+
+#[tokio::test]
+async fn test_openai_provider_posts_audio_with_model_and_language() {
+    let captured = Captured::default();
+    let url = serve(
+        Router::new()
+            .route("/v1/audio/transcriptions", post(transcribe))
+            .with_state(captured.clone()),
+    )
+    .await;
+    let transcriber = HttpTranscriber::new(&config(
+        TranscriptionProvider::Openai,
+        format!("{}/v1/", url),
+        Some("sk-test"),
+    ))
+    .unwrap();
+
+    let text = transcriber
+        .transcribe(b"OggS-audio".to_vec(), "voice.ogg", "audio/ogg", Some("es"))
+        .await
+        .unwrap();
+
+    assert_eq!(text, "ship it on friday");
+    let seen = captured.lock().unwrap().clone();
+    assert_eq!(seen.path, "/v1/audio/transcriptions");
+    assert_eq!(seen.authorization.as_deref(), Some("Bearer sk-test"));
+    assert!(seen.body.contains("filename=\"voice.ogg\""));
+    assert!(seen.body.contains("OggS-audio"));
+    assert!(has_field(&seen.body, "model", "whisper-1"));
+    assert!(has_field(&seen.body, "language", "es"));
+}
+
+#[tokio::test]
+async fn test_whisper_cpp_provider_asks_for_language_detection() {
+    let captured = Captured::default();
+    let url = serve(
+        Router::new()
+            .route("/inference", post(transcribe))
+            .with_state(captured.clone()),
+    )
+    .await;
+    let transcriber =
+        HttpTranscriber::new(&config(TranscriptionProvider::WhisperCpp, url, None)).unwrap();
+
+    let text = transcriber
+        .transcribe(b"audio".to_vec(), "voice.ogg", "audio/ogg", None)
+        .await
+        .unwrap();
+
+    assert_eq!(text, "ship it on friday");
+    let seen = captured.lock().unwrap().clone();
+    assert_eq!(seen.path, "/inference");
+    assert!(seen.authorization.is_none());
+    assert!(has_field(&seen.body, "language", "auto"));
+    assert!(!seen.body.contains("name=\"model\""));
+}
+
+#[tokio::test]
+async fn test_service_errors_are_reported() {
+    let url = serve(Router::new().route("/inference", post(overloaded))).await;
+    let transcriber =
+        HttpTranscriber::new(&config(TranscriptionProvider::WhisperCpp, url, None)).unwrap();
+
+    let err = transcriber
+        .transcribe(b"audio".to_vec(), "voice.ogg", "audio/ogg", None)
+        .await
+        .unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("503"), "{}", message);
+    assert!(message.contains("model is loading"), "{}", message);
+}
+
+#[test]
+fn test_is_audio() {
+    assert!(is_audio("audio/ogg"));
+    assert!(is_audio("Audio/MPEG"));
+    assert!(!is_audio("video/mp4"));
+    assert!(!is_audio("image/png"));
+    assert!(!is_audio("aud"));
+}
+
+#[test]
+fn test_transcription_config_defaults() {
+    let config: TranscriptionConfig = toml::from_str(r#"url = "http://localhost:8080""#).unwrap();
+    assert_eq!(config.provider, TranscriptionProvider::Openai);
+    assert_eq!(config.model, "whisper-1");
+    assert!(config.api_key.is_none());
+
+    let config: TranscriptionConfig = toml::from_str(
+        r#"
+provider = "whisper_cpp"
+url = "http://localhost:8080"
+api_key = "secret"
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.provider, TranscriptionProvider::WhisperCpp);
+    assert!(!format!("{:?}", config).contains("secret"));
+}
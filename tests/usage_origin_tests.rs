@@ -0,0 +1,90 @@
+// ABOUTME: Tests that agent invocations are attributed to the usage bucket for their origin.
+// ABOUTME: Runs handle_text against the mock backend and checks bus source to origin mapping.
+
+use std::time::Duration;
+
+use gorp::bus::MessageSource;
+use gorp::message_handler::handle_text;
+use gorp::orchestrator::invocation_origin;
+use gorp::session::SessionStore;
+use gorp::usage::{InvocationOrigin, UsageBucket};
+use gorp::warm_session::{create_shared_manager, SharedWarmSessionManager, WarmConfig};
+use tempfile::TempDir;
+
+fn mock_manager() -> SharedWarmSessionManager {
+    create_shared_manager(WarmConfig {
+        keep_alive_duration: Duration::from_secs(60),
+        pre_warm_lead_time: Duration::from_secs(30),
+        agent_binary: "claude".to_string(),
+        backend_type: "mock".to_string(),
+        model: None,
+        max_tokens: None,
+        global_system_prompt_path: None,
+        mcp_servers: vec![],
+        max_response_chars: 0,
+        save_truncated_responses: false,
+    })
+}
+
+#[tokio::test]
+async fn test_handle_text_buckets_each_origin() {
+    let cases = [
+        (InvocationOrigin::User, UsageBucket::Conversation),
+        (InvocationOrigin::Webhook, UsageBucket::Conversation),
+        (InvocationOrigin::Schedule, UsageBucket::Conversation),
+        (InvocationOrigin::Internal, UsageBucket::Overhead),
+    ];
+
+    for (origin, expected) in cases {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+        let channel = store.create_channel("usage", "!usage:example.com").unwrap();
+        let warm_manager = mock_manager();
+
+        handle_text("hello", &channel, &store, &warm_manager, origin)
+            .await
+            .unwrap();
+
+        let usage = store.get_channel_usage("usage").unwrap();
+        let other = match expected {
+            UsageBucket::Conversation => UsageBucket::Overhead,
+            UsageBucket::Overhead => UsageBucket::Conversation,
+        };
+        assert_eq!(
+            usage.bucket(expected).invocations,
+            1,
+            "{} should count toward {}",
+            origin,
+            expected.as_str()
+        );
+        assert_eq!(
+            usage.bucket(other).invocations,
+            0,
+            "{} should not count toward {}",
+            origin,
+            other.as_str()
+        );
+    }
+}
+
+#[test]
+fn test_bus_sources_map_to_origins() {
+    let platform = MessageSource::Platform {
+        platform_id: "slack".to_string(),
+        channel_id: "C1".to_string(),
+    };
+    let web = MessageSource::Web {
+        connection_id: "conn".to_string(),
+    };
+    let scheduler = MessageSource::Api {
+        token_hint: "scheduler".to_string(),
+    };
+    let webhook = MessageSource::Api {
+        token_hint: "webhook".to_string(),
+    };
+
+    assert_eq!(invocation_origin(&platform), InvocationOrigin::User);
+    assert_eq!(invocation_origin(&web), InvocationOrigin::User);
+    assert_eq!(invocation_origin(&scheduler), InvocationOrigin::Schedule);
+    assert_eq!(invocation_origin(&webhook), InvocationOrigin::Webhook);
+}
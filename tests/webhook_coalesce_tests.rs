@@ -0,0 +1,188 @@
+// ABOUTME: Tests for webhook burst coalescing: grouping, window expiry and flushing after a restart.
+// ABOUTME: Drives the batch store, flusher and webhook handler against a real MessageBus.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use chrono::{Duration, Utc};
+use gorp::bus::{BusMessage, BusResponse, MessageBus, ResponseContent, SessionTarget};
+use gorp::config::Config;
+use gorp::runtime_mode::RuntimeMode;
+use gorp::session::SessionStore;
+use gorp::webhook::{flush_due_batches, recover_batches, webhook_router};
+use gorp::webhook_batch::{group_key, sample_of};
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::sync::broadcast::Receiver;
+use tower::ServiceExt;
+
+const WINDOW_SECS: i64 = 60;
+
+/// Store an alert delivery the way the webhook handler does, grouped by alertname
+fn deliver(store: &SessionStore, alertname: &str, host: &str, at: chrono::DateTime<Utc>) {
+    let payload = json!({"labels": {"alertname": alertname, "host": host}});
+    let prompt = "Investigate this alert";
+    let key = group_key(prompt, Some(&payload), Some("$.labels.alertname"));
+    store
+        .add_webhook_delivery(
+            "alerts",
+            &key,
+            prompt,
+            Some(&sample_of(&payload)),
+            Duration::seconds(WINDOW_SECS),
+            at,
+        )
+        .unwrap();
+}
+
+fn drain(rx: &mut Receiver<BusMessage>) -> Vec<BusMessage> {
+    let mut messages = Vec::new();
+    while let Ok(msg) = rx.try_recv() {
+        messages.push(msg);
+    }
+    messages
+}
+
+#[test]
+fn test_burst_is_grouped_and_flushed_once_window_closes() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let bus = MessageBus::new(64);
+    let mut rx = bus.subscribe_inbound();
+    let start = Utc::now();
+
+    for i in 0..12 {
+        deliver(
+            &store,
+            "DiskFull",
+            &format!("db{}", i),
+            start + Duration::seconds(i),
+        );
+    }
+    deliver(&store, "CpuHigh", "web1", start + Duration::seconds(5));
+
+    // Nothing goes out while the windows are open
+    let before_close = start + Duration::seconds(WINDOW_SECS - 1);
+    assert_eq!(flush_due_batches(&store, &bus, before_close).unwrap(), 0);
+    assert!(drain(&mut rx).is_empty());
+
+    let after_close = start + Duration::seconds(WINDOW_SECS + 5);
+    assert_eq!(flush_due_batches(&store, &bus, after_close).unwrap(), 2);
+    let sent = drain(&mut rx);
+    assert_eq!(sent.len(), 2);
+
+    let disk = sent
+        .iter()
+        .find(|m| m.body.contains("DiskFull"))
+        .expect("DiskFull batch");
+    assert!(disk.body.starts_with("12 occurrences of DiskFull between "));
+    assert!(disk.body.contains("Investigate this alert"));
+    assert!(disk.body.contains("\"host\":\"db0\""));
+    assert!(
+        !disk.body.contains("\"host\":\"db11\""),
+        "only the first few payloads are sampled"
+    );
+    assert_eq!(
+        disk.session_target,
+        SessionTarget::Session {
+            name: "alerts".to_string()
+        }
+    );
+
+    // A lone delivery goes through as its own prompt
+    let cpu = sent.iter().find(|m| !m.body.contains("DiskFull")).unwrap();
+    assert_eq!(cpu.body, "Investigate this alert");
+
+    // Flushed batches are gone; the next delivery opens a new one
+    assert_eq!(flush_due_batches(&store, &bus, after_close).unwrap(), 0);
+}
+
+#[test]
+fn test_unflushed_batches_are_recovered_after_restart() {
+    let tmp = TempDir::new().unwrap();
+    let start = Utc::now();
+    {
+        let store = SessionStore::new(tmp.path()).unwrap();
+        deliver(&store, "DiskFull", "db1", start);
+        deliver(&store, "DiskFull", "db2", start + Duration::seconds(1));
+        // Process stops here with the window still open
+    }
+
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let bus = MessageBus::new(64);
+
+    // Nobody listening yet: batches stay put rather than being dropped
+    assert_eq!(recover_batches(&store, &bus).unwrap(), 0);
+
+    let mut rx = bus.subscribe_inbound();
+    assert_eq!(recover_batches(&store, &bus).unwrap(), 1);
+    let sent = drain(&mut rx);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].body.starts_with("2 occurrences of DiskFull"));
+
+    assert_eq!(recover_batches(&store, &bus).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_urgent_delivery_skips_the_batch() {
+    let tmp = TempDir::new().unwrap();
+    let store = SessionStore::new(tmp.path()).unwrap();
+    let channel = store
+        .create_channel("alerts", "!alerts:example.org")
+        .unwrap();
+    let toml = format!(
+        "[webhook]\n[webhook.coalesce.alerts]\ncoalesce_secs = {}\n[workspace]\npath = {:?}\n",
+        WINDOW_SECS,
+        tmp.path().to_str().unwrap()
+    );
+    let config: Config = toml::from_str(&toml).unwrap();
+    let bus = Arc::new(MessageBus::new(16));
+    let router = webhook_router(
+        store.clone(),
+        Arc::clone(&bus),
+        Arc::new(config),
+        RuntimeMode::new(false),
+    );
+
+    // Stand in for the orchestrator, answering with what it was asked for
+    let mut inbound = bus.subscribe_inbound();
+    let responder = Arc::clone(&bus);
+    tokio::spawn(async move {
+        while let Ok(msg) = inbound.recv().await {
+            let SessionTarget::Session { name } = msg.session_target else {
+                continue;
+            };
+            responder.publish_response(BusResponse {
+                session_name: name,
+                content: ResponseContent::Complete(format!("urgent={}", msg.urgent)),
+                timestamp: Utc::now(),
+            });
+        }
+    });
+    let post = |body: &'static str| {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/webhook/session/{}", channel.session_id))
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let batched = router
+        .clone()
+        .oneshot(post(r#"{"prompt": "Disk full"}"#))
+        .await
+        .unwrap();
+    assert_eq!(batched.status(), StatusCode::ACCEPTED);
+
+    let urgent = router
+        .oneshot(post(r#"{"prompt": "Disk full", "urgent": true}"#))
+        .await
+        .unwrap();
+    assert_eq!(urgent.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(urgent.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("urgent=true"));
+}
@@ -0,0 +1,98 @@
+// ABOUTME: Tests for HMAC-signed webhook deliveries: with a secret set, only correctly signed
+// ABOUTME: requests reach the handler; without one, unsigned requests work as before.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use gorp::bus::MessageBus;
+use gorp::config::Config;
+use gorp::runtime_mode::RuntimeMode;
+use gorp::session::SessionStore;
+use gorp::webhook::{sign, webhook_router, SIGNATURE_HEADER};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+const SECRET: &str = "hunter2";
+const BODY: &str = r#"{"prompt": "nightly report"}"#;
+
+fn router(tmp: &TempDir, secret: Option<&str>) -> Router {
+    let secret_line = secret
+        .map(|secret| format!("secret = {:?}\n", secret))
+        .unwrap_or_default();
+    let toml = format!(
+        "[webhook]\n{}[workspace]\npath = {:?}\n",
+        secret_line,
+        tmp.path().to_str().unwrap()
+    );
+    let config: Config = toml::from_str(&toml).unwrap();
+    webhook_router(
+        SessionStore::new(tmp.path()).unwrap(),
+        Arc::new(MessageBus::new(16)),
+        Arc::new(config),
+        RuntimeMode::new(false),
+    )
+}
+
+/// A delivery to a session that doesn't exist: getting past the signature check
+/// shows up as 404 from the handler, which also proves the body arrived intact.
+fn delivery(signature: Option<&str>) -> Request<Body> {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/webhook/session/no-such-session")
+        .header("content-type", "application/json");
+    if let Some(signature) = signature {
+        request = request.header(SIGNATURE_HEADER, signature);
+    }
+    request.body(Body::from(BODY)).unwrap()
+}
+
+#[tokio::test]
+async fn test_signed_delivery_is_verified() {
+    let tmp = TempDir::new().unwrap();
+    let router = router(&tmp, Some(SECRET));
+
+    let valid = sign(SECRET, BODY.as_bytes());
+    let response = router
+        .clone()
+        .oneshot(delivery(Some(&valid)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let bare_hex = valid.trim_start_matches("sha256=");
+    let response = router
+        .clone()
+        .oneshot(delivery(Some(bare_hex)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let forged = sign("wrong-secret", BODY.as_bytes());
+    for signature in [Some(forged.as_str()), Some("sha256=zz"), None] {
+        let response = router.clone().oneshot(delivery(signature)).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::UNAUTHORIZED,
+            "{:?}",
+            signature
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_unsigned_delivery_passes_without_a_secret() {
+    let tmp = TempDir::new().unwrap();
+    let response = router(&tmp, None).oneshot(delivery(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_signature_format() {
+    // echo -n 'abc' | openssl dgst -sha256 -hmac key
+    assert_eq!(
+        sign("key", b"abc"),
+        "sha256=9c196e32dc0175f86f4b1cb89289d6619de6bee699e4c378e68309ed97a1a6ab"
+    );
+}